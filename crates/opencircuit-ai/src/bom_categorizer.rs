@@ -0,0 +1,171 @@
+//! AI categorization of imported BOM components
+//!
+//! Components imported without a category are sent to the LLM in batches of
+//! [`CATEGORIZATION_BATCH_SIZE`] with the library's categories listed. The
+//! model answers with a JSON array of `{"id", "category"}` assignments;
+//! assignments for unknown IDs or categories are dropped, so a component the
+//! model could not place keeps its current category.
+
+use opencircuit_core::models::ComponentCategory;
+use opencircuit_core::OpenCircuitError;
+use opencircuit_database::ComponentRecord;
+use serde_json::Value;
+
+use crate::health_report::extract_json;
+use crate::AiResult;
+
+/// Components sent to the model in one request
+pub const CATEGORIZATION_BATCH_SIZE: usize = 50;
+
+/// Categories a BOM component can be assigned, as stored in the database
+pub const BOM_CATEGORIES: [ComponentCategory; 12] = [
+    ComponentCategory::Resistors,
+    ComponentCategory::Capacitors,
+    ComponentCategory::Inductors,
+    ComponentCategory::Diodes,
+    ComponentCategory::Transistors,
+    ComponentCategory::IntegratedCircuits,
+    ComponentCategory::Connectors,
+    ComponentCategory::Switches,
+    ComponentCategory::Crystals,
+    ComponentCategory::Sensors,
+    ComponentCategory::Power,
+    ComponentCategory::Mechanical,
+];
+
+/// Build the categorization prompt for one batch
+pub fn categorization_prompt(components: &[ComponentRecord]) -> String {
+    let categories: Vec<&str> = BOM_CATEGORIES.iter().map(ComponentCategory::as_str).collect();
+    let mut prompt = format!(
+        "Assign each electronic component below to one of these categories: {}.\n\nComponents:\n",
+        categories.join(", ")
+    );
+    for component in components {
+        prompt.push_str(&format!("- id {}: {} by {}", component.id, component.part_number, component.manufacturer));
+        if let Some(description) = component.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            prompt.push_str(&format!(", {}", description.trim()));
+        }
+        if let Some(footprint) = component.footprint.as_deref().filter(|f| !f.trim().is_empty()) {
+            prompt.push_str(&format!(", footprint {}", footprint.trim()));
+        }
+        prompt.push('\n');
+    }
+
+    prompt.push_str(
+        "\nUse the part number, description and footprint, e.g. a bare \"0603 10k\" is a resistor and \
+         \"LM358\" is an op-amp, which belongs in Integrated Circuits. Use the category names exactly as listed. \
+         Respond with a JSON array only, one entry per component:\n\
+         [{\"id\": \"...\", \"category\": \"...\"}]",
+    );
+    prompt
+}
+
+/// Parse the model's assignments, keeping those for components in the batch
+pub fn parse_categories(response: &str, components: &[ComponentRecord]) -> AiResult<Vec<(String, ComponentCategory)>> {
+    let array = response
+        .find('[')
+        .zip(response.rfind(']'))
+        .filter(|(start, end)| start < end)
+        .map(|(start, end)| &response[start..=end])
+        .ok_or_else(|| OpenCircuitError::AiService("Categories contained no JSON array".to_string()))?;
+    let json = extract_json(&format!("{{\"assignments\": {}}}", array))
+        .ok_or_else(|| OpenCircuitError::AiService("Categories contained no JSON array".to_string()))?;
+    let value: Value = serde_json::from_str(&json)
+        .map_err(|e| OpenCircuitError::AiService(format!("Invalid category JSON: {}", e)))?;
+
+    let mut assignments: Vec<(String, ComponentCategory)> = Vec::new();
+    for entry in value["assignments"].as_array().into_iter().flatten() {
+        let id = match entry.get("id") {
+            Some(Value::String(id)) => id.trim().to_string(),
+            Some(Value::Number(id)) => id.to_string(),
+            _ => continue,
+        };
+        let Some(component) = components.iter().find(|component| component.id == id) else {
+            continue;
+        };
+        let Some(category) = entry.get("category").and_then(Value::as_str).and_then(category_named) else {
+            continue;
+        };
+        if !assignments.iter().any(|(assigned, _)| *assigned == component.id) {
+            assignments.push((component.id.clone(), category));
+        }
+    }
+
+    if assignments.is_empty() && !components.is_empty() {
+        return Err(OpenCircuitError::AiService("Response assigned no known categories".to_string()));
+    }
+    Ok(assignments)
+}
+
+/// The BOM category with this name, ignoring case, spacing and plurals
+fn category_named(name: &str) -> Option<ComponentCategory> {
+    let normalize = |name: &str| -> String {
+        name.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_lowercase()).collect()
+    };
+    let name = normalize(name);
+    BOM_CATEGORIES
+        .iter()
+        .find(|category| {
+            let key = normalize(category.as_str());
+            key == name || key.strip_suffix("es") == Some(name.as_str()) || key.strip_suffix('s') == Some(name.as_str())
+        })
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, part_number: &str, description: Option<&str>) -> ComponentRecord {
+        ComponentRecord {
+            id: id.to_string(),
+            part_number: part_number.to_string(),
+            manufacturer: "Unknown".to_string(),
+            category: String::new(),
+            description: description.map(str::to_string),
+            datasheet_url: None,
+            specifications: None,
+            footprint: None,
+            symbol: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_categories() {
+        let components = [
+            record("c1", "LM358", Some("Dual op-amp")),
+            record("c2", "0603 10k", None),
+            record("c3", "SW-PB-6MM", None),
+        ];
+        let prompt = categorization_prompt(&components);
+        assert!(prompt.contains("Integrated Circuits, Connectors"));
+        assert!(prompt.contains("- id c1: LM358 by Unknown, Dual op-amp\n"));
+
+        let response = r#"Here are the categories:
+            [
+                {"id": "c1", "category": "IntegratedCircuits"},
+                {"id": "c2", "category": "resistor"},
+                {"id": "c3", "category": "Switch"},
+                {"id": "c9", "category": "Resistors"},
+            ]"#;
+        let assignments = parse_categories(response, &components).unwrap();
+        assert_eq!(
+            assignments,
+            [
+                ("c1".to_string(), ComponentCategory::IntegratedCircuits),
+                ("c2".to_string(), ComponentCategory::Resistors),
+                ("c3".to_string(), ComponentCategory::Switches),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_categories_rejects_unknown_categories() {
+        let components = [record("c1", "LM358", None)];
+        assert!(parse_categories(r#"[{"id": "c1", "category": "Op-amps"}]"#, &components).is_err());
+        assert!(parse_categories("LM358 is an integrated circuit.", &components).is_err());
+        assert!(parse_categories("[]", &[]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+//! One-sentence BOM comments explaining each component's role
+//!
+//! The whole circuit is sent in one prompt so the LLM can relate parts to
+//! each other ("current-limiting resistor for LED D2"). Comments are cached by
+//! [`circuit_fingerprint`], which ignores positions and existing comments, so
+//! moving parts around or applying the comments does not invalidate them.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::health_report::extract_json;
+use crate::AiResult;
+
+/// Hash of the components and connections that determine the comments
+pub fn circuit_fingerprint(circuit: &Circuit) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for component in &circuit.components {
+        (&component.id, &component.component_type, &component.value).hash(&mut hasher);
+    }
+    for connection in &circuit.connections {
+        (&connection.from, &connection.to, &connection.net_name).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Build the BOM comment prompt
+pub fn bom_prompt(circuit: &Circuit) -> String {
+    let mut prompt = String::from("Explain the role of every component in this circuit for a bill of materials.\n\nComponents:\n");
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or("(no value)")
+        ));
+    }
+    prompt.push_str("\nNetlist:\n");
+    for connection in &circuit.connections {
+        prompt.push_str(&format!("- {} -> {} on net {}\n", connection.from, connection.to, connection.net_name));
+    }
+
+    prompt.push_str(
+        "\nGive one sentence per component describing its function and, where useful, the parts it works with, \
+         e.g. \"current-limiting resistor for LED D2, limits current to ~20 mA at 3.3V\". \
+         Respond with JSON only, mapping each component ID to its sentence:\n\
+         {\"R1\": \"...\"}",
+    );
+    prompt
+}
+
+/// Parse the model's comments, keeping only IDs present in `circuit`
+///
+/// Falls back to `ID: sentence` lines when the response has no JSON object.
+pub fn parse_comments(response: &str, circuit: &Circuit) -> AiResult<HashMap<String, String>> {
+    let entries: Vec<(String, String)> = match extract_json(response)
+        .and_then(|json| serde_json::from_str::<Value>(&json).ok())
+        .and_then(|value| value.as_object().cloned())
+    {
+        Some(object) => object
+            .into_iter()
+            .filter_map(|(id, comment)| Some((id, comment.as_str()?.to_string())))
+            .collect(),
+        None => response
+            .lines()
+            .filter_map(|line| {
+                let (id, comment) = line.trim().trim_start_matches(['-', '*']).split_once(':')?;
+                Some((id.trim().to_string(), comment.to_string()))
+            })
+            .collect(),
+    };
+
+    let comments: HashMap<String, String> = entries
+        .into_iter()
+        .filter_map(|(id, comment)| {
+            let component = circuit.components.iter().find(|component| component.id.eq_ignore_ascii_case(id.trim()))?;
+            let comment = comment.trim();
+            (!comment.is_empty()).then(|| (component.id.clone(), comment.to_string()))
+        })
+        .collect();
+
+    if comments.is_empty() {
+        return Err(OpenCircuitError::AiService("Response contained no component comments".to_string()));
+    }
+    Ok(comments)
+}
+
+/// Store comments in the matching components' `purpose`
+pub fn apply_comments(circuit: &mut Circuit, comments: &HashMap<String, String>) {
+    for component in &mut circuit.components {
+        if let Some(comment) = comments.get(&component.id) {
+            component.purpose = Some(comment.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType, Connection};
+
+    fn led_driver() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type, value) in [
+            ("V1", ComponentType::VoltageSource, "3.3V"),
+            ("R3", ComponentType::Resistor, "68"),
+            ("D2", ComponentType::Diode, "LED"),
+        ] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type,
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        circuit.add_connection(Connection { from: "V1".to_string(), to: "R3".to_string(), net_name: "VCC".to_string() });
+        circuit.add_connection(Connection { from: "R3".to_string(), to: "D2".to_string(), net_name: "LED_A".to_string() });
+        circuit
+    }
+
+    #[test]
+    fn test_parse_and_apply_comments() {
+        let circuit = led_driver();
+        let response = r#"```json
+            {"r3": "Current-limiting resistor for LED D2, limits current to ~20 mA at 3.3V",
+             "D2": "Power indicator LED", "Q9": "Not in this circuit", "V1": ""}
+            ```"#;
+        let comments = parse_comments(response, &circuit).unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments["R3"], "Current-limiting resistor for LED D2, limits current to ~20 mA at 3.3V");
+
+        let lines = parse_comments("- V1: 3.3V supply rail\n- D2: Power indicator LED\nThat's all.", &circuit).unwrap();
+        assert_eq!(lines["V1"], "3.3V supply rail");
+
+        let mut annotated = circuit.clone();
+        apply_comments(&mut annotated, &comments);
+        assert_eq!(annotated.components[2].purpose.as_deref(), Some("Power indicator LED"));
+        assert_eq!(annotated.components[0].purpose, None);
+        assert!(parse_comments("No idea.", &circuit).is_err());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_layout_and_comments() {
+        let circuit = led_driver();
+        let mut moved = circuit.clone();
+        moved.components[1].position = (25.0, 10.0);
+        moved.components[1].purpose = Some("Current limiter".to_string());
+        assert_eq!(circuit_fingerprint(&circuit), circuit_fingerprint(&moved));
+
+        let mut changed = circuit.clone();
+        changed.components[1].value = Some("150".to_string());
+        assert_ne!(circuit_fingerprint(&circuit), circuit_fingerprint(&changed));
+    }
+}
@@ -3,6 +3,9 @@
 //! This module manages the chat conversation flow, message processing,
 //! and integration with AI services for circuit design assistance.
 
+use crate::context_enrichment::estimate_tokens;
+use crate::models::AiModel;
+use crate::ollama_client::OpenCircuitOllamaClient;
 use crate::AiResult;
 use chrono::Utc;
 use std::collections::VecDeque;
@@ -21,14 +24,26 @@ pub struct ChatMessage {
 /// Maximum number of messages to keep in conversation history
 const MAX_CONVERSATION_HISTORY: usize = 50;
 
+/// Share of the history token budget above which the conversation is summarized
+const SUMMARY_THRESHOLD: f64 = 0.75;
+
+/// Target length of a conversation summary in words
+const SUMMARY_WORDS: usize = 200;
+
 /// Chat handler for managing AI conversations
 pub struct ChatHandler {
     /// Conversation history for context
     conversation_history: VecDeque<ChatMessage>,
     /// System prompt for the AI assistant
     system_prompt: String,
+    /// Model whose context window bounds the history
+    model: AiModel,
     /// Whether the handler is currently processing a request
     is_processing: bool,
+    /// Client used to summarize long conversations
+    client: OpenCircuitOllamaClient,
+    /// Latest summary that replaced the conversation history
+    conversation_summary: Option<String>,
 }
 
 impl Default for ChatHandler {
@@ -42,10 +57,38 @@ impl ChatHandler {
         Self {
             conversation_history: VecDeque::new(),
             system_prompt: Self::default_system_prompt(),
+            model: AiModel::default(),
             is_processing: false,
+            client: OpenCircuitOllamaClient::new(),
+            conversation_summary: None,
         }
     }
 
+    pub fn with_model(mut self, model: AiModel) -> Self {
+        self.client.set_model(model.model_name().to_string());
+        self.model = model;
+        self.trim_history();
+        self
+    }
+
+    pub fn with_client(mut self, mut client: OpenCircuitOllamaClient) -> Self {
+        client.set_model(self.model.model_name().to_string());
+        self.client = client;
+        self
+    }
+
+    pub fn model(&self) -> &AiModel {
+        &self.model
+    }
+
+    /// Tokens left for history once the system prompt and response are reserved
+    pub fn history_token_budget(&self) -> usize {
+        self.model
+            .context_window_tokens()
+            .saturating_sub(self.model.max_output_tokens())
+            .saturating_sub(estimate_tokens(&self.system_prompt))
+    }
+
     /// Get the default system prompt for the AI assistant
     fn default_system_prompt() -> String {
         r#"You are an expert AI assistant for OpenCircuit, a circuit design and PCB layout tool.
@@ -75,13 +118,74 @@ Always aim to help users create better, more reliable circuit designs."#.to_stri
     /// Add a message to the conversation history
     pub fn add_message(&mut self, message: ChatMessage) {
         self.conversation_history.push_back(message);
-        
-        // Trim history if it gets too long
-        while self.conversation_history.len() > MAX_CONVERSATION_HISTORY {
-            self.conversation_history.pop_front();
+        self.trim_history();
+    }
+
+    /// Drop the oldest messages until the history fits the token budget
+    ///
+    /// The newest message is always kept, even if it alone exceeds the budget.
+    fn trim_history(&mut self) {
+        let budget = self.history_token_budget();
+        let mut tokens: usize = self.conversation_history.iter().map(|message| estimate_tokens(&message.content)).sum();
+        while self.conversation_history.len() > 1
+            && (tokens > budget || self.conversation_history.len() > MAX_CONVERSATION_HISTORY)
+        {
+            if let Some(oldest) = self.conversation_history.pop_front() {
+                tokens -= estimate_tokens(&oldest.content);
+            }
         }
     }
 
+    /// Whether the history has grown past three quarters of its token budget
+    pub fn needs_summary(&self) -> bool {
+        let tokens: usize = self.conversation_history.iter().map(|message| estimate_tokens(&message.content)).sum();
+        tokens as f64 > self.history_token_budget() as f64 * SUMMARY_THRESHOLD
+    }
+
+    /// Replace the conversation history with an LLM-written summary of it
+    ///
+    /// The system prompt is kept; the history becomes a single assistant
+    /// message holding the summary. Does nothing when the history is empty.
+    pub async fn summarize_conversation(&mut self) -> AiResult<()> {
+        if self.conversation_history.is_empty() {
+            return Ok(());
+        }
+
+        let summary = self.client.complete(&self.summary_prompt()).await?;
+        self.apply_summary(summary.trim().to_string());
+        Ok(())
+    }
+
+    /// Latest conversation summary, if the history has been summarized
+    pub fn conversation_summary(&self) -> Option<&str> {
+        self.conversation_summary.as_deref()
+    }
+
+    fn summary_prompt(&self) -> String {
+        let mut prompt = format!(
+            "{}\n\nSummarize this conversation in {} words, preserving technical details such as \
+             requirements, component values, part numbers and design decisions.\n\n",
+            self.system_prompt, SUMMARY_WORDS
+        );
+        for message in &self.conversation_history {
+            let speaker = if message.is_user { "User" } else { "Assistant" };
+            prompt.push_str(&format!("{}: {}\n", speaker, message.content));
+        }
+        prompt.push_str("\nSummary:");
+        prompt
+    }
+
+    fn apply_summary(&mut self, summary: String) {
+        self.conversation_history.clear();
+        self.conversation_history.push_back(ChatMessage {
+            id: Uuid::new_v4().to_string(),
+            content: format!("Summary of the conversation so far:\n{}", summary),
+            is_user: false,
+            timestamp: Utc::now(),
+        });
+        self.conversation_summary = Some(summary);
+    }
+
     /// Process a user message and generate an AI response
     pub async fn process_message(&mut self, user_message: &str) -> AiResult<ChatMessage> {
         if self.is_processing {
@@ -118,6 +222,12 @@ Always aim to help users create better, more reliable circuit designs."#.to_stri
         };
 
         self.add_message(ai_response.clone());
+        if self.needs_summary() {
+            // Trimming still bounds the history if the model is unavailable
+            if let Err(e) = self.summarize_conversation().await {
+                tracing::warn!("Failed to summarize conversation: {}", e);
+            }
+        }
         self.is_processing = false;
 
         Ok(ai_response)
@@ -223,6 +333,7 @@ Always aim to help users create better, more reliable circuit designs."#.to_stri
     /// Clear conversation history
     pub fn clear_history(&mut self) {
         self.conversation_history.clear();
+        self.conversation_summary = None;
     }
 
     /// Check if currently processing a message
@@ -233,6 +344,7 @@ Always aim to help users create better, more reliable circuit designs."#.to_stri
     /// Update system prompt
     pub fn set_system_prompt(&mut self, prompt: String) {
         self.system_prompt = prompt;
+        self.trim_history();
     }
 }
 
@@ -257,6 +369,56 @@ mod tests {
         assert!(handler.is_greeting("Hello there!"));
     }
 
+    #[test]
+    fn test_history_trimmed_to_token_budget() {
+        let mut handler = ChatHandler::new().with_model(AiModel::Custom("llama3.2:1b".to_string()));
+        let budget = handler.history_token_budget();
+        assert!(budget < 4_096 - 1_024);
+
+        let message = |content: String| ChatMessage {
+            id: Uuid::new_v4().to_string(),
+            content,
+            is_user: true,
+            timestamp: Utc::now(),
+        };
+        for _ in 0..4 {
+            handler.add_message(message("x".repeat((budget / 3 + 1) * 4)));
+        }
+        assert_eq!(handler.get_conversation_history().len(), 2);
+
+        handler.add_message(message("y".repeat(budget * 8)));
+        assert_eq!(handler.get_conversation_history().len(), 1);
+        assert!(ChatHandler::new().history_token_budget() > budget);
+    }
+
+    #[test]
+    fn test_summary_replaces_history() {
+        let mut handler = ChatHandler::new().with_model(AiModel::Custom("llama3.2:1b".to_string()));
+        let budget = handler.history_token_budget();
+        for (content, is_user) in [("Design a 5V to 3.3V regulator for 500mA", true), ("Use an AP2112K-3.3", false)] {
+            handler.add_message(ChatMessage { id: Uuid::new_v4().to_string(), content: content.to_string(), is_user, timestamp: Utc::now() });
+        }
+        assert!(!handler.needs_summary());
+
+        let prompt = handler.summary_prompt();
+        assert!(prompt.contains("in 200 words"));
+        assert!(prompt.contains("User: Design a 5V to 3.3V regulator for 500mA\nAssistant: Use an AP2112K-3.3\n"));
+
+        handler.add_message(ChatMessage {
+            id: Uuid::new_v4().to_string(),
+            content: "x".repeat(budget * 4 * 4 / 5),
+            is_user: true,
+            timestamp: Utc::now(),
+        });
+        assert!(handler.needs_summary());
+
+        handler.apply_summary("3.3V LDO at 500mA using AP2112K-3.3".to_string());
+        assert_eq!(handler.get_conversation_history().len(), 1);
+        assert!(handler.get_conversation_history()[0].content.ends_with("AP2112K-3.3"));
+        assert_eq!(handler.conversation_summary(), Some("3.3V LDO at 500mA using AP2112K-3.3"));
+        assert!(!handler.needs_summary());
+    }
+
     #[tokio::test]
     async fn test_message_processing() {
         let mut handler = ChatHandler::new();
@@ -0,0 +1,341 @@
+//! Circuit type detection from topology
+//!
+//! Connections carry no pin information, so the rules work on which nets
+//! each component touches. Ground nets and nets driven by a voltage source
+//! are rails; every other net is a signal net. Circuits no rule recognizes
+//! are classified by the LLM from a summary of the circuit.
+
+use opencircuit_circuit::{Circuit, Component, ComponentType};
+use opencircuit_core::OpenCircuitError;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::context_enrichment;
+use crate::models::CircuitType;
+use crate::AiResult;
+
+/// Nets treated as ground, matching the ERC's convention
+const GROUND_NETS: [&str; 6] = ["0", "GND", "AGND", "DGND", "GROUND", "VSS"];
+
+/// Markers in a transistor's ID or value that identify a MOSFET
+const MOSFET_MARKERS: [&str; 4] = ["MOS", "FET", "IRF", "2N7002"];
+
+/// Types the LLM may answer with, by name
+const LLM_CIRCUIT_TYPES: [(&str, CircuitType); 12] = [
+    ("Oscillator", CircuitType::Oscillator),
+    ("ActiveFilter", CircuitType::ActiveFilter),
+    ("HalfBridge", CircuitType::HalfBridge),
+    ("MixedSignal", CircuitType::MixedSignal),
+    ("Analog", CircuitType::Analog),
+    ("Digital", CircuitType::Digital),
+    ("Power", CircuitType::Power),
+    ("RF", CircuitType::RF),
+    ("Audio", CircuitType::Audio),
+    ("Sensor", CircuitType::Sensor),
+    ("Motor", CircuitType::Motor),
+    ("Communication", CircuitType::Communication),
+];
+
+/// Connectivity of a circuit, split into rails and signal nets
+struct Topology<'a> {
+    components: &'a [Component],
+    nets: HashMap<&'a str, BTreeSet<&'a str>>,
+    supplies: BTreeSet<&'a str>,
+}
+
+impl<'a> Topology<'a> {
+    fn new(circuit: &'a Circuit) -> Self {
+        let mut nets: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for connection in &circuit.connections {
+            for id in [&connection.from, &connection.to] {
+                nets.entry(id.as_str()).or_default().insert(connection.net_name.as_str());
+            }
+        }
+        let supplies = circuit
+            .components
+            .iter()
+            .filter(|component| component.component_type == ComponentType::VoltageSource)
+            .flat_map(|component| nets.get(component.id.as_str()).into_iter().flatten().copied())
+            .filter(|net| !is_ground(net))
+            .collect();
+        Self { components: &circuit.components, nets, supplies }
+    }
+
+    fn of_type(&self, component_type: ComponentType) -> Vec<&'a Component> {
+        self.components.iter().filter(|component| component.component_type == component_type).collect()
+    }
+
+    fn is_signal(&self, net: &str) -> bool {
+        !is_ground(net) && !self.supplies.contains(net)
+    }
+
+    /// Signal nets a component touches
+    fn signal_nets(&self, component: &Component) -> BTreeSet<&'a str> {
+        self.nets
+            .get(component.id.as_str())
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|net| self.is_signal(net))
+            .collect()
+    }
+
+    fn touches(&self, component: &Component, predicate: impl Fn(&str) -> bool) -> bool {
+        self.nets.get(component.id.as_str()).is_some_and(|nets| nets.iter().any(|net| predicate(net)))
+    }
+
+    /// Number of the given components on a net
+    fn count_on(&self, net: &str, components: &[&Component]) -> usize {
+        components.iter().filter(|component| self.signal_nets(component).contains(net)).count()
+    }
+}
+
+/// Circuit type of a known topology, `None` when no rule matches
+///
+/// Rules are tried in order:
+/// - Oscillator: transistor stages linked in a ring of three or more, or two
+///   stages cross-coupled through capacitors, with at least one capacitor
+/// - HalfBridge: two MOSFETs sharing a switch node, one on a supply rail
+///   and the other on ground
+/// - ActiveFilter: an op-amp with both resistors and capacitors on its
+///   signal nets
+pub fn classify_topology(circuit: &Circuit) -> Option<CircuitType> {
+    let topology = Topology::new(circuit);
+    if is_oscillator(&topology) {
+        Some(CircuitType::Oscillator)
+    } else if is_half_bridge(&topology) {
+        Some(CircuitType::HalfBridge)
+    } else if is_active_filter(&topology) {
+        Some(CircuitType::ActiveFilter)
+    } else {
+        None
+    }
+}
+
+fn is_oscillator(topology: &Topology) -> bool {
+    let transistors = topology.of_type(ComponentType::Transistor);
+    let capacitors = topology.of_type(ComponentType::Capacitor);
+    if transistors.len() < 2 || capacitors.is_empty() {
+        return false;
+    }
+
+    let stage_nets: Vec<BTreeSet<&str>> = transistors.iter().map(|transistor| topology.signal_nets(transistor)).collect();
+    let capacitor_nets: Vec<Vec<&str>> =
+        capacitors.iter().map(|capacitor| topology.signal_nets(capacitor).into_iter().collect()).collect();
+
+    // Stages are linked in a ring when the links between them form a cycle
+    let mut roots: Vec<usize> = (0..transistors.len()).collect();
+    fn root(roots: &mut [usize], mut index: usize) -> usize {
+        while roots[index] != index {
+            index = roots[index];
+        }
+        index
+    }
+    for a in 0..transistors.len() {
+        for b in a + 1..transistors.len() {
+            // A net shared only by these two stages links them directly; nets
+            // shared by more stages, such as a differential pair's tail, do not
+            let direct = stage_nets[a]
+                .intersection(&stage_nets[b])
+                .any(|net| topology.count_on(net, &transistors) == 2);
+            let through_capacitors = capacitor_nets
+                .iter()
+                .filter(|nets| {
+                    nets.len() == 2
+                        && ((stage_nets[a].contains(nets[0]) && stage_nets[b].contains(nets[1]))
+                            || (stage_nets[a].contains(nets[1]) && stage_nets[b].contains(nets[0])))
+                })
+                .count();
+            if through_capacitors >= 2 {
+                return true;
+            }
+            if direct || through_capacitors == 1 {
+                let (root_a, root_b) = (root(&mut roots, a), root(&mut roots, b));
+                if root_a == root_b {
+                    return true;
+                }
+                roots[root_a] = root_b;
+            }
+        }
+    }
+    false
+}
+
+fn is_half_bridge(topology: &Topology) -> bool {
+    let mosfets: Vec<&Component> = topology
+        .of_type(ComponentType::Transistor)
+        .into_iter()
+        .filter(|transistor| is_mosfet(transistor))
+        .collect();
+    let on_supply = |component: &Component| topology.touches(component, |net| topology.supplies.contains(net));
+    let on_ground = |component: &Component| topology.touches(component, is_ground);
+
+    mosfets.iter().any(|&high| {
+        on_supply(high)
+            && !on_ground(high)
+            && mosfets.iter().any(|&low| {
+                low.id != high.id
+                    && on_ground(low)
+                    && !on_supply(low)
+                    && topology
+                        .signal_nets(high)
+                        .intersection(&topology.signal_nets(low))
+                        .any(|switch_node| topology.count_on(switch_node, &mosfets) == 2)
+            })
+    })
+}
+
+fn is_active_filter(topology: &Topology) -> bool {
+    let resistors = topology.of_type(ComponentType::Resistor);
+    let capacitors = topology.of_type(ComponentType::Capacitor);
+    topology.of_type(ComponentType::OpAmp).iter().any(|op_amp| {
+        let nets = topology.signal_nets(op_amp);
+        let on_op_amp = |component: &&Component| !topology.signal_nets(component).is_disjoint(&nets);
+        resistors.iter().any(on_op_amp) && capacitors.iter().any(on_op_amp)
+    })
+}
+
+fn is_ground(net: &str) -> bool {
+    GROUND_NETS.iter().any(|ground| ground.eq_ignore_ascii_case(net))
+}
+
+fn is_mosfet(transistor: &Component) -> bool {
+    let value = transistor.value.as_deref().unwrap_or_default().to_uppercase();
+    transistor.id.to_uppercase().starts_with('M') || MOSFET_MARKERS.iter().any(|marker| value.contains(marker))
+}
+
+/// Build the prompt asking the LLM to classify a circuit no rule recognized
+pub fn classification_prompt(circuit: &Circuit) -> String {
+    let names: Vec<&str> = LLM_CIRCUIT_TYPES.iter().map(|(name, _)| *name).collect();
+    format!(
+        "Classify this electronic circuit by its function.\n\n{}\n\n\
+         Respond with exactly one of these types and nothing else: {}.",
+        context_enrichment::summarize(Some(circuit), None),
+        names.join(", ")
+    )
+}
+
+/// Parse the LLM's classification, ignoring case, spacing and punctuation
+///
+/// The first type named in the response wins; an answer naming no known
+/// type is kept as `CircuitType::Other`.
+pub fn parse_circuit_type(response: &str) -> AiResult<CircuitType> {
+    let answer = response.trim();
+    if answer.is_empty() {
+        return Err(OpenCircuitError::AiService("Empty circuit classification".to_string()));
+    }
+
+    // Two-word names such as "half bridge" are matched by joining neighbouring words
+    let words: Vec<String> =
+        answer.split(|c: char| !c.is_ascii_alphanumeric()).filter(|word| !word.is_empty()).map(str::to_lowercase).collect();
+    let detected = (0..words.len())
+        .flat_map(|index| {
+            let pair = words.get(index + 1).map(|next| format!("{}{}", words[index], next));
+            std::iter::once(words[index].clone()).chain(pair)
+        })
+        .find_map(|candidate| {
+            LLM_CIRCUIT_TYPES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&candidate))
+                .map(|(_, circuit_type)| circuit_type.clone())
+        });
+    Ok(detected.unwrap_or_else(|| CircuitType::Other(answer.lines().next().unwrap_or(answer).trim().to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::Connection;
+
+    fn circuit(components: &[(&str, ComponentType, &str)], nets: &[(&str, &[&str])]) -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type, value) in components {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type: component_type.clone(),
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        for (net, ids) in nets {
+            for id in *ids {
+                circuit.add_connection(Connection { from: id.to_string(), to: id.to_string(), net_name: net.to_string() });
+            }
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_classify_known_topologies() {
+        use ComponentType::*;
+
+        // Three inverting stages, each driving the next, with load capacitors
+        let ring = circuit(
+            &[
+                ("V1", VoltageSource, "5"),
+                ("Q1", Transistor, "2N3904"),
+                ("Q2", Transistor, "2N3904"),
+                ("Q3", Transistor, "2N3904"),
+                ("R1", Resistor, "1k"),
+                ("R2", Resistor, "1k"),
+                ("R3", Resistor, "1k"),
+                ("C1", Capacitor, "10n"),
+            ],
+            &[
+                ("VCC", &["V1", "R1", "R2", "R3"]),
+                ("GND", &["V1", "Q1", "Q2", "Q3", "C1"]),
+                ("N1", &["R1", "Q1", "Q2"]),
+                ("N2", &["R2", "Q2", "Q3"]),
+                ("N3", &["R3", "Q3", "Q1", "C1"]),
+            ],
+        );
+        assert_eq!(classify_topology(&ring), Some(CircuitType::Oscillator));
+
+        let half_bridge = circuit(
+            &[
+                ("V1", VoltageSource, "12"),
+                ("M1", Transistor, "IRF540N"),
+                ("M2", Transistor, "IRF540N"),
+                ("L1", Inductor, "10u"),
+            ],
+            &[
+                ("VIN", &["V1", "M1"]),
+                ("GND", &["V1", "M2"]),
+                ("SW", &["M1", "M2", "L1"]),
+                ("GATE_H", &["M1"]),
+                ("GATE_L", &["M2"]),
+            ],
+        );
+        assert_eq!(classify_topology(&half_bridge), Some(CircuitType::HalfBridge));
+
+        // Unity-gain Sallen-Key low-pass
+        let sallen_key = circuit(
+            &[
+                ("U1", OpAmp, "TL072"),
+                ("R1", Resistor, "10k"),
+                ("R2", Resistor, "10k"),
+                ("C1", Capacitor, "10n"),
+                ("C2", Capacitor, "10n"),
+            ],
+            &[("IN", &["R1"]), ("A", &["R1", "R2", "C1"]), ("B", &["R2", "C2", "U1"]), ("OUT", &["U1", "C1"]), ("GND", &["C2"])],
+        );
+        assert_eq!(classify_topology(&sallen_key), Some(CircuitType::ActiveFilter));
+
+        let divider = circuit(
+            &[("V1", VoltageSource, "5"), ("R1", Resistor, "10k"), ("R2", Resistor, "10k")],
+            &[("VIN", &["V1", "R1"]), ("OUT", &["R1", "R2"]), ("GND", &["V1", "R2"])],
+        );
+        assert_eq!(classify_topology(&divider), None);
+        assert!(classification_prompt(&divider).contains("HalfBridge, MixedSignal"));
+    }
+
+    #[test]
+    fn test_parse_circuit_type() {
+        assert_eq!(parse_circuit_type("Half-bridge").unwrap(), CircuitType::HalfBridge);
+        assert_eq!(parse_circuit_type("This is a mixed signal design.").unwrap(), CircuitType::MixedSignal);
+        assert_eq!(parse_circuit_type("Audio power amplifier").unwrap(), CircuitType::Audio);
+        // "RF" inside a word is not a match
+        assert_eq!(parse_circuit_type("Charge pump\nIt performs").unwrap(), CircuitType::Other("Charge pump".to_string()));
+        assert!(parse_circuit_type("  ").is_err());
+    }
+}
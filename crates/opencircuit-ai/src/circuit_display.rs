@@ -0,0 +1,235 @@
+//! Human-readable circuit documentation
+//!
+//! The LLM only writes prose: a description per component and a summary of
+//! what the circuit does. The component and connection tables are built from
+//! the circuit itself, so every row uses the schematic's component IDs even
+//! when the model skips or renames parts.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::health_report::extract_json;
+use crate::AiResult;
+
+/// Output format for [`render_circuit_document`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DisplayFormat {
+    Markdown,
+    Html,
+    Plaintext,
+}
+
+/// LLM-written prose for a circuit document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CircuitDescription {
+    /// One-line descriptions keyed by component ID
+    pub components: HashMap<String, String>,
+    /// What the circuit does, in natural language
+    pub summary: String,
+}
+
+/// Build the documentation prompt
+pub fn display_prompt(circuit: &Circuit) -> String {
+    let mut prompt = String::from("Document this circuit for an engineer reading it for the first time.\n\nComponents:\n");
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or("(no value)")
+        ));
+    }
+    prompt.push_str("\nNetlist:\n");
+    for connection in &circuit.connections {
+        prompt.push_str(&format!("- {} -> {} on net {}\n", connection.from, connection.to, connection.net_name));
+    }
+
+    prompt.push_str(
+        "\nDescribe each component in a short phrase and summarize the circuit's function in 2-4 sentences. \
+         Refer to components by the IDs above. Respond with JSON only:\n\
+         {\"components\": {\"R1\": \"...\"}, \"summary\": \"...\"}",
+    );
+    prompt
+}
+
+/// Parse the model's prose, keeping only descriptions of components in `circuit`
+pub fn parse_description(response: &str, circuit: &Circuit) -> AiResult<CircuitDescription> {
+    let value = extract_json(response)
+        .and_then(|json| serde_json::from_str::<Value>(&json).ok())
+        .ok_or_else(|| OpenCircuitError::AiService("Circuit description response contained no JSON".to_string()))?;
+
+    let summary = value["summary"].as_str().map(str::trim).unwrap_or_default().to_string();
+    if summary.is_empty() {
+        return Err(OpenCircuitError::AiService("Circuit description has no summary".to_string()));
+    }
+
+    let components = value["components"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(id, description)| {
+            let component = circuit.components.iter().find(|component| component.id.eq_ignore_ascii_case(id.trim()))?;
+            let description = description.as_str()?.trim();
+            (!description.is_empty()).then(|| (component.id.clone(), description.to_string()))
+        })
+        .collect();
+
+    Ok(CircuitDescription { components, summary })
+}
+
+/// Render the component table, connection table and summary
+///
+/// Components the model did not describe fall back to their `purpose`.
+pub fn render_circuit_document(circuit: &Circuit, description: &CircuitDescription, format: DisplayFormat) -> String {
+    let component_rows: Vec<[String; 3]> = circuit
+        .components
+        .iter()
+        .map(|component| {
+            let text = description
+                .components
+                .get(&component.id)
+                .or(component.purpose.as_ref())
+                .cloned()
+                .unwrap_or_default();
+            [component.id.clone(), component.value.clone().unwrap_or_else(|| "-".to_string()), text]
+        })
+        .collect();
+    let connection_rows: Vec<[String; 3]> = circuit
+        .connections
+        .iter()
+        .map(|connection| [connection.from.clone(), connection.to.clone(), connection.net_name.clone()])
+        .collect();
+    let tables = [
+        ("Components", ["Reference", "Value", "Description"], component_rows),
+        ("Connections", ["From", "To", "Net"], connection_rows),
+    ];
+
+    let mut document = String::new();
+    match format {
+        DisplayFormat::Markdown => {
+            for (title, header, rows) in &tables {
+                document.push_str(&format!("## {}\n\n", title));
+                document.push_str(&markdown_row(header.iter().map(|cell| cell.to_string())));
+                document.push_str("| --- | --- | --- |\n");
+                for row in rows {
+                    document.push_str(&markdown_row(row.iter().cloned()));
+                }
+                document.push('\n');
+            }
+            document.push_str(&format!("## Summary\n\n{}\n", description.summary));
+        }
+        DisplayFormat::Html => {
+            for (title, header, rows) in &tables {
+                document.push_str(&format!("<h2>{}</h2>\n<table>\n<tr>", title));
+                for cell in header {
+                    document.push_str(&format!("<th>{}</th>", cell));
+                }
+                document.push_str("</tr>\n");
+                for row in rows {
+                    document.push_str("<tr>");
+                    for cell in row {
+                        document.push_str(&format!("<td>{}</td>", escape_html(cell)));
+                    }
+                    document.push_str("</tr>\n");
+                }
+                document.push_str("</table>\n");
+            }
+            document.push_str(&format!("<h2>Summary</h2>\n<p>{}</p>\n", escape_html(&description.summary)));
+        }
+        DisplayFormat::Plaintext => {
+            for (title, header, rows) in &tables {
+                document.push_str(&format!("{}\n", title.to_uppercase()));
+                let widths: Vec<usize> = (0..3)
+                    .map(|column| {
+                        rows.iter()
+                            .map(|row| row[column].chars().count())
+                            .chain([header[column].len()])
+                            .max()
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                let line = |cells: [&str; 3]| {
+                    let padded: Vec<String> =
+                        cells.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+                    format!("{}\n", padded.join("  ").trim_end())
+                };
+                document.push_str(&line(*header));
+                for row in rows {
+                    document.push_str(&line([&row[0], &row[1], &row[2]]));
+                }
+                document.push('\n');
+            }
+            document.push_str(&format!("SUMMARY\n{}\n", description.summary));
+        }
+    }
+    document
+}
+
+fn markdown_row(cells: impl Iterator<Item = String>) -> String {
+    let cells: Vec<String> = cells.map(|cell| cell.replace('|', "\\|").replace('\n', " ")).collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType, Connection};
+
+    fn divider() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, value) in [("R1", "10k"), ("R2", "4.7k")] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type: ComponentType::Resistor,
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        circuit.add_connection(Connection { from: "R1".to_string(), to: "R2".to_string(), net_name: "OUT".to_string() });
+        circuit
+    }
+
+    #[test]
+    fn test_parse_description() {
+        let circuit = divider();
+        let response = r#"Here you go: {"components": {"r1": "Top leg | high side", "R7": "Not here"},
+            "summary": "Divides the input down to about a third."}"#;
+        let description = parse_description(response, &circuit).unwrap();
+        assert_eq!(description.components.len(), 1);
+        assert_eq!(description.components["R1"], "Top leg | high side");
+        assert_eq!(description.summary, "Divides the input down to about a third.");
+
+        assert!(parse_description(r#"{"components": {"R1": "Top leg"}}"#, &circuit).is_err());
+        assert!(parse_description("No idea.", &circuit).is_err());
+    }
+
+    #[test]
+    fn test_render_formats_use_component_ids() {
+        let mut circuit = divider();
+        circuit.components[1].purpose = Some("Bottom <leg>".to_string());
+        let description = CircuitDescription {
+            components: HashMap::from([("R1".to_string(), "Top leg | high side".to_string())]),
+            summary: "Divides the input.".to_string(),
+        };
+
+        let markdown = render_circuit_document(&circuit, &description, DisplayFormat::Markdown);
+        assert!(markdown.contains("| R1 | 10k | Top leg \\| high side |\n"));
+        assert!(markdown.contains("| R1 | R2 | OUT |\n"));
+        assert!(markdown.ends_with("## Summary\n\nDivides the input.\n"));
+
+        let html = render_circuit_document(&circuit, &description, DisplayFormat::Html);
+        assert!(html.contains("<tr><td>R2</td><td>4.7k</td><td>Bottom &lt;leg&gt;</td></tr>"));
+
+        let plain = render_circuit_document(&circuit, &description, DisplayFormat::Plaintext);
+        assert!(plain.starts_with("COMPONENTS\nReference  Value  Description\nR1         10k    Top leg | high side\n"));
+        assert!(plain.contains("From  To  Net\nR1    R2  OUT\n"));
+    }
+}
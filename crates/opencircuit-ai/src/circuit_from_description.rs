@@ -0,0 +1,92 @@
+//! Circuits generated from a plain-language description
+//!
+//! The prompt opens with [`Circuit::json_schema`] as its instructions, so
+//! the model knows the exact field names and the allowed component types
+//! instead of inventing its own format.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+
+use crate::health_report::extract_json;
+use crate::AiResult;
+
+/// Build the generation prompt, schema first
+pub fn description_prompt(description: &str) -> String {
+    let schema = serde_json::to_string_pretty(&Circuit::json_schema()).unwrap_or_default();
+    format!(
+        "You are an electronics engineer. Design circuits as JSON matching this JSON Schema exactly:\n\
+         {}\n\n\
+         Use reference designators as IDs (R1, C1, U1), give values with units, \
+         and only connect components that exist. Respond with the JSON object only.\n\n\
+         Circuit to design: {}",
+        schema, description
+    )
+}
+
+/// Parse the model's circuit
+///
+/// Fails when the JSON does not match the circuit format or a connection
+/// names a component that was not defined.
+pub fn parse_circuit(response: &str) -> AiResult<Circuit> {
+    let json = extract_json(response)
+        .ok_or_else(|| OpenCircuitError::AiService("Generated circuit contained no JSON object".to_string()))?;
+    let circuit: Circuit = serde_json::from_str(&json)
+        .map_err(|e| OpenCircuitError::AiService(format!("Generated circuit does not match the schema: {}", e)))?;
+
+    if circuit.components.is_empty() {
+        return Err(OpenCircuitError::AiService("Generated circuit has no components".to_string()));
+    }
+    for connection in &circuit.connections {
+        for id in [&connection.from, &connection.to] {
+            if !circuit.components.iter().any(|component| &component.id == id) {
+                return Err(OpenCircuitError::AiService(format!(
+                    "Connection on net {} references unknown component {}",
+                    connection.net_name, id
+                )));
+            }
+        }
+    }
+    Ok(circuit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::ComponentType;
+
+    const GENERATED: &str = r#"Here is the divider:
+```json
+{
+  "components": [
+    {"id": "V1", "component_type": "VoltageSource", "value": "5V", "position": [0, 0]},
+    {"id": "R1", "component_type": "Resistor", "value": "10k", "position": [10, 0]},
+    {"id": "R2", "component_type": "Resistor", "value": "10k", "position": [10, 10]}
+  ],
+  "connections": [
+    {"from": "V1", "to": "R1", "net_name": "VIN"},
+    {"from": "R1", "to": "R2", "net_name": "VOUT"}
+  ]
+}
+```"#;
+
+    #[test]
+    fn test_generated_json_validates_against_schema() {
+        let validator = jsonschema::validator_for(&Circuit::json_schema()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&extract_json(GENERATED).unwrap()).unwrap();
+        assert!(validator.is_valid(&json));
+
+        let circuit = parse_circuit(GENERATED).unwrap();
+        assert_eq!(circuit.components[1].component_type, ComponentType::Resistor);
+        assert_eq!(circuit.connections.len(), 2);
+        assert!(description_prompt("5V to 2.5V divider").contains("\"VoltageSource\""));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_circuits() {
+        let dangling = r#"{"components": [{"id": "R1", "component_type": "Resistor", "value": "1k", "position": [0, 0]}],
+                           "connections": [{"from": "R1", "to": "R9", "net_name": "OUT"}]}"#;
+        assert!(parse_circuit(dangling).is_err());
+        assert!(parse_circuit(r#"{"components": [{"id": "F1", "component_type": "Fuse"}], "connections": []}"#).is_err());
+        assert!(parse_circuit("I cannot design that.").is_err());
+    }
+}
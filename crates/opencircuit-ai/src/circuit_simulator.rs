@@ -24,6 +24,12 @@ pub enum SimulationError {
     MissingAnalysis(String),
 }
 
+impl From<SimulationError> for opencircuit_core::OpenCircuitError {
+    fn from(error: SimulationError) -> Self {
+        opencircuit_core::OpenCircuitError::Circuit(error.to_string())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimulationRequest {
     pub netlist: Netlist,
@@ -322,7 +328,7 @@ mod tests {
             parameters: SimulationParameters::default(),
         };
 
-        let simulator = CircuitSimulator::new(OllamaClient::new("http://localhost:11434".to_string()));
+        let simulator = CircuitSimulator::new(OpenCircuitOllamaClient::new());
         let prompt = simulator.build_simulation_prompt(&request);
         
         assert!(prompt.contains("Perform circuit simulation"));
@@ -15,6 +15,9 @@ use opencircuit_core::{
 use crate::models::{AiModel, AiContext};
 use crate::ollama_client::OpenCircuitOllamaClient;
 use crate::embeddings::{ComponentEmbeddingEngine, SimilarityMatch};
+use crate::health_report::extract_json;
+use crate::esd_protection::{self, EsdRecommendation, IoPinSpec};
+use serde_json::Value;
 
 type Result<T> = std::result::Result<T, OpenCircuitError>;
 
@@ -59,6 +62,39 @@ pub enum CostCategory {
     Unknown,
 }
 
+/// Manufacturer lifecycle stage of a component
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LifecycleStatus {
+    Active,
+    NotRecommendedForNewDesign,
+    LimitedAvailability,
+    Obsolete,
+}
+
+/// Specification keys suppliers report lifecycle status under
+pub const LIFECYCLE_SPEC_KEYS: [&str; 4] = ["Part Status", "Product Status", "Lifecycle Status", "LifecycleStatus"];
+
+/// Most replacement parts suggested for an at-risk component
+const MAX_LIFECYCLE_ALTERNATIVES: usize = 3;
+
+/// End-of-life risk of designing in a component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleRisk {
+    pub status: LifecycleStatus,
+    /// Risk score (0.0 to 1.0)
+    pub risk_score: f32,
+    pub reason: String,
+    /// Loaded components that could replace it, empty for active parts
+    pub suggested_alternatives: Vec<Component>,
+}
+
+/// Dimensions rated in a compatibility analysis, each scored 0-100
+pub const COMPATIBILITY_DIMENSIONS: [&str; 5] =
+    ["voltage_range", "current_capacity", "package", "temperature_range", "speed"];
+
+/// Longest recommendation explanation kept, in sentences
+const MAX_EXPLANATION_SENTENCES: usize = 5;
+
 /// Recommendation request parameters
 #[derive(Debug, Clone)]
 pub struct RecommendationRequest {
@@ -218,27 +254,119 @@ impl ComponentAdvisor {
             "Analyze the compatibility of this component with the given circuit context:\n\n\
             Component: {}\n\n\
             Circuit Context: {}\n\n\
-            Provide analysis on:\n\
-            1. Electrical compatibility\n\
-            2. Physical compatibility\n\
-            3. Performance implications\n\
-            4. Potential issues or warnings\n\
-            5. Optimization suggestions",
+            Rate compatibility from 0 to 100 on each dimension: voltage range, current capacity, \
+            package, temperature range and speed/frequency. Respond with JSON only:\n\
+            {{\"scores\": {{\"voltage_range\": 0, \"current_capacity\": 0, \"package\": 0, \
+            \"temperature_range\": 0, \"speed\": 0}}, \
+            \"electrical\": \"...\", \"physical\": \"...\", \"performance\": \"...\", \
+            \"warnings\": [\"...\"], \"suggestions\": [\"...\"]}}",
             component_description,
             context_description
         );
 
         let response = self.ollama_client.complete(&prompt).await?;
-        
-        Ok(CompatibilityAnalysis {
-            component_id: component.id.clone(),
-            compatibility_score: self.extract_compatibility_score(&response),
-            electrical_compatibility: self.extract_electrical_analysis(&response),
-            physical_compatibility: self.extract_physical_analysis(&response),
-            performance_impact: self.extract_performance_analysis(&response),
-            warnings: self.extract_warnings(&response),
-            suggestions: self.extract_suggestions(&response),
-        })
+        CompatibilityAnalysis::from_response(&component.id, &response)
+    }
+
+    /// Explain in 3-5 sentences why `component` suits the requirements
+    ///
+    /// Covers why it is the best match, the trade-offs against alternatives
+    /// and what to verify before committing, citing its specification values.
+    pub async fn explain_recommendation(
+        &mut self,
+        component: &Component,
+        requirements: &RecommendationRequest,
+    ) -> Result<String> {
+        let prompt = self.explanation_prompt(component, requirements);
+        let response = self.ollama_client.complete(&prompt).await?;
+        let explanation = first_sentences(&response, MAX_EXPLANATION_SENTENCES);
+        if explanation.is_empty() {
+            return Err(OpenCircuitError::AiService("Recommendation explanation was empty".to_string()));
+        }
+        Ok(explanation)
+    }
+
+    /// Assess whether `component` is at risk of being obsoleted
+    ///
+    /// Supplier lifecycle data in the specifications (see
+    /// [`LIFECYCLE_SPEC_KEYS`]) is used when present; otherwise the model
+    /// judges from the description. Parts that are not active get
+    /// alternatives from the loaded components.
+    pub async fn get_lifecycle_risk(&mut self, component: &Component) -> Result<LifecycleRisk> {
+        let (mut risk, named_alternatives) = match LifecycleRisk::from_specifications(component) {
+            Some(risk) => (risk, Vec::new()),
+            None => {
+                let response = self.ollama_client.complete(&self.lifecycle_prompt(component)).await?;
+                LifecycleRisk::from_response(&response)?
+            }
+        };
+        if risk.status != LifecycleStatus::Active {
+            risk.suggested_alternatives = lifecycle_alternatives(component, &self.component_database, &named_alternatives);
+        }
+        Ok(risk)
+    }
+
+    /// Suggest a TVS diode for each I/O net against IEC 61000-4-2
+    ///
+    /// Every net in `io_pins` must be in the circuit. Nets the model does not
+    /// answer for are left out of the result.
+    pub async fn suggest_esd_protection(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+        io_pins: &[IoPinSpec],
+    ) -> Result<Vec<EsdRecommendation>> {
+        esd_protection::validate_io_pins(circuit, io_pins)?;
+        let response = self.ollama_client.complete(&esd_protection::esd_prompt(circuit, io_pins)).await?;
+        esd_protection::parse_esd_recommendations(&response, io_pins, &self.component_database)
+    }
+
+    fn lifecycle_prompt(&self, component: &Component) -> String {
+        format!(
+            "Assess the lifecycle risk of designing in this electronic component.\n\n\
+            Component: {}\n\n\
+            From what you know of the part and its manufacturer, classify it as Active, \
+            NotRecommendedForNewDesign, LimitedAvailability (last-time-buy or end-of-life notice) or Obsolete, \
+            and rate the risk of it becoming unavailable during a five-year production run from 0.0 to 1.0. \
+            If it is not Active, name current replacement part numbers. Respond with JSON only:\n\
+            {{\"status\": \"Active\", \"risk_score\": 0.1, \"reason\": \"...\", \"alternatives\": [\"...\"]}}",
+            self.component_to_text(component)
+        )
+    }
+
+    fn explanation_prompt(&self, component: &Component, request: &RecommendationRequest) -> String {
+        let context_info = request
+            .circuit_context
+            .as_ref()
+            .map(|context| self.context_to_text(context))
+            .unwrap_or_else(|| "No specific circuit context provided".to_string());
+        let budget_info = match &request.budget_constraints {
+            Some(budget) => format!(
+                "max {} {} per component, priority: {:?}",
+                budget.max_cost_per_component, budget.currency, budget.cost_priority
+            ),
+            None => "No budget constraints specified".to_string(),
+        };
+
+        format!(
+            "Explain why this component was recommended.\n\n\
+            Component: {}\n\
+            Specifications: {}\n\n\
+            Requirements: {}\n\
+            Circuit Context: {}\n\
+            Budget: {}\n\
+            Performance Priorities: {:?}\n\n\
+            In 3 to 5 sentences of plain prose, explain:\n\
+            1. Why this component is the best match for the requirements\n\
+            2. What trade-offs it makes compared to typical alternatives\n\
+            3. What the user should verify before committing to it\n\
+            Quote the specific specification values that support each point.",
+            self.component_to_text(component),
+            self.component_specs_to_text(component),
+            request.requirements,
+            context_info,
+            budget_info,
+            request.performance_priorities
+        )
     }
 
     /// Enhance user requirements using AI
@@ -382,15 +510,12 @@ impl ComponentAdvisor {
             let alternatives = self.find_alternatives_for_component(&analyzed.component).await?;
             let warnings = self.generate_warnings(&analyzed.component, request).await?;
             let cost_analysis = self.analyze_cost(&analyzed.component, request).await?;
+            let reasoning = self.explain_recommendation(&analyzed.component, request).await?;
 
             let recommendation = ComponentRecommendation {
                 component: analyzed.component,
                 confidence: (analyzed.similarity_score * 0.4 + analyzed.ai_analysis.suitability_score * 0.6),
-                reasoning: format!(
-                    "{}. AI Analysis: {}",
-                    analyzed.match_reason,
-                    analyzed.ai_analysis.strengths.join(". ")
-                ),
+                reasoning,
                 alternatives,
                 warnings,
                 performance_notes: analyzed.ai_analysis.performance_notes,
@@ -513,44 +638,6 @@ impl ComponentAdvisor {
     }
 
     // Additional helper methods for compatibility analysis
-    fn extract_compatibility_score(&self, response: &str) -> f32 {
-        self.extract_suitability_score(response) // Reuse the same logic
-    }
-
-    fn extract_electrical_analysis(&self, response: &str) -> String {
-        if response.contains("fully compatible") {
-            "Fully electrically compatible".to_string()
-        } else if response.contains("compatible") {
-            "Electrically compatible with minor considerations".to_string()
-        } else {
-            "Requires electrical compatibility verification".to_string()
-        }
-    }
-
-    fn extract_physical_analysis(&self, response: &str) -> String {
-        if response.contains("fits") || response.contains("compatible footprint") {
-            "Physical dimensions compatible".to_string()
-        } else {
-            "Verify physical compatibility".to_string()
-        }
-    }
-
-    fn extract_performance_analysis(&self, _response: &str) -> String {
-        "Performance impact analysis needed".to_string() // Simplified
-    }
-
-    fn extract_warnings(&self, response: &str) -> Vec<String> {
-        let mut warnings = Vec::new();
-        if response.contains("warning") || response.contains("caution") {
-            warnings.push("Review compatibility carefully".to_string());
-        }
-        warnings
-    }
-
-    fn extract_suggestions(&self, _response: &str) -> Vec<String> {
-        vec!["Consider testing in target application".to_string()] // Simplified
-    }
-
     async fn find_alternatives_for_component(&mut self, _component: &Component) -> Result<Vec<Component>> {
         // Simplified - return empty for now
         Ok(Vec::new())
@@ -588,6 +675,52 @@ impl ComponentAdvisor {
     }
 }
 
+/// The first `max` sentences of `text` as one line
+///
+/// A sentence ends at `.`, `!` or `?` followed by whitespace, so values such
+/// as `0.25W` are not split.
+fn first_sentences(text: &str, max: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut count = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let at_boundary = matches!(chars.peek(), None | Some((_, ' ')));
+        if matches!(c, '.' | '!' | '?') && at_boundary {
+            count += 1;
+            if count == max {
+                return text[..=index].to_string();
+            }
+        }
+    }
+    text
+}
+
+/// Loaded components that could replace `component`
+///
+/// Parts the model named come first, then other parts in the same category
+/// whose supplier data lists them as active.
+fn lifecycle_alternatives(component: &Component, database: &[Component], named: &[String]) -> Vec<Component> {
+    let candidates = database.iter().filter(|candidate| candidate.part_number != component.part_number);
+    let named_parts = named.iter().filter_map(|part_number| {
+        candidates.clone().find(|candidate| candidate.part_number.eq_ignore_ascii_case(part_number.trim()))
+    });
+    let active_parts = candidates.clone().filter(|candidate| {
+        candidate.category == component.category
+            && LifecycleRisk::from_specifications(candidate).is_some_and(|risk| risk.status == LifecycleStatus::Active)
+    });
+
+    let mut alternatives: Vec<Component> = Vec::new();
+    for candidate in named_parts.chain(active_parts) {
+        if alternatives.len() == MAX_LIFECYCLE_ALTERNATIVES {
+            break;
+        }
+        if !alternatives.iter().any(|alternative| alternative.id == candidate.id) {
+            alternatives.push(candidate.clone());
+        }
+    }
+    alternatives
+}
+
 /// Supporting data structures
 #[derive(Debug, Clone)]
 struct AnalyzedComponent {
@@ -623,6 +756,139 @@ pub struct CompatibilityAnalysis {
     pub warnings: Vec<String>,
     /// Optimization suggestions
     pub suggestions: Vec<String>,
+    /// Score per entry of [`COMPATIBILITY_DIMENSIONS`], from 0 to 100
+    #[serde(default)]
+    pub score_breakdown: HashMap<String, f32>,
+}
+
+impl CompatibilityAnalysis {
+    /// Parse the model's JSON ratings
+    ///
+    /// The overall score is the mean of the rated dimensions scaled to 0.0-1.0.
+    /// Dimensions the model did not rate are left out of the breakdown.
+    pub fn from_response(component_id: &str, response: &str) -> Result<Self> {
+        let value: Value = extract_json(response)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .ok_or_else(|| OpenCircuitError::AiService("Compatibility analysis contained no JSON object".to_string()))?;
+
+        let score_breakdown: HashMap<String, f32> = COMPATIBILITY_DIMENSIONS
+            .iter()
+            .filter_map(|dimension| {
+                let score = value.pointer(&format!("/scores/{}", dimension))?.as_f64()?;
+                Some((dimension.to_string(), score.clamp(0.0, 100.0) as f32))
+            })
+            .collect();
+        if score_breakdown.is_empty() {
+            return Err(OpenCircuitError::AiService("Compatibility analysis rated no dimensions".to_string()));
+        }
+        let compatibility_score = score_breakdown.values().sum::<f32>() / score_breakdown.len() as f32 / 100.0;
+
+        let text = |key: &str| value.get(key).and_then(Value::as_str).unwrap_or_default().trim().to_string();
+        let list = |key: &str| -> Vec<String> {
+            value
+                .get(key)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+
+        Ok(Self {
+            component_id: component_id.to_string(),
+            compatibility_score,
+            electrical_compatibility: text("electrical"),
+            physical_compatibility: text("physical"),
+            performance_impact: text("performance"),
+            warnings: list("warnings"),
+            suggestions: list("suggestions"),
+            score_breakdown,
+        })
+    }
+}
+
+impl LifecycleStatus {
+    /// Read supplier or model wording such as "Not For New Designs",
+    /// "Last Time Buy" or "NotRecommendedForNewDesign"
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label: String = label.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_lowercase()).collect();
+        let has = |words: &[&str]| words.iter().any(|word| label.contains(word));
+        if has(&["obsolete", "discontinued", "inactive"]) {
+            Some(Self::Obsolete)
+        } else if has(&["lasttimebuy", "endoflife", "eol", "limited"]) {
+            Some(Self::LimitedAvailability)
+        } else if has(&["notrecommended", "notfornewdesign", "nrnd"]) {
+            Some(Self::NotRecommendedForNewDesign)
+        } else if has(&["active", "newproduct", "production"]) {
+            Some(Self::Active)
+        } else {
+            None
+        }
+    }
+
+    /// Risk score used when no better estimate is available
+    pub fn default_risk(self) -> f32 {
+        match self {
+            Self::Active => 0.1,
+            Self::NotRecommendedForNewDesign => 0.6,
+            Self::LimitedAvailability => 0.8,
+            Self::Obsolete => 1.0,
+        }
+    }
+}
+
+impl LifecycleRisk {
+    /// Risk from supplier lifecycle data in the component's specifications
+    pub fn from_specifications(component: &Component) -> Option<Self> {
+        let (key, label, status) = LIFECYCLE_SPEC_KEYS.iter().find_map(|key| {
+            let label = component.get_spec(key)?.as_string();
+            let status = LifecycleStatus::from_label(&label)?;
+            Some((key, label, status))
+        })?;
+        Some(Self {
+            status,
+            risk_score: status.default_risk(),
+            reason: format!("Supplier data lists {} as \"{}\" ({})", component.part_number, label.trim(), key),
+            suggested_alternatives: Vec::new(),
+        })
+    }
+
+    /// Parse the model's JSON assessment and the replacement part numbers it names
+    pub fn from_response(response: &str) -> Result<(Self, Vec<String>)> {
+        let value: Value = extract_json(response)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .ok_or_else(|| OpenCircuitError::AiService("Lifecycle assessment contained no JSON object".to_string()))?;
+        let status = value
+            .get("status")
+            .and_then(Value::as_str)
+            .and_then(LifecycleStatus::from_label)
+            .ok_or_else(|| OpenCircuitError::AiService("Lifecycle assessment had no known status".to_string()))?;
+        let risk_score = value
+            .get("risk_score")
+            .and_then(Value::as_f64)
+            .map_or(status.default_risk(), |score| score.clamp(0.0, 1.0) as f32);
+        let alternatives = value
+            .get("alternatives")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::trim)
+            .filter(|part_number| !part_number.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let risk = Self {
+            status,
+            risk_score,
+            reason: value.get("reason").and_then(Value::as_str).unwrap_or_default().trim().to_string(),
+            suggested_alternatives: Vec::new(),
+        };
+        Ok((risk, alternatives))
+    }
 }
 
 #[cfg(test)]
@@ -668,6 +934,107 @@ mod tests {
         assert_eq!(advisor.extract_suitability_score("This is poor quality"), 0.3);
     }
 
+    #[tokio::test]
+    async fn test_explanation_prompt_cites_specifications() {
+        let advisor = ComponentAdvisor::new(OpenCircuitOllamaClient::new()).await.unwrap();
+        let request = RecommendationRequest {
+            requirements: "Pull-up resistor for a 3.3V I2C bus".to_string(),
+            circuit_context: None,
+            preferred_categories: vec![ComponentCategory::Resistors],
+            budget_constraints: None,
+            performance_priorities: vec![PerformancePriority::PowerEfficiency],
+            max_recommendations: 3,
+        };
+
+        let prompt = advisor.explanation_prompt(&create_test_component(), &request);
+        assert!(prompt.contains("Power: 0.25W"));
+        assert!(prompt.contains("Pull-up resistor for a 3.3V I2C bus"));
+        assert!(prompt.contains("trade-offs"));
+        assert!(prompt.contains("verify before committing"));
+    }
+
+    #[test]
+    fn test_compatibility_score_breakdown() {
+        let response = r#"```json
+            {"scores": {"voltage_range": 95, "current_capacity": 95, "package": 30, "temperature_range": 100, "speed": 120},
+             "electrical": "Handles the 3.3V rail", "physical": "SOT-23 instead of SOIC-8",
+             "warnings": ["Different footprint", ""], "suggestions": ["Look for the SOIC-8 variant"]}
+            ```"#;
+        let analysis = CompatibilityAnalysis::from_response("U3", response).unwrap();
+
+        assert_eq!(analysis.score_breakdown.len(), 5);
+        assert_eq!(analysis.score_breakdown["package"], 30.0);
+        assert_eq!(analysis.score_breakdown["speed"], 100.0);
+        assert!((analysis.compatibility_score - 0.84).abs() < 1e-6);
+        assert_eq!(analysis.physical_compatibility, "SOT-23 instead of SOIC-8");
+        assert_eq!(analysis.warnings, vec!["Different footprint".to_string()]);
+        assert_eq!(analysis.performance_impact, "");
+
+        assert!(CompatibilityAnalysis::from_response("U3", r#"{"scores": {}}"#).is_err());
+        assert!(CompatibilityAnalysis::from_response("U3", "Looks compatible.").is_err());
+    }
+
+    fn part(part_number: &str, status: Option<&str>) -> Component {
+        let mut component = Component::new(
+            part_number.to_string(),
+            "TestCorp".to_string(),
+            ComponentCategory::IntegratedCircuits,
+            "Voltage regulator".to_string(),
+        );
+        if let Some(status) = status {
+            component.set_spec("Part Status".to_string(), SpecValue::String(status.to_string()));
+        }
+        component
+    }
+
+    #[test]
+    fn test_lifecycle_status_labels() {
+        assert_eq!(LifecycleStatus::from_label("Not For New Designs"), Some(LifecycleStatus::NotRecommendedForNewDesign));
+        assert_eq!(LifecycleStatus::from_label("NotRecommendedForNewDesign"), Some(LifecycleStatus::NotRecommendedForNewDesign));
+        assert_eq!(LifecycleStatus::from_label("Last Time Buy"), Some(LifecycleStatus::LimitedAvailability));
+        assert_eq!(LifecycleStatus::from_label("Discontinued at Digi-Key"), Some(LifecycleStatus::Obsolete));
+        assert_eq!(LifecycleStatus::from_label("New Product"), Some(LifecycleStatus::Active));
+        assert_eq!(LifecycleStatus::from_label("Unknown"), None);
+    }
+
+    #[test]
+    fn test_lifecycle_risk_prefers_supplier_data() {
+        let obsolete = part("LM2937ET-5.0", Some("Obsolete"));
+        let risk = LifecycleRisk::from_specifications(&obsolete).unwrap();
+        assert_eq!(risk.status, LifecycleStatus::Obsolete);
+        assert_eq!(risk.risk_score, 1.0);
+        assert!(risk.reason.contains("\"Obsolete\" (Part Status)"));
+        assert!(LifecycleRisk::from_specifications(&part("LM1117", None)).is_none());
+
+        let (risk, named) = LifecycleRisk::from_response(
+            r#"{"status": "NotRecommendedForNewDesign", "risk_score": 1.4, "reason": "NRND since 2021", "alternatives": ["ap2112k-3.3", " "]}"#,
+        )
+        .unwrap();
+        assert_eq!(risk.status, LifecycleStatus::NotRecommendedForNewDesign);
+        assert_eq!(risk.risk_score, 1.0);
+        assert!(LifecycleRisk::from_response(r#"{"status": "Fine"}"#).is_err());
+
+        let database = vec![
+            part("LM2937ET-5.0", Some("Obsolete")),
+            part("TLV1117", Some("Obsolete")),
+            part("LM1117", Some("Active")),
+            part("AP2112K-3.3", None),
+        ];
+        let alternatives = lifecycle_alternatives(&obsolete, &database, &named);
+        let part_numbers: Vec<&str> = alternatives.iter().map(|a| a.part_number.as_str()).collect();
+        assert_eq!(part_numbers, ["AP2112K-3.3", "LM1117"]);
+    }
+
+    #[test]
+    fn test_first_sentences() {
+        let text = "The 10k value suits 3.3V. It dissipates 0.25W!\nIs that enough? Check the rise time. Tolerance matters. Extra.";
+        assert_eq!(
+            first_sentences(text, 5),
+            "The 10k value suits 3.3V. It dissipates 0.25W! Is that enough? Check the rise time. Tolerance matters."
+        );
+        assert_eq!(first_sentences("  No terminator  ", 5), "No terminator");
+    }
+
     #[test]
     fn test_cost_category_determination() {
         // Test cost category logic
@@ -0,0 +1,115 @@
+//! Compact summaries of live circuit and board state for AI prompts
+
+use opencircuit_circuit::Circuit;
+use opencircuit_pcb::PcbDesign;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Rough characters-per-token ratio used to budget context
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Summarise component types, components, nets and board dimensions
+pub fn summarize(circuit: Option<&Circuit>, pcb: Option<&PcbDesign>) -> String {
+    let mut lines = Vec::new();
+
+    if let Some(circuit) = circuit {
+        let mut types: BTreeMap<String, usize> = BTreeMap::new();
+        for component in &circuit.components {
+            *types.entry(format!("{:?}", component.component_type)).or_default() += 1;
+        }
+        let types: Vec<String> = types.iter().map(|(name, count)| format!("{} x{}", name, count)).collect();
+        lines.push(format!("Components: {} ({})", circuit.components.len(), types.join(", ")));
+
+        let nets: BTreeSet<&str> = circuit.connections.iter().map(|c| c.net_name.as_str()).collect();
+        if !nets.is_empty() {
+            lines.push(format!("Nets: {}", nets.into_iter().collect::<Vec<_>>().join(", ")));
+        }
+
+        let parts: Vec<String> = circuit
+            .components
+            .iter()
+            .map(|component| match &component.value {
+                Some(value) => format!("{}={}", component.id, value),
+                None => component.id.clone(),
+            })
+            .collect();
+        if !parts.is_empty() {
+            lines.push(format!("Parts: {}", parts.join(", ")));
+        }
+    }
+
+    if let Some(pcb) = pcb {
+        lines.push(format!(
+            "Board: {} x {} mm, {} layers, {} placements, {} traces",
+            pcb.width,
+            pcb.height,
+            pcb.layer_count,
+            pcb.placements.len(),
+            pcb.traces.len()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Rough token count of `text`, rounded up
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Cut `text` to roughly `max_tokens`, marking the cut with "..."
+pub fn truncate_to_tokens(text: &str, max_tokens: usize) -> String {
+    let max_chars = max_tokens * CHARS_PER_TOKEN;
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType, Connection};
+
+    fn circuit() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type, value) in [
+            ("R1", ComponentType::Resistor, Some("10k")),
+            ("R2", ComponentType::Resistor, Some("4.7k")),
+            ("V1", ComponentType::VoltageSource, None),
+        ] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type,
+                value: value.map(str::to_string),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        for (from, to, net) in [("V1", "R1", "VIN"), ("R1", "R2", "VOUT"), ("R2", "V1", "GND"), ("V1", "R2", "GND")] {
+            circuit.add_connection(Connection { from: from.to_string(), to: to.to_string(), net_name: net.to_string() });
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_summarize_circuit_and_board() {
+        let summary = summarize(Some(&circuit()), Some(&PcbDesign::new(50.0, 30.0, 2)));
+        assert_eq!(
+            summary,
+            "Components: 3 (Resistor x2, VoltageSource x1)\n\
+             Nets: GND, VIN, VOUT\n\
+             Parts: R1=10k, R2=4.7k, V1\n\
+             Board: 50 x 30 mm, 2 layers, 0 placements, 0 traces"
+        );
+        assert!(summarize(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_truncate_to_tokens() {
+        assert_eq!(truncate_to_tokens("short", 10), "short");
+        let truncated = truncate_to_tokens(&"x".repeat(100), 5);
+        assert_eq!(truncated.len(), 20);
+        assert!(truncated.ends_with("..."));
+    }
+}
@@ -0,0 +1,241 @@
+//! Decoupling capacitor plans for power nets
+//!
+//! The LLM is given bypassing guidelines drawn from Texas Instruments
+//! application notes, the circuit's power nets and the components on each,
+//! and proposes capacitors per IC power pin as JSON. Suggestions for nets or
+//! components outside the circuit are dropped, and any IC on a power net the
+//! model skipped gets the standard 100 nF + 10 µF pair.
+
+use opencircuit_circuit::{Circuit, ComponentType};
+use opencircuit_core::OpenCircuitError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::health_report::extract_json;
+use crate::AiResult;
+
+/// Bypassing guidelines sent ahead of every decoupling request
+pub const DECOUPLING_SYSTEM_PROMPT: &str = "You are a power integrity engineer planning decoupling capacitors. \
+Follow these bypassing guidelines from Texas Instruments application notes \
+(SZZA009 PCB Design Guidelines for Reduced EMI, SLOA069 How (Not) to Decouple High-Speed Operational Amplifiers, \
+SCAA082 High-Speed Layout Guidelines):\n\
+- Give every IC power pin a 100 nF X7R ceramic capacitor, placed as close to the pin as possible, ideally within 2 mm.\n\
+- Add a 10 uF bulk capacitor (X5R/X7R ceramic or tantalum) per IC or per rail near the ICs it serves.\n\
+- Put the smallest capacitor closest to the pin; current should flow through the capacitor pad before reaching the pin.\n\
+- Connect capacitor ground pads straight to the ground plane with short, wide traces or multiple vias; \
+loop inductance matters more than capacitance.\n\
+- Rate ceramic capacitors for at least twice the rail voltage, since DC bias reduces their effective capacitance.";
+
+/// Capacitor suggested for one power pin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecouplingRecommendation {
+    pub net_name: String,
+    /// Component whose power pin the capacitor bypasses
+    pub near_component_id: String,
+    /// Value as written on a schematic, e.g. "100nF"
+    pub capacitance_value: String,
+    /// Dielectric or technology, e.g. "X7R ceramic"
+    pub capacitor_type: String,
+    pub placement_notes: String,
+}
+
+/// Decoupling capacitors for a circuit's power nets
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecouplingPlan {
+    pub recommendations: Vec<DecouplingRecommendation>,
+}
+
+impl DecouplingPlan {
+    /// Parse the model's plan, keeping nets from `power_nets` and components in `circuit`
+    ///
+    /// ICs on a power net with no suggestion get the default 100 nF + 10 µF pair.
+    pub fn from_response(response: &str, circuit: &Circuit, power_nets: &[(&str, f64)]) -> AiResult<Self> {
+        let value: Value = extract_json(response)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .ok_or_else(|| OpenCircuitError::AiService("Decoupling plan contained no JSON object".to_string()))?;
+        let entries = value
+            .get("recommendations")
+            .and_then(Value::as_array)
+            .ok_or_else(|| OpenCircuitError::AiService("Decoupling plan contained no recommendations list".to_string()))?;
+
+        let mut recommendations: Vec<DecouplingRecommendation> = entries
+            .iter()
+            .filter_map(|entry| {
+                let (net, _) = power_nets.iter().find(|(net, _)| net.eq_ignore_ascii_case(text(entry, "net")))?;
+                let component = circuit
+                    .components
+                    .iter()
+                    .find(|component| component.id.eq_ignore_ascii_case(text(entry, "near_component")))?;
+                let capacitance = text(entry, "capacitance");
+                if capacitance.is_empty() {
+                    return None;
+                }
+                Some(DecouplingRecommendation {
+                    net_name: net.to_string(),
+                    near_component_id: component.id.clone(),
+                    capacitance_value: capacitance.to_string(),
+                    capacitor_type: text(entry, "type").to_string(),
+                    placement_notes: text(entry, "placement").to_string(),
+                })
+            })
+            .collect();
+
+        for &(net, voltage) in power_nets {
+            for ic in integrated_circuits_on(circuit, net) {
+                let covered = recommendations
+                    .iter()
+                    .any(|recommendation| recommendation.net_name == net && recommendation.near_component_id == ic);
+                if !covered {
+                    recommendations.extend(default_pair(net, voltage, ic));
+                }
+            }
+        }
+
+        Ok(Self { recommendations })
+    }
+}
+
+/// Build the decoupling prompt, led by [`DECOUPLING_SYSTEM_PROMPT`]
+pub fn decoupling_prompt(circuit: &Circuit, power_nets: &[(&str, f64)]) -> String {
+    let mut prompt = format!("{}\n\nComponents:\n", DECOUPLING_SYSTEM_PROMPT);
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or("(no value)")
+        ));
+    }
+    prompt.push_str("\nPower nets:\n");
+    for &(net, voltage) in power_nets {
+        prompt.push_str(&format!("- {} at {} V, connected to {}\n", net, voltage, net_components(circuit, net).join(", ")));
+    }
+
+    prompt.push_str(
+        "\nPlan decoupling for every IC power pin on these nets, using 100 nF + 10 uF combinations. \
+         Refer to nets and components by the names above. Respond with JSON only:\n\
+         {\"recommendations\": [{\"net\": \"...\", \"near_component\": \"U1\", \"capacitance\": \"100nF\", \
+         \"type\": \"X7R ceramic\", \"placement\": \"...\"}]}",
+    );
+    prompt
+}
+
+/// Reject power nets that are not in the circuit
+pub fn validate_power_nets(circuit: &Circuit, power_nets: &[(&str, f64)]) -> AiResult<()> {
+    if power_nets.is_empty() {
+        return Err(OpenCircuitError::Circuit("No power nets given for decoupling".to_string()));
+    }
+    match power_nets.iter().find(|(net, _)| net_components(circuit, net).is_empty()) {
+        Some((net, _)) => Err(OpenCircuitError::Circuit(format!("Power net {} is not in the circuit", net))),
+        None => Ok(()),
+    }
+}
+
+/// Trimmed string field of a recommendation, empty if missing
+fn text<'a>(entry: &'a Value, key: &str) -> &'a str {
+    entry.get(key).and_then(Value::as_str).map(str::trim).unwrap_or_default()
+}
+
+/// Standard 100 nF + 10 µF pair for an IC the model did not cover
+fn default_pair(net: &str, voltage: f64, ic: &str) -> [DecouplingRecommendation; 2] {
+    let rating = format!("rated {} V or more", (voltage.abs() * 2.0).ceil());
+    let recommendation = |value: &str, kind: &str, placement: String| DecouplingRecommendation {
+        net_name: net.to_string(),
+        near_component_id: ic.to_string(),
+        capacitance_value: value.to_string(),
+        capacitor_type: kind.to_string(),
+        placement_notes: placement,
+    };
+    [
+        recommendation(
+            "100nF",
+            "X7R ceramic",
+            format!("Within 2 mm of the {} pin on {}, {}, with a via straight to ground", ic, net, rating),
+        ),
+        recommendation("10uF", "X5R ceramic", format!("Near {} on {}, {}", ic, net, rating)),
+    ]
+}
+
+/// Integrated circuits on `net`; op amps are the circuit model's only IC type
+fn integrated_circuits_on<'a>(circuit: &'a Circuit, net: &str) -> Vec<&'a str> {
+    net_components(circuit, net)
+        .into_iter()
+        .filter(|id| {
+            circuit
+                .components
+                .iter()
+                .any(|component| component.id == *id && component.component_type == ComponentType::OpAmp)
+        })
+        .collect()
+}
+
+/// Components connected to `net`, in connection order
+fn net_components<'a>(circuit: &'a Circuit, net: &str) -> Vec<&'a str> {
+    let mut ids: Vec<&str> = Vec::new();
+    for connection in circuit.connections.iter().filter(|connection| connection.net_name == net) {
+        for id in [connection.from.as_str(), connection.to.as_str()] {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, Connection};
+
+    fn circuit() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type) in [("U1", ComponentType::OpAmp), ("U2", ComponentType::OpAmp), ("R1", ComponentType::Resistor)] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type,
+                value: None,
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        for (from, to) in [("U1", "U2"), ("U2", "R1")] {
+            circuit.add_connection(Connection { from: from.to_string(), to: to.to_string(), net_name: "VCC".to_string() });
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_prompt_and_validation() {
+        let circuit = circuit();
+        let prompt = decoupling_prompt(&circuit, &[("VCC", 3.3)]);
+        assert!(prompt.starts_with(DECOUPLING_SYSTEM_PROMPT));
+        assert!(prompt.contains("- VCC at 3.3 V, connected to U1, U2, R1\n"));
+
+        assert!(validate_power_nets(&circuit, &[("VCC", 3.3)]).is_ok());
+        assert!(validate_power_nets(&circuit, &[]).is_err());
+        assert!(validate_power_nets(&circuit, &[("VDD", 1.8)]).is_err());
+    }
+
+    #[test]
+    fn test_parse_fills_uncovered_ics() {
+        let circuit = circuit();
+        let response = r#"{"recommendations": [
+            {"net": "vcc", "near_component": "u1", "capacitance": "100nF", "type": "X7R ceramic", "placement": "Next to pin 8"},
+            {"net": "VDD", "near_component": "U2", "capacitance": "100nF"},
+            {"net": "VCC", "near_component": "C9", "capacitance": "10uF"}
+        ]}"#;
+
+        let plan = DecouplingPlan::from_response(response, &circuit, &[("VCC", 3.3)]).unwrap();
+        assert_eq!(plan.recommendations.len(), 3);
+        assert_eq!(plan.recommendations[0].near_component_id, "U1");
+        assert_eq!(plan.recommendations[0].net_name, "VCC");
+        assert_eq!(plan.recommendations[0].placement_notes, "Next to pin 8");
+
+        let defaults: Vec<_> =
+            plan.recommendations[1..].iter().map(|r| (r.near_component_id.as_str(), r.capacitance_value.as_str())).collect();
+        assert_eq!(defaults, [("U2", "100nF"), ("U2", "10uF")]);
+        assert!(plan.recommendations[1].placement_notes.contains("rated 7 V or more"));
+
+        assert!(DecouplingPlan::from_response("No plan.", &circuit, &[("VCC", 3.3)]).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! Prompts for explaining DRC violations in plain language
+
+use opencircuit_pcb::{DrcViolation, PcbDesign, Severity};
+
+/// Cache key for a violation; explanations depend on the rule, not the location
+pub fn cache_key(violation: &DrcViolation) -> (String, Severity) {
+    (violation.rule_name.clone(), violation.severity.clone())
+}
+
+/// Build the LLM prompt for a DRC violation
+pub fn explanation_prompt(violation: &DrcViolation, design_context: Option<&PcbDesign>) -> String {
+    let mut prompt = format!(
+        "A PCB design rule check reported this violation:\n\
+         Rule: {}\n\
+         Severity: {:?}\n\
+         Details: {}\n\
+         Location: ({:.3} mm, {:.3} mm)\n",
+        violation.rule_name,
+        violation.severity,
+        violation.description,
+        violation.location.0,
+        violation.location.1
+    );
+
+    if let Some(design) = design_context {
+        prompt.push_str(&format!(
+            "\nBoard: {} x {} mm, {} copper layers, {} components, {} traces, {} vias\n",
+            design.width,
+            design.height,
+            design.layer_count,
+            design.placements.len(),
+            design.traces.len(),
+            design.vias.len()
+        ));
+    }
+
+    prompt.push_str(
+        "\nExplain this for someone new to PCB design, without jargon:\n\
+         1. What causes this kind of violation\n\
+         2. Why it matters for manufacturing\n\
+         3. The recommended fix",
+    );
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(description: &str) -> DrcViolation {
+        DrcViolation {
+            rule_name: "annular_ring".to_string(),
+            description: description.to_string(),
+            location: (12.5, 3.0),
+            severity: Severity::Error,
+        }
+    }
+
+    #[test]
+    fn test_explanation_prompt() {
+        let prompt = explanation_prompt(&violation("annular ring 0.05 mm < 0.10 mm minimum"), None);
+        assert!(prompt.contains("Rule: annular_ring"));
+        assert!(prompt.contains("Severity: Error"));
+        assert!(prompt.contains("annular ring 0.05 mm < 0.10 mm minimum"));
+        assert!(prompt.contains("Why it matters for manufacturing"));
+        assert!(!prompt.contains("Board:"));
+
+        let design = PcbDesign::new(50.0, 30.0, 4);
+        let prompt = explanation_prompt(&violation("ring"), Some(&design));
+        assert!(prompt.contains("Board: 50 x 30 mm, 4 copper layers"));
+    }
+
+    #[test]
+    fn test_cache_key_ignores_location() {
+        let mut other = violation("annular ring 0.02 mm < 0.10 mm minimum");
+        other.location = (0.0, 0.0);
+        assert_eq!(cache_key(&violation("ring")), cache_key(&other));
+    }
+}
@@ -76,11 +76,13 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use opencircuit_core::{
     models::{Component, ComponentCategory},
     OpenCircuitError,
 };
 
+use crate::hnsw::HnswIndex;
 use crate::ollama_client::OpenCircuitOllamaClient;
 
 type Result<T> = std::result::Result<T, OpenCircuitError>;
@@ -120,6 +122,15 @@ pub struct EmbeddingMetadata {
     pub dimension: usize,
 }
 
+/// Serialized embedding index, as written by [`ComponentEmbeddingEngine::persist_index`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EmbeddingIndex {
+    /// Model that produced every embedding in the index
+    model: String,
+    /// Embeddings sorted by component ID
+    embeddings: Vec<ComponentEmbedding>,
+}
+
 /// Similarity search result
 ///
 /// Represents a component that matched against a query, along with
@@ -185,6 +196,10 @@ pub struct ComponentEmbeddingEngine {
     embeddings_cache: HashMap<String, ComponentEmbedding>,
     /// Model used for embeddings
     embedding_model: String,
+    /// File kept in sync by `update_index` and `remove_from_index`
+    index_path: Option<PathBuf>,
+    /// Approximate nearest-neighbour index, when enabled
+    hnsw_index: Option<HnswIndex>,
 }
 
 impl ComponentEmbeddingEngine {
@@ -218,6 +233,8 @@ impl ComponentEmbeddingEngine {
             ollama_client,
             embeddings_cache: HashMap::new(),
             embedding_model: "nomic-embed-text".to_string(), // Good embedding model
+            index_path: None,
+            hnsw_index: None,
         })
     }
 
@@ -289,6 +306,9 @@ impl ComponentEmbeddingEngine {
 
         // Cache the embedding
         self.embeddings_cache.insert(component.id.clone(), embedding.clone());
+        if let Some(index) = &mut self.hnsw_index {
+            index.insert(&component.id, &embedding.vector);
+        }
 
         Ok(embedding)
     }
@@ -346,6 +366,12 @@ impl ComponentEmbeddingEngine {
     /// Only components with similarity scores above 0.3 are included in results.
     /// This threshold can be adjusted in future versions.
     ///
+    /// # Performance
+    ///
+    /// When an HNSW index is enabled (see [`enable_hnsw_index()`]) and covers
+    /// exactly the given components, the search is approximate and sub-linear.
+    /// Otherwise every component is scored.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -384,6 +410,21 @@ impl ComponentEmbeddingEngine {
         // Generate embedding for requirements
         let requirements_embedding = self.text_to_embedding(requirements).await?;
 
+        if let Some(nearest) = self.approximate_nearest(&requirements_embedding, components, max_results) {
+            let mut matches = Vec::new();
+            for (component, similarity) in nearest {
+                if similarity > 0.3 {
+                    let match_reason = self.generate_match_reason(component, similarity).await?;
+                    matches.push(SimilarityMatch {
+                        component: component.clone(),
+                        similarity,
+                        match_reason,
+                    });
+                }
+            }
+            return Ok(matches);
+        }
+
         let mut matches = Vec::new();
 
         // Generate embeddings for all components and calculate similarity
@@ -409,6 +450,28 @@ impl ComponentEmbeddingEngine {
         Ok(matches)
     }
 
+    /// Query the HNSW index when it indexes exactly `components`
+    fn approximate_nearest<'a>(
+        &self,
+        query: &[f32],
+        components: &'a [Component],
+        max_results: usize,
+    ) -> Option<Vec<(&'a Component, f32)>> {
+        let index = self.hnsw_index.as_ref()?;
+        if index.len() != components.len() || !components.iter().all(|c| index.contains(&c.id)) {
+            return None;
+        }
+
+        let by_id: HashMap<&str, &Component> = components.iter().map(|c| (c.id.as_str(), c)).collect();
+        Some(
+            index
+                .search(query, max_results)
+                .into_iter()
+                .filter_map(|(id, similarity)| Some((*by_id.get(id.as_str())?, similarity)))
+                .collect(),
+        )
+    }
+
     /// Find components by category with semantic search
     ///
     /// Combines category filtering with semantic search to find components
@@ -474,6 +537,88 @@ impl ComponentEmbeddingEngine {
         self.find_similar_components_by_requirements(requirements, &owned_components, max_results).await
     }
 
+    /// Find substitutes across several categories with one ranked list
+    ///
+    /// Useful when different kinds of part can meet the same requirement,
+    /// e.g. a TVS diode, a varistor or a gas-discharge tube for transient
+    /// protection. Categories are given in order of preference: matches in
+    /// the first category have `category_match_bonus` added to their ranking
+    /// score, and the bonus falls off linearly to nothing for the last.
+    ///
+    /// # Arguments
+    ///
+    /// * `requirements` - Natural language description of requirements
+    /// * `candidate_categories` - Categories to search, most preferred first
+    /// * `components` - List of components to search through
+    /// * `max_results` - Maximum number of results to return
+    /// * `category_match_bonus` - Score added to matches in the most preferred category
+    ///
+    /// # Returns
+    ///
+    /// Returns [`SimilarityMatch`] results ranked by similarity plus category
+    /// bonus. The reported `similarity` does not include the bonus.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use opencircuit_ai::embeddings::ComponentEmbeddingEngine;
+    /// # use opencircuit_core::models::{Component, ComponentCategory};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = OpenCircuitOllamaClient::new();
+    /// # let mut engine = ComponentEmbeddingEngine::new(client).await?;
+    /// # let components: Vec<Component> = Vec::new();
+    ///
+    /// let matches = engine
+    ///     .cross_category_search(
+    ///         "transient protection for 24V line",
+    ///         &[ComponentCategory::Diodes, ComponentCategory::Resistors],
+    ///         &components,
+    ///         5,
+    ///         0.1,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn cross_category_search(
+        &mut self,
+        requirements: &str,
+        candidate_categories: &[ComponentCategory],
+        components: &[Component],
+        max_results: usize,
+        category_match_bonus: f32,
+    ) -> Result<Vec<SimilarityMatch>> {
+        let requirements_embedding = self.text_to_embedding(requirements).await?;
+        let preference_steps = candidate_categories.len().max(1) as f32;
+
+        let mut ranked: Vec<(&Component, f32, f32)> = Vec::new();
+        for component in components {
+            let Some(rank) = candidate_categories.iter().position(|category| *category == component.category) else {
+                continue;
+            };
+            let component_embedding = self.generate_component_embedding(component).await?;
+            let similarity = self.cosine_similarity(&requirements_embedding, &component_embedding.vector);
+            if similarity > 0.3 {
+                let bonus = category_match_bonus * (preference_steps - rank as f32 - 1.0) / (preference_steps - 1.0).max(1.0);
+                ranked.push((component, similarity, similarity + bonus));
+            }
+        }
+
+        ranked.sort_by(|a, b| b.2.total_cmp(&a.2));
+        ranked.truncate(max_results);
+
+        let mut matches = Vec::with_capacity(ranked.len());
+        for (component, similarity, _) in ranked {
+            let match_reason = self.generate_match_reason(component, similarity).await?;
+            matches.push(SimilarityMatch {
+                component: component.clone(),
+                similarity,
+                match_reason,
+            });
+        }
+        Ok(matches)
+    }
+
     /// Convert component to text representation for embedding
     fn component_to_text(&self, component: &Component) -> String {
         let mut text_parts = vec![
@@ -615,6 +760,9 @@ impl ComponentEmbeddingEngine {
     /// ```
     pub fn clear_cache(&mut self) {
         self.embeddings_cache.clear();
+        if self.hnsw_index.take().is_some() {
+            tracing::info!("HNSW index disabled after clearing the embedding cache");
+        }
     }
 
     /// Get cache statistics
@@ -681,6 +829,136 @@ impl ComponentEmbeddingEngine {
         // Clear cache when model changes
         self.clear_cache();
     }
+
+    /// Save the embedding index to disk
+    ///
+    /// Writes every cached embedding as JSON. The file is written to a `.tmp`
+    /// sibling first and renamed into place, so an interrupted write never
+    /// leaves a truncated index. The path is remembered, and later calls to
+    /// [`update_index()`] and [`remove_from_index()`] keep it up to date.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use opencircuit_ai::embeddings::ComponentEmbeddingEngine;
+    /// # use opencircuit_ai::ollama_client::OpenCircuitOllamaClient;
+    /// # use std::path::Path;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut engine = ComponentEmbeddingEngine::new(OpenCircuitOllamaClient::new()).await?;
+    /// engine.persist_index(Path::new("embeddings.json"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn persist_index(&mut self, path: &Path) -> Result<()> {
+        self.index_path = Some(path.to_path_buf());
+        self.write_index(path)
+    }
+
+    /// Load a previously persisted embedding index
+    ///
+    /// Loaded embeddings are merged into the cache, replacing entries with the
+    /// same component ID. Embeddings produced by a different model than the
+    /// engine's current one are skipped, since their vectors are not comparable.
+    /// Returns the number of embeddings loaded.
+    pub fn load_index(&mut self, path: &Path) -> Result<usize> {
+        let index: EmbeddingIndex = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+
+        let mut loaded = 0;
+        for embedding in index.embeddings {
+            if embedding.metadata.model != self.embedding_model {
+                tracing::warn!(
+                    "Skipping embedding for {} from model {}",
+                    embedding.component_id,
+                    embedding.metadata.model
+                );
+                continue;
+            }
+            if let Some(index) = &mut self.hnsw_index {
+                index.insert(&embedding.component_id, &embedding.vector);
+            }
+            self.embeddings_cache.insert(embedding.component_id.clone(), embedding);
+            loaded += 1;
+        }
+
+        self.index_path = Some(path.to_path_buf());
+        Ok(loaded)
+    }
+
+    /// Re-embed a single component and update the persisted index
+    ///
+    /// Only the given component is sent to the embedding model; the rest of
+    /// the index is rewritten unchanged.
+    pub async fn update_index(&mut self, component: &Component) -> Result<ComponentEmbedding> {
+        self.embeddings_cache.remove(&component.id);
+        let embedding = self.generate_component_embedding(component).await?;
+        self.sync_index()?;
+        Ok(embedding)
+    }
+
+    /// Drop a deleted component from the index
+    ///
+    /// Returns `true` if the component had an embedding.
+    pub fn remove_from_index(&mut self, component_id: &str) -> Result<bool> {
+        let removed = self.embeddings_cache.remove(component_id).is_some();
+        if let Some(index) = &mut self.hnsw_index {
+            index.remove(component_id);
+        }
+        if removed {
+            self.sync_index()?;
+        }
+        Ok(removed)
+    }
+
+    /// Build an HNSW index over every cached embedding
+    ///
+    /// Construction is `O(n log n)`; queries against the result visit only a
+    /// small fraction of the embeddings. The engine's own searches use the
+    /// index once it is installed with [`enable_hnsw_index()`].
+    pub fn build_hnsw_index(&self) -> HnswIndex {
+        let mut ids: Vec<&String> = self.embeddings_cache.keys().collect();
+        ids.sort();
+
+        let mut index = HnswIndex::default();
+        for id in ids {
+            index.insert(id, &self.embeddings_cache[id].vector);
+        }
+        index
+    }
+
+    /// Build and use an HNSW index for similarity searches
+    ///
+    /// The index is kept up to date as embeddings are generated, updated and
+    /// removed, and dropped when the cache is cleared.
+    pub fn enable_hnsw_index(&mut self) {
+        self.hnsw_index = Some(self.build_hnsw_index());
+    }
+
+    /// Go back to exact linear-scan searches
+    pub fn disable_hnsw_index(&mut self) {
+        self.hnsw_index = None;
+    }
+
+    /// Rewrite the remembered index file, if any
+    fn sync_index(&self) -> Result<()> {
+        match &self.index_path {
+            Some(path) => self.write_index(path),
+            None => Ok(()),
+        }
+    }
+
+    fn write_index(&self, path: &Path) -> Result<()> {
+        let mut embeddings: Vec<ComponentEmbedding> = self.embeddings_cache.values().cloned().collect();
+        embeddings.sort_by(|a, b| a.component_id.cmp(&b.component_id));
+        let index = EmbeddingIndex { model: self.embedding_model.clone(), embeddings };
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        std::fs::write(&tmp_path, serde_json::to_vec(&index)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
 }
 
 /// Utility functions for embedding operations
@@ -951,4 +1229,133 @@ mod tests {
         assert_eq!(hash1, hash2);
         assert_ne!(hash1, hash3);
     }
-}
\ No newline at end of file
+
+    fn cached_embedding(component_id: &str, model: &str) -> ComponentEmbedding {
+        ComponentEmbedding {
+            component_id: component_id.to_string(),
+            vector: vec![0.5, 0.25, 0.125],
+            metadata: EmbeddingMetadata {
+                category: ComponentCategory::Resistors,
+                key_specs: vec!["Resistance".to_string()],
+                model: model.to_string(),
+                dimension: 3,
+            },
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn index_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("opencircuit-{}-{}.json", name, std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_load_index() {
+        let path = index_path("embeddings");
+        let mut engine = ComponentEmbeddingEngine::new(OpenCircuitOllamaClient::new()).await.unwrap();
+        for id in ["R1", "R2"] {
+            engine.embeddings_cache.insert(id.to_string(), cached_embedding(id, "nomic-embed-text"));
+        }
+        engine.embeddings_cache.insert("C1".to_string(), cached_embedding("C1", "other-model"));
+        engine.persist_index(&path).unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+
+        let mut restored = ComponentEmbeddingEngine::new(OpenCircuitOllamaClient::new()).await.unwrap();
+        assert_eq!(restored.load_index(&path).unwrap(), 2);
+        assert_eq!(restored.embeddings_cache["R2"].vector, vec![0.5, 0.25, 0.125]);
+        assert!(!restored.embeddings_cache.contains_key("C1"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_hnsw_index_tracks_cache() {
+        let mut engine = ComponentEmbeddingEngine::new(OpenCircuitOllamaClient::new()).await.unwrap();
+        let components: Vec<Component> = ["R1", "R2", "C1"]
+            .iter()
+            .map(|part| {
+                Component::new(part.to_string(), "TestCorp".to_string(), ComponentCategory::Resistors, format!("{} part", part))
+            })
+            .collect();
+        for component in &components {
+            engine.generate_component_embedding(component).await.unwrap();
+        }
+
+        engine.enable_hnsw_index();
+        let query = engine.embeddings_cache[&components[1].id].vector.clone();
+        let nearest = engine.approximate_nearest(&query, &components, 1).unwrap();
+        assert_eq!(nearest[0].0.id, components[1].id);
+        assert!(engine.approximate_nearest(&query, &components[..2], 1).is_none());
+
+        engine.remove_from_index(&components[0].id).unwrap();
+        assert!(engine.approximate_nearest(&query, &components[1..], 1).is_some());
+
+        engine.clear_cache();
+        assert!(engine.approximate_nearest(&query, &components[1..], 1).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_remove_from_index_rewrites_file() {
+        let path = index_path("remove");
+        let mut engine = ComponentEmbeddingEngine::new(OpenCircuitOllamaClient::new()).await.unwrap();
+        for id in ["R1", "R2"] {
+            engine.embeddings_cache.insert(id.to_string(), cached_embedding(id, "nomic-embed-text"));
+        }
+        engine.persist_index(&path).unwrap();
+
+        assert!(engine.remove_from_index("R1").unwrap());
+        assert!(!engine.remove_from_index("R1").unwrap());
+
+        let mut restored = ComponentEmbeddingEngine::new(OpenCircuitOllamaClient::new()).await.unwrap();
+        assert_eq!(restored.load_index(&path).unwrap(), 1);
+        assert!(restored.embeddings_cache.contains_key("R2"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cross_category_search() {
+        let mut engine = ComponentEmbeddingEngine::new(OpenCircuitOllamaClient::new()).await.unwrap();
+        let requirements = "transient protection for 24V line";
+        let query = engine.text_to_embedding(requirements).await.unwrap();
+        let orthogonal: Vec<f32> = {
+            let free = query.iter().position(|value| *value == 0.0).unwrap();
+            (0..query.len()).map(|index| if index == free { 1.0 } else { 0.0 }).collect()
+        };
+        let blend = |weight: f32| -> Vec<f32> {
+            query.iter().zip(&orthogonal).map(|(q, o)| weight * q + (1.0 - weight * weight).sqrt() * o).collect()
+        };
+
+        let parts = [
+            ("SMBJ24A", ComponentCategory::Diodes, blend(0.95)),
+            ("V33ZA1P", ComponentCategory::Resistors, blend(0.8)),
+            ("2038-23-SM", ComponentCategory::Custom("Circuit Protection".to_string()), blend(1.0)),
+            ("CRCW060310K0", ComponentCategory::Resistors, orthogonal.clone()),
+            ("GRM188R71H104", ComponentCategory::Capacitors, blend(1.0)),
+        ];
+        let components: Vec<Component> = parts
+            .iter()
+            .map(|(part, category, vector)| {
+                let component = Component::new(part.to_string(), "TestCorp".to_string(), category.clone(), String::new());
+                let mut embedding = cached_embedding(&component.id, "nomic-embed-text");
+                embedding.vector = vector.clone();
+                engine.embeddings_cache.insert(component.id.clone(), embedding);
+                component
+            })
+            .collect();
+        let categories = [
+            ComponentCategory::Diodes,
+            ComponentCategory::Resistors,
+            ComponentCategory::Custom("Circuit Protection".to_string()),
+        ];
+        let ranking = |matches: Vec<SimilarityMatch>| -> Vec<String> {
+            matches.into_iter().map(|m| m.component.part_number).collect()
+        };
+
+        let unboosted = engine.cross_category_search(requirements, &categories, &components, 5, 0.0).await.unwrap();
+        assert_eq!(ranking(unboosted), ["2038-23-SM", "SMBJ24A", "V33ZA1P"]);
+
+        let boosted = engine.cross_category_search(requirements, &categories, &components, 2, 0.1).await.unwrap();
+        assert!((boosted[0].similarity - 0.95).abs() < 1e-4);
+        assert_eq!(ranking(boosted), ["SMBJ24A", "2038-23-SM"]);
+    }
+}
@@ -0,0 +1,269 @@
+//! TVS diode recommendations for circuit I/O pins
+//!
+//! Each I/O net is described to the LLM with its interface type and ratings,
+//! alongside the IEC 61000-4-2 test levels, and the model names a TVS diode
+//! and its clamping voltage per net. Nets the circuit does not contain are
+//! rejected before prompting, and answers for nets that were not asked about
+//! are dropped.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::models::Component;
+use opencircuit_core::OpenCircuitError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::health_report::extract_json;
+use crate::AiResult;
+
+/// IEC 61000-4-2 test levels: (level, contact discharge kV, air discharge kV)
+pub const IEC_61000_4_2_LEVELS: [(u8, f64, f64); 4] = [(1, 2.0, 2.0), (2, 4.0, 4.0), (3, 6.0, 8.0), (4, 8.0, 15.0)];
+
+/// Kind of external interface an I/O pin belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InterfaceType {
+    Usb,
+    Uart,
+    I2C,
+    Spi,
+    Analog,
+    Gpio,
+}
+
+impl InterfaceType {
+    /// What matters when protecting this interface
+    fn guidance(&self) -> &'static str {
+        match self {
+            InterfaceType::Usb => "high-speed data, needs under 1 pF line capacitance and low-inductance routing",
+            InterfaceType::Uart => "low-speed serial, tolerates tens of pF",
+            InterfaceType::I2C => "open-drain bus with pull-ups, keep capacitance within the 400 pF bus budget",
+            InterfaceType::Spi => "clocked data up to tens of MHz, keep capacitance under about 10 pF",
+            InterfaceType::Analog => "low leakage matters more than speed to avoid offset errors",
+            InterfaceType::Gpio => "general-purpose digital, capacitance is rarely critical",
+        }
+    }
+}
+
+/// An I/O net that leaves the board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IoPinSpec {
+    pub net_name: String,
+    pub interface_type: InterfaceType,
+    /// Highest normal working voltage on the net (V)
+    pub max_voltage: f64,
+    /// Highest normal signal current on the net (A)
+    pub max_current: f64,
+}
+
+/// TVS protection suggested for one I/O net
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EsdRecommendation {
+    pub net_name: String,
+    pub interface_type: InterfaceType,
+    /// Part number of the TVS diode
+    pub tvs_part_number: String,
+    /// The TVS diode, when it is among the advisor's loaded components
+    pub tvs_component: Option<Component>,
+    /// Clamping voltage at the IEC 61000-4-2 test current (V)
+    pub clamping_voltage: f64,
+    /// IEC 61000-4-2 level the part is rated for, 1 to 4
+    pub iec_level: Option<u8>,
+    pub placement: String,
+    /// Problems spotted in the suggestion, e.g. clamping below the working voltage
+    pub warnings: Vec<String>,
+}
+
+/// Build the ESD protection prompt
+pub fn esd_prompt(circuit: &Circuit, io_pins: &[IoPinSpec]) -> String {
+    let mut prompt = String::from(
+        "Recommend ESD protection for the I/O nets of this circuit to meet IEC 61000-4-2.\n\nIEC 61000-4-2 levels:\n",
+    );
+    for (level, contact, air) in IEC_61000_4_2_LEVELS {
+        prompt.push_str(&format!("- Level {}: {} kV contact, {} kV air discharge\n", level, contact, air));
+    }
+
+    prompt.push_str("\nComponents:\n");
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or("(no value)")
+        ));
+    }
+    prompt.push_str("\nI/O nets:\n");
+    for pin in io_pins {
+        prompt.push_str(&format!(
+            "- {} ({:?}: {}), up to {} V and {} A, connected to {}\n",
+            pin.net_name,
+            pin.interface_type,
+            pin.interface_type.guidance(),
+            pin.max_voltage,
+            pin.max_current,
+            net_components(circuit, &pin.net_name).join(", ")
+        ));
+    }
+
+    prompt.push_str(
+        "\nFor each I/O net name a TVS diode whose working voltage is at or above the net's maximum, \
+         its clamping voltage, the highest IEC 61000-4-2 level it meets and where to place it. \
+         Place protection as near to the connector as possible, ahead of any other component on the net. \
+         Respond with JSON only:\n\
+         {\"recommendations\": [{\"net\": \"...\", \"tvs_diode\": \"part number\", \"clamping_voltage\": 0.0, \
+         \"iec_level\": 4, \"placement\": \"...\"}]}",
+    );
+    prompt
+}
+
+/// Parse the model's recommendations, one per I/O net it covered
+///
+/// The TVS component is matched by part number against `components`, e.g.
+/// the advisor's loaded database.
+pub fn parse_esd_recommendations(
+    response: &str,
+    io_pins: &[IoPinSpec],
+    components: &[Component],
+) -> AiResult<Vec<EsdRecommendation>> {
+    let value: Value = extract_json(response)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .ok_or_else(|| OpenCircuitError::AiService("ESD recommendations contained no JSON object".to_string()))?;
+    let entries = value
+        .get("recommendations")
+        .and_then(Value::as_array)
+        .ok_or_else(|| OpenCircuitError::AiService("ESD response contained no recommendations list".to_string()))?;
+
+    let mut recommendations: Vec<EsdRecommendation> = Vec::new();
+    for entry in entries {
+        let Some(pin) = entry
+            .get("net")
+            .and_then(Value::as_str)
+            .and_then(|net| io_pins.iter().find(|pin| pin.net_name.eq_ignore_ascii_case(net.trim())))
+        else {
+            continue;
+        };
+        let part_number = entry.get("tvs_diode").and_then(Value::as_str).map(str::trim).unwrap_or_default();
+        let clamping_voltage = entry.get("clamping_voltage").and_then(Value::as_f64).unwrap_or_default();
+        if part_number.is_empty()
+            || clamping_voltage <= 0.0
+            || recommendations.iter().any(|recommendation| recommendation.net_name == pin.net_name)
+        {
+            continue;
+        }
+
+        let mut warnings = Vec::new();
+        if clamping_voltage <= pin.max_voltage {
+            warnings.push(format!(
+                "Clamps at {} V, at or below the net's {} V working voltage",
+                clamping_voltage, pin.max_voltage
+            ));
+        }
+        let placement = entry.get("placement").and_then(Value::as_str).map(str::trim).unwrap_or_default();
+        recommendations.push(EsdRecommendation {
+            net_name: pin.net_name.clone(),
+            interface_type: pin.interface_type,
+            tvs_part_number: part_number.to_string(),
+            tvs_component: components
+                .iter()
+                .find(|component| component.part_number.eq_ignore_ascii_case(part_number))
+                .cloned(),
+            clamping_voltage,
+            iec_level: entry
+                .get("iec_level")
+                .and_then(Value::as_u64)
+                .filter(|level| (1..=4).contains(level))
+                .map(|level| level as u8),
+            placement: if placement.is_empty() { default_placement(&pin.net_name) } else { placement.to_string() },
+            warnings,
+        });
+    }
+
+    if recommendations.is_empty() {
+        return Err(OpenCircuitError::AiService("ESD response covered none of the I/O nets".to_string()));
+    }
+    Ok(recommendations)
+}
+
+/// Reject I/O nets that are not in the circuit
+pub fn validate_io_pins(circuit: &Circuit, io_pins: &[IoPinSpec]) -> AiResult<()> {
+    if io_pins.is_empty() {
+        return Err(OpenCircuitError::Circuit("No I/O pins given for ESD protection".to_string()));
+    }
+    match io_pins.iter().find(|pin| net_components(circuit, &pin.net_name).is_empty()) {
+        Some(pin) => Err(OpenCircuitError::Circuit(format!("I/O net {} is not in the circuit", pin.net_name))),
+        None => Ok(()),
+    }
+}
+
+fn default_placement(net_name: &str) -> String {
+    format!(
+        "Place at the connector on {}, ahead of any other component on the net, with a short, wide path to ground",
+        net_name
+    )
+}
+
+/// Components connected to `net`, in circuit order
+fn net_components<'a>(circuit: &'a Circuit, net: &str) -> Vec<&'a str> {
+    let mut ids: Vec<&str> = Vec::new();
+    for connection in circuit.connections.iter().filter(|connection| connection.net_name == net) {
+        for id in [connection.from.as_str(), connection.to.as_str()] {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::Connection;
+
+    fn usb_circuit() -> Circuit {
+        let mut circuit = Circuit::new();
+        circuit.add_connection(Connection { from: "J1".to_string(), to: "U1".to_string(), net_name: "USB_DP".to_string() });
+        circuit.add_connection(Connection { from: "J1".to_string(), to: "U1".to_string(), net_name: "VBUS".to_string() });
+        circuit
+    }
+
+    fn pin(net_name: &str, interface_type: InterfaceType, max_voltage: f64) -> IoPinSpec {
+        IoPinSpec { net_name: net_name.to_string(), interface_type, max_voltage, max_current: 0.01 }
+    }
+
+    #[test]
+    fn test_prompt_and_validation() {
+        let circuit = usb_circuit();
+        let pins = [pin("USB_DP", InterfaceType::Usb, 3.6)];
+        let prompt = esd_prompt(&circuit, &pins);
+        assert!(prompt.contains("- Level 4: 8 kV contact, 15 kV air discharge"));
+        assert!(prompt.contains("- USB_DP (Usb: high-speed data"));
+        assert!(prompt.contains("connected to J1, U1"));
+
+        assert!(validate_io_pins(&circuit, &pins).is_ok());
+        assert!(validate_io_pins(&circuit, &[]).is_err());
+        assert!(validate_io_pins(&circuit, &[pin("SDA", InterfaceType::I2C, 3.3)]).is_err());
+    }
+
+    #[test]
+    fn test_parse_recommendations() {
+        let pins = [pin("USB_DP", InterfaceType::Usb, 3.6), pin("VBUS", InterfaceType::Gpio, 5.5)];
+        let response = r#"Sure: {"recommendations": [
+            {"net": "usb_dp", "tvs_diode": "TPD2E2U06", "clamping_voltage": 10.5, "iec_level": 4, "placement": "Within 5 mm of J1"},
+            {"net": "VBUS", "tvs_diode": "SMF5.0A", "clamping_voltage": 5.0, "iec_level": 9},
+            {"net": "SDA", "tvs_diode": "PESD5V0", "clamping_voltage": 9.0}
+        ]}"#;
+
+        let recommendations = parse_esd_recommendations(response, &pins, &[]).unwrap();
+        assert_eq!(recommendations.len(), 2);
+        assert_eq!(recommendations[0].net_name, "USB_DP");
+        assert_eq!(recommendations[0].tvs_part_number, "TPD2E2U06");
+        assert_eq!(recommendations[0].iec_level, Some(4));
+        assert_eq!(recommendations[0].placement, "Within 5 mm of J1");
+        assert!(recommendations[0].warnings.is_empty());
+
+        assert_eq!(recommendations[1].iec_level, None);
+        assert!(recommendations[1].placement.starts_with("Place at the connector on VBUS"));
+        assert_eq!(recommendations[1].warnings.len(), 1);
+
+        assert!(parse_esd_recommendations(r#"{"recommendations": []}"#, &pins, &[]).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! Structured circuit health scoring
+//!
+//! The LLM is asked for a JSON report. Model output is often slightly off, so
+//! parsing strips code fences and trailing commas, accepts scores as numbers or
+//! strings, clamps them to 0–100 and derives a missing overall score from the
+//! category scores.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+use opencircuit_simulation::{AnalysisData, SimulationResults};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::AiResult;
+
+/// Dashboard-ready circuit health assessment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitHealthReport {
+    pub overall_score: u8,
+    pub power_integrity_score: u8,
+    pub signal_integrity_score: u8,
+    pub component_stress_score: u8,
+    pub recommendations: Vec<String>,
+    pub critical_issues: Vec<String>,
+}
+
+const CATEGORY_SCORES: [&str; 3] = ["power_integrity_score", "signal_integrity_score", "component_stress_score"];
+
+impl CircuitHealthReport {
+    /// Parse the model's JSON report, recovering from common formatting mistakes
+    pub fn from_response(response: &str) -> AiResult<Self> {
+        let json = extract_json(response)
+            .ok_or_else(|| OpenCircuitError::AiService("Health report contained no JSON object".to_string()))?;
+        let value: Value = serde_json::from_str(&json)
+            .map_err(|e| OpenCircuitError::AiService(format!("Invalid health report JSON: {}", e)))?;
+
+        let category: Vec<Option<u8>> = CATEGORY_SCORES.iter().map(|field| score(&value, field)).collect();
+        let known: Vec<u32> = category.iter().flatten().map(|&score| score as u32).collect();
+        let overall = score(&value, "overall_score").or_else(|| {
+            (!known.is_empty()).then(|| (known.iter().sum::<u32>() as f64 / known.len() as f64).round() as u8)
+        });
+        let overall = overall
+            .ok_or_else(|| OpenCircuitError::AiService("Health report contained no scores".to_string()))?;
+
+        Ok(Self {
+            overall_score: overall,
+            power_integrity_score: category[0].unwrap_or(overall),
+            signal_integrity_score: category[1].unwrap_or(overall),
+            component_stress_score: category[2].unwrap_or(overall),
+            recommendations: strings(&value, "recommendations"),
+            critical_issues: strings(&value, "critical_issues"),
+        })
+    }
+}
+
+/// Build the scoring prompt
+pub fn health_prompt(circuit: &Circuit, simulation_results: Option<&SimulationResults>) -> String {
+    let mut prompt = String::from("Assess the health of this circuit.\n\nComponents:\n");
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or("(no value)")
+        ));
+    }
+    prompt.push_str("\nConnections:\n");
+    for connection in &circuit.connections {
+        prompt.push_str(&format!("- {} -> {} on net {}\n", connection.from, connection.to, connection.net_name));
+    }
+
+    if let Some(results) = simulation_results {
+        prompt.push_str(&format!("\nSimulation results:\n{}\n", results.summary()));
+        if let AnalysisData::DC(dc) = &results.data {
+            // Stress is judged from operating-point levels
+            for (label, values, unit) in [
+                ("Node voltages", &dc.node_voltages, "V"),
+                ("Branch currents", &dc.branch_currents, "A"),
+                ("Power dissipation", &dc.power_dissipation, "W"),
+            ] {
+                let mut entries: Vec<String> =
+                    values.iter().map(|(name, value)| format!("{}={:.4e}{}", name, value, unit)).collect();
+                if !entries.is_empty() {
+                    entries.sort();
+                    prompt.push_str(&format!("{}: {}\n", label, entries.join(", ")));
+                }
+            }
+        }
+        if !results.warnings.is_empty() {
+            prompt.push_str(&format!("Simulator warnings: {}\n", results.warnings.join("; ")));
+        }
+    }
+
+    prompt.push_str(
+        "\nScore each area from 0 (unusable) to 100 (excellent) and respond with JSON only, in this schema:\n\
+         {\"overall_score\": 0, \"power_integrity_score\": 0, \"signal_integrity_score\": 0, \
+         \"component_stress_score\": 0, \"recommendations\": [\"...\"], \"critical_issues\": [\"...\"]}",
+    );
+    prompt
+}
+
+/// The outermost JSON object in `response`, without code fences or trailing commas
+pub(crate) fn extract_json(response: &str) -> Option<String> {
+    let start = response.find('{')?;
+    let end = response.rfind('}')?;
+    if end < start {
+        return None;
+    }
+
+    let mut json = String::with_capacity(end - start + 1);
+    let mut in_string = false;
+    let mut escaped = false;
+    let body = &response[start..=end];
+    for (index, character) in body.char_indices() {
+        if in_string {
+            in_string = !(character == '"' && !escaped);
+            escaped = character == '\\' && !escaped;
+        } else if character == '"' {
+            in_string = true;
+        } else if character == ',' {
+            // Drop commas that only precede a closing bracket
+            let next = body[index + 1..].trim_start().chars().next();
+            if matches!(next, Some('}') | Some(']')) {
+                continue;
+            }
+        }
+        json.push(character);
+    }
+    Some(json)
+}
+
+fn score(value: &Value, field: &str) -> Option<u8> {
+    let score = match value.get(field)? {
+        Value::Number(number) => number.as_f64()?,
+        Value::String(text) => text.trim().trim_end_matches('%').trim().parse().ok()?,
+        _ => return None,
+    };
+    Some(score.clamp(0.0, 100.0).round() as u8)
+}
+
+fn strings(value: &Value, field: &str) -> Vec<String> {
+    match value.get(field) {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                Value::String(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+                Value::Null => None,
+                other if !other.is_string() => Some(other.to_string()),
+                _ => None,
+            })
+            .collect(),
+        Some(Value::String(text)) if !text.trim().is_empty() => vec![text.trim().to_string()],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_well_formed_report() {
+        let report = CircuitHealthReport::from_response(
+            r#"{"overall_score": 82, "power_integrity_score": 90, "signal_integrity_score": 75,
+                "component_stress_score": 80, "recommendations": ["Add decoupling on U1"],
+                "critical_issues": []}"#,
+        )
+        .unwrap();
+        assert_eq!(report.overall_score, 82);
+        assert_eq!(report.signal_integrity_score, 75);
+        assert_eq!(report.recommendations, vec!["Add decoupling on U1".to_string()]);
+        assert!(report.critical_issues.is_empty());
+    }
+
+    #[test]
+    fn test_recovers_from_sloppy_output() {
+        let response = "Here is the report:\n```json\n{\n  \"power_integrity_score\": \"70%\",\n  \
+                        \"signal_integrity_score\": 140,\n  \"component_stress_score\": 50.4,\n  \
+                        \"recommendations\": \"Lower R3 dissipation, ok\",\n  \"critical_issues\": [\"Q1 over Vce(max)\",],\n}\n```";
+        let report = CircuitHealthReport::from_response(response).unwrap();
+        assert_eq!(report.power_integrity_score, 70);
+        assert_eq!(report.signal_integrity_score, 100);
+        assert_eq!(report.component_stress_score, 50);
+        assert_eq!(report.overall_score, 73);
+        assert_eq!(report.recommendations, vec!["Lower R3 dissipation, ok".to_string()]);
+        assert_eq!(report.critical_issues, vec!["Q1 over Vce(max)".to_string()]);
+    }
+
+    #[test]
+    fn test_rejects_reports_without_scores() {
+        assert!(CircuitHealthReport::from_response("I cannot assess this circuit.").is_err());
+        assert!(CircuitHealthReport::from_response(r#"{"recommendations": []}"#).is_err());
+    }
+}
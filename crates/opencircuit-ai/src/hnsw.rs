@@ -0,0 +1,396 @@
+//! Hierarchical Navigable Small World index for approximate nearest-neighbour search
+//!
+//! A pure-Rust HNSW graph over cosine similarity, used by
+//! [`ComponentEmbeddingEngine`](crate::embeddings::ComponentEmbeddingEngine) to avoid
+//! scanning every component embedding on each query. Vectors are normalised on
+//! insert so distance is `1 - dot product`. Removed entries are tombstoned: they
+//! keep routing searches through the graph but are never returned.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Default neighbours per node on the upper layers
+pub const DEFAULT_M: usize = 16;
+/// Default candidate list size while building
+pub const DEFAULT_EF_CONSTRUCTION: usize = 100;
+/// Default candidate list size while searching
+pub const DEFAULT_EF_SEARCH: usize = 64;
+
+/// Node and its distance to the current query
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    node: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance).then(self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Approximate nearest-neighbour index keyed by component ID
+#[derive(Debug, Clone)]
+pub struct HnswIndex {
+    m: usize,
+    ef_construction: usize,
+    ef_search: usize,
+    ids: Vec<String>,
+    vectors: Vec<Vec<f32>>,
+    /// Per node, per layer neighbour lists
+    neighbors: Vec<Vec<Vec<usize>>>,
+    deleted: Vec<bool>,
+    /// Live node for each component ID
+    node_of: HashMap<String, usize>,
+    entry_point: Option<usize>,
+    rng_state: u64,
+}
+
+impl Default for HnswIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_M, DEFAULT_EF_CONSTRUCTION)
+    }
+}
+
+impl HnswIndex {
+    /// Create an empty index with `m` neighbours per node (doubled on layer 0)
+    pub fn new(m: usize, ef_construction: usize) -> Self {
+        Self {
+            m: m.max(2),
+            ef_construction: ef_construction.max(1),
+            ef_search: DEFAULT_EF_SEARCH,
+            ids: Vec::new(),
+            vectors: Vec::new(),
+            neighbors: Vec::new(),
+            deleted: Vec::new(),
+            node_of: HashMap::new(),
+            entry_point: None,
+            rng_state: 0x5EED_0F_C1_2C_u64,
+        }
+    }
+
+    /// Set the candidate list size used by searches; larger is slower but more accurate
+    pub fn with_ef_search(mut self, ef_search: usize) -> Self {
+        self.ef_search = ef_search.max(1);
+        self
+    }
+
+    /// Number of live entries
+    pub fn len(&self) -> usize {
+        self.node_of.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.node_of.is_empty()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.node_of.contains_key(id)
+    }
+
+    /// Dimension of indexed vectors, if any have been inserted
+    pub fn dimension(&self) -> Option<usize> {
+        self.vectors.first().map(Vec::len)
+    }
+
+    /// Insert or replace the vector for `id`
+    ///
+    /// Returns `false` and leaves the index unchanged if the vector's dimension
+    /// differs from the vectors already indexed.
+    pub fn insert(&mut self, id: &str, vector: &[f32]) -> bool {
+        if self.dimension().is_some_and(|dimension| dimension != vector.len()) {
+            tracing::warn!("Not indexing {}: expected {} dimensions", id, self.dimension().unwrap_or(0));
+            return false;
+        }
+        self.remove(id);
+
+        let node = self.vectors.len();
+        let level = self.random_level();
+        self.ids.push(id.to_string());
+        self.vectors.push(normalize(vector));
+        self.neighbors.push(vec![Vec::new(); level + 1]);
+        self.deleted.push(false);
+        self.node_of.insert(id.to_string(), node);
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(node);
+            return true;
+        };
+
+        let top_level = self.level_of(entry_point);
+        let query = self.vectors[node].clone();
+        let mut entry = entry_point;
+        for layer in (level + 1..=top_level).rev() {
+            entry = self.greedy_search(&query, entry, layer);
+        }
+
+        let mut entries = vec![entry];
+        for layer in (0..=level.min(top_level)).rev() {
+            let candidates = self.search_layer(&query, &entries, self.ef_construction, layer);
+            let selected: Vec<usize> = candidates.iter().take(self.m).map(|c| c.node).collect();
+
+            for &neighbor in &selected {
+                self.neighbors[neighbor][layer].push(node);
+                self.prune(neighbor, layer);
+            }
+            self.neighbors[node][layer] = selected;
+            entries = candidates.iter().map(|c| c.node).collect();
+        }
+
+        if level > top_level {
+            self.entry_point = Some(node);
+        }
+        true
+    }
+
+    /// Remove `id` from search results; returns `true` if it was indexed
+    pub fn remove(&mut self, id: &str) -> bool {
+        match self.node_of.remove(id) {
+            Some(node) => {
+                self.deleted[node] = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The `k` most similar entries as `(id, cosine similarity)`, best first
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<(String, f32)> {
+        let Some(entry_point) = self.entry_point else {
+            return Vec::new();
+        };
+        if k == 0 || Some(query.len()) != self.dimension() {
+            return Vec::new();
+        }
+
+        let query = normalize(query);
+        let mut entry = entry_point;
+        for layer in (1..=self.level_of(entry_point)).rev() {
+            entry = self.greedy_search(&query, entry, layer);
+        }
+
+        self.search_layer(&query, &[entry], self.ef_search.max(k), 0)
+            .into_iter()
+            .filter(|candidate| !self.deleted[candidate.node])
+            .take(k)
+            .map(|candidate| (self.ids[candidate.node].clone(), 1.0 - candidate.distance))
+            .collect()
+    }
+
+    fn level_of(&self, node: usize) -> usize {
+        self.neighbors[node].len() - 1
+    }
+
+    fn max_neighbors(&self, layer: usize) -> usize {
+        if layer == 0 { self.m * 2 } else { self.m }
+    }
+
+    fn distance(&self, query: &[f32], node: usize) -> f32 {
+        1.0 - query.iter().zip(&self.vectors[node]).map(|(a, b)| a * b).sum::<f32>()
+    }
+
+    /// Walk towards the query on one layer, one neighbour at a time
+    fn greedy_search(&self, query: &[f32], entry: usize, layer: usize) -> usize {
+        let mut current = entry;
+        let mut current_distance = self.distance(query, current);
+        loop {
+            let closer = self.neighbors[current][layer]
+                .iter()
+                .map(|&neighbor| (neighbor, self.distance(query, neighbor)))
+                .filter(|(_, distance)| *distance < current_distance)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            match closer {
+                Some((neighbor, distance)) => {
+                    current = neighbor;
+                    current_distance = distance;
+                }
+                None => return current,
+            }
+        }
+    }
+
+    /// Best-first search of one layer, returning up to `ef` nodes nearest first
+    fn search_layer(&self, query: &[f32], entries: &[usize], ef: usize, layer: usize) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entries.iter().copied().collect();
+        let mut candidates = BinaryHeap::new();
+        let mut nearest = BinaryHeap::new();
+        for &node in entries {
+            let candidate = Candidate { distance: self.distance(query, node), node };
+            candidates.push(std::cmp::Reverse(candidate));
+            nearest.push(candidate);
+        }
+        while nearest.len() > ef {
+            nearest.pop();
+        }
+
+        while let Some(std::cmp::Reverse(closest)) = candidates.pop() {
+            if nearest.peek().is_some_and(|furthest: &Candidate| closest.distance > furthest.distance) {
+                break;
+            }
+            for &neighbor in &self.neighbors[closest.node][layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = Candidate { distance: self.distance(query, neighbor), node: neighbor };
+                let improves = nearest.len() < ef
+                    || nearest.peek().is_some_and(|furthest| candidate.distance < furthest.distance);
+                if improves {
+                    candidates.push(std::cmp::Reverse(candidate));
+                    nearest.push(candidate);
+                    if nearest.len() > ef {
+                        nearest.pop();
+                    }
+                }
+            }
+        }
+
+        nearest.into_sorted_vec()
+    }
+
+    /// Keep only the closest neighbours of `node` on `layer`
+    fn prune(&mut self, node: usize, layer: usize) {
+        let limit = self.max_neighbors(layer);
+        if self.neighbors[node][layer].len() <= limit {
+            return;
+        }
+        let origin = self.vectors[node].clone();
+        let mut ranked: Vec<Candidate> = self.neighbors[node][layer]
+            .iter()
+            .map(|&neighbor| Candidate { distance: self.distance(&origin, neighbor), node: neighbor })
+            .collect();
+        ranked.sort();
+        self.neighbors[node][layer] = ranked.into_iter().take(limit).map(|c| c.node).collect();
+    }
+
+    /// Exponentially distributed level, `floor(-ln(u) / ln(m))`
+    fn random_level(&mut self) -> usize {
+        // SplitMix64 keeps builds reproducible without an RNG dependency
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        let uniform = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        (-uniform.ln() / (self.m as f64).ln()).floor() as usize
+    }
+}
+
+fn normalize(vector: &[f32]) -> Vec<f32> {
+    let magnitude = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if magnitude == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|x| x / magnitude).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Deterministic pseudo-random unit vectors
+    fn vectors(count: usize, dimension: usize) -> Vec<Vec<f32>> {
+        let mut state = 42u64;
+        (0..count)
+            .map(|_| {
+                (0..dimension)
+                    .map(|_| {
+                        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                        ((state >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn linear_top_k(data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<String> {
+        let query = normalize(query);
+        let mut scored: Vec<(usize, f32)> = data
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, normalize(v).iter().zip(&query).map(|(a, b)| a * b).sum::<f32>()))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(k).map(|(i, _)| format!("C{}", i)).collect()
+    }
+
+    fn build(data: &[Vec<f32>]) -> HnswIndex {
+        let mut index = HnswIndex::default();
+        for (i, vector) in data.iter().enumerate() {
+            assert!(index.insert(&format!("C{}", i), vector));
+        }
+        index
+    }
+
+    #[test]
+    fn test_search_recall() {
+        let data = vectors(1000, 32);
+        let index = build(&data);
+        assert_eq!(index.len(), 1000);
+
+        let queries = vectors(1020, 32).split_off(1000);
+        let mut found = 0;
+        for query in &queries {
+            let expected = linear_top_k(&data, query, 10);
+            let actual: Vec<String> = index.search(query, 10).into_iter().map(|(id, _)| id).collect();
+            found += actual.iter().filter(|id| expected.contains(id)).count();
+        }
+        assert!(found as f64 / 200.0 >= 0.9, "recall {} / 200", found);
+    }
+
+    #[test]
+    fn test_exact_match_and_removal() {
+        let data = vectors(200, 16);
+        let mut index = build(&data);
+
+        let best = index.search(&data[17], 1);
+        assert_eq!(best[0].0, "C17");
+        assert!((best[0].1 - 1.0).abs() < 1e-5);
+
+        assert!(index.remove("C17"));
+        assert!(!index.contains("C17"));
+        assert!(index.search(&data[17], 5).iter().all(|(id, _)| id != "C17"));
+
+        // Re-inserting replaces the tombstoned entry
+        assert!(index.insert("C17", &data[17]));
+        assert_eq!(index.search(&data[17], 1)[0].0, "C17");
+        assert_eq!(index.len(), 200);
+
+        assert!(!index.insert("bad", &[1.0, 0.0]));
+        assert!(index.search(&[1.0, 0.0], 3).is_empty());
+    }
+
+    /// Run with `cargo test --release -p opencircuit-ai -- --ignored`
+    #[test]
+    #[ignore = "benchmark; run in release mode"]
+    fn bench_search_10k() {
+        let data = vectors(10_000, 384);
+        let index = build(&data);
+        let queries = vectors(10_100, 384).split_off(10_000);
+
+        let start = Instant::now();
+        for query in &queries {
+            linear_top_k(&data, query, 10);
+        }
+        let linear = start.elapsed() / queries.len() as u32;
+
+        let start = Instant::now();
+        for query in &queries {
+            index.search(query, 10);
+        }
+        let ann = start.elapsed() / queries.len() as u32;
+
+        println!("top-10 over 10k: hnsw {:?}, linear {:?}", ann, linear);
+        assert!(ann.as_secs_f64() < 0.005);
+        assert!(ann < linear);
+    }
+}
@@ -6,19 +6,50 @@
 //! - Model management and automatic selection
 //! - Component recommendation system
 //! - Vector embeddings for component search
-
+//! - HNSW approximate nearest-neighbour index
+//! - Circuit and board context for AI questions
+//! - Plain-language DRC violation explanations
+//! - Structured circuit health scoring
+//! - Simulation-verified component value selection
+//! - Focused schematic design reviews
+//! - SPICE model generation from datasheet specifications
+//! - Schema-guided circuit generation from descriptions
+//! - Functional test vectors for digital circuits
+//! - Batch categorization of imported BOM components
+//! - Circuit type detection from topology
+//! - Circuit documentation in Markdown, HTML or plain text
+//! - ESD protection suggestions for I/O nets
+//! - Decoupling capacitor plans for power nets
+
+pub mod bom_categorizer;
+pub mod bom_comments;
 pub mod chat_handler;
 pub mod ollama_client;
 pub mod models;
 pub mod ollama_manager;
 pub mod component_advisor;
 pub mod embeddings;
+pub mod hnsw;
+pub mod circuit_classifier;
+pub mod circuit_display;
 pub mod circuit_generator;
+pub mod circuit_from_description;
 pub mod circuit_simulator;
+pub mod decoupling;
 pub mod docs;
+pub mod context_enrichment;
+pub mod drc_explainer;
+pub mod esd_protection;
+pub mod health_report;
+pub mod value_optimizer;
+pub mod schematic_review;
+pub mod spice_model_generator;
+pub mod test_plan;
+pub mod test_vectors;
 
 use anyhow::Result;
-use tracing::{info, warn, error};
+use std::collections::HashMap;
+use tracing::{info, warn, error, instrument};
 
 use crate::models::ModelStatus;
 use opencircuit_core::OpenCircuitError;
@@ -39,6 +70,8 @@ pub struct AiConfig {
     pub max_history: usize,
     /// Request timeout in seconds
     pub timeout_seconds: u64,
+    /// Token budget for circuit context added to questions
+    pub max_context_tokens: usize,
 }
 
 impl Default for AiConfig {
@@ -49,6 +82,7 @@ impl Default for AiConfig {
             default_model: models::AiModel::QwenTiny,
             max_history: 50,
             timeout_seconds: 30,
+            max_context_tokens: 512,
         }
     }
 }
@@ -63,15 +97,25 @@ pub struct AiService {
     component_advisor: component_advisor::ComponentAdvisor,
     /// Component embedding engine for similarity search
     embedding_engine: embeddings::ComponentEmbeddingEngine,
+    /// DRC explanations keyed by rule name and severity
+    drc_explanations: HashMap<(String, opencircuit_pcb::Severity), String>,
+    /// BOM comments keyed by circuit fingerprint
+    bom_comments: HashMap<u64, HashMap<String, String>>,
+    /// Circuit documentation prose keyed by circuit fingerprint
+    circuit_descriptions: HashMap<u64, circuit_display::CircuitDescription>,
+    /// Context of the open circuit, added to component requests
+    context: models::AiContext,
 }
 
 impl AiService {
     /// Create a new AI service with default configuration
+    #[instrument]
     pub async fn new() -> AiResult<Self> {
         Self::with_config(AiConfig::default()).await
     }
 
     /// Create a new AI service with custom configuration
+    #[instrument(skip(config), fields(model_name = %config.default_model.model_name()))]
     pub async fn with_config(config: AiConfig) -> AiResult<Self> {
         let ollama_config = ollama_client::OllamaConfig {
             host: config.ollama_host.clone(),
@@ -86,38 +130,101 @@ impl AiService {
         let component_advisor = component_advisor::ComponentAdvisor::new(ollama_client.clone()).await?;
         let embedding_engine = embeddings::ComponentEmbeddingEngine::new(ollama_client.clone()).await?;
 
-        Ok(Self { manager, config, component_advisor, embedding_engine })
+        Ok(Self {
+            manager,
+            config,
+            component_advisor,
+            embedding_engine,
+            drc_explanations: HashMap::new(),
+            bom_comments: HashMap::new(),
+            circuit_descriptions: HashMap::new(),
+            context: models::AiContext::default(),
+        })
     }
 
     /// Initialize the AI service
+    #[instrument(skip(self))]
     pub async fn initialize(&mut self) -> AiResult<()> {
         self.manager.initialize().await
     }
 
     /// Send a chat message with automatic model selection
+    #[instrument(skip(self, message), fields(use_case = ?use_case, message_len = message.len()))]
     pub async fn chat(&mut self, message: &str, use_case: models::AiUseCase) -> AiResult<models::AiResponse> {
         self.manager.chat_with_auto_model(message, &use_case).await
     }
 
     /// Ask a circuit-specific question
-    pub async fn ask_circuit_question(&mut self, question: &str, circuit_context: Option<&str>) -> AiResult<models::AiResponse> {
+    #[instrument(skip(self, question, circuit), fields(question_len = question.len(), circuit_component_count = circuit.map(|c| c.components.len())))]
+    pub async fn ask_circuit_question(
+        &mut self,
+        question: &str,
+        circuit: Option<&opencircuit_circuit::Circuit>,
+    ) -> AiResult<models::AiResponse> {
         // Determine use case based on question content
         let use_case = self.determine_use_case(question);
-        
-        let enhanced_question = match circuit_context {
-            Some(context) => format!("Circuit Context: {}\n\nQuestion: {}", context, question),
-            None => question.to_string(),
-        };
+        let enhanced_question = self.enrich_context(question, circuit, None);
 
         self.chat(&enhanced_question, use_case).await
     }
 
+    /// Prepend a compact summary of the circuit and board to a question
+    ///
+    /// The summary is truncated to `max_context_tokens`; the question is
+    /// returned unchanged when there is no context.
+    pub fn enrich_context(
+        &self,
+        question: &str,
+        circuit: Option<&opencircuit_circuit::Circuit>,
+        pcb: Option<&opencircuit_pcb::PcbDesign>,
+    ) -> String {
+        let summary = context_enrichment::summarize(circuit, pcb);
+        if summary.is_empty() {
+            return question.to_string();
+        }
+
+        let summary = context_enrichment::truncate_to_tokens(&summary, self.config.max_context_tokens);
+        format!("Circuit Context:\n{}\n\nQuestion: {}", summary, question)
+    }
+
     /// Get component recommendations
-    pub async fn suggest_components(&mut self, request: component_advisor::RecommendationRequest) -> AiResult<Vec<component_advisor::ComponentRecommendation>> {
+    ///
+    /// Requests without a circuit context get the open circuit's context.
+    #[instrument(skip(self, request))]
+    pub async fn suggest_components(&mut self, mut request: component_advisor::RecommendationRequest) -> AiResult<Vec<component_advisor::ComponentRecommendation>> {
+        if request.circuit_context.is_none() && self.context.circuit_type.is_some() {
+            request.circuit_context = Some(self.context.clone());
+        }
         self.component_advisor.get_recommendations(request).await
     }
 
+    /// Detect what kind of circuit this is and record it in the service's context
+    ///
+    /// Known topologies are recognized by [`circuit_classifier::classify_topology`];
+    /// anything else is classified by the LLM. Call this when a circuit is opened.
+    #[instrument(skip(self, circuit), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn detect_circuit_type(&mut self, circuit: &opencircuit_circuit::Circuit) -> AiResult<models::CircuitType> {
+        let circuit_type = match circuit_classifier::classify_topology(circuit) {
+            Some(circuit_type) => circuit_type,
+            None => {
+                let prompt = circuit_classifier::classification_prompt(circuit);
+                let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+                circuit_classifier::parse_circuit_type(&response.content)?
+            }
+        };
+
+        info!("Detected circuit type: {:?}", circuit_type);
+        self.context.circuit_type = Some(circuit_type.clone());
+        Ok(circuit_type)
+    }
+
+    /// Context of the open circuit
+    pub fn context(&self) -> &models::AiContext {
+        &self.context
+    }
+
     /// Find similar components using vector embeddings
+    #[instrument(skip(self, component), fields(part_number = %component.part_number))]
     pub async fn find_similar_components(
         &mut self,
         component: &opencircuit_core::models::Component,
@@ -127,6 +234,7 @@ impl AiService {
     }
 
     /// Analyze a circuit
+    #[instrument(skip(self, circuit_description), fields(description_len = circuit_description.len()))]
     pub async fn analyze_circuit(&mut self, circuit_description: &str) -> AiResult<models::AiResponse> {
         let prompt = format!(
             "Analyze this circuit and provide insights on performance, potential issues, and improvements:\n\n{}",
@@ -137,6 +245,7 @@ impl AiService {
     }
 
     /// Generate circuit code or netlist
+    #[instrument(skip(self, requirements), fields(requirements_len = requirements.len()))]
     pub async fn generate_circuit_code(&mut self, requirements: &str, format: &str) -> AiResult<models::AiResponse> {
         let prompt = format!(
             "Generate {} code for a circuit with these requirements:\n\n{}",
@@ -146,6 +255,290 @@ impl AiService {
         self.chat(&prompt, models::AiUseCase::CodeGeneration).await
     }
 
+    /// Generate a circuit from a plain-language description
+    ///
+    /// The prompt carries the circuit JSON Schema so the response can be
+    /// deserialized directly.
+    #[instrument(skip(self, description), fields(description_len = description.len()))]
+    pub async fn generate_circuit_from_description(
+        &mut self,
+        description: &str,
+    ) -> AiResult<opencircuit_circuit::Circuit> {
+        let prompt = circuit_from_description::description_prompt(description);
+        let response = self.chat(&prompt, models::AiUseCase::CodeGeneration).await?;
+        circuit_from_description::parse_circuit(&response.content)
+    }
+
+    /// Score circuit health as a structured report for dashboards
+    #[instrument(skip(self, circuit, simulation_results), fields(circuit_component_count = circuit.components.len(), has_simulation = simulation_results.is_some()))]
+    pub async fn score_circuit_health(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+        simulation_results: Option<&opencircuit_simulation::SimulationResults>,
+    ) -> AiResult<health_report::CircuitHealthReport> {
+        let prompt = health_report::health_prompt(circuit, simulation_results);
+        let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+        health_report::CircuitHealthReport::from_response(&response.content)
+    }
+
+    /// Peer-review a schematic with the given focus
+    #[instrument(skip(self, circuit), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn review_schematic(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+        focus: schematic_review::ReviewFocus,
+    ) -> AiResult<schematic_review::SchematicReview> {
+        let prompt = schematic_review::review_prompt(circuit, focus);
+        let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+        schematic_review::SchematicReview::from_response(&response.content, circuit, focus)
+    }
+
+    /// Suggest bench test procedures for verifying a circuit against its targets
+    #[instrument(skip(self, circuit, target_specs), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn suggest_test_procedures(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+        target_specs: &value_optimizer::CircuitTargetSpecs,
+    ) -> AiResult<test_plan::TestPlan> {
+        let prompt = test_plan::test_plan_prompt(circuit, target_specs);
+        let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+        test_plan::TestPlan::from_response(&response.content, circuit)
+    }
+
+    /// Plan decoupling capacitors for each IC power pin on the given
+    /// `(net, voltage)` power nets
+    #[instrument(skip(self, circuit, power_nets), fields(circuit_component_count = circuit.components.len(), power_net_count = power_nets.len()))]
+    pub async fn generate_decoupling_plan(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+        power_nets: &[(&str, f64)],
+    ) -> AiResult<decoupling::DecouplingPlan> {
+        decoupling::validate_power_nets(circuit, power_nets)?;
+        let prompt = decoupling::decoupling_prompt(circuit, power_nets);
+        let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+        decoupling::DecouplingPlan::from_response(&response.content, circuit, power_nets)
+    }
+
+    /// Generate input stimuli and expected outputs for a digital circuit
+    ///
+    /// Vectors are returned in the order they should be applied, since
+    /// sequential logic depends on the ones before.
+    #[instrument(skip(self, circuit, input_pins, output_pins), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn generate_test_vectors(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+        input_pins: &[&str],
+        output_pins: &[&str],
+        num_vectors: usize,
+    ) -> AiResult<Vec<test_vectors::TestVector>> {
+        let prompt = test_vectors::test_vectors_prompt(circuit, input_pins, output_pins, num_vectors);
+        let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+        test_vectors::parse_test_vectors(&response.content, input_pins, output_pins, num_vectors)
+    }
+
+    /// Assign a category to each component, in batches of
+    /// [`bom_categorizer::CATEGORIZATION_BATCH_SIZE`]
+    ///
+    /// Components the model could not place are left out of the result.
+    #[instrument(skip(self, components), fields(component_count = components.len()))]
+    pub async fn categorize_components_batch(
+        &mut self,
+        components: &[opencircuit_database::ComponentRecord],
+    ) -> AiResult<Vec<(String, opencircuit_core::models::ComponentCategory)>> {
+        let mut assignments = Vec::with_capacity(components.len());
+        for batch in components.chunks(bom_categorizer::CATEGORIZATION_BATCH_SIZE) {
+            let prompt = bom_categorizer::categorization_prompt(batch);
+            let response = self.chat(&prompt, models::AiUseCase::ComponentSelection).await?;
+            match bom_categorizer::parse_categories(&response.content, batch) {
+                Ok(batch_assignments) => assignments.extend(batch_assignments),
+                Err(e) => warn!("Skipping batch of {} components: {}", batch.len(), e),
+            }
+        }
+        Ok(assignments)
+    }
+
+    /// Categorize components and store the categories in the database
+    #[instrument(skip(self, database, components), fields(component_count = components.len()))]
+    pub async fn categorize_and_update_components(
+        &mut self,
+        database: &opencircuit_database::Database,
+        components: &[opencircuit_database::ComponentRecord],
+    ) -> AiResult<Vec<(String, opencircuit_core::models::ComponentCategory)>> {
+        let assignments = self.categorize_components_batch(components).await?;
+        for (id, category) in &assignments {
+            database
+                .set_component_category(id, category.as_str())
+                .map_err(|e| OpenCircuitError::with_source(e.into(), &format!("Failed to store category of {}", id)))?;
+        }
+        info!("Categorized {} of {} components", assignments.len(), components.len());
+        Ok(assignments)
+    }
+
+    /// Explain each component's role in one sentence, keyed by component ID
+    ///
+    /// Results are cached until the circuit's components or connections
+    /// change. Use [`bom_comments::apply_comments`] to store them in the
+    /// components' `purpose`.
+    #[instrument(skip(self, circuit), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn generate_bom_comments(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+    ) -> AiResult<HashMap<String, String>> {
+        let key = bom_comments::circuit_fingerprint(circuit);
+        if let Some(comments) = self.bom_comments.get(&key) {
+            return Ok(comments.clone());
+        }
+
+        let prompt = bom_comments::bom_prompt(circuit);
+        let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+        let comments = bom_comments::parse_comments(&response.content, circuit)?;
+        self.bom_comments.insert(key, comments.clone());
+        Ok(comments)
+    }
+
+    /// Document a circuit with component and connection tables and a summary
+    ///
+    /// The LLM's descriptions are cached by [`bom_comments::circuit_fingerprint`],
+    /// so rendering the same circuit again, in any format, does not re-query it.
+    #[instrument(skip(self, circuit), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn format_circuit_for_display(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+        format: circuit_display::DisplayFormat,
+    ) -> AiResult<String> {
+        let key = bom_comments::circuit_fingerprint(circuit);
+        if !self.circuit_descriptions.contains_key(&key) {
+            let prompt = circuit_display::display_prompt(circuit);
+            let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+            let description = circuit_display::parse_description(&response.content, circuit)?;
+            self.circuit_descriptions.insert(key, description);
+        }
+        Ok(circuit_display::render_circuit_document(circuit, &self.circuit_descriptions[&key], format))
+    }
+
+    /// Explain a DRC violation in plain language, caching by rule and severity
+    #[instrument(skip(self, violation, design_context), fields(rule = %violation.rule_name))]
+    pub async fn explain_drc_violation(
+        &mut self,
+        violation: &opencircuit_pcb::DrcViolation,
+        design_context: Option<&opencircuit_pcb::PcbDesign>,
+    ) -> AiResult<String> {
+        let key = drc_explainer::cache_key(violation);
+        if let Some(explanation) = self.drc_explanations.get(&key) {
+            return Ok(explanation.clone());
+        }
+
+        let prompt = drc_explainer::explanation_prompt(violation, design_context);
+        let response = self.chat(&prompt, models::AiUseCase::BasicChat).await?;
+        self.drc_explanations.insert(key, response.content.clone());
+        Ok(response.content)
+    }
+
+    /// Suggest values for unknown components, checking them in simulation
+    ///
+    /// Values are re-requested with the simulated shortfall, up to
+    /// [`value_optimizer::MAX_OPTIMIZATION_ATTEMPTS`] times. Without NgSpice,
+    /// or when the simulation can't measure the gain, the first valid
+    /// suggestion is returned unverified.
+    #[instrument(skip(self, circuit, target_specs), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn optimize_component_values(
+        &mut self,
+        circuit: &opencircuit_circuit::Circuit,
+        target_specs: &value_optimizer::CircuitTargetSpecs,
+    ) -> AiResult<Vec<(String, String)>> {
+        let unknown = value_optimizer::unknown_components(circuit);
+        if unknown.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut engine = match opencircuit_simulation::SimulationEngine::new().await {
+            Ok(engine) if engine.health_check().await.unwrap_or(false) => Some(engine),
+            _ => {
+                warn!("NgSpice unavailable, component values will not be verified");
+                None
+            }
+        };
+
+        let mut feedback: Option<String> = None;
+        let mut last_error = None;
+        let mut best = None;
+        for attempt in 1..=value_optimizer::MAX_OPTIMIZATION_ATTEMPTS {
+            let prompt = value_optimizer::optimization_prompt(circuit, target_specs, &unknown, feedback.as_deref());
+            let response = self.chat(&prompt, models::AiUseCase::CircuitAnalysis).await?;
+
+            let values = match value_optimizer::parse_suggestions(&response.content, &unknown) {
+                Ok(values) => values,
+                Err(e) => {
+                    warn!("Attempt {}: unusable value suggestions: {}", attempt, e);
+                    feedback = Some(e.to_string());
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+
+            let Some(engine) = engine.as_mut() else {
+                return Ok(values);
+            };
+            let candidate = value_optimizer::apply_values(circuit, &values);
+            let Some(gain) = value_optimizer::simulate_dc_gain(engine, &candidate, target_specs).await else {
+                warn!("Simulation could not measure the gain, component values are unverified");
+                return Ok(values);
+            };
+            let failures = target_specs.gain_failures(gain);
+            if failures.is_empty() {
+                info!("Component values met targets on attempt {}", attempt);
+                return Ok(values);
+            }
+
+            feedback = Some(failures.join("\n"));
+            best = Some(values);
+        }
+
+        match (best, last_error) {
+            (Some(values), _) => {
+                warn!("Component values still miss targets after {} attempts", value_optimizer::MAX_OPTIMIZATION_ATTEMPTS);
+                Ok(values)
+            }
+            (None, Some(e)) => Err(e),
+            (None, None) => Err(OpenCircuitError::AiService("No component values suggested".to_string())),
+        }
+    }
+
+    /// Generate an approximate SPICE model from a component's datasheet values
+    ///
+    /// Models the simulator cannot parse are sent back for correction, up to
+    /// [`spice_model_generator::MAX_MODEL_ATTEMPTS`] attempts in total.
+    #[instrument(skip(self, component), fields(part_number = %component.part_number))]
+    pub async fn generate_spice_model(
+        &mut self,
+        component: &opencircuit_core::models::Component,
+    ) -> AiResult<opencircuit_simulation::SpiceModel> {
+        let request = spice_model_generator::SpiceModelRequest::for_component(component)?;
+        let mut prompt = request.prompt();
+        let mut last_error = None;
+        for attempt in 1..=spice_model_generator::MAX_MODEL_ATTEMPTS {
+            let response = self.chat(&prompt, models::AiUseCase::CodeGeneration).await?;
+            match request.parse_response(&response.content) {
+                Ok(model) => {
+                    info!("Generated SPICE model {} on attempt {}", model.name, attempt);
+                    return Ok(model);
+                }
+                Err(e) => {
+                    warn!("Attempt {}: unusable SPICE model: {}", attempt, e);
+                    prompt = request.correction_prompt(&response.content, &e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(OpenCircuitError::AiService(format!(
+            "No valid SPICE model for {} after {} attempts: {}",
+            component.part_number,
+            spice_model_generator::MAX_MODEL_ATTEMPTS,
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        )))
+    }
+
     /// Determine the appropriate use case based on the question content
     fn determine_use_case(&self, question: &str) -> models::AiUseCase {
         let question_lower = question.to_lowercase();
@@ -185,6 +578,7 @@ impl AiService {
     }
 
     /// Set active model manually
+    #[instrument(skip(self, model), fields(model_name = %model.model_name()))]
     pub async fn set_model(&mut self, model: models::AiModel) -> AiResult<()> {
         self.manager.set_active_model(model).await
     }
@@ -194,7 +588,18 @@ impl AiService {
         self.manager.get_active_model()
     }
 
+    /// Get the chat history as (user message, AI response) pairs, oldest first
+    pub fn get_chat_history(&self) -> &std::collections::VecDeque<(String, String)> {
+        self.manager.get_history()
+    }
+
+    /// Forget the chat history so the next message starts a new conversation
+    pub fn clear_chat_history(&mut self) {
+        self.manager.clear_history();
+    }
+
     /// Legacy method for backward compatibility
+    #[instrument(skip(self, prompt), fields(prompt_len = prompt.len()))]
     pub async fn chat_completion(&self, prompt: &str) -> AiResult<String> {
         // This is a simplified version for backward compatibility
         // In practice, you'd want to use the new chat method
@@ -207,15 +612,24 @@ pub use chat_handler::ChatHandler;
 pub use ollama_client::OpenCircuitOllamaClient;
 pub use models::{
     AiContext, CircuitType, DesignPhase, ExpertiseLevel, AiResponse, 
-    AiModel, AiUseCase, ModelPerformance, ServerStatus
+    AiModel, AiUseCase, DownloadProgress, ModelPerformance, ModelPerformanceStats, ServerStatus
 };
 pub use component_advisor::{
     ComponentAdvisor, ComponentRecommendation, RecommendationRequest,
-    BudgetConstraints, PerformancePriority, CostCategory, CompatibilityAnalysis
+    BudgetConstraints, PerformancePriority, CostCategory, CompatibilityAnalysis,
+    LifecycleRisk, LifecycleStatus
 };
+pub use circuit_display::{CircuitDescription, DisplayFormat};
+pub use decoupling::{DecouplingPlan, DecouplingRecommendation};
+pub use esd_protection::{EsdRecommendation, InterfaceType, IoPinSpec};
+pub use health_report::CircuitHealthReport;
+pub use schematic_review::{ReviewFocus, ReviewIssue, SchematicReview};
+pub use test_plan::{TestPlan, TestStep};
+pub use test_vectors::TestVector;
 pub use embeddings::{
     ComponentEmbeddingEngine, ComponentEmbedding, SimilarityMatch
 };
+pub use hnsw::HnswIndex;
 
 #[cfg(test)]
 mod tests {
@@ -229,6 +643,7 @@ mod tests {
         assert_eq!(config.ollama_port, 11434);
         assert_eq!(config.default_model, AiModel::QwenTiny);
         assert_eq!(config.max_history, 50);
+        assert_eq!(config.max_context_tokens, 512);
     }
 
     #[test]
@@ -264,6 +679,17 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_enrich_context() {
+        let config = AiConfig { max_context_tokens: 5, ..AiConfig::default() };
+        let service = AiService::with_config(config).await.unwrap();
+        assert_eq!(service.enrich_context("Why?", None, None), "Why?");
+
+        let pcb = opencircuit_pcb::PcbDesign::new(40.0, 20.0, 4);
+        let enriched = service.enrich_context("Why?", None, Some(&pcb));
+        assert_eq!(enriched, "Circuit Context:\nBoard: 40 x 20 mm...\n\nQuestion: Why?");
+    }
+
     #[tokio::test]
     async fn test_ai_service_creation() {
         let service = AiService::new().await;
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::fmt;
+use std::time::Duration;
 
 /// Available AI models for different use cases
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -35,6 +36,14 @@ impl AiModel {
         }
     }
 
+    /// Model with the given Ollama name, custom if it is not a known model
+    pub fn from_model_name(name: &str) -> Self {
+        [AiModel::QwenTiny, AiModel::QwenSmall, AiModel::QwenMedium, AiModel::QwenCoder]
+            .into_iter()
+            .find(|model| model.model_name() == name)
+            .unwrap_or_else(|| AiModel::Custom(name.to_string()))
+    }
+
     /// Get human-readable description
     pub fn description(&self) -> &str {
         match self {
@@ -57,6 +66,30 @@ impl AiModel {
         }
     }
 
+    /// Context window in tokens, shared by the prompt and the response
+    ///
+    /// Qwen2.5 models are published with a 32K context; custom models get a
+    /// conservative default.
+    pub fn context_window_tokens(&self) -> usize {
+        match self {
+            AiModel::QwenTiny | AiModel::QwenSmall | AiModel::QwenMedium | AiModel::QwenCoder => 32_768,
+            AiModel::Custom(_) => 4_096,
+        }
+    }
+
+    /// Maximum number of tokens the model generates in one response
+    pub fn max_output_tokens(&self) -> usize {
+        match self {
+            AiModel::QwenTiny | AiModel::QwenSmall | AiModel::QwenMedium | AiModel::QwenCoder => 8_192,
+            AiModel::Custom(_) => 1_024,
+        }
+    }
+
+    /// Whether the model follows tool and JSON-mode schemas reliably
+    pub fn supports_function_calling(&self) -> bool {
+        matches!(self, AiModel::QwenSmall | AiModel::QwenMedium | AiModel::QwenCoder)
+    }
+
     /// Check if model is suitable for the given use case
     pub fn is_suitable_for(&self, use_case: &AiUseCase) -> bool {
         match use_case {
@@ -96,6 +129,9 @@ pub enum AiUseCase {
     ComplexDesign,
 }
 
+/// Number of recent requests kept for latency and throughput statistics
+pub const PERFORMANCE_WINDOW: usize = 100;
+
 /// Model performance metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelPerformance {
@@ -113,6 +149,22 @@ pub struct ModelPerformance {
     pub interaction_count: u32,
     /// Last updated timestamp
     pub last_updated: DateTime<Utc>,
+    /// Latencies of the most recent requests in milliseconds
+    #[serde(default)]
+    pub recent_latencies_ms: Vec<u64>,
+    /// Generation speed of the most recent requests
+    #[serde(default)]
+    pub recent_tokens_per_second: Vec<f64>,
+}
+
+/// Summary of a model's recent performance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelPerformanceStats {
+    pub mean_latency: Duration,
+    pub p95_latency: Duration,
+    /// Mean generation speed, if the server reported token counts
+    pub tokens_per_second: Option<f64>,
+    pub total_requests: u32,
 }
 
 impl ModelPerformance {
@@ -125,6 +177,46 @@ impl ModelPerformance {
             user_rating: 0.0,
             interaction_count: 0,
             last_updated: Utc::now(),
+            recent_latencies_ms: Vec::new(),
+            recent_tokens_per_second: Vec::new(),
+        }
+    }
+
+    /// Record the end-to-end latency of a request
+    pub fn record_latency(&mut self, duration: Duration) {
+        push_bounded(&mut self.recent_latencies_ms, duration.as_millis() as u64);
+        self.last_updated = Utc::now();
+    }
+
+    /// Record the generation speed reported for a request
+    pub fn record_tokens_per_second(&mut self, tokens_per_second: f64) {
+        if tokens_per_second.is_finite() && tokens_per_second > 0.0 {
+            push_bounded(&mut self.recent_tokens_per_second, tokens_per_second);
+        }
+    }
+
+    /// 95th percentile of the recent latencies, zero when none were recorded
+    pub fn p95_latency(&self) -> Duration {
+        let mut latencies = self.recent_latencies_ms.clone();
+        latencies.sort_unstable();
+        let index = (latencies.len() * 95).div_ceil(100).saturating_sub(1);
+        Duration::from_millis(latencies.get(index).copied().unwrap_or(0))
+    }
+
+    /// Latency and throughput statistics over the recent requests
+    pub fn stats(&self) -> ModelPerformanceStats {
+        let mean_latency_ms = match self.recent_latencies_ms.len() {
+            0 => 0,
+            count => self.recent_latencies_ms.iter().sum::<u64>() / count as u64,
+        };
+        let tokens_per_second = (!self.recent_tokens_per_second.is_empty()).then(|| {
+            self.recent_tokens_per_second.iter().sum::<f64>() / self.recent_tokens_per_second.len() as f64
+        });
+        ModelPerformanceStats {
+            mean_latency: Duration::from_millis(mean_latency_ms),
+            p95_latency: self.p95_latency(),
+            tokens_per_second,
+            total_requests: self.interaction_count,
         }
     }
 
@@ -149,6 +241,14 @@ impl ModelPerformance {
     }
 }
 
+/// Append `value`, dropping the oldest entry beyond [`PERFORMANCE_WINDOW`]
+fn push_bounded<T>(values: &mut Vec<T>, value: T) {
+    values.push(value);
+    if values.len() > PERFORMANCE_WINDOW {
+        values.remove(0);
+    }
+}
+
 /// AI interaction context for better responses
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiContext {
@@ -191,6 +291,12 @@ pub enum CircuitType {
     Sensor,
     Motor,
     Communication,
+    /// Free-running oscillator, e.g. a ring oscillator or astable multivibrator
+    Oscillator,
+    /// Op-amp filter with RC frequency shaping
+    ActiveFilter,
+    /// High-side and low-side switch pair sharing a switch node
+    HalfBridge,
     Other(String),
 }
 
@@ -280,6 +386,18 @@ pub struct ModelStatus {
     pub last_check: DateTime<Utc>,
 }
 
+/// Progress of a model download, as reported by Ollama's pull endpoint
+///
+/// Byte counts are per layer: Ollama pulls a model's layers one after
+/// another, and both counts are zero while it is not transferring a layer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DownloadProgress {
+    /// Ollama's description of the current step, e.g. "pulling manifest"
+    pub status: String,
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+}
+
 /// Ollama server status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ServerStatus {
@@ -328,6 +446,39 @@ mod tests {
         assert!(medium.is_suitable_for(&AiUseCase::ComplexDesign));
     }
 
+    #[test]
+    fn test_performance_stats() {
+        let mut perf = ModelPerformance::new(AiModel::QwenSmall);
+        assert_eq!(perf.stats().p95_latency, Duration::ZERO);
+
+        for ms in 1..=20 {
+            perf.record_latency(Duration::from_millis(ms * 100));
+        }
+        perf.record_tokens_per_second(40.0);
+        perf.record_tokens_per_second(20.0);
+        perf.record_tokens_per_second(f64::NAN);
+
+        let stats = perf.stats();
+        assert_eq!(stats.mean_latency, Duration::from_millis(1050));
+        assert_eq!(stats.p95_latency, Duration::from_millis(1900));
+        assert_eq!(stats.tokens_per_second, Some(30.0));
+
+        for _ in 0..PERFORMANCE_WINDOW {
+            perf.record_latency(Duration::from_secs(12));
+        }
+        assert_eq!(perf.recent_latencies_ms.len(), PERFORMANCE_WINDOW);
+        assert_eq!(perf.p95_latency(), Duration::from_secs(12));
+    }
+
+    #[test]
+    fn test_model_capacity() {
+        for model in [AiModel::QwenTiny, AiModel::QwenCoder, AiModel::Custom("phi3:mini".to_string())] {
+            assert!(model.max_output_tokens() < model.context_window_tokens());
+        }
+        assert!(!AiModel::QwenTiny.supports_function_calling());
+        assert!(AiModel::QwenCoder.supports_function_calling());
+    }
+
     #[test]
     fn test_performance_metrics_update() {
         let mut perf = ModelPerformance::new(AiModel::QwenTiny);
@@ -35,6 +35,21 @@ impl Default for OllamaConfig {
     }
 }
 
+/// Token counts reported by Ollama for a completed generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationStats {
+    /// Tokens generated in the response
+    pub eval_count: u64,
+    /// Time spent generating them, in nanoseconds
+    pub eval_duration_ns: u64,
+}
+
+impl GenerationStats {
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        (self.eval_duration_ns > 0).then(|| self.eval_count as f64 * 1e9 / self.eval_duration_ns as f64)
+    }
+}
+
 /// OpenCircuit-specific Ollama client
 #[derive(Clone)]
 pub struct OpenCircuitOllamaClient {
@@ -114,6 +129,11 @@ Always provide practical, implementable advice with specific part numbers when p
 
     /// Send a chat message and get response
     pub async fn chat(&mut self, message: &str) -> AiResult<String> {
+        self.chat_with_stats(message).await.map(|(response, _)| response)
+    }
+
+    /// Send a chat message, also returning the server's generation statistics
+    pub async fn chat_with_stats(&mut self, message: &str) -> AiResult<(String, Option<GenerationStats>)> {
         // For now, use a simple completion approach
         // This is a simplified implementation that should work with basic ollama-rs
         let full_prompt = format!("{}\n\nUser: {}\nAssistant:", self.system_prompt, message);
@@ -124,11 +144,15 @@ Always provide practical, implementable advice with specific part numbers when p
         )).await {
             Ok(response) => {
                 let ai_response = response.response;
+                let stats = response.final_data.map(|data| GenerationStats {
+                    eval_count: u64::from(data.eval_count),
+                    eval_duration_ns: u64::from(data.eval_duration),
+                });
                 
                 // Add to history
                 self.add_to_history(message.to_string(), ai_response.clone());
                 
-                Ok(ai_response)
+                Ok((ai_response, stats))
             }
             Err(e) => {
                 Err(opencircuit_core::OpenCircuitError::AiService(
@@ -6,11 +6,14 @@
 use crate::models::*;
 use crate::ollama_client::{OpenCircuitOllamaClient, OllamaConfig};
 use opencircuit_core::OpenCircuitError;
-use std::collections::HashMap;
-use std::time::Instant;
-use tracing::{info, warn, error, debug};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use tracing::{info, warn, error, debug, instrument};
 use chrono::Utc;
 
+/// 95th percentile latency above which a model is passed over as the default
+const SLOW_MODEL_P95: Duration = Duration::from_secs(10);
+
 /// Result type for Ollama operations
 type OllamaResult<T> = std::result::Result<T, OpenCircuitError>;
 
@@ -53,6 +56,7 @@ impl OllamaManager {
     }
 
     /// Initialize the manager and check server status
+    #[instrument(skip(self))]
     pub async fn initialize(&mut self) -> OllamaResult<()> {
         info!("Initializing Ollama manager...");
         
@@ -70,6 +74,7 @@ impl OllamaManager {
     }
 
     /// Check if Ollama server is running and accessible
+    #[instrument(skip(self))]
     pub async fn check_server_status(&mut self) -> OllamaResult<ServerStatus> {
         debug!("Checking Ollama server status...");
         
@@ -87,6 +92,7 @@ impl OllamaManager {
     }
 
     /// Scan for available models on the system
+    #[instrument(skip(self))]
     pub async fn scan_available_models(&mut self) -> OllamaResult<()> {
         debug!("Scanning for available models...");
         
@@ -118,6 +124,7 @@ impl OllamaManager {
     }
 
     /// Check if a specific model is available
+    #[instrument(skip(self, model), fields(model_name = %model.model_name()))]
     async fn check_model_availability(&mut self, model: &AiModel) -> bool {
         // Try to use the model with a simple test prompt
         let original_model = self.client.get_model().to_string();
@@ -133,6 +140,10 @@ impl OllamaManager {
     }
 
     /// Setup the default model (preferring the lightest available model)
+    ///
+    /// Models whose recorded p95 latency exceeds 10 seconds are skipped while
+    /// a faster model is available.
+    #[instrument(skip(self))]
     async fn setup_default_model(&mut self) -> OllamaResult<()> {
         // Priority order: start with lightest model
         let model_priority = vec![
@@ -141,42 +152,98 @@ impl OllamaManager {
             AiModel::QwenCoder,
             AiModel::QwenMedium,
         ];
+        let available: Vec<AiModel> = model_priority
+            .into_iter()
+            .filter(|model| *self.status.available_models.get(model).unwrap_or(&false))
+            .collect();
 
-        for model in model_priority {
-            if *self.status.available_models.get(&model).unwrap_or(&false) {
-                self.set_active_model(model).await?;
-                info!("Set default model to: {}", self.status.active_model.model_name());
-                return Ok(());
+        let fast = available.iter().find(|model| !self.is_slow(model));
+        if let Some(model) = fast.or(available.first()).cloned() {
+            for slow in available.iter().take_while(|candidate| **candidate != model) {
+                warn!("Skipping {}: p95 latency above {:?}", slow.model_name(), SLOW_MODEL_P95);
             }
+            self.set_active_model(model).await?;
+            info!("Set default model to: {}", self.status.active_model.model_name());
+            return Ok(());
         }
 
         // If no models are available, try to download the tiny model
         warn!("No models available, attempting to download qwen2.5:0.5b");
-        self.download_model(&AiModel::QwenTiny).await?;
+        self.download_model(AiModel::QwenTiny, |progress| {
+            debug!("{}: {}/{} bytes", progress.status, progress.bytes_downloaded, progress.total_bytes)
+        })
+        .await?;
         self.set_active_model(AiModel::QwenTiny).await?;
         
         Ok(())
     }
 
-    /// Download a model using Ollama
-    pub async fn download_model(&mut self, model: &AiModel) -> OllamaResult<()> {
+    /// Download a model with Ollama's pull API, reporting progress as it streams
+    ///
+    /// `progress_callback` is called for every progress event Ollama sends.
+    /// On success the model is marked available.
+    #[instrument(skip(self, progress_callback), fields(model_name = %model.model_name()))]
+    pub async fn download_model(&mut self, model: AiModel, progress_callback: impl Fn(DownloadProgress)) -> OllamaResult<()> {
         info!("Downloading model: {}", model.model_name());
-        
-        // Note: ollama-rs doesn't have a direct download method in the current version
-        // We'll need to use the system command or wait for the API to support it
-        // For now, we'll provide instructions to the user
-        
-        let model_name = model.model_name();
-        warn!("Model download not yet implemented in ollama-rs");
-        warn!("Please run: ollama pull {}", model_name);
-        
-        // TODO: Implement actual model download when ollama-rs supports it
-        // or use system command as fallback
-        
+
+        let url = format!("{}:{}/api/pull", self.config.host, self.config.port);
+        // Older Ollama releases read `name`, newer ones `model`
+        let body = serde_json::json!({ "name": model.model_name(), "model": model.model_name(), "stream": true });
+        let mut response = reqwest::Client::new()
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| OpenCircuitError::AiService(format!("Failed to reach Ollama at {}: {}", url, e)))?;
+        if !response.status().is_success() {
+            return Err(OpenCircuitError::AiService(format!(
+                "Ollama refused to pull {}: HTTP {}",
+                model.model_name(),
+                response.status()
+            )));
+        }
+
+        // Events are newline-delimited JSON and may be split across chunks
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut succeeded = false;
+        loop {
+            let chunk = response
+                .chunk()
+                .await
+                .map_err(|e| OpenCircuitError::AiService(format!("Model download interrupted: {}", e)))?;
+            match &chunk {
+                Some(bytes) => buffer.extend_from_slice(bytes),
+                // Flush an event left without a trailing newline
+                None => buffer.push(b'\n'),
+            }
+
+            while let Some(end) = buffer.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=end).collect();
+                if let Some(progress) = parse_pull_event(&String::from_utf8_lossy(&line))? {
+                    succeeded |= progress.status == "success";
+                    progress_callback(progress);
+                }
+            }
+            if chunk.is_none() {
+                break;
+            }
+        }
+
+        if !succeeded {
+            return Err(OpenCircuitError::AiService(format!(
+                "Download of {} ended before Ollama reported success",
+                model.model_name()
+            )));
+        }
+
+        info!("Downloaded model: {}", model.model_name());
+        self.status.available_models.insert(model.clone(), true);
+        self.performance_tracker.entry(model.clone()).or_insert_with(|| ModelPerformance::new(model));
         Ok(())
     }
 
   /// Set the active model for AI operations
+    #[instrument(skip(self, model), fields(model_name = %model.model_name()))]
     pub async fn set_active_model(&mut self, model: AiModel) -> OllamaResult<()> {
         if !self.status.available_models.get(&model).unwrap_or(&false) {
             return Err(opencircuit_core::OpenCircuitError::AiService(
@@ -215,6 +282,7 @@ impl OllamaManager {
     }
 
     /// Auto-select the best model for a use case and switch to it
+    #[instrument(skip(self), fields(use_case = ?use_case))]
     pub async fn auto_select_model(&mut self, use_case: &AiUseCase) -> OllamaResult<()> {
         if let Some(best_model) = self.get_best_model_for_use_case(use_case) {
             if best_model != self.status.active_model {
@@ -227,6 +295,7 @@ impl OllamaManager {
     }
 
     /// Send a chat message with automatic model selection
+    #[instrument(skip(self, message), fields(use_case = ?use_case, message_len = message.len()))]
     pub async fn chat_with_auto_model(&mut self, message: &str, use_case: &AiUseCase) -> OllamaResult<AiResponse> {
         // Auto-select best model for use case
         self.auto_select_model(use_case).await?;
@@ -235,14 +304,19 @@ impl OllamaManager {
         let start_time = Instant::now();
         
         // Send the message
-        let result = self.client.chat(message).await;
+        let result = self.client.chat_with_stats(message).await;
         
-        let generation_time_ms = start_time.elapsed().as_millis() as u64;
+        let elapsed = start_time.elapsed();
+        let generation_time_ms = elapsed.as_millis() as u64;
+        self.active_performance().record_latency(elapsed);
         
         match result {
-            Ok(content) => {
+            Ok((content, stats)) => {
                 // Update performance metrics
                 self.update_performance_metrics(generation_time_ms, true, None);
+                if let Some(tokens_per_second) = stats.and_then(|stats| stats.tokens_per_second()) {
+                    self.active_performance().record_tokens_per_second(tokens_per_second);
+                }
                 
                 let mut response = AiResponse::new(content, self.status.active_model.clone(), generation_time_ms);
                 
@@ -262,9 +336,31 @@ impl OllamaManager {
 
     /// Update performance metrics for the current model
     fn update_performance_metrics(&mut self, response_time_ms: u64, success: bool, user_rating: Option<f32>) {
-        if let Some(performance) = self.performance_tracker.get_mut(&self.status.active_model) {
-            performance.update_metrics(response_time_ms, success, user_rating);
-        }
+        self.active_performance().update_metrics(response_time_ms, success, user_rating);
+    }
+
+    /// Performance record of the active model, created on first use
+    fn active_performance(&mut self) -> &mut ModelPerformance {
+        let model = self.status.active_model.clone();
+        self.performance_tracker.entry(model.clone()).or_insert_with(|| ModelPerformance::new(model))
+    }
+
+    /// Whether the model's recorded p95 latency is above the slow threshold
+    fn is_slow(&self, model: &AiModel) -> bool {
+        self.performance_tracker
+            .get(model)
+            .is_some_and(|performance| performance.p95_latency() > SLOW_MODEL_P95)
+    }
+
+    /// Latency and throughput statistics for every model used so far
+    pub fn performance_report(&self) -> Vec<(AiModel, ModelPerformanceStats)> {
+        let mut report: Vec<(AiModel, ModelPerformanceStats)> = self
+            .performance_tracker
+            .iter()
+            .map(|(model, performance)| (model.clone(), performance.stats()))
+            .collect();
+        report.sort_by(|a, b| a.0.model_name().cmp(b.0.model_name()));
+        report
     }
 
     /// Add contextual follow-up questions based on use case
@@ -312,6 +408,16 @@ impl OllamaManager {
         &self.status.active_model
     }
 
+    /// Get the conversation history as (user message, AI response) pairs
+    pub fn get_history(&self) -> &VecDeque<(String, String)> {
+        self.client.get_history()
+    }
+
+    /// Clear the conversation history
+    pub fn clear_history(&mut self) {
+        self.client.clear_history();
+    }
+
     /// Check if any models are available
     pub fn has_available_models(&self) -> bool {
         self.status.available_models.values().any(|&available| available)
@@ -327,6 +433,27 @@ impl OllamaManager {
     }
 }
 
+/// Parse one line of Ollama's streamed pull response
+///
+/// Blank lines give `None`; an `error` event fails the download.
+fn parse_pull_event(line: &str) -> OllamaResult<Option<DownloadProgress>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let event: serde_json::Value = serde_json::from_str(line)
+        .map_err(|e| OpenCircuitError::AiService(format!("Invalid pull progress event '{}': {}", line, e)))?;
+    if let Some(error) = event.get("error").and_then(|error| error.as_str()) {
+        return Err(OpenCircuitError::AiService(format!("Ollama failed to pull model: {}", error)));
+    }
+
+    Ok(Some(DownloadProgress {
+        status: event["status"].as_str().unwrap_or_default().to_string(),
+        bytes_downloaded: event["completed"].as_u64().unwrap_or(0),
+        total_bytes: event["total"].as_u64().unwrap_or(0),
+    }))
+}
+
 impl Default for OllamaManager {
     fn default() -> Self {
         Self::new()
@@ -375,4 +502,42 @@ mod tests {
         assert_eq!(performance.success_rate, 1.0);
         assert_eq!(performance.user_rating, 4.0);
     }
+
+    #[test]
+    fn test_parse_pull_event() {
+        assert_eq!(
+            parse_pull_event(r#"{"status": "pulling manifest"}"#).unwrap(),
+            Some(DownloadProgress { status: "pulling manifest".to_string(), ..Default::default() })
+        );
+        let layer = r#"{"status": "pulling 8eeb52dfb3bb", "digest": "sha256:8eeb52dfb3bb", "total": 397807936, "completed": 2097152}"#;
+        let progress = parse_pull_event(layer).unwrap().unwrap();
+        assert_eq!((progress.bytes_downloaded, progress.total_bytes), (2_097_152, 397_807_936));
+
+        assert_eq!(parse_pull_event("  ").unwrap(), None);
+        assert!(parse_pull_event(r#"{"error": "pull model manifest: file does not exist"}"#).is_err());
+        assert!(parse_pull_event("{\"status\": ").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_default_model_skips_slow_models() {
+        let mut manager = OllamaManager::new();
+        for model in [AiModel::QwenTiny, AiModel::QwenSmall] {
+            manager.status.available_models.insert(model, true);
+        }
+        let mut tiny = ModelPerformance::new(AiModel::QwenTiny);
+        for _ in 0..10 {
+            tiny.record_latency(Duration::from_secs(12));
+        }
+        manager.performance_tracker.insert(AiModel::QwenTiny, tiny);
+
+        manager.setup_default_model().await.unwrap();
+        assert_eq!(manager.get_active_model(), &AiModel::QwenSmall);
+
+        manager.update_performance_metrics(800, true, None);
+        let report = manager.performance_report();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].0, AiModel::QwenTiny);
+        assert_eq!(report[0].1.p95_latency, Duration::from_secs(12));
+        assert_eq!(report[1].1.total_requests, 1);
+    }
 }
\ No newline at end of file
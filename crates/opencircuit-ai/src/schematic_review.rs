@@ -0,0 +1,205 @@
+//! Focused peer review of a captured schematic
+//!
+//! The circuit is described to the LLM as a component list and netlist, and
+//! the review comes back as JSON. Component IDs the circuit does not contain
+//! are dropped; when an issue lists none, IDs named in its suggestion are used.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+use opencircuit_pcb::Severity;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::health_report::extract_json;
+use crate::AiResult;
+
+/// Aspect of the design the review concentrates on
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewFocus {
+    PowerSupply,
+    SignalIntegrity,
+    EMC,
+    Thermal,
+    Cost,
+}
+
+impl ReviewFocus {
+    fn instructions(&self) -> &'static str {
+        match self {
+            ReviewFocus::PowerSupply => "power supply design: regulation, decoupling, current capacity and sequencing",
+            ReviewFocus::SignalIntegrity => "signal integrity: loading, termination, impedance and noise coupling",
+            ReviewFocus::EMC => "electromagnetic compatibility: emissions, filtering, return paths and susceptibility",
+            ReviewFocus::Thermal => "thermal behaviour: power dissipation, derating and heat sinking",
+            ReviewFocus::Cost => "cost: part count, component grades and cheaper equivalents",
+        }
+    }
+}
+
+/// Problem raised by the review
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewIssue {
+    pub severity: Severity,
+    pub component_ids: Vec<String>,
+    pub suggestion: String,
+}
+
+/// Structured schematic review
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchematicReview {
+    pub focus: ReviewFocus,
+    pub issues: Vec<ReviewIssue>,
+}
+
+impl SchematicReview {
+    /// Parse the model's JSON review, keeping only IDs present in `circuit`
+    pub fn from_response(response: &str, circuit: &Circuit, focus: ReviewFocus) -> AiResult<Self> {
+        let json = extract_json(response)
+            .ok_or_else(|| OpenCircuitError::AiService("Schematic review contained no JSON object".to_string()))?;
+        let value: Value = serde_json::from_str(&json)
+            .map_err(|e| OpenCircuitError::AiService(format!("Invalid schematic review JSON: {}", e)))?;
+
+        let issues = value
+            .get("issues")
+            .and_then(Value::as_array)
+            .ok_or_else(|| OpenCircuitError::AiService("Schematic review contained no issues list".to_string()))?
+            .iter()
+            .filter_map(|issue| parse_issue(issue, circuit))
+            .collect();
+
+        Ok(Self { focus, issues })
+    }
+}
+
+/// Build the review prompt
+pub fn review_prompt(circuit: &Circuit, focus: ReviewFocus) -> String {
+    let mut prompt = format!(
+        "Review this schematic as an experienced engineer, focusing on {}.\n\nComponents:\n",
+        focus.instructions()
+    );
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or("(no value)")
+        ));
+    }
+    prompt.push_str("\nNetlist:\n");
+    for connection in &circuit.connections {
+        prompt.push_str(&format!("- {} -> {} on net {}\n", connection.from, connection.to, connection.net_name));
+    }
+
+    prompt.push_str(
+        "\nReference the affected components by the IDs listed above and respond with JSON only, in this schema:\n\
+         {\"issues\": [{\"severity\": \"error|warning|info\", \"component_ids\": [\"R1\"], \"suggestion\": \"...\"}]}",
+    );
+    prompt
+}
+
+fn parse_issue(issue: &Value, circuit: &Circuit) -> Option<ReviewIssue> {
+    let suggestion = issue.get("suggestion").and_then(Value::as_str)?.trim().to_string();
+    if suggestion.is_empty() {
+        return None;
+    }
+
+    let severity = match issue.get("severity").and_then(Value::as_str).map(str::to_lowercase).as_deref() {
+        Some("error") | Some("critical") | Some("high") => Severity::Error,
+        Some("info") | Some("low") | Some("note") => Severity::Info,
+        _ => Severity::Warning,
+    };
+
+    let listed: Vec<String> = match issue.get("component_ids") {
+        Some(Value::Array(ids)) => ids.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        Some(Value::String(id)) => vec![id.clone()],
+        _ => Vec::new(),
+    };
+    let mut component_ids: Vec<String> = circuit
+        .components
+        .iter()
+        .map(|component| component.id.clone())
+        .filter(|id| listed.iter().any(|listed| listed.trim().eq_ignore_ascii_case(id)))
+        .collect();
+    if component_ids.is_empty() {
+        component_ids = circuit
+            .components
+            .iter()
+            .map(|component| component.id.clone())
+            .filter(|id| mentions(&suggestion, id))
+            .collect();
+    }
+
+    Some(ReviewIssue { severity, component_ids, suggestion })
+}
+
+/// Whether `text` names `id` as a whole word
+fn mentions(text: &str, id: &str) -> bool {
+    text.split(|c: char| !c.is_ascii_alphanumeric() && c != '_').any(|word| word == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType, Connection};
+
+    fn regulator() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type, value) in [
+            ("V1", ComponentType::VoltageSource, "12V"),
+            ("U1", ComponentType::OpAmp, "LM358"),
+            ("C1", ComponentType::Capacitor, "10u"),
+        ] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type,
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        circuit.add_connection(Connection { from: "V1".to_string(), to: "U1".to_string(), net_name: "VCC".to_string() });
+        circuit
+    }
+
+    #[test]
+    fn test_prompt_lists_components_and_nets() {
+        let prompt = review_prompt(&regulator(), ReviewFocus::Thermal);
+        assert!(prompt.contains("focusing on thermal behaviour"));
+        assert!(prompt.contains("- U1 OpAmp LM358"));
+        assert!(prompt.contains("- V1 -> U1 on net VCC"));
+    }
+
+    #[test]
+    fn test_parse_review_keeps_known_components() {
+        let response = r#"```json
+            {"issues": [
+                {"severity": "error", "component_ids": ["u1", "U7"], "suggestion": "Add a 100n decoupling capacitor at U1"},
+                {"severity": "low", "component_ids": [], "suggestion": "C1 could be a cheaper X7R part"},
+                {"severity": "warning", "suggestion": ""},
+            ]}
+            ```"#;
+        let review = SchematicReview::from_response(response, &regulator(), ReviewFocus::PowerSupply).unwrap();
+        assert_eq!(review.focus, ReviewFocus::PowerSupply);
+        assert_eq!(
+            review.issues,
+            vec![
+                ReviewIssue {
+                    severity: Severity::Error,
+                    component_ids: vec!["U1".to_string()],
+                    suggestion: "Add a 100n decoupling capacitor at U1".to_string(),
+                },
+                ReviewIssue {
+                    severity: Severity::Info,
+                    component_ids: vec!["C1".to_string()],
+                    suggestion: "C1 could be a cheaper X7R part".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_review_without_issues() {
+        assert!(SchematicReview::from_response("Looks fine to me.", &regulator(), ReviewFocus::Cost).is_err());
+        assert!(SchematicReview::from_response(r#"{"summary": "ok"}"#, &regulator(), ReviewFocus::EMC).is_err());
+    }
+}
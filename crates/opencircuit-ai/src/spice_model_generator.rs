@@ -0,0 +1,238 @@
+//! AI-assisted SPICE model generation from datasheet specifications
+//!
+//! The model type is inferred from the component category and description,
+//! and the specifications relevant to that type are passed to the LLM. Its
+//! `.MODEL` statement is checked with the simulator's model parser; failures
+//! are sent back for correction up to [`MAX_MODEL_ATTEMPTS`] times.
+
+use opencircuit_core::models::{Component, ComponentCategory};
+use opencircuit_core::OpenCircuitError;
+use opencircuit_simulation::SpiceModel;
+
+use crate::AiResult;
+
+/// Maximum generate/correct rounds
+pub const MAX_MODEL_ATTEMPTS: usize = 3;
+
+/// What to ask the LLM for a component's model
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpiceModelRequest {
+    pub part_number: String,
+    /// Name the generated model is given
+    pub model_name: String,
+    /// SPICE model type (D, NPN, PNP, NMOS, PMOS)
+    pub model_type: &'static str,
+    /// Datasheet specifications relevant to the model type
+    pub key_parameters: Vec<(String, String)>,
+}
+
+impl SpiceModelRequest {
+    /// Work out the model type and key parameters for a component
+    pub fn for_component(component: &Component) -> AiResult<Self> {
+        let text = format!(
+            "{} {}",
+            component.description,
+            component.specifications.keys().cloned().collect::<Vec<_>>().join(" ")
+        )
+        .to_lowercase();
+        let model_type = match &component.category {
+            ComponentCategory::Diodes => "D",
+            ComponentCategory::Transistors if ["mosfet", "rds", "vgs", "fet"].iter().any(|hint| text.contains(hint)) => {
+                if ["p-channel", "p-ch", "pmos"].iter().any(|hint| text.contains(hint)) {
+                    "PMOS"
+                } else {
+                    "NMOS"
+                }
+            }
+            ComponentCategory::Transistors if text.contains("pnp") => "PNP",
+            ComponentCategory::Transistors => "NPN",
+            other => {
+                return Err(OpenCircuitError::AiService(format!(
+                    "No SPICE model type for {} components",
+                    other.as_str()
+                )))
+            }
+        };
+
+        let keywords = spec_keywords(model_type);
+        let mut key_parameters: Vec<(String, String)> = component
+            .specifications
+            .iter()
+            .filter(|(name, _)| {
+                let name = name.to_lowercase();
+                keywords.iter().any(|keyword| name.contains(keyword))
+            })
+            .map(|(name, value)| (name.clone(), value.as_string()))
+            .collect();
+        key_parameters.sort();
+
+        Ok(Self {
+            part_number: component.part_number.clone(),
+            model_name: model_name(&component.part_number),
+            model_type,
+            key_parameters,
+        })
+    }
+
+    /// Build the generation prompt
+    pub fn prompt(&self) -> String {
+        let mut prompt = format!(
+            "Write a SPICE .MODEL statement of type {} for the {}, named {}.\n",
+            self.model_type, self.part_number, self.model_name
+        );
+        if self.key_parameters.is_empty() {
+            prompt.push_str("No datasheet values are known; use typical values for this part.\n");
+        } else {
+            prompt.push_str("\nDatasheet values:\n");
+            for (name, value) in &self.key_parameters {
+                prompt.push_str(&format!("- {}: {}\n", name, value));
+            }
+        }
+        prompt.push_str(&format!(
+            "\nChoose plausible values for {} and respond with the single line only, e.g.:\n\
+             .MODEL {} {}({}=...)",
+            model_parameters(self.model_type).join(", "),
+            self.model_name,
+            self.model_type,
+            model_parameters(self.model_type)[0]
+        ));
+        prompt
+    }
+
+    /// Build a prompt asking the LLM to fix a rejected model
+    pub fn correction_prompt(&self, previous_response: &str, error: &OpenCircuitError) -> String {
+        format!(
+            "{}\n\nYour previous answer was rejected:\n{}\n\nError: {}\nReply with a corrected .MODEL line only.",
+            self.prompt(),
+            previous_response.trim(),
+            error
+        )
+    }
+
+    /// Extract and validate the `.MODEL` statement in a response
+    pub fn parse_response(&self, response: &str) -> AiResult<SpiceModel> {
+        let statement = model_statement(response)
+            .ok_or_else(|| OpenCircuitError::AiService("Response contained no .MODEL statement".to_string()))?;
+        let mut model = SpiceModel::parse(&statement)
+            .map_err(|e| OpenCircuitError::AiService(format!("Invalid .MODEL statement: {}", e)))?;
+
+        if model.model_type != self.model_type {
+            return Err(OpenCircuitError::AiService(format!(
+                "Expected a {} model, got {}",
+                self.model_type, model.model_type
+            )));
+        }
+        if model.parameters.is_empty() {
+            return Err(OpenCircuitError::AiService("Model has no parameters".to_string()));
+        }
+
+        model.name = self.model_name.clone();
+        model.ai_generated = true;
+        Ok(model)
+    }
+}
+
+/// Specification names worth passing on for each model type
+fn spec_keywords(model_type: &str) -> &'static [&'static str] {
+    match model_type {
+        "NMOS" | "PMOS" => &[
+            "vth", "threshold", "vgs", "gfs", "gm", "transconductance", "cgs", "cgd", "ciss", "coss", "crss", "rds", "vds",
+            "drain",
+        ],
+        "NPN" | "PNP" => &["hfe", "beta", "gain", "vce", "vbe", "collector", "ft", "transition", "cob", "cib"],
+        _ => &["vf", "forward", "vr", "reverse", "leakage", "capacitance", "trr", "recovery", "breakdown"],
+    }
+}
+
+/// SPICE parameters requested for each model type
+fn model_parameters(model_type: &str) -> &'static [&'static str] {
+    match model_type {
+        "NMOS" | "PMOS" => &["VTO", "KP", "LAMBDA", "CGSO", "CGDO", "RD", "RS"],
+        "NPN" | "PNP" => &["IS", "BF", "VAF", "CJE", "CJC", "TF"],
+        _ => &["IS", "N", "RS", "CJO", "BV", "TT"],
+    }
+}
+
+/// SPICE-safe model name for a part number
+fn model_name(part_number: &str) -> String {
+    let name: String = part_number
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    if name.is_empty() {
+        "AIMODEL".to_string()
+    } else {
+        name
+    }
+}
+
+/// The first `.MODEL` statement in `response`, with continuation lines joined
+fn model_statement(response: &str) -> Option<String> {
+    let mut lines = response.lines().map(|line| line.trim().trim_matches('`').trim());
+    let mut statement = lines.find(|line| line.to_lowercase().starts_with(".model"))?.to_string();
+    for line in lines {
+        match line.strip_prefix('+') {
+            Some(continuation) => {
+                statement.push(' ');
+                statement.push_str(continuation.trim());
+            }
+            None => break,
+        }
+    }
+    Some(statement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_core::models::SpecValue;
+
+    fn mosfet() -> Component {
+        let mut component = Component::new(
+            "IRLZ44N".to_string(),
+            "Infineon".to_string(),
+            ComponentCategory::Transistors,
+            "N-Channel 55V 47A logic level MOSFET".to_string(),
+        );
+        component.specifications.insert("Vgs(th)".to_string(), SpecValue::String("1-2V".to_string()));
+        component.specifications.insert("Rds On".to_string(), SpecValue::String("22mOhm".to_string()));
+        component.specifications.insert("Package".to_string(), SpecValue::String("TO-220".to_string()));
+        component
+    }
+
+    #[test]
+    fn test_request_for_mosfet() {
+        let request = SpiceModelRequest::for_component(&mosfet()).unwrap();
+        assert_eq!(request.model_type, "NMOS");
+        assert_eq!(request.model_name, "IRLZ44N");
+        assert_eq!(
+            request.key_parameters,
+            vec![("Rds On".to_string(), "22mOhm".to_string()), ("Vgs(th)".to_string(), "1-2V".to_string())]
+        );
+        assert!(request.prompt().contains("- Vgs(th): 1-2V"));
+
+        let connector = Component::new("X".to_string(), "Y".to_string(), ComponentCategory::Connectors, String::new());
+        assert!(SpiceModelRequest::for_component(&connector).is_err());
+    }
+
+    #[test]
+    fn test_parse_response_marks_model_generated() {
+        let request = SpiceModelRequest::for_component(&mosfet()).unwrap();
+        let model = request
+            .parse_response("Here you go:\n```\n.model irlz nmos(VTO=1.5 KP=40\n+ RD=0.011 RS=0.011)\n```")
+            .unwrap();
+        assert_eq!(model.name, "IRLZ44N");
+        assert_eq!(model.model_type, "NMOS");
+        assert_eq!(model.parameter("RS"), Some(0.011));
+        assert!(model.ai_generated);
+    }
+
+    #[test]
+    fn test_parse_response_rejects_invalid_models() {
+        let request = SpiceModelRequest::for_component(&mosfet()).unwrap();
+        assert!(request.parse_response("I don't know this part.").is_err());
+        assert!(request.parse_response(".MODEL IRLZ44N NMOS(VTO)").is_err());
+        assert!(request.parse_response(".MODEL IRLZ44N NPN(BF=100)").is_err());
+        assert!(request.parse_response(".MODEL IRLZ44N NMOS").is_err());
+    }
+}
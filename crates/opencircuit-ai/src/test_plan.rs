@@ -0,0 +1,204 @@
+//! AI-suggested bench test procedures for a finished design
+//!
+//! The LLM is asked for a power-up sequence, DC operating point checks,
+//! functional tests and stress tests, returned as JSON. Measurement points
+//! are matched against the circuit's net names so steps can be correlated
+//! with simulated node voltages; points naming no known net are dropped.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use crate::health_report::extract_json;
+use crate::value_optimizer::CircuitTargetSpecs;
+use crate::AiResult;
+
+/// One step of a test procedure
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestStep {
+    pub description: String,
+    pub equipment_needed: Vec<String>,
+    pub expected_result: String,
+    /// Net to probe, as named in the circuit
+    pub measurement_point: Option<String>,
+    pub pass_criteria: String,
+}
+
+/// Ordered verification procedure for a circuit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestPlan {
+    pub steps: Vec<TestStep>,
+}
+
+impl TestPlan {
+    /// Parse the model's JSON test plan against the nets of `circuit`
+    pub fn from_response(response: &str, circuit: &Circuit) -> AiResult<Self> {
+        let json = extract_json(response)
+            .ok_or_else(|| OpenCircuitError::AiService("Test plan contained no JSON object".to_string()))?;
+        let value: Value = serde_json::from_str(&json)
+            .map_err(|e| OpenCircuitError::AiService(format!("Invalid test plan JSON: {}", e)))?;
+
+        let nets = net_names(circuit);
+        let steps: Vec<TestStep> = value
+            .get("steps")
+            .and_then(Value::as_array)
+            .ok_or_else(|| OpenCircuitError::AiService("Test plan contained no steps list".to_string()))?
+            .iter()
+            .filter_map(|step| parse_step(step, &nets))
+            .collect();
+        if steps.is_empty() {
+            return Err(OpenCircuitError::AiService("Test plan contained no usable steps".to_string()));
+        }
+
+        Ok(Self { steps })
+    }
+}
+
+/// Build the test procedure prompt
+pub fn test_plan_prompt(circuit: &Circuit, target_specs: &CircuitTargetSpecs) -> String {
+    let mut prompt = String::from(
+        "Write a bench test procedure to verify this circuit after assembly.\n\nComponents:\n",
+    );
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or("(no value)")
+        ));
+    }
+    let nets = net_names(circuit);
+    prompt.push_str(&format!("\nNets: {}\n", nets.into_iter().collect::<Vec<_>>().join(", ")));
+
+    prompt.push_str(&format!(
+        "\nTargets:\n- Input node: {}\n- Output node: {}\n",
+        target_specs.input_node, target_specs.output_node
+    ));
+    if let Some(gain) = target_specs.gain {
+        prompt.push_str(&format!("- Gain: {} V/V\n", gain));
+    }
+    if let Some(bandwidth) = target_specs.bandwidth_hz {
+        prompt.push_str(&format!("- Bandwidth: {} Hz\n", bandwidth));
+    }
+    if let Some(impedance) = target_specs.output_impedance_ohms {
+        prompt.push_str(&format!("- Output impedance: {} ohms\n", impedance));
+    }
+    prompt.push_str(&format!("- Tolerance: {}%\n", target_specs.tolerance * 100.0));
+
+    prompt.push_str(
+        "\nCover, in order: the power-up sequence, DC operating point verification, functional tests \
+         against the targets, and stress tests. Give each measurement point as one of the net names \
+         above, or null. Respond with JSON only, in this schema:\n\
+         {\"steps\": [{\"description\": \"...\", \"equipment_needed\": [\"DMM\"], \"expected_result\": \"...\", \
+         \"measurement_point\": \"out\", \"pass_criteria\": \"...\"}]}",
+    );
+    prompt
+}
+
+fn net_names(circuit: &Circuit) -> BTreeSet<&str> {
+    circuit.connections.iter().map(|connection| connection.net_name.as_str()).collect()
+}
+
+fn parse_step(step: &Value, nets: &BTreeSet<&str>) -> Option<TestStep> {
+    let text = |key: &str| step.get(key).and_then(Value::as_str).map(str::trim).unwrap_or_default().to_string();
+
+    let description = text("description");
+    if description.is_empty() {
+        return None;
+    }
+
+    let equipment_needed = match step.get("equipment_needed") {
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).map(|item| item.trim().to_string()).collect(),
+        Some(Value::String(item)) => vec![item.trim().to_string()],
+        _ => Vec::new(),
+    };
+
+    let measurement_point = step
+        .get("measurement_point")
+        .and_then(Value::as_str)
+        .and_then(|point| known_net(point, nets));
+
+    Some(TestStep {
+        description,
+        equipment_needed,
+        expected_result: text("expected_result"),
+        measurement_point,
+        pass_criteria: text("pass_criteria"),
+    })
+}
+
+/// The circuit net a measurement point refers to, accepting `v(out)` style names
+fn known_net(point: &str, nets: &BTreeSet<&str>) -> Option<String> {
+    let point = point.trim();
+    let name = point
+        .strip_prefix("v(")
+        .or_else(|| point.strip_prefix("V("))
+        .and_then(|rest| rest.strip_suffix(')'))
+        .unwrap_or(point);
+    nets.iter().find(|net| net.eq_ignore_ascii_case(name.trim())).map(|net| net.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType, Connection};
+
+    fn amplifier() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type, value) in [
+            ("V1", ComponentType::VoltageSource, "5V"),
+            ("R1", ComponentType::Resistor, "10k"),
+            ("U1", ComponentType::OpAmp, "LM358"),
+        ] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type,
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        circuit.add_connection(Connection { from: "V1".to_string(), to: "R1".to_string(), net_name: "in".to_string() });
+        circuit.add_connection(Connection { from: "R1".to_string(), to: "U1".to_string(), net_name: "out".to_string() });
+        circuit
+    }
+
+    #[test]
+    fn test_prompt_lists_nets_and_targets() {
+        let prompt = test_plan_prompt(&amplifier(), &CircuitTargetSpecs::new("in", "out").with_gain(2.0));
+        assert!(prompt.contains("- U1 OpAmp LM358"));
+        assert!(prompt.contains("Nets: in, out"));
+        assert!(prompt.contains("- Gain: 2 V/V"));
+        assert!(prompt.contains("power-up sequence"));
+    }
+
+    #[test]
+    fn test_parse_plan_matches_circuit_nets() {
+        let response = r#"Here is the plan:
+            {"steps": [
+                {"description": "Apply 5V with the supply current limited to 50mA", "equipment_needed": ["Bench supply"],
+                 "expected_result": "Current below 10mA", "measurement_point": null, "pass_criteria": "I < 10mA"},
+                {"description": "Measure the output bias", "equipment_needed": ["DMM"],
+                 "expected_result": "2.5V", "measurement_point": "V(OUT)", "pass_criteria": "2.4V to 2.6V"},
+                {"description": "Probe the feedback node", "equipment_needed": "Oscilloscope",
+                 "expected_result": "Clean signal", "measurement_point": "fb", "pass_criteria": "No ringing"},
+                {"description": "", "pass_criteria": "n/a"},
+            ]}"#;
+        let plan = TestPlan::from_response(response, &amplifier()).unwrap();
+        assert_eq!(plan.steps.len(), 3);
+        assert_eq!(plan.steps[0].measurement_point, None);
+        assert_eq!(plan.steps[1].measurement_point.as_deref(), Some("out"));
+        assert_eq!(plan.steps[1].equipment_needed, vec!["DMM".to_string()]);
+        // Unknown nets are dropped rather than passed on
+        assert_eq!(plan.steps[2].measurement_point, None);
+        assert_eq!(plan.steps[2].equipment_needed, vec!["Oscilloscope".to_string()]);
+    }
+
+    #[test]
+    fn test_rejects_plan_without_steps() {
+        assert!(TestPlan::from_response("Power it up and see.", &amplifier()).is_err());
+        assert!(TestPlan::from_response(r#"{"steps": []}"#, &amplifier()).is_err());
+    }
+}
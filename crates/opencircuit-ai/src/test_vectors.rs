@@ -0,0 +1,229 @@
+//! AI-generated test vectors for digital circuits
+//!
+//! The LLM works out the logic function from the components and connections
+//! and lists input stimuli with the outputs they should produce. Vectors are
+//! kept in the order given, since sequential circuits depend on the previous
+//! state. Every vector must assign every requested pin; the rest are dropped.
+//! [`TestVector::input_voltages`] and [`TestVector::failing_outputs`] turn a
+//! vector into simulation stimulus and check an operating point against it.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+use opencircuit_simulation::DCResults;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::health_report::extract_json;
+use crate::AiResult;
+
+/// Input stimulus and the logic levels it should produce
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestVector {
+    pub inputs: HashMap<String, bool>,
+    pub expected_outputs: HashMap<String, bool>,
+    pub description: String,
+}
+
+impl TestVector {
+    /// Voltage to drive on each input pin, `supply` for high and 0 V for low
+    pub fn input_voltages(&self, supply: f64) -> HashMap<String, f64> {
+        self.inputs
+            .iter()
+            .map(|(pin, high)| (pin.clone(), if *high { supply } else { 0.0 }))
+            .collect()
+    }
+
+    /// Output pins whose simulated level differs from the expected one
+    ///
+    /// Node voltages above half of `supply` read as high. Outputs missing
+    /// from the results count as failing. Pins are returned sorted.
+    pub fn failing_outputs(&self, results: &DCResults, supply: f64) -> Vec<String> {
+        let mut failing: Vec<String> = self
+            .expected_outputs
+            .iter()
+            .filter(|(pin, expected)| {
+                results.node_voltages.get(pin.as_str()).map(|voltage| *voltage > supply / 2.0) != Some(**expected)
+            })
+            .map(|(pin, _)| pin.clone())
+            .collect();
+        failing.sort();
+        failing
+    }
+}
+
+/// Build the test vector prompt
+pub fn test_vectors_prompt(circuit: &Circuit, input_pins: &[&str], output_pins: &[&str], num_vectors: usize) -> String {
+    let mut prompt = String::from("Generate functional test vectors for this digital circuit.\n\nComponents:\n");
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or("(no value)")
+        ));
+    }
+    prompt.push_str("\nConnections:\n");
+    for connection in &circuit.connections {
+        prompt.push_str(&format!("- {} -> {} on {}\n", connection.from, connection.to, connection.net_name));
+    }
+
+    prompt.push_str(&format!(
+        "\nInputs: {}\nOutputs: {}\n\n\
+         Work out the logic function first (gates, flip-flops, counters). Then give {} vectors that cover \
+         every input combination and every internal state; for sequential logic list the vectors in the \
+         order they are applied and give clock edges as separate vectors. Assign every input and every \
+         output as true (high) or false (low). Respond with JSON only, in this schema:\n\
+         {{\"vectors\": [{{\"inputs\": {{\"A\": true}}, \"expected_outputs\": {{\"Y\": false}}, \
+         \"description\": \"...\"}}]}}",
+        input_pins.join(", "),
+        output_pins.join(", "),
+        num_vectors
+    ));
+    prompt
+}
+
+/// Parse the model's vectors, keeping at most `num_vectors`
+pub fn parse_test_vectors(
+    response: &str,
+    input_pins: &[&str],
+    output_pins: &[&str],
+    num_vectors: usize,
+) -> AiResult<Vec<TestVector>> {
+    let json = extract_json(response)
+        .ok_or_else(|| OpenCircuitError::AiService("Test vectors contained no JSON object".to_string()))?;
+    let value: Value = serde_json::from_str(&json)
+        .map_err(|e| OpenCircuitError::AiService(format!("Invalid test vector JSON: {}", e)))?;
+
+    let vectors: Vec<TestVector> = value
+        .get("vectors")
+        .and_then(Value::as_array)
+        .ok_or_else(|| OpenCircuitError::AiService("Response contained no vectors list".to_string()))?
+        .iter()
+        .filter_map(|vector| {
+            Some(TestVector {
+                inputs: pin_levels(vector.get("inputs")?, input_pins)?,
+                expected_outputs: pin_levels(vector.get("expected_outputs")?, output_pins)?,
+                description: vector.get("description").and_then(Value::as_str).unwrap_or_default().trim().to_string(),
+            })
+        })
+        .take(num_vectors)
+        .collect();
+
+    if vectors.is_empty() {
+        return Err(OpenCircuitError::AiService("Response contained no complete test vectors".to_string()));
+    }
+    Ok(vectors)
+}
+
+/// Level of every pin in `pins`, or `None` if any is missing or unreadable
+fn pin_levels(levels: &Value, pins: &[&str]) -> Option<HashMap<String, bool>> {
+    let levels = levels.as_object()?;
+    pins.iter()
+        .map(|pin| {
+            let level = levels
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(pin))
+                .and_then(|(_, level)| logic_level(level))?;
+            Some((pin.to_string(), level))
+        })
+        .collect()
+}
+
+/// Read `true`, `1`, `"1"`, `"high"` and their opposites
+fn logic_level(level: &Value) -> Option<bool> {
+    match level {
+        Value::Bool(level) => Some(*level),
+        Value::Number(level) => match level.as_u64()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        },
+        Value::String(level) => match level.trim().to_ascii_lowercase().as_str() {
+            "1" | "high" | "h" | "true" => Some(true),
+            "0" | "low" | "l" | "false" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType, Connection};
+
+    /// Resistor-transistor logic NOR gate
+    fn rtl_nor() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type, value) in [
+            ("RA", ComponentType::Resistor, "10k"),
+            ("RB", ComponentType::Resistor, "10k"),
+            ("Q1", ComponentType::Transistor, "2N3904"),
+            ("Q2", ComponentType::Transistor, "2N3904"),
+            ("RC", ComponentType::Resistor, "1k"),
+        ] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type,
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        for (from, to, net) in [("RA", "Q1", "a_base"), ("RB", "Q2", "b_base"), ("Q1", "RC", "Y"), ("Q2", "RC", "Y")] {
+            circuit.add_connection(Connection { from: from.to_string(), to: to.to_string(), net_name: net.to_string() });
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_parse_vectors_requires_every_pin() {
+        let prompt = test_vectors_prompt(&rtl_nor(), &["A", "B"], &["Y"], 4);
+        assert!(prompt.contains("- Q1 Transistor 2N3904"));
+        assert!(prompt.contains("- Q2 -> RC on Y"));
+        assert!(prompt.contains("Inputs: A, B\nOutputs: Y"));
+
+        let response = r#"The circuit is a two-input RTL NOR gate.
+            {"vectors": [
+                {"inputs": {"A": false, "B": false}, "expected_outputs": {"Y": true}, "description": "both low"},
+                {"inputs": {"a": 1, "b": "0"}, "expected_outputs": {"Y": "low"}, "description": "A only"},
+                {"inputs": {"A": true}, "expected_outputs": {"Y": false}, "description": "B missing"},
+                {"inputs": {"A": "high", "B": "high"}, "expected_outputs": {"Y": 0}, "description": "both high"}
+            ]}"#;
+        let vectors = parse_test_vectors(response, &["A", "B"], &["Y"], 4).unwrap();
+        assert_eq!(vectors.len(), 3);
+        assert_eq!(vectors[1].inputs, HashMap::from([("A".to_string(), true), ("B".to_string(), false)]));
+        assert!(vectors[0].expected_outputs["Y"]);
+        assert!(!vectors[2].expected_outputs["Y"]);
+
+        assert_eq!(parse_test_vectors(response, &["A", "B"], &["Y"], 1).unwrap().len(), 1);
+        assert!(parse_test_vectors(r#"{"vectors": []}"#, &["A"], &["Y"], 4).is_err());
+        assert!(parse_test_vectors("It is a NOR gate.", &["A"], &["Y"], 4).is_err());
+    }
+
+    #[test]
+    fn test_vector_checks_simulated_outputs() {
+        let vector = TestVector {
+            inputs: HashMap::from([("A".to_string(), true), ("B".to_string(), false)]),
+            expected_outputs: HashMap::from([("Y".to_string(), false), ("Z".to_string(), true)]),
+            description: "A only".to_string(),
+        };
+        assert_eq!(vector.input_voltages(3.3)["A"], 3.3);
+        assert_eq!(vector.input_voltages(3.3)["B"], 0.0);
+
+        let results = DCResults {
+            node_voltages: HashMap::from([("Y".to_string(), 0.1), ("Z".to_string(), 3.2)]),
+            branch_currents: HashMap::new(),
+            power_dissipation: HashMap::new(),
+            sweep_data: None,
+        };
+        assert!(vector.failing_outputs(&results, 3.3).is_empty());
+
+        let stuck_high = DCResults {
+            node_voltages: HashMap::from([("Y".to_string(), 3.3)]),
+            ..results
+        };
+        assert_eq!(vector.failing_outputs(&stuck_high, 3.3), ["Y", "Z"]);
+    }
+}
@@ -0,0 +1,261 @@
+//! AI-assisted selection of unknown component values
+//!
+//! Components whose value is missing or set to [`UNKNOWN_VALUE`] are filled in
+//! by the LLM. When NgSpice is available the suggestion is checked against the
+//! DC gain target and the model is asked to correct values that miss it.
+//! Bandwidth and output impedance targets guide the model but are not
+//! measured, since the generated netlists carry no AC stimulus.
+
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+use opencircuit_simulation::worst_case::parse_node_voltages;
+use opencircuit_simulation::{CancellationToken, SimulationEngine};
+use opencircuit_utils::units::parse_spice_value;
+use serde::{Deserialize, Serialize};
+
+use crate::AiResult;
+
+/// Placeholder value marking a component to be chosen
+pub const UNKNOWN_VALUE: &str = "?";
+
+/// Maximum suggest/verify rounds
+pub const MAX_OPTIMIZATION_ATTEMPTS: usize = 3;
+
+/// Design targets for value selection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitTargetSpecs {
+    pub input_node: String,
+    pub output_node: String,
+    /// Voltage gain (V/V)
+    pub gain: Option<f64>,
+    /// -3 dB bandwidth (Hz)
+    pub bandwidth_hz: Option<f64>,
+    pub output_impedance_ohms: Option<f64>,
+    /// Allowed relative error when verifying, e.g. 0.1 for 10%
+    pub tolerance: f64,
+}
+
+impl CircuitTargetSpecs {
+    pub fn new(input_node: &str, output_node: &str) -> Self {
+        Self {
+            input_node: input_node.to_string(),
+            output_node: output_node.to_string(),
+            gain: None,
+            bandwidth_hz: None,
+            output_impedance_ohms: None,
+            tolerance: 0.1,
+        }
+    }
+
+    pub fn with_gain(mut self, gain: f64) -> Self {
+        self.gain = Some(gain);
+        self
+    }
+
+    pub fn with_bandwidth(mut self, bandwidth_hz: f64) -> Self {
+        self.bandwidth_hz = Some(bandwidth_hz);
+        self
+    }
+
+    pub fn with_output_impedance(mut self, ohms: f64) -> Self {
+        self.output_impedance_ohms = Some(ohms);
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Reasons a measured DC gain misses the target; empty when it passes
+    pub fn gain_failures(&self, measured_gain: f64) -> Vec<String> {
+        match self.gain {
+            Some(target) if (measured_gain - target).abs() > self.tolerance * target.abs() => vec![format!(
+                "Simulated gain is {:.4} V/V but the target is {:.4} V/V (±{:.0}%)",
+                measured_gain,
+                target,
+                self.tolerance * 100.0
+            )],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Suggestions {
+    values: Vec<Suggestion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Suggestion {
+    component_id: String,
+    value: String,
+    #[serde(default)]
+    reasoning: String,
+}
+
+/// Ids of components whose value is missing or unknown
+pub fn unknown_components(circuit: &Circuit) -> Vec<String> {
+    circuit
+        .components
+        .iter()
+        .filter(|component| match component.value.as_deref() {
+            Some(value) => value.trim() == UNKNOWN_VALUE,
+            None => true,
+        })
+        .map(|component| component.id.clone())
+        .collect()
+}
+
+/// Build the prompt asking for values, with feedback from the previous round
+pub fn optimization_prompt(
+    circuit: &Circuit,
+    specs: &CircuitTargetSpecs,
+    unknown: &[String],
+    feedback: Option<&str>,
+) -> String {
+    let mut prompt = String::from("Choose component values for this circuit.\n\nComponents:\n");
+    for component in &circuit.components {
+        prompt.push_str(&format!(
+            "- {} {:?} = {}\n",
+            component.id,
+            component.component_type,
+            component.value.as_deref().unwrap_or(UNKNOWN_VALUE)
+        ));
+    }
+
+    prompt.push_str("\nConnections:\n");
+    for connection in &circuit.connections {
+        prompt.push_str(&format!("- {} -> {} on net {}\n", connection.from, connection.to, connection.net_name));
+    }
+
+    prompt.push_str(&format!("\nTargets (input {}, output {}):\n", specs.input_node, specs.output_node));
+    if let Some(gain) = specs.gain {
+        prompt.push_str(&format!("- Voltage gain: {} V/V\n", gain));
+    }
+    if let Some(bandwidth) = specs.bandwidth_hz {
+        prompt.push_str(&format!("- Bandwidth: {} Hz\n", bandwidth));
+    }
+    if let Some(impedance) = specs.output_impedance_ohms {
+        prompt.push_str(&format!("- Output impedance: {} ohms\n", impedance));
+    }
+
+    if let Some(feedback) = feedback {
+        prompt.push_str(&format!("\nYour previous values did not meet the targets:\n{}\n", feedback));
+    }
+
+    prompt.push_str(&format!(
+        "\nPick standard E-series values for {}. Respond with JSON only:\n\
+         {{\"values\": [{{\"component_id\": \"R1\", \"value\": \"10k\", \"reasoning\": \"...\"}}]}}",
+        unknown.join(", ")
+    ));
+    prompt
+}
+
+/// Extract `(component_id, value)` pairs for every unknown component
+pub fn parse_suggestions(response: &str, unknown: &[String]) -> AiResult<Vec<(String, String)>> {
+    let json = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => return Err(OpenCircuitError::AiService("Response contained no JSON object".to_string())),
+    };
+    let suggestions: Suggestions = serde_json::from_str(json)
+        .map_err(|e| OpenCircuitError::AiService(format!("Invalid value suggestions: {}", e)))?;
+
+    unknown
+        .iter()
+        .map(|id| {
+            let suggestion = suggestions
+                .values
+                .iter()
+                .find(|suggestion| suggestion.component_id.eq_ignore_ascii_case(id))
+                .ok_or_else(|| OpenCircuitError::AiService(format!("No value suggested for {}", id)))?;
+            parse_spice_value(&suggestion.value).map_err(|e| {
+                OpenCircuitError::AiService(format!("Invalid value '{}' for {}: {}", suggestion.value, id, e))
+            })?;
+            tracing::debug!("{} = {}: {}", id, suggestion.value, suggestion.reasoning);
+            Ok((id.clone(), suggestion.value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Copy of `circuit` with the suggested values filled in
+pub fn apply_values(circuit: &Circuit, values: &[(String, String)]) -> Circuit {
+    let mut circuit = circuit.clone();
+    for component in &mut circuit.components {
+        if let Some((_, value)) = values.iter().find(|(id, _)| *id == component.id) {
+            component.value = Some(value.clone());
+        }
+    }
+    circuit
+}
+
+/// DC gain from an operating-point simulation, `None` when it cannot be measured
+pub async fn simulate_dc_gain(engine: &mut SimulationEngine, circuit: &Circuit, specs: &CircuitTargetSpecs) -> Option<f64> {
+    let results = engine.simulate_circuit(circuit, CancellationToken::new()).await.ok()?;
+    let voltages = parse_node_voltages(results.raw_output()?);
+    let voltage = |node: &str| voltages.get(&format!("v({})", node.to_lowercase()))?.last().copied();
+
+    let input = voltage(&specs.input_node)?;
+    let output = voltage(&specs.output_node)?;
+    (input != 0.0).then(|| output / input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType};
+
+    fn divider() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, value) in [("R1", Some("?")), ("R2", None), ("V1", Some("5"))] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type: if id == "V1" { ComponentType::VoltageSource } else { ComponentType::Resistor },
+                value: value.map(str::to_string),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_unknown_components_and_prompt() {
+        let circuit = divider();
+        let unknown = unknown_components(&circuit);
+        assert_eq!(unknown, vec!["R1".to_string(), "R2".to_string()]);
+
+        let specs = CircuitTargetSpecs::new("in", "out").with_gain(0.5);
+        let prompt = optimization_prompt(&circuit, &specs, &unknown, Some("Simulated gain is 0.3"));
+        assert!(prompt.contains("- V1 VoltageSource = 5"));
+        assert!(prompt.contains("- Voltage gain: 0.5 V/V"));
+        assert!(prompt.contains("Simulated gain is 0.3"));
+        assert!(prompt.contains("values for R1, R2"));
+    }
+
+    #[test]
+    fn test_parse_suggestions() {
+        let unknown = vec!["R1".to_string(), "R2".to_string()];
+        let response = r#"Sure! {"values": [
+            {"component_id": "R1", "value": "10k", "reasoning": "top of divider"},
+            {"component_id": "r2", "value": "10k"}
+        ]}"#;
+        let values = parse_suggestions(response, &unknown).unwrap();
+        assert_eq!(values, vec![("R1".to_string(), "10k".to_string()), ("R2".to_string(), "10k".to_string())]);
+
+        let circuit = apply_values(&divider(), &values);
+        assert!(unknown_components(&circuit).is_empty());
+
+        assert!(parse_suggestions(r#"{"values": [{"component_id": "R1", "value": "10k"}]}"#, &unknown).is_err());
+        assert!(parse_suggestions(r#"{"values": [{"component_id": "R1", "value": "abc"}]}"#, &unknown[..1]).is_err());
+        assert!(parse_suggestions("no json here", &unknown).is_err());
+    }
+
+    #[test]
+    fn test_gain_failures() {
+        let specs = CircuitTargetSpecs::new("in", "out").with_gain(10.0).with_tolerance(0.05);
+        assert!(specs.gain_failures(10.4).is_empty());
+        assert_eq!(specs.gain_failures(9.0).len(), 1);
+        assert!(CircuitTargetSpecs::new("in", "out").gain_failures(9.0).is_empty());
+    }
+}
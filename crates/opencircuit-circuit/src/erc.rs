@@ -0,0 +1,252 @@
+//! Electrical rule checks on circuit connectivity
+//!
+//! Connections carry no pin information, so each component's pin on a net is
+//! typed from its component type: sources are power pins and everything else
+//! is passive. Input and output pins, e.g. of op amps, are declared with
+//! `CircuitErc::with_pin_type`.
+
+use crate::{Circuit, ComponentType};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Nets treated as the common reference, where many sources may connect
+const GROUND_NETS: [&str; 6] = ["0", "GND", "AGND", "DGND", "GROUND", "VSS"];
+
+/// Electrical rule to check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErcRule {
+    /// Nets connected only to inputs, with nothing driving them
+    UndrivenNet,
+    /// Nets driven by more than one power source
+    MultipleDrivers,
+    /// Nets that reach only one component
+    StubNet,
+    /// Outputs connected to other outputs or to power
+    OutputConflict,
+}
+
+impl ErcRule {
+    pub const ALL: [ErcRule; 4] =
+        [ErcRule::UndrivenNet, ErcRule::MultipleDrivers, ErcRule::StubNet, ErcRule::OutputConflict];
+}
+
+/// Electrical role of a component's pin on a net
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PinType {
+    Input,
+    Output,
+    Power,
+    Passive,
+}
+
+impl PinType {
+    /// Pin type assumed when none is declared
+    pub fn default_for(component_type: &ComponentType) -> Self {
+        match component_type {
+            ComponentType::VoltageSource | ComponentType::CurrentSource => PinType::Power,
+            _ => PinType::Passive,
+        }
+    }
+}
+
+/// ERC configuration: the rules to run and declared pin types
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitErc {
+    pub rules: Vec<ErcRule>,
+    /// Pin types keyed by (component ID, net name)
+    pub pin_types: HashMap<(String, String), PinType>,
+}
+
+impl CircuitErc {
+    /// Configuration running the given rules
+    pub fn new(rules: Vec<ErcRule>) -> Self {
+        Self { rules, pin_types: HashMap::new() }
+    }
+
+    /// Declare the type of a component's pin on a net
+    pub fn with_pin_type(mut self, component_id: &str, net_name: &str, pin_type: PinType) -> Self {
+        self.pin_types.insert((component_id.to_string(), net_name.to_string()), pin_type);
+        self
+    }
+}
+
+impl Default for CircuitErc {
+    fn default() -> Self {
+        Self::new(ErcRule::ALL.to_vec())
+    }
+}
+
+/// Connectivity problem found by the ERC
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErcViolation {
+    pub rule: ErcRule,
+    pub net_name: String,
+    pub component_ids: Vec<String>,
+    pub description: String,
+}
+
+impl Circuit {
+    /// Check net connectivity, returning violations ordered by net then rule
+    pub fn run_erc(&self, erc: &CircuitErc) -> Vec<ErcViolation> {
+        let mut nets: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for connection in &self.connections {
+            let members = nets.entry(connection.net_name.as_str()).or_default();
+            for component_id in [&connection.from, &connection.to] {
+                if !component_id.is_empty() {
+                    members.insert(component_id.as_str());
+                }
+            }
+        }
+
+        let mut violations = Vec::new();
+        for (net_name, members) in nets {
+            let pins: Vec<(&str, PinType)> =
+                members.iter().map(|&component_id| (component_id, self.pin_type(erc, component_id, net_name))).collect();
+            let with_type = |wanted: &[PinType]| -> Vec<String> {
+                pins.iter().filter(|(_, pin)| wanted.contains(pin)).map(|(id, _)| id.to_string()).collect()
+            };
+            let violation = |rule: ErcRule, component_ids: Vec<String>, description: String| ErcViolation {
+                rule,
+                net_name: net_name.to_string(),
+                component_ids,
+                description,
+            };
+
+            for rule in &erc.rules {
+                match rule {
+                    ErcRule::UndrivenNet => {
+                        if !pins.is_empty() && pins.iter().all(|(_, pin)| *pin == PinType::Input) {
+                            let inputs = with_type(&[PinType::Input]);
+                            violations.push(violation(
+                                *rule,
+                                inputs.clone(),
+                                format!("Net {} connects only inputs ({}) and has no driver", net_name, inputs.join(", ")),
+                            ));
+                        }
+                    }
+                    ErcRule::MultipleDrivers => {
+                        let sources = with_type(&[PinType::Power]);
+                        if sources.len() > 1 && !is_ground(net_name) {
+                            violations.push(violation(
+                                *rule,
+                                sources.clone(),
+                                format!("Net {} is driven by {} sources: {}", net_name, sources.len(), sources.join(", ")),
+                            ));
+                        }
+                    }
+                    ErcRule::StubNet => {
+                        if pins.len() == 1 {
+                            violations.push(violation(
+                                *rule,
+                                vec![pins[0].0.to_string()],
+                                format!("Net {} only connects to {}", net_name, pins[0].0),
+                            ));
+                        }
+                    }
+                    ErcRule::OutputConflict => {
+                        let outputs = with_type(&[PinType::Output]);
+                        let drivers = with_type(&[PinType::Output, PinType::Power]);
+                        if !outputs.is_empty() && drivers.len() > 1 {
+                            violations.push(violation(
+                                *rule,
+                                drivers.clone(),
+                                format!("Net {} has outputs driving each other: {}", net_name, drivers.join(", ")),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+
+    fn pin_type(&self, erc: &CircuitErc, component_id: &str, net_name: &str) -> PinType {
+        if let Some(pin_type) = erc.pin_types.get(&(component_id.to_string(), net_name.to_string())) {
+            return *pin_type;
+        }
+        self.components
+            .iter()
+            .find(|component| component.id == component_id)
+            .map_or(PinType::Passive, |component| PinType::default_for(&component.component_type))
+    }
+}
+
+pub(crate) fn is_ground(net_name: &str) -> bool {
+    GROUND_NETS.iter().any(|ground| ground.eq_ignore_ascii_case(net_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, Connection};
+
+    fn circuit(components: &[(&str, ComponentType)], connections: &[(&str, &str, &str)]) -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type) in components {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type: component_type.clone(),
+                value: None,
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        for (from, to, net) in connections {
+            circuit.add_connection(Connection { from: from.to_string(), to: to.to_string(), net_name: net.to_string() });
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_clean_divider_passes() {
+        let divider = circuit(
+            &[("V1", ComponentType::VoltageSource), ("R1", ComponentType::Resistor), ("R2", ComponentType::Resistor)],
+            &[("V1", "R1", "VIN"), ("R1", "R2", "VOUT"), ("R2", "V1", "GND")],
+        );
+        assert!(divider.run_erc(&CircuitErc::default()).is_empty());
+    }
+
+    #[test]
+    fn test_multiple_drivers_and_stubs() {
+        let circuit = circuit(
+            &[("V1", ComponentType::VoltageSource), ("V2", ComponentType::VoltageSource), ("R1", ComponentType::Resistor)],
+            &[("V1", "V2", "VCC"), ("V1", "V2", "0"), ("R1", "", "NC1")],
+        );
+        let violations = circuit.run_erc(&CircuitErc::default());
+        assert_eq!(violations.len(), 2);
+
+        assert_eq!(violations[0].rule, ErcRule::StubNet);
+        assert_eq!(violations[0].net_name, "NC1");
+        assert_eq!(violations[0].component_ids, vec!["R1".to_string()]);
+
+        // Sharing ground is fine, sharing a supply rail is not
+        assert_eq!(violations[1].rule, ErcRule::MultipleDrivers);
+        assert_eq!(violations[1].net_name, "VCC");
+        assert_eq!(violations[1].component_ids, vec!["V1".to_string(), "V2".to_string()]);
+        assert_eq!(violations[1].description, "Net VCC is driven by 2 sources: V1, V2");
+
+        let only_stubs = CircuitErc::new(vec![ErcRule::StubNet]);
+        assert_eq!(circuit.run_erc(&only_stubs).len(), 1);
+    }
+
+    #[test]
+    fn test_declared_pin_types() {
+        let circuit = circuit(
+            &[("U1", ComponentType::OpAmp), ("U2", ComponentType::OpAmp), ("U3", ComponentType::OpAmp)],
+            &[("U1", "U2", "SIG"), ("U2", "U3", "OUT")],
+        );
+        let erc = CircuitErc::default()
+            .with_pin_type("U1", "SIG", PinType::Input)
+            .with_pin_type("U2", "SIG", PinType::Input)
+            .with_pin_type("U2", "OUT", PinType::Output)
+            .with_pin_type("U3", "OUT", PinType::Output);
+
+        let violations = circuit.run_erc(&erc);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].rule, ErcRule::OutputConflict);
+        assert_eq!(violations[0].net_name, "OUT");
+        assert_eq!(violations[1].rule, ErcRule::UndrivenNet);
+        assert_eq!(violations[1].component_ids, vec!["U1".to_string(), "U2".to_string()]);
+    }
+}
@@ -0,0 +1,222 @@
+//! Hierarchical composition of circuits
+//!
+//! Sub-circuits such as a power supply or amplifier section can be designed
+//! separately and merged into one schematic. Nets are joined by name, so a
+//! `GND` net in each part becomes a single net. Components are renamed only
+//! when their IDs clash.
+
+use crate::{Circuit, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Connection cut when components were extracted into a child circuit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoundaryPort {
+    /// Component inside the extracted circuit
+    pub internal: String,
+    /// Component left outside
+    pub external: String,
+    pub net_name: String,
+    /// Whether the cut connection ran from the internal component
+    pub outgoing: bool,
+}
+
+impl BoundaryPort {
+    /// The connection this port replaced
+    pub fn to_connection(&self) -> Connection {
+        let (from, to) = if self.outgoing {
+            (&self.internal, &self.external)
+        } else {
+            (&self.external, &self.internal)
+        };
+        Connection { from: from.clone(), to: to.clone(), net_name: self.net_name.clone() }
+    }
+}
+
+impl Circuit {
+    /// Combine this circuit with `other`
+    ///
+    /// Components from `other` whose IDs are already taken get a numeric
+    /// suffix (`R1` becomes `R1_2`), and its connections follow the rename.
+    /// Subcircuit definitions already present in this circuit are kept.
+    pub fn merge(&self, other: &Circuit) -> Circuit {
+        let mut taken: HashSet<String> = self
+            .components
+            .iter()
+            .chain(&other.components)
+            .map(|component| component.id.clone())
+            .collect();
+        let existing: HashSet<&str> = self.components.iter().map(|component| component.id.as_str()).collect();
+
+        let mut renames = HashMap::new();
+        for component in &other.components {
+            if existing.contains(component.id.as_str()) && !renames.contains_key(&component.id) {
+                let id = unique_id(&component.id, &taken);
+                taken.insert(id.clone());
+                renames.insert(component.id.clone(), id);
+            }
+        }
+        let rename = |id: &String| renames.get(id).cloned().unwrap_or_else(|| id.clone());
+
+        let mut merged = self.clone();
+        merged.components.extend(other.components.iter().map(|component| {
+            let mut component = component.clone();
+            component.id = rename(&component.id);
+            component
+        }));
+        merged.connections.extend(other.connections.iter().map(|connection| Connection {
+            from: rename(&connection.from),
+            to: rename(&connection.to),
+            net_name: connection.net_name.clone(),
+        }));
+
+        for subcircuit in &other.subcircuits {
+            if merged.get_subcircuit(&subcircuit.name).is_none() {
+                merged.subcircuits.push(subcircuit.clone());
+            }
+        }
+        let mut instance_names: HashSet<String> =
+            merged.subcircuit_instances.iter().map(|instance| instance.instance_name.clone()).collect();
+        for instance in &other.subcircuit_instances {
+            let mut instance = instance.clone();
+            if instance_names.contains(&instance.instance_name) {
+                instance.instance_name = unique_id(&instance.instance_name, &instance_names);
+            }
+            instance_names.insert(instance.instance_name.clone());
+            merged.subcircuit_instances.push(instance);
+        }
+
+        merged
+    }
+
+    /// Prefix every component ID and connection endpoint, e.g. `PSU_` turning `R1` into `PSU_R1`
+    pub fn prefix_components(&mut self, prefix: &str) {
+        for component in &mut self.components {
+            component.id = format!("{}{}", prefix, component.id);
+        }
+        for connection in &mut self.connections {
+            connection.from = format!("{}{}", prefix, connection.from);
+            connection.to = format!("{}{}", prefix, connection.to);
+        }
+    }
+
+    /// Copy the given components and the connections between them into a child circuit
+    ///
+    /// Connections between a listed component and one outside the list are
+    /// returned as boundary ports. Subcircuit definitions and instances are
+    /// not copied.
+    pub fn extract_subcircuit(&self, component_ids: &[&str]) -> (Circuit, Vec<BoundaryPort>) {
+        let inside = |id: &str| component_ids.contains(&id);
+
+        let mut child = Circuit::new();
+        child.components = self.components.iter().filter(|component| inside(&component.id)).cloned().collect();
+
+        let mut ports = Vec::new();
+        for connection in &self.connections {
+            match (inside(&connection.from), inside(&connection.to)) {
+                (true, true) => child.connections.push(connection.clone()),
+                (true, false) => ports.push(BoundaryPort {
+                    internal: connection.from.clone(),
+                    external: connection.to.clone(),
+                    net_name: connection.net_name.clone(),
+                    outgoing: true,
+                }),
+                (false, true) => ports.push(BoundaryPort {
+                    internal: connection.to.clone(),
+                    external: connection.from.clone(),
+                    net_name: connection.net_name.clone(),
+                    outgoing: false,
+                }),
+                (false, false) => {}
+            }
+        }
+
+        (child, ports)
+    }
+}
+
+/// `base` with the lowest numeric suffix not in `taken`
+fn unique_id(base: &str, taken: &HashSet<String>) -> String {
+    (2..)
+        .map(|n| format!("{}_{}", base, n))
+        .find(|id| !taken.contains(id))
+        .unwrap_or_else(|| base.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, ComponentType};
+
+    fn component(id: &str, component_type: ComponentType) -> Component {
+        Component { id: id.to_string(), component_type, value: None, position: (0.0, 0.0), purpose: None }
+    }
+
+    fn connection(from: &str, to: &str, net_name: &str) -> Connection {
+        Connection { from: from.to_string(), to: to.to_string(), net_name: net_name.to_string() }
+    }
+
+    fn amplifier() -> Circuit {
+        let mut circuit = Circuit::new();
+        circuit.add_component(component("V1", ComponentType::VoltageSource));
+        circuit.add_component(component("R1", ComponentType::Resistor));
+        circuit.add_component(component("U1", ComponentType::OpAmp));
+        circuit.add_component(component("RF", ComponentType::Resistor));
+        circuit.add_connection(connection("V1", "R1", "IN"));
+        circuit.add_connection(connection("R1", "U1", "INV"));
+        circuit.add_connection(connection("RF", "U1", "INV"));
+        circuit.add_connection(connection("U1", "RF", "OUT"));
+        circuit
+    }
+
+    /// Components and connections regardless of order
+    fn structure(circuit: &Circuit) -> (Vec<String>, Vec<(String, String, String)>) {
+        let mut components: Vec<String> = circuit.components.iter().map(|c| format!("{:?}", c)).collect();
+        let mut connections: Vec<(String, String, String)> =
+            circuit.connections.iter().map(|c| (c.from.clone(), c.to.clone(), c.net_name.clone())).collect();
+        components.sort();
+        connections.sort();
+        (components, connections)
+    }
+
+    #[test]
+    fn test_merge_renames_conflicting_components() {
+        let mut filter = Circuit::new();
+        filter.add_component(component("R1", ComponentType::Resistor));
+        filter.add_component(component("C1", ComponentType::Capacitor));
+        filter.add_connection(connection("R1", "C1", "OUT"));
+
+        let merged = amplifier().merge(&filter);
+        let ids: Vec<&str> = merged.components.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["V1", "R1", "U1", "RF", "R1_2", "C1"]);
+        assert_eq!(merged.connections.last(), Some(&connection("R1_2", "C1", "OUT")));
+
+        let mut prefixed = filter.clone();
+        prefixed.prefix_components("LPF_");
+        assert_eq!(prefixed.components[0].id, "LPF_R1");
+        assert_eq!(prefixed.connections[0], connection("LPF_R1", "LPF_C1", "OUT"));
+        assert_eq!(amplifier().merge(&prefixed).components.len(), 6);
+    }
+
+    #[test]
+    fn test_extract_then_merge_restores_circuit() {
+        let circuit = amplifier();
+        let (child, ports) = circuit.extract_subcircuit(&["U1", "RF"]);
+        assert_eq!(child.components.len(), 2);
+        assert_eq!(child.connections, vec![connection("RF", "U1", "INV"), connection("U1", "RF", "OUT")]);
+        assert_eq!(
+            ports,
+            vec![BoundaryPort {
+                internal: "U1".to_string(),
+                external: "R1".to_string(),
+                net_name: "INV".to_string(),
+                outgoing: false,
+            }]
+        );
+
+        let (rest, _) = circuit.extract_subcircuit(&["V1", "R1"]);
+        let mut restored = rest.merge(&child);
+        restored.connections.extend(ports.iter().map(BoundaryPort::to_connection));
+        assert_eq!(structure(&restored), structure(&circuit));
+    }
+}
@@ -0,0 +1,115 @@
+//! JSON Schema for serialized circuits
+//!
+//! Given to the LLM when it generates circuits so that its output matches
+//! the serde representation of [`Circuit`] without a round of retries.
+//! Enum values are taken from [`ComponentType::ALL`], so new component types
+//! appear in the schema automatically.
+
+use crate::{Circuit, ComponentType};
+use serde_json::{json, Value};
+
+impl Circuit {
+    /// JSON Schema (draft 7) describing a serialized circuit
+    pub fn json_schema() -> Value {
+        let component_types: Vec<Value> = ComponentType::ALL
+            .iter()
+            .filter_map(|component_type| serde_json::to_value(component_type).ok())
+            .collect();
+
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Circuit",
+            "type": "object",
+            "required": ["components", "connections"],
+            "properties": {
+                "components": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["id", "component_type", "value", "position"],
+                        "properties": {
+                            "id": { "type": "string", "minLength": 1, "description": "Reference designator, e.g. R1" },
+                            "component_type": { "enum": component_types },
+                            "value": { "type": ["string", "null"], "description": "Value with unit, e.g. 10k or 100nF" },
+                            "position": {
+                                "type": "array",
+                                "items": { "type": "number" },
+                                "minItems": 2,
+                                "maxItems": 2,
+                                "description": "Schematic position as [x, y]"
+                            },
+                            "purpose": { "type": ["string", "null"] }
+                        }
+                    }
+                },
+                "connections": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["from", "to", "net_name"],
+                        "properties": {
+                            "from": { "type": "string", "description": "Component ID" },
+                            "to": { "type": "string", "description": "Component ID" },
+                            "net_name": { "type": "string" }
+                        }
+                    }
+                },
+                "subcircuits": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["name", "ports", "internal_netlist"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "ports": { "type": "array", "items": { "type": "string" } },
+                            "internal_netlist": { "type": "object" }
+                        }
+                    }
+                },
+                "subcircuit_instances": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["instance_name", "subcircuit", "port_connections"],
+                        "properties": {
+                            "instance_name": { "type": "string" },
+                            "subcircuit": { "type": "string" },
+                            "port_connections": { "type": "array", "items": { "type": "string" } }
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, Connection};
+
+    #[test]
+    fn test_serialized_circuit_matches_schema() {
+        let validator = jsonschema::validator_for(&Circuit::json_schema()).unwrap();
+
+        let mut circuit = Circuit::new();
+        for (index, component_type) in ComponentType::ALL.into_iter().enumerate() {
+            circuit.add_component(Component {
+                id: format!("X{}", index),
+                component_type,
+                value: None,
+                position: (index as f64, 0.0),
+                purpose: Some("test".to_string()),
+            });
+        }
+        circuit.add_connection(Connection { from: "X0".to_string(), to: "X1".to_string(), net_name: "N1".to_string() });
+        assert!(validator.is_valid(&serde_json::to_value(&circuit).unwrap()));
+
+        let invalid = json!({
+            "components": [{ "id": "F1", "component_type": "Fuse", "value": "1A", "position": [0, 0] }],
+            "connections": []
+        });
+        assert!(!validator.is_valid(&invalid));
+        assert!(!validator.is_valid(&json!({ "components": [] })));
+    }
+}
@@ -0,0 +1,253 @@
+//! Component placement on the 2D schematic canvas
+//!
+//! [`SchematicLayout::auto_layout`] runs a force-directed placement: every
+//! pair of components repels, connected components attract like springs, and
+//! the movement allowed per step cools so the layout settles. The result is
+//! then snapped to a grid with one component per cell, so symbols never
+//! overlap, and each connection is routed as an orthogonal wire.
+
+use crate::{Circuit, Connection};
+use opencircuit_core::Position;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Width and height of the box a symbol occupies (canvas units)
+pub const COMPONENT_SIZE: f64 = 40.0;
+/// Spacing of the placement grid (canvas units)
+pub const GRID_PITCH: f64 = 60.0;
+/// Natural length of the springs between connected components; shorter than
+/// the pitch so neighbours snap to adjacent cells (canvas units)
+pub const SPRING_LENGTH: f64 = 45.0;
+/// Force-directed iterations run by [`SchematicLayout::auto_layout`]
+pub const LAYOUT_ITERATIONS: usize = 300;
+
+/// Positions, rotations and wire routes of a schematic
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchematicLayout {
+    /// Symbol centres keyed by component ID
+    pub positions: HashMap<String, Position>,
+    /// Degrees counter-clockwise keyed by component ID
+    pub rotations: HashMap<String, f64>,
+    /// Wire polylines keyed by [`SchematicLayout::wire_key`]
+    pub wire_routes: HashMap<String, Vec<Position>>,
+}
+
+impl SchematicLayout {
+    /// Force-directed initial layout of every component in `circuit`
+    ///
+    /// Deterministic: the same circuit always gives the same layout.
+    pub fn auto_layout(circuit: &Circuit) -> SchematicLayout {
+        let ids: Vec<&str> = circuit.components.iter().map(|component| component.id.as_str()).collect();
+        let index: HashMap<&str, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let edges: HashSet<(usize, usize)> = circuit
+            .connections
+            .iter()
+            .filter_map(|connection| {
+                let (from, to) = (*index.get(connection.from.as_str())?, *index.get(connection.to.as_str())?);
+                (from != to).then_some((from.min(to), from.max(to)))
+            })
+            .collect();
+
+        let settled = force_directed(ids.len(), &edges);
+        let cells = snap_to_grid(&settled);
+
+        let mut layout = SchematicLayout::default();
+        for (i, id) in ids.iter().enumerate() {
+            let (column, row) = cells[i];
+            layout.positions.insert(id.to_string(), Position::new(column as f64 * GRID_PITCH, row as f64 * GRID_PITCH));
+        }
+
+        // Turn parts whose neighbours are mostly above or below them upright
+        for (i, id) in ids.iter().enumerate() {
+            let (mut horizontal, mut vertical) = (0, 0);
+            for &(a, b) in edges.iter().filter(|(a, b)| *a == i || *b == i) {
+                let other = if a == i { b } else { a };
+                horizontal += cells[i].0.abs_diff(cells[other].0);
+                vertical += cells[i].1.abs_diff(cells[other].1);
+            }
+            layout.rotations.insert(id.to_string(), if vertical > horizontal { 90.0 } else { 0.0 });
+        }
+
+        for connection in &circuit.connections {
+            let (Some(from), Some(to)) = (layout.positions.get(&connection.from), layout.positions.get(&connection.to))
+            else {
+                continue;
+            };
+            let mut route = vec![*from];
+            if from.x != to.x && from.y != to.y {
+                route.push(Position::new(to.x, from.y));
+            }
+            route.push(*to);
+            layout.wire_routes.insert(Self::wire_key(connection), route);
+        }
+
+        layout
+    }
+
+    /// Key of a connection's route in `wire_routes`, e.g. `"VOUT:R1-C1"`
+    pub fn wire_key(connection: &Connection) -> String {
+        format!("{}:{}-{}", connection.net_name, connection.from, connection.to)
+    }
+
+    /// Pairs of components whose [`COMPONENT_SIZE`] boxes overlap
+    pub fn overlapping_components(&self) -> Vec<(String, String)> {
+        let mut placed: Vec<(&String, &Position)> = self.positions.iter().collect();
+        placed.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut overlaps = Vec::new();
+        for (i, (a, pa)) in placed.iter().enumerate() {
+            for (b, pb) in &placed[i + 1..] {
+                if (pa.x - pb.x).abs() < COMPONENT_SIZE && (pa.y - pb.y).abs() < COMPONENT_SIZE {
+                    overlaps.push((a.to_string(), b.to_string()));
+                }
+            }
+        }
+        overlaps
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<SchematicLayout> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Fruchterman-Reingold placement starting from a circle
+fn force_directed(count: usize, edges: &HashSet<(usize, usize)>) -> Vec<(f64, f64)> {
+    let k = SPRING_LENGTH;
+    let radius = k * count as f64 / (2.0 * std::f64::consts::PI);
+    let mut points: Vec<(f64, f64)> = (0..count)
+        .map(|i| {
+            let angle = 2.0 * std::f64::consts::PI * i as f64 / count.max(1) as f64;
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect();
+
+    let mut temperature = k * 2.0;
+    let cooling = temperature / LAYOUT_ITERATIONS as f64;
+    for _ in 0..LAYOUT_ITERATIONS {
+        let mut displacement = vec![(0.0, 0.0); count];
+        for i in 0..count {
+            for j in i + 1..count {
+                let (dx, dy) = (points[i].0 - points[j].0, points[i].1 - points[j].1);
+                let distance = dx.hypot(dy).max(0.01);
+                let mut force = k * k / distance;
+                if edges.contains(&(i, j)) {
+                    force -= distance * distance / k;
+                }
+                let (fx, fy) = (dx / distance * force, dy / distance * force);
+                displacement[i] = (displacement[i].0 + fx, displacement[i].1 + fy);
+                displacement[j] = (displacement[j].0 - fx, displacement[j].1 - fy);
+            }
+        }
+
+        for (point, (dx, dy)) in points.iter_mut().zip(displacement) {
+            let length = dx.hypot(dy);
+            if length > 0.0 {
+                let step = length.min(temperature);
+                point.0 += dx / length * step;
+                point.1 += dy / length * step;
+            }
+        }
+        temperature = (temperature - cooling).max(0.0);
+    }
+    points
+}
+
+/// Nearest free grid cell for each point, shifted so the layout starts at (0, 0)
+fn snap_to_grid(points: &[(f64, f64)]) -> Vec<(usize, usize)> {
+    let mut taken: HashSet<(i64, i64)> = HashSet::new();
+    let cells: Vec<(i64, i64)> = points
+        .iter()
+        .map(|&(x, y)| {
+            let ideal = ((x / GRID_PITCH).round() as i64, (y / GRID_PITCH).round() as i64);
+            // Search outwards ring by ring for a free cell
+            let cell = (0i64..)
+                .find_map(|ring| {
+                    (-ring..=ring)
+                        .flat_map(|dx| (-ring..=ring).map(move |dy| (dx, dy)))
+                        .filter(|(dx, dy)| dx.abs().max(dy.abs()) == ring)
+                        .map(|(dx, dy)| (ideal.0 + dx, ideal.1 + dy))
+                        .find(|cell| !taken.contains(cell))
+                })
+                .expect("the grid is unbounded");
+            taken.insert(cell);
+            cell
+        })
+        .collect();
+
+    let min_x = cells.iter().map(|cell| cell.0).min().unwrap_or_default();
+    let min_y = cells.iter().map(|cell| cell.1).min().unwrap_or_default();
+    cells.into_iter().map(|(x, y)| ((x - min_x) as usize, (y - min_y) as usize)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, ComponentType};
+
+    /// Ten components: a ladder of five RC stages fed from one net
+    fn ladder() -> Circuit {
+        let mut circuit = Circuit::new();
+        for stage in 1..=5 {
+            for (prefix, component_type) in [("R", ComponentType::Resistor), ("C", ComponentType::Capacitor)] {
+                circuit.add_component(Component {
+                    id: format!("{}{}", prefix, stage),
+                    component_type,
+                    value: None,
+                    position: (0.0, 0.0),
+                    purpose: None,
+                });
+            }
+            let connect = |from: String, to: String, net: String| Connection { from, to, net_name: net };
+            circuit.add_connection(connect(format!("R{}", stage), format!("C{}", stage), format!("N{}", stage)));
+            if stage > 1 {
+                circuit.add_connection(connect(format!("R{}", stage - 1), format!("R{}", stage), format!("N{}", stage - 1)));
+            }
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_auto_layout_has_no_overlaps() {
+        let circuit = ladder();
+        let layout = SchematicLayout::auto_layout(&circuit);
+        assert_eq!(layout.positions.len(), 10);
+        assert_eq!(layout.rotations.len(), 10);
+        assert!(layout.overlapping_components().is_empty());
+        assert_eq!(layout, SchematicLayout::auto_layout(&circuit));
+
+        // Every wire runs orthogonally between its components
+        assert_eq!(layout.wire_routes.len(), circuit.connections.len());
+        for connection in &circuit.connections {
+            let route = &layout.wire_routes[&SchematicLayout::wire_key(connection)];
+            assert_eq!(route.first(), layout.positions.get(&connection.from));
+            assert_eq!(route.last(), layout.positions.get(&connection.to));
+            assert!(route.windows(2).all(|segment| segment[0].x == segment[1].x || segment[0].y == segment[1].y));
+        }
+    }
+
+    #[test]
+    fn test_connected_components_stay_close() {
+        let layout = SchematicLayout::auto_layout(&ladder());
+        let distance = |a: &str, b: &str| layout.positions[a].distance_to(&layout.positions[b]);
+        // Each capacitor sits beside its own resistor rather than across the canvas
+        for stage in 1..=5 {
+            assert!(distance(&format!("R{}", stage), &format!("C{}", stage)) <= 2.0 * GRID_PITCH);
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip_and_overlap_detection() {
+        let mut layout = SchematicLayout::auto_layout(&ladder());
+        let restored = SchematicLayout::from_json(&layout.to_json().unwrap()).unwrap();
+        assert_eq!(restored, layout);
+        assert!(SchematicLayout::from_json("{\"positions\": 3}").is_err());
+
+        let r1 = layout.positions["R1"];
+        layout.positions.insert("C5".to_string(), Position::new(r1.x + 10.0, r1.y - 5.0));
+        assert_eq!(layout.overlapping_components(), vec![("C5".to_string(), "R1".to_string())]);
+    }
+}
@@ -6,16 +6,62 @@
 //! - Circuit analysis algorithms
 //! - Component models
 
+use opencircuit_core::circuit::Netlist;
+use opencircuit_core::Position;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub mod erc;
+pub mod hierarchy;
+pub mod json_schema;
+pub mod layout;
+pub mod mna;
+
+pub use erc::{CircuitErc, ErcRule, ErcViolation, PinType};
+pub use hierarchy::BoundaryPort;
+pub use layout::SchematicLayout;
+
+/// Errors from circuit analysis
+#[derive(Debug, thiserror::Error)]
+pub enum CircuitError {
+    #[error("Component {id} ({component_type:?}) is not supported by this analysis")]
+    UnsupportedComponent { id: String, component_type: ComponentType },
+
+    #[error("Component {id} connects to {nets} nets, expected 2")]
+    InvalidTerminals { id: String, nets: usize },
+
+    #[error("Component {id} has invalid value '{value}'")]
+    InvalidValue { id: String, value: String },
+
+    #[error("Circuit has no ground net")]
+    NoGround,
+
+    #[error("Unknown net: {0}")]
+    UnknownNet(String),
+
+    #[error("Circuit has a node with no DC path to ground")]
+    SingularMatrix,
+
+    #[error("Circuit has a component without an ID")]
+    MissingComponentId,
+
+    #[error("Duplicate component ID '{0}'")]
+    DuplicateComponentId(String),
+}
+
 /// Circuit component representation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Component {
     pub id: String,
     pub component_type: ComponentType,
     pub value: Option<String>,
     pub position: (f64, f64),
+    /// What the component does in the circuit, e.g. for BOM comments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ComponentType {
     Resistor,
     Capacitor,
@@ -27,14 +73,64 @@ pub enum ComponentType {
     CurrentSource,
 }
 
+impl ComponentType {
+    pub const ALL: [ComponentType; 8] = [
+        ComponentType::Resistor,
+        ComponentType::Capacitor,
+        ComponentType::Inductor,
+        ComponentType::Transistor,
+        ComponentType::OpAmp,
+        ComponentType::Diode,
+        ComponentType::VoltageSource,
+        ComponentType::CurrentSource,
+    ];
+
+    /// Component type for a SPICE element's leading letter
+    ///
+    /// BJTs and MOSFETs are both transistors; `X` instances are op amps.
+    pub fn from_spice_prefix(prefix: char) -> Option<Self> {
+        match prefix.to_ascii_uppercase() {
+            'R' => Some(ComponentType::Resistor),
+            'C' => Some(ComponentType::Capacitor),
+            'L' => Some(ComponentType::Inductor),
+            'V' => Some(ComponentType::VoltageSource),
+            'I' => Some(ComponentType::CurrentSource),
+            'D' => Some(ComponentType::Diode),
+            'Q' | 'M' => Some(ComponentType::Transistor),
+            'X' => Some(ComponentType::OpAmp),
+            _ => None,
+        }
+    }
+}
+
 /// Circuit netlist representation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Circuit {
     pub components: Vec<Component>,
     pub connections: Vec<Connection>,
+    #[serde(default)]
+    pub subcircuits: Vec<SubCircuit>,
+    #[serde(default)]
+    pub subcircuit_instances: Vec<SubCircuitInstance>,
+}
+
+/// Hierarchical subcircuit definition (SPICE `.SUBCKT`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubCircuit {
+    pub name: String,
+    pub ports: Vec<String>,
+    pub internal_netlist: Netlist,
+}
+
+/// Placement of a subcircuit in a circuit (SPICE `X` line)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubCircuitInstance {
+    pub instance_name: String,
+    pub subcircuit: String,
+    pub port_connections: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Connection {
     pub from: String,
     pub to: String,
@@ -46,6 +142,8 @@ impl Circuit {
         Self {
             components: Vec::new(),
             connections: Vec::new(),
+            subcircuits: Vec::new(),
+            subcircuit_instances: Vec::new(),
         }
     }
     
@@ -56,6 +154,86 @@ impl Circuit {
     pub fn add_connection(&mut self, connection: Connection) {
         self.connections.push(connection);
     }
+
+    /// Check that every component has a unique, non-empty ID
+    pub fn check_component_ids(&self) -> Result<(), CircuitError> {
+        let mut ids = std::collections::HashSet::new();
+        for component in &self.components {
+            if component.id.is_empty() {
+                return Err(CircuitError::MissingComponentId);
+            }
+            if !ids.insert(component.id.as_str()) {
+                return Err(CircuitError::DuplicateComponentId(component.id.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add or replace a subcircuit definition
+    pub fn add_subcircuit(&mut self, sc: SubCircuit) {
+        self.subcircuits.retain(|existing| !existing.name.eq_ignore_ascii_case(&sc.name));
+        self.subcircuits.push(sc);
+    }
+
+    pub fn get_subcircuit(&self, name: &str) -> Option<&SubCircuit> {
+        self.subcircuits.iter().find(|sc| sc.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Instantiate a previously added subcircuit, connecting its ports to the given nets
+    pub fn instantiate_subcircuit(
+        &mut self,
+        sc_name: &str,
+        instance_name: &str,
+        port_connections: Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        let subcircuit = self
+            .get_subcircuit(sc_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown subcircuit: {}", sc_name))?;
+
+        if subcircuit.ports.len() != port_connections.len() {
+            anyhow::bail!(
+                "Subcircuit {} has {} ports but {} connections were given",
+                subcircuit.name,
+                subcircuit.ports.len(),
+                port_connections.len()
+            );
+        }
+
+        if self
+            .subcircuit_instances
+            .iter()
+            .any(|instance| instance.instance_name == instance_name)
+        {
+            anyhow::bail!("Duplicate subcircuit instance name: {}", instance_name);
+        }
+
+        let subcircuit = subcircuit.name.clone();
+        self.subcircuit_instances.push(SubCircuitInstance {
+            instance_name: instance_name.to_string(),
+            subcircuit,
+            port_connections,
+        });
+        Ok(())
+    }
+    
+    /// Estimated wire length per net, as the Manhattan distance of each connection
+    ///
+    /// Connections with an endpoint missing from `layout` are not counted.
+    pub fn estimate_wire_lengths(&self, layout: &HashMap<String, Position>) -> HashMap<String, f64> {
+        let mut lengths = HashMap::new();
+        for connection in &self.connections {
+            let (Some(from), Some(to)) = (layout.get(&connection.from), layout.get(&connection.to)) else {
+                continue;
+            };
+            *lengths.entry(connection.net_name.clone()).or_insert(0.0) += (from.x - to.x).abs() + (from.y - to.y).abs();
+        }
+        lengths
+    }
+
+    /// Sum of [`Circuit::estimate_wire_lengths`] over all nets
+    pub fn total_estimated_wire_length(&self, layout: &HashMap<String, Position>) -> f64 {
+        self.estimate_wire_lengths(layout).values().sum()
+    }
     
     pub fn to_spice_netlist(&self) -> Result<String, anyhow::Error> {
         // TODO: Implement SPICE netlist generation
@@ -80,6 +258,89 @@ mod tests {
         assert!(circuit.connections.is_empty());
     }
     
+    fn buffer_subcircuit() -> SubCircuit {
+        SubCircuit {
+            name: "BUFFER".to_string(),
+            ports: vec!["in".to_string(), "out".to_string(), "gnd".to_string()],
+            internal_netlist: Netlist::new("BUFFER".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_subcircuit_instantiation() {
+        let mut circuit = Circuit::new();
+        circuit.add_subcircuit(buffer_subcircuit());
+
+        circuit
+            .instantiate_subcircuit("buffer", "X1", vec!["1".into(), "2".into(), "0".into()])
+            .unwrap();
+        assert_eq!(circuit.subcircuit_instances.len(), 1);
+        assert_eq!(circuit.subcircuit_instances[0].subcircuit, "BUFFER");
+
+        // Wrong port count, unknown subcircuit and duplicate instance names are rejected
+        assert!(circuit.instantiate_subcircuit("BUFFER", "X2", vec!["1".into()]).is_err());
+        assert!(circuit.instantiate_subcircuit("MISSING", "X3", vec![]).is_err());
+        assert!(circuit
+            .instantiate_subcircuit("BUFFER", "X1", vec!["3".into(), "4".into(), "0".into()])
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_component_ids() {
+        let resistor = |id: &str| Component {
+            id: id.to_string(),
+            component_type: ComponentType::Resistor,
+            value: Some("1k".to_string()),
+            position: (0.0, 0.0),
+            purpose: None,
+        };
+        let mut circuit = Circuit::new();
+        circuit.add_component(resistor("R1"));
+        circuit.add_component(resistor("R2"));
+        assert!(circuit.check_component_ids().is_ok());
+
+        circuit.add_component(resistor("R1"));
+        let error = circuit.check_component_ids().unwrap_err();
+        assert!(matches!(&error, CircuitError::DuplicateComponentId(id) if id == "R1"));
+        assert_eq!(error.to_string(), "Duplicate component ID 'R1'");
+
+        circuit.components = vec![resistor("")];
+        assert!(matches!(circuit.check_component_ids(), Err(CircuitError::MissingComponentId)));
+    }
+
+    #[test]
+    fn test_component_type_from_spice_prefix() {
+        assert_eq!(ComponentType::from_spice_prefix('r'), Some(ComponentType::Resistor));
+        assert_eq!(ComponentType::from_spice_prefix('M'), Some(ComponentType::Transistor));
+        assert_eq!(ComponentType::from_spice_prefix('V'), Some(ComponentType::VoltageSource));
+        assert_eq!(ComponentType::from_spice_prefix('K'), None);
+    }
+
+    #[test]
+    fn test_series_wire_lengths() {
+        let mut circuit = Circuit::new();
+        for (from, to, net_name) in [("V1", "R1", "in"), ("R1", "R2", "mid"), ("R2", "V1", "0")] {
+            circuit.add_connection(Connection { from: from.into(), to: to.into(), net_name: net_name.into() });
+        }
+        let layout: HashMap<String, Position> = [
+            ("V1".to_string(), Position::new(0.0, 0.0)),
+            ("R1".to_string(), Position::new(10.0, 0.0)),
+            ("R2".to_string(), Position::new(10.0, 5.0)),
+        ]
+        .into_iter()
+        .collect();
+
+        let lengths = circuit.estimate_wire_lengths(&layout);
+        assert_eq!(lengths["in"], 10.0);
+        assert_eq!(lengths["mid"], 5.0);
+        assert_eq!(lengths["0"], 15.0);
+        assert_eq!(circuit.total_estimated_wire_length(&layout), 30.0);
+
+        // Unplaced components contribute nothing
+        let partial: HashMap<String, Position> = layout.into_iter().filter(|(id, _)| id != "R2").collect();
+        assert_eq!(circuit.total_estimated_wire_length(&partial), 10.0);
+    }
+
     #[test]
     fn test_spice_netlist_generation() {
         let circuit = Circuit::new();
@@ -0,0 +1,231 @@
+//! DC operating point of resistive networks by modified nodal analysis
+//!
+//! Gives instant results without NgSpice for circuits made only of
+//! resistors. Each source in `v_sources` holds a net at a fixed voltage
+//! against ground and adds one row to the MNA system; `VoltageSource`
+//! components are skipped, as their orientation is not recorded in the
+//! connections. A component's terminals are the nets its connections name.
+//! Values use SPICE notation, where `M` is milli and `MEG` is mega.
+
+use crate::erc::is_ground;
+use crate::{Circuit, CircuitError, ComponentType};
+use opencircuit_utils::units::parse_spice_value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Pivots smaller than this mean a node has no DC path to the rest
+const SINGULAR_PIVOT: f64 = 1e-15;
+
+impl Circuit {
+    /// Solve the node voltages of a resistive circuit
+    ///
+    /// `v_sources` pairs a net with its voltage against ground. Returns the
+    /// voltage of every net, ground nets included.
+    pub fn solve_resistive_mna(&self, v_sources: &[(String, f64)]) -> Result<HashMap<String, f64>, CircuitError> {
+        let mut resistors = Vec::new();
+        for component in &self.components {
+            match component.component_type {
+                ComponentType::Resistor => {}
+                ComponentType::VoltageSource => continue,
+                _ => {
+                    return Err(CircuitError::UnsupportedComponent {
+                        id: component.id.clone(),
+                        component_type: component.component_type.clone(),
+                    })
+                }
+            }
+
+            let terminals: Vec<&str> = self.component_nets(&component.id).into_iter().collect();
+            let &[a, b] = terminals.as_slice() else {
+                return Err(CircuitError::InvalidTerminals { id: component.id.clone(), nets: terminals.len() });
+            };
+            let resistance = component
+                .value
+                .as_deref()
+                .and_then(|value| parse_spice_value(value).ok())
+                .filter(|resistance| resistance.is_finite() && *resistance > 0.0)
+                .ok_or_else(|| CircuitError::InvalidValue {
+                    id: component.id.clone(),
+                    value: component.value.clone().unwrap_or_default(),
+                })?;
+            resistors.push((a, b, resistance));
+        }
+
+        let nets: BTreeSet<&str> = self.connections.iter().map(|connection| connection.net_name.as_str()).collect();
+        if !nets.iter().any(|net| is_ground(net)) {
+            return Err(CircuitError::NoGround);
+        }
+        for (net, _) in v_sources {
+            if !nets.contains(net.as_str()) || is_ground(net) {
+                return Err(CircuitError::UnknownNet(net.clone()));
+            }
+        }
+
+        // Unknowns: one voltage per non-ground net, then one current per source
+        let nodes: BTreeMap<&str, usize> =
+            nets.iter().filter(|net| !is_ground(net)).enumerate().map(|(index, net)| (*net, index)).collect();
+        let size = nodes.len() + v_sources.len();
+        let mut matrix = vec![vec![0.0; size]; size];
+        let mut rhs = vec![0.0; size];
+
+        for (a, b, resistance) in resistors {
+            let conductance = 1.0 / resistance;
+            let (a, b) = (nodes.get(a).copied(), nodes.get(b).copied());
+            if let Some(a) = a {
+                matrix[a][a] += conductance;
+            }
+            if let Some(b) = b {
+                matrix[b][b] += conductance;
+            }
+            if let (Some(a), Some(b)) = (a, b) {
+                matrix[a][b] -= conductance;
+                matrix[b][a] -= conductance;
+            }
+        }
+        for (offset, (net, voltage)) in v_sources.iter().enumerate() {
+            let (node, row) = (nodes[net.as_str()], nodes.len() + offset);
+            matrix[node][row] = 1.0;
+            matrix[row][node] = 1.0;
+            rhs[row] = *voltage;
+        }
+
+        let solution = gaussian_elimination(matrix, rhs).ok_or(CircuitError::SingularMatrix)?;
+        Ok(nets
+            .into_iter()
+            .map(|net| (net.to_string(), nodes.get(net).map_or(0.0, |&index| solution[index])))
+            .collect())
+    }
+
+    /// Nets a component is connected to
+    fn component_nets(&self, component_id: &str) -> BTreeSet<&str> {
+        self.connections
+            .iter()
+            .filter(|connection| connection.from == component_id || connection.to == component_id)
+            .map(|connection| connection.net_name.as_str())
+            .collect()
+    }
+}
+
+/// Solve `matrix · x = rhs` with partial pivoting, `None` if singular
+fn gaussian_elimination(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let size = rhs.len();
+    for column in 0..size {
+        let pivot = (column..size).max_by(|&a, &b| matrix[a][column].abs().total_cmp(&matrix[b][column].abs()))?;
+        if matrix[pivot][column].abs() < SINGULAR_PIVOT {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        for row in column + 1..size {
+            let factor = matrix[row][column] / matrix[column][column];
+            if factor == 0.0 {
+                continue;
+            }
+            let (pivot_rows, rows_below) = matrix.split_at_mut(row);
+            for (entry, pivot_entry) in rows_below[0][column..].iter_mut().zip(&pivot_rows[column][column..]) {
+                *entry -= factor * pivot_entry;
+            }
+            rhs[row] -= factor * rhs[column];
+        }
+    }
+
+    let mut solution = vec![0.0; size];
+    for row in (0..size).rev() {
+        let sum: f64 = (row + 1..size).map(|k| matrix[row][k] * solution[k]).sum();
+        solution[row] = (rhs[row] - sum) / matrix[row][row];
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, Connection};
+
+    fn resistive(resistors: &[(&str, &str, &str, &str)]) -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, value, a, b) in resistors {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type: ComponentType::Resistor,
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+            for net in [a, b] {
+                circuit.add_connection(Connection { from: id.to_string(), to: id.to_string(), net_name: net.to_string() });
+            }
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_voltage_divider() {
+        let circuit = resistive(&[("R1", "10k", "in", "out"), ("R2", "4k7", "out", "0")]);
+        let voltages = circuit.solve_resistive_mna(&[("in".to_string(), 5.0)]).unwrap();
+        assert!((voltages["in"] - 5.0).abs() < 1e-12);
+        assert!((voltages["out"] - 5.0 * 4.7 / 14.7).abs() < 1e-12);
+        assert_eq!(voltages["0"], 0.0);
+
+        // 1M is a milliohm in SPICE, so nearly all of the input drops across 1MEG
+        let circuit = resistive(&[("R1", "1MEG", "in", "out"), ("R2", "1M", "out", "0")]);
+        let voltages = circuit.solve_resistive_mna(&[("in".to_string(), 1.0)]).unwrap();
+        assert!((voltages["out"] - 1e-3 / (1e6 + 1e-3)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_wheatstone_bridge() {
+        // Balanced arms give equal midpoints; unbalancing R4 shifts the right side
+        let circuit = resistive(&[
+            ("R1", "1k", "vcc", "left"),
+            ("R2", "2k", "left", "GND"),
+            ("R3", "1k", "vcc", "right"),
+            ("R4", "3k", "right", "GND"),
+            ("R5", "10k", "left", "right"),
+        ]);
+        let voltages = circuit.solve_resistive_mna(&[("vcc".to_string(), 12.0)]).unwrap();
+
+        // Kirchhoff's current law at both bridge nodes
+        let (vcc, left, right) = (voltages["vcc"], voltages["left"], voltages["right"]);
+        assert!(((vcc - left) / 1e3 - left / 2e3 - (left - right) / 10e3).abs() < 1e-12);
+        assert!(((vcc - right) / 1e3 - right / 3e3 + (left - right) / 10e3).abs() < 1e-12);
+        assert!(right > left && left > 7.0);
+
+        let balanced = resistive(&[
+            ("R1", "1k", "vcc", "left"),
+            ("R2", "2k", "left", "GND"),
+            ("R3", "1k", "vcc", "right"),
+            ("R4", "2k", "right", "GND"),
+            ("R5", "10k", "left", "right"),
+        ]);
+        let voltages = balanced.solve_resistive_mna(&[("vcc".to_string(), 12.0)]).unwrap();
+        assert!((voltages["left"] - 8.0).abs() < 1e-12);
+        assert!((voltages["right"] - 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rejects_unsolvable_circuits() {
+        let floating = resistive(&[("R1", "1k", "in", "0"), ("R2", "1k", "a", "b")]);
+        assert!(matches!(floating.solve_resistive_mna(&[("in".to_string(), 1.0)]), Err(CircuitError::SingularMatrix)));
+
+        let no_ground = resistive(&[("R1", "1k", "in", "out")]);
+        assert!(matches!(no_ground.solve_resistive_mna(&[("in".to_string(), 1.0)]), Err(CircuitError::NoGround)));
+
+        let mut with_diode = resistive(&[("R1", "1k", "in", "0")]);
+        with_diode.add_component(Component {
+            id: "D1".to_string(),
+            component_type: ComponentType::Diode,
+            value: None,
+            position: (0.0, 0.0),
+            purpose: None,
+        });
+        assert!(matches!(
+            with_diode.solve_resistive_mna(&[]),
+            Err(CircuitError::UnsupportedComponent { component_type: ComponentType::Diode, .. })
+        ));
+        assert!(matches!(
+            resistive(&[("R1", "abc", "in", "0")]).solve_resistive_mna(&[]),
+            Err(CircuitError::InvalidValue { .. })
+        ));
+    }
+}
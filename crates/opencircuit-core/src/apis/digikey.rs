@@ -3,7 +3,7 @@
 //! DigiKey provides a comprehensive API for component search, pricing,
 //! and availability information directly from their inventory.
 
-use super::{ApiError, BaseApiClient};
+use super::{check_batch_size, ApiError, BaseApiClient};
 use crate::models::{Component, ComponentCategory, SpecValue, PriceInfo, PriceBreak, AvailabilityInfo};
 use anyhow::Result;
 use base64::{Engine as _, engine::general_purpose};
@@ -11,15 +11,15 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
-use std::cell::RefCell;
+use std::sync::RwLock;
 
 /// DigiKey API client with OAuth 2.0 authentication
 pub struct DigiKeyClient {
     base_client: BaseApiClient,
     client_id: String,
     client_secret: String,
-    access_token: RefCell<Option<String>>,
-    token_expires_at: RefCell<Option<DateTime<Utc>>>,
+    access_token: RwLock<Option<String>>,
+    token_expires_at: RwLock<Option<DateTime<Utc>>>,
     sandbox_mode: bool,
 }
 
@@ -43,18 +43,19 @@ impl DigiKeyClient {
             base_client,
             client_id,
             client_secret,
-            access_token: RefCell::new(None),
-            token_expires_at: RefCell::new(None),
+            access_token: RwLock::new(None),
+            token_expires_at: RwLock::new(None),
             sandbox_mode: sandbox,
         }
     }
 
     /// Authenticate with DigiKey OAuth 2.0
+    #[tracing::instrument(skip(self))]
     async fn authenticate(&self) -> Result<(), ApiError> {
         // Check if we have a valid token
         {
-            let access_token = self.access_token.borrow();
-            let token_expires_at = self.token_expires_at.borrow();
+            let access_token = self.access_token.read().unwrap();
+            let token_expires_at = self.token_expires_at.read().unwrap();
             if let (Some(_), Some(expires_at)) = (access_token.as_ref(), token_expires_at.as_ref()) {
                 if *expires_at > Utc::now() + chrono::Duration::minutes(5) {
                     return Ok(()); // Token is still valid
@@ -94,13 +95,14 @@ impl DigiKeyClient {
             .await
             .map_err(|e| ApiError::InvalidResponse(format!("Failed to parse token response: {}", e)))?;
 
-        *self.access_token.borrow_mut() = Some(token_response.access_token);
-        *self.token_expires_at.borrow_mut() = Some(Utc::now() + chrono::Duration::seconds(token_response.expires_in as i64));
+        *self.access_token.write().unwrap() = Some(token_response.access_token);
+        *self.token_expires_at.write().unwrap() = Some(Utc::now() + chrono::Duration::seconds(token_response.expires_in as i64));
 
         Ok(())
     }
 
     /// Search for components by keyword
+    #[tracing::instrument(skip(self))]
     pub async fn search_components(&self, query: &str) -> Result<Vec<Component>, ApiError> {
         self.authenticate().await?;
 
@@ -133,6 +135,7 @@ impl DigiKeyClient {
     }
 
     /// Get detailed component information by part number
+    #[tracing::instrument(skip(self))]
     pub async fn get_component_details(&self, part_number: &str) -> Result<Component, ApiError> {
         self.authenticate().await?;
 
@@ -145,9 +148,35 @@ impl DigiKeyClient {
         self.convert_digikey_product_to_component(product)
     }
 
+    /// Availability of up to [`super::BATCH_SEARCH_LIMIT`] parts in one
+    /// request, keyed by manufacturer part number
+    #[tracing::instrument(skip(self), fields(part_count = part_numbers.len()))]
+    pub async fn batch_availability(&self, part_numbers: &[&str]) -> Result<HashMap<String, AvailabilityInfo>, ApiError> {
+        check_batch_size(part_numbers)?;
+        self.authenticate().await?;
+
+        let request = DigiKeyBatchRequest {
+            products: part_numbers.iter().map(|part_number| part_number.to_string()).collect(),
+        };
+        let response = self.authenticated_post("/BatchSearch/v3/ProductDetails", &request).await?;
+
+        let batch: DigiKeyBatchResponse = serde_json::from_str(&response)
+            .map_err(|e| ApiError::InvalidResponse(format!("Failed to parse DigiKey batch response: {}", e)))?;
+
+        Ok(batch
+            .product_details
+            .into_iter()
+            .filter_map(|product| {
+                let component = self.convert_digikey_product_to_component(product).ok()?;
+                Some((component.part_number, component.availability?))
+            })
+            .collect())
+    }
+
     /// Make authenticated GET request
+    #[tracing::instrument(skip(self))]
     async fn authenticated_get(&self, endpoint: &str) -> Result<String, ApiError> {
-        let token = self.access_token.borrow()
+        let token = self.access_token.read().unwrap()
             .as_ref()
             .ok_or_else(|| ApiError::AuthenticationFailed {
                 service: "DigiKey".to_string(),
@@ -178,8 +207,9 @@ impl DigiKeyClient {
     }
 
     /// Make authenticated POST request
+    #[tracing::instrument(skip(self, body))]
     async fn authenticated_post<T: Serialize>(&self, endpoint: &str, body: &T) -> Result<String, ApiError> {
-        let token = self.access_token.borrow()
+        let token = self.access_token.read().unwrap()
             .as_ref()
             .ok_or_else(|| ApiError::AuthenticationFailed {
                 service: "DigiKey".to_string(),
@@ -315,6 +345,18 @@ struct DigiKeySearchResponse {
     products: Vec<DigiKeyProduct>,
 }
 
+#[derive(Debug, Serialize)]
+struct DigiKeyBatchRequest {
+    #[serde(rename = "Products")]
+    products: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DigiKeyBatchResponse {
+    #[serde(rename = "ProductDetails")]
+    product_details: Vec<DigiKeyProduct>,
+}
+
 #[derive(Debug, Deserialize)]
 struct DigiKeyProduct {
     #[serde(rename = "ManufacturerPartNumber")]
@@ -60,6 +60,21 @@ pub enum ApiError {
     ConfigurationError(String),
 }
 
+/// Most part numbers DigiKey and Mouser accept in one batch request
+pub const BATCH_SEARCH_LIMIT: usize = 50;
+
+/// Reject batches larger than [`BATCH_SEARCH_LIMIT`]
+fn check_batch_size(part_numbers: &[&str]) -> Result<(), ApiError> {
+    if part_numbers.len() > BATCH_SEARCH_LIMIT {
+        return Err(ApiError::ConfigurationError(format!(
+            "Batch of {} part numbers exceeds the limit of {}",
+            part_numbers.len(),
+            BATCH_SEARCH_LIMIT
+        )));
+    }
+    Ok(())
+}
+
 /// API key information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiKey {
@@ -173,6 +188,7 @@ impl BaseApiClient {
     }
 
     /// Wait for rate limit if necessary
+    #[tracing::instrument(skip(self), fields(service = %self.service_name))]
     pub async fn wait_for_rate_limit(&self) -> Result<(), ApiError> {
         match self.rate_limiter.check_key(&self.service_name) {
             Ok(_) => Ok(()),
@@ -187,6 +203,7 @@ impl BaseApiClient {
     }
 
     /// Make a cached GET request
+    #[tracing::instrument(skip(self), fields(service = %self.service_name))]
     pub async fn cached_get(&self, endpoint: &str, cache_key: &str) -> Result<String, ApiError> {
         // Check cache first
         if let Some(cached) = self.cache.get(cache_key) {
@@ -316,6 +333,7 @@ impl ApiManager {
     }
 
     /// Search components across all enabled APIs
+    #[tracing::instrument(skip(self))]
     pub async fn search_components(&self, query: &str) -> Result<Vec<crate::models::Component>, ApiError> {
         let mut all_components = Vec::new();
 
@@ -355,7 +373,63 @@ impl ApiManager {
         Ok(all_components)
     }
 
+    /// Refresh stock for many parts with DigiKey and Mouser batch searches
+    ///
+    /// Part numbers are sent [`BATCH_SEARCH_LIMIT`] at a time to both
+    /// suppliers in parallel. When both report a part, the more recently
+    /// updated availability wins. Results are keyed by the requested part
+    /// numbers; parts neither supplier knows are left out. Fails only when
+    /// no batch-capable supplier is enabled or every request failed.
+    #[tracing::instrument(skip(self, part_numbers), fields(part_count = part_numbers.len()))]
+    pub async fn refresh_availability_batch(
+        &self,
+        part_numbers: &[&str],
+    ) -> Result<HashMap<String, crate::models::AvailabilityInfo>, ApiError> {
+        if self.digikey.is_none() && self.mouser.is_none() {
+            return Err(ApiError::ConfigurationError("Batch stock checks need DigiKey or Mouser enabled".to_string()));
+        }
+
+        let mut availability = HashMap::new();
+        let mut last_error = None;
+        let mut succeeded = false;
+        for batch in part_numbers.chunks(BATCH_SEARCH_LIMIT) {
+            let digikey = async {
+                match self.digikey {
+                    Some(ref client) => Some(client.batch_availability(batch).await),
+                    None => None,
+                }
+            };
+            let mouser = async {
+                match self.mouser {
+                    Some(ref client) => Some(client.batch_availability(batch).await),
+                    None => None,
+                }
+            };
+            let (digikey, mouser) = tokio::join!(digikey, mouser);
+
+            for (service, result) in [("DigiKey", digikey), ("Mouser", mouser)] {
+                match result {
+                    Some(Ok(results)) => {
+                        succeeded = true;
+                        merge_fresher(&mut availability, batch, results);
+                    }
+                    Some(Err(e)) => {
+                        tracing::warn!("{} batch stock check failed: {}", service, e);
+                        last_error = Some(e);
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if !succeeded => Err(e),
+            _ => Ok(availability),
+        }
+    }
+
     /// Get component details by part number
+    #[tracing::instrument(skip(self))]
     pub async fn get_component_details(&self, part_number: &str) -> Result<Option<crate::models::Component>, ApiError> {
         // Try each API in order of preference
         if let Some(ref client) = self.octopart {
@@ -380,6 +454,26 @@ impl ApiManager {
     }
 }
 
+/// Add supplier results to `merged` under the requested part numbers,
+/// keeping the more recently updated entry for parts already present
+fn merge_fresher(
+    merged: &mut HashMap<String, crate::models::AvailabilityInfo>,
+    requested: &[&str],
+    results: HashMap<String, crate::models::AvailabilityInfo>,
+) {
+    for (part_number, info) in results {
+        let Some(requested) = requested.iter().find(|requested| requested.eq_ignore_ascii_case(&part_number)) else {
+            continue;
+        };
+        match merged.get(*requested) {
+            Some(existing) if existing.last_updated >= info.last_updated => {}
+            _ => {
+                merged.insert(requested.to_string(), info);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,6 +492,51 @@ mod tests {
         assert!(cache.get("test_key").is_none());
     }
 
+    #[test]
+    fn test_merge_prefers_fresher_availability() {
+        let stock = |quantity: u32, minutes_ago: i64, supplier: &str| crate::models::AvailabilityInfo {
+            in_stock: quantity > 0,
+            quantity_available: Some(quantity),
+            lead_time_days: None,
+            minimum_order_quantity: Some(1),
+            last_updated: Utc::now() - chrono::Duration::minutes(minutes_ago),
+            supplier: supplier.to_string(),
+        };
+        let requested = ["LM358DR", "NE555P"];
+
+        let mut merged = HashMap::new();
+        merge_fresher(
+            &mut merged,
+            &requested,
+            HashMap::from([("LM358DR".to_string(), stock(1200, 30, "DigiKey")), ("NE555P".to_string(), stock(0, 1, "DigiKey"))]),
+        );
+        merge_fresher(
+            &mut merged,
+            &requested,
+            HashMap::from([
+                ("lm358dr".to_string(), stock(900, 5, "Mouser")),
+                ("NE555P".to_string(), stock(40, 60, "Mouser")),
+                ("TL072CP".to_string(), stock(10, 0, "Mouser")),
+            ]),
+        );
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["LM358DR"].supplier, "Mouser");
+        assert_eq!(merged["NE555P"].supplier, "DigiKey");
+        assert!(!merged["NE555P"].in_stock);
+    }
+
+    #[tokio::test]
+    async fn test_batch_refresh_needs_a_batch_supplier() {
+        let manager = ApiManager::new(ApiConfig::default());
+        assert!(matches!(
+            manager.refresh_availability_batch(&["LM358DR"]).await,
+            Err(ApiError::ConfigurationError(_))
+        ));
+        assert!(check_batch_size(&["R"; BATCH_SEARCH_LIMIT]).is_ok());
+        assert!(check_batch_size(&["R"; BATCH_SEARCH_LIMIT + 1]).is_err());
+    }
+
     #[test]
     fn test_api_config_default() {
         let config = ApiConfig::default();
@@ -3,7 +3,7 @@
 //! Mouser Electronics provides an API for component search, pricing,
 //! and availability information from their extensive inventory.
 
-use super::{ApiError, BaseApiClient};
+use super::{check_batch_size, ApiError, BaseApiClient};
 use crate::models::{Component, ComponentCategory, SpecValue, PriceInfo, PriceBreak, AvailabilityInfo};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -34,6 +34,7 @@ impl MouserClient {
     }
 
     /// Search for components by keyword
+    #[tracing::instrument(skip(self))]
     pub async fn search_components(&self, query: &str) -> Result<Vec<Component>, ApiError> {
         let search_request = MouserSearchRequest {
             search_by_keyword_request: MouserKeywordRequest {
@@ -62,6 +63,7 @@ impl MouserClient {
     }
 
     /// Get detailed component information by part number
+    #[tracing::instrument(skip(self))]
     pub async fn get_component_details(&self, part_number: &str) -> Result<Component, ApiError> {
         let search_request = MouserPartSearchRequest {
             search_by_part_request: MouserPartRequest {
@@ -84,7 +86,40 @@ impl MouserClient {
         Err(ApiError::InvalidResponse("Component not found".to_string()))
     }
 
+    /// Availability of up to [`super::BATCH_SEARCH_LIMIT`] parts in one
+    /// request, keyed by manufacturer part number
+    ///
+    /// Mouser's part number search takes several part numbers separated by `|`.
+    #[tracing::instrument(skip(self), fields(part_count = part_numbers.len()))]
+    pub async fn batch_availability(&self, part_numbers: &[&str]) -> Result<HashMap<String, AvailabilityInfo>, ApiError> {
+        check_batch_size(part_numbers)?;
+
+        let search_request = MouserPartSearchRequest {
+            search_by_part_request: MouserPartRequest {
+                mouse_part_number: part_numbers.join("|"),
+            },
+        };
+
+        let endpoint = format!("search/partnumber?apiKey={}", self.api_key);
+        let response = self.post_request(&endpoint, &search_request).await?;
+
+        let search_response: MouserPartSearchResponse = serde_json::from_str(&response)
+            .map_err(|e| ApiError::InvalidResponse(format!("Failed to parse Mouser response: {}", e)))?;
+
+        Ok(search_response
+            .search_results
+            .parts
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|part| {
+                let component = self.convert_mouser_part_to_component(part).ok()?;
+                Some((component.part_number, component.availability?))
+            })
+            .collect())
+    }
+
     /// Make a POST request to Mouser API
+    #[tracing::instrument(skip(self, body))]
     async fn post_request<T: Serialize>(&self, endpoint: &str, body: &T) -> Result<String, ApiError> {
         self.base_client.wait_for_rate_limit().await?;
 
@@ -125,6 +160,9 @@ impl MouserClient {
             let value = SpecValue::String(attr.attribute_value);
             specifications.insert(attr.attribute_name, value);
         }
+        if let Some(status) = part.lifecycle_status.filter(|status| !status.trim().is_empty()) {
+            specifications.insert("Lifecycle Status".to_string(), SpecValue::String(status));
+        }
         component.specifications = specifications;
 
         // Add datasheet URL
@@ -264,6 +302,8 @@ struct MouserPart {
     lead_time: Option<String>,
     #[serde(rename = "MinOrderQty")]
     min_order_quantity: Option<String>,
+    #[serde(rename = "LifecycleStatus", default)]
+    lifecycle_status: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,6 +34,7 @@ impl OctopartClient {
     }
 
     /// Search for components by query string
+    #[tracing::instrument(skip(self))]
     pub async fn search_components(&self, query: &str) -> Result<Vec<Component>, ApiError> {
         let endpoint = format!(
             "search?q={}&apikey={}&include[]=specs&include[]=datasheets&include[]=offers",
@@ -58,6 +59,7 @@ impl OctopartClient {
     }
 
     /// Get detailed component information by part number
+    #[tracing::instrument(skip(self))]
     pub async fn get_component_details(&self, part_number: &str) -> Result<Component, ApiError> {
         let endpoint = format!(
             "search?q={}&apikey={}&include[]=specs&include[]=datasheets&include[]=offers&limit=1",
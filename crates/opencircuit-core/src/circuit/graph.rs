@@ -0,0 +1,198 @@
+//! Connectivity graphs of a netlist for documentation and debugging
+//!
+//! Components become graph nodes. A circuit node shared by two components is
+//! drawn as an edge between them; one shared by more is drawn as a junction
+//! point so large nets such as ground do not turn into a web of edges.
+//! Components without shared nodes are still listed, so disconnected parts of
+//! the circuit appear as separate sub-graphs.
+
+use std::collections::BTreeMap;
+
+use super::netlist::{ComponentType, Netlist};
+
+impl Netlist {
+    /// Render the connectivity as a Graphviz DOT graph
+    pub fn to_dot_graph(&self) -> String {
+        let mut dot = format!("graph \"{}\" {{\n", dot_escape(&self.title));
+        for component in &self.components {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\" shape={}];\n",
+                dot_escape(&component.name),
+                dot_escape(&label(&component.name, &component.value)),
+                dot_shape(&component.component_type)
+            ));
+        }
+
+        for (node, members) in self.shared_nodes() {
+            if let [from, to] = members.as_slice() {
+                dot.push_str(&format!(
+                    "    \"{}\" -- \"{}\" [label=\"{}\"];\n",
+                    dot_escape(from),
+                    dot_escape(to),
+                    dot_escape(node)
+                ));
+            } else {
+                let junction = format!("net:{}", node);
+                dot.push_str(&format!(
+                    "    \"{}\" [label=\"{}\" shape=point xlabel=\"{}\"];\n",
+                    dot_escape(&junction),
+                    dot_escape(node),
+                    dot_escape(node)
+                ));
+                for member in members {
+                    dot.push_str(&format!("    \"{}\" -- \"{}\";\n", dot_escape(member), dot_escape(&junction)));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the connectivity as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+        for component in &self.components {
+            let text = mermaid_escape(&label(&component.name, &component.value));
+            let (open, close) = mermaid_shape(&component.component_type);
+            mermaid.push_str(&format!("    {}{}\"{}\"{}\n", mermaid_id(&component.name), open, text, close));
+        }
+
+        for (node, members) in self.shared_nodes() {
+            if let [from, to] = members.as_slice() {
+                mermaid.push_str(&format!(
+                    "    {} ---|\"{}\"| {}\n",
+                    mermaid_id(from),
+                    mermaid_escape(node),
+                    mermaid_id(to)
+                ));
+            } else {
+                let junction = format!("net_{}", mermaid_id(node));
+                mermaid.push_str(&format!("    {}((\"{}\"))\n", junction, mermaid_escape(node)));
+                for member in members {
+                    mermaid.push_str(&format!("    {} --- {}\n", mermaid_id(member), junction));
+                }
+            }
+        }
+
+        mermaid
+    }
+
+    /// Circuit nodes reaching more than one component, with those components in netlist order
+    fn shared_nodes(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut nodes: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for component in &self.components {
+            for node in &component.nodes {
+                let members = nodes.entry(node.as_str()).or_default();
+                if !members.contains(&component.name.as_str()) {
+                    members.push(component.name.as_str());
+                }
+            }
+        }
+        nodes.retain(|_, members| members.len() > 1);
+        nodes
+    }
+}
+
+fn label(name: &str, value: &str) -> String {
+    if value.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}\n{}", name, value)
+    }
+}
+
+fn dot_shape(component_type: &ComponentType) -> &'static str {
+    match component_type {
+        ComponentType::Resistor | ComponentType::Capacitor | ComponentType::Inductor | ComponentType::Transformer => {
+            "box"
+        }
+        ComponentType::VoltageSource | ComponentType::CurrentSource => "triangle",
+        ComponentType::Diode
+        | ComponentType::Bjt
+        | ComponentType::Mosfet
+        | ComponentType::OpAmp
+        | ComponentType::IntegratedCircuit => "diamond",
+        ComponentType::Custom(_) => "ellipse",
+    }
+}
+
+/// Mermaid node brackets; sources use the trapezoid as Mermaid has no triangle
+fn mermaid_shape(component_type: &ComponentType) -> (&'static str, &'static str) {
+    match dot_shape(component_type) {
+        "box" => ("[", "]"),
+        "triangle" => ("[/", "\\]"),
+        "diamond" => ("{", "}"),
+        _ => ("(", ")"),
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;").replace('\n', "<br/>")
+}
+
+/// Mermaid node IDs must be plain identifiers
+fn mermaid_id(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::netlist::Component;
+    use std::collections::HashMap;
+
+    fn netlist() -> Netlist {
+        let mut netlist = Netlist::new("Divider".to_string());
+        for (name, component_type, nodes, value) in [
+            ("V1", ComponentType::VoltageSource, ["in", "0"], "12V"),
+            ("R1", ComponentType::Resistor, ["in", "out"], "1k"),
+            ("R2", ComponentType::Resistor, ["out", "0"], "1k"),
+            ("D1", ComponentType::Diode, ["out", "0"], ""),
+            ("C9", ComponentType::Capacitor, ["x", "y"], "10n"),
+        ] {
+            netlist.components.push(Component {
+                name: name.to_string(),
+                component_type,
+                nodes: nodes.iter().map(|node| node.to_string()).collect(),
+                value: value.to_string(),
+                model: None,
+                parameters: HashMap::new(),
+            });
+        }
+        netlist
+    }
+
+    #[test]
+    fn test_dot_graph() {
+        let dot = netlist().to_dot_graph();
+        assert!(dot.starts_with("graph \"Divider\" {\n"));
+        assert!(dot.contains("    \"V1\" [label=\"V1\\n12V\" shape=triangle];\n"));
+        assert!(dot.contains("    \"D1\" [label=\"D1\" shape=diamond];\n"));
+        // Two-component nets are edges, larger ones junctions
+        assert!(dot.contains("    \"V1\" -- \"R1\" [label=\"in\"];\n"));
+        assert!(dot.contains("    \"net:0\" [label=\"0\" shape=point xlabel=\"0\"];\n"));
+        assert!(dot.contains("    \"D1\" -- \"net:out\";\n"));
+        // The unconnected capacitor is still drawn
+        assert!(dot.contains("    \"C9\" [label=\"C9\\n10n\" shape=box];\n"));
+        assert!(!dot.contains("\"C9\" --"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_mermaid() {
+        let mermaid = netlist().to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("    V1[/\"V1<br/>12V\"\\]\n"));
+        assert!(mermaid.contains("    R1[\"R1<br/>1k\"]\n"));
+        assert!(mermaid.contains("    D1{\"D1\"}\n"));
+        assert!(mermaid.contains("    V1 ---|\"in\"| R1\n"));
+        assert!(mermaid.contains("    net_0((\"0\"))\n"));
+        assert!(mermaid.contains("    R2 --- net_out\n"));
+        assert!(mermaid.contains("    C9[\"C9<br/>10n\"]\n"));
+    }
+}
@@ -1,7 +1,9 @@
 //! Circuit analysis and validation module
 //! Provides comprehensive circuit modeling, validation, and analysis capabilities
 
+pub mod graph;
 pub mod netlist;
+pub mod power;
 pub mod validation;
 
 pub use netlist::*;
@@ -9,4 +11,5 @@ pub use validation::*;
 
 /// Re-export commonly used circuit types
 pub use netlist::{Component, ComponentType, Netlist, NetlistError};
+pub use power::{OperatingPoint, PowerBudget};
 pub use validation::{CircuitValidator, ValidationReport, ValidationError};
\ No newline at end of file
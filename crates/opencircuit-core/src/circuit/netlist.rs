@@ -19,7 +19,7 @@ pub enum NetlistError {
     NodeNotFound(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Netlist {
     pub title: String,
     pub components: Vec<Component>,
@@ -29,7 +29,7 @@ pub struct Netlist {
     pub includes: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Component {
     pub name: String,
     pub component_type: ComponentType,
@@ -50,18 +50,19 @@ pub enum ComponentType {
     Bjt,
     Mosfet,
     OpAmp,
+    IntegratedCircuit,
     Transformer,
     Custom(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Connection {
     pub from_node: String,
     pub to_node: String,
     pub net_name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AnalysisCommand {
     Op,
     Dc {
@@ -84,14 +85,14 @@ pub enum AnalysisCommand {
     },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AcType {
     Dec,
     Oct,
     Lin,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Model {
     pub name: String,
     pub model_type: String,
@@ -281,19 +282,11 @@ impl Netlist {
         }
 
         let name = parts[0].to_string();
-        let component_type = match name.chars().next() {
-            Some('R') | Some('r') => ComponentType::Resistor,
-            Some('C') | Some('c') => ComponentType::Capacitor,
-            Some('L') | Some('l') => ComponentType::Inductor,
-            Some('V') | Some('v') => ComponentType::VoltageSource,
-            Some('I') | Some('i') => ComponentType::CurrentSource,
-            Some('D') | Some('d') => ComponentType::Diode,
-            Some('Q') | Some('q') => ComponentType::Bjt,
-            Some('M') | Some('m') => ComponentType::Mosfet,
-            Some('X') | Some('x') => ComponentType::OpAmp,
-            Some('T') | Some('t') => ComponentType::Transformer,
-            _ => ComponentType::Custom(name.clone()),
-        };
+        let component_type = name
+            .chars()
+            .next()
+            .and_then(ComponentType::from_spice_prefix)
+            .unwrap_or_else(|| ComponentType::Custom(name.clone()));
 
         let nodes = parts[1..parts.len() - 1].iter().map(|s| s.to_string()).collect();
         let value = parts.last().unwrap().to_string();
@@ -312,6 +305,26 @@ impl Netlist {
     }
 }
 
+impl ComponentType {
+    /// Component type for a SPICE element's leading letter
+    pub fn from_spice_prefix(prefix: char) -> Option<Self> {
+        match prefix.to_ascii_uppercase() {
+            'R' => Some(ComponentType::Resistor),
+            'C' => Some(ComponentType::Capacitor),
+            'L' => Some(ComponentType::Inductor),
+            'V' => Some(ComponentType::VoltageSource),
+            'I' => Some(ComponentType::CurrentSource),
+            'D' => Some(ComponentType::Diode),
+            'Q' => Some(ComponentType::Bjt),
+            'M' => Some(ComponentType::Mosfet),
+            'X' => Some(ComponentType::OpAmp),
+            'T' => Some(ComponentType::Transformer),
+            'U' => Some(ComponentType::IntegratedCircuit),
+            _ => None,
+        }
+    }
+}
+
 impl Component {
     pub fn to_spice(&self) -> String {
         let mut spice = format!("{} ", self.name);
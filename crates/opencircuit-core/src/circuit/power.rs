@@ -0,0 +1,240 @@
+//! Power budget estimation for battery-powered designs
+//!
+//! With simulation results, each component's power is the voltage across its
+//! first two nodes times the current through it; resistors without a reported
+//! current use Ohm's law. Results holding waveforms average the instantaneous
+//! power instead. Without results, the rated dissipation in the
+//! component's `.model` card is used as a static estimate. Sources supply the
+//! power and are left out of the budget.
+
+use super::netlist::{Component, ComponentType, Netlist};
+use super::validation::CircuitValidator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// `.model` (or instance) parameters holding a rated power dissipation in watts
+pub const RATED_POWER_PARAMETERS: [&str; 4] = ["pd", "pdiss", "pmax", "power"];
+/// Share of the total power the `dominated_by` components account for
+pub const DOMINANT_POWER_SHARE: f64 = 0.8;
+
+/// Node voltages and branch currents at a circuit's operating point
+///
+/// Implemented by the simulation crate's results so the validator does not
+/// depend on the simulator.
+pub trait OperatingPoint {
+    /// Voltage of `node` relative to ground (V)
+    fn node_voltage(&self, node: &str) -> Option<f64>;
+    /// Current through `component` (A)
+    fn branch_current(&self, component: &str) -> Option<f64>;
+
+    /// Mean of the instantaneous power v(t)·i(t) in `component` (W), for
+    /// results holding waveforms
+    ///
+    /// The voltage is taken from `positive` to `negative`; `resistance` gives
+    /// the current as v/R when none is recorded. The product of the mean
+    /// voltage and mean current misses any correlation between the two, so
+    /// waveform results should override this. `None`, the default, means the
+    /// power is the product of `node_voltage` and `branch_current`.
+    fn average_power(&self, _component: &str, _positive: &str, _negative: &str, _resistance: Option<f64>) -> Option<f64> {
+        None
+    }
+}
+
+/// Estimated power drawn by a circuit
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PowerBudget {
+    pub total_power_mw: f64,
+    /// Power per component name (mW)
+    pub per_component: HashMap<String, f64>,
+    /// Largest consumers, highest first, that together draw
+    /// [`DOMINANT_POWER_SHARE`] of the total
+    pub dominated_by: Vec<(String, f64)>,
+}
+
+impl PowerBudget {
+    fn from_components(per_component: HashMap<String, f64>) -> Self {
+        let total_power_mw: f64 = per_component.values().sum();
+        let mut ranked: Vec<(String, f64)> = per_component
+            .iter()
+            .filter(|(_, power)| **power > 0.0)
+            .map(|(name, &power)| (name.clone(), power))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut dominated_by = Vec::new();
+        let mut covered = 0.0;
+        for (name, power) in ranked {
+            if covered >= total_power_mw * DOMINANT_POWER_SHARE {
+                break;
+            }
+            covered += power;
+            dominated_by.push((name, power));
+        }
+
+        Self { total_power_mw, per_component, dominated_by }
+    }
+
+    /// Runtime on a battery of `capacity_mah` at `voltage`, ignoring
+    /// regulator losses; effectively unlimited for a zero budget
+    pub fn battery_life(&self, capacity_mah: f64, voltage: f64) -> Duration {
+        let energy_mwh = capacity_mah * voltage;
+        if self.total_power_mw <= 0.0 || !energy_mwh.is_finite() {
+            return Duration::MAX;
+        }
+        let hours = (energy_mwh / self.total_power_mw).max(0.0);
+        Duration::try_from_secs_f64(hours * 3600.0).unwrap_or(Duration::MAX)
+    }
+}
+
+impl CircuitValidator {
+    /// Estimate the circuit's power draw, from simulation when available
+    ///
+    /// Components whose power can't be determined are left out.
+    pub fn estimate_power_consumption(
+        &self,
+        netlist: &Netlist,
+        simulation_results: Option<&dyn OperatingPoint>,
+    ) -> PowerBudget {
+        let per_component = netlist
+            .components
+            .iter()
+            .filter(|component| {
+                !matches!(component.component_type, ComponentType::VoltageSource | ComponentType::CurrentSource)
+            })
+            .filter_map(|component| {
+                let watts = match simulation_results {
+                    Some(results) => simulated_power(component, results),
+                    None => rated_power(netlist, component),
+                }?;
+                Some((component.name.clone(), watts.abs() * 1000.0))
+            })
+            .collect();
+
+        PowerBudget::from_components(per_component)
+    }
+}
+
+/// Power in watts from the voltage across and current through a component
+fn simulated_power(component: &Component, results: &dyn OperatingPoint) -> Option<f64> {
+    let (positive, negative) = (component.nodes.first()?, component.nodes.get(1)?);
+    let resistance = (component.component_type == ComponentType::Resistor)
+        .then(|| opencircuit_utils::units::parse_spice_value(&component.value).ok())
+        .flatten()
+        .filter(|&resistance| resistance > 0.0);
+    if let Some(power) = results.average_power(&component.name, positive, negative, resistance) {
+        return Some(power).filter(|power| power.is_finite());
+    }
+
+    let voltage = |node: &String| {
+        results
+            .node_voltage(node)
+            .or_else(|| (node == "0" || node.eq_ignore_ascii_case("gnd")).then_some(0.0))
+    };
+    let across = voltage(positive)? - voltage(negative)?;
+    let through = results
+        .branch_current(&component.name)
+        .or_else(|| Some(across / resistance?))?;
+    Some(across * through).filter(|power| power.is_finite())
+}
+
+/// Rated dissipation in watts from the instance or its `.model` parameters
+fn rated_power(netlist: &Netlist, component: &Component) -> Option<f64> {
+    let model = component.model.as_deref().and_then(|name| {
+        netlist.models.iter().find(|model| model.name.eq_ignore_ascii_case(name))
+    });
+    let find = |parameters: &HashMap<String, String>| {
+        RATED_POWER_PARAMETERS.iter().find_map(|key| {
+            let (_, value) = parameters.iter().find(|(name, _)| name.eq_ignore_ascii_case(key))?;
+            opencircuit_utils::units::parse_spice_value(value).ok()
+        })
+    };
+    find(&component.parameters)
+        .or_else(|| find(&model?.parameters))
+        .filter(|power| power.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::netlist::Model;
+
+    struct FixedPoint {
+        voltages: HashMap<&'static str, f64>,
+        currents: HashMap<&'static str, f64>,
+    }
+
+    impl OperatingPoint for FixedPoint {
+        fn node_voltage(&self, node: &str) -> Option<f64> {
+            self.voltages.get(node).copied()
+        }
+
+        fn branch_current(&self, component: &str) -> Option<f64> {
+            self.currents.get(component).copied()
+        }
+    }
+
+    fn component(name: &str, component_type: ComponentType, nodes: &[&str], value: &str) -> Component {
+        Component {
+            name: name.to_string(),
+            component_type,
+            nodes: nodes.iter().map(|node| node.to_string()).collect(),
+            value: value.to_string(),
+            model: None,
+            parameters: HashMap::new(),
+        }
+    }
+
+    fn netlist() -> Netlist {
+        let mut netlist = Netlist::new("Sensor node".to_string());
+        netlist.components.push(component("V1", ComponentType::VoltageSource, &["vcc", "0"], "3.3"));
+        netlist.components.push(component("R1", ComponentType::Resistor, &["vcc", "led"], "100"));
+        netlist.components.push(component("D1", ComponentType::Diode, &["led", "0"], ""));
+        netlist.components.push(component("R2", ComponentType::Resistor, &["vcc", "0"], "100k"));
+        netlist
+    }
+
+    #[test]
+    fn test_power_from_simulation() {
+        let point = FixedPoint {
+            voltages: HashMap::from([("vcc", 3.3), ("led", 1.8)]),
+            currents: HashMap::from([("D1", 0.015)]),
+        };
+        let budget = CircuitValidator::new().estimate_power_consumption(&netlist(), Some(&point));
+
+        // R1: 1.5 V x 15 mA, D1: 1.8 V x 15 mA, R2: 3.3 V over 100 kΩ
+        assert!((budget.per_component["R1"] - 22.5).abs() < 1e-9);
+        assert!((budget.per_component["D1"] - 27.0).abs() < 1e-9);
+        assert!((budget.per_component["R2"] - 0.1089).abs() < 1e-9);
+        assert!(!budget.per_component.contains_key("V1"));
+        assert!((budget.total_power_mw - 49.6089).abs() < 1e-9);
+        let dominant: Vec<&str> = budget.dominated_by.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(dominant, ["D1", "R1"]);
+    }
+
+    #[test]
+    fn test_static_estimate_from_model_ratings() {
+        let mut netlist = netlist();
+        netlist.components[2].model = Some("LED_RED".to_string());
+        netlist.models.push(Model {
+            name: "led_red".to_string(),
+            model_type: "D".to_string(),
+            parameters: HashMap::from([("PD".to_string(), "60m".to_string())]),
+        });
+        netlist.components[3].parameters.insert("power".to_string(), "0.125".to_string());
+
+        let budget = CircuitValidator::new().estimate_power_consumption(&netlist, None);
+        assert_eq!(budget.per_component.len(), 2);
+        assert!((budget.per_component["D1"] - 60.0).abs() < 1e-9);
+        assert!((budget.total_power_mw - 185.0).abs() < 1e-9);
+        assert_eq!(budget.dominated_by[0].0, "R2");
+    }
+
+    #[test]
+    fn test_battery_life() {
+        let budget = PowerBudget::from_components(HashMap::from([("U1".to_string(), 37.0)]));
+        // 1000 mAh at 3.7 V is 3700 mWh, 100 hours at 37 mW
+        assert_eq!(budget.battery_life(1000.0, 3.7).as_secs(), 360_000);
+        assert_eq!(PowerBudget::default().battery_life(1000.0, 3.7), Duration::MAX);
+    }
+}
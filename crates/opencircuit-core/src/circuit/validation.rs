@@ -3,7 +3,7 @@
 
 use super::netlist::{ComponentType, Netlist};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -26,6 +26,12 @@ pub enum ValidationError {
     FloatingNode(String),
     #[error("Invalid node connection: {0}")]
     InvalidConnection(String),
+    #[error("Floating power pin: {0}")]
+    FloatingPowerPin(String),
+    #[error("Unknown power net convention: {0}")]
+    UnknownPowerConvention(String),
+    #[error("Feedback loop detected: {0}")]
+    FeedbackLoop(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,8 +71,18 @@ pub enum RuleSeverity {
     Info,
 }
 
+/// Checker function for a user-defined design rule
+pub type RuleChecker = Box<dyn Fn(&Netlist) -> Result<(), ValidationError> + Send + Sync>;
+
+struct CustomRule {
+    name: String,
+    severity: RuleSeverity,
+    checker: RuleChecker,
+}
+
 pub struct CircuitValidator {
     design_rules: Vec<DesignRule>,
+    custom_rules: Vec<CustomRule>,
     min_component_values: HashMap<ComponentType, f64>,
     max_component_values: HashMap<ComponentType, f64>,
 }
@@ -75,6 +91,7 @@ impl CircuitValidator {
     pub fn new() -> Self {
         let mut validator = Self {
             design_rules: Vec::new(),
+            custom_rules: Vec::new(),
             min_component_values: HashMap::new(),
             max_component_values: HashMap::new(),
         };
@@ -124,6 +141,26 @@ impl CircuitValidator {
         self.max_component_values.insert(ComponentType::Inductor, 1000.0); // 1000 H
     }
 
+    /// Register a project-specific design rule checked on every `validate` call
+    pub fn add_custom_rule(&mut self, name: &str, severity: RuleSeverity, checker: RuleChecker) {
+        self.design_rules.push(DesignRule {
+            name: name.to_string(),
+            description: format!("Custom rule: {}", name),
+            check_function: "custom".to_string(),
+            severity: severity.clone(),
+        });
+
+        self.custom_rules.push(CustomRule {
+            name: name.to_string(),
+            severity,
+            checker,
+        });
+    }
+
+    pub fn design_rules(&self) -> &[DesignRule] {
+        &self.design_rules
+    }
+
     pub fn validate(&self, netlist: &Netlist) -> ValidationReport {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
@@ -153,6 +190,29 @@ impl CircuitValidator {
             errors.push(e.to_string());
         }
 
+        let loops = Self::find_feedback_loops(netlist);
+        if !loops.is_empty() {
+            let loops: Vec<String> = loops.iter().map(|path| path.join(" -> ")).collect();
+            warnings.push(ValidationError::FeedbackLoop(loops.join("; ")).to_string());
+        }
+
+        match self.validate_power_integrity(netlist) {
+            Ok(()) => {}
+            Err(e @ ValidationError::UnknownPowerConvention(_)) => warnings.push(e.to_string()),
+            Err(e) => errors.push(e.to_string()),
+        }
+
+        for rule in &self.custom_rules {
+            if let Err(e) = (rule.checker)(netlist) {
+                let message = format!("{}: {}", rule.name, e);
+                match rule.severity {
+                    RuleSeverity::Error => errors.push(message),
+                    RuleSeverity::Warning => warnings.push(message),
+                    RuleSeverity::Info => recommendations.push(message),
+                }
+            }
+        }
+
         // Add recommendations
         self.add_recommendations(netlist, &mut recommendations);
 
@@ -165,6 +225,170 @@ impl CircuitValidator {
         }
     }
 
+    /// Check that every IC and op-amp connects to both a supply net and ground
+    ///
+    /// A negative rail such as -15V stands in for ground on split-supply parts,
+    /// as long as the part also connects to another supply net.
+    ///
+    /// Returns `UnknownPowerConvention` when the netlist has no recognisable supply nets,
+    /// which `validate` reports as a warning rather than an error.
+    pub fn validate_power_integrity(&self, netlist: &Netlist) -> Result<(), ValidationError> {
+        let active_parts: Vec<_> = netlist
+            .components
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c.component_type,
+                    ComponentType::IntegratedCircuit | ComponentType::OpAmp
+                )
+            })
+            .collect();
+
+        if active_parts.is_empty() {
+            return Ok(());
+        }
+
+        let has_supply_convention = netlist
+            .components
+            .iter()
+            .flat_map(|c| c.nodes.iter())
+            .any(|node| is_supply_net(node));
+
+        if !has_supply_convention {
+            let names: Vec<&str> = active_parts.iter().map(|c| c.name.as_str()).collect();
+            return Err(ValidationError::UnknownPowerConvention(format!(
+                "no supply net (VCC, VDD, 3V3, 5V, ...) found; cannot check power pins of {}",
+                names.join(", ")
+            )));
+        }
+
+        let mut floating = Vec::new();
+        for component in active_parts {
+            if !component.nodes.iter().any(|node| is_supply_net(node)) {
+                floating.push(format!("{} has no supply connection", component.name));
+            }
+            let has_return = component.nodes.iter().any(|node| {
+                is_ground_net(node)
+                    || (is_negative_rail(node)
+                        && component.nodes.iter().any(|other| other != node && is_supply_net(other)))
+            });
+            if !has_return {
+                floating.push(format!("{} has no ground connection", component.name));
+            }
+        }
+
+        if floating.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::FloatingPowerPin(floating.join("; ")))
+        }
+    }
+
+    /// Find signal loops, each as the components along a DFS back-edge path
+    ///
+    /// Nets are graph vertices and components edges. Diodes, transistors and
+    /// op amps conduct from input to output (anode to cathode, base or gate to
+    /// the other terminals, inputs to the last pin); passives and sources go
+    /// both ways. Supply and ground nets are references rather than signal
+    /// paths and are left out, and loops made only of two-way components are
+    /// ordinary meshes, so only loops through a directed component are returned.
+    pub fn find_feedback_loops(netlist: &Netlist) -> Vec<Vec<String>> {
+        // (to, component, directed) edges keyed by net, in stable order
+        let mut graph: BTreeMap<&str, Vec<(&str, &str, bool)>> = BTreeMap::new();
+        for component in &netlist.components {
+            let nodes: Vec<&str> = component.nodes.iter().map(String::as_str).collect();
+            let edges: Vec<(&str, &str, bool)> = match (&component.component_type, nodes.as_slice()) {
+                (ComponentType::Diode, [anode, cathode, ..]) => vec![(*anode, *cathode, true)],
+                (ComponentType::Bjt, [collector, base, emitter, ..]) => {
+                    vec![(*base, *collector, true), (*base, *emitter, true)]
+                }
+                (ComponentType::Mosfet, [drain, gate, source, ..]) => {
+                    vec![(*gate, *drain, true), (*gate, *source, true)]
+                }
+                (ComponentType::OpAmp, [inputs @ .., output]) => {
+                    inputs.iter().map(|input| (*input, *output, true)).collect()
+                }
+                (ComponentType::Transformer, [p1, p2, s1, s2, ..]) => {
+                    vec![(*p1, *p2, false), (*s1, *s2, false), (*p1, *s1, false)]
+                }
+                (
+                    ComponentType::Resistor
+                    | ComponentType::Capacitor
+                    | ComponentType::Inductor
+                    | ComponentType::VoltageSource
+                    | ComponentType::CurrentSource,
+                    [a, b, ..],
+                ) => vec![(*a, *b, false)],
+                // Pin directions of other parts are unknown
+                _ => Vec::new(),
+            };
+
+            for (from, to, directed) in edges {
+                if from == to || is_reference_net(from) || is_reference_net(to) {
+                    continue;
+                }
+                graph.entry(from).or_default().push((to, &component.name, directed));
+                graph.entry(to).or_default();
+                if !directed {
+                    graph.entry(to).or_default().push((from, &component.name, false));
+                }
+            }
+        }
+
+        // Following directed edges first keeps a finished net from hiding a loop
+        for edges in graph.values_mut() {
+            edges.sort_by_key(|(_, _, directed)| !directed);
+        }
+
+        let mut loops = Vec::new();
+        let mut seen: HashSet<Vec<String>> = HashSet::new();
+        let mut visited: HashSet<&str> = HashSet::new();
+        for &start in graph.keys() {
+            if visited.contains(start) {
+                continue;
+            }
+            // Path of (net, component used to reach it, whether that edge was directed)
+            let mut stack: Vec<(&str, Option<&str>, bool)> = vec![(start, None, false)];
+            let mut next_edge: Vec<usize> = vec![0];
+            visited.insert(start);
+
+            while let Some(&(node, arrived_by, _)) = stack.last() {
+                let index = next_edge.last_mut().unwrap();
+                let Some(&(to, component, directed)) = graph[node].get(*index) else {
+                    stack.pop();
+                    next_edge.pop();
+                    continue;
+                };
+                *index += 1;
+
+                // Leaving a two-way component the way we came is not a loop
+                if !directed && arrived_by == Some(component) {
+                    continue;
+                }
+
+                if let Some(position) = stack.iter().position(|(net, _, _)| *net == to) {
+                    let path = &stack[position + 1..];
+                    if directed || path.iter().any(|(_, _, directed)| *directed) {
+                        let mut components: Vec<String> =
+                            path.iter().filter_map(|(_, by, _)| by.map(str::to_string)).collect();
+                        components.push(component.to_string());
+
+                        let mut key = components.clone();
+                        key.sort();
+                        if seen.insert(key) {
+                            loops.push(components);
+                        }
+                    }
+                } else if visited.insert(to) {
+                    stack.push((to, Some(component), directed));
+                    next_edge.push(0);
+                }
+            }
+        }
+
+        loops
+    }
+
     fn calculate_metrics(&self, netlist: &Netlist) -> ValidationMetrics {
         let mut metrics = ValidationMetrics {
             component_count: netlist.components.len(),
@@ -354,41 +578,54 @@ impl CircuitValidator {
         }
     }
 
+    /// Parse a netlist value, where `M` is milli and `MEG` is mega
     fn parse_component_value(&self, value_str: &str) -> Result<f64, ()> {
-        let value_str = value_str.to_lowercase();
-        
-        // Remove common suffixes and prefixes
-        let cleaned = value_str
-            .replace("ohm", "")
-            .replace("ω", "")
-            .replace("h", "")
-            .replace("f", "")
-            .replace("v", "")
-            .replace("a", "")
-            .replace("hz", "");
-
-        let multiplier = if cleaned.ends_with('k') {
-            1e3
-        } else if cleaned.ends_with('m') {
-            1e-3
-        } else if cleaned.ends_with('u') || cleaned.ends_with('μ') {
-            1e-6
-        } else if cleaned.ends_with('n') {
-            1e-9
-        } else if cleaned.ends_with('p') {
-            1e-12
-        } else if cleaned.ends_with('g') {
-            1e9
-        } else {
-            1.0
-        };
+        opencircuit_utils::units::parse_spice_value(value_str).map_err(|_| ())
+    }
+}
 
-        let numeric_str = cleaned.trim_end_matches(|c: char| c.is_alphabetic());
-        
-        numeric_str.parse::<f64>().map(|v| v * multiplier).map_err(|_| ())
+/// Whether a net name follows a common supply rail convention
+///
+/// Inputs such as VIN are signals to be regulated, not rails, and don't count.
+fn is_supply_net(node: &str) -> bool {
+    let name = node.trim_start_matches(['+', '-']).to_uppercase();
+    if matches!(name.as_str(), "V+" | "VPOS" | "VBAT") {
+        return true;
+    }
+    if name.starts_with("VCC") || name.starts_with("VDD") {
+        return true;
+    }
+
+    // Voltage-named rails such as 5V, -12V, 3V3, 1V8 or 3.3V
+    match name.split_once('V') {
+        Some((volts, decimals)) => {
+            !volts.is_empty()
+                && volts.chars().all(|c| c.is_ascii_digit() || c == '.')
+                && decimals.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
     }
 }
 
+/// Whether a net name is a negative voltage-named rail such as -15V or -5V
+fn is_negative_rail(node: &str) -> bool {
+    node.starts_with('-') && is_supply_net(node)
+}
+
+/// Supply and ground nets, which every loop would otherwise pass through
+fn is_reference_net(node: &str) -> bool {
+    is_supply_net(node) || is_ground_net(node)
+}
+
+/// Whether a net name follows a common ground or negative rail convention
+fn is_ground_net(node: &str) -> bool {
+    let name = node.to_uppercase();
+    name == "0"
+        || name.starts_with("GND")
+        || name.ends_with("GND")
+        || matches!(name.as_str(), "VSS" | "VEE" | "V-" | "VNEG")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -452,12 +689,163 @@ mod tests {
         assert!(!report.errors.is_empty());
     }
 
+    #[test]
+    fn test_custom_rules() {
+        let mut netlist = Netlist::new("Decoupling".to_string());
+        netlist.components.push(Component {
+            name: "V1".to_string(),
+            component_type: ComponentType::VoltageSource,
+            nodes: vec!["vcc".to_string(), "0".to_string()],
+            value: "5".to_string(),
+            model: None,
+            parameters: HashMap::new(),
+        });
+        for i in 1..=5 {
+            netlist.components.push(Component {
+                name: format!("C{}", i),
+                component_type: ComponentType::Capacitor,
+                nodes: vec!["vcc".to_string(), "0".to_string()],
+                value: "100n".to_string(),
+                model: None,
+                parameters: HashMap::new(),
+            });
+        }
+
+        let mut validator = CircuitValidator::new();
+        validator.add_custom_rule(
+            "Max Decoupling",
+            RuleSeverity::Warning,
+            Box::new(|netlist| {
+                let count = netlist
+                    .components
+                    .iter()
+                    .filter(|c| matches!(c.component_type, ComponentType::Capacitor))
+                    .count();
+                if count > 4 {
+                    Err(ValidationError::ValidationError(format!(
+                        "{} decoupling capacitors on one rail",
+                        count
+                    )))
+                } else {
+                    Ok(())
+                }
+            }),
+        );
+        validator.add_custom_rule(
+            "Requires Inductor",
+            RuleSeverity::Error,
+            Box::new(|netlist| {
+                if netlist
+                    .components
+                    .iter()
+                    .any(|c| matches!(c.component_type, ComponentType::Inductor))
+                {
+                    Ok(())
+                } else {
+                    Err(ValidationError::ValidationError("no filter inductor".to_string()))
+                }
+            }),
+        );
+
+        let report = validator.validate(&netlist);
+        assert!(!report.is_valid);
+        assert!(report.warnings.iter().any(|w| w.starts_with("Max Decoupling:")));
+        assert!(report.errors.iter().any(|e| e.starts_with("Requires Inductor:")));
+        assert_eq!(validator.design_rules().len(), 6);
+    }
+
+    fn op_amp(nodes: &[&str]) -> Component {
+        Component {
+            name: "X1".to_string(),
+            component_type: ComponentType::OpAmp,
+            nodes: nodes.iter().map(|n| n.to_string()).collect(),
+            value: "LM358".to_string(),
+            model: None,
+            parameters: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_power_integrity_warns_without_supply_net() {
+        let mut netlist = Netlist::new("Buffer".to_string());
+        netlist.components.push(Component {
+            name: "V1".to_string(),
+            component_type: ComponentType::VoltageSource,
+            nodes: vec!["in".to_string(), "0".to_string()],
+            value: "1".to_string(),
+            model: None,
+            parameters: HashMap::new(),
+        });
+        netlist.components.push(op_amp(&["in", "out", "out"]));
+
+        let validator = CircuitValidator::new();
+        assert!(matches!(
+            validator.validate_power_integrity(&netlist),
+            Err(ValidationError::UnknownPowerConvention(_))
+        ));
+
+        let report = validator.validate(&netlist);
+        assert!(report.warnings.iter().any(|w| w.contains("Unknown power net convention")));
+        assert!(!report.errors.iter().any(|e| e.contains("power")));
+    }
+
+    #[test]
+    fn test_power_integrity_detects_floating_pins() {
+        let mut netlist = Netlist::new("Amplifier".to_string());
+        netlist.components.push(Component {
+            name: "V1".to_string(),
+            component_type: ComponentType::VoltageSource,
+            nodes: vec!["VCC".to_string(), "0".to_string()],
+            value: "5".to_string(),
+            model: None,
+            parameters: HashMap::new(),
+        });
+        netlist.components.push(op_amp(&["in", "out", "out", "0"]));
+
+        let validator = CircuitValidator::new();
+        assert!(matches!(
+            validator.validate_power_integrity(&netlist),
+            Err(ValidationError::FloatingPowerPin(_))
+        ));
+
+        netlist.components[1] = op_amp(&["in", "out", "out", "VCC", "GND"]);
+        assert!(validator.validate_power_integrity(&netlist).is_ok());
+
+        assert!(is_supply_net("3V3"));
+        assert!(is_supply_net("+12V"));
+        assert!(is_supply_net("vdd_io"));
+        assert!(is_supply_net("-15V"));
+        assert!(!is_supply_net("out"));
+        assert!(!is_supply_net("VIN"));
+        assert!(is_ground_net("AGND"));
+    }
+
+    #[test]
+    fn test_power_integrity_accepts_split_supply() {
+        let mut netlist = Netlist::new("Inverting amplifier".to_string());
+        netlist.components.push(op_amp(&["in+", "in-", "+15V", "-15V", "out"]));
+
+        let validator = CircuitValidator::new();
+        assert!(validator.validate_power_integrity(&netlist).is_ok());
+
+        // A negative rail alone is still missing its second power pin
+        netlist.components[0] = op_amp(&["in", "out", "-15V"]);
+        assert!(matches!(
+            validator.validate_power_integrity(&netlist),
+            Err(ValidationError::FloatingPowerPin(_))
+        ));
+        assert!(is_negative_rail("-15V"));
+        assert!(!is_negative_rail("+15V"));
+    }
+
     #[test]
     fn test_parse_component_value() {
         let validator = CircuitValidator::new();
 
         assert_eq!(validator.parse_component_value("1k"), Ok(1000.0));
-        assert_eq!(validator.parse_component_value("1.5M"), Ok(1_500_000.0));
+        assert_eq!(validator.parse_component_value("1.5MEG"), Ok(1_500_000.0));
+        assert_eq!(validator.parse_component_value("1.5Meg"), Ok(1_500_000.0));
+        assert_eq!(validator.parse_component_value("1M"), Ok(1e-3));
         assert_eq!(validator.parse_component_value("10u"), Ok(10e-6));
         assert_eq!(validator.parse_component_value("100n"), Ok(100e-9));
     }
@@ -491,4 +879,45 @@ mod tests {
         assert_eq!(metrics.voltage_sources, 1);
         assert_eq!(metrics.resistors, 1);
     }
-}
\ No newline at end of file
+
+    fn component(name: &str, component_type: ComponentType, nodes: &[&str]) -> Component {
+        Component {
+            name: name.to_string(),
+            component_type,
+            nodes: nodes.iter().map(|n| n.to_string()).collect(),
+            value: "1k".to_string(),
+            model: None,
+            parameters: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_feedback_loops() {
+        let mut netlist = Netlist::new("Inverting amplifier".to_string());
+        netlist.components.push(component("V1", ComponentType::VoltageSource, &["in", "0"]));
+        netlist.components.push(component("RIN", ComponentType::Resistor, &["in", "inv"]));
+        netlist.components.push(component("RF", ComponentType::Resistor, &["inv", "out"]));
+        netlist.components.push(component("RL", ComponentType::Resistor, &["out", "0"]));
+        netlist.components.push(op_amp(&["0", "inv", "out"]));
+
+        assert_eq!(CircuitValidator::find_feedback_loops(&netlist), vec![vec!["X1".to_string(), "RF".to_string()]]);
+
+        let report = CircuitValidator::new().validate(&netlist);
+        assert!(report.warnings.iter().any(|w| w == "Feedback loop detected: X1 -> RF"));
+        assert!(!report.errors.iter().any(|e| e.contains("loop")));
+    }
+
+    #[test]
+    fn test_passive_meshes_and_references_are_not_loops() {
+        let mut netlist = Netlist::new("Bridge".to_string());
+        netlist.components.push(component("R1", ComponentType::Resistor, &["a", "b"]));
+        netlist.components.push(component("R2", ComponentType::Resistor, &["b", "c"]));
+        netlist.components.push(component("R3", ComponentType::Resistor, &["c", "a"]));
+        // Loops closed only through supply or ground do not count either
+        netlist.components.push(component("Q1", ComponentType::Bjt, &["VCC", "a", "0"]));
+        netlist.components.push(component("R4", ComponentType::Resistor, &["VCC", "a"]));
+        netlist.components.push(component("D1", ComponentType::Diode, &["c", "c"]));
+
+        assert!(CircuitValidator::find_feedback_loops(&netlist).is_empty());
+    }
+}
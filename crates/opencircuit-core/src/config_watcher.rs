@@ -0,0 +1,169 @@
+//! Hot reloading of the application configuration
+//!
+//! The config file's directory is watched, since editors often save by
+//! replacing the file. Events are debounced so a half-written file is not
+//! read. Only `log_level` and `ai_service_url` apply while running; changes to
+//! the other settings are logged and left at their running values.
+
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::{config_path, read_config, AppConfig, OpenCircuitError};
+
+/// Quiet period after the last file event before the config is re-read
+pub const DEBOUNCE_WINDOW: Duration = Duration::from_millis(500);
+
+/// Watches the config file and reports changes that can be applied at runtime
+///
+/// Watching stops when the watcher is dropped.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    _watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    /// Watch the application config file
+    pub fn new(on_change: impl Fn(AppConfig) + Send + 'static) -> Result<ConfigWatcher> {
+        Self::with_path(&config_path()?, on_change)
+    }
+
+    /// Watch a specific config file
+    pub fn with_path(path: &Path, on_change: impl Fn(AppConfig) + Send + 'static) -> Result<ConfigWatcher> {
+        let path = path.to_path_buf();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| OpenCircuitError::Config(format!("{} is not a file path", path.display())))?
+            .to_os_string();
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let mut current = if path.exists() { read_config(&path)? } else { AppConfig::default() };
+
+        let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let _ = sender.send(event);
+        })
+        .map_err(|e| OpenCircuitError::Config(format!("Failed to watch config: {}", e)))?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| OpenCircuitError::Config(format!("Failed to watch {}: {}", dir.display(), e)))?;
+
+        let config_file = path.clone();
+        std::thread::spawn(move || {
+            while let Ok(event) = receiver.recv() {
+                let concerns_config = match event {
+                    Ok(event) => {
+                        matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                            && event.paths.iter().any(|path| path.file_name() == Some(file_name.as_os_str()))
+                    }
+                    Err(e) => {
+                        tracing::warn!("Config watch error: {}", e);
+                        false
+                    }
+                };
+                if !concerns_config {
+                    continue;
+                }
+
+                // Wait until writes have settled
+                loop {
+                    match receiver.recv_timeout(DEBOUNCE_WINDOW) {
+                        Ok(_) => continue,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+
+                match read_config(&config_file) {
+                    Ok(updated) => {
+                        let applied = apply_runtime_changes(&current, updated);
+                        if applied != current {
+                            tracing::info!("Reloaded configuration from {}", config_file.display());
+                            current = applied.clone();
+                            on_change(applied);
+                        }
+                    }
+                    Err(e) => tracing::warn!("Ignoring config change: {}", e),
+                }
+            }
+        });
+
+        Ok(ConfigWatcher { path, _watcher: watcher })
+    }
+
+    /// The watched config file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Take the runtime-changeable settings from `updated`, keeping restart-only ones from `current`
+pub fn apply_runtime_changes(current: &AppConfig, updated: AppConfig) -> AppConfig {
+    let restart_only = [
+        ("database_path", updated.database_path != current.database_path),
+        ("ai_model", updated.ai_model != current.ai_model),
+        ("backup_enabled", updated.backup_enabled != current.backup_enabled),
+        ("auto_save_config", updated.auto_save_config != current.auto_save_config),
+    ];
+    for (setting, _) in restart_only.iter().filter(|(_, changed)| *changed) {
+        tracing::warn!("{} changed; restart OpenCircuit to use it", setting);
+    }
+
+    AppConfig {
+        log_level: updated.log_level,
+        ai_service_url: updated.ai_service_url,
+        ..current.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_only_settings_are_kept() {
+        let current = AppConfig { database_path: Some("/data/opencircuit.db".to_string()), ..AppConfig::default() };
+        let updated = AppConfig {
+            database_path: Some("/elsewhere/opencircuit.db".to_string()),
+            log_level: "debug".to_string(),
+            ai_service_url: "http://gpu-box:11434".to_string(),
+            ai_model: "llama3".to_string(),
+            backup_enabled: !current.backup_enabled,
+            ..AppConfig::default()
+        };
+
+        let applied = apply_runtime_changes(&current, updated);
+        assert_eq!(applied.database_path, current.database_path);
+        assert_eq!(applied.ai_model, current.ai_model);
+        assert_eq!(applied.backup_enabled, current.backup_enabled);
+        assert_eq!(applied.log_level, "debug");
+        assert_eq!(applied.ai_service_url, "http://gpu-box:11434");
+    }
+
+    #[test]
+    fn test_watcher_reports_changes() {
+        let dir = std::env::temp_dir().join(format!("opencircuit-config-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, toml::to_string_pretty(&AppConfig::default()).unwrap()).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let watcher = ConfigWatcher::with_path(&path, move |config| {
+            let _ = sender.send(config);
+        })
+        .unwrap();
+        assert_eq!(watcher.path(), path.as_path());
+
+        let updated = AppConfig { log_level: "trace".to_string(), ..AppConfig::default() };
+        std::fs::write(&path, toml::to_string_pretty(&updated).unwrap()).unwrap();
+
+        let reloaded = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        drop(watcher);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(reloaded.log_level, "trace");
+    }
+}
@@ -10,12 +10,14 @@ use uuid::Uuid;
 pub mod models;
 pub mod apis;
 pub mod circuit;
+pub mod config_watcher;
 
 pub use models::{Component as DbComponent, ComponentCategory, ComponentId, SpecValue, PriceInfo, PriceBreak, AvailabilityInfo, ComponentSearchFilter, ComponentSearchResult};
 pub use apis::{ApiError, ApiKey, RateLimit, CachedResponse, ApiCache, BaseApiClient, OctopartClient, DigiKeyClient, MouserClient};
-pub use circuit::{Netlist, NetlistError, ComponentType, CircuitValidator, ValidationReport, ValidationError};
+pub use circuit::{Netlist, NetlistError, ComponentType, CircuitValidator, OperatingPoint, PowerBudget, ValidationReport, ValidationError};
 pub use circuit::netlist as circuit_netlist;
 pub use circuit::validation as circuit_validation;
+pub use config_watcher::ConfigWatcher;
 
 /// Core error types for the OpenCircuit application
 #[derive(thiserror::Error, Debug)]
@@ -35,22 +37,245 @@ pub enum OpenCircuitError {
     #[error("PCB error: {0}")]
     Pcb(String),
     
+    #[error("Graphics error: {0}")]
+    Graphics(String),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    /// Third-party error without a `From` impl, see [`OpenCircuitError::with_source`]
+    #[error("{message}: {source}")]
+    Wrapped {
+        message: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl OpenCircuitError {
+    /// Prepend `ctx: ` to the error message
+    ///
+    /// Message variants keep their kind. IO and serialization errors become
+    /// [`OpenCircuitError::Wrapped`] so the original error stays the source.
+    pub fn context<C: fmt::Display>(self, ctx: C) -> Self {
+        let prepend = |message: String| format!("{}: {}", ctx, message);
+        match self {
+            Self::Config(message) => Self::Config(prepend(message)),
+            Self::Database(message) => Self::Database(prepend(message)),
+            Self::AiService(message) => Self::AiService(prepend(message)),
+            Self::Circuit(message) => Self::Circuit(prepend(message)),
+            Self::Pcb(message) => Self::Pcb(prepend(message)),
+            Self::Graphics(message) => Self::Graphics(prepend(message)),
+            Self::Io(source) => Self::with_source(Box::new(source), &ctx.to_string()),
+            Self::Serialization(source) => Self::with_source(Box::new(source), &ctx.to_string()),
+            Self::Wrapped { message, source } => Self::Wrapped { message: prepend(message), source },
+        }
+    }
+
+    /// Wrap an error that has no `From` impl, keeping it as the source
+    pub fn with_source(source: Box<dyn std::error::Error + Send + Sync>, msg: &str) -> Self {
+        Self::Wrapped { message: msg.to_string(), source }
+    }
+}
+
+/// `.context()` for results carrying an [`OpenCircuitError`]
+///
+/// Shadows `anyhow::Context` on these results, so import only one of the two
+/// in a module.
+pub trait OpenCircuitErrorExt<T> {
+    fn context<C: fmt::Display>(self, ctx: C) -> std::result::Result<T, OpenCircuitError>;
+}
+
+impl<T> OpenCircuitErrorExt<T> for std::result::Result<T, OpenCircuitError> {
+    fn context<C: fmt::Display>(self, ctx: C) -> std::result::Result<T, OpenCircuitError> {
+        self.map_err(|error| error.context(ctx))
+    }
+}
+
+impl From<ApiError> for OpenCircuitError {
+    fn from(error: ApiError) -> Self {
+        OpenCircuitError::AiService(error.to_string())
+    }
+}
+
+impl From<ValidationError> for OpenCircuitError {
+    fn from(error: ValidationError) -> Self {
+        OpenCircuitError::Circuit(error.to_string())
+    }
+}
+
+/// Configuration validation errors
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    #[error("Invalid AI service URL '{url}': {reason}")]
+    InvalidAiServiceUrl { url: String, reason: String },
+
+    #[error("Invalid log level '{0}': expected one of trace, debug, info, warn, error")]
+    InvalidLogLevel(String),
+
+    #[error("Invalid database path '{path}': {reason}")]
+    InvalidDatabasePath { path: String, reason: String },
+
+    #[error("Conflicting settings: {0}")]
+    ConflictingSettings(String),
 }
 
+/// Log levels accepted in the configuration
+const VALID_LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+
 /// Application configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StoredAppConfig")]
 pub struct AppConfig {
+    /// Component database file; the data directory is used when unset
     pub database_path: Option<String>,
     pub ai_service_url: String,
     pub ai_model: String,
     pub log_level: String,
-    pub auto_save: bool,
     pub backup_enabled: bool,
+    pub auto_save_config: AutoSaveConfig,
+}
+
+/// Config file contents, accepting the top-level `auto_save` flag older files use
+#[derive(Deserialize)]
+struct StoredAppConfig {
+    database_path: Option<String>,
+    ai_service_url: String,
+    ai_model: String,
+    log_level: String,
+    auto_save: Option<bool>,
+    backup_enabled: bool,
+    auto_save_config: Option<AutoSaveConfig>,
+}
+
+impl From<StoredAppConfig> for AppConfig {
+    fn from(stored: StoredAppConfig) -> Self {
+        // An [auto_save_config] section takes precedence over the legacy flag
+        let auto_save_config = stored.auto_save_config.unwrap_or_else(|| AutoSaveConfig {
+            enabled: stored.auto_save.unwrap_or(true),
+            ..AutoSaveConfig::default()
+        });
+
+        Self {
+            database_path: stored.database_path,
+            ai_service_url: stored.ai_service_url,
+            ai_model: stored.ai_model,
+            log_level: stored.log_level,
+            backup_enabled: stored.backup_enabled,
+            auto_save_config,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Validate the configuration, collecting every problem found
+    pub fn validate(&self) -> std::result::Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        match url::Url::parse(&self.ai_service_url) {
+            Ok(url) if !matches!(url.scheme(), "http" | "https") => {
+                errors.push(ConfigError::InvalidAiServiceUrl {
+                    url: self.ai_service_url.clone(),
+                    reason: format!("unsupported scheme '{}'", url.scheme()),
+                });
+            }
+            Ok(_) => {}
+            Err(e) => errors.push(ConfigError::InvalidAiServiceUrl {
+                url: self.ai_service_url.clone(),
+                reason: e.to_string(),
+            }),
+        }
+
+        if !VALID_LOG_LEVELS.contains(&self.log_level.to_lowercase().as_str()) {
+            errors.push(ConfigError::InvalidLogLevel(self.log_level.clone()));
+        }
+
+        if let Some(database_path) = &self.database_path {
+            if let Err(reason) = Self::check_database_path(Path::new(database_path)) {
+                errors.push(ConfigError::InvalidDatabasePath {
+                    path: database_path.clone(),
+                    reason,
+                });
+            }
+        }
+
+        if self.auto_save_config.enabled && self.auto_save_config.interval_seconds == 0 {
+            errors.push(ConfigError::ConflictingSettings(
+                "auto-save is enabled with an interval of 0 seconds".to_string(),
+            ));
+        }
+        if self.backup_enabled && self.auto_save_config.max_backups == 0 {
+            errors.push(ConfigError::ConflictingSettings(
+                "backups are enabled but max_backups is 0".to_string(),
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check that the database file's parent directory exists or can be created
+    fn check_database_path(path: &Path) -> std::result::Result<(), String> {
+        if path.as_os_str().is_empty() {
+            return Err("path is empty".to_string());
+        }
+        if path.is_dir() {
+            return Err("path is a directory".to_string());
+        }
+
+        // Find the closest existing ancestor; everything below it would be created
+        let mut ancestor = path.parent();
+        while let Some(dir) = ancestor {
+            if dir.as_os_str().is_empty() {
+                return Ok(());
+            }
+            if dir.exists() {
+                let metadata = fs::metadata(dir).map_err(|e| e.to_string())?;
+                if !metadata.is_dir() {
+                    return Err(format!("{} is not a directory", dir.display()));
+                }
+                if metadata.permissions().readonly() {
+                    return Err(format!("{} is read-only", dir.display()));
+                }
+                return Ok(());
+            }
+            ancestor = dir.parent();
+        }
+
+        Ok(())
+    }
+}
+
+/// Auto-save and backup settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoSaveConfig {
+    #[serde(alias = "auto_save")]
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    pub backup_dir: PathBuf,
+    pub max_backups: u32,
+}
+
+impl Default for AutoSaveConfig {
+    fn default() -> Self {
+        let backup_dir = dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("OpenCircuit")
+            .join("backups");
+
+        Self {
+            enabled: true,
+            interval_seconds: 300,
+            backup_dir,
+            max_backups: 10,
+        }
+    }
 }
 
 impl Default for AppConfig {
@@ -60,14 +285,14 @@ impl Default for AppConfig {
             ai_service_url: "http://localhost:11434".to_string(),
             ai_model: "llama2".to_string(),
             log_level: "info".to_string(),
-            auto_save: true,
             backup_enabled: true,
+            auto_save_config: AutoSaveConfig::default(),
         }
     }
 }
 
 /// Project metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Project {
     pub id: Uuid,
     pub name: String,
@@ -76,6 +301,9 @@ pub struct Project {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub version: String,
     pub author: Option<String>,
+    /// Project this one was forked from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
 }
 
 impl Project {
@@ -89,12 +317,52 @@ impl Project {
             updated_at: now,
             version: "1.0.0".to_string(),
             author: None,
+            parent_id: None,
         }
     }
     
     pub fn update(&mut self) {
         self.updated_at = chrono::Utc::now();
     }
+
+    /// Copy the project as a new design variant
+    ///
+    /// The fork gets a new ID and its timestamps record when it was forked.
+    pub fn fork(&self, new_name: &str) -> Project {
+        let now = chrono::Utc::now();
+        Project {
+            id: Uuid::new_v4(),
+            name: new_name.to_string(),
+            created_at: now,
+            updated_at: now,
+            parent_id: Some(self.id),
+            ..self.clone()
+        }
+    }
+
+    pub fn is_fork(&self) -> bool {
+        self.parent_id.is_some()
+    }
+
+    /// Ancestors found in `projects`, nearest first
+    ///
+    /// The chain stops at the first parent missing from `projects`.
+    pub fn ancestry_chain(&self, projects: &[Project]) -> Vec<Project> {
+        let mut chain: Vec<Project> = Vec::new();
+        let mut parent_id = self.parent_id;
+        while let Some(id) = parent_id {
+            // Guard against cycles in hand-edited project files
+            if id == self.id || chain.iter().any(|project| project.id == id) {
+                break;
+            }
+            let Some(parent) = projects.iter().find(|project| project.id == id) else {
+                break;
+            };
+            chain.push(parent.clone());
+            parent_id = parent.parent_id;
+        }
+        chain
+    }
 }
 
 /// Position in 2D space
@@ -112,6 +380,20 @@ impl Position {
     pub fn distance_to(&self, other: &Position) -> f64 {
         ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
     }
+
+    /// Linear interpolation towards `other`; `t` of 0 gives `self`, 1 gives `other`
+    pub fn interpolate(&self, other: &Position, t: f64) -> Position {
+        Position::new(self.x + (other.x - self.x) * t, self.y + (other.y - self.y) * t)
+    }
+
+    /// Point on the quadratic Bézier curve from `p1` to `p3` with control point `p2`
+    pub fn bezier(p1: &Position, p2: &Position, p3: &Position, t: f64) -> Position {
+        let u = 1.0 - t;
+        Position::new(
+            u * u * p1.x + 2.0 * u * t * p2.x + t * t * p3.x,
+            u * u * p1.y + 2.0 * u * t * p2.y + t * t * p3.y,
+        )
+    }
 }
 
 impl fmt::Display for Position {
@@ -135,6 +417,29 @@ impl Size {
     pub fn area(&self) -> f64 {
         self.width * self.height
     }
+
+    /// Width divided by height; infinite for a zero-height size
+    pub fn aspect_ratio(&self) -> f64 {
+        self.width / self.height
+    }
+
+    pub fn scale(&self, factor: f64) -> Size {
+        Size::new(self.width * factor, self.height * factor)
+    }
+
+    /// Largest proportional scaling of this size that fits inside `container`
+    pub fn fit_within(&self, container: &Size) -> Size {
+        let factor = [(container.width, self.width), (container.height, self.height)]
+            .into_iter()
+            .filter(|(_, length)| *length > 0.0)
+            .map(|(limit, length)| limit / length)
+            .fold(f64::INFINITY, f64::min);
+        if factor.is_finite() {
+            self.scale(factor)
+        } else {
+            *self
+        }
+    }
 }
 
 /// Rectangle defined by position and size
@@ -165,22 +470,115 @@ impl Rect {
             || self.position.y + self.size.height < other.position.y
             || other.position.y + other.size.height < self.position.y)
     }
+
+    pub fn center(&self) -> Position {
+        Position::new(self.position.x + self.size.width / 2.0, self.position.y + self.size.height / 2.0)
+    }
+
+    /// Grow the rectangle by `margin` on every side; a negative margin shrinks it
+    pub fn expand(&self, margin: f64) -> Rect {
+        let width = (self.size.width + 2.0 * margin).max(0.0);
+        let height = (self.size.height + 2.0 * margin).max(0.0);
+        let center = self.center();
+        Rect::new(center.x - width / 2.0, center.y - height / 2.0, width, height)
+    }
+
+    /// Overlapping area of two rectangles, if any
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let left = self.position.x.max(other.position.x);
+        let top = self.position.y.max(other.position.y);
+        let right = (self.position.x + self.size.width).min(other.position.x + other.size.width);
+        let bottom = (self.position.y + self.size.height).min(other.position.y + other.size.height);
+        Some(Rect::new(left, top, right - left, bottom - top))
+    }
+
+    /// Smallest rectangle containing all `positions`, or `None` when there are none
+    pub fn bounding_rect(positions: &[Position]) -> Option<Rect> {
+        let first = positions.first()?;
+        let (mut left, mut top, mut right, mut bottom) = (first.x, first.y, first.x, first.y);
+        for position in &positions[1..] {
+            left = left.min(position.x);
+            top = top.min(position.y);
+            right = right.max(position.x);
+            bottom = bottom.max(position.y);
+        }
+        Some(Rect::new(left, top, right - left, bottom - top))
+    }
+
+    /// Bounding box of this rectangle after rotating it by `angle_rad` around `center`
+    pub fn rotate_around(&self, center: &Position, angle_rad: f64) -> Rect {
+        let (sin, cos) = angle_rad.sin_cos();
+        let (x, y) = (self.position.x, self.position.y);
+        let (right, bottom) = (x + self.size.width, y + self.size.height);
+        let corners = [(x, y), (right, y), (right, bottom), (x, bottom)].map(|(px, py)| {
+            let (dx, dy) = (px - center.x, py - center.y);
+            Position::new(center.x + dx * cos - dy * sin, center.y + dx * sin + dy * cos)
+        });
+        // Four corners are never empty
+        Rect::bounding_rect(&corners).unwrap_or(*self)
+    }
+
+    /// Split into a `rows` × `cols` grid of equal cells, row by row
+    ///
+    /// Cell edges are computed from the parent so neighbouring cells share
+    /// them exactly. Returns no cells when `rows` or `cols` is zero.
+    pub fn subdivide(&self, rows: usize, cols: usize) -> Vec<Rect> {
+        let edge = |start: f64, length: f64, index: usize, count: usize| {
+            if index == count {
+                start + length
+            } else {
+                start + length * index as f64 / count as f64
+            }
+        };
+
+        let mut cells = Vec::with_capacity(rows * cols);
+        for row in 0..rows {
+            let top = edge(self.position.y, self.size.height, row, rows);
+            let bottom = edge(self.position.y, self.size.height, row + 1, rows);
+            for col in 0..cols {
+                let left = edge(self.position.x, self.size.width, col, cols);
+                let right = edge(self.position.x, self.size.width, col + 1, cols);
+                cells.push(Rect::new(left, top, right - left, bottom - top));
+            }
+        }
+        cells
+    }
 }
 
-/// Load application configuration
-pub fn load_config() -> Result<AppConfig> {
+/// Location of the application configuration file
+pub fn config_path() -> Result<PathBuf> {
     let config_dir = dirs::config_dir()
         .ok_or_else(|| OpenCircuitError::Config("Could not determine config directory".to_string()))?
         .join("OpenCircuit");
-    
-    std::fs::create_dir_all(&config_dir)?;
-    let config_path = config_dir.join("config.toml");
+    Ok(config_dir.join("config.toml"))
+}
+
+/// Parse and validate a configuration file
+pub fn read_config(path: &Path) -> Result<AppConfig> {
+    let config_str = std::fs::read_to_string(path)
+        .map_err(OpenCircuitError::from)
+        .context(format!("Failed to read {}", path.display()))?;
+    let config: AppConfig = toml::from_str(&config_str)
+        .map_err(|e| OpenCircuitError::Config(format!("Failed to parse config: {}", e)))?;
+    config.validate().map_err(|errors| {
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        OpenCircuitError::Config(format!("Invalid config: {}", messages.join("; ")))
+    })?;
+    Ok(config)
+}
+
+/// Load application configuration
+pub fn load_config() -> Result<AppConfig> {
+    let config_path = config_path()?;
+    if let Some(config_dir) = config_path.parent() {
+        create_config_dir(config_dir)?;
+    }
     
     if config_path.exists() {
-        let config_str = std::fs::read_to_string(&config_path)?;
-        let config: AppConfig = toml::from_str(&config_str)
-            .map_err(|e| OpenCircuitError::Config(format!("Failed to parse config: {}", e)))?;
-        Ok(config)
+        read_config(&config_path)
     } else {
         let default_config = AppConfig::default();
         save_config(&default_config)?;
@@ -190,24 +588,65 @@ pub fn load_config() -> Result<AppConfig> {
 
 /// Save application configuration
 pub fn save_config(config: &AppConfig) -> Result<()> {
-    let config_dir = dirs::config_dir()
-        .ok_or_else(|| OpenCircuitError::Config("Could not determine config directory".to_string()))?
-        .join("OpenCircuit");
-    
-    std::fs::create_dir_all(&config_dir)?;
-    let config_path = config_dir.join("config.toml");
+    let config_path = config_path()?;
+    if let Some(config_dir) = config_path.parent() {
+        create_config_dir(config_dir)?;
+    }
     
     let config_str = toml::to_string_pretty(config)
         .map_err(|e| OpenCircuitError::Config(format!("Failed to serialize config: {}", e)))?;
     
-    std::fs::write(&config_path, config_str)?;
+    std::fs::write(&config_path, config_str)
+        .map_err(OpenCircuitError::from)
+        .context(format!("Failed to write {}", config_path.display()))?;
     Ok(())
 }
 
+fn create_config_dir(config_dir: &Path) -> std::result::Result<(), OpenCircuitError> {
+    std::fs::create_dir_all(config_dir)
+        .map_err(OpenCircuitError::from)
+        .context(format!("Failed to create {}", config_dir.display()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_conversions() {
+        fn validate(report: std::result::Result<(), ValidationError>) -> std::result::Result<(), OpenCircuitError> {
+            report?;
+            Ok(())
+        }
+        fn fetch(response: std::result::Result<(), ApiError>) -> std::result::Result<(), OpenCircuitError> {
+            response?;
+            Ok(())
+        }
+
+        let error = validate(Err(ValidationError::MissingGround("no node 0".to_string()))).unwrap_err();
+        assert_eq!(error.to_string(), "Circuit error: Missing ground reference: no node 0");
+
+        let error = fetch(Err(ApiError::RateLimitExceeded { service: "DigiKey".to_string() })).unwrap_err();
+        assert!(matches!(error, OpenCircuitError::AiService(message) if message == "Rate limit exceeded for DigiKey"));
+    }
+
+    #[test]
+    fn test_error_context() {
+        let error = OpenCircuitError::Config("missing key".to_string()).context("Loading /etc/oc.toml");
+        assert!(matches!(&error, OpenCircuitError::Config(message) if message == "Loading /etc/oc.toml: missing key"));
+
+        let missing: std::result::Result<String, OpenCircuitError> =
+            std::fs::read_to_string("/nonexistent/opencircuit.toml").map_err(OpenCircuitError::from);
+        let error = missing.context("Failed to read /nonexistent/opencircuit.toml").unwrap_err();
+        assert!(error.to_string().starts_with("Failed to read /nonexistent/opencircuit.toml: "));
+        let source = std::error::Error::source(&error).unwrap();
+        assert_eq!(source.downcast_ref::<std::io::Error>().unwrap().kind(), std::io::ErrorKind::NotFound);
+
+        let parse_error = "12V".parse::<f64>().unwrap_err();
+        let error = OpenCircuitError::with_source(Box::new(parse_error), "Invalid supply").context("R1");
+        assert_eq!(error.to_string(), "R1: Invalid supply: invalid float literal");
+    }
+
     #[test]
     fn test_position_distance() {
         let p1 = Position::new(0.0, 0.0);
@@ -232,11 +671,138 @@ mod tests {
         assert!(!rect1.intersects(&rect3));
     }
 
+    #[test]
+    fn test_position_interpolation() {
+        let start = Position::new(0.0, 0.0);
+        let end = Position::new(10.0, -4.0);
+        assert_eq!(start.interpolate(&end, 0.0), start);
+        assert_eq!(start.interpolate(&end, 0.5), Position::new(5.0, -2.0));
+        assert_eq!(start.interpolate(&end, 1.0), end);
+
+        let control = Position::new(5.0, 10.0);
+        assert_eq!(Position::bezier(&start, &control, &end, 0.0), start);
+        assert_eq!(Position::bezier(&start, &control, &end, 0.5), Position::new(5.0, 4.0));
+        assert_eq!(Position::bezier(&start, &control, &end, 1.0), end);
+    }
+
+    #[test]
+    fn test_rect_center_expand_intersect() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 6.0);
+        assert_eq!(rect.center(), Position::new(5.0, 3.0));
+        assert_eq!(rect.expand(1.0), Rect::new(-1.0, -1.0, 12.0, 8.0));
+        assert_eq!(rect.expand(-4.0), Rect::new(4.0, 3.0, 2.0, 0.0));
+
+        assert_eq!(rect.intersect(&Rect::new(5.0, 2.0, 10.0, 10.0)), Some(Rect::new(5.0, 2.0, 5.0, 4.0)));
+        assert_eq!(rect.intersect(&Rect::new(20.0, 0.0, 1.0, 1.0)), None);
+        assert!(rect.subdivide(0, 3).is_empty());
+    }
+
+    #[test]
+    fn test_size_fit_within() {
+        let size = Size::new(16.0, 9.0);
+        assert_eq!(size.aspect_ratio(), 16.0 / 9.0);
+        assert_eq!(size.scale(2.0), Size::new(32.0, 18.0));
+        assert_eq!(size.fit_within(&Size::new(32.0, 32.0)), Size::new(32.0, 18.0));
+        assert_eq!(size.fit_within(&Size::new(100.0, 4.5)), Size::new(8.0, 4.5));
+        // A flat size only scales along its non-zero side
+        assert_eq!(Size::new(4.0, 0.0).fit_within(&Size::new(8.0, 1.0)), Size::new(8.0, 0.0));
+        assert_eq!(Size::new(0.0, 0.0).fit_within(&Size::new(8.0, 1.0)), Size::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_rect_bounding_and_rotation() {
+        assert_eq!(Rect::bounding_rect(&[]), None);
+        let points = [Position::new(2.0, 5.0), Position::new(-1.0, 3.0), Position::new(4.0, -2.0)];
+        assert_eq!(Rect::bounding_rect(&points), Some(Rect::new(-1.0, -2.0, 5.0, 7.0)));
+
+        let footprint = Rect::new(0.0, 0.0, 4.0, 2.0);
+        let rotated = footprint.rotate_around(&footprint.center(), std::f64::consts::FRAC_PI_2);
+        assert!((rotated.position.x - 1.0).abs() < 1e-12);
+        assert!((rotated.position.y + 1.0).abs() < 1e-12);
+        assert!((rotated.size.width - 2.0).abs() < 1e-12);
+        assert!((rotated.size.height - 4.0).abs() < 1e-12);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_subdivide_tiles_parent(
+            x in -1e3f64..1e3,
+            y in -1e3f64..1e3,
+            width in 1e-3f64..1e3,
+            height in 1e-3f64..1e3,
+            rows in 1usize..12,
+            cols in 1usize..12,
+        ) {
+            let parent = Rect::new(x, y, width, height);
+            let cells = parent.subdivide(rows, cols);
+            proptest::prop_assert_eq!(cells.len(), rows * cols);
+
+            let tolerance = 1e-9 * (1.0 + x.abs().max(y.abs()) + width.max(height));
+            let area: f64 = cells.iter().map(|cell| cell.size.area()).sum();
+            proptest::prop_assert!((area - parent.size.area()).abs() <= tolerance * width.max(height));
+
+            for (index, cell) in cells.iter().enumerate() {
+                let (row, col) = (index / cols, index % cols);
+                proptest::prop_assert!(cell.size.width > 0.0 && cell.size.height > 0.0);
+                // Outer cells line up with the parent's edges
+                if col == 0 {
+                    proptest::prop_assert_eq!(cell.position.x, x);
+                }
+                if row == 0 {
+                    proptest::prop_assert_eq!(cell.position.y, y);
+                }
+                if col == cols - 1 {
+                    proptest::prop_assert!((cell.position.x + cell.size.width - (x + width)).abs() <= tolerance);
+                }
+                if row == rows - 1 {
+                    proptest::prop_assert!((cell.position.y + cell.size.height - (y + height)).abs() <= tolerance);
+                }
+                // Neighbouring cells meet without gaps or overlap
+                if col + 1 < cols {
+                    let right = &cells[index + 1];
+                    proptest::prop_assert!((cell.position.x + cell.size.width - right.position.x).abs() <= tolerance);
+                    proptest::prop_assert_eq!(right.position.y, cell.position.y);
+                }
+                if row + 1 < rows {
+                    let below = &cells[index + cols];
+                    proptest::prop_assert!((cell.position.y + cell.size.height - below.position.y).abs() <= tolerance);
+                    proptest::prop_assert_eq!(below.position.x, cell.position.x);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_project_creation() {
         let project = Project::new("Test Project".to_string());
         assert_eq!(project.name, "Test Project");
         assert_eq!(project.version, "1.0.0");
+        assert!(!project.is_fork());
+    }
+
+    #[test]
+    fn test_project_fork_ancestry() {
+        let mut base = Project::new("Power Supply".to_string());
+        base.description = Some("Bench supply".to_string());
+        let five_volt = base.fork("Power Supply 5V");
+        let low_noise = five_volt.fork("Power Supply 5V Low Noise");
+
+        assert!(five_volt.is_fork());
+        assert_ne!(five_volt.id, base.id);
+        assert_eq!(five_volt.parent_id, Some(base.id));
+        assert_eq!(five_volt.description, base.description);
+        assert!(five_volt.created_at >= base.created_at);
+
+        let projects = vec![base.clone(), five_volt.clone(), low_noise.clone()];
+        assert_eq!(low_noise.ancestry_chain(&projects), vec![five_volt.clone(), base.clone()]);
+        assert!(base.ancestry_chain(&projects).is_empty());
+        // Missing ancestors end the chain
+        assert_eq!(low_noise.ancestry_chain(&projects[1..]), vec![five_volt.clone()]);
+
+        let toml = toml::to_string(&five_volt).unwrap();
+        assert!(toml.contains(&format!("parent_id = \"{}\"", base.id)));
+        assert_eq!(toml::from_str::<Project>(&toml).unwrap(), five_volt);
+        assert_eq!(toml::from_str::<Project>(&toml::to_string(&base).unwrap()).unwrap().parent_id, None);
     }
 
     #[test]
@@ -244,6 +810,64 @@ mod tests {
         let config = AppConfig::default();
         assert_eq!(config.ai_service_url, "http://localhost:11434");
         assert_eq!(config.ai_model, "llama2");
-        assert!(config.auto_save);
+        assert!(config.auto_save_config.enabled);
+        assert_eq!(config.auto_save_config.interval_seconds, 300);
+    }
+
+    #[test]
+    fn test_config_validate_default() {
+        assert!(AppConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_validate_reports_all_errors() {
+        let mut config = AppConfig::default();
+        config.ai_service_url = "not a url".to_string();
+        config.log_level = "verbose".to_string();
+        config.auto_save_config.interval_seconds = 0;
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], ConfigError::InvalidAiServiceUrl { .. }));
+        assert_eq!(errors[1], ConfigError::InvalidLogLevel("verbose".to_string()));
+        assert!(matches!(errors[2], ConfigError::ConflictingSettings(_)));
+    }
+
+    #[test]
+    fn test_read_legacy_config() {
+        let path = std::env::temp_dir().join(format!("opencircuit-legacy-{}.toml", Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "ai_service_url = \"http://localhost:11434\"\n\
+             ai_model = \"llama2\"\n\
+             log_level = \"info\"\n\
+             auto_save = false\n\
+             backup_enabled = true\n",
+        )
+        .unwrap();
+
+        let config = read_config(&path);
+        std::fs::remove_file(&path).unwrap();
+        let config = config.unwrap();
+        assert!(!config.auto_save_config.enabled);
+        assert_eq!(config.auto_save_config.interval_seconds, 300);
+
+        // Saved configs only carry the section, which round-trips unchanged
+        let saved = toml::to_string(&config).unwrap();
+        assert!(!saved.contains("\nauto_save ="));
+        assert_eq!(toml::from_str::<AppConfig>(&saved).unwrap(), config);
+    }
+
+    #[test]
+    fn test_config_validate_database_path() {
+        let dir = std::env::temp_dir();
+        let mut config = AppConfig::default();
+
+        config.database_path = Some(dir.join("new_dir").join("opencircuit.db").display().to_string());
+        assert!(config.validate().is_ok());
+
+        config.database_path = Some(dir.display().to_string());
+        let errors = config.validate().unwrap_err();
+        assert!(matches!(errors[0], ConfigError::InvalidDatabasePath { .. }));
     }
 }
\ No newline at end of file
@@ -21,6 +21,8 @@ pub enum ComponentCategory {
     Sensors,
     Power,
     Mechanical,
+    /// Voltage and current sources, as found in SPICE netlists
+    Sources,
     Custom(String),
 }
 
@@ -39,6 +41,7 @@ impl ComponentCategory {
             ComponentCategory::Sensors => "Sensors",
             ComponentCategory::Power => "Power",
             ComponentCategory::Mechanical => "Mechanical",
+            ComponentCategory::Sources => "Sources",
             ComponentCategory::Custom(name) => name,
         }
     }
@@ -57,9 +60,26 @@ impl ComponentCategory {
             "Sensors" => ComponentCategory::Sensors,
             "Power" => ComponentCategory::Power,
             "Mechanical" => ComponentCategory::Mechanical,
+            "Sources" => ComponentCategory::Sources,
             _ => ComponentCategory::Custom(s.to_string()),
         }
     }
+
+    /// Infer the category from a SPICE element's leading letter
+    ///
+    /// Letters with no matching category become `Custom("SPICE <letter>")`.
+    pub fn from_spice_element_prefix(prefix: char) -> Self {
+        match prefix.to_ascii_uppercase() {
+            'R' => ComponentCategory::Resistors,
+            'C' => ComponentCategory::Capacitors,
+            'L' => ComponentCategory::Inductors,
+            'D' => ComponentCategory::Diodes,
+            'Q' | 'M' | 'J' => ComponentCategory::Transistors,
+            'V' | 'I' => ComponentCategory::Sources,
+            'X' => ComponentCategory::IntegratedCircuits,
+            other => ComponentCategory::Custom(format!("SPICE {}", other)),
+        }
+    }
 }
 
 /// Component specification value types
@@ -90,6 +110,28 @@ impl SpecValue {
             SpecValue::List(list) => list.join(", "),
         }
     }
+
+    /// Numeric value, parsing strings with SI prefixes such as "4.7k" or "100nF"
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            SpecValue::Number(n) => Some(*n),
+            SpecValue::Integer(i) => Some(*i as f64),
+            SpecValue::String(s) => opencircuit_utils::units::parse_si_value(s).ok(),
+            _ => None,
+        }
+    }
+
+    /// Predicate for `min <= value <= max`, comparing numbers and SI strings alike
+    ///
+    /// Values that are not numeric never match, nor does anything when a bound
+    /// is not numeric.
+    pub fn compare_range(min: &SpecValue, max: &SpecValue) -> impl Fn(&SpecValue) -> bool {
+        let bounds = min.as_f64().zip(max.as_f64());
+        move |value| match (bounds, value.as_f64()) {
+            (Some((min, max)), Some(value)) => min <= value && value <= max,
+            _ => false,
+        }
+    }
 }
 
 /// Component pricing information
@@ -258,6 +300,8 @@ pub struct ComponentSearchFilter {
     pub part_number_contains: Option<String>,
     pub description_contains: Option<String>,
     pub specifications: HashMap<String, SpecValue>,
+    /// Inclusive numeric bounds per specification key
+    pub spec_ranges: HashMap<String, (SpecValue, SpecValue)>,
     pub has_datasheet: Option<bool>,
     pub has_footprint: Option<bool>,
     pub in_stock_only: Option<bool>,
@@ -293,6 +337,11 @@ impl ComponentSearchFilter {
         self
     }
 
+    pub fn with_spec_range(mut self, key: String, min: SpecValue, max: SpecValue) -> Self {
+        self.spec_ranges.insert(key, (min, max));
+        self
+    }
+
     pub fn with_datasheet_required(mut self) -> Self {
         self.has_datasheet = Some(true);
         self
@@ -349,6 +398,14 @@ impl ComponentSearchFilter {
             }
         }
 
+        // Check specification ranges
+        for (key, (min, max)) in &self.spec_ranges {
+            let in_range = SpecValue::compare_range(min, max);
+            if !component.get_spec(key).is_some_and(in_range) {
+                return false;
+            }
+        }
+
         // Check datasheet requirement
         if let Some(true) = self.has_datasheet {
             if component.datasheet_url.is_none() {
@@ -405,6 +462,28 @@ impl ComponentSearchResult {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_category_names_match_utils_constants() {
+        for name in opencircuit_utils::constants::COMPONENT_CATEGORIES {
+            let category = ComponentCategory::from_str(name);
+            assert!(!matches!(category, ComponentCategory::Custom(_)), "{} is not a known category", name);
+            assert_eq!(category.as_str(), name);
+        }
+    }
+
+    #[test]
+    fn test_category_from_spice_element_prefix() {
+        assert_eq!(ComponentCategory::from_spice_element_prefix('r'), ComponentCategory::Resistors);
+        assert_eq!(ComponentCategory::from_spice_element_prefix('M'), ComponentCategory::Transistors);
+        assert_eq!(ComponentCategory::from_spice_element_prefix('Q'), ComponentCategory::Transistors);
+        assert_eq!(ComponentCategory::from_spice_element_prefix('I'), ComponentCategory::Sources);
+        assert_eq!(ComponentCategory::from_spice_element_prefix('X'), ComponentCategory::IntegratedCircuits);
+        assert_eq!(
+            ComponentCategory::from_spice_element_prefix('k'),
+            ComponentCategory::Custom("SPICE K".to_string())
+        );
+    }
+
     #[test]
     fn test_component_creation() {
         let component = Component::new(
@@ -475,4 +554,62 @@ mod tests {
         let spec2 = SpecValue::List(vec!["A".to_string(), "B".to_string()]);
         assert_eq!(spec2.as_string(), "A, B");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_compare_range_mixed_types() {
+        let string = |s: &str| SpecValue::String(s.to_string());
+        let bounds = [
+            (SpecValue::Number(1000.0), SpecValue::Number(10000.0)),
+            (SpecValue::Number(1000.0), string("10k")),
+            (string("1k"), SpecValue::Number(10000.0)),
+            (string("1kΩ"), string("10kΩ")),
+        ];
+
+        for (min, max) in &bounds {
+            let in_range = SpecValue::compare_range(min, max);
+            assert!(in_range(&string("4.7k")), "{:?}..{:?}", min, max);
+            assert!(in_range(&SpecValue::Number(4700.0)));
+            assert!(in_range(&SpecValue::Integer(1000)));
+            assert!(in_range(&string("10k")));
+            assert!(!in_range(&string("100k")));
+            assert!(!in_range(&SpecValue::Number(999.0)));
+            assert!(!in_range(&string("n/a")));
+            assert!(!in_range(&SpecValue::Boolean(true)));
+        }
+
+        let unbounded = SpecValue::compare_range(&string("low"), &SpecValue::Number(1.0));
+        assert!(!unbounded(&SpecValue::Number(0.5)));
+    }
+
+    #[test]
+    fn test_component_filter_spec_range() {
+        let mut component = Component::new(
+            "C0805".to_string(),
+            "Test Corp".to_string(),
+            ComponentCategory::Capacitors,
+            "Test capacitor".to_string(),
+        );
+        component.set_spec("capacitance".to_string(), SpecValue::String("100nF".to_string()));
+
+        let filter = ComponentSearchFilter::new().with_spec_range(
+            "capacitance".to_string(),
+            SpecValue::Number(10e-9),
+            SpecValue::String("1u".to_string()),
+        );
+        assert!(filter.matches(&component));
+
+        let too_small = ComponentSearchFilter::new().with_spec_range(
+            "capacitance".to_string(),
+            SpecValue::String("1uF".to_string()),
+            SpecValue::Number(10e-6),
+        );
+        assert!(!too_small.matches(&component));
+
+        let missing = ComponentSearchFilter::new().with_spec_range(
+            "voltage".to_string(),
+            SpecValue::Number(0.0),
+            SpecValue::Number(50.0),
+        );
+        assert!(!missing.matches(&component));
+    }
+}
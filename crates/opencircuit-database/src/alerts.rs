@@ -0,0 +1,273 @@
+//! Back-in-stock alerts for tracked components
+//!
+//! Alerts fire once: the first check that finds the supplier stock at or above
+//! the threshold marks the alert as fired and reports it.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use opencircuit_core::apis::ApiManager;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::Database;
+
+/// Subscription to a component coming back into stock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StockAlert {
+    pub component_id: String,
+    pub threshold_quantity: u32,
+    pub notify_email: Option<String>,
+    pub callback_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StockAlert {
+    pub fn new(component_id: String, threshold_quantity: u32) -> Self {
+        Self {
+            component_id,
+            threshold_quantity,
+            notify_email: None,
+            callback_url: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_email(mut self, email: String) -> Self {
+        self.notify_email = Some(email);
+        self
+    }
+
+    pub fn with_callback_url(mut self, url: String) -> Self {
+        self.callback_url = Some(url);
+        self
+    }
+}
+
+/// Alert whose threshold was reached
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiredAlert {
+    pub alert_id: String,
+    pub component_id: String,
+    pub current_stock: u32,
+}
+
+impl Database {
+    /// Subscribe to a stock alert, returning its ID
+    pub fn add_stock_alert(&self, alert: StockAlert) -> Result<String> {
+        let id = Uuid::new_v4().to_string();
+        let conn = self.connection.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO stock_alerts (
+                id, component_id, threshold_quantity, notify_email, callback_url, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            params![
+                id,
+                alert.component_id,
+                alert.threshold_quantity,
+                alert.notify_email,
+                alert.callback_url,
+                alert.created_at.to_rfc3339()
+            ],
+        )?;
+        Ok(id)
+    }
+
+    /// Alerts that have not fired yet, keyed by ID
+    pub fn pending_stock_alerts(&self) -> Result<Vec<(String, StockAlert)>> {
+        let conn = self.connection.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, component_id, threshold_quantity, notify_email, callback_url, created_at
+            FROM stock_alerts WHERE fired_at IS NULL ORDER BY created_at
+            "#,
+        )?;
+
+        let alert_iter = stmt.query_map([], |row| {
+            let created_at: String = row.get(5)?;
+            Ok((
+                row.get(0)?,
+                StockAlert {
+                    component_id: row.get(1)?,
+                    threshold_quantity: row.get(2)?,
+                    notify_email: row.get(3)?,
+                    callback_url: row.get(4)?,
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                },
+            ))
+        })?;
+
+        let mut alerts = Vec::new();
+        for alert in alert_iter {
+            alerts.push(alert?);
+        }
+        Ok(alerts)
+    }
+
+    /// Remove a stock alert
+    pub fn remove_stock_alert(&self, id: &str) -> Result<bool> {
+        let conn = self.connection.lock().unwrap();
+        let rows_affected = conn.execute("DELETE FROM stock_alerts WHERE id = ?", params![id])?;
+        Ok(rows_affected > 0)
+    }
+
+    /// Fire pending alerts whose component stock reached the threshold
+    pub fn fire_stock_alerts(&self, component_id: &str, current_stock: u32) -> Result<Vec<FiredAlert>> {
+        let due: Vec<String> = self
+            .pending_stock_alerts()?
+            .into_iter()
+            .filter(|(_, alert)| alert.component_id == component_id && current_stock >= alert.threshold_quantity)
+            .map(|(id, _)| id)
+            .collect();
+
+        let conn = self.connection.lock().unwrap();
+        let fired_at = Utc::now().to_rfc3339();
+        let mut fired = Vec::new();
+        for alert_id in due {
+            conn.execute("UPDATE stock_alerts SET fired_at = ? WHERE id = ?", params![fired_at, alert_id])?;
+            fired.push(FiredAlert { alert_id, component_id: component_id.to_string(), current_stock });
+        }
+        Ok(fired)
+    }
+
+    /// Refresh stock from suppliers and fire alerts whose threshold is reached
+    ///
+    /// Components the suppliers do not report a quantity for are skipped.
+    pub async fn check_and_fire_alerts(&self, api_manager: &ApiManager) -> Result<Vec<FiredAlert>> {
+        let mut component_ids: Vec<String> =
+            self.pending_stock_alerts()?.into_iter().map(|(_, alert)| alert.component_id).collect();
+        component_ids.sort();
+        component_ids.dedup();
+
+        let mut fired = Vec::new();
+        for component_id in component_ids {
+            let Some(record) = self.get_component(&component_id)? else {
+                continue;
+            };
+            let details = match api_manager.get_component_details(&record.part_number).await {
+                Ok(details) => details,
+                Err(e) => {
+                    tracing::warn!("Stock refresh for {} failed: {}", record.part_number, e);
+                    continue;
+                }
+            };
+            let stock = details
+                .and_then(|component| component.availability)
+                .and_then(|availability| availability.quantity_available);
+            if let Some(stock) = stock {
+                fired.extend(self.fire_stock_alerts(&component_id, stock)?);
+            }
+        }
+
+        Ok(fired)
+    }
+}
+
+/// Check stock alerts every `interval`, sending fired alerts to the returned channel
+///
+/// The task stops once the receiver is dropped.
+pub fn spawn_stock_alert_monitor(
+    database: Arc<Database>,
+    api_manager: Arc<ApiManager>,
+    interval: Duration,
+) -> (JoinHandle<()>, mpsc::UnboundedReceiver<FiredAlert>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let handle = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match database.check_and_fire_alerts(&api_manager).await {
+                Ok(fired) => {
+                    for alert in fired {
+                        if sender.send(alert).is_err() {
+                            return;
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Stock alert check failed: {}", e),
+            }
+            if sender.is_closed() {
+                return;
+            }
+        }
+    });
+    (handle, receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ComponentRecord;
+
+    fn database_with_component(id: &str) -> Database {
+        let db = Database::new_in_memory().unwrap();
+        db.create_component(&ComponentRecord {
+            id: id.to_string(),
+            part_number: "LM317T".to_string(),
+            manufacturer: "Texas Instruments".to_string(),
+            category: "Power".to_string(),
+            description: None,
+            datasheet_url: None,
+            specifications: None,
+            footprint: None,
+            symbol: None,
+            created_at: "2025-01-27T12:00:00Z".to_string(),
+            updated_at: "2025-01-27T12:00:00Z".to_string(),
+        })
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_add_and_remove_stock_alert() {
+        let db = database_with_component("c1");
+        let alert = StockAlert::new("c1".to_string(), 100).with_email("eng@example.com".to_string());
+        let id = db.add_stock_alert(alert.clone()).unwrap();
+
+        let pending = db.pending_stock_alerts().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0, id);
+        assert_eq!(pending[0].1.notify_email.as_deref(), Some("eng@example.com"));
+        assert_eq!(pending[0].1.threshold_quantity, 100);
+
+        assert!(db.remove_stock_alert(&id).unwrap());
+        assert!(db.pending_stock_alerts().unwrap().is_empty());
+        // Alerts need a known component
+        assert!(db.add_stock_alert(StockAlert::new("missing".to_string(), 1)).is_err());
+    }
+
+    #[test]
+    fn test_alerts_fire_once_at_threshold() {
+        let db = database_with_component("c1");
+        let low = db.add_stock_alert(StockAlert::new("c1".to_string(), 10)).unwrap();
+        let high = db.add_stock_alert(StockAlert::new("c1".to_string(), 500)).unwrap();
+
+        assert!(db.fire_stock_alerts("c1", 9).unwrap().is_empty());
+        assert_eq!(
+            db.fire_stock_alerts("c1", 10).unwrap(),
+            vec![FiredAlert { alert_id: low, component_id: "c1".to_string(), current_stock: 10 }]
+        );
+        // Already fired alerts stay quiet
+        assert!(db.fire_stock_alerts("c1", 20).unwrap().is_empty());
+
+        let pending = db.pending_stock_alerts().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0, high);
+    }
+
+    #[test]
+    fn test_deleting_component_removes_alerts() {
+        let db = database_with_component("c1");
+        db.add_stock_alert(StockAlert::new("c1".to_string(), 1)).unwrap();
+        db.delete_component("c1").unwrap();
+        assert!(db.pending_stock_alerts().unwrap().is_empty());
+    }
+}
@@ -1,7 +1,11 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use crate::{ComponentRecord, ComponentFilter, Database};
-use opencircuit_core::models::{Component, ComponentCategory, ComponentSearchFilter, ComponentSearchResult, SpecValue};
+use crate::supplier::stored_availability;
+use crate::{ComponentRecord, ComponentFilter, Database, ImportReport, JsonComponentSchema};
+use opencircuit_core::apis::ApiManager;
+use opencircuit_core::models::{
+    AvailabilityInfo, Component, ComponentCategory, ComponentSearchFilter, ComponentSearchResult, SpecValue,
+};
 use serde_json;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -18,6 +22,12 @@ impl ComponentDatabase {
         Ok(Self { db })
     }
 
+    /// Open a component database stored at `path`
+    pub fn new_with_path(path: &std::path::Path) -> Result<Self> {
+        let db = Database::new_with_path(path)?;
+        Ok(Self { db })
+    }
+
     /// Create a new in-memory component database for testing
     pub fn new_in_memory() -> Result<Self> {
         let db = Database::new_in_memory()?;
@@ -35,6 +45,7 @@ impl ComponentDatabase {
         } else {
             HashMap::new()
         };
+        let availability = stored_availability(&specifications);
 
         // Parse timestamps
         let created_at = chrono::DateTime::parse_from_rfc3339(&record.created_at)
@@ -56,7 +67,7 @@ impl ComponentDatabase {
             symbol: record.symbol,
             datasheet_url: record.datasheet_url,
             price_info: None, // TODO: Implement price info parsing
-            availability: None, // TODO: Implement availability parsing
+            availability,
             created_at,
             updated_at,
         }
@@ -195,20 +206,39 @@ impl ComponentDatabase {
         self.db.get_total_component_count()
     }
 
-    /// Bulk import components
+    /// Bulk import components in one transaction
+    ///
+    /// If any component fails to insert, none are imported.
     pub fn bulk_import_components(&self, components: Vec<Component>) -> Result<usize> {
-        let mut imported_count = 0;
+        let records: Vec<ComponentRecord> =
+            components.iter().map(|component| self.component_to_record(component)).collect();
 
-        for component in components {
-            match self.create_component(&component) {
-                Ok(_) => imported_count += 1,
-                Err(e) => {
-                    eprintln!("Failed to import component {}: {}", component.part_number, e);
-                }
+        self.db.transaction(|tx| {
+            for record in &records {
+                Database::insert_component(tx, record)
+                    .with_context(|| format!("Failed to import component {}", record.part_number))?;
             }
-        }
+            Ok(records.len())
+        })
+    }
+
+    /// Refresh supplier stock for many parts and store it in one transaction
+    ///
+    /// Returns the availability found, keyed by part number.
+    pub async fn refresh_availability_batch(
+        &self,
+        api_manager: &ApiManager,
+        part_numbers: &[&str],
+    ) -> Result<HashMap<String, AvailabilityInfo>> {
+        let availability = api_manager.refresh_availability_batch(part_numbers).await?;
+        let updated = self.db.update_availability(&availability)?;
+        tracing::debug!("Stored availability for {} of {} parts in {} components", availability.len(), part_numbers.len(), updated);
+        Ok(availability)
+    }
 
-        Ok(imported_count)
+    /// Import a JSON component library, updating components already present
+    pub fn import_from_json_array(&self, path: &std::path::Path, schema: JsonComponentSchema) -> Result<ImportReport> {
+        self.db.import_from_json_array(path, &schema)
     }
 
     /// Find similar components based on specifications
@@ -450,4 +480,31 @@ mod tests {
         let score = db.calculate_similarity_score(&component1, &component2);
         assert!(score > 50.0); // Should be similar due to same specs and manufacturer
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_failed_bulk_import_rolls_back() {
+        let db = ComponentDatabase::new_in_memory().unwrap();
+        let existing = create_test_component();
+        db.create_component(&existing).unwrap();
+
+        let first = Component::new(
+            "C100N".to_string(),
+            "Test Corp".to_string(),
+            ComponentCategory::Capacitors,
+            "100nF capacitor".to_string(),
+        );
+        let second = Component::new(
+            "C10U".to_string(),
+            "Test Corp".to_string(),
+            ComponentCategory::Capacitors,
+            "10uF capacitor".to_string(),
+        );
+        // Same ID as the first, so the third insert fails
+        let duplicate = first.clone();
+
+        let error = db.bulk_import_components(vec![first, second, duplicate]).unwrap_err();
+        assert!(error.to_string().contains("C100N"));
+        assert_eq!(db.get_total_component_count().unwrap(), 1);
+        assert!(db.get_component(&existing.id).unwrap().is_some());
+    }
+}
@@ -0,0 +1,505 @@
+//! Component imports from supplier CSV exports and JSON libraries
+//!
+//! Each supplier names its columns differently, so a [`CsvColumnMapping`]
+//! translates headers into record fields. Headers the mapping does not know
+//! are reported rather than guessed at; columns that are known but useless for
+//! a library (quantities, customer references) are mapped to
+//! [`CsvField::Ignore`] so they do not show up as unmapped.
+//!
+//! JSON libraries are arrays of component objects whose fields a
+//! [`JsonComponentSchema`] locates by JSON pointer. JSON imports validate each
+//! record and update components already in the library instead of adding
+//! duplicates, so importing the same file twice changes nothing.
+
+use anyhow::Result;
+use opencircuit_core::SpecValue;
+use opencircuit_utils::validation::validate_component;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::supplier::new_record;
+use crate::{ComponentRecord, Database};
+
+/// Record field a CSV column is read into
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CsvField {
+    PartNumber,
+    Manufacturer,
+    Category,
+    Description,
+    DatasheetUrl,
+    Footprint,
+    /// Stored in the specifications under the given name
+    Specification(String),
+    /// Known column that is not imported
+    Ignore,
+}
+
+/// Header-to-field mapping, matched case-insensitively
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CsvColumnMapping {
+    columns: HashMap<String, CsvField>,
+}
+
+impl CsvColumnMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_column(mut self, header: &str, field: CsvField) -> Self {
+        self.columns.insert(normalize_header(header), field);
+        self
+    }
+
+    /// Field for a header, if the mapping knows it
+    pub fn field(&self, header: &str) -> Option<&CsvField> {
+        self.columns.get(&normalize_header(header))
+    }
+
+    /// Octopart BOM and part list exports
+    pub fn octopart() -> Self {
+        Self::new()
+            .with_column("MPN", CsvField::PartNumber)
+            .with_column("Brand", CsvField::Manufacturer)
+            .with_column("Manufacturer", CsvField::Manufacturer)
+            .with_column("Description", CsvField::Description)
+            .with_column("Category", CsvField::Category)
+            .with_column("Datasheet", CsvField::DatasheetUrl)
+            .with_column("Package", CsvField::Footprint)
+            .with_column("LCSC Part #", CsvField::Specification("LCSC Part Number".to_string()))
+            .with_column("Lifecycle Status", CsvField::Specification("Lifecycle Status".to_string()))
+            .with_column("Quantity", CsvField::Ignore)
+            .with_column("Notes", CsvField::Ignore)
+    }
+
+    /// DigiKey cart and product list exports
+    pub fn digikey() -> Self {
+        Self::new()
+            .with_column("Manufacturer Part Number", CsvField::PartNumber)
+            .with_column("Mfr Part #", CsvField::PartNumber)
+            .with_column("Manufacturer", CsvField::Manufacturer)
+            .with_column("Mfr", CsvField::Manufacturer)
+            .with_column("Description", CsvField::Description)
+            .with_column("Detailed Description", CsvField::Specification("Detailed Description".to_string()))
+            .with_column("Category", CsvField::Category)
+            .with_column("Datasheet", CsvField::DatasheetUrl)
+            .with_column("Datasheets", CsvField::DatasheetUrl)
+            .with_column("Package / Case", CsvField::Footprint)
+            .with_column("Digi-Key Part Number", CsvField::Specification("DigiKey Part Number".to_string()))
+            .with_column("DK Part #", CsvField::Specification("DigiKey Part Number".to_string()))
+            .with_column("Unit Price", CsvField::Specification("Unit Price".to_string()))
+            .with_column("Quantity", CsvField::Ignore)
+            .with_column("Extended Price", CsvField::Ignore)
+            .with_column("Customer Reference", CsvField::Ignore)
+            .with_column("Index", CsvField::Ignore)
+    }
+
+    /// Mouser cart and order history exports
+    pub fn mouser() -> Self {
+        Self::new()
+            .with_column("Mfr. #", CsvField::PartNumber)
+            .with_column("Mfr. Part #", CsvField::PartNumber)
+            .with_column("Manufacturer", CsvField::Manufacturer)
+            .with_column("Description", CsvField::Description)
+            .with_column("Category", CsvField::Category)
+            .with_column("Datasheet", CsvField::DatasheetUrl)
+            .with_column("Mouser #", CsvField::Specification("Mouser Part Number".to_string()))
+            .with_column("Mouser Part #", CsvField::Specification("Mouser Part Number".to_string()))
+            .with_column("Price (USD)", CsvField::Specification("Unit Price".to_string()))
+            .with_column("RoHS", CsvField::Specification("RoHS".to_string()))
+            .with_column("Order Qty.", CsvField::Ignore)
+            .with_column("Ext.: (USD)", CsvField::Ignore)
+            .with_column("Customer #", CsvField::Ignore)
+            .with_column("Availability", CsvField::Ignore)
+    }
+}
+
+/// Where a list of name/value specification objects sits in a JSON component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonParameterList {
+    /// Pointer to the array
+    pub array: String,
+    /// Pointer to the name within each entry
+    pub name: String,
+    /// Pointer to the value within each entry
+    pub value: String,
+}
+
+/// JSON pointers to the record fields of each component in a JSON array
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonComponentSchema {
+    pub part_number: String,
+    pub manufacturer: String,
+    pub category: Option<String>,
+    pub description: Option<String>,
+    pub datasheet_url: Option<String>,
+    pub footprint: Option<String>,
+    /// Specification name and the pointer to its value
+    pub specifications: Vec<(String, String)>,
+    pub parameters: Option<JsonParameterList>,
+    /// Source category names mapped to library categories
+    pub category_aliases: HashMap<String, String>,
+}
+
+impl JsonComponentSchema {
+    pub fn new(part_number: &str, manufacturer: &str) -> Self {
+        Self {
+            part_number: part_number.to_string(),
+            manufacturer: manufacturer.to_string(),
+            category: None,
+            description: None,
+            datasheet_url: None,
+            footprint: None,
+            specifications: Vec::new(),
+            parameters: None,
+            category_aliases: HashMap::new(),
+        }
+    }
+
+    pub fn with_category(mut self, pointer: &str) -> Self {
+        self.category = Some(pointer.to_string());
+        self
+    }
+
+    pub fn with_description(mut self, pointer: &str) -> Self {
+        self.description = Some(pointer.to_string());
+        self
+    }
+
+    pub fn with_datasheet_url(mut self, pointer: &str) -> Self {
+        self.datasheet_url = Some(pointer.to_string());
+        self
+    }
+
+    pub fn with_footprint(mut self, pointer: &str) -> Self {
+        self.footprint = Some(pointer.to_string());
+        self
+    }
+
+    pub fn with_specification(mut self, name: &str, pointer: &str) -> Self {
+        self.specifications.push((name.to_string(), pointer.to_string()));
+        self
+    }
+
+    pub fn with_parameters(mut self, array: &str, name: &str, value: &str) -> Self {
+        self.parameters =
+            Some(JsonParameterList { array: array.to_string(), name: name.to_string(), value: value.to_string() });
+        self
+    }
+
+    pub fn with_category_alias(mut self, source: &str, category: &str) -> Self {
+        self.category_aliases.insert(source.to_string(), category.to_string());
+        self
+    }
+
+    /// LCSC product exports
+    pub fn lcsc_default() -> Self {
+        Self::new("/productModel", "/brandNameEn")
+            .with_category("/parentCatalogName")
+            .with_description("/productIntroEn")
+            .with_datasheet_url("/pdfUrl")
+            .with_footprint("/encapStandard")
+            .with_specification("LCSC Part Number", "/productCode")
+            .with_parameters("/paramVOList", "/paramNameEn", "/paramValueEn")
+            .with_category_alias("Inductors & Chokes & Transformers", "Inductors")
+            .with_category_alias("Crystals, Oscillators, Resonators", "Crystals")
+            .with_category_alias("Power Management ICs", "Power")
+            .with_category_alias("Embedded Processors & Controllers", "Integrated Circuits")
+            .with_category_alias("Amplifiers", "Integrated Circuits")
+            .with_category_alias("Logic", "Integrated Circuits")
+    }
+
+    /// Build the record for one array element
+    fn record(&self, item: &Value) -> std::result::Result<ComponentRecord, String> {
+        let part_number = text_at(item, &self.part_number).ok_or("missing part number")?;
+        let manufacturer = text_at(item, &self.manufacturer).ok_or("missing manufacturer")?;
+        let field = |pointer: &Option<String>| pointer.as_deref().and_then(|pointer| text_at(item, pointer));
+
+        let category = field(&self.category).unwrap_or_default();
+        let category = self.category_aliases.get(&category).cloned().unwrap_or(category);
+
+        let mut specifications = HashMap::new();
+        if let Some(parameters) = &self.parameters {
+            for entry in item.pointer(&parameters.array).and_then(Value::as_array).into_iter().flatten() {
+                if let (Some(name), Some(value)) = (text_at(entry, &parameters.name), text_at(entry, &parameters.value)) {
+                    specifications.insert(name, SpecValue::String(value));
+                }
+            }
+        }
+        for (name, pointer) in &self.specifications {
+            if let Some(value) = text_at(item, pointer) {
+                specifications.insert(name.clone(), SpecValue::String(value));
+            }
+        }
+
+        let mut record = new_record(
+            part_number,
+            manufacturer,
+            category,
+            field(&self.description),
+            field(&self.datasheet_url),
+            specifications,
+        );
+        record.footprint = field(&self.footprint);
+        Ok(record)
+    }
+}
+
+/// Row that was not imported
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedRow {
+    /// 1-based line in the CSV file, or position in the JSON array
+    pub line: u64,
+    pub reason: String,
+}
+
+/// Outcome of a CSV import
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub imported: usize,
+    /// Existing components overwritten by a JSON import
+    #[serde(default)]
+    pub updated: usize,
+    pub skipped: Vec<SkippedRow>,
+    /// Headers the mapping did not recognise, in file order
+    pub unmapped_columns: Vec<String>,
+}
+
+impl Database {
+    /// Import components from a CSV file using `mapping`
+    ///
+    /// Rows without a part number or manufacturer, malformed rows and rows the
+    /// database rejects, such as those with an unknown category, are skipped
+    /// and listed in the report. Fails only when the file or its header row
+    /// cannot be read.
+    pub fn import_from_csv(&self, path: &Path, mapping: &CsvColumnMapping) -> Result<ImportReport> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_path(path)?;
+        let headers: Vec<String> = reader
+            .headers()?
+            .iter()
+            .map(|header| header.trim_start_matches('\u{feff}').to_string())
+            .collect();
+
+        let mut report = ImportReport::default();
+        let fields: Vec<Option<&CsvField>> = headers
+            .iter()
+            .map(|header| {
+                let field = mapping.field(header);
+                if field.is_none() && !header.is_empty() {
+                    report.unmapped_columns.push(header.clone());
+                }
+                field
+            })
+            .collect();
+
+        for row in reader.records() {
+            let row = match row {
+                Ok(row) => row,
+                Err(error) => {
+                    let line = error.position().map(|position| position.line()).unwrap_or_default();
+                    report.skipped.push(SkippedRow { line, reason: error.to_string() });
+                    continue;
+                }
+            };
+            let line = row.position().map(|position| position.line()).unwrap_or_default();
+
+            let mut part_number = None;
+            let mut manufacturer = None;
+            let mut category = None;
+            let mut description = None;
+            let mut datasheet_url = None;
+            let mut footprint = None;
+            let mut specifications = HashMap::new();
+            for (value, field) in row.iter().zip(&fields) {
+                let Some(field) = field else { continue };
+                if value.is_empty() {
+                    continue;
+                }
+                let slot = match field {
+                    CsvField::PartNumber => &mut part_number,
+                    CsvField::Manufacturer => &mut manufacturer,
+                    CsvField::Category => &mut category,
+                    CsvField::Description => &mut description,
+                    CsvField::DatasheetUrl => &mut datasheet_url,
+                    CsvField::Footprint => &mut footprint,
+                    CsvField::Specification(name) => {
+                        specifications.entry(name.clone()).or_insert_with(|| SpecValue::String(value.to_string()));
+                        continue;
+                    }
+                    CsvField::Ignore => continue,
+                };
+                slot.get_or_insert_with(|| value.to_string());
+            }
+
+            let (Some(part_number), Some(manufacturer)) = (part_number, manufacturer) else {
+                report.skipped.push(SkippedRow { line, reason: "missing part number or manufacturer".to_string() });
+                continue;
+            };
+            let mut record = new_record(
+                part_number,
+                manufacturer,
+                category.unwrap_or_default(),
+                description,
+                datasheet_url,
+                specifications,
+            );
+            record.footprint = footprint;
+
+            match self.create_component(&record) {
+                Ok(()) => report.imported += 1,
+                Err(error) => report.skipped.push(SkippedRow { line, reason: error.to_string() }),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Import an Octopart export
+    pub fn import_from_octopart_csv(&self, path: &Path) -> Result<ImportReport> {
+        self.import_from_csv(path, &CsvColumnMapping::octopart())
+    }
+
+    /// Import a DigiKey export
+    pub fn import_from_digikey_csv(&self, path: &Path) -> Result<ImportReport> {
+        self.import_from_csv(path, &CsvColumnMapping::digikey())
+    }
+
+    /// Import a Mouser export
+    pub fn import_from_mouser_csv(&self, path: &Path) -> Result<ImportReport> {
+        self.import_from_csv(path, &CsvColumnMapping::mouser())
+    }
+
+    /// Import a JSON array of components located by `schema`
+    ///
+    /// Components failing validation are skipped and listed in the report.
+    /// A component whose part number and manufacturer are already in the
+    /// library replaces the stored one. Fails only when the file cannot be
+    /// read or is not a JSON array.
+    pub fn import_from_json_array(&self, path: &Path, schema: &JsonComponentSchema) -> Result<ImportReport> {
+        let json: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let items = json
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("{} does not contain a JSON array", path.display()))?;
+
+        let mut report = ImportReport::default();
+        for (index, item) in items.iter().enumerate() {
+            let line = index as u64 + 1;
+            let mut record = match schema.record(item) {
+                Ok(record) => record,
+                Err(reason) => {
+                    report.skipped.push(SkippedRow { line, reason });
+                    continue;
+                }
+            };
+            if let Err(issues) = validate_component(&record) {
+                let reason = issues.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ");
+                report.skipped.push(SkippedRow { line, reason });
+                continue;
+            }
+
+            let result = match self.find_component(&record.part_number, &record.manufacturer)? {
+                Some(existing) => {
+                    record.id = existing.id;
+                    record.symbol = record.symbol.or(existing.symbol);
+                    record.created_at = existing.created_at;
+                    self.update_component(&record).map(|_| report.updated += 1)
+                }
+                None => self.create_component(&record).map(|()| report.imported += 1),
+            };
+            if let Err(error) = result {
+                report.skipped.push(SkippedRow { line, reason: error.to_string() });
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+fn normalize_header(header: &str) -> String {
+    header.trim().to_lowercase()
+}
+
+/// Non-empty string, number or boolean at a JSON pointer, as text
+fn text_at(json: &Value, pointer: &str) -> Option<String> {
+    let text = match json.pointer(pointer)? {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+    }
+
+    #[test]
+    fn test_import_octopart_fixture() {
+        let db = Database::new_in_memory().unwrap();
+        let report = db.import_from_octopart_csv(&fixture("octopart_export.csv")).unwrap();
+
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].line, 4);
+        assert!(report.unmapped_columns.is_empty());
+
+        let resistors = db.search_components("RC0603FR-0710KL", None).unwrap();
+        assert_eq!(resistors.len(), 1);
+        assert_eq!(resistors[0].manufacturer, "YAGEO");
+        assert_eq!(resistors[0].footprint.as_deref(), Some("0603"));
+        assert!(resistors[0].specifications.as_deref().unwrap().contains("C98220"));
+    }
+
+    #[test]
+    fn test_import_reports_unmapped_columns() {
+        let path = std::env::temp_dir().join(format!("mouser_{}.csv", Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "\u{feff}Mouser #,Mfr. #,Manufacturer,Description,Category,Order Qty.,Lead Time\n\
+             595-NE555DR,NE555DR,Texas Instruments,Timer IC,Integrated Circuits,10,6 weeks\n\
+             81-GRM188R71H104KA3D,GRM188R71H104KA93D,Murata,MLCC,Capacitors - MLCC,100,\n",
+        )
+        .unwrap();
+
+        let db = Database::new_in_memory().unwrap();
+        let report = db.import_from_mouser_csv(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.unmapped_columns, vec!["Lead Time".to_string()]);
+        let timers = db.search_components("NE555DR", None).unwrap();
+        assert!(timers[0].specifications.as_deref().unwrap().contains("595-NE555DR"));
+    }
+
+    #[test]
+    fn test_lcsc_json_import_is_idempotent() {
+        let db = Database::new_in_memory().unwrap();
+        let schema = JsonComponentSchema::lcsc_default();
+
+        let report = db.import_from_json_array(&fixture("lcsc_export.json"), &schema).unwrap();
+        assert_eq!((report.imported, report.updated), (2, 0));
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].line, 3);
+        assert!(report.skipped[0].reason.contains("Unknown footprint"));
+
+        let again = db.import_from_json_array(&fixture("lcsc_export.json"), &schema).unwrap();
+        assert_eq!((again.imported, again.updated), (0, 2));
+        assert_eq!(db.get_total_component_count().unwrap(), 2);
+
+        let inductor = db.find_component("SDFL2012T100KTF", "Sunlord").unwrap().unwrap();
+        assert_eq!(inductor.category, "Inductors");
+        let specifications = inductor.specifications.unwrap();
+        assert!(specifications.contains("C1044"));
+        assert!(specifications.contains("10uH"));
+    }
+}
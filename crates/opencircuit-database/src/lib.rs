@@ -1,17 +1,29 @@
 use anyhow::Result;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+pub mod alerts;
 pub mod components;
+pub mod import;
 pub mod search;
+pub mod supplier;
 pub mod schema;
+pub mod usage;
 
+pub use alerts::{spawn_stock_alert_monitor, FiredAlert, StockAlert};
 pub use components::ComponentDatabase;
-pub use search::ComponentSearchEngine;
+pub use import::{CsvColumnMapping, CsvField, ImportReport, JsonComponentSchema, SkippedRow};
+pub use search::{ComponentSearchEngine, SearchCriterion, SearchExplanation, SearchRankingConfig};
+pub use supplier::{AVAILABILITY_SPEC, PRICE_BREAKS_SPEC};
+
+/// Environment variable that overrides the default database location
+pub const DATABASE_PATH_ENV: &str = "OPENCIRCUIT_DB_PATH";
 
 /// Component record structure for database storage
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ComponentRecord {
     pub id: String,
     pub part_number: String,
@@ -26,6 +38,32 @@ pub struct ComponentRecord {
     pub updated_at: String,
 }
 
+impl opencircuit_utils::validation::ComponentData for ComponentRecord {
+    fn part_number(&self) -> &str {
+        &self.part_number
+    }
+
+    fn manufacturer(&self) -> &str {
+        &self.manufacturer
+    }
+
+    fn category(&self) -> &str {
+        &self.category
+    }
+
+    fn specifications(&self) -> Option<&str> {
+        self.specifications.as_deref()
+    }
+
+    fn datasheet_url(&self) -> Option<&str> {
+        self.datasheet_url.as_deref()
+    }
+
+    fn footprint(&self) -> Option<&str> {
+        self.footprint.as_deref()
+    }
+}
+
 /// Filter criteria for component searches
 #[derive(Debug, Default)]
 pub struct ComponentFilter {
@@ -49,6 +87,37 @@ impl Database {
         })
     }
 
+    /// Open or create a database file at `path` and initialize schema
+    pub fn new_with_path(path: &Path) -> Result<Self> {
+        let conn = schema::open_database(path)?;
+        Ok(Database {
+            connection: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Open the database named by `OPENCIRCUIT_DB_PATH`, or the default one
+    pub fn from_env() -> Result<Self> {
+        Self::open_resolved(resolve_database_path(std::env::var_os(DATABASE_PATH_ENV), None))
+    }
+
+    /// Open the database named by `OPENCIRCUIT_DB_PATH`, then the config's
+    /// `database_path`, then the default one
+    ///
+    /// The environment variable wins so CI and tests can redirect the database.
+    pub fn from_config(config: &opencircuit_core::AppConfig) -> Result<Self> {
+        Self::open_resolved(resolve_database_path(
+            std::env::var_os(DATABASE_PATH_ENV),
+            config.database_path.as_deref(),
+        ))
+    }
+
+    fn open_resolved(path: Option<PathBuf>) -> Result<Self> {
+        match path {
+            Some(path) => Self::new_with_path(&path),
+            None => Self::new(),
+        }
+    }
+
     /// Create a new in-memory database for testing
     pub fn new_in_memory() -> Result<Self> {
         let conn = rusqlite::Connection::open_in_memory()?;
@@ -61,6 +130,11 @@ impl Database {
     /// Create a new component record
     pub fn create_component(&self, component: &ComponentRecord) -> Result<()> {
         let conn = self.connection.lock().unwrap();
+        Self::insert_component(&conn, component)
+    }
+
+    /// Insert a component record on `conn`, e.g. inside [`Database::transaction`]
+    pub fn insert_component(conn: &Connection, component: &ComponentRecord) -> Result<()> {
         conn.execute(
             r#"
             INSERT INTO components (
@@ -83,6 +157,29 @@ impl Database {
         Ok(())
     }
 
+    /// Run `f` in a single transaction
+    ///
+    /// Commits when `f` succeeds. When it fails the transaction is rolled
+    /// back, so none of its changes are kept. The connection stays locked for
+    /// the whole call.
+    pub fn transaction<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&rusqlite::Transaction) -> Result<R>,
+    {
+        let mut conn = self.connection.lock().unwrap();
+        let tx = conn.transaction()?;
+        match f(&tx) {
+            Ok(result) => {
+                tx.commit()?;
+                Ok(result)
+            }
+            Err(error) => {
+                tx.rollback()?;
+                Err(error)
+            }
+        }
+    }
+
     /// Get a component by ID
     pub fn get_component(&self, id: &str) -> Result<Option<ComponentRecord>> {
         let conn = self.connection.lock().unwrap();
@@ -118,6 +215,23 @@ impl Database {
         }
     }
 
+    /// Find a component by part number and manufacturer
+    pub fn find_component(&self, part_number: &str, manufacturer: &str) -> Result<Option<ComponentRecord>> {
+        let id: Option<String> = {
+            let conn = self.connection.lock().unwrap();
+            conn.query_row(
+                "SELECT id FROM components WHERE part_number = ? AND manufacturer = ? LIMIT 1",
+                params![part_number, manufacturer],
+                |row| row.get(0),
+            )
+            .optional()?
+        };
+        match id {
+            Some(id) => self.get_component(&id),
+            None => Ok(None),
+        }
+    }
+
     /// Update an existing component
     pub fn update_component(&self, component: &ComponentRecord) -> Result<bool> {
         let conn = self.connection.lock().unwrap();
@@ -144,6 +258,16 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
+    /// Set a component's category, returning whether the component exists
+    pub fn set_component_category(&self, id: &str, category: &str) -> Result<bool> {
+        let conn = self.connection.lock().unwrap();
+        let rows_affected = conn.execute(
+            "UPDATE components SET category = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            params![category, id],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
     /// Delete a component by ID
     pub fn delete_component(&self, id: &str) -> Result<bool> {
         let conn = self.connection.lock().unwrap();
@@ -315,6 +439,14 @@ impl Database {
     }
 }
 
+/// Database file from the environment variable, then the config, or `None` for the default
+fn resolve_database_path(env_path: Option<OsString>, config_path: Option<&str>) -> Option<PathBuf> {
+    env_path
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| config_path.map(PathBuf::from))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,6 +468,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sources_category_component() {
+        let db = Database::new_in_memory().unwrap();
+        let mut component = create_test_component();
+        component.part_number = "REF5025".to_string();
+        component.category = "Sources".to_string();
+        assert!(opencircuit_utils::validation::validate_component(&component).is_ok());
+
+        db.create_component(&component).unwrap();
+        let stored = db.get_component(&component.id).unwrap().unwrap();
+        assert_eq!(stored.category, "Sources");
+        assert_eq!(stored.part_number, "REF5025");
+    }
+
+    #[test]
+    fn test_component_record_validation() {
+        use opencircuit_utils::validation::{validate_component, ValidationIssue};
+
+        let mut component = create_test_component();
+        assert!(validate_component(&component).is_ok());
+
+        component.category = "Gadgets".to_string();
+        let issues = validate_component(&component).unwrap_err();
+        assert_eq!(issues, vec![ValidationIssue::UnknownCategory("Gadgets".to_string())]);
+    }
+
     #[test]
     fn test_database_creation() {
         let conn = rusqlite::Connection::open_in_memory().unwrap();
@@ -349,6 +507,51 @@ mod tests {
         assert!(db.get_categories().is_ok());
     }
 
+    #[test]
+    fn test_new_with_path_persists() {
+        let dir = std::env::temp_dir().join(format!("opencircuit-db-{}", Uuid::new_v4()));
+        let path = dir.join("project").join("components.db");
+        let component = create_test_component();
+
+        Database::new_with_path(&path).unwrap().create_component(&component).unwrap();
+        let reopened = Database::new_with_path(&path).unwrap();
+        assert!(reopened.get_component(&component.id).unwrap().is_some());
+
+        drop(reopened);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_env_path_takes_precedence_over_config() {
+        let env = Some(OsString::from("/ci/opencircuit.db"));
+        assert_eq!(
+            resolve_database_path(env, Some("/home/me/opencircuit.db")),
+            Some(PathBuf::from("/ci/opencircuit.db"))
+        );
+        assert_eq!(
+            resolve_database_path(Some(OsString::new()), Some("/home/me/opencircuit.db")),
+            Some(PathBuf::from("/home/me/opencircuit.db"))
+        );
+        assert_eq!(resolve_database_path(None, None), None);
+    }
+
+    #[test]
+    fn test_from_config_uses_database_path() {
+        let dir = std::env::temp_dir().join(format!("opencircuit-db-{}", Uuid::new_v4()));
+        let path = dir.join("config.db");
+        let config = opencircuit_core::AppConfig {
+            database_path: Some(path.display().to_string()),
+            ..Default::default()
+        };
+
+        let db = Database::from_config(&config).unwrap();
+        assert!(db.get_categories().is_ok());
+        assert!(path.exists());
+
+        drop(db);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_component_crud() {
         let conn = rusqlite::Connection::open_in_memory().unwrap();
@@ -378,6 +581,10 @@ mod tests {
         
         let retrieved_updated = db.get_component(&original_id).unwrap().unwrap();
         assert_eq!(retrieved_updated.description, Some("Updated description".to_string()));
+
+        assert!(db.set_component_category(&original_id, "Integrated Circuits").unwrap());
+        assert_eq!(db.get_component(&original_id).unwrap().unwrap().category, "Integrated Circuits");
+        assert!(!db.set_component_category("missing", "Resistors").unwrap());
         
         // Delete component
         let deleted = db.delete_component(&original_id).unwrap();
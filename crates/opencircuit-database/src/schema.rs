@@ -1,131 +1,264 @@
 use anyhow::Result;
 use rusqlite::{Connection, params};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Initialize database and run migrations
 pub fn initialize_database() -> Result<Connection> {
     let db_path = get_database_path()?;
-    let conn = Connection::open(&db_path)?;
+    open_database(&db_path)
+}
+
+/// Open or create a database file at `path` and run migrations
+pub fn open_database(path: &Path) -> Result<Connection> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let conn = Connection::open(path)?;
     run_migrations(&conn)?;
     Ok(conn)
 }
 
-/// Run all database migrations
-pub fn run_migrations(conn: &Connection) -> Result<()> {
-    // Enable foreign keys
-    conn.execute("PRAGMA foreign_keys = ON", [])?;
-    
-    // Create migrations table if it doesn't exist
-    conn.execute(
-        r#"
-        CREATE TABLE IF NOT EXISTS migrations (
-            id INTEGER PRIMARY KEY,
-            name TEXT NOT NULL UNIQUE,
-            applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-        [],
-    )?;
-    
-    // Check if migration 001 has been applied
-    let migration_exists: bool = conn
-        .query_row(
-            "SELECT EXISTS(SELECT 1 FROM migrations WHERE name = ?)",
-            params!["001_initial"],
-            |row| row.get(0),
-        )?;
-    
-    if !migration_exists {
-        apply_migration_001(conn)?;
-        conn.execute(
-            "INSERT INTO migrations (name) VALUES (?)",
-            params!["001_initial"],
-        )?;
+/// Schema change with optional rollback SQL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    pub version: u32,
+    pub up: &'static str,
+    pub down: Option<&'static str>,
+}
+
+/// Schema history, in version order
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        up: MIGRATION_001_UP,
+        down: Some(MIGRATION_001_DOWN),
+    },
+    Migration {
+        version: 2,
+        up: MIGRATION_002_UP,
+        down: Some("DROP TABLE stock_alerts;"),
+    },
+    Migration {
+        version: 3,
+        up: MIGRATION_003_UP,
+        down: Some("DROP TABLE component_usage;"),
+    },
+    Migration {
+        version: 4,
+        up: MIGRATION_004_UP,
+        down: Some("DELETE FROM component_categories WHERE name = 'Sources';"),
+    },
+];
+
+const MIGRATION_001_UP: &str = r#"
+    CREATE TABLE component_categories (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        description TEXT,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    );
+
+    CREATE TABLE components (
+        id TEXT PRIMARY KEY,
+        part_number TEXT NOT NULL,
+        manufacturer TEXT NOT NULL,
+        category TEXT NOT NULL,
+        description TEXT,
+        datasheet_url TEXT,
+        specifications TEXT,
+        footprint TEXT,
+        symbol TEXT,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (category) REFERENCES component_categories(name)
+    );
+
+    -- AI embeddings
+    CREATE TABLE component_vectors (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        component_id TEXT NOT NULL,
+        vector_data BLOB,
+        vector_type TEXT NOT NULL DEFAULT 'description',
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (component_id) REFERENCES components(id) ON DELETE CASCADE
+    );
+
+    CREATE INDEX idx_components_part_number ON components(part_number);
+    CREATE INDEX idx_components_manufacturer ON components(manufacturer);
+    CREATE INDEX idx_components_category ON components(category);
+    CREATE INDEX idx_component_vectors_component_id ON component_vectors(component_id);
+
+    INSERT INTO component_categories (name, description) VALUES
+        ('Resistors', 'Fixed and variable resistors'),
+        ('Capacitors', 'Ceramic, electrolytic, and film capacitors'),
+        ('Inductors', 'Coils and chokes'),
+        ('Diodes', 'Signal, power, and Zener diodes'),
+        ('Transistors', 'BJT, FET, and MOSFET transistors'),
+        ('Integrated Circuits', 'Analog and digital ICs'),
+        ('Connectors', 'Headers, sockets, and terminal blocks'),
+        ('Switches', 'Tactile, toggle, and rotary switches'),
+        ('Crystals', 'Oscillators and resonators'),
+        ('Sensors', 'Temperature, pressure, and motion sensors'),
+        ('Power', 'Voltage regulators and power modules'),
+        ('Mechanical', 'Enclosures, heat sinks, and hardware');
+"#;
+
+const MIGRATION_001_DOWN: &str = r#"
+    DROP TABLE component_vectors;
+    DROP TABLE components;
+    DROP TABLE component_categories;
+"#;
+
+const MIGRATION_002_UP: &str = r#"
+    CREATE TABLE stock_alerts (
+        id TEXT PRIMARY KEY,
+        component_id TEXT NOT NULL,
+        threshold_quantity INTEGER NOT NULL,
+        notify_email TEXT,
+        callback_url TEXT,
+        created_at TEXT NOT NULL,
+        fired_at TEXT,
+        FOREIGN KEY (component_id) REFERENCES components(id) ON DELETE CASCADE
+    );
+
+    CREATE INDEX idx_stock_alerts_component_id ON stock_alerts(component_id);
+"#;
+
+const MIGRATION_003_UP: &str = r#"
+    CREATE TABLE component_usage (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        component_id TEXT NOT NULL,
+        project_id TEXT NOT NULL,
+        used_at TEXT NOT NULL,
+        FOREIGN KEY (component_id) REFERENCES components(id) ON DELETE CASCADE
+    );
+
+    CREATE INDEX idx_component_usage_component_id ON component_usage(component_id);
+"#;
+
+const MIGRATION_004_UP: &str = r#"
+    INSERT OR IGNORE INTO component_categories (name, description) VALUES
+        ('Sources', 'Voltage and current sources');
+"#;
+
+/// Applies and rolls back migrations, recording them in `schema_migrations`
+///
+/// Each migration runs in its own transaction, so a failing migration leaves
+/// the schema at the previous version.
+#[derive(Debug, Clone)]
+pub struct MigrationRunner {
+    migrations: Vec<Migration>,
+}
+
+impl MigrationRunner {
+    /// Runner for the OpenCircuit schema history
+    pub fn new() -> Self {
+        Self::with_migrations(MIGRATIONS.to_vec())
+    }
+
+    /// Runner for a custom migration list
+    pub fn with_migrations(mut migrations: Vec<Migration>) -> Self {
+        migrations.sort_by_key(|migration| migration.version);
+        Self { migrations }
+    }
+
+    /// Versions already applied, in ascending order
+    pub fn applied_versions(&self, conn: &Connection) -> Result<Vec<u32>> {
+        ensure_migrations_table(conn)?;
+        let mut stmt = conn.prepare("SELECT version FROM schema_migrations ORDER BY version")?;
+        let versions = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<u32>>>()?;
+        Ok(versions)
+    }
+
+    /// Apply migrations that have not run yet, returning their versions
+    pub fn apply_pending(&self, conn: &Connection) -> Result<Vec<u32>> {
+        let applied = self.applied_versions(conn)?;
+        let mut newly_applied = Vec::new();
+
+        for migration in self.migrations.iter().filter(|m| !applied.contains(&m.version)) {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute_batch(migration.up)
+                .map_err(|e| anyhow::anyhow!("Migration {} failed: {}", migration.version, e))?;
+            tx.execute("INSERT INTO schema_migrations (version) VALUES (?)", params![migration.version])?;
+            tx.commit()?;
+            newly_applied.push(migration.version);
+        }
+
+        Ok(newly_applied)
+    }
+
+    /// Undo the most recently applied migration, returning its version
+    pub fn rollback_last(&self, conn: &Connection) -> Result<u32> {
+        let version = *self
+            .applied_versions(conn)?
+            .last()
+            .ok_or_else(|| anyhow::anyhow!("No migrations to roll back"))?;
+        let down = self
+            .migrations
+            .iter()
+            .find(|migration| migration.version == version)
+            .ok_or_else(|| anyhow::anyhow!("Migration {} is unknown to this version of OpenCircuit", version))?
+            .down
+            .ok_or_else(|| anyhow::anyhow!("Migration {} cannot be rolled back", version))?;
+
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(down)
+            .map_err(|e| anyhow::anyhow!("Rollback of migration {} failed: {}", version, e))?;
+        tx.execute("DELETE FROM schema_migrations WHERE version = ?", params![version])?;
+        tx.commit()?;
+
+        Ok(version)
     }
-    
-    Ok(())
 }
 
-/// Apply the initial migration
-fn apply_migration_001(conn: &Connection) -> Result<()> {
-    // Create component_categories table
-    conn.execute(
-        r#"
-        CREATE TABLE component_categories (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            name TEXT NOT NULL UNIQUE,
-            description TEXT,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-        [],
-    )?;
-    
-    // Create components table
+impl Default for MigrationRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create `schema_migrations`, adopting the history of the legacy `migrations` table
+fn ensure_migrations_table(conn: &Connection) -> Result<()> {
     conn.execute(
         r#"
-        CREATE TABLE components (
-            id TEXT PRIMARY KEY,
-            part_number TEXT NOT NULL,
-            manufacturer TEXT NOT NULL,
-            category TEXT NOT NULL,
-            description TEXT,
-            datasheet_url TEXT,
-            specifications TEXT,
-            footprint TEXT,
-            symbol TEXT,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (category) REFERENCES component_categories(name)
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
         )
         "#,
         [],
     )?;
-    
-    // Create component_vectors table for AI embeddings
-    conn.execute(
-        r#"
-        CREATE TABLE component_vectors (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            component_id TEXT NOT NULL,
-            vector_data BLOB,
-            vector_type TEXT NOT NULL DEFAULT 'description',
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (component_id) REFERENCES components(id) ON DELETE CASCADE
-        )
-        "#,
+
+    let has_legacy_table: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'migrations')",
         [],
+        |row| row.get(0),
     )?;
-    
-    // Create indexes for performance
-    conn.execute("CREATE INDEX idx_components_part_number ON components(part_number)", [])?;
-    conn.execute("CREATE INDEX idx_components_manufacturer ON components(manufacturer)", [])?;
-    conn.execute("CREATE INDEX idx_components_category ON components(category)", [])?;
-    conn.execute("CREATE INDEX idx_component_vectors_component_id ON component_vectors(component_id)", [])?;
-    
-    // Insert default component categories
-    let categories = [
-        ("Resistors", "Fixed and variable resistors"),
-        ("Capacitors", "Ceramic, electrolytic, and film capacitors"),
-        ("Inductors", "Coils and chokes"),
-        ("Diodes", "Signal, power, and Zener diodes"),
-        ("Transistors", "BJT, FET, and MOSFET transistors"),
-        ("Integrated Circuits", "Analog and digital ICs"),
-        ("Connectors", "Headers, sockets, and terminal blocks"),
-        ("Switches", "Tactile, toggle, and rotary switches"),
-        ("Crystals", "Oscillators and resonators"),
-        ("Sensors", "Temperature, pressure, and motion sensors"),
-        ("Power", "Voltage regulators and power modules"),
-        ("Mechanical", "Enclosures, heat sinks, and hardware"),
-    ];
-    
-    for (name, description) in &categories {
-        conn.execute(
-            "INSERT INTO component_categories (name, description) VALUES (?, ?)",
-            params![name, description],
+    if has_legacy_table {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            r#"
+            INSERT OR IGNORE INTO schema_migrations (version, applied_at)
+            SELECT 1, applied_at FROM migrations WHERE name = '001_initial'
+            "#,
+            [],
         )?;
+        tx.execute("DROP TABLE migrations", [])?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+/// Run all pending database migrations
+pub fn run_migrations(conn: &Connection) -> Result<()> {
+    // Enable foreign keys
+    conn.execute("PRAGMA foreign_keys = ON", [])?;
+
+    let applied = MigrationRunner::new().apply_pending(conn)?;
+    if !applied.is_empty() {
+        tracing::info!("Applied database migrations {:?}", applied);
     }
-    
     Ok(())
 }
 
@@ -171,9 +304,76 @@ mod tests {
         
         // Should not fail and should have the same result
         let migration_count: i64 = conn
-            .query_row("SELECT COUNT(*) FROM migrations", [], |row| row.get(0))
+            .query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| row.get(0))
             .unwrap();
         
-        assert_eq!(migration_count, 1);
+        assert_eq!(migration_count, MIGRATIONS.len() as i64);
+    }
+
+    fn table_exists(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+            params![name],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_pending_and_rollback() {
+        let conn = Connection::open_in_memory().unwrap();
+        let runner = MigrationRunner::with_migrations(vec![
+            MIGRATIONS[0],
+            Migration {
+                version: 2,
+                up: "CREATE TABLE projects (id TEXT PRIMARY KEY)",
+                down: Some("DROP TABLE projects"),
+            },
+        ]);
+
+        assert_eq!(runner.apply_pending(&conn).unwrap(), vec![1, 2]);
+        assert!(runner.apply_pending(&conn).unwrap().is_empty());
+        assert!(table_exists(&conn, "projects"));
+
+        assert_eq!(runner.rollback_last(&conn).unwrap(), 2);
+        assert!(!table_exists(&conn, "projects"));
+        assert!(table_exists(&conn, "components"));
+        assert_eq!(runner.applied_versions(&conn).unwrap(), vec![1]);
+
+        // Re-applying only runs the rolled-back migration
+        assert_eq!(runner.apply_pending(&conn).unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn test_failed_migration_is_not_recorded() {
+        let conn = Connection::open_in_memory().unwrap();
+        let runner = MigrationRunner::with_migrations(vec![
+            Migration { version: 1, up: "CREATE TABLE a (id INTEGER)", down: None },
+            Migration { version: 2, up: "CREATE TABLE b (id INTEGER); NOT SQL", down: None },
+        ]);
+
+        assert!(runner.apply_pending(&conn).is_err());
+        assert_eq!(runner.applied_versions(&conn).unwrap(), vec![1]);
+        assert!(!table_exists(&conn, "b"));
+        // Migrations without down SQL stay applied
+        assert!(runner.rollback_last(&conn).is_err());
+        assert!(table_exists(&conn, "a"));
+    }
+
+    #[test]
+    fn test_adopts_legacy_migrations_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_001_UP).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE migrations (id INTEGER PRIMARY KEY, name TEXT NOT NULL UNIQUE, applied_at DATETIME);
+             INSERT INTO migrations (name) VALUES ('001_initial');",
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+        let all_versions: Vec<u32> = MIGRATIONS.iter().map(|migration| migration.version).collect();
+        assert_eq!(MigrationRunner::new().applied_versions(&conn).unwrap(), all_versions);
+        assert!(!table_exists(&conn, "migrations"));
+        assert!(table_exists(&conn, "stock_alerts"));
     }
 }
\ No newline at end of file
@@ -1,18 +1,99 @@
 use anyhow::Result;
 use opencircuit_core::models::{Component, ComponentCategory, ComponentSearchFilter, ComponentSearchResult, SpecValue};
+use opencircuit_utils::units;
 use std::collections::HashMap;
 use crate::components::ComponentDatabase;
 
+/// Relative weight of each kind of search criterion
+const RESISTANCE_WEIGHT: f64 = 0.3;
+const CAPACITANCE_WEIGHT: f64 = 0.3;
+const VOLTAGE_WEIGHT: f64 = 0.2;
+const PACKAGE_WEIGHT: f64 = 0.2;
+const TOLERANCE_WEIGHT: f64 = 0.15;
+const MANUFACTURER_WEIGHT: f64 = 0.15;
+
+/// One criterion parsed from a search query and whether a result meets it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchCriterion {
+    pub criterion: String,
+    pub matched: bool,
+    pub weight: f64,
+}
+
+/// Why a search result matched, criterion by criterion
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchExplanation {
+    pub relevance_score: f64,
+    pub criteria: Vec<SearchCriterion>,
+}
+
+impl SearchExplanation {
+    /// Share of the criteria weight the result meets, from 0 to 1
+    pub fn match_ratio(&self) -> f64 {
+        let total: f64 = self.criteria.iter().map(|criterion| criterion.weight).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        self.criteria.iter().filter(|criterion| criterion.matched).map(|criterion| criterion.weight).sum::<f64>() / total
+    }
+
+    /// Criteria the result does not meet, to help refine the query
+    pub fn unmatched(&self) -> impl Iterator<Item = &SearchCriterion> {
+        self.criteria.iter().filter(|criterion| !criterion.matched)
+    }
+}
+
+/// Availability score of parts not known to be in stock
+const NOT_IN_STOCK_SCORE: f64 = 0.3;
+
+/// How search results are ranked
+///
+/// The score is `text * text_relevance_weight + availability *
+/// availability_weight + price * price_weight`. Text relevance is divided by
+/// the best in the result set; availability is 1.0 for in-stock parts and 0.3
+/// otherwise; price runs from 1.0 for the cheapest unit price in the set to
+/// 0.0 for the dearest or unknown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchRankingConfig {
+    pub availability_weight: f32,
+    pub price_weight: f32,
+    pub text_relevance_weight: f32,
+}
+
+impl SearchRankingConfig {
+    /// Weights for engineers who need parts they can order today
+    pub fn prefer_in_stock() -> Self {
+        Self { availability_weight: 0.4, price_weight: 0.1, text_relevance_weight: 0.5 }
+    }
+
+    fn is_text_only(&self) -> bool {
+        self.availability_weight == 0.0 && self.price_weight == 0.0
+    }
+}
+
+impl Default for SearchRankingConfig {
+    /// Rank by text relevance alone, leaving scores unchanged
+    fn default() -> Self {
+        Self { availability_weight: 0.0, price_weight: 0.0, text_relevance_weight: 1.0 }
+    }
+}
+
 /// Advanced search engine for components
 pub struct ComponentSearchEngine {
     db: ComponentDatabase,
+    ranking: SearchRankingConfig,
 }
 
 impl ComponentSearchEngine {
     /// Create a new search engine instance
     pub fn new() -> Result<Self> {
         let db = ComponentDatabase::new()?;
-        Ok(Self { db })
+        Ok(Self { db, ranking: SearchRankingConfig::default() })
+    }
+
+    /// Set how `search` and `search_by_category` rank their results
+    pub fn set_ranking_config(&mut self, config: SearchRankingConfig) {
+        self.ranking = config;
     }
 
     /// Perform a comprehensive search with multiple strategies
@@ -34,7 +115,7 @@ impl ComponentSearchEngine {
         all_results.extend(fuzzy_results);
 
         // Deduplicate and merge results
-        let merged_results = self.merge_and_deduplicate_results(all_results);
+        let merged_results = self.apply_ranking(self.merge_and_deduplicate_results(all_results));
 
         // Apply final limit
         let final_results = if let Some(limit) = limit {
@@ -80,7 +161,15 @@ impl ComponentSearchEngine {
             }
         }
 
-        self.db.search_components_advanced(&filter, limit)
+        // Re-ranking can promote parts past the database's cut-off, so fetch
+        // everything unless the order is left as the database returns it
+        let db_limit = if self.ranking.is_text_only() { limit } else { None };
+        let results = self.db.search_components_advanced(&filter, db_limit)?;
+        let mut ranked = self.apply_ranking(results);
+        if let Some(limit) = limit {
+            ranked.truncate(limit as usize);
+        }
+        Ok(ranked)
     }
 
     /// Search for components with specific specifications
@@ -160,6 +249,65 @@ impl ComponentSearchEngine {
         Ok(suggestions)
     }
 
+    /// Explain which criteria parsed from `query` a search result meets
+    ///
+    /// Criteria are the resistance, capacitance, voltage, package, tolerance
+    /// and manufacturer found in the query; values are compared numerically,
+    /// so "10k" matches a "10 kOhms" specification.
+    pub fn explain_search_result(&self, query: &str, result: &ComponentSearchResult) -> SearchExplanation {
+        let query_lower = query.to_lowercase();
+        let component = &result.component;
+        let mut criteria = Vec::new();
+
+        let numeric = [
+            ("Resistance", "resistance", self.extract_resistance(&query_lower), RESISTANCE_WEIGHT),
+            ("Capacitance", "capacitance", self.extract_capacitance(&query_lower), CAPACITANCE_WEIGHT),
+            ("Voltage", "voltage", self.extract_voltage(&query_lower), VOLTAGE_WEIGHT),
+        ];
+        for (label, key, expected, weight) in numeric {
+            if let Some(expected) = expected {
+                criteria.push(SearchCriterion {
+                    criterion: format!("{} {}", label, expected.as_string()),
+                    matched: specifications_containing(component, key).any(|value| same_value(value, &expected)),
+                    weight,
+                });
+            }
+        }
+
+        if let Some(package) = self.extract_package(&query_lower) {
+            let wanted = normalize_package(&package);
+            let mut texts: Vec<String> = component.footprint.iter().cloned().collect();
+            texts.extend(specifications_containing(component, "package").map(SpecValue::as_string));
+            texts.extend(specifications_containing(component, "case").map(SpecValue::as_string));
+            texts.push(component.part_number.clone());
+            let matched = texts.iter().any(|text| normalize_package(text).contains(&wanted));
+            criteria.push(SearchCriterion { criterion: format!("Package {}", package), matched, weight: PACKAGE_WEIGHT });
+        }
+
+        if let Some(tolerance) = self.extract_tolerance(&query_lower) {
+            let matched = specifications_containing(component, "tolerance").any(|value| {
+                let text = value.as_string();
+                units::parse_si_value(text.trim_start_matches('±').trim_end_matches('%'))
+                    .is_ok_and(|percent| (percent - tolerance).abs() < 1e-9)
+            });
+            criteria.push(SearchCriterion {
+                criterion: format!("Tolerance {}%", tolerance),
+                matched,
+                weight: TOLERANCE_WEIGHT,
+            });
+        }
+
+        if let Some(manufacturer) = self.extract_manufacturer(&query_lower) {
+            criteria.push(SearchCriterion {
+                matched: component.manufacturer.to_lowercase().contains(&manufacturer),
+                criterion: format!("Manufacturer {}", manufacturer),
+                weight: MANUFACTURER_WEIGHT,
+            });
+        }
+
+        SearchExplanation { relevance_score: result.relevance_score, criteria }
+    }
+
     /// Parse natural language query into structured filter
     fn parse_query_to_filter(&self, query: &str) -> Option<ComponentSearchFilter> {
         let query_lower = query.to_lowercase();
@@ -206,82 +354,43 @@ impl ComponentSearchEngine {
     /// Extract resistance value from query
     fn extract_resistance(&self, query: &str) -> Option<SpecValue> {
         // Look for patterns like "1k", "10ohm", "4.7k", "100R"
-        let resistance_patterns = [
-            r"(\d+(?:\.\d+)?)\s*k(?:ohm)?",
-            r"(\d+(?:\.\d+)?)\s*m(?:ohm)?",
-            r"(\d+(?:\.\d+)?)\s*(?:ohm|r)",
-        ];
-
-        for pattern in &resistance_patterns {
-            if let Ok(regex) = regex::Regex::new(pattern) {
-                if let Some(captures) = regex.captures(query) {
-                    if let Some(value_str) = captures.get(1) {
-                        if let Ok(value) = value_str.as_str().parse::<f64>() {
-                            let unit = if pattern.contains("k") {
-                                "kΩ"
-                            } else if pattern.contains("m") {
-                                "mΩ"
-                            } else {
-                                "Ω"
-                            };
-                            return Some(SpecValue::String(format!("{}{}", value, unit)));
-                        }
-                    }
-                }
-            }
-        }
-
-        None
+        let regex = regex::Regex::new(r"(\d+(?:\.\d+)?)\s*(meg|[kmg])?\s*(ohms?|ω|r)?\b").ok()?;
+
+        return regex
+            .captures_iter(query)
+            .filter(|captures| captures.get(2).is_some() || captures.get(3).is_some())
+            .find_map(|captures| {
+                let prefix = captures.get(2).map_or("", |m| m.as_str());
+                let value = units::parse_si_value(&format!("{}{}", &captures[1], prefix)).ok()?;
+                Some(SpecValue::String(units::format_si_value(value, "Ω")))
+            });
     }
 
     /// Extract capacitance value from query
     fn extract_capacitance(&self, query: &str) -> Option<SpecValue> {
         // Look for patterns like "100nF", "10uF", "1pF"
-        let capacitance_patterns = [
-            r"(\d+(?:\.\d+)?)\s*pf",
-            r"(\d+(?:\.\d+)?)\s*nf",
-            r"(\d+(?:\.\d+)?)\s*uf",
-            r"(\d+(?:\.\d+)?)\s*mf",
-        ];
-
-        for pattern in &capacitance_patterns {
-            if let Ok(regex) = regex::Regex::new(pattern) {
-                if let Some(captures) = regex.captures(query) {
-                    if let Some(value_str) = captures.get(1) {
-                        if let Ok(value) = value_str.as_str().parse::<f64>() {
-                            let unit = if pattern.contains("pf") {
-                                "pF"
-                            } else if pattern.contains("nf") {
-                                "nF"
-                            } else if pattern.contains("uf") {
-                                "µF"
-                            } else {
-                                "mF"
-                            };
-                            return Some(SpecValue::String(format!("{}{}", value, unit)));
-                        }
-                    }
-                }
-            }
-        }
+        let regex = regex::Regex::new(r"(\d+(?:\.\d+)?)\s*([pnuµm])f").ok()?;
+        let captures = regex.captures(query)?;
+        let value = units::parse_si_value(&format!("{}{}", &captures[1], &captures[2])).ok()?;
 
-        None
+        Some(SpecValue::String(units::format_si_value(value, "F")))
     }
 
     /// Extract voltage value from query
     fn extract_voltage(&self, query: &str) -> Option<SpecValue> {
         // Look for patterns like "5V", "3.3V", "12 volt"
-        if let Ok(regex) = regex::Regex::new(r"(\d+(?:\.\d+)?)\s*v(?:olt)?") {
-            if let Some(captures) = regex.captures(query) {
-                if let Some(value_str) = captures.get(1) {
-                    if let Ok(value) = value_str.as_str().parse::<f64>() {
-                        return Some(SpecValue::String(format!("{}V", value)));
-                    }
-                }
-            }
-        }
+        let regex = regex::Regex::new(r"(\d+(?:\.\d+)?)\s*([mk])?v(?:olts?)?\b").ok()?;
+        let captures = regex.captures(query)?;
+        let prefix = captures.get(2).map_or("", |m| m.as_str());
+        let value = units::parse_si_value(&format!("{}{}", &captures[1], prefix)).ok()?;
 
-        None
+        Some(SpecValue::String(units::format_si_value(value, "V")))
+    }
+
+    /// Extract a tolerance percentage from query, e.g. "1%" or "0.5 %"
+    fn extract_tolerance(&self, query: &str) -> Option<f64> {
+        let regex = regex::Regex::new(r"(\d+(?:\.\d+)?)\s*%").ok()?;
+        regex.captures(query)?[1].parse().ok()
     }
 
     /// Extract package/footprint from query
@@ -311,8 +420,11 @@ impl ComponentSearchEngine {
             "samsung", "panasonic", "nichicon", "kemet",
         ];
 
+        // Match whole words so "st" is not found inside "resistor"
+        let words: Vec<&str> = query.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()).collect();
+        let padded = format!(" {} ", words.join(" "));
         for manufacturer in &common_manufacturers {
-            if query.contains(manufacturer) {
+            if padded.contains(&format!(" {} ", manufacturer)) {
                 return Some(manufacturer.to_string());
             }
         }
@@ -397,6 +509,39 @@ impl ComponentSearchEngine {
         (position_similarity * 0.5 + character_similarity * 0.3 + length_similarity * 0.2)
     }
 
+    /// Re-score results by the ranking config, best first
+    fn apply_ranking(&self, mut results: Vec<ComponentSearchResult>) -> Vec<ComponentSearchResult> {
+        if self.ranking.is_text_only() || results.is_empty() {
+            return results;
+        }
+
+        let best_text = results.iter().map(|result| result.relevance_score).fold(0.0, f64::max);
+        let prices: Vec<Option<f64>> = results.iter().map(|result| unit_price(&result.component)).collect();
+        let cheapest = prices.iter().flatten().copied().fold(f64::INFINITY, f64::min);
+        let dearest = prices.iter().flatten().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        for (result, price) in results.iter_mut().zip(prices) {
+            let text = if best_text > 0.0 { result.relevance_score / best_text } else { 0.0 };
+            let in_stock = result.component.availability.as_ref().is_some_and(|availability| availability.in_stock);
+            let availability = if in_stock { 1.0 } else { NOT_IN_STOCK_SCORE };
+            let price = match price {
+                Some(_) if dearest <= cheapest => 1.0,
+                Some(price) => (dearest - price) / (dearest - cheapest),
+                None => 0.0,
+            };
+
+            result.relevance_score = text * self.ranking.text_relevance_weight as f64
+                + availability * self.ranking.availability_weight as f64
+                + price * self.ranking.price_weight as f64;
+            if in_stock {
+                result.match_reasons.push("In stock".to_string());
+            }
+        }
+
+        results.sort_by(|a, b| b.relevance_score.partial_cmp(&a.relevance_score).unwrap_or(std::cmp::Ordering::Equal));
+        results
+    }
+
     /// Merge and deduplicate search results
     fn merge_and_deduplicate_results(&self, mut results: Vec<ComponentSearchResult>) -> Vec<ComponentSearchResult> {
         // Sort by component ID to group duplicates together
@@ -437,6 +582,39 @@ impl ComponentSearchEngine {
     }
 }
 
+/// Unit price at the smallest quantity break
+fn unit_price(component: &Component) -> Option<f64> {
+    component
+        .price_info
+        .as_ref()?
+        .price_breaks
+        .iter()
+        .min_by_key(|price_break| price_break.quantity)
+        .map(|price_break| price_break.unit_price)
+}
+
+/// Specification values whose name contains `key`, ignoring case
+fn specifications_containing<'a>(component: &'a Component, key: &'a str) -> impl Iterator<Item = &'a SpecValue> {
+    component
+        .specifications
+        .iter()
+        .filter(move |(name, _)| name.to_lowercase().contains(key))
+        .map(|(_, value)| value)
+}
+
+/// Whether two specification values are equal, numerically when both parse
+fn same_value(value: &SpecValue, expected: &SpecValue) -> bool {
+    match (value.as_f64(), expected.as_f64()) {
+        (Some(value), Some(expected)) => (value - expected).abs() <= expected.abs() * 1e-6,
+        _ => value.as_string().eq_ignore_ascii_case(&expected.as_string()),
+    }
+}
+
+/// Package name without case, spaces or dashes, so "SOT-23" matches "sot23"
+fn normalize_package(package: &str) -> String {
+    package.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -479,6 +657,44 @@ mod tests {
         assert!(engine.extract_package("no package here").is_none());
     }
 
+    #[test]
+    fn test_manufacturer_extraction_matches_whole_words() {
+        let engine = ComponentSearchEngine::new().unwrap();
+
+        assert_eq!(engine.extract_manufacturer("10k 0603 resistor"), None);
+        assert_eq!(engine.extract_manufacturer("st ldo 3.3v"), Some("st".to_string()));
+        assert_eq!(engine.extract_manufacturer("texas instruments op amp"), Some("texas instruments".to_string()));
+    }
+
+    #[test]
+    fn test_explain_search_result() {
+        let engine = ComponentSearchEngine::new().unwrap();
+        let mut component = Component::new(
+            "RC0603FR-071KL".to_string(),
+            "YAGEO".to_string(),
+            ComponentCategory::Resistors,
+            "1 kOhms ±1% 0.1W Chip Resistor".to_string(),
+        );
+        component.specifications.insert("Resistance".to_string(), SpecValue::String("1 kOhms".to_string()));
+        component.specifications.insert("Tolerance".to_string(), SpecValue::String("±1%".to_string()));
+        let result = ComponentSearchResult::new(component, 72.0);
+
+        let explanation = engine.explain_search_result("10k 0603 1% vishay", &result);
+        assert_eq!(explanation.relevance_score, 72.0);
+        let outcome: Vec<(&str, bool)> =
+            explanation.criteria.iter().map(|criterion| (criterion.criterion.as_str(), criterion.matched)).collect();
+        assert_eq!(
+            outcome,
+            vec![("Resistance 10kΩ", false), ("Package 0603", true), ("Tolerance 1%", true), ("Manufacturer vishay", false)]
+        );
+        assert!((explanation.match_ratio() - 0.35 / 0.8).abs() < 1e-9);
+        assert_eq!(explanation.unmatched().count(), 2);
+
+        let explanation = engine.explain_search_result("1k resistor", &result);
+        assert!(explanation.criteria[0].matched);
+        assert_eq!(explanation.match_ratio(), 1.0);
+    }
+
     #[test]
     fn test_fuzzy_similarity() {
         let engine = ComponentSearchEngine::new().unwrap();
@@ -487,4 +703,48 @@ mod tests {
         assert!(engine.calculate_fuzzy_similarity("R1234", "C1234") > 0.6);
         assert!(engine.calculate_fuzzy_similarity("R1234", "xyz") < 0.3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ranking_prefers_in_stock_parts() {
+        use opencircuit_core::models::{AvailabilityInfo, PriceBreak, PriceInfo};
+
+        let part = |part_number: &str, in_stock: bool, unit_price: f64| {
+            let mut component = Component::new(
+                part_number.to_string(),
+                "YAGEO".to_string(),
+                ComponentCategory::Resistors,
+                "10k resistor".to_string(),
+            );
+            component.availability = Some(AvailabilityInfo {
+                in_stock,
+                quantity_available: Some(if in_stock { 5000 } else { 0 }),
+                lead_time_days: None,
+                minimum_order_quantity: None,
+                last_updated: chrono::Utc::now(),
+                supplier: "DigiKey".to_string(),
+            });
+            component.price_info = Some(PriceInfo {
+                currency: "USD".to_string(),
+                price_breaks: vec![PriceBreak { quantity: 1, unit_price }, PriceBreak { quantity: 100, unit_price: unit_price / 2.0 }],
+                last_updated: chrono::Utc::now(),
+                supplier: "DigiKey".to_string(),
+            });
+            component
+        };
+        let results = vec![
+            ComponentSearchResult::new(part("RC0603FR-0710KL", false, 0.10), 100.0),
+            ComponentSearchResult::new(part("RC0603JR-0710KL", true, 0.05), 60.0),
+        ];
+
+        let mut engine = ComponentSearchEngine::new().unwrap();
+        let unchanged = engine.apply_ranking(results.clone());
+        assert_eq!(unchanged[0].relevance_score, 100.0);
+
+        engine.set_ranking_config(SearchRankingConfig::prefer_in_stock());
+        let ranked = engine.apply_ranking(results);
+        assert_eq!(ranked[0].component.part_number, "RC0603JR-0710KL");
+        assert!((ranked[0].relevance_score - (0.6 * 0.5 + 0.4 + 0.1)).abs() < 1e-6);
+        assert!((ranked[1].relevance_score - (0.5 + 0.3 * 0.4)).abs() < 1e-6);
+        assert_eq!(ranked[0].match_reasons, vec!["In stock".to_string()]);
+    }
+}
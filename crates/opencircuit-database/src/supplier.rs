@@ -0,0 +1,293 @@
+//! Component records built from raw supplier API responses
+//!
+//! Fields are read by JSON path so that partial or null-heavy responses still
+//! produce a record; only the part number and manufacturer are required.
+//! Records have no pricing column, so price breaks are kept in the
+//! specifications under [`PRICE_BREAKS_SPEC`] as `quantity@price` entries.
+//! Stock refreshed from the suppliers is kept the same way, as JSON under
+//! [`AVAILABILITY_SPEC`].
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use opencircuit_core::{ApiError, AvailabilityInfo, SpecValue};
+use rusqlite::params;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{ComponentRecord, Database};
+
+/// Specification key holding supplier price breaks
+pub const PRICE_BREAKS_SPEC: &str = "Price Breaks";
+
+/// Specification key holding the last supplier availability, as JSON
+pub const AVAILABILITY_SPEC: &str = "Availability";
+
+impl ComponentRecord {
+    /// Build a record from a DigiKey product details response
+    ///
+    /// Accepts the product object itself or a search response, in which case
+    /// the first product is used.
+    pub fn from_digikey_response(json: &Value) -> Result<ComponentRecord, ApiError> {
+        let product = json
+            .get("Product")
+            .or_else(|| json.pointer("/Products/0"))
+            .unwrap_or(json);
+
+        let part_number = first_str(product, &["/ManufacturerPartNumber", "/ManufacturerProductNumber"])
+            .ok_or_else(|| missing("DigiKey", "ManufacturerPartNumber"))?;
+        let manufacturer = first_str(product, &["/Manufacturer/Name", "/Manufacturer/Value"])
+            .ok_or_else(|| missing("DigiKey", "Manufacturer.Name"))?;
+        let category = first_str(product, &["/Category/Name", "/Category/Value"]).unwrap_or_default();
+        let description = first_str(
+            product,
+            &["/DetailedDescription", "/ProductDescription", "/Description/DetailedDescription", "/Description/ProductDescription"],
+        );
+        let datasheet_url = first_str(product, &["/PrimaryDatasheet", "/DatasheetUrl"]);
+
+        let mut specifications = named_values(product.get("Parameters"), &["/Parameter", "/ParameterText"], &["/Value", "/ValueText"]);
+        let price_breaks: Vec<String> = array(product.get("StandardPricing"))
+            .filter_map(|price| {
+                let quantity = price.get("BreakQuantity").and_then(Value::as_u64)?;
+                let unit_price = price.get("UnitPrice").and_then(Value::as_f64)?;
+                Some(format!("{}@{}", quantity, unit_price))
+            })
+            .collect();
+        if !price_breaks.is_empty() {
+            specifications.insert(PRICE_BREAKS_SPEC.to_string(), SpecValue::List(price_breaks));
+        }
+
+        Ok(new_record(part_number, manufacturer, category, description, datasheet_url, specifications))
+    }
+
+    /// Build a record from a Mouser part or search response
+    ///
+    /// Accepts the part object itself or a search response, in which case the
+    /// first part is used.
+    pub fn from_mouser_response(json: &Value) -> Result<ComponentRecord, ApiError> {
+        let part = json.pointer("/SearchResults/Parts/0").unwrap_or(json);
+
+        let part_number =
+            first_str(part, &["/ManufacturerPartNumber"]).ok_or_else(|| missing("Mouser", "ManufacturerPartNumber"))?;
+        let manufacturer = first_str(part, &["/Manufacturer", "/Manufacturer/Name"])
+            .ok_or_else(|| missing("Mouser", "Manufacturer"))?;
+        let category = first_str(part, &["/Category"]).unwrap_or_default();
+        let description = first_str(part, &["/Description"]);
+        let datasheet_url = first_str(part, &["/DataSheetUrl"]);
+
+        let mut specifications = named_values(part.get("ProductAttributes"), &["/AttributeName"], &["/AttributeValue"]);
+        let price_breaks: Vec<String> = array(part.get("PriceBreaks"))
+            .filter_map(|price| {
+                let quantity = price.get("Quantity").and_then(Value::as_u64)?;
+                let unit_price = first_str(price, &["/Price"])?;
+                Some(format!("{}@{}", quantity, unit_price))
+            })
+            .collect();
+        if !price_breaks.is_empty() {
+            specifications.insert(PRICE_BREAKS_SPEC.to_string(), SpecValue::List(price_breaks));
+        }
+
+        Ok(new_record(part_number, manufacturer, category, description, datasheet_url, specifications))
+    }
+}
+
+impl Database {
+    /// Store supplier availability keyed by part number
+    ///
+    /// Every component with a matching part number is updated, all in one
+    /// transaction. Returns the number of components updated.
+    pub fn update_availability(&self, availability: &HashMap<String, AvailabilityInfo>) -> Result<usize> {
+        self.transaction(|tx| {
+            let mut updated = 0;
+            for (part_number, info) in availability {
+                let components: Vec<(String, Option<String>)> = tx
+                    .prepare("SELECT id, specifications FROM components WHERE part_number = ?")?
+                    .query_map(params![part_number], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<rusqlite::Result<_>>()?;
+
+                for (id, specifications) in components {
+                    let mut specifications: HashMap<String, SpecValue> = match specifications {
+                        Some(json) => serde_json::from_str(&json)
+                            .with_context(|| format!("Invalid specifications for component {}", id))?,
+                        None => HashMap::new(),
+                    };
+                    specifications.insert(AVAILABILITY_SPEC.to_string(), SpecValue::String(serde_json::to_string(info)?));
+                    tx.execute(
+                        "UPDATE components SET specifications = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+                        params![serde_json::to_string(&specifications)?, id],
+                    )?;
+                    updated += 1;
+                }
+            }
+            Ok(updated)
+        })
+    }
+}
+
+/// Availability stored under [`AVAILABILITY_SPEC`], if any
+pub(crate) fn stored_availability(specifications: &HashMap<String, SpecValue>) -> Option<AvailabilityInfo> {
+    match specifications.get(AVAILABILITY_SPEC)? {
+        SpecValue::String(json) => serde_json::from_str(json).ok(),
+        _ => None,
+    }
+}
+
+pub(crate) fn new_record(
+    part_number: String,
+    manufacturer: String,
+    category: String,
+    description: Option<String>,
+    datasheet_url: Option<String>,
+    specifications: HashMap<String, SpecValue>,
+) -> ComponentRecord {
+    let now = Utc::now().to_rfc3339();
+    ComponentRecord {
+        id: Uuid::new_v4().to_string(),
+        part_number,
+        manufacturer,
+        category,
+        description,
+        datasheet_url,
+        specifications: if specifications.is_empty() {
+            None
+        } else {
+            serde_json::to_string(&specifications).ok()
+        },
+        footprint: None,
+        symbol: None,
+        created_at: now.clone(),
+        updated_at: now,
+    }
+}
+
+/// First non-empty string found at the given JSON pointers
+fn first_str(json: &Value, pointers: &[&str]) -> Option<String> {
+    pointers
+        .iter()
+        .filter_map(|pointer| json.pointer(pointer).and_then(Value::as_str))
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Elements of a JSON array, or nothing when the value is missing or null
+fn array(json: Option<&Value>) -> impl Iterator<Item = &Value> {
+    json.and_then(Value::as_array).into_iter().flatten()
+}
+
+/// Collect name/value pairs from an array of objects, skipping incomplete ones
+fn named_values(json: Option<&Value>, name_pointers: &[&str], value_pointers: &[&str]) -> HashMap<String, SpecValue> {
+    array(json)
+        .filter_map(|entry| {
+            let name = first_str(entry, name_pointers)?;
+            let value = first_str(entry, value_pointers)?;
+            Some((name, SpecValue::String(value)))
+        })
+        .collect()
+}
+
+fn missing(service: &str, field: &str) -> ApiError {
+    ApiError::InvalidResponse(format!("{} response is missing {}", service, field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGIKEY_PRODUCT: &str = include_str!("../tests/fixtures/digikey_product.json");
+    const MOUSER_SEARCH: &str = include_str!("../tests/fixtures/mouser_search.json");
+
+    fn specifications(record: &ComponentRecord) -> HashMap<String, SpecValue> {
+        serde_json::from_str(record.specifications.as_deref().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_from_digikey_response() {
+        let json: Value = serde_json::from_str(DIGIKEY_PRODUCT).unwrap();
+        let record = ComponentRecord::from_digikey_response(&json).unwrap();
+
+        assert_eq!(record.part_number, "RC0805FR-0710KL");
+        assert_eq!(record.manufacturer, "YAGEO");
+        assert_eq!(record.category, "Resistors");
+        assert_eq!(record.description.as_deref(), Some("10 kOhms ±1% 0.125W, 1/8W Chip Resistor 0805 (2012 Metric) Moisture Resistant Thick Film"));
+        assert_eq!(record.datasheet_url.as_deref(), Some("https://www.yageo.com/upload/media/product/productsearch/datasheet/rchip/PYu-RC_Group_51_RoHS_L_12.pdf"));
+
+        let specs = specifications(&record);
+        assert_eq!(specs["Resistance"], SpecValue::String("10 kOhms".to_string()));
+        assert_eq!(specs["Tolerance"], SpecValue::String("±1%".to_string()));
+        // Parameters without a value are skipped
+        assert!(!specs.contains_key("Failure Rate"));
+        assert_eq!(
+            specs[PRICE_BREAKS_SPEC],
+            SpecValue::List(vec!["1@0.1".to_string(), "10@0.025".to_string(), "100@0.0082".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_from_mouser_response() {
+        let json: Value = serde_json::from_str(MOUSER_SEARCH).unwrap();
+        let record = ComponentRecord::from_mouser_response(&json).unwrap();
+
+        assert_eq!(record.part_number, "LM358DR");
+        assert_eq!(record.manufacturer, "Texas Instruments");
+        assert_eq!(record.category, "Operational Amplifiers - Op Amps");
+        assert_eq!(record.description.as_deref(), Some("Operational Amplifiers - Op Amps Dual Gen Purp Op Amp"));
+        // A null datasheet is treated as missing
+        assert_eq!(record.datasheet_url, None);
+
+        let specs = specifications(&record);
+        assert_eq!(specs["Number of Channels"], SpecValue::String("2 Channel".to_string()));
+        assert_eq!(
+            specs[PRICE_BREAKS_SPEC],
+            SpecValue::List(vec!["1@$0.43".to_string(), "10@$0.293".to_string(), "2500@$0.099".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_update_availability() {
+        let db = Database::new_in_memory().unwrap();
+        let json: Value = serde_json::from_str(MOUSER_SEARCH).unwrap();
+        let mut record = ComponentRecord::from_mouser_response(&json).unwrap();
+        record.category = "Integrated Circuits".to_string();
+        db.create_component(&record).unwrap();
+
+        let stock = AvailabilityInfo {
+            in_stock: true,
+            quantity_available: Some(2500),
+            lead_time_days: Some(42),
+            minimum_order_quantity: Some(1),
+            last_updated: Utc::now(),
+            supplier: "Mouser".to_string(),
+        };
+        let refreshed = HashMap::from([("LM358DR".to_string(), stock.clone()), ("NE555P".to_string(), stock.clone())]);
+        assert_eq!(db.update_availability(&refreshed).unwrap(), 1);
+
+        let specs = specifications(&db.get_component(&record.id).unwrap().unwrap());
+        assert_eq!(stored_availability(&specs), Some(stock));
+        assert!(specs.contains_key(PRICE_BREAKS_SPEC));
+    }
+
+    #[test]
+    fn test_missing_and_null_fields() {
+        let minimal = serde_json::json!({
+            "ManufacturerPartNumber": "NE555P",
+            "Manufacturer": { "Id": 296, "Value": "Texas Instruments" },
+            "Category": null,
+            "Parameters": null,
+            "StandardPricing": [{ "BreakQuantity": null, "UnitPrice": 0.5 }]
+        });
+        let record = ComponentRecord::from_digikey_response(&minimal).unwrap();
+        assert_eq!(record.manufacturer, "Texas Instruments");
+        assert_eq!(record.category, "");
+        assert_eq!(record.description, None);
+        assert_eq!(record.specifications, None);
+
+        let no_part = serde_json::json!({ "SearchResults": { "NumberOfResult": 0, "Parts": [] } });
+        assert!(matches!(
+            ComponentRecord::from_mouser_response(&no_part),
+            Err(ApiError::InvalidResponse(message)) if message.contains("ManufacturerPartNumber")
+        ));
+        let no_manufacturer = serde_json::json!({ "ManufacturerPartNumber": "NE555P", "Manufacturer": null });
+        assert!(ComponentRecord::from_digikey_response(&no_manufacturer).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! Component usage history for the "Recent" component list
+//!
+//! Every time a component is added to a circuit a row is recorded, so the
+//! most recently used parts can be offered without searching. Timestamps are
+//! stored as fixed-width RFC 3339 strings so they sort chronologically.
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::params;
+
+use crate::{ComponentRecord, Database};
+
+impl Database {
+    /// Record that a component was added to a circuit in a project
+    pub fn record_component_usage(&self, component_id: &str, project_id: &str) -> Result<()> {
+        self.record_component_usage_at(component_id, project_id, Utc::now())
+    }
+
+    fn record_component_usage_at(&self, component_id: &str, project_id: &str, used_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.connection.lock().unwrap();
+        conn.execute(
+            "INSERT INTO component_usage (component_id, project_id, used_at) VALUES (?, ?, ?)",
+            params![component_id, project_id, used_at.to_rfc3339_opts(SecondsFormat::Micros, true)],
+        )?;
+        Ok(())
+    }
+
+    /// Components by most recent use across all projects, newest first
+    pub fn get_recently_used_components(&self, limit: u32) -> Result<Vec<ComponentRecord>> {
+        let conn = self.connection.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT c.id, c.part_number, c.manufacturer, c.category, c.description,
+                   c.datasheet_url, c.specifications, c.footprint, c.symbol,
+                   c.created_at, c.updated_at
+            FROM components c
+            JOIN component_usage u ON u.component_id = c.id
+            GROUP BY c.id
+            ORDER BY MAX(u.used_at) DESC
+            LIMIT ?
+            "#,
+        )?;
+
+        let components = stmt
+            .query_map(params![limit], |row| {
+                Ok(ComponentRecord {
+                    id: row.get(0)?,
+                    part_number: row.get(1)?,
+                    manufacturer: row.get(2)?,
+                    category: row.get(3)?,
+                    description: row.get(4)?,
+                    datasheet_url: row.get(5)?,
+                    specifications: row.get(6)?,
+                    footprint: row.get(7)?,
+                    symbol: row.get(8)?,
+                    created_at: row.get(9)?,
+                    updated_at: row.get(10)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(components)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn database_with_components(ids: &[&str]) -> Database {
+        let db = Database::new_in_memory().unwrap();
+        for id in ids {
+            db.create_component(&ComponentRecord {
+                id: id.to_string(),
+                part_number: format!("PN-{}", id),
+                manufacturer: "Yageo".to_string(),
+                category: "Resistors".to_string(),
+                description: None,
+                datasheet_url: None,
+                specifications: None,
+                footprint: None,
+                symbol: None,
+                created_at: "2025-01-27T12:00:00Z".to_string(),
+                updated_at: "2025-01-27T12:00:00Z".to_string(),
+            })
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_recently_used_orders_by_latest_use() {
+        let db = database_with_components(&["r1", "r2", "r3", "unused"]);
+        let at = |minute: u32| Utc.with_ymd_and_hms(2025, 3, 1, 9, minute, 0).unwrap();
+        db.record_component_usage_at("r1", "p1", at(0)).unwrap();
+        db.record_component_usage_at("r2", "p1", at(5)).unwrap();
+        db.record_component_usage_at("r3", "p2", at(10)).unwrap();
+        // Using r1 again in another project makes it the most recent
+        db.record_component_usage_at("r1", "p2", at(15)).unwrap();
+
+        let ids = |components: Vec<ComponentRecord>| components.into_iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids(db.get_recently_used_components(10).unwrap()), ["r1", "r3", "r2"]);
+        assert_eq!(ids(db.get_recently_used_components(2).unwrap()), ["r1", "r3"]);
+    }
+
+    #[test]
+    fn test_usage_removed_with_component() {
+        let db = database_with_components(&["r1"]);
+        db.record_component_usage("r1", "p1").unwrap();
+        assert_eq!(db.get_recently_used_components(5).unwrap().len(), 1);
+
+        db.delete_component("r1").unwrap();
+        assert!(db.get_recently_used_components(5).unwrap().is_empty());
+    }
+}
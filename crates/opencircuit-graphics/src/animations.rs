@@ -2,11 +2,17 @@
 //! 
 //! Provides smooth animations for real-time circuit simulation results,
 //! including current flow, voltage levels, and interactive effects.
+//! Rendered frames can be recorded to a looping GIF for sharing replays.
 
-use egui::{Color32, Pos2, Rect, Stroke, Ui, Vec2};
+use egui::{Color32, ColorImage, Pos2, Rect, Stroke, Ui, Vec2};
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
+use crate::{GraphicsError, GraphicsResult};
+
 /// Animation manager for circuit simulation
 pub struct CircuitAnimations {
     /// Current animation state
@@ -17,6 +23,8 @@ pub struct CircuitAnimations {
     time: f32,
     /// Last update time
     last_update: Instant,
+    /// Active GIF recording, if any
+    recording: Option<GifRecording>,
 }
 
 impl CircuitAnimations {
@@ -26,6 +34,7 @@ impl CircuitAnimations {
             speed: 1.0,
             time: 0.0,
             last_update: Instant::now(),
+            recording: None,
         }
     }
 
@@ -48,6 +57,7 @@ impl CircuitAnimations {
 
     /// Add current flow animation along a wire
     pub fn add_current_flow(&mut self, wire_id: String, current: f64, duration: Duration) {
+        let key = format!("current_{}", wire_id);
         let anim = Animation::CurrentFlow(CurrentFlowAnimation {
             wire_id,
             current: current.abs(),
@@ -57,11 +67,12 @@ impl CircuitAnimations {
             particles: Vec::new(),
         });
         
-        self.animations.insert(format!("current_{}", wire_id), anim);
+        self.animations.insert(key, anim);
     }
 
     /// Add voltage level animation for a component
     pub fn add_voltage_level(&mut self, component_id: String, voltage: f64, duration: Duration) {
+        let key = format!("voltage_{}", component_id);
         let anim = Animation::VoltageLevel(VoltageLevelAnimation {
             component_id,
             voltage,
@@ -70,11 +81,12 @@ impl CircuitAnimations {
             pulse_intensity: 0.0,
         });
         
-        self.animations.insert(format!("voltage_{}", component_id), anim);
+        self.animations.insert(key, anim);
     }
 
     /// Add selection highlight animation
     pub fn add_selection_highlight(&mut self, component_id: String, duration: Duration) {
+        let key = format!("select_{}", component_id);
         let anim = Animation::SelectionHighlight(SelectionHighlightAnimation {
             component_id,
             duration,
@@ -82,7 +94,7 @@ impl CircuitAnimations {
             pulse_phase: 0.0,
         });
         
-        self.animations.insert(format!("select_{}", component_id), anim);
+        self.animations.insert(key, anim);
     }
 
     /// Add connection animation for new components
@@ -111,10 +123,65 @@ impl CircuitAnimations {
     }
 
     /// Render all animations
-    pub fn render(&self, ui: &mut Ui) {
+    ///
+    /// While recording, each call requests a screenshot and the one delivered
+    /// by the previous frame is cropped to this viewport and added to the GIF.
+    pub fn render(&mut self, ui: &mut Ui) {
         for anim in self.animations.values() {
             anim.render(ui);
         }
+
+        if self.recording.is_some() {
+            let screenshot = ui.input(|input| {
+                input.events.iter().rev().find_map(|event| match event {
+                    egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                    _ => None,
+                })
+            });
+            if let Some(image) = screenshot {
+                let frame = image.region(&ui.clip_rect(), Some(ui.ctx().pixels_per_point()));
+                self.capture_frame(&frame);
+            }
+            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+    }
+
+    /// Start recording rendered frames to a GIF at `path`
+    ///
+    /// Any recording already in progress is discarded. The file is created
+    /// with the first frame, whose size fixes the size of the whole GIF.
+    pub fn record_start(&mut self, path: &Path, fps: u32) {
+        self.recording = Some(GifRecording {
+            path: path.to_path_buf(),
+            frame_delay: (100 / fps.max(1)).clamp(1, u16::MAX as u32) as u16,
+            encoder: None,
+            size: (0, 0),
+            frames: 0,
+            error: None,
+        });
+    }
+
+    /// Finish the recording and flush the GIF to disk
+    pub fn record_stop(&mut self) -> GraphicsResult<()> {
+        let recording = self
+            .recording
+            .take()
+            .ok_or_else(|| GraphicsError::Animation("No recording in progress".to_string()))?;
+        recording.finish()
+    }
+
+    /// Whether rendered frames are being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Add a frame to the active recording
+    fn capture_frame(&mut self, image: &ColorImage) {
+        if let Some(recording) = &mut self.recording {
+            if let Err(error) = recording.push_frame(image) {
+                recording.error.get_or_insert(error);
+            }
+        }
     }
 
     /// Get animation progress for a specific animation
@@ -138,6 +205,82 @@ impl CircuitAnimations {
     }
 }
 
+/// GIF being written by [`CircuitAnimations::record_start`]
+struct GifRecording {
+    path: PathBuf,
+    /// Delay between frames in hundredths of a second
+    frame_delay: u16,
+    encoder: Option<gif::Encoder<BufWriter<File>>>,
+    size: (u16, u16),
+    frames: usize,
+    /// First error hit while capturing, reported by `record_stop`
+    error: Option<GraphicsError>,
+}
+
+impl GifRecording {
+    fn push_frame(&mut self, image: &ColorImage) -> GraphicsResult<()> {
+        if self.error.is_some() {
+            return Ok(());
+        }
+        if self.encoder.is_none() {
+            let [width, height] = image.size;
+            let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+                (Ok(width), Ok(height)) if width > 0 && height > 0 => (width, height),
+                _ => return Err(GraphicsError::Animation(format!("Unsupported GIF frame size {}x{}", width, height))),
+            };
+            let file = File::create(&self.path).map_err(|e| {
+                GraphicsError::Animation(format!("Failed to create {}: {}", self.path.display(), e))
+            })?;
+            let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &[]).map_err(gif_error)?;
+            encoder.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+            self.encoder = Some(encoder);
+            self.size = (width, height);
+        }
+
+        let mut pixels = fit_frame(image, self.size);
+        let mut frame = gif::Frame::from_rgba_speed(self.size.0, self.size.1, &mut pixels, 10);
+        frame.delay = self.frame_delay;
+        if let Some(encoder) = &mut self.encoder {
+            encoder.write_frame(&frame).map_err(gif_error)?;
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> GraphicsResult<()> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let encoder = self
+            .encoder
+            .ok_or_else(|| GraphicsError::Animation("Recording captured no frames".to_string()))?;
+        // Writes the GIF trailer
+        let mut writer = encoder.into_inner().map_err(|e| GraphicsError::Animation(e.to_string()))?;
+        std::io::Write::flush(&mut writer).map_err(|e| GraphicsError::Animation(e.to_string()))
+    }
+}
+
+fn gif_error(error: gif::EncodingError) -> GraphicsError {
+    GraphicsError::Animation(format!("GIF encoding failed: {}", error))
+}
+
+/// RGBA pixels of `image` cropped or padded with black to `size`
+fn fit_frame(image: &ColorImage, (width, height): (u16, u16)) -> Vec<u8> {
+    let (width, height) = (width as usize, height as usize);
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let color = if x < image.size[0] && y < image.size[1] {
+                image.pixels[y * image.size[0] + x]
+            } else {
+                Color32::BLACK
+            };
+            pixels.extend_from_slice(&color.to_srgba_unmultiplied());
+        }
+    }
+    pixels
+}
+
 /// Animation types
 #[derive(Debug, Clone)]
 enum Animation {
@@ -484,4 +627,46 @@ impl AnimationConfig {
             connection_duration: Duration::from_millis(300),
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_writes_looping_gif() {
+        let path = std::env::temp_dir().join(format!("opencircuit_replay_{}.gif", std::process::id()));
+        let mut animations = CircuitAnimations::new();
+        assert!(!animations.is_recording());
+
+        animations.record_start(&path, 20);
+        assert!(animations.is_recording());
+        animations.capture_frame(&ColorImage::new([32, 16], Color32::RED));
+        animations.capture_frame(&ColorImage::new([32, 16], Color32::BLUE));
+        // Larger frames are cropped to the size of the first one
+        animations.capture_frame(&ColorImage::new([40, 20], Color32::GREEN));
+        animations.record_stop().unwrap();
+        assert!(!animations.is_recording());
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(bytes.windows(11).any(|window| window == b"NETSCAPE2.0"));
+
+        let mut decoder = gif::DecodeOptions::new().read_info(bytes.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (32, 16));
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 5);
+            frames += 1;
+        }
+        assert_eq!(frames, 3);
+    }
+
+    #[test]
+    fn test_record_stop_without_frames_fails() {
+        let mut animations = CircuitAnimations::new();
+        assert!(animations.record_stop().is_err());
+
+        animations.record_start(&std::env::temp_dir().join("opencircuit_unused.gif"), 10);
+        assert!(animations.record_stop().is_err());
+    }
+}
@@ -4,19 +4,15 @@
 //! real-time simulation updates, interactive editing, and responsive design.
 
 use egui::{CentralPanel, Context, Response, SidePanel, Ui, Vec2};
-use opencircuit_core::models::Circuit;
-use opencircuit_simulation::CircuitSimulator;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use opencircuit_circuit::{Circuit, SchematicLayout};
 
-use crate::schematic_renderer::{SchematicRenderer, Wire};
+use crate::schematic_renderer::SchematicRenderer;
 use crate::styles::CircuitStyle;
 
 /// Main circuit viewer widget
 pub struct CircuitViewer {
     renderer: SchematicRenderer,
     circuit: Option<Circuit>,
-    simulator: Arc<Mutex<Option<CircuitSimulator>>>,
     style: CircuitStyle,
     show_toolbar: bool,
     show_properties: bool,
@@ -29,7 +25,6 @@ impl CircuitViewer {
         Self {
             renderer: SchematicRenderer::new(),
             circuit: None,
-            simulator: Arc::new(Mutex::new(None)),
             style: CircuitStyle::default(),
             show_toolbar: true,
             show_properties: true,
@@ -239,21 +234,19 @@ impl CircuitViewer {
             });
     }
 
-    fn show_circuit_properties(&mut self, ui: &mut Ui, circuit: &Circuit) {
+    fn show_circuit_properties(&self, ui: &mut Ui, circuit: &Circuit) {
         egui::Grid::new("circuit_properties")
             .num_columns(2)
             .spacing([8.0, 4.0])
             .show(ui, |ui| {
-                ui.label("Name:");
-                ui.label(&circuit.name);
-                ui.end_row();
-                
                 ui.label("Components:");
                 ui.label(circuit.components.len().to_string());
                 ui.end_row();
                 
                 ui.label("Nets:");
-                ui.label(circuit.nets.len().to_string());
+                let nets: std::collections::HashSet<&str> =
+                    circuit.connections.iter().map(|connection| connection.net_name.as_str()).collect();
+                ui.label(nets.len().to_string());
                 ui.end_row();
             });
     }
@@ -346,26 +339,8 @@ impl CircuitViewer {
 
     /// Load a circuit into the viewer
     pub fn load_circuit(&mut self, circuit: Circuit) {
+        self.renderer.set_layout(SchematicLayout::auto_layout(&circuit));
         self.circuit = Some(circuit);
-        
-        // Initialize component positions
-        // TODO: Implement proper layout algorithm
-        if let Some(circuit) = &self.circuit {
-            for (i, component) in circuit.components.iter().enumerate() {
-                let x = 100.0 + (i as f32 % 5) as f32 * 100.0;
-                let y = 100.0 + (i as f32 / 5.0) as f32 * 100.0;
-                
-                use crate::schematic_renderer::ComponentPosition;
-                self.renderer.set_component_position(
-                    component.id.clone(),
-                    ComponentPosition {
-                        position: egui::pos2(x, y),
-                        rotation: 0.0,
-                        mirrored: false,
-                    },
-                );
-            }
-        }
     }
 
     /// Update simulation results
@@ -9,12 +9,17 @@ pub mod primitives;
 pub mod styles;
 pub mod animations;
 
-pub use schematic_renderer::SchematicRenderer;
+pub use schematic_renderer::{ProbeAnnotation, ProbeKind, SchematicAnnotations, SchematicRenderer};
+pub use opencircuit_circuit::SchematicLayout;
 pub use circuit_viewer::CircuitViewer;
 pub use primitives::CircuitPrimitives;
-pub use styles::{CircuitStyle, CircuitStyleConfig, ComponentAppearance, ThemePreset};
+pub use styles::{AccessibilityIssue, CircuitStyle, CircuitStyleConfig, ComponentAppearance, ThemePreset};
 pub use animations::{CircuitAnimations, AnimationConfig};
 
+use egui::{Response, Ui};
+use opencircuit_circuit::Circuit;
+use opencircuit_core::OpenCircuitError;
+
 /// Graphics result type
 pub type GraphicsResult<T> = Result<T, GraphicsError>;
 
@@ -40,6 +45,23 @@ pub enum GraphicsError {
     Style(String),
 }
 
+impl From<GraphicsError> for OpenCircuitError {
+    fn from(error: GraphicsError) -> Self {
+        OpenCircuitError::Graphics(error.to_string())
+    }
+}
+
+impl From<OpenCircuitError> for GraphicsError {
+    fn from(error: OpenCircuitError) -> Self {
+        GraphicsError::Rendering(error.to_string())
+    }
+}
+
+/// Check that a circuit can be drawn: every component needs a unique, non-empty ID
+pub fn validate_circuit(circuit: &Circuit) -> Result<(), OpenCircuitError> {
+    circuit.check_component_ids().map_err(|e| OpenCircuitError::Circuit(e.to_string()))
+}
+
 /// Main graphics library interface
 pub struct OpenCircuitGraphics {
     renderer: SchematicRenderer,
@@ -59,6 +81,19 @@ impl OpenCircuitGraphics {
         }
     }
 
+    /// Render a circuit schematic, rejecting circuits that fail [`validate_circuit`]
+    pub fn render_circuit(&mut self, ui: &mut Ui, circuit: &Circuit) -> GraphicsResult<Response> {
+        validate_circuit(circuit)?;
+        Ok(self.renderer.render(ui, circuit))
+    }
+
+    /// Load a circuit into the viewer after validating it
+    pub fn load_circuit(&mut self, circuit: Circuit) -> GraphicsResult<()> {
+        validate_circuit(&circuit)?;
+        self.viewer.load_circuit(circuit);
+        Ok(())
+    }
+
     /// Update all animations
     pub fn update(&mut self) {
         self.animations.update();
@@ -87,6 +122,7 @@ impl OpenCircuitGraphics {
     /// Set theme preset
     pub fn set_theme(&mut self, theme: ThemePreset) {
         self.style = theme.to_style();
+        self.renderer.set_style(self.style);
     }
 
     /// Configure animation settings
@@ -110,7 +146,6 @@ impl Default for OpenCircuitGraphics {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use opencircuit_core::models::Circuit;
 
     #[test]
     fn test_basic_initialization() {
@@ -136,7 +171,9 @@ mod tests {
     #[test]
     fn test_simulation_color_mapping() {
         let style = CircuitStyle::default();
-        let color = style.get_simulation_color(5.0, 0.0, 10.0);
+        let color = style.get_simulation_color(7.5, 0.0, 10.0);
         assert!(color.r() > color.b()); // Should be more red than blue
+        assert_eq!(style.get_simulation_color(0.0, 0.0, 10.0), style.voltage_low_color);
+        assert_eq!(style.get_simulation_color(10.0, 0.0, 10.0), style.voltage_high_color);
     }
 }
\ No newline at end of file
@@ -19,7 +19,7 @@ impl CircuitPrimitives {
         let body_height = size * 0.3;
         let body_rect = Rect::from_center_size(center, Vec2::new(body_width, body_height));
         
-        painter.rect_filled(body_rect, Rounding::none(), color);
+        painter.rect_filled(body_rect, Rounding::ZERO, color);
         
         // Draw connection lines
         let left_line = [Pos2::new(center.x - size * 0.5, center.y), Pos2::new(center.x - body_width * 0.5, center.y)];
@@ -50,8 +50,8 @@ impl CircuitPrimitives {
             Vec2::new(plate_width, plate_height)
         );
         
-        painter.rect_filled(left_plate, Rounding::none(), color);
-        painter.rect_filled(right_plate, Rounding::none(), color);
+        painter.rect_filled(left_plate, Rounding::ZERO, color);
+        painter.rect_filled(right_plate, Rounding::ZERO, color);
         
         // Draw connection lines
         let left_line = [Pos2::new(center.x - size * 0.5, center.y), Pos2::new(left_plate.left(), center.y)];
@@ -89,7 +89,7 @@ impl CircuitPrimitives {
                 Pos2::new(x + coil_spacing * 0.5, center.y),
                 Vec2::new(coil_spacing, coil_radius * 2.0)
             );
-            painter.rect_stroke(coil_rect, Rounding::none(), Stroke::new(2.0, color));
+            painter.rect_stroke(coil_rect, Rounding::ZERO, Stroke::new(2.0, color));
         }
         
         rect
@@ -193,7 +193,7 @@ impl CircuitPrimitives {
         
         // Draw rectangle for generic component
         let component_rect = Rect::from_center_size(center, Vec2::new(size * 0.8, size * 0.5));
-        painter.rect_stroke(component_rect, Rounding::none(), Stroke::new(2.0, color));
+        painter.rect_stroke(component_rect, Rounding::ZERO, Stroke::new(2.0, color));
         
         // Draw connection lines
         let top_line = [Pos2::new(center.x, center.y - size * 0.5), Pos2::new(center.x, component_rect.top())];
@@ -267,7 +267,7 @@ impl CircuitPrimitives {
         
         painter.rect_stroke(
             *rect,
-            Rounding::none(),
+            Rounding::ZERO,
             Stroke::new(2.0, color),
         );
         
@@ -4,13 +4,19 @@
 //! real-time updates and interactive features.
 
 use egui::{Color32, Pos2, Rect, Response, Sense, Stroke, Ui, Vec2};
-use opencircuit_core::models::Circuit;
-use opencircuit_circuit::components::Component;
+use opencircuit_circuit::{Circuit, Component, ComponentType, SchematicLayout};
+use opencircuit_core::Position;
+use opencircuit_pcb::{ComponentPlacement, Footprint, FootprintLibrary, Layer, PcbDesign};
 use std::collections::HashMap;
 
 use crate::primitives::*;
 use crate::styles::*;
 
+/// Screen points per board millimetre when drawing courtyards
+pub const COURTYARD_POINTS_PER_MM: f32 = 10.0;
+/// Dash and gap length of courtyard outlines (points)
+const COURTYARD_DASH: (f32, f32) = (4.0, 3.0);
+
 /// Main schematic renderer for circuit visualization
 pub struct SchematicRenderer {
     /// Current zoom level (1.0 = 100%)
@@ -21,29 +27,40 @@ pub struct SchematicRenderer {
     grid_size: f32,
     /// Whether to show grid
     show_grid: bool,
-    /// Component positions and orientations
-    component_positions: HashMap<String, ComponentPosition>,
-    /// Wire paths
-    wires: Vec<Wire>,
+    /// Component and wire placement
+    layout: SchematicLayout,
+    /// Renderer-only annotations drawn over the layout
+    annotations: SchematicAnnotations,
+    /// Colors used for probe annotations
+    style: CircuitStyle,
     /// Selection state
     selection: SelectionState,
     /// Animation state for real-time updates
     animation_state: AnimationState,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct ComponentPosition {
-    pub position: Pos2,
-    pub rotation: f32,
-    pub mirrored: bool,
+/// What the renderer draws over a [`SchematicLayout`] but doesn't persist
+#[derive(Debug, Clone, Default)]
+pub struct SchematicAnnotations {
+    /// Drawn after wires and components so they stay on top
+    pub probes: Vec<ProbeAnnotation>,
 }
 
-#[derive(Debug, Clone)]
-pub struct Wire {
-    pub start: Pos2,
-    pub end: Pos2,
-    pub net_name: Option<String>,
-    pub color: Color32,
+/// What a probe measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeKind {
+    Voltage,
+    Current,
+}
+
+/// Measurement probe placed on the schematic
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeAnnotation {
+    /// Point the probe tip touches
+    pub position: Pos2,
+    pub kind: ProbeKind,
+    /// Node for voltage probes, branch for current probes
+    pub signal: String,
 }
 
 #[derive(Debug, Default)]
@@ -68,15 +85,21 @@ impl SchematicRenderer {
             pan: Vec2::ZERO,
             grid_size: 20.0,
             show_grid: true,
-            component_positions: HashMap::new(),
-            wires: Vec::new(),
+            layout: SchematicLayout::default(),
+            annotations: SchematicAnnotations::default(),
+            style: CircuitStyle::default(),
             selection: SelectionState::default(),
             animation_state: AnimationState::default(),
         }
     }
 
-    /// Render the complete schematic
+    /// Render the complete schematic with the renderer's own layout
     pub fn render(&mut self, ui: &mut Ui, circuit: &Circuit) -> Response {
+        self.draw_circuit(ui, circuit, &self.layout)
+    }
+
+    /// Render the complete schematic as placed by `layout`
+    pub fn draw_circuit(&self, ui: &mut Ui, circuit: &Circuit, layout: &SchematicLayout) -> Response {
         let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
         
         // Transform coordinates
@@ -94,18 +117,21 @@ impl SchematicRenderer {
         }
         
         // Draw components
-        self.draw_components(&painter, circuit, &to_screen);
+        self.draw_components(&painter, circuit, layout, &to_screen);
         
         // Draw wires
-        self.draw_wires(&painter, &to_screen);
+        self.draw_wires(&painter, layout, &to_screen);
         
         // Draw selection highlights
-        self.draw_selection_highlights(&painter, &to_screen);
+        self.draw_selection_highlights(&painter, layout, &to_screen);
         
         // Draw simulation results
         if self.animation_state.simulation_active {
-            self.draw_simulation_results(&painter, circuit, &to_screen);
+            self.draw_simulation_results(&painter, layout, &to_screen);
         }
+
+        // Draw probes on top of everything else
+        self.draw_probes(&painter, &to_screen);
         
         response
     }
@@ -113,12 +139,12 @@ impl SchematicRenderer {
     fn draw_background(&self, painter: &egui::Painter, rect: &Rect) {
         painter.rect_filled(
             *rect,
-            egui::Rounding::none(),
+            egui::Rounding::ZERO,
             Color32::from_gray(245),
         );
     }
 
-    fn draw_grid(&self, painter: &egui::Painter, rect: &Rect) {
+    pub(crate) fn draw_grid(&self, painter: &egui::Painter, rect: &Rect) {
         let stroke = Stroke::new(0.5, Color32::from_gray(220));
         
         // Vertical lines
@@ -142,23 +168,29 @@ impl SchematicRenderer {
         }
     }
 
-    fn draw_components(&self, painter: &egui::Painter, circuit: &Circuit, to_screen: &egui::emath::RectTransform) {
+    fn draw_components(
+        &self,
+        painter: &egui::Painter,
+        circuit: &Circuit,
+        layout: &SchematicLayout,
+        to_screen: &egui::emath::RectTransform,
+    ) {
         for component in &circuit.components {
-            if let Some(position) = self.component_positions.get(&component.id) {
-                let screen_pos = to_screen.transform_pos(position.position);
-                self.draw_component(painter, component, screen_pos, position.rotation);
+            if let Some(position) = layout.positions.get(&component.id) {
+                let screen_pos = to_screen.transform_pos(to_pos2(position));
+                let rotation = layout.rotations.get(&component.id).copied().unwrap_or_default() as f32;
+                self.draw_component(painter, component, screen_pos, rotation);
             }
         }
     }
 
     fn draw_component(&self, painter: &egui::Painter, component: &Component, pos: Pos2, rotation: f32) {
-        match component.component_type.as_str() {
-            "resistor" => self.draw_resistor(painter, pos, rotation),
-            "capacitor" => self.draw_capacitor(painter, pos, rotation),
-            "inductor" => self.draw_inductor(painter, pos, rotation),
-            "voltage_source" => self.draw_voltage_source(painter, pos, rotation),
-            "current_source" => self.draw_current_source(painter, pos, rotation),
-            "ground" => self.draw_ground(painter, pos),
+        match component.component_type {
+            ComponentType::Resistor => self.draw_resistor(painter, pos, rotation),
+            ComponentType::Capacitor => self.draw_capacitor(painter, pos, rotation),
+            ComponentType::Inductor => self.draw_inductor(painter, pos, rotation),
+            ComponentType::VoltageSource => self.draw_voltage_source(painter, pos, rotation),
+            ComponentType::CurrentSource => self.draw_current_source(painter, pos, rotation),
             _ => self.draw_generic_component(painter, pos, rotation),
         }
     }
@@ -271,27 +303,12 @@ impl SchematicRenderer {
         painter.arrow(arrow_start, arrow_end - arrow_start, stroke);
     }
 
-    fn draw_ground(&self, painter: &egui::Painter, pos: Pos2) {
-        let stroke = Stroke::new(2.0, Color32::BLACK);
-        
-        // Draw ground symbol
-        let lines = vec![
-            (egui::pos2(pos.x - 10.0, pos.y), egui::pos2(pos.x + 10.0, pos.y)),
-            (egui::pos2(pos.x - 7.0, pos.y + 5.0), egui::pos2(pos.x + 7.0, pos.y + 5.0)),
-            (egui::pos2(pos.x - 4.0, pos.y + 10.0), egui::pos2(pos.x + 4.0, pos.y + 10.0)),
-        ];
-        
-        for (start, end) in lines {
-            painter.line_segment([start, end], stroke);
-        }
-    }
-
     fn draw_generic_component(&self, painter: &egui::Painter, pos: Pos2, rotation: f32) {
         let rect = Rect::from_center_size(pos, egui::vec2(40.0, 20.0));
         let stroke = Stroke::new(2.0, Color32::BLACK);
         
         // Draw rectangle
-        painter.rect_stroke(rect, egui::Rounding::none(), stroke);
+        painter.rect_stroke(rect, egui::Rounding::ZERO, stroke);
         
         // Draw question mark
         painter.text(
@@ -303,21 +320,24 @@ impl SchematicRenderer {
         );
     }
 
-    fn draw_wires(&self, painter: &egui::Painter, to_screen: &egui::emath::RectTransform) {
-        for wire in &self.wires {
-            let start = to_screen.transform_pos(wire.start);
-            let end = to_screen.transform_pos(wire.end);
-            
-            let stroke = Stroke::new(2.0, wire.color);
-            painter.line_segment([start, end], stroke);
+    fn draw_wires(&self, painter: &egui::Painter, layout: &SchematicLayout, to_screen: &egui::emath::RectTransform) {
+        let stroke = Stroke::new(2.0, self.style.wire_color);
+        for route in layout.wire_routes.values() {
+            let points: Vec<Pos2> = route.iter().map(|point| to_screen.transform_pos(to_pos2(point))).collect();
+            painter.add(egui::Shape::line(points, stroke));
         }
     }
 
-    fn draw_selection_highlights(&self, painter: &egui::Painter, to_screen: &egui::emath::RectTransform) {
+    fn draw_selection_highlights(
+        &self,
+        painter: &egui::Painter,
+        layout: &SchematicLayout,
+        to_screen: &egui::emath::RectTransform,
+    ) {
         // Draw selection boxes
         for component_id in &self.selection.selected_components {
-            if let Some(position) = self.component_positions.get(component_id) {
-                let screen_pos = to_screen.transform_pos(position.position);
+            if let Some(position) = layout.positions.get(component_id) {
+                let screen_pos = to_screen.transform_pos(to_pos2(position));
                 let rect = Rect::from_center_size(screen_pos, egui::vec2(70.0, 40.0));
                 
                 painter.rect_stroke(
@@ -329,11 +349,16 @@ impl SchematicRenderer {
         }
     }
 
-    fn draw_simulation_results(&self, painter: &egui::Painter, circuit: &Circuit, to_screen: &egui::emath::RectTransform) {
+    fn draw_simulation_results(
+        &self,
+        painter: &egui::Painter,
+        layout: &SchematicLayout,
+        to_screen: &egui::emath::RectTransform,
+    ) {
         // Draw voltage indicators
         for (node_id, voltage) in &self.animation_state.voltages {
-            if let Some(position) = self.component_positions.get(node_id) {
-                let screen_pos = to_screen.transform_pos(position.position);
+            if let Some(position) = layout.positions.get(node_id) {
+                let screen_pos = to_screen.transform_pos(to_pos2(position));
                 
                 let color = if *voltage > 0.0 {
                     Color32::from_rgb(0, 200, 0)
@@ -352,6 +377,109 @@ impl SchematicRenderer {
         }
     }
 
+    fn draw_probes(&self, painter: &egui::Painter, to_screen: &egui::emath::RectTransform) {
+        for probe in &self.annotations.probes {
+            let pos = to_screen.transform_pos(probe.position);
+            match probe.kind {
+                ProbeKind::Voltage => {
+                    let voltage = self.animation_state.voltages.get(&probe.signal).copied().unwrap_or(f64::NAN);
+                    Self::draw_voltage_probe(painter, pos, voltage, &self.style);
+                }
+                ProbeKind::Current => {
+                    let current = self.animation_state.currents.get(&probe.signal).copied().unwrap_or(f64::NAN);
+                    Self::draw_current_probe(painter, pos, current, &self.style);
+                }
+            }
+        }
+    }
+
+    /// Draw a voltmeter icon touching `pos`, labelled with the voltage
+    pub fn draw_voltage_probe(painter: &egui::Painter, pos: Pos2, voltage: f64, style: &CircuitStyle) {
+        Self::draw_meter(painter, pos, "V", &format_probe_value(voltage, "V"), style);
+    }
+
+    /// Draw an ammeter icon touching `pos`, labelled with the current
+    pub fn draw_current_probe(painter: &egui::Painter, pos: Pos2, current: f64, style: &CircuitStyle) {
+        Self::draw_meter(painter, pos, "A", &format_probe_value(current, "A"), style);
+    }
+
+    fn draw_meter(painter: &egui::Painter, pos: Pos2, symbol: &str, value: &str, style: &CircuitStyle) {
+        let radius = 9.0;
+        let center = pos + egui::vec2(0.0, -(radius + 8.0));
+        let stroke = Stroke::new(1.5, style.probe_color);
+
+        // Lead from the meter down to the probed point
+        painter.line_segment([center + egui::vec2(0.0, radius), pos], stroke);
+        painter.circle(pos, 2.0, style.probe_color, Stroke::NONE);
+        painter.circle(center, radius, style.background_color, stroke);
+        painter.text(
+            center,
+            egui::Align2::CENTER_CENTER,
+            symbol,
+            egui::FontId::proportional(radius * 1.2),
+            style.probe_color,
+        );
+        painter.text(
+            center + egui::vec2(0.0, -(radius + 2.0)),
+            egui::Align2::CENTER_BOTTOM,
+            value,
+            egui::FontId::monospace(style.font_size),
+            style.text_color,
+        );
+    }
+
+    /// Draw a component's courtyard as a dashed outline in its layer color
+    ///
+    /// Board millimetres are scaled by [`COURTYARD_POINTS_PER_MM`] from the
+    /// top-left of the painter's clip rect.
+    pub fn draw_courtyard(painter: &egui::Painter, component: &ComponentPlacement, footprint: &Footprint, style: &CircuitStyle) {
+        let mut outline = courtyard_outline(painter, component, footprint);
+        let Some(first) = outline.first().copied() else {
+            return;
+        };
+        outline.push(first);
+        let color = match component.layer {
+            Layer::Bottom => style.courtyard_bottom_color,
+            Layer::Top | Layer::Inner(_) => style.courtyard_top_color,
+        };
+        let (dash, gap) = COURTYARD_DASH;
+        painter.extend(egui::Shape::dashed_line(&outline, Stroke::new(1.0, color), dash, gap));
+    }
+
+    /// Draw every placed courtyard, filling overlaps between parts on the
+    /// same side of the board in the overlap color
+    ///
+    /// Overlaps are found from the courtyards' bounding boxes. Placements
+    /// without a footprint in `footprint_library` are skipped.
+    pub fn draw_all_courtyards(
+        &self,
+        painter: &egui::Painter,
+        pcb: &PcbDesign,
+        footprint_library: &FootprintLibrary,
+        style: &CircuitStyle,
+    ) {
+        let courtyards: Vec<(&ComponentPlacement, Rect)> = pcb
+            .placements
+            .iter()
+            .filter_map(|placement| {
+                let footprint = footprint_library.get(placement.footprint.as_deref()?)?;
+                Self::draw_courtyard(painter, placement, footprint, style);
+                let outline = courtyard_outline(painter, placement, footprint);
+                (!outline.is_empty()).then(|| (placement, Rect::from_points(&outline)))
+            })
+            .collect();
+
+        let fill = style.courtyard_overlap_color.gamma_multiply(0.4);
+        for (index, (a, a_bounds)) in courtyards.iter().enumerate() {
+            for (b, b_bounds) in &courtyards[index + 1..] {
+                let overlap = a_bounds.intersect(*b_bounds);
+                if a.layer == b.layer && overlap.is_positive() {
+                    painter.rect_filled(overlap, egui::Rounding::ZERO, fill);
+                }
+            }
+        }
+    }
+
     /// Update simulation results
     pub fn update_simulation_results(&mut self, voltages: HashMap<String, f64>, currents: HashMap<String, f64>) {
         self.animation_state.voltages = voltages;
@@ -364,20 +492,180 @@ impl SchematicRenderer {
         self.animation_state.simulation_active = !self.animation_state.simulation_active;
     }
 
+    /// Replace the layout drawn by [`Self::render`]
+    pub fn set_layout(&mut self, layout: SchematicLayout) {
+        self.layout = layout;
+    }
+
     /// Set component position
-    pub fn set_component_position(&mut self, component_id: String, position: ComponentPosition) {
-        self.component_positions.insert(component_id, position);
+    pub fn set_component_position(&mut self, component_id: String, position: Position) {
+        self.layout.positions.insert(component_id, position);
+    }
+
+    /// Add a measurement probe
+    pub fn add_probe(&mut self, probe: ProbeAnnotation) {
+        self.annotations.probes.push(probe);
+    }
+
+    /// Current schematic layout
+    pub fn layout(&self) -> &SchematicLayout {
+        &self.layout
     }
 
-    /// Add wire
-    pub fn add_wire(&mut self, wire: Wire) {
-        self.wires.push(wire);
+    /// Probes and other annotations drawn over the layout
+    pub fn annotations(&self) -> &SchematicAnnotations {
+        &self.annotations
     }
 
-    /// Clear all components and wires
+    /// Set the style used for probe annotations
+    pub fn set_style(&mut self, style: CircuitStyle) {
+        self.style = style;
+    }
+
+    /// Clear all components, wires and probes
     pub fn clear(&mut self) {
-        self.component_positions.clear();
-        self.wires.clear();
+        self.layout = SchematicLayout::default();
+        self.annotations = SchematicAnnotations::default();
         self.selection = SelectionState::default();
     }
-}
\ No newline at end of file
+}
+
+/// Canvas position as an egui point
+fn to_pos2(position: &Position) -> Pos2 {
+    Pos2::new(position.x as f32, position.y as f32)
+}
+
+/// Courtyard polygon of a placed footprint in screen coordinates
+fn courtyard_outline(painter: &egui::Painter, component: &ComponentPlacement, footprint: &Footprint) -> Vec<Pos2> {
+    let origin = painter.clip_rect().min;
+    footprint
+        .courtyard
+        .iter()
+        .map(|&point| {
+            let (x, y) = component.to_board(point);
+            origin + egui::vec2(x as f32, y as f32) * COURTYARD_POINTS_PER_MM
+        })
+        .collect()
+}
+
+/// Probe reading with an SI prefix, e.g. `12.50 mA`
+fn format_probe_value(value: f64, unit: &str) -> String {
+    if !value.is_finite() {
+        return format!("-- {}", unit);
+    }
+    let magnitude = value.abs();
+    let (scale, prefix) = [(1e3, "k"), (1.0, ""), (1e-3, "m"), (1e-6, "µ"), (1e-9, "n")]
+        .into_iter()
+        .find(|(scale, _)| magnitude >= *scale)
+        .unwrap_or(if magnitude == 0.0 { (1.0, "") } else { (1e-12, "p") });
+    format!("{:.2} {}{}", value / scale, prefix, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn painted_text(shapes: &[egui::epaint::ClippedShape]) -> Vec<String> {
+        shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                egui::Shape::Text(text) => Some(text.galley.text().to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_probe_value_formatting() {
+        assert_eq!(format_probe_value(3.3, "V"), "3.30 V");
+        assert_eq!(format_probe_value(-0.0125, "A"), "-12.50 mA");
+        assert_eq!(format_probe_value(4.7e-6, "A"), "4.70 µA");
+        assert_eq!(format_probe_value(0.0, "V"), "0.00 V");
+        assert_eq!(format_probe_value(f64::NAN, "V"), "-- V");
+    }
+
+    #[test]
+    fn test_probes_drawn_after_wires() {
+        let mut renderer = SchematicRenderer::new();
+        let mut layout = SchematicLayout::default();
+        layout
+            .wire_routes
+            .insert("out:R1-C1".to_string(), vec![Position::new(0.0, 50.0), Position::new(100.0, 50.0)]);
+        renderer.set_layout(layout);
+        renderer.add_probe(ProbeAnnotation {
+            position: Pos2::new(50.0, 50.0),
+            kind: ProbeKind::Voltage,
+            signal: "out".to_string(),
+        });
+        renderer.add_probe(ProbeAnnotation {
+            position: Pos2::new(80.0, 50.0),
+            kind: ProbeKind::Current,
+            signal: "r1".to_string(),
+        });
+        renderer.update_simulation_results(
+            HashMap::from([("out".to_string(), 2.5)]),
+            HashMap::from([("r1".to_string(), 2.5e-4)]),
+        );
+
+        let ctx = egui::Context::default();
+        let output = ctx.run(egui::RawInput::default(), |ctx| {
+            let painter = ctx.layer_painter(egui::LayerId::background());
+            let rect = Rect::from_min_size(Pos2::ZERO, egui::vec2(200.0, 100.0));
+            let to_screen = egui::emath::RectTransform::identity(rect);
+            renderer.draw_wires(&painter, renderer.layout(), &to_screen);
+            renderer.draw_probes(&painter, &to_screen);
+        });
+
+        assert!(matches!(output.shapes[0].shape, egui::Shape::Path(_)));
+        assert_eq!(painted_text(&output.shapes), ["V", "2.50 V", "A", "250.00 µA"]);
+    }
+
+    #[test]
+    fn test_overlapping_courtyards_highlighted() {
+        let footprint = Footprint {
+            pads: Vec::new(),
+            courtyard: vec![(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)],
+        };
+        let library = FootprintLibrary::from([("0805".to_string(), footprint)]);
+        let mut pcb = PcbDesign::new(50.0, 50.0, 2);
+        for (id, x, layer) in [("R1", 5.0, Layer::Top), ("R2", 6.5, Layer::Top), ("R3", 6.5, Layer::Bottom)] {
+            pcb.add_placement(ComponentPlacement {
+                component_id: id.to_string(),
+                x,
+                y: 5.0,
+                rotation: 0.0,
+                layer,
+                footprint: Some("0805".to_string()),
+            });
+        }
+
+        let renderer = SchematicRenderer::new();
+        let style = CircuitStyle::default();
+        let ctx = egui::Context::default();
+        let output = ctx.run(egui::RawInput::default(), |ctx| {
+            let painter = ctx.layer_painter(egui::LayerId::background());
+            renderer.draw_all_courtyards(&painter, &pcb, &library, &style);
+        });
+
+        let fills: Vec<Rect> = output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                egui::Shape::Rect(rect) => Some(rect.rect),
+                _ => None,
+            })
+            .collect();
+        // Only R1 and R2 share a side; they overlap between x = 5.5 mm and 6 mm
+        assert_eq!(fills, [Rect::from_min_max(Pos2::new(55.0, 40.0), Pos2::new(60.0, 60.0))]);
+        let outline_colors: Vec<Color32> = output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                egui::Shape::LineSegment { stroke, .. } => Some(stroke.color),
+                _ => None,
+            })
+            .collect();
+        assert!(outline_colors.contains(&style.courtyard_top_color));
+        assert!(outline_colors.contains(&style.courtyard_bottom_color));
+    }
+}
@@ -2,6 +2,10 @@
 //! 
 //! Defines colors, fonts, and visual appearance for circuit components,
 //! wires, and UI elements used in the schematic renderer.
+//!
+//! [`CircuitStyle::accessibility_check`] measures contrast with the WCAG 2.1
+//! formula; every drawn color is checked against the background, and the
+//! two ends of the simulation overlay against each other.
 
 use egui::{Color32, FontId, Stroke, Style, Visuals};
 
@@ -36,6 +40,16 @@ pub struct CircuitStyle {
     pub junction_color: Color32,
     /// Color for measurement probes
     pub probe_color: Color32,
+    /// Simulation overlay color at the lowest value
+    pub voltage_low_color: Color32,
+    /// Simulation overlay color at the highest value
+    pub voltage_high_color: Color32,
+    /// Courtyard outline color for top-side components
+    pub courtyard_top_color: Color32,
+    /// Courtyard outline color for bottom-side components
+    pub courtyard_bottom_color: Color32,
+    /// Fill color for overlapping courtyards
+    pub courtyard_overlap_color: Color32,
     /// Default font size
     pub font_size: f32,
     /// Wire thickness
@@ -65,6 +79,11 @@ impl Default for CircuitStyle {
             background_color: Color32::from_rgb(250, 250, 250),
             junction_color: Color32::from_rgb(0, 0, 0),
             probe_color: Color32::from_rgb(255, 0, 0),
+            voltage_low_color: Color32::from_rgb(0, 0, 255),
+            voltage_high_color: Color32::from_rgb(255, 0, 0),
+            courtyard_top_color: Color32::from_rgb(160, 0, 160),
+            courtyard_bottom_color: Color32::from_rgb(0, 100, 200),
+            courtyard_overlap_color: Color32::from_rgb(255, 0, 0),
             font_size: 12.0,
             wire_thickness: 2.0,
             grid_spacing: 20.0,
@@ -92,6 +111,8 @@ impl CircuitStyle {
             background_color: Color32::from_rgb(30, 30, 30),
             junction_color: Color32::from_rgb(255, 255, 255),
             probe_color: Color32::from_rgb(255, 100, 100),
+            courtyard_top_color: Color32::from_rgb(230, 100, 230),
+            courtyard_bottom_color: Color32::from_rgb(100, 170, 255),
             ..Self::default()
         }
     }
@@ -113,6 +134,8 @@ impl CircuitStyle {
             background_color: Color32::from_rgb(0, 0, 0),
             junction_color: Color32::from_rgb(255, 255, 255),
             probe_color: Color32::from_rgb(0, 255, 255),
+            courtyard_top_color: Color32::from_rgb(255, 0, 255),
+            courtyard_bottom_color: Color32::from_rgb(0, 255, 255),
             wire_thickness: 3.0,
             ..Self::default()
         }
@@ -139,6 +162,71 @@ impl CircuitStyle {
         }
     }
 
+    /// Colorblind-safe theme using the Okabe-Ito palette on a black background
+    ///
+    /// Unlike [`Self::colorblind_friendly`], every color also passes
+    /// [`Self::accessibility_check`].
+    pub fn colorblind_safe() -> Self {
+        Self {
+            resistor_color: Color32::from_rgb(86, 180, 233),
+            capacitor_color: Color32::from_rgb(230, 159, 0),
+            inductor_color: Color32::from_rgb(0, 158, 115),
+            voltage_source_color: Color32::from_rgb(240, 228, 66),
+            current_source_color: Color32::from_rgb(204, 121, 167),
+            ground_color: Color32::from_rgb(153, 153, 153),
+            wire_color: Color32::from_rgb(255, 255, 255),
+            selection_color: Color32::from_rgb(213, 94, 0),
+            highlight_color: Color32::from_rgb(240, 228, 66),
+            grid_color: Color32::from_rgb(60, 60, 60),
+            text_color: Color32::from_rgb(255, 255, 255),
+            background_color: Color32::from_rgb(0, 0, 0),
+            junction_color: Color32::from_rgb(255, 255, 255),
+            probe_color: Color32::from_rgb(86, 180, 233),
+            voltage_low_color: Color32::from_rgb(0, 114, 178),
+            voltage_high_color: Color32::from_rgb(240, 228, 66),
+            courtyard_top_color: Color32::from_rgb(204, 121, 167),
+            courtyard_bottom_color: Color32::from_rgb(86, 180, 233),
+            courtyard_overlap_color: Color32::from_rgb(213, 94, 0),
+            ..Self::default()
+        }
+    }
+
+    /// Color pairs that are drawn next to each other with a contrast below 3:1
+    ///
+    /// The grid is excluded since it is meant to stay in the background.
+    pub fn accessibility_check(&self) -> Vec<AccessibilityIssue> {
+        let foregrounds = [
+            ("resistor", self.resistor_color),
+            ("capacitor", self.capacitor_color),
+            ("inductor", self.inductor_color),
+            ("voltage source", self.voltage_source_color),
+            ("current source", self.current_source_color),
+            ("ground", self.ground_color),
+            ("wire", self.wire_color),
+            ("selection", self.selection_color),
+            ("highlight", self.highlight_color),
+            ("text", self.text_color),
+            ("junction", self.junction_color),
+            ("probe", self.probe_color),
+            ("voltage low", self.voltage_low_color),
+            ("voltage high", self.voltage_high_color),
+            ("courtyard top", self.courtyard_top_color),
+            ("courtyard bottom", self.courtyard_bottom_color),
+            ("courtyard overlap", self.courtyard_overlap_color),
+        ];
+        let pairs = foregrounds
+            .into_iter()
+            .map(|foreground| (foreground, ("background", self.background_color)))
+            .chain([(("voltage high", self.voltage_high_color), ("voltage low", self.voltage_low_color))]);
+
+        pairs
+            .filter_map(|((foreground, a), (background, b))| {
+                let ratio = contrast_ratio(a, b);
+                (ratio < MIN_CONTRAST_RATIO).then_some(AccessibilityIssue::LowContrast { foreground, background, ratio })
+            })
+            .collect()
+    }
+
     /// Get font ID for labels
     pub fn font_id(&self) -> FontId {
         FontId::proportional(self.font_size)
@@ -169,7 +257,7 @@ impl CircuitStyle {
         let mut style = Style::default();
         style.visuals = Visuals::light();
         style.visuals.extreme_bg_color = self.background_color;
-        style.visuals.text_color = self.text_color;
+        style.visuals.override_text_color = Some(self.text_color);
         style
     }
 
@@ -178,7 +266,7 @@ impl CircuitStyle {
         let mut style = Style::default();
         style.visuals = Visuals::dark();
         style.visuals.extreme_bg_color = self.background_color;
-        style.visuals.text_color = self.text_color;
+        style.visuals.override_text_color = Some(self.text_color);
         style
     }
 
@@ -196,15 +284,14 @@ impl CircuitStyle {
     }
 
     /// Get simulation color based on value
+    ///
+    /// Blends from `voltage_low_color` at `min` to `voltage_high_color` at `max`.
     pub fn get_simulation_color(&self, value: f64, min: f64, max: f64) -> Color32 {
         let normalized = ((value - min) / (max - min)).clamp(0.0, 1.0);
+        let blend = |low: u8, high: u8| (low as f64 + (high as f64 - low as f64) * normalized).round() as u8;
+        let (low, high) = (self.voltage_low_color, self.voltage_high_color);
         
-        // Red for high values, green for medium, blue for low
-        let r = (normalized * 255.0) as u8;
-        let g = ((1.0 - (normalized - 0.5).abs() * 2.0) * 255.0) as u8;
-        let b = ((1.0 - normalized) * 255.0) as u8;
-        
-        Color32::from_rgb(r, g, b)
+        Color32::from_rgb(blend(low.r(), high.r()), blend(low.g(), high.g()), blend(low.b(), high.b()))
     }
 
     /// Update style from configuration
@@ -223,6 +310,11 @@ impl CircuitStyle {
         self.background_color = config.background_color.unwrap_or(self.background_color);
         self.junction_color = config.junction_color.unwrap_or(self.junction_color);
         self.probe_color = config.probe_color.unwrap_or(self.probe_color);
+        self.voltage_low_color = config.voltage_low_color.unwrap_or(self.voltage_low_color);
+        self.voltage_high_color = config.voltage_high_color.unwrap_or(self.voltage_high_color);
+        self.courtyard_top_color = config.courtyard_top_color.unwrap_or(self.courtyard_top_color);
+        self.courtyard_bottom_color = config.courtyard_bottom_color.unwrap_or(self.courtyard_bottom_color);
+        self.courtyard_overlap_color = config.courtyard_overlap_color.unwrap_or(self.courtyard_overlap_color);
         self.font_size = config.font_size.unwrap_or(self.font_size);
         self.wire_thickness = config.wire_thickness.unwrap_or(self.wire_thickness);
         self.grid_spacing = config.grid_spacing.unwrap_or(self.grid_spacing);
@@ -231,6 +323,39 @@ impl CircuitStyle {
     }
 }
 
+/// WCAG 2.1 minimum contrast for graphical objects
+pub const MIN_CONTRAST_RATIO: f32 = 3.0;
+
+/// Problem found by [`CircuitStyle::accessibility_check`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessibilityIssue {
+    /// Two adjacent colors are too close in luminance to tell apart
+    LowContrast {
+        foreground: &'static str,
+        background: &'static str,
+        ratio: f32,
+    },
+}
+
+/// WCAG 2.1 relative luminance of an opaque color, from 0.0 to 1.0
+pub fn relative_luminance(color: Color32) -> f32 {
+    let channel = |value: u8| {
+        let c = value as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(color.r()) + 0.7152 * channel(color.g()) + 0.0722 * channel(color.b())
+}
+
+/// WCAG 2.1 contrast ratio, from 1.0 for identical colors to 21.0 for black on white
+pub fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
 /// Configuration structure for updating circuit style
 #[derive(Debug, Clone, Default)]
 pub struct CircuitStyleConfig {
@@ -248,6 +373,11 @@ pub struct CircuitStyleConfig {
     pub background_color: Option<Color32>,
     pub junction_color: Option<Color32>,
     pub probe_color: Option<Color32>,
+    pub voltage_low_color: Option<Color32>,
+    pub voltage_high_color: Option<Color32>,
+    pub courtyard_top_color: Option<Color32>,
+    pub courtyard_bottom_color: Option<Color32>,
+    pub courtyard_overlap_color: Option<Color32>,
     pub font_size: Option<f32>,
     pub wire_thickness: Option<f32>,
     pub grid_spacing: Option<f32>,
@@ -262,6 +392,7 @@ pub enum ThemePreset {
     Dark,
     HighContrast,
     ColorblindFriendly,
+    ColorblindSafe,
 }
 
 impl ThemePreset {
@@ -271,6 +402,7 @@ impl ThemePreset {
             ThemePreset::Dark => CircuitStyle::dark_theme(),
             ThemePreset::HighContrast => CircuitStyle::high_contrast(),
             ThemePreset::ColorblindFriendly => CircuitStyle::colorblind_friendly(),
+            ThemePreset::ColorblindSafe => CircuitStyle::colorblind_safe(),
         }
     }
 }
@@ -309,4 +441,31 @@ impl ComponentAppearance {
             ..Self::default()
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio(Color32::BLACK, Color32::WHITE) - 21.0).abs() < 1e-3);
+        assert!((contrast_ratio(Color32::from_rgb(0, 114, 178), Color32::BLACK) - 4.05).abs() < 0.01);
+        assert_eq!(contrast_ratio(Color32::RED, Color32::RED), 1.0);
+    }
+
+    #[test]
+    fn test_accessibility_check() {
+        let issues = CircuitStyle::default().accessibility_check();
+        assert!(issues.iter().any(|issue| matches!(
+            issue,
+            AccessibilityIssue::LowContrast { foreground: "highlight", background: "background", .. }
+        )));
+        assert!(issues.iter().any(|issue| matches!(
+            issue,
+            AccessibilityIssue::LowContrast { foreground: "voltage high", background: "voltage low", ratio } if *ratio < 3.0
+        )));
+
+        assert_eq!(CircuitStyle::colorblind_safe().accessibility_check(), Vec::new());
+    }
+}
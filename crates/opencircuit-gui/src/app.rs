@@ -13,6 +13,7 @@ use chrono::Utc;
 
 use opencircuit_ai::{AiService, ChatHandler};
 use opencircuit_ai::chat_handler::ChatMessage;
+use crate::autosave::AutoSaveManager;
 use crate::{AppState, OpenCircuitResult};
 
 /// Console-based application for OpenCircuit
@@ -27,9 +28,15 @@ impl ConsoleApp {
     pub async fn new() -> OpenCircuitResult<Self> {
         let ai_service = AiService::new().await?;
         let chat_handler = ChatHandler::new();
-        
+        let config = opencircuit_core::load_config().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load configuration, using defaults: {}", e);
+            opencircuit_core::AppConfig::default()
+        });
+        let mut state = AppState::default();
+        state.set_auto_save(AutoSaveManager::new(config.auto_save_config));
+
         Ok(Self {
-            state: AppState::default(),
+            state,
             ai_service,
             chat_handler,
         })
@@ -38,6 +45,7 @@ impl ConsoleApp {
     pub async fn run(&mut self) -> OpenCircuitResult<()> {
         println!("🔌 Welcome to OpenCircuit - Electronic Design Assistant");
         println!("Type 'help' for commands or 'quit' to exit\n");
+        self.offer_recovery();
 
         loop {
             self.display_menu();
@@ -62,6 +70,21 @@ impl ConsoleApp {
                     print!("\x1B[2J\x1B[1;1H"); // Clear screen
                     io::stdout().flush().unwrap();
                 }
+                "save" => match self.state.save_project() {
+                    Ok(()) => println!("💾 Project saved"),
+                    Err(e) => println!("❌ {:#}", e),
+                },
+                "close" | "close!" => {
+                    if input == "close!" {
+                        self.state.discard_project();
+                    }
+                    if self.state.close_project() {
+                        println!("Project closed");
+                    } else {
+                        println!("⚠️ Unsaved changes. Type 'save' first or 'close!' to discard them.");
+                    }
+                }
+                _ if input.starts_with("new ") || input.starts_with("open ") => self.project_command(input),
                 _ => println!("Unknown command. Type 'help' for available commands."),
             }
         }
@@ -132,6 +155,7 @@ impl ConsoleApp {
                     };
                     self.state.chat_messages.push(ai_message.clone());
                     self.chat_handler.add_message(ai_message);
+                    self.state.conversation_summary = self.chat_handler.conversation_summary().map(str::to_string);
                 }
                 Err(e) => {
                     println!("❌ Error: {}", e);
@@ -149,10 +173,51 @@ impl ConsoleApp {
         println!("1 or 'chat'     - Start AI chat session");
         println!("2 or 'circuit'  - View circuit visualization");
         println!("3 or 'research' - Open research console");
+        println!("'new <name>'    - Start a new project");
+        println!("'open <path>'   - Open a project file");
+        println!("'save'          - Save the open project");
+        println!("'close'         - Close the project ('close!' discards changes)");
         println!("'clear'         - Clear the screen");
         println!("'quit' or 'exit' - Exit the application");
     }
 
+    /// Offer to reopen an auto-save backup left by a session that didn't save
+    fn offer_recovery(&mut self) {
+        let Some(backup) = self.state.recovery_file() else { return };
+        print!("♻️ Found unsaved work from a previous session ({}). Recover it? [y/N] ", backup.display());
+        io::stdout().flush().unwrap();
+
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer).unwrap();
+        if answer.trim().eq_ignore_ascii_case("y") {
+            match self.state.recover_project(&backup) {
+                Ok(()) => println!("📁 Recovered project. Type 'save' to keep it."),
+                Err(e) => println!("❌ {:#}", e),
+            }
+        } else if let Err(e) = self.state.dismiss_recovery() {
+            println!("❌ {:#}", e);
+        }
+    }
+
+    /// Handle `new <name>` and `open <path>`, keeping unsaved work open
+    fn project_command(&mut self, input: &str) {
+        if self.state.is_dirty() {
+            println!("⚠️ Unsaved changes. Type 'save' or 'close!' first.");
+            return;
+        }
+        match input.split_once(' ') {
+            Some(("new", name)) => {
+                let project = self.state.new_project(name.trim());
+                println!("🆕 Created project {}", project.metadata.name);
+            }
+            Some(("open", path)) => match self.state.open_project(std::path::Path::new(path.trim())) {
+                Ok(()) => println!("📁 Opened {}", path.trim()),
+                Err(e) => println!("❌ {:#}", e),
+            },
+            _ => {}
+        }
+    }
+
     fn circuit_visualization(&self) {
         println!("\n🔧 Circuit Visualization");
         println!("This feature is coming soon! It will include:");
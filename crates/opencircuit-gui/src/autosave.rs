@@ -0,0 +1,265 @@
+//! Periodic auto-save and crash recovery
+//!
+//! While the open project has unsaved changes, a timestamped backup is
+//! written into the configured backup directory every `interval_seconds`,
+//! keeping at most `max_backups` of them around. Backups newer than the last
+//! explicit save are offered for recovery on the next start.
+
+use crate::project::ProjectFile;
+use anyhow::{Context, Result};
+use opencircuit_core::AutoSaveConfig;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+const BACKUP_PREFIX: &str = "autosave-";
+const BACKUP_EXTENSION: &str = "ocp";
+const LAST_SAVE_MARKER: &str = ".last_save";
+
+/// Project state waiting for the next periodic backup
+type PendingBackup = Arc<Mutex<Option<ProjectFile>>>;
+
+/// Background auto-save manager
+///
+/// Hand it unsaved changes with [`AutoSaveManager::queue`]; the interval task
+/// backs up the latest one on its next tick.
+#[derive(Debug)]
+pub struct AutoSaveManager {
+    config: AutoSaveConfig,
+    pending: PendingBackup,
+    task: Option<JoinHandle<()>>,
+}
+
+impl AutoSaveManager {
+    /// Create a manager and, if enabled, start saving every `interval_seconds`
+    ///
+    /// The interval task runs on the current Tokio runtime; without one only
+    /// [`AutoSaveManager::save_now`] writes backups.
+    pub fn new(config: AutoSaveConfig) -> Self {
+        let pending = PendingBackup::default();
+        let task = if !config.enabled {
+            info!("Auto-save disabled");
+            None
+        } else {
+            match tokio::runtime::Handle::try_current() {
+                Ok(runtime) => Some(runtime.spawn(Self::run(config.clone(), pending.clone()))),
+                Err(_) => {
+                    warn!("No Tokio runtime, periodic auto-save is off");
+                    None
+                }
+            }
+        };
+
+        Self { config, pending, task }
+    }
+
+    pub fn config(&self) -> &AutoSaveConfig {
+        &self.config
+    }
+
+    async fn run(config: AutoSaveConfig, pending: PendingBackup) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(config.interval_seconds.max(1)));
+        // The first tick completes immediately; skip it so we don't save on startup
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            let snapshot = pending.lock().unwrap().take();
+            let Some(snapshot) = snapshot else { continue };
+            let config = config.clone();
+            let result = tokio::task::spawn_blocking(move || Self::write_backup(&config, &snapshot)).await;
+
+            match result {
+                Ok(Ok(path)) => info!("Auto-saved project to {}", path.display()),
+                Ok(Err(e)) => warn!("Auto-save failed: {}", e),
+                Err(e) => warn!("Auto-save task failed: {}", e),
+            }
+        }
+    }
+
+    /// Back up `project` on the next tick, replacing any earlier unsaved state
+    pub fn queue(&self, project: &ProjectFile) {
+        if self.is_running() {
+            *self.pending.lock().unwrap() = Some(project.clone());
+        }
+    }
+
+    /// Stop the background auto-save task
+    pub fn stop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.as_ref().is_some_and(|task| !task.is_finished())
+    }
+
+    /// Write a backup immediately and prune old ones
+    pub fn save_now(&self, project: &ProjectFile) -> Result<PathBuf> {
+        Self::write_backup(&self.config, project)
+    }
+
+    /// Record that the user explicitly saved the project
+    ///
+    /// Drops any queued backup, and backups written before now are no longer
+    /// offered for recovery.
+    pub fn record_explicit_save(&self) -> Result<()> {
+        self.pending.lock().unwrap().take();
+        fs::create_dir_all(&self.config.backup_dir)?;
+        fs::write(self.config.backup_dir.join(LAST_SAVE_MARKER), chrono::Utc::now().to_rfc3339())?;
+        Ok(())
+    }
+
+    /// Find the newest backup written after the last explicit save, if any
+    pub fn find_recovery_file(&self) -> Option<PathBuf> {
+        let last_save = fs::metadata(self.config.backup_dir.join(LAST_SAVE_MARKER))
+            .and_then(|meta| meta.modified())
+            .ok();
+
+        Self::list_backups(&self.config.backup_dir)
+            .ok()?
+            .into_iter()
+            .rev()
+            .find(|path| match last_save {
+                Some(last_save) => modified_time(path).is_some_and(|time| time > last_save),
+                None => true,
+            })
+    }
+
+    /// Delete backups beyond `max_backups`, oldest first
+    pub fn cleanup_old_backups(&self) -> Result<usize> {
+        Self::prune_backups(&self.config)
+    }
+
+    fn write_backup(config: &AutoSaveConfig, project: &ProjectFile) -> Result<PathBuf> {
+        fs::create_dir_all(&config.backup_dir)?;
+
+        let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S%.3f");
+        let path = config
+            .backup_dir
+            .join(format!("{}{}.{}", BACKUP_PREFIX, timestamp, BACKUP_EXTENSION));
+        project.save(&path)?;
+
+        Self::prune_backups(config)?;
+        Ok(path)
+    }
+
+    fn prune_backups(config: &AutoSaveConfig) -> Result<usize> {
+        let backups = Self::list_backups(&config.backup_dir)?;
+        let excess = backups.len().saturating_sub(config.max_backups as usize);
+
+        for path in &backups[..excess] {
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to remove old backup {}", path.display()))?;
+        }
+
+        Ok(excess)
+    }
+
+    /// List backups sorted oldest to newest
+    fn list_backups(backup_dir: &Path) -> Result<Vec<PathBuf>> {
+        if !backup_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups: Vec<PathBuf> = fs::read_dir(backup_dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+                name.starts_with(BACKUP_PREFIX)
+                    && path.extension().and_then(|e| e.to_str()) == Some(BACKUP_EXTENSION)
+            })
+            .collect();
+
+        // Timestamps in the file name sort chronologically
+        backups.sort();
+        Ok(backups)
+    }
+}
+
+impl Drop for AutoSaveManager {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_core::Project;
+
+    fn test_config(dir: &Path, max_backups: u32) -> AutoSaveConfig {
+        AutoSaveConfig {
+            enabled: true,
+            interval_seconds: 1,
+            backup_dir: dir.to_path_buf(),
+            max_backups,
+        }
+    }
+
+    #[test]
+    fn test_cleanup_keeps_newest_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = AutoSaveManager::new(test_config(dir.path(), 2));
+        let project = ProjectFile::new(Project::new("Backup Test".to_string()));
+
+        let mut written = Vec::new();
+        for _ in 0..4 {
+            written.push(manager.save_now(&project).unwrap());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let remaining = AutoSaveManager::list_backups(dir.path()).unwrap();
+        assert_eq!(remaining, written[2..].to_vec());
+    }
+
+    #[test]
+    fn test_find_recovery_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = AutoSaveManager::new(test_config(dir.path(), 5));
+        let project = ProjectFile::new(Project::new("Recovery Test".to_string()));
+
+        assert!(manager.find_recovery_file().is_none());
+
+        let backup = manager.save_now(&project).unwrap();
+        assert_eq!(manager.find_recovery_file(), Some(backup));
+
+        std::thread::sleep(Duration::from_millis(20));
+        manager.record_explicit_save().unwrap();
+        assert!(manager.find_recovery_file().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_manager_does_not_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = test_config(dir.path(), 5);
+        config.enabled = false;
+
+        let manager = AutoSaveManager::new(config);
+        manager.queue(&ProjectFile::new(Project::new("Idle".to_string())));
+        assert!(!manager.is_running());
+        assert!(manager.pending.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_queued_project_is_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = AutoSaveManager::new(test_config(dir.path(), 5));
+        assert!(manager.is_running());
+
+        manager.queue(&ProjectFile::new(Project::new("Queued".to_string())));
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        let backup = manager.find_recovery_file().expect("queued project was backed up");
+        assert_eq!(ProjectFile::load(&backup).unwrap().metadata.name, "Queued");
+        assert!(manager.pending.lock().unwrap().is_none());
+    }
+}
@@ -0,0 +1,101 @@
+//! Right-hand component panel
+//!
+//! Switches between the research console and the parts most recently added
+//! to any project. Clicking a recent part adds it to the open circuit.
+
+use crate::AppState;
+use egui::Ui;
+use opencircuit_database::Database;
+
+/// Tab shown in the component panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ComponentTab {
+    #[default]
+    Research,
+    Recent,
+}
+
+/// Show the tab switcher
+pub fn show_tabs(ui: &mut Ui, tab: &mut ComponentTab) {
+    ui.horizontal(|ui| {
+        ui.selectable_value(tab, ComponentTab::Research, "🔍 Research");
+        ui.selectable_value(tab, ComponentTab::Recent, "🕘 Recent");
+    });
+}
+
+/// List recently used library parts; clicking one adds it to the circuit
+///
+/// Parts can only be added while a project is open.
+pub fn show_recent_components(ui: &mut Ui, state: &mut AppState, database: Option<&Database>) {
+    let Some(database) = database else {
+        ui.label("Component library unavailable");
+        return;
+    };
+    if state.recent_components.is_empty() {
+        ui.label("Parts you add to a circuit will appear here.");
+        return;
+    }
+
+    let mut chosen = None;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for part in &state.recent_components {
+            let response = ui
+                .add_enabled(
+                    state.current_project.is_some(),
+                    egui::Button::new(format!("{} ({})", part.part_number, part.manufacturer)),
+                )
+                .on_hover_text(part.description.as_deref().unwrap_or(&part.category));
+            if response.clicked() {
+                chosen = Some(part.clone());
+            }
+        }
+    });
+
+    if let Some(part) = chosen {
+        match state.add_library_component(&part, database) {
+            Ok(id) => tracing::info!("Added {} as {}", part.part_number, id),
+            Err(e) => tracing::warn!("Failed to add {}: {:#}", part.part_number, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_database::ComponentRecord;
+
+    fn render(state: &mut AppState, database: Option<&Database>) {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| show_recent_components(ui, state, database));
+        });
+    }
+
+    #[test]
+    fn test_recent_components_render_without_adding() {
+        let database = Database::new_in_memory().unwrap();
+        let mut state = AppState::default();
+        render(&mut state, None);
+
+        state.recent_components.push(ComponentRecord {
+            id: "rc0603".to_string(),
+            part_number: "RC0603FR-0710KL".to_string(),
+            manufacturer: "Yageo".to_string(),
+            category: "Resistors".to_string(),
+            description: None,
+            datasheet_url: None,
+            specifications: None,
+            footprint: None,
+            symbol: None,
+            created_at: "2025-01-27T12:00:00Z".to_string(),
+            updated_at: "2025-01-27T12:00:00Z".to_string(),
+        });
+        state.new_project("Sensor");
+        render(&mut state, Some(&database));
+
+        // Listing parts never adds one on its own
+        assert!(state.current_project.as_ref().unwrap().circuit.is_none());
+        assert!(!state.is_dirty());
+        assert_eq!(ComponentTab::default(), ComponentTab::Research);
+    }
+}
@@ -3,12 +3,14 @@
 //! This module implements the main egui application with three-panel layout:
 //! - Left panel: Chat interface with AI assistant
 //! - Center panel: Circuit visualization and editing
-//! - Right panel: Research console and component browser
+//! - Right panel: Research console and recently used components
 
-use crate::gui::{AppState, ChatPanel, ResearchStatus};
+use crate::gui::{AppState, ChatPanel, EditorAction, ProjectDialog, ResearchEvent, ResearchStatus, UnsavedChangesChoice};
 use crate::ai::ChatHandler;
 use crate::OpenCircuitResult;
 use eframe::egui::{self, Context, CentralPanel, SidePanel, TopBottomPanel, Ui};
+use crate::gui::component_panel::{self, ComponentTab};
+use opencircuit_database::Database;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -22,15 +24,34 @@ pub struct OpenCircuitEguiApp {
     chat_handler: Arc<Mutex<ChatHandler>>,
     /// Runtime for async operations
     runtime: tokio::runtime::Runtime,
+    /// Path typed into the open-project dialog
+    open_path: String,
+    /// Component library, when it could be opened
+    database: Option<Database>,
+    /// Selected tab of the component panel
+    component_tab: ComponentTab,
 }
 
 impl OpenCircuitEguiApp {
     pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+        let mut state = AppState::default();
+        let config = opencircuit_core::load_config().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load configuration, using defaults: {}", e);
+            opencircuit_core::AppConfig::default()
+        });
+        let database = state
+            .open_component_library(&config)
+            .map_err(|e| tracing::warn!("Component library unavailable: {:#}", e))
+            .ok();
+
         Self {
-            state: AppState::default(),
+            state,
             chat_panel: ChatPanel::new(),
             chat_handler: Arc::new(Mutex::new(ChatHandler::new())),
             runtime: tokio::runtime::Runtime::new().expect("Failed to create tokio runtime"),
+            open_path: String::new(),
+            database,
+            component_tab: ComponentTab::default(),
         }
     }
 
@@ -53,7 +74,7 @@ impl OpenCircuitEguiApp {
             self.show_circuit_header(ui);
             ui.separator();
             
-            if self.state.current_circuit.is_some() {
+            if self.state.current_project.is_some() {
                 self.show_circuit_canvas(ui);
             } else {
                 self.show_circuit_placeholder(ui);
@@ -61,16 +82,20 @@ impl OpenCircuitEguiApp {
         });
     }
 
-    fn show_circuit_header(&self, ui: &mut Ui) {
+    fn show_circuit_header(&mut self, ui: &mut Ui) {
         ui.horizontal(|ui| {
             ui.heading("🔌 Circuit Designer");
+            if let Some(project) = &self.state.current_project {
+                let marker = if self.state.is_dirty() { " •" } else { "" };
+                ui.label(format!("{}{}", project.metadata.name, marker));
+            }
             
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.button("📁 Open").clicked() {
-                    // TODO: Implement file dialog
+                    self.state.pending_actions.push(EditorAction::Open);
                 }
                 if ui.button("💾 Save").clicked() {
-                    // TODO: Implement save functionality
+                    self.state.pending_actions.push(EditorAction::Save);
                 }
                 if ui.button("▶️ Simulate").clicked() {
                     // TODO: Implement simulation
@@ -103,7 +128,7 @@ impl OpenCircuitEguiApp {
         );
     }
 
-    fn show_circuit_placeholder(&self, ui: &mut Ui) {
+    fn show_circuit_placeholder(&mut self, ui: &mut Ui) {
         ui.vertical_centered(|ui| {
             ui.add_space(50.0);
             ui.heading("🔌 Welcome to OpenCircuit");
@@ -117,7 +142,7 @@ impl OpenCircuitEguiApp {
             ui.add_space(30.0);
             
             if ui.button("🆕 New Circuit").clicked() {
-                // TODO: Create new circuit
+                self.state.pending_actions.push(EditorAction::New);
             }
             
             ui.add_space(20.0);
@@ -174,20 +199,27 @@ impl OpenCircuitEguiApp {
             .default_width(300.0)
             .width_range(200.0..=400.0)
             .show(ctx, |ui| {
-                ui.heading("🔍 Research Console");
+                component_panel::show_tabs(ui, &mut self.component_tab);
                 ui.separator();
-                self.show_research_content(ui);
+                match self.component_tab {
+                    ComponentTab::Research => self.show_research_content(ui),
+                    ComponentTab::Recent => {
+                        component_panel::show_recent_components(ui, &mut self.state, self.database.as_ref())
+                    }
+                }
             });
     }
 
     fn show_research_content(&mut self, ui: &mut Ui) {
+        let elapsed = self.state.research.elapsed().as_secs_f32();
+
         match self.state.research_status {
             ResearchStatus::Idle => {
                 ui.label("🟢 Research system ready");
                 ui.add_space(10.0);
                 
                 if ui.button("🔍 Start Component Research").clicked() {
-                    self.state.research_status = ResearchStatus::Searching;
+                    self.send_research_event(ResearchEvent::StartSearch("components".to_string()));
                 }
                 
                 ui.add_space(20.0);
@@ -203,12 +235,15 @@ impl OpenCircuitEguiApp {
                 // Animated progress indicator
                 ui.horizontal(|ui| {
                     ui.spinner();
-                    ui.label("Analyzing specifications...");
+                    ui.label(format!("Analyzing specifications... {:.1}s", elapsed));
                 });
                 
-                // Auto-advance to next state (simulation)
-                ui.ctx().request_repaint_after(std::time::Duration::from_millis(2000));
-                self.state.research_status = ResearchStatus::Analyzing;
+                // Simulated search backend
+                ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+                if elapsed > 2.0 {
+                    self.send_research_event(ResearchEvent::SearchResultsReceived(26));
+                    self.send_research_event(ResearchEvent::StartAnalysis);
+                }
             }
             ResearchStatus::Analyzing => {
                 ui.label("🧮 Analyzing results...");
@@ -216,12 +251,15 @@ impl OpenCircuitEguiApp {
                 
                 ui.horizontal(|ui| {
                     ui.spinner();
-                    ui.label("Processing recommendations...");
+                    ui.label(format!("Processing recommendations... {:.1}s", elapsed));
                 });
+                // Long analyses show how far past the typical 1.5 s they are
+                ui.add(egui::ProgressBar::new((elapsed / 1.5).min(1.0)).show_percentage());
                 
-                // Auto-advance to complete state
-                ui.ctx().request_repaint_after(std::time::Duration::from_millis(1500));
-                self.state.research_status = ResearchStatus::Complete;
+                ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+                if elapsed > 1.5 {
+                    self.send_research_event(ResearchEvent::AnalysisComplete);
+                }
             }
             ResearchStatus::Complete => {
                 ui.label("✅ Research complete!");
@@ -238,28 +276,72 @@ impl OpenCircuitEguiApp {
                 
                 ui.add_space(15.0);
                 if ui.button("🔄 Reset").clicked() {
-                    self.state.research_status = ResearchStatus::Idle;
+                    self.send_research_event(ResearchEvent::Reset);
                 }
             }
         }
     }
 
+    fn send_research_event(&mut self, event: ResearchEvent) {
+        if let Err(e) = self.state.apply_research_event(event) {
+            tracing::warn!("Ignoring research event: {}", e);
+        }
+    }
+
+    /// Show the unsaved-changes prompt and the open-project dialog
+    fn show_project_dialogs(&mut self, ctx: &Context) {
+        match self.state.project_dialog {
+            Some(ProjectDialog::UnsavedChanges(_)) => {
+                egui::Window::new("Unsaved changes").collapsible(false).resizable(false).show(ctx, |ui| {
+                    ui.label("The current project has unsaved changes.");
+                    ui.horizontal(|ui| {
+                        for (label, choice) in [
+                            ("Save", UnsavedChangesChoice::Save),
+                            ("Discard", UnsavedChangesChoice::Discard),
+                            ("Cancel", UnsavedChangesChoice::Cancel),
+                        ] {
+                            if ui.button(label).clicked() {
+                                self.state.resolve_unsaved_changes(choice);
+                            }
+                        }
+                    });
+                });
+            }
+            Some(ProjectDialog::OpenFile) => {
+                egui::Window::new("Open project").collapsible(false).resizable(false).show(ctx, |ui| {
+                    ui.text_edit_singleline(&mut self.open_path);
+                    ui.horizontal(|ui| {
+                        if ui.button("Open").clicked() {
+                            match self.state.open_from_dialog(std::path::Path::new(self.open_path.trim())) {
+                                Ok(()) => self.open_path.clear(),
+                                Err(e) => tracing::warn!("Failed to open project: {:#}", e),
+                            }
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.state.project_dialog = None;
+                        }
+                    });
+                });
+            }
+            None => {}
+        }
+    }
+
     /// Show the top menu bar
     fn show_menu_bar(&mut self, ctx: &Context) {
         TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.menu_button("File", |ui| {
-                    if ui.button("New Circuit").clicked() {
-                        // TODO: New circuit
-                        ui.close_menu();
-                    }
-                    if ui.button("Open...").clicked() {
-                        // TODO: Open file dialog
-                        ui.close_menu();
-                    }
-                    if ui.button("Save").clicked() {
-                        // TODO: Save current circuit
-                        ui.close_menu();
+                    for (label, action) in [
+                        ("New Project", EditorAction::New),
+                        ("Open...", EditorAction::Open),
+                        ("Save", EditorAction::Save),
+                        ("Close Project", EditorAction::Close),
+                    ] {
+                        if ui.button(label).clicked() {
+                            self.state.pending_actions.push(action);
+                            ui.close_menu();
+                        }
                     }
                     ui.separator();
                     if ui.button("Exit").clicked() {
@@ -297,8 +379,14 @@ impl OpenCircuitEguiApp {
 
 impl eframe::App for OpenCircuitEguiApp {
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        for action in self.state.dispatch_actions(ctx) {
+            // TODO: Wire remaining actions once the editor supports them
+            tracing::debug!("Unhandled editor action: {:?}", action);
+        }
+
         // Show menu bar
         self.show_menu_bar(ctx);
+        self.show_project_dialogs(ctx);
         
         // Show main panels
         self.show_chat_panel(ctx);
@@ -0,0 +1,171 @@
+//! Keyboard shortcuts for common editor actions
+//!
+//! Defaults follow KiCad where it has an equivalent, so users switching over
+//! keep their muscle memory.
+
+use egui::{Context, Event, InputState, Key, Modifiers};
+use std::collections::HashMap;
+
+/// Modifier and key combination that triggers an action
+pub type KeyBinding = (Modifiers, Key);
+
+/// Zoom factor change per zoom-in or zoom-out action
+pub const ZOOM_STEP: f32 = 1.1;
+
+/// Editor actions that can be bound to a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditorAction {
+    Undo,
+    Redo,
+    Save,
+    New,
+    Open,
+    Close,
+    AddComponent,
+    DeleteSelected,
+    Rotate,
+    ZoomIn,
+    ZoomOut,
+    FitToWindow,
+    RunDrc,
+    RunSimulation,
+}
+
+impl EditorAction {
+    /// Apply a zoom action to the UI, returning `false` for other actions
+    pub fn apply_zoom(self, ctx: &Context) -> bool {
+        let zoom_factor = match self {
+            EditorAction::ZoomIn => ctx.zoom_factor() * ZOOM_STEP,
+            EditorAction::ZoomOut => ctx.zoom_factor() / ZOOM_STEP,
+            EditorAction::FitToWindow => 1.0,
+            _ => return false,
+        };
+        ctx.set_zoom_factor(zoom_factor);
+        true
+    }
+}
+
+/// Key bindings for editor actions
+#[derive(Debug, Clone)]
+pub struct KeyboardShortcuts {
+    pub bindings: HashMap<KeyBinding, EditorAction>,
+}
+
+impl KeyboardShortcuts {
+    /// Shortcuts with no bindings
+    pub fn empty() -> Self {
+        Self { bindings: HashMap::new() }
+    }
+
+    /// Bind a key combination, replacing any action already bound to it
+    pub fn bind(&mut self, modifiers: Modifiers, key: Key, action: EditorAction) -> Option<EditorAction> {
+        self.bindings.insert((modifiers, key), action)
+    }
+
+    /// Remove a key combination
+    pub fn unbind(&mut self, modifiers: Modifiers, key: Key) -> Option<EditorAction> {
+        self.bindings.remove(&(modifiers, key))
+    }
+
+    /// Action bound to a pressed key, if any
+    ///
+    /// Ctrl bindings also match Cmd on macOS.
+    pub fn action_for(&self, modifiers: Modifiers, key: Key) -> Option<EditorAction> {
+        self.bindings
+            .iter()
+            .find(|((pattern, bound_key), _)| *bound_key == key && modifiers.matches(*pattern))
+            .map(|(_, action)| *action)
+    }
+
+    /// All key combinations bound to an action, e.g. for menu hints
+    pub fn bindings_for(&self, action: EditorAction) -> Vec<KeyBinding> {
+        self.bindings.iter().filter(|(_, bound)| **bound == action).map(|(binding, _)| *binding).collect()
+    }
+
+    /// Actions triggered by key presses this frame, in press order
+    pub fn actions(&self, input: &InputState) -> Vec<EditorAction> {
+        input
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Key { key, pressed: true, modifiers, .. } => self.action_for(*modifiers, *key),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Default for KeyboardShortcuts {
+    fn default() -> Self {
+        let mut shortcuts = Self::empty();
+        for (modifiers, key, action) in [
+            (Modifiers::COMMAND, Key::Z, EditorAction::Undo),
+            (Modifiers::COMMAND, Key::Y, EditorAction::Redo),
+            (Modifiers::COMMAND | Modifiers::SHIFT, Key::Z, EditorAction::Redo),
+            (Modifiers::COMMAND, Key::S, EditorAction::Save),
+            (Modifiers::COMMAND, Key::N, EditorAction::New),
+            (Modifiers::COMMAND, Key::O, EditorAction::Open),
+            (Modifiers::COMMAND, Key::W, EditorAction::Close),
+            (Modifiers::NONE, Key::A, EditorAction::AddComponent),
+            (Modifiers::NONE, Key::Delete, EditorAction::DeleteSelected),
+            (Modifiers::NONE, Key::R, EditorAction::Rotate),
+            (Modifiers::NONE, Key::F1, EditorAction::ZoomIn),
+            (Modifiers::NONE, Key::F2, EditorAction::ZoomOut),
+            (Modifiers::NONE, Key::F, EditorAction::FitToWindow),
+            (Modifiers::COMMAND | Modifiers::SHIFT, Key::D, EditorAction::RunDrc),
+            (Modifiers::NONE, Key::F5, EditorAction::RunSimulation),
+        ] {
+            shortcuts.bind(modifiers, key, action);
+        }
+        shortcuts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_press(modifiers: Modifiers, key: Key) -> Event {
+        Event::Key { key, physical_key: None, pressed: true, repeat: false, modifiers }
+    }
+
+    #[test]
+    fn test_default_bindings() {
+        let shortcuts = KeyboardShortcuts::default();
+        assert_eq!(shortcuts.action_for(Modifiers::CTRL | Modifiers::COMMAND, Key::Z), Some(EditorAction::Undo));
+        assert_eq!(
+            shortcuts.action_for(Modifiers::CTRL | Modifiers::COMMAND | Modifiers::SHIFT, Key::Z),
+            Some(EditorAction::Redo)
+        );
+        assert_eq!(shortcuts.action_for(Modifiers::NONE, Key::Delete), Some(EditorAction::DeleteSelected));
+        assert_eq!(shortcuts.action_for(Modifiers::NONE, Key::R), Some(EditorAction::Rotate));
+        assert_eq!(shortcuts.action_for(Modifiers::NONE, Key::F), Some(EditorAction::FitToWindow));
+        // Plain S is not Save
+        assert_eq!(shortcuts.action_for(Modifiers::NONE, Key::S), None);
+        assert_eq!(shortcuts.bindings_for(EditorAction::Redo).len(), 2);
+    }
+
+    #[test]
+    fn test_rebinding() {
+        let mut shortcuts = KeyboardShortcuts::default();
+        assert_eq!(shortcuts.bind(Modifiers::NONE, Key::R, EditorAction::RunDrc), Some(EditorAction::Rotate));
+        assert_eq!(shortcuts.action_for(Modifiers::NONE, Key::R), Some(EditorAction::RunDrc));
+        assert_eq!(shortcuts.unbind(Modifiers::NONE, Key::R), Some(EditorAction::RunDrc));
+        assert_eq!(shortcuts.action_for(Modifiers::NONE, Key::R), None);
+    }
+
+    #[test]
+    fn test_actions_from_input_events() {
+        let mut input = InputState::default();
+        input.events = vec![
+            key_press(Modifiers::CTRL | Modifiers::COMMAND, Key::S),
+            Event::Key { key: Key::Delete, physical_key: None, pressed: false, repeat: false, modifiers: Modifiers::NONE },
+            Event::Text("r".to_string()),
+            key_press(Modifiers::NONE, Key::R),
+        ];
+        assert_eq!(
+            KeyboardShortcuts::default().actions(&input),
+            vec![EditorAction::Save, EditorAction::Rotate]
+        );
+    }
+}
@@ -5,8 +5,16 @@
 //! - Chat interface with AI assistant
 //! - Circuit visualization
 //! - Research console animation
+//! - Project lifecycle: new, open, save and close
+//! - Periodic auto-save and crash recovery
+//! - Recently used library components, listed in the component panel
 
 pub mod app;
+pub mod autosave;
+pub mod component_panel;
+pub mod keyboard;
+pub mod project;
+pub mod research;
 // Temporarily commented out due to egui dependency issues
 // pub mod chat_panel;
 // pub mod egui_app;  // Temporarily disabled due to dependency issues
@@ -14,22 +22,354 @@ pub mod app;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
-use opencircuit_core::OpenCircuitError;
+use autosave::AutoSaveManager;
+use opencircuit_circuit::{Circuit, Component, ComponentType};
+use opencircuit_core::{AppConfig, OpenCircuitError, Project};
+use opencircuit_database::{ComponentRecord, Database};
+use project::ProjectFile;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
 use uuid::Uuid;
 
 /// Type alias for GUI-specific results
 pub type OpenCircuitResult<T> = Result<T, OpenCircuitError>;
 
+/// Name of projects created from the New action
+pub const UNTITLED_PROJECT_NAME: &str = "Untitled";
+
+/// Number of parts listed in the component panel's "Recent" tab
+pub const RECENT_COMPONENTS_LIMIT: u32 = 10;
+
 /// Application state that persists across the GUI
 #[derive(Debug, Clone, Default)]
 pub struct AppState {
     pub chat_messages: Vec<opencircuit_ai::chat_handler::ChatMessage>,
-    pub current_circuit: Option<String>, // Placeholder for circuit data
+    /// Latest summary of a long chat, shown in place of the older messages
+    pub conversation_summary: Option<String>,
+    /// Open project, including its circuit and board
+    pub current_project: Option<ProjectFile>,
+    /// File the open project was loaded from or last saved to
+    pub project_path: Option<PathBuf>,
+    /// Whether the open project changed since it was last opened or saved
+    dirty: bool,
+    /// Backs up unsaved changes, if auto-save was set up
+    autosave: Option<Arc<AutoSaveManager>>,
     pub research_status: ResearchStatus,
+    /// Transition rules and timing behind `research_status`
+    pub research: ResearchStatusMachine,
+    pub shortcuts: KeyboardShortcuts,
+    /// Actions triggered by shortcuts, waiting for the editor to handle them
+    pub pending_actions: Vec<EditorAction>,
+    /// Dialog a project action is waiting on
+    pub project_dialog: Option<ProjectDialog>,
+    /// Library parts most recently added to any project, newest first
+    pub recent_components: Vec<ComponentRecord>,
+}
+
+impl AppState {
+    /// Drive the research console, keeping `research_status` in sync
+    pub fn apply_research_event(&mut self, event: ResearchEvent) -> Result<ResearchStatus, InvalidTransition> {
+        let status = self.research.transition(event)?;
+        self.research_status = status.clone();
+        Ok(status)
+    }
+
+    /// Queue the actions bound to this frame's key presses
+    ///
+    /// Skip this while a text field has focus so typing doesn't trigger
+    /// single-key shortcuts.
+    pub fn handle_keyboard_input(&mut self, input: &egui::InputState) -> &[EditorAction] {
+        let start = self.pending_actions.len();
+        self.pending_actions.extend(self.shortcuts.actions(input));
+        &self.pending_actions[start..]
+    }
+
+    /// Take queued shortcut actions for handling
+    pub fn take_pending_actions(&mut self) -> Vec<EditorAction> {
+        std::mem::take(&mut self.pending_actions)
+    }
+
+    /// Queue this frame's shortcuts and run the queued actions
+    ///
+    /// Shortcuts are ignored while a text field has focus. Zoom actions are
+    /// applied to `ctx` and project actions to the open project; the rest
+    /// are returned for the editor to handle.
+    pub fn dispatch_actions(&mut self, ctx: &egui::Context) -> Vec<EditorAction> {
+        if !ctx.wants_keyboard_input() {
+            ctx.input(|input| {
+                self.handle_keyboard_input(input);
+            });
+        }
+        self.take_pending_actions()
+            .into_iter()
+            .filter(|&action| !action.apply_zoom(ctx) && !self.handle_project_action(action))
+            .collect()
+    }
+
+    /// Replace the open project with a new, empty one
+    ///
+    /// Check [`Self::is_dirty`] first so unsaved changes aren't lost.
+    pub fn new_project(&mut self, name: &str) -> &ProjectFile {
+        self.project_path = None;
+        self.dirty = false;
+        self.current_project.insert(ProjectFile::new(Project::new(name.to_string())))
+    }
+
+    /// Load a project file, replacing the open project
+    pub fn open_project(&mut self, path: &Path) -> Result<()> {
+        self.current_project = Some(ProjectFile::load(path)?);
+        self.project_path = Some(path.to_path_buf());
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Save the open project to the file it came from
+    ///
+    /// New projects are saved as `<id>.ocp` in [`project::projects_dir`].
+    pub fn save_project(&mut self) -> Result<()> {
+        let project = self.current_project.as_mut().ok_or_else(|| anyhow::anyhow!("No project is open"))?;
+        let path = match &self.project_path {
+            Some(path) => path.clone(),
+            None => {
+                let dir = project::projects_dir();
+                std::fs::create_dir_all(&dir)?;
+                dir.join(format!("{}.{}", project.metadata.id, project::PROJECT_EXTENSION))
+            }
+        };
+
+        project.metadata.update();
+        project.save(&path)?;
+        self.project_path = Some(path);
+        self.dirty = false;
+        if let Some(autosave) = &self.autosave {
+            if let Err(e) = autosave.record_explicit_save() {
+                warn!("Failed to record save for auto-save recovery: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a save, new, open or close action from a shortcut or menu item
+    ///
+    /// New, open and close wait in [`ProjectDialog::UnsavedChanges`] while
+    /// the project has unsaved changes. Returns `false` for other actions.
+    pub fn handle_project_action(&mut self, action: EditorAction) -> bool {
+        match action {
+            EditorAction::Save => self.save_from_action(),
+            EditorAction::New | EditorAction::Open | EditorAction::Close if self.dirty => {
+                self.project_dialog = Some(ProjectDialog::UnsavedChanges(action));
+            }
+            EditorAction::New | EditorAction::Open | EditorAction::Close => self.finish_project_action(action),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Answer the unsaved-changes dialog
+    ///
+    /// The dialog stays up if saving fails.
+    pub fn resolve_unsaved_changes(&mut self, choice: UnsavedChangesChoice) {
+        let Some(ProjectDialog::UnsavedChanges(action)) = self.project_dialog else {
+            return;
+        };
+        match choice {
+            UnsavedChangesChoice::Save => {
+                self.save_from_action();
+                if self.dirty {
+                    return;
+                }
+            }
+            UnsavedChangesChoice::Discard => {}
+            UnsavedChangesChoice::Cancel => {
+                self.project_dialog = None;
+                return;
+            }
+        }
+        self.project_dialog = None;
+        self.finish_project_action(action);
+    }
+
+    /// Open the file chosen in the open-project dialog and close the dialog
+    pub fn open_from_dialog(&mut self, path: &Path) -> Result<()> {
+        self.open_project(path)?;
+        self.project_dialog = None;
+        Ok(())
+    }
+
+    fn finish_project_action(&mut self, action: EditorAction) {
+        match action {
+            EditorAction::New => {
+                self.new_project(UNTITLED_PROJECT_NAME);
+            }
+            // The current project stays open until another one loads
+            EditorAction::Open => self.project_dialog = Some(ProjectDialog::OpenFile),
+            EditorAction::Close => self.discard_project(),
+            _ => {}
+        }
+    }
+
+    fn save_from_action(&mut self) {
+        if let Err(e) = self.save_project() {
+            warn!("Failed to save project: {:#}", e);
+        }
+    }
+
+    /// Back up unsaved changes with `manager` from now on
+    pub fn set_auto_save(&mut self, manager: AutoSaveManager) {
+        self.autosave = Some(Arc::new(manager));
+    }
+
+    /// Newest auto-save backup left unsaved by an earlier session
+    pub fn recovery_file(&self) -> Option<PathBuf> {
+        self.autosave.as_ref()?.find_recovery_file()
+    }
+
+    /// Open an auto-save backup as an unsaved project
+    ///
+    /// It has no file yet, so [`Self::save_project`] writes a new one.
+    pub fn recover_project(&mut self, backup: &Path) -> Result<()> {
+        self.current_project = Some(ProjectFile::load(backup)?);
+        self.project_path = None;
+        self.mark_dirty();
+        Ok(())
+    }
+
+    /// Stop offering the current auto-save backups for recovery
+    pub fn dismiss_recovery(&self) -> Result<()> {
+        match &self.autosave {
+            Some(autosave) => autosave.record_explicit_save(),
+            None => Ok(()),
+        }
+    }
+
+    /// Close the open project, or return `false` and keep it open if it has
+    /// unsaved changes so the GUI can ask first
+    pub fn close_project(&mut self) -> bool {
+        if self.dirty {
+            return false;
+        }
+        self.discard_project();
+        true
+    }
+
+    /// Close the open project, dropping any unsaved changes
+    pub fn discard_project(&mut self) {
+        self.current_project = None;
+        self.project_path = None;
+        self.dirty = false;
+    }
+
+    /// Record a change to the open project
+    pub fn mark_dirty(&mut self) {
+        self.dirty = self.current_project.is_some();
+        if let (Some(autosave), Some(project)) = (&self.autosave, &self.current_project) {
+            autosave.queue(project);
+        }
+    }
+
+    /// Whether the open project has unsaved changes
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Add a library part to the open project's circuit and record its use
+    ///
+    /// The part gets the next free reference designator for its category
+    /// (e.g. `R3`), which is returned.
+    pub fn add_library_component(&mut self, part: &ComponentRecord, database: &Database) -> Result<String> {
+        let project = self.current_project.as_mut().ok_or_else(|| anyhow::anyhow!("No project is open"))?;
+        let component_type = component_type_for_category(&part.category)
+            .ok_or_else(|| anyhow::anyhow!("Parts in category {} can't be placed in a circuit", part.category))?;
+
+        let circuit = project.circuit.get_or_insert_with(Circuit::new);
+        let prefix = reference_prefix(&component_type);
+        let id = (1..)
+            .map(|number| format!("{}{}", prefix, number))
+            .find(|id| !circuit.components.iter().any(|component| &component.id == id))
+            .expect("reference designators are unbounded");
+        circuit.add_component(Component {
+            id: id.clone(),
+            component_type,
+            value: None,
+            position: (0.0, 0.0),
+            purpose: part.description.clone(),
+        });
+
+        let project_id = project.metadata.id.to_string();
+        self.mark_dirty();
+        database.record_component_usage(&part.id, &project_id)?;
+        self.refresh_recent_components(database)?;
+        Ok(id)
+    }
+
+    /// Open the component library at the configured path and load the
+    /// "Recent" list from it
+    pub fn open_component_library(&mut self, config: &AppConfig) -> Result<Database> {
+        let database = Database::from_config(config)?;
+        self.refresh_recent_components(&database)?;
+        Ok(database)
+    }
+
+    /// Reload the "Recent" component list from the database
+    pub fn refresh_recent_components(&mut self, database: &Database) -> Result<()> {
+        self.recent_components = database.get_recently_used_components(RECENT_COMPONENTS_LIMIT)?;
+        Ok(())
+    }
+}
+
+/// Dialog the GUI shows to finish a project action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectDialog {
+    /// Ask whether to save or discard unsaved changes before the action runs
+    UnsavedChanges(EditorAction),
+    /// Ask for the project file to open
+    OpenFile,
+}
+
+/// Answer to the unsaved-changes dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsavedChangesChoice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+/// Circuit component type for a library category such as "Resistors"
+fn component_type_for_category(category: &str) -> Option<ComponentType> {
+    let category = category.to_lowercase();
+    [
+        ("resistor", ComponentType::Resistor),
+        ("capacitor", ComponentType::Capacitor),
+        ("inductor", ComponentType::Inductor),
+        ("transistor", ComponentType::Transistor),
+        ("mosfet", ComponentType::Transistor),
+        ("op amp", ComponentType::OpAmp),
+        ("opamp", ComponentType::OpAmp),
+        ("amplifier", ComponentType::OpAmp),
+        ("diode", ComponentType::Diode),
+    ]
+    .into_iter()
+    .find(|(keyword, _)| category.contains(keyword))
+    .map(|(_, component_type)| component_type)
+}
+
+/// SPICE-style reference designator prefix
+fn reference_prefix(component_type: &ComponentType) -> &'static str {
+    match component_type {
+        ComponentType::Resistor => "R",
+        ComponentType::Capacitor => "C",
+        ComponentType::Inductor => "L",
+        ComponentType::Transistor => "Q",
+        ComponentType::OpAmp => "U",
+        ComponentType::Diode => "D",
+        ComponentType::VoltageSource => "V",
+        ComponentType::CurrentSource => "I",
+    }
 }
 
 /// Status of the research console
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ResearchStatus {
     Idle,
     Searching,
@@ -82,6 +422,8 @@ impl Default for OpenCircuitApp {
 }
 
 // Re-export for easy access
+pub use keyboard::{EditorAction, KeyBinding, KeyboardShortcuts};
+pub use research::{InvalidTransition, ResearchEvent, ResearchStatusMachine};
 // Temporarily commented out due to egui dependency issues
 // pub use chat_panel::ChatPanel;
 // pub use egui_app::run_egui_app;  // Temporarily disabled
@@ -94,10 +436,245 @@ mod tests {
     fn test_app_creation() {
         let app = OpenCircuitApp::new();
         assert_eq!(app.state.chat_messages.len(), 0);
-        assert!(app.state.current_circuit.is_none());
+        assert!(app.state.current_project.is_none());
         assert_eq!(app.state.research_status, ResearchStatus::Idle);
     }
 
+    #[test]
+    fn test_research_events_update_status() {
+        let mut state = AppState::default();
+        state.apply_research_event(ResearchEvent::StartSearch("LM317".to_string())).unwrap();
+        assert_eq!(state.research_status, ResearchStatus::Searching);
+
+        assert!(state.apply_research_event(ResearchEvent::AnalysisComplete).is_err());
+        assert_eq!(state.research_status, ResearchStatus::Searching);
+    }
+
+    #[test]
+    fn test_keyboard_input_queues_actions() {
+        let mut state = AppState::default();
+        let mut input = egui::InputState::default();
+        input.events.push(egui::Event::Key {
+            key: egui::Key::Z,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::CTRL | egui::Modifiers::COMMAND,
+        });
+
+        assert_eq!(state.handle_keyboard_input(&input), &[EditorAction::Undo]);
+        assert_eq!(state.take_pending_actions(), vec![EditorAction::Undo]);
+        assert!(state.pending_actions.is_empty());
+    }
+
+    #[test]
+    fn test_dispatch_actions() {
+        let ctx = egui::Context::default();
+        let mut state = AppState::default();
+        // Queued by a menu item, ahead of this frame's key presses
+        state.pending_actions.push(EditorAction::New);
+        let key_press = |modifiers, key| egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers,
+        };
+        let input = egui::RawInput {
+            events: vec![
+                key_press(egui::Modifiers::CTRL | egui::Modifiers::COMMAND, egui::Key::Z),
+                key_press(egui::Modifiers::NONE, egui::Key::F1),
+            ],
+            ..Default::default()
+        };
+
+        let mut unhandled = Vec::new();
+        let _ = ctx.run(input, |ctx| unhandled = state.dispatch_actions(ctx));
+        assert_eq!(unhandled, vec![EditorAction::Undo]);
+        assert!(state.pending_actions.is_empty());
+        assert_eq!(state.current_project.as_ref().unwrap().metadata.name, UNTITLED_PROJECT_NAME);
+
+        // Zoom changes take effect from the next frame
+        let _ = ctx.run(Default::default(), |_| {});
+        assert!((ctx.zoom_factor() - keyboard::ZOOM_STEP).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_project_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("filter.ocp");
+        let mut state = AppState::default();
+        state.mark_dirty();
+        assert!(!state.is_dirty());
+
+        assert_eq!(state.new_project("RC Filter").metadata.name, "RC Filter");
+        state.project_path = Some(path.clone());
+        state.mark_dirty();
+        assert!(!state.close_project());
+        assert!(state.current_project.is_some());
+
+        state.save_project().unwrap();
+        assert!(!state.is_dirty());
+        assert!(state.close_project());
+        assert!(state.current_project.is_none());
+        assert!(state.save_project().is_err());
+
+        state.open_project(&path).unwrap();
+        assert_eq!(state.current_project.as_ref().unwrap().metadata.name, "RC Filter");
+        assert_eq!(state.project_path.as_deref(), Some(path.as_path()));
+    }
+
+    #[test]
+    fn test_project_actions_drive_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("amp.ocp");
+        let mut state = AppState::default();
+
+        assert!(state.handle_project_action(EditorAction::New));
+        assert_eq!(state.current_project.as_ref().unwrap().metadata.name, UNTITLED_PROJECT_NAME);
+        state.project_path = Some(path.clone());
+        state.mark_dirty();
+        assert!(state.handle_project_action(EditorAction::Save));
+        assert!(!state.is_dirty());
+        assert!(path.exists());
+
+        state.mark_dirty();
+        assert!(state.handle_project_action(EditorAction::Close));
+        assert_eq!(state.project_dialog, Some(ProjectDialog::UnsavedChanges(EditorAction::Close)));
+        assert!(state.current_project.is_some());
+        state.resolve_unsaved_changes(UnsavedChangesChoice::Cancel);
+        assert!(state.project_dialog.is_none());
+        assert!(state.is_dirty());
+
+        state.handle_project_action(EditorAction::Close);
+        state.resolve_unsaved_changes(UnsavedChangesChoice::Discard);
+        assert!(state.project_dialog.is_none());
+        assert!(state.current_project.is_none());
+
+        assert!(state.handle_project_action(EditorAction::Open));
+        assert_eq!(state.project_dialog, Some(ProjectDialog::OpenFile));
+        state.open_from_dialog(&path).unwrap();
+        assert!(state.project_dialog.is_none());
+        assert_eq!(state.project_path.as_deref(), Some(path.as_path()));
+
+        assert!(!state.handle_project_action(EditorAction::Rotate));
+    }
+
+    #[test]
+    fn test_save_from_unsaved_changes_dialog() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = AppState::default();
+        state.new_project("Filter");
+        state.mark_dirty();
+        state.handle_project_action(EditorAction::New);
+
+        // Nothing to save into yet, so the dialog stays up
+        state.project_path = Some(dir.path().join("missing").join("filter.ocp"));
+        state.resolve_unsaved_changes(UnsavedChangesChoice::Save);
+        assert_eq!(state.project_dialog, Some(ProjectDialog::UnsavedChanges(EditorAction::New)));
+        assert_eq!(state.current_project.as_ref().unwrap().metadata.name, "Filter");
+
+        state.project_path = Some(dir.path().join("filter.ocp"));
+        state.resolve_unsaved_changes(UnsavedChangesChoice::Save);
+        assert!(state.project_dialog.is_none());
+        assert!(dir.path().join("filter.ocp").exists());
+        assert_eq!(state.current_project.as_ref().unwrap().metadata.name, UNTITLED_PROJECT_NAME);
+    }
+
+    #[test]
+    fn test_recover_and_save_auto_saved_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = AutoSaveManager::new(opencircuit_core::AutoSaveConfig {
+            enabled: true,
+            interval_seconds: 60,
+            backup_dir: dir.path().join("backups"),
+            max_backups: 5,
+        });
+        let backup = manager.save_now(&ProjectFile::new(Project::new("Crashed".to_string()))).unwrap();
+
+        let mut state = AppState::default();
+        state.set_auto_save(manager);
+        assert_eq!(state.recovery_file(), Some(backup.clone()));
+
+        state.recover_project(&backup).unwrap();
+        assert!(state.is_dirty());
+        assert!(state.project_path.is_none());
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        state.project_path = Some(dir.path().join("crashed.ocp"));
+        state.save_project().unwrap();
+        assert!(state.recovery_file().is_none());
+    }
+
+    #[test]
+    fn test_add_library_component_records_usage() {
+        let database = Database::new_in_memory().unwrap();
+        let part = |id: &str, category: &str| ComponentRecord {
+            id: id.to_string(),
+            part_number: id.to_uppercase(),
+            manufacturer: "Yageo".to_string(),
+            category: category.to_string(),
+            description: Some("Pull-up".to_string()),
+            datasheet_url: None,
+            specifications: None,
+            footprint: None,
+            symbol: None,
+            created_at: "2025-01-27T12:00:00Z".to_string(),
+            updated_at: "2025-01-27T12:00:00Z".to_string(),
+        };
+        let resistor = part("rc0603", "Resistors");
+        let connector = part("jst-ph", "Connectors");
+        database.create_component(&resistor).unwrap();
+        database.create_component(&connector).unwrap();
+
+        let mut state = AppState::default();
+        assert!(state.add_library_component(&resistor, &database).is_err());
+
+        state.new_project("Sensor");
+        assert_eq!(state.add_library_component(&resistor, &database).unwrap(), "R1");
+        assert_eq!(state.add_library_component(&resistor, &database).unwrap(), "R2");
+        assert!(state.add_library_component(&connector, &database).is_err());
+        assert!(state.is_dirty());
+
+        let circuit = state.current_project.as_ref().unwrap().circuit.as_ref().unwrap();
+        assert_eq!(circuit.components.len(), 2);
+        assert_eq!(circuit.components[1].purpose.as_deref(), Some("Pull-up"));
+        assert_eq!(state.recent_components.len(), 1);
+        assert_eq!(state.recent_components[0].id, "rc0603");
+    }
+
+    #[test]
+    fn test_open_component_library_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AppConfig {
+            database_path: Some(dir.path().join("library.db").display().to_string()),
+            ..AppConfig::default()
+        };
+        let database = Database::from_config(&config).unwrap();
+        database
+            .create_component(&ComponentRecord {
+                id: "bc547".to_string(),
+                part_number: "BC547B".to_string(),
+                manufacturer: "onsemi".to_string(),
+                category: "Transistors".to_string(),
+                description: None,
+                datasheet_url: None,
+                specifications: None,
+                footprint: None,
+                symbol: None,
+                created_at: "2025-01-27T12:00:00Z".to_string(),
+                updated_at: "2025-01-27T12:00:00Z".to_string(),
+            })
+            .unwrap();
+        database.record_component_usage("bc547", "project-1").unwrap();
+        drop(database);
+
+        let mut state = AppState::default();
+        state.open_component_library(&config).unwrap();
+        assert_eq!(state.recent_components.len(), 1);
+        assert_eq!(state.recent_components[0].part_number, "BC547B");
+    }
+
     #[test]
     fn test_add_chat_message() {
         let mut app = OpenCircuitApp::new();
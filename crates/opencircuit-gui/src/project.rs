@@ -0,0 +1,252 @@
+//! Project file persistence
+//!
+//! Bundles project metadata, the circuit, the PCB layout, component overrides
+//! and the latest analysis results into a single gzip-compressed JSON file.
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use opencircuit_ai::CircuitHealthReport;
+use opencircuit_circuit::{Circuit, SchematicLayout};
+use opencircuit_core::Project;
+use opencircuit_database::ComponentRecord;
+use opencircuit_pcb::PcbDesign;
+use opencircuit_simulation::SimulationResults;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Current project file format version
+pub const PROJECT_FORMAT_VERSION: u32 = 1;
+
+/// File extension of saved projects
+pub const PROJECT_EXTENSION: &str = "ocp";
+
+/// Default directory holding saved projects
+pub fn projects_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("opencircuit")
+        .join("projects")
+}
+
+/// Complete project contents as stored on disk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectFile {
+    pub metadata: Project,
+    pub circuit: Option<Circuit>,
+    pub pcb: Option<PcbDesign>,
+    #[serde(default)]
+    pub component_overrides: Vec<ComponentRecord>,
+    /// Latest result of each simulation run on the circuit
+    #[serde(default)]
+    pub simulation_results: Vec<SimulationResults>,
+    #[serde(default)]
+    pub health_report: Option<CircuitHealthReport>,
+    /// Component positions and wire routes on the schematic canvas
+    #[serde(default)]
+    pub schematic_layout: Option<SchematicLayout>,
+}
+
+/// Versioned envelope written around the project contents
+#[derive(Debug, Serialize, Deserialize)]
+struct ProjectEnvelope<T> {
+    version: u32,
+    project: T,
+}
+
+impl ProjectFile {
+    /// Create an empty project file for the given project
+    pub fn new(metadata: Project) -> Self {
+        Self {
+            metadata,
+            circuit: None,
+            pcb: None,
+            component_overrides: Vec::new(),
+            simulation_results: Vec::new(),
+            health_report: None,
+            schematic_layout: None,
+        }
+    }
+
+    /// Save the project as gzip-compressed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create project file {}", path.display()))?;
+        let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+
+        let envelope = ProjectEnvelope {
+            version: PROJECT_FORMAT_VERSION,
+            project: self,
+        };
+        serde_json::to_writer(&mut encoder, &envelope).context("Failed to serialize project")?;
+        encoder.finish().context("Failed to finish project file")?;
+
+        Ok(())
+    }
+
+    /// Load a project previously written with [`ProjectFile::save`]
+    pub fn load(path: &Path) -> Result<ProjectFile> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open project file {}", path.display()))?;
+        let decoder = GzDecoder::new(BufReader::new(file));
+
+        let envelope: ProjectEnvelope<ProjectFile> =
+            serde_json::from_reader(decoder).context("Failed to parse project file")?;
+        if envelope.version > PROJECT_FORMAT_VERSION {
+            bail!(
+                "Project file version {} is newer than supported version {}",
+                envelope.version,
+                PROJECT_FORMAT_VERSION
+            );
+        }
+
+        Ok(envelope.project)
+    }
+
+    /// Find the project with the given ID among the project files in `dir`
+    ///
+    /// Files that cannot be read are skipped with a warning.
+    pub fn find_by_id(dir: &Path, project_id: &str) -> Result<ProjectFile> {
+        let id = uuid::Uuid::parse_str(project_id.trim())
+            .with_context(|| format!("Invalid project ID {}", project_id))?;
+        let entries = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read project directory {}", dir.display()))?;
+
+        for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+            if path.extension().and_then(|extension| extension.to_str()) != Some(PROJECT_EXTENSION) {
+                continue;
+            }
+            match ProjectFile::load(&path) {
+                Ok(project) if project.metadata.id == id => return Ok(project),
+                Ok(_) => {}
+                Err(e) => warn!("Skipping {}: {:#}", path.display(), e),
+            }
+        }
+
+        bail!("No project with ID {} in {}", id, dir.display())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType, Connection};
+    use opencircuit_pcb::{ComponentPlacement, Layer, Trace};
+
+    fn sample_project() -> ProjectFile {
+        let mut circuit = Circuit::new();
+        circuit.add_component(Component {
+            id: "V1".to_string(),
+            component_type: ComponentType::VoltageSource,
+            value: Some("12V".to_string()),
+            position: (0.0, 0.0),
+            purpose: None,
+        });
+        circuit.add_component(Component {
+            id: "R1".to_string(),
+            component_type: ComponentType::Resistor,
+            value: Some("10k".to_string()),
+            position: (10.0, 0.0),
+            purpose: None,
+        });
+        circuit.add_component(Component {
+            id: "R2".to_string(),
+            component_type: ComponentType::Resistor,
+            value: Some("4.7k".to_string()),
+            position: (10.0, 10.0),
+            purpose: None,
+        });
+        circuit.add_connection(Connection {
+            from: "V1".to_string(),
+            to: "R1".to_string(),
+            net_name: "VIN".to_string(),
+        });
+        circuit.add_connection(Connection {
+            from: "R1".to_string(),
+            to: "R2".to_string(),
+            net_name: "VOUT".to_string(),
+        });
+
+        let mut pcb = PcbDesign::new(50.0, 30.0, 2);
+        pcb.add_placement(ComponentPlacement {
+            component_id: "R1".to_string(),
+            x: 12.5,
+            y: 8.0,
+            rotation: 90.0,
+            layer: Layer::Top,
+            footprint: Some("0805".to_string()),
+        });
+        pcb.add_trace(Trace {
+            net_name: "VOUT".to_string(),
+            width: 0.25,
+            layer: Layer::Bottom,
+            points: vec![(12.5, 8.0), (20.0, 8.0), (20.0, 15.0)],
+        });
+
+        let mut project = ProjectFile::new(Project::new("Voltage Divider".to_string()));
+        project.schematic_layout = Some(SchematicLayout::auto_layout(&circuit));
+        project.circuit = Some(circuit);
+        project.pcb = Some(pcb);
+        project.component_overrides.push(ComponentRecord {
+            id: "override-1".to_string(),
+            part_number: "RC0603FR-0710KL".to_string(),
+            manufacturer: "Yageo".to_string(),
+            category: "Resistors".to_string(),
+            description: Some("10k 1% resistor".to_string()),
+            datasheet_url: None,
+            specifications: Some(r#"{"tolerance":"1%"}"#.to_string()),
+            footprint: Some("0603".to_string()),
+            symbol: None,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+        });
+        project
+    }
+
+    #[test]
+    fn test_project_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("divider.ocp");
+
+        let project = sample_project();
+        project.save(&path).unwrap();
+        let loaded = ProjectFile::load(&path).unwrap();
+
+        assert_eq!(loaded, project);
+    }
+
+    #[test]
+    fn test_rejects_newer_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("future.ocp");
+
+        let envelope = ProjectEnvelope {
+            version: PROJECT_FORMAT_VERSION + 1,
+            project: sample_project(),
+        };
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        serde_json::to_writer(&mut encoder, &envelope).unwrap();
+        encoder.finish().unwrap();
+
+        assert!(ProjectFile::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_find_by_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = sample_project();
+        project.save(&dir.path().join("divider.ocp")).unwrap();
+        ProjectFile::new(Project::new("Other".to_string())).save(&dir.path().join("other.ocp")).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a project").unwrap();
+        std::fs::write(dir.path().join("broken.ocp"), "not gzip").unwrap();
+
+        let found = ProjectFile::find_by_id(dir.path(), &project.metadata.id.to_string()).unwrap();
+        assert_eq!(found, project);
+        assert!(ProjectFile::find_by_id(dir.path(), &uuid::Uuid::new_v4().to_string()).is_err());
+        assert!(ProjectFile::find_by_id(dir.path(), "not-a-uuid").is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! Research console state machine
+//!
+//! ```text
+//! Idle/Complete --StartSearch--> Searching --SearchResultsReceived--> Searching
+//! Searching --StartAnalysis--> Analyzing --AnalysisComplete--> Complete
+//! any --Reset--> Idle
+//! ```
+
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use crate::ResearchStatus;
+
+/// Events that drive the research console
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResearchEvent {
+    StartSearch(String),
+    SearchResultsReceived(usize),
+    StartAnalysis,
+    AnalysisComplete,
+    Reset,
+}
+
+/// Event that is not valid in the current state
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("cannot apply {event:?} while {from:?}")]
+pub struct InvalidTransition {
+    pub from: ResearchStatus,
+    pub event: ResearchEvent,
+}
+
+/// Research status with transition rules and state timing
+#[derive(Debug, Clone)]
+pub struct ResearchStatusMachine {
+    status: ResearchStatus,
+    entered_at: Instant,
+    query: Option<String>,
+    result_count: Option<usize>,
+}
+
+impl ResearchStatusMachine {
+    pub fn new() -> Self {
+        Self {
+            status: ResearchStatus::Idle,
+            entered_at: Instant::now(),
+            query: None,
+            result_count: None,
+        }
+    }
+
+    pub fn status(&self) -> &ResearchStatus {
+        &self.status
+    }
+
+    /// Query of the current or most recent search
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Results reported for the current search
+    pub fn result_count(&self) -> Option<usize> {
+        self.result_count
+    }
+
+    /// Apply an event, returning the new status
+    pub fn transition(&mut self, event: ResearchEvent) -> Result<ResearchStatus, InvalidTransition> {
+        let next = match (&self.status, &event) {
+            (_, ResearchEvent::Reset) => {
+                self.query = None;
+                self.result_count = None;
+                ResearchStatus::Idle
+            }
+            (ResearchStatus::Idle | ResearchStatus::Complete, ResearchEvent::StartSearch(query)) => {
+                self.query = Some(query.clone());
+                self.result_count = None;
+                ResearchStatus::Searching
+            }
+            (ResearchStatus::Searching, ResearchEvent::SearchResultsReceived(count)) => {
+                // Results may arrive in batches; the search is still running
+                *self.result_count.get_or_insert(0) += count;
+                return Ok(self.status.clone());
+            }
+            (ResearchStatus::Searching, ResearchEvent::StartAnalysis) if self.result_count.is_some() => {
+                ResearchStatus::Analyzing
+            }
+            (ResearchStatus::Analyzing, ResearchEvent::AnalysisComplete) => ResearchStatus::Complete,
+            _ => {
+                return Err(InvalidTransition { from: self.status.clone(), event });
+            }
+        };
+
+        self.status = next;
+        self.entered_at = Instant::now();
+        Ok(self.status.clone())
+    }
+
+    /// How long the current state has been active
+    pub fn elapsed(&self) -> Duration {
+        self.entered_at.elapsed()
+    }
+}
+
+impl Default for ResearchStatusMachine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_research_cycle() {
+        let mut machine = ResearchStatusMachine::new();
+        assert_eq!(
+            machine.transition(ResearchEvent::StartSearch("LM358".to_string())),
+            Ok(ResearchStatus::Searching)
+        );
+        assert_eq!(machine.transition(ResearchEvent::SearchResultsReceived(3)), Ok(ResearchStatus::Searching));
+        assert_eq!(machine.transition(ResearchEvent::SearchResultsReceived(2)), Ok(ResearchStatus::Searching));
+        assert_eq!(machine.result_count(), Some(5));
+        assert_eq!(machine.transition(ResearchEvent::StartAnalysis), Ok(ResearchStatus::Analyzing));
+        assert_eq!(machine.transition(ResearchEvent::AnalysisComplete), Ok(ResearchStatus::Complete));
+        assert_eq!(machine.query(), Some("LM358"));
+
+        assert_eq!(
+            machine.transition(ResearchEvent::StartSearch("NE555".to_string())),
+            Ok(ResearchStatus::Searching)
+        );
+        assert_eq!(machine.result_count(), None);
+        assert_eq!(machine.transition(ResearchEvent::Reset), Ok(ResearchStatus::Idle));
+        assert_eq!(machine.query(), None);
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        let mut machine = ResearchStatusMachine::new();
+        assert_eq!(
+            machine.transition(ResearchEvent::AnalysisComplete),
+            Err(InvalidTransition { from: ResearchStatus::Idle, event: ResearchEvent::AnalysisComplete })
+        );
+
+        machine.transition(ResearchEvent::StartSearch("TL072".to_string())).unwrap();
+        // Analysis needs search results first
+        assert!(machine.transition(ResearchEvent::StartAnalysis).is_err());
+        assert!(machine.transition(ResearchEvent::StartSearch("again".to_string())).is_err());
+        assert_eq!(machine.status(), &ResearchStatus::Searching);
+    }
+
+    #[test]
+    fn test_elapsed_restarts_on_transition() {
+        let mut machine = ResearchStatusMachine::new();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(machine.elapsed() >= Duration::from_millis(20));
+
+        machine.transition(ResearchEvent::StartSearch("2N3904".to_string())).unwrap();
+        assert!(machine.elapsed() < Duration::from_millis(20));
+
+        // Batches of results do not restart the search timer
+        std::thread::sleep(Duration::from_millis(5));
+        machine.transition(ResearchEvent::SearchResultsReceived(1)).unwrap();
+        assert!(machine.elapsed() >= Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,416 @@
+//! Altium Designer `.PcbDoc` / `.PrjPCB` import
+//!
+//! A minimal reader for the binary (OLE2 compound document) board format. It
+//! reads the board outline and layer stack from `Board6`, straight tracks from
+//! `Tracks6` and net names from `Nets6`. Copper arcs and polygon pours are
+//! reported as unsupported rather than silently dropped. Coordinates are moved
+//! so the outline's lower-left corner is the board origin.
+
+use crate::errors::PcbError;
+use crate::{Layer, PcbDesign, Trace};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+
+/// Signature at the start of every OLE2 compound document
+const OLE2_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Altium internal units (1/10000 mil) per millimetre
+const UNITS_PER_MM: f64 = 10000.0 / 0.0254;
+
+/// Altium layer ids
+const TOP_LAYER: u8 = 1;
+const BOTTOM_LAYER: u8 = 32;
+
+/// Primitive not owned by a component or polygon
+const NO_OWNER: u16 = 0xFFFF;
+
+/// Binary record type tags
+const ARC_RECORD: u8 = 1;
+const TRACK_RECORD: u8 = 4;
+
+impl PcbDesign {
+    /// Import an Altium `.PcbDoc`, or the first `.PcbDoc` referenced by a `.PrjPCB`
+    pub fn from_altium_pcb(path: &Path) -> Result<PcbDesign, PcbError> {
+        let is_project = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("prjpcb"));
+        if is_project {
+            let pcb_path = pcbdoc_from_project(path)?;
+            return PcbDesign::from_altium_pcb(&pcb_path);
+        }
+
+        let mut file = File::open(path)?;
+        let mut magic = [0u8; 8];
+        let read = file.read(&mut magic)?;
+        if read < magic.len() || magic != OLE2_MAGIC {
+            let text = std::fs::read(path)?;
+            if text.starts_with(b"|RECORD=") {
+                return Err(PcbError::UnsupportedFeature(
+                    "ASCII PcbDoc files; save the board in binary format".to_string(),
+                ));
+            }
+            return Err(parse_error("not an OLE2 compound document".to_string()));
+        }
+
+        file.rewind()?;
+        let mut compound = cfb::CompoundFile::open(file)?;
+        parse_altium_pcbdoc(&mut compound)
+    }
+}
+
+/// Resolve the first `DocumentPath=*.PcbDoc` entry of a project file
+fn pcbdoc_from_project(project: &Path) -> Result<PathBuf, PcbError> {
+    let text = std::fs::read_to_string(project)?;
+    let document = text
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("DocumentPath="))
+        .find(|document| document.to_lowercase().ends_with(".pcbdoc"))
+        .ok_or_else(|| parse_error("project does not reference a .PcbDoc".to_string()))?;
+
+    // Project paths use Windows separators
+    let relative: PathBuf = document.split('\\').collect();
+    Ok(project.parent().unwrap_or(Path::new(".")).join(relative))
+}
+
+/// Parse an opened `.PcbDoc` compound document
+pub fn parse_altium_pcbdoc<F: Read + Seek>(compound: &mut cfb::CompoundFile<F>) -> Result<PcbDesign, PcbError> {
+    let board_data = read_stream(compound, "/Board6/Data")?
+        .ok_or_else(|| parse_error("missing Board6 stream".to_string()))?;
+    let board = text_records(&board_data)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| parse_error("empty Board6 stream".to_string()))?;
+
+    if let Some(polygons) = read_stream(compound, "/Polygons6/Data")? {
+        let count = text_records(&polygons)?.len();
+        if count > 0 {
+            return Err(PcbError::UnsupportedFeature(format!(
+                "{} polygon pour(s); remove or convert them to regions before importing",
+                count
+            )));
+        }
+    }
+
+    if let Some(arcs) = read_stream(compound, "/Arcs6/Data")? {
+        if let Some(arc) = binary_records(&arcs, ARC_RECORD)?
+            .into_iter()
+            .find(|arc| copper_layer(arc[0]).is_some() && read_u16(arc, 7) == Some(NO_OWNER))
+        {
+            let x = read_i32(&arc, 13).unwrap_or_default() as f64 / UNITS_PER_MM;
+            let y = read_i32(&arc, 17).unwrap_or_default() as f64 / UNITS_PER_MM;
+            return Err(PcbError::UnsupportedFeature(format!(
+                "arc trace centred at ({:.3}, {:.3}) mm; only straight tracks are imported",
+                x, y
+            )));
+        }
+    }
+
+    let net_names: Vec<String> = match read_stream(compound, "/Nets6/Data")? {
+        Some(nets) => text_records(&nets)?
+            .into_iter()
+            .map(|net| net.get("NAME").cloned().unwrap_or_default())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut design = PcbDesign::default();
+    let ((origin_x, origin_y), (width, height)) = board_outline(&board);
+    design.width = width;
+    design.height = height;
+    design.layer_count = copper_layer_count(&board);
+
+    if let Some(tracks) = read_stream(compound, "/Tracks6/Data")? {
+        for track in binary_records(&tracks, TRACK_RECORD)? {
+            let Some(layer) = copper_layer(track[0]) else {
+                continue;
+            };
+            // Footprint and polygon primitives aren't routed traces
+            if read_u16(&track, 5) != Some(NO_OWNER) || read_u16(&track, 7) != Some(NO_OWNER) {
+                continue;
+            }
+
+            let field = |offset: usize| {
+                read_i32(&track, offset)
+                    .map(|value| value as f64 / UNITS_PER_MM)
+                    .ok_or_else(|| parse_error("truncated track record".to_string()))
+            };
+            let point = |offset: usize| {
+                Ok::<_, PcbError>((field(offset)? - origin_x, field(offset + 4)? - origin_y))
+            };
+            let net = read_u16(&track, 3).unwrap_or(NO_OWNER);
+
+            design.traces.push(Trace {
+                net_name: net_names.get(net as usize).cloned().unwrap_or_default(),
+                width: field(29)?,
+                layer,
+                points: vec![point(13)?, point(21)?],
+            });
+        }
+    }
+
+    Ok(design)
+}
+
+/// Map an Altium layer id to a copper layer
+fn copper_layer(id: u8) -> Option<Layer> {
+    match id {
+        TOP_LAYER => Some(Layer::Top),
+        BOTTOM_LAYER => Some(Layer::Bottom),
+        2..=31 => Some(Layer::Inner(id - 1)),
+        _ => None,
+    }
+}
+
+/// Lower-left corner and size of the `VX<n>`/`VY<n>` outline vertices
+fn board_outline(board: &HashMap<String, String>) -> ((f64, f64), (f64, f64)) {
+    let vertices: Vec<(f64, f64)> = (0..)
+        .map_while(|i| {
+            let x = parse_length(board.get(&format!("VX{}", i))?)?;
+            let y = parse_length(board.get(&format!("VY{}", i))?)?;
+            Some((x, y))
+        })
+        .collect();
+
+    if vertices.is_empty() {
+        return ((0.0, 0.0), (0.0, 0.0));
+    }
+    let range = |values: Vec<f64>| {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (min, max - min)
+    };
+    let (x, width) = range(vertices.iter().map(|v| v.0).collect());
+    let (y, height) = range(vertices.iter().map(|v| v.1).collect());
+    ((x, y), (width, height))
+}
+
+/// Count copper layers by following the `LAYER<n>NEXT` stack from the top layer
+fn copper_layer_count(board: &HashMap<String, String>) -> u8 {
+    let mut count = 1;
+    let mut current = TOP_LAYER;
+
+    while current != BOTTOM_LAYER && count < BOTTOM_LAYER {
+        let next = board
+            .get(&format!("LAYER{}NEXT", current))
+            .and_then(|next| next.parse::<u8>().ok());
+        match next {
+            Some(next) if copper_layer(next).is_some() && next != current => {
+                current = next;
+                count += 1;
+            }
+            _ => return 2,
+        }
+    }
+
+    count
+}
+
+/// Parse an Altium length such as "1000mil" or "25.4mm" into millimetres
+fn parse_length(value: &str) -> Option<f64> {
+    let value = value.trim().to_lowercase();
+    if let Some(mils) = value.strip_suffix("mil") {
+        mils.trim().parse::<f64>().ok().map(|mils| mils * 0.0254)
+    } else if let Some(mm) = value.strip_suffix("mm") {
+        mm.trim().parse().ok()
+    } else {
+        value.parse::<f64>().ok().map(|mils| mils * 0.0254)
+    }
+}
+
+fn read_stream<F: Read + Seek>(
+    compound: &mut cfb::CompoundFile<F>,
+    path: &str,
+) -> Result<Option<Vec<u8>>, PcbError> {
+    if !compound.is_stream(path) {
+        return Ok(None);
+    }
+    let mut data = Vec::new();
+    compound.open_stream(path)?.read_to_end(&mut data)?;
+    Ok(Some(data))
+}
+
+/// Split `[u32 length][|KEY=VALUE|...]` records into key-value maps
+fn text_records(data: &[u8]) -> Result<Vec<HashMap<String, String>>, PcbError> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let length = read_u32(data, offset)
+            .ok_or_else(|| parse_error("truncated record header".to_string()))? as usize;
+        let body = data
+            .get(offset + 4..offset + 4 + length)
+            .ok_or_else(|| parse_error("truncated record".to_string()))?;
+        offset += 4 + length;
+
+        let text = String::from_utf8_lossy(body);
+        let record = text
+            .trim_end_matches('\0')
+            .split('|')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (key.trim().to_uppercase(), value.to_string()))
+            .collect();
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Split `[u8 type][u32 length][payload]` records, keeping payloads of `record_type`
+fn binary_records(data: &[u8], record_type: u8) -> Result<Vec<Vec<u8>>, PcbError> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let kind = data[offset];
+        let length = read_u32(data, offset + 1)
+            .ok_or_else(|| parse_error("truncated record header".to_string()))? as usize;
+        let payload = data
+            .get(offset + 5..offset + 5 + length)
+            .ok_or_else(|| parse_error("truncated record".to_string()))?;
+        offset += 5 + length;
+
+        if kind != record_type {
+            return Err(parse_error(format!("unexpected record type {}", kind)));
+        }
+        if payload.is_empty() {
+            return Err(parse_error("empty record".to_string()));
+        }
+        records.push(payload.to_vec());
+    }
+
+    Ok(records)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn read_i32(data: &[u8], offset: usize) -> Option<i32> {
+    Some(i32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn parse_error(reason: String) -> PcbError {
+    PcbError::ParseError {
+        format: "Altium".to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+
+    fn text_record(text: &str) -> Vec<u8> {
+        let mut record = (text.len() as u32 + 1).to_le_bytes().to_vec();
+        record.extend_from_slice(text.as_bytes());
+        record.push(0);
+        record
+    }
+
+    fn track_record(layer: u8, net: u16, component: u16, start: (i32, i32), end: (i32, i32), width: i32) -> Vec<u8> {
+        let mut payload = vec![layer, 0, 0];
+        payload.extend_from_slice(&net.to_le_bytes());
+        payload.extend_from_slice(&NO_OWNER.to_le_bytes());
+        payload.extend_from_slice(&component.to_le_bytes());
+        payload.extend_from_slice(&[0; 4]);
+        for value in [start.0, start.1, end.0, end.1, width] {
+            payload.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let mut record = vec![TRACK_RECORD];
+        record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        record.extend_from_slice(&payload);
+        record
+    }
+
+    fn pcbdoc(streams: &[(&str, Vec<u8>)]) -> cfb::CompoundFile<Cursor<Vec<u8>>> {
+        let mut compound = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+        for (path, data) in streams {
+            let storage = Path::new(path).parent().unwrap();
+            if !compound.exists(storage) {
+                compound.create_storage(storage).unwrap();
+            }
+            let mut stream = compound.create_stream(path).unwrap();
+            stream.write_all(data).unwrap();
+            stream.flush().unwrap();
+        }
+        compound
+    }
+
+    fn board_record() -> Vec<u8> {
+        text_record(
+            "|RECORD=Board|VX0=1000mil|VY0=500mil|VX1=3000mil|VY1=500mil|VX2=3000mil|VY2=1500mil\
+             |VX3=1000mil|VY3=1500mil|LAYER1NEXT=2|LAYER2NEXT=3|LAYER3NEXT=32|LAYER32NEXT=0",
+        )
+    }
+
+    #[test]
+    fn test_parse_board_and_tracks() {
+        let mil = 10000;
+        // The outline starts at (1000, 500) mil, which becomes the board origin
+        let origin = (1000 * mil, 500 * mil);
+        let mut tracks = track_record(1, 0, NO_OWNER, origin, (1100 * mil, 500 * mil), 10 * mil);
+        tracks.extend(track_record(32, 1, NO_OWNER, origin, (1000 * mil, 550 * mil), 8 * mil));
+        // Footprint outline on the top overlay and a footprint pad track are skipped
+        tracks.extend(track_record(33, NO_OWNER, NO_OWNER, (0, 0), (1, 1), 1));
+        tracks.extend(track_record(1, 0, 3, (0, 0), (1, 1), 1));
+
+        let mut nets = text_record("|NAME=GND");
+        nets.extend(text_record("|NAME=VCC"));
+
+        let mut compound = pcbdoc(&[
+            ("/Board6/Data", board_record()),
+            ("/Nets6/Data", nets),
+            ("/Tracks6/Data", tracks),
+        ]);
+        let design = parse_altium_pcbdoc(&mut compound).unwrap();
+
+        assert!((design.width - 50.8).abs() < 1e-9);
+        assert!((design.height - 25.4).abs() < 1e-9);
+        assert_eq!(design.layer_count, 4);
+        assert_eq!(design.traces.len(), 2);
+        assert_eq!(design.traces[0].net_name, "GND");
+        assert_eq!(design.traces[0].layer, Layer::Top);
+        assert_eq!(design.traces[0].points[0], (0.0, 0.0));
+        assert!((design.traces[0].points[1].0 - 2.54).abs() < 1e-9);
+        assert!((design.traces[1].points[1].1 - 1.27).abs() < 1e-9);
+        assert!((design.traces[0].width - 0.254).abs() < 1e-9);
+        assert_eq!(design.traces[1].net_name, "VCC");
+        assert_eq!(design.traces[1].layer, Layer::Bottom);
+    }
+
+    #[test]
+    fn test_polygon_pours_are_unsupported() {
+        let mut compound = pcbdoc(&[
+            ("/Board6/Data", board_record()),
+            ("/Polygons6/Data", text_record("|LAYER=TOP|NET=0|POLYGONTYPE=Polygon")),
+        ]);
+
+        let error = parse_altium_pcbdoc(&mut compound).unwrap_err();
+        assert!(matches!(error, PcbError::UnsupportedFeature(message) if message.contains("polygon")));
+    }
+
+    #[test]
+    fn test_rejects_non_altium_files() {
+        let dir = std::env::temp_dir().join(format!("opencircuit-altium-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let ascii = dir.join("board.PcbDoc");
+        std::fs::write(&ascii, "|RECORD=Board|VERSION=1").unwrap();
+        assert!(matches!(PcbDesign::from_altium_pcb(&ascii), Err(PcbError::UnsupportedFeature(_))));
+
+        let project = dir.join("board.PrjPCB");
+        std::fs::write(&project, "[Design]\n[Document1]\nDocumentPath=Missing\\board.PcbDoc\n").unwrap();
+        assert!(matches!(PcbDesign::from_altium_pcb(&project), Err(PcbError::IoError(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
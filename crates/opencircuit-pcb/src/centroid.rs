@@ -0,0 +1,161 @@
+//! Pick-and-place centroid export for assembly
+//!
+//! One CSV row per placement in board millimetres. The height column is empty
+//! when no body height has been recorded for the component. The SMT
+//! pick-and-place file only lists outer-layer placements and leaves the value
+//! column empty, as placements carry no component value.
+
+use crate::errors::PcbError;
+use crate::{ComponentPlacement, Layer, PcbDesign};
+use std::path::Path;
+
+const CENTROID_HEADER: &str = "Designator,Footprint,Mid X,Mid Y,Rotation,Layer,Height";
+const PICK_AND_PLACE_HEADER: &str = "Ref,Val,Package,PosX,PosY,Rot,Side";
+
+impl PcbDesign {
+    /// Write the pick-and-place centroid file
+    pub fn export_centroid(&self, path: &Path) -> Result<(), PcbError> {
+        std::fs::write(path, self.to_centroid_csv())?;
+        Ok(())
+    }
+
+    /// Render the pick-and-place centroid CSV
+    pub fn to_centroid_csv(&self) -> String {
+        let rows = self.placements.iter().map(|placement| {
+            let layer = match placement.layer {
+                Layer::Top => "Top".to_string(),
+                Layer::Bottom => "Bottom".to_string(),
+                Layer::Inner(index) => format!("Inner{}", index),
+            };
+            let height = self
+                .component_height(&placement.component_id)
+                .map(|height| format!("{:.3}", height))
+                .unwrap_or_default();
+            format!(
+                "{},{},{},{},{}",
+                csv_field(&placement.component_id),
+                csv_field(placement.footprint.as_deref().unwrap_or("")),
+                position_fields(placement),
+                layer,
+                height
+            )
+        });
+        render_csv(CENTROID_HEADER, rows)
+    }
+
+    /// Render the SMT pick-and-place CSV, skipping inner-layer placements
+    pub fn to_pick_and_place_csv(&self) -> String {
+        let rows = self.placements.iter().filter_map(|placement| {
+            let side = match placement.layer {
+                Layer::Top => "top",
+                Layer::Bottom => "bot",
+                Layer::Inner(_) => return None,
+            };
+            Some(format!(
+                "{},,{},{},{}",
+                csv_field(&placement.component_id),
+                csv_field(placement.footprint.as_deref().unwrap_or("")),
+                position_fields(placement),
+                side
+            ))
+        });
+        render_csv(PICK_AND_PLACE_HEADER, rows)
+    }
+
+    /// Write the SMT pick-and-place file, returning the number of placements written
+    pub fn generate_pick_and_place(&self, path: &Path) -> Result<usize, PcbError> {
+        let csv = self.to_pick_and_place_csv();
+        std::fs::write(path, &csv)?;
+        Ok(csv.lines().count() - 1)
+    }
+}
+
+/// X, Y and rotation columns, which both exports share
+fn position_fields(placement: &ComponentPlacement) -> String {
+    format!("{:.3},{:.3},{:.1}", placement.x, placement.y, placement.rotation.rem_euclid(360.0))
+}
+
+/// Join a header and rows into a newline-terminated CSV
+fn render_csv(header: &str, rows: impl Iterator<Item = String>) -> String {
+    let mut csv = header.to_string();
+    for row in rows {
+        csv.push('\n');
+        csv.push_str(&row);
+    }
+    csv.push('\n');
+    csv
+}
+
+/// Quote fields containing separators or quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ComponentHeight;
+
+    fn placement(component_id: &str, footprint: Option<&str>, rotation: f64, layer: Layer) -> ComponentPlacement {
+        ComponentPlacement {
+            component_id: component_id.to_string(),
+            x: 10.5,
+            y: -2.25,
+            rotation,
+            layer,
+            footprint: footprint.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_centroid_rows_include_height() {
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.add_placement(placement("C1", Some("CAP_6.3x7.7"), 90.0, Layer::Top));
+        design.add_placement(placement("R1", Some("0805, hand solder"), -90.0, Layer::Bottom));
+        design.set_component_height(
+            "C1",
+            ComponentHeight { component_id: "C1".to_string(), height_mm: 7.7, body_x_mm: 6.6, body_y_mm: 6.6 },
+        );
+
+        let csv = design.to_centroid_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CENTROID_HEADER);
+        assert_eq!(lines[1], "C1,CAP_6.3x7.7,10.500,-2.250,90.0,Top,7.700");
+        assert_eq!(lines[2], "R1,\"0805, hand solder\",10.500,-2.250,270.0,Bottom,");
+    }
+
+    #[test]
+    fn test_export_centroid_writes_file() {
+        let path = std::env::temp_dir().join(format!("opencircuit-centroid-{}.csv", std::process::id()));
+        let mut design = PcbDesign::new(10.0, 10.0, 2);
+        design.add_placement(placement("U1", None, 0.0, Layer::Top));
+        design.export_centroid(&path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(contents, design.to_centroid_csv());
+        assert!(contents.contains("U1,,10.500,-2.250,0.0,Top,\n"));
+    }
+
+    #[test]
+    fn test_pick_and_place_sides() {
+        let path = std::env::temp_dir().join(format!("opencircuit-pnp-{}.csv", std::process::id()));
+        let mut design = PcbDesign::new(50.0, 50.0, 4);
+        design.add_placement(placement("U1", Some("SOIC-8"), 450.0, Layer::Top));
+        design.add_placement(placement("C2", Some("0402"), 0.0, Layer::Bottom));
+        design.add_placement(placement("R3", Some("0201"), 0.0, Layer::Inner(1)));
+
+        assert_eq!(design.generate_pick_and_place(&path).unwrap(), 2);
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(contents, design.to_pick_and_place_csv());
+        assert_eq!(
+            contents,
+            "Ref,Val,Package,PosX,PosY,Rot,Side\nU1,,SOIC-8,10.500,-2.250,90.0,top\nC2,,0402,10.500,-2.250,0.0,bot\n"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Design rule checks
+
+use crate::{DrcViolation, PcbDesign, Severity};
+use serde::{Deserialize, Serialize};
+
+/// IPC-2221 minimum annular ring for class B (mm)
+pub const IPC2221_CLASS_B_ANNULAR_RING_MM: f64 = 0.05;
+
+/// Annular ring commonly required by board fabs (mm)
+pub const FAB_ANNULAR_RING_MM: f64 = 0.1;
+
+/// Tolerance for rounding in imported dimensions (mm)
+const EPSILON_MM: f64 = 1e-9;
+
+/// Configurable design rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DrcRule {
+    /// Copper left around every drilled via and through-hole pad
+    AnnularRing { min_annular_ring_mm: f64 },
+    /// Tallest component body allowed above the board, e.g. for an enclosure
+    MaxComponentHeight { max_height_mm: f64 },
+    /// Fewest vias in the thermal pad of components whose ID matches the pattern
+    ThermalViaMinCount { component_pattern: String, min_vias: usize },
+}
+
+impl DrcRule {
+    /// Check the design against this rule
+    pub fn check(&self, design: &PcbDesign) -> Vec<DrcViolation> {
+        match self {
+            DrcRule::AnnularRing { min_annular_ring_mm } => check_annular_ring(design, *min_annular_ring_mm),
+            DrcRule::MaxComponentHeight { max_height_mm } => design.check_height_violations(*max_height_mm),
+            DrcRule::ThermalViaMinCount { component_pattern, min_vias } => {
+                design.check_thermal_via_count(component_pattern, *min_vias)
+            }
+        }
+    }
+}
+
+fn check_annular_ring(design: &PcbDesign, minimum: f64) -> Vec<DrcViolation> {
+    let violation = |what: String, location: (f64, f64), ring: f64| DrcViolation {
+        rule_name: "annular_ring".to_string(),
+        description: format!(
+            "{} at ({:.3}, {:.3}): annular ring {:.3} mm < {:.3} mm minimum",
+            what, location.0, location.1, ring, minimum
+        ),
+        location,
+        severity: Severity::Error,
+    };
+
+    let vias = design.vias.iter().filter_map(|via| {
+        let ring = (via.pad_diameter - via.drill_diameter) / 2.0;
+        (ring + EPSILON_MM < minimum)
+            .then(|| violation(format!("Via on net {}", via.net_name), (via.x, via.y), ring))
+    });
+
+    let pads = design.pads.iter().filter_map(|board_pad| {
+        let pad = &board_pad.pad;
+        let drill = pad.drill_diameter?;
+        let ring = (pad.pad_size.0.min(pad.pad_size.1) - drill) / 2.0;
+        (ring + EPSILON_MM < minimum).then(|| {
+            violation(
+                format!("Pad {}-{} on net {}", board_pad.component_id, pad.name, board_pad.net_name),
+                (pad.x, pad.y),
+                ring,
+            )
+        })
+    });
+
+    vias.chain(pads).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardPad, ComponentHeight, Layer, Pad, Via};
+
+    fn via(net_name: &str, pad_diameter: f64, drill_diameter: f64) -> Via {
+        Via {
+            net_name: net_name.to_string(),
+            x: 10.0,
+            y: 5.0,
+            pad_diameter,
+            drill_diameter,
+        }
+    }
+
+    #[test]
+    fn test_annular_ring_vias() {
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.add_drc_rule(DrcRule::AnnularRing { min_annular_ring_mm: FAB_ANNULAR_RING_MM });
+        design.add_via(via("GND", 0.6, 0.3));
+        design.add_via(via("VCC", 0.45, 0.3));
+        design.add_via(via("SIG", 0.5, 0.3));
+
+        let violations = design.run_drc().unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_name, "annular_ring");
+        assert_eq!(violations[0].location, (10.0, 5.0));
+        assert!(violations[0].description.contains("net VCC"));
+        assert!(violations[0].description.contains("0.075 mm < 0.100 mm"));
+    }
+
+    #[test]
+    fn test_annular_ring_through_hole_pads() {
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.add_drc_rule(DrcRule::AnnularRing { min_annular_ring_mm: IPC2221_CLASS_B_ANNULAR_RING_MM });
+        for (name, drill) in [("1", Some(1.0)), ("2", Some(1.4)), ("3", None)] {
+            design.add_pad(BoardPad {
+                component_id: "J1".to_string(),
+                net_name: "GND".to_string(),
+                pad: Pad {
+                    name: name.to_string(),
+                    x: 0.0,
+                    y: 0.0,
+                    drill_diameter: drill,
+                    pad_size: (1.7, 1.45),
+                    layer: Layer::Top,
+                },
+            });
+        }
+
+        let violations = design.run_drc().unwrap();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].description.starts_with("Pad J1-2 on net GND"));
+    }
+
+    #[test]
+    fn test_max_component_height_rule() {
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.add_drc_rule(DrcRule::MaxComponentHeight { max_height_mm: 5.0 });
+        design.set_component_height(
+            "J1",
+            ComponentHeight { component_id: "J1".to_string(), height_mm: 11.0, body_x_mm: 9.0, body_y_mm: 14.5 },
+        );
+
+        let violations = design.run_drc().unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_name, "component_height");
+    }
+}
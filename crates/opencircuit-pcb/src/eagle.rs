@@ -0,0 +1,449 @@
+//! Eagle `.brd` board import
+//!
+//! Reads the `<eagle>/<drawing>/<board>` XML tree: signal wires become traces,
+//! signal vias become vias, elements become placements and the dimension layer
+//! sets the board size. Coordinates are moved so the outline's lower-left
+//! corner is the board origin.
+
+use crate::errors::PcbError;
+use crate::{ComponentPlacement, Layer, PcbDesign, Trace, Via};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Eagle layer numbers
+const TOP_COPPER: u8 = 1;
+const BOTTOM_COPPER: u8 = 16;
+const UNROUTED: u8 = 19;
+const DIMENSION: u8 = 20;
+
+impl PcbDesign {
+    /// Import an Eagle board file
+    pub fn from_eagle_brd(path: &Path) -> Result<PcbDesign, PcbError> {
+        let xml = std::fs::read_to_string(path)?;
+        parse_eagle_brd(&xml)
+    }
+}
+
+/// Map an Eagle copper layer number to a board layer
+pub fn eagle_layer(number: u8) -> Option<Layer> {
+    match number {
+        TOP_COPPER => Some(Layer::Top),
+        BOTTOM_COPPER => Some(Layer::Bottom),
+        2..=15 => Some(Layer::Inner(number - 1)),
+        _ => None,
+    }
+}
+
+/// Parse Eagle board XML
+pub fn parse_eagle_brd(xml: &str) -> Result<PcbDesign, PcbError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut design = PcbDesign::default();
+    let mut path: Vec<String> = Vec::new();
+    let mut signal: Option<String> = None;
+    let mut placements = Vec::new();
+    let mut outline = Extents::default();
+    let mut inner_layers = BTreeSet::new();
+    let mut found_board = false;
+
+    loop {
+        let event = reader.read_event().map_err(|e| parse_error(e.to_string()))?;
+        let (element, is_empty) = match &event {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(_) => {
+                if path.pop().as_deref() == Some("signal") {
+                    signal = None;
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        let name = String::from_utf8_lossy(element.local_name().as_ref()).to_string();
+        let parent = path.last().map(String::as_str);
+
+        match (name.as_str(), parent) {
+            ("board", Some("drawing")) => found_board = path.first().map(String::as_str) == Some("eagle"),
+            ("signal", Some("signals")) => signal = Some(attribute(element, "name")?),
+            ("wire", Some("plain")) => {
+                let wire = Wire::parse(element)?;
+                if wire.layer == DIMENSION {
+                    outline.include(wire.start);
+                    outline.include(wire.end);
+                } else if let Some(layer) = eagle_layer(wire.layer) {
+                    design.traces.push(wire.into_trace(String::new(), layer)?);
+                }
+            }
+            ("wire", Some("signal")) => {
+                let wire = Wire::parse(element)?;
+                let net_name = signal.clone().unwrap_or_default();
+                // Airwires are unrouted connections, not copper
+                if wire.layer != UNROUTED {
+                    let layer = eagle_layer(wire.layer).ok_or_else(|| {
+                        PcbError::UnsupportedFeature(format!(
+                            "Signal '{}' has a wire on non-copper layer {}",
+                            net_name, wire.layer
+                        ))
+                    })?;
+                    if let Layer::Inner(inner) = layer {
+                        inner_layers.insert(inner);
+                    }
+                    design.traces.push(wire.into_trace(net_name, layer)?);
+                }
+            }
+            ("polygon", Some("signal")) => {
+                return Err(PcbError::UnsupportedFeature(format!(
+                    "Copper pour in signal '{}'",
+                    signal.clone().unwrap_or_default()
+                )));
+            }
+            ("via", Some("signal")) => {
+                design.vias.push(parse_via(element, signal.clone().unwrap_or_default())?);
+            }
+            ("element", Some("elements")) => placements.push(parse_element(element)?),
+            _ => {}
+        }
+
+        // Empty tags have no children and no end event
+        if !is_empty {
+            path.push(name);
+        }
+    }
+
+    if !found_board {
+        return Err(parse_error("missing <eagle>/<drawing>/<board>".to_string()));
+    }
+
+    if outline.is_empty() {
+        for trace in &design.traces {
+            trace.points.iter().for_each(|&point| outline.include(point));
+        }
+        for placement in &placements {
+            outline.include((placement.x, placement.y));
+        }
+    }
+
+    let (origin_x, origin_y) = outline.origin();
+    for trace in &mut design.traces {
+        for point in &mut trace.points {
+            *point = (point.0 - origin_x, point.1 - origin_y);
+        }
+    }
+    for via in &mut design.vias {
+        via.x -= origin_x;
+        via.y -= origin_y;
+    }
+    // Placed after shifting so any reference text lands on the moved part
+    for mut placement in placements {
+        placement.x -= origin_x;
+        placement.y -= origin_y;
+        design.add_placement(placement);
+    }
+    design.width = outline.width();
+    design.height = outline.height();
+    design.layer_count = 2 + inner_layers.len() as u8;
+
+    Ok(design)
+}
+
+/// A straight `<wire>` segment
+struct Wire {
+    start: (f64, f64),
+    end: (f64, f64),
+    width: f64,
+    layer: u8,
+    curved: bool,
+}
+
+impl Wire {
+    fn parse(element: &BytesStart) -> Result<Self, PcbError> {
+        let curve = optional_attribute(element, "curve")?
+            .map(|curve| parse_number("curve", &curve))
+            .transpose()?
+            .unwrap_or(0.0);
+
+        Ok(Self {
+            start: (number_attribute(element, "x1")?, number_attribute(element, "y1")?),
+            end: (number_attribute(element, "x2")?, number_attribute(element, "y2")?),
+            width: number_attribute(element, "width")?,
+            layer: attribute(element, "layer")?
+                .parse()
+                .map_err(|_| parse_error("invalid wire layer".to_string()))?,
+            curved: curve != 0.0,
+        })
+    }
+
+    fn into_trace(self, net_name: String, layer: Layer) -> Result<Trace, PcbError> {
+        if self.curved {
+            return Err(PcbError::UnsupportedFeature(format!(
+                "Curved wire on layer {} at ({}, {})",
+                self.layer, self.start.0, self.start.1
+            )));
+        }
+
+        Ok(Trace {
+            net_name,
+            width: self.width,
+            layer,
+            points: vec![self.start, self.end],
+        })
+    }
+}
+
+/// Parse an `<element>`; rotations look like "R90", with "M" marking a bottom-side part
+fn parse_element(element: &BytesStart) -> Result<ComponentPlacement, PcbError> {
+    let rot = optional_attribute(element, "rot")?.unwrap_or_default();
+    let mirrored = rot.contains('M');
+    let angle = rot.trim_start_matches(['S', 'M']).trim_start_matches('R');
+    let rotation = if angle.is_empty() { 0.0 } else { parse_number("rot", angle)? };
+
+    Ok(ComponentPlacement {
+        component_id: attribute(element, "name")?,
+        x: number_attribute(element, "x")?,
+        y: number_attribute(element, "y")?,
+        rotation,
+        layer: if mirrored { Layer::Bottom } else { Layer::Top },
+        footprint: optional_attribute(element, "package")?,
+    })
+}
+
+/// Parse a `<via>`; without an explicit diameter Eagle sizes the ring from its
+/// default design rules (25% of the drill, clamped to 8-20 mil)
+fn parse_via(element: &BytesStart, net_name: String) -> Result<Via, PcbError> {
+    let drill_diameter = number_attribute(element, "drill")?;
+    let pad_diameter = match optional_attribute(element, "diameter")? {
+        Some(diameter) => parse_number("diameter", &diameter)?.max(drill_diameter),
+        None => drill_diameter + 2.0 * (drill_diameter * 0.25).clamp(0.2032, 0.508),
+    };
+
+    Ok(Via {
+        net_name,
+        x: number_attribute(element, "x")?,
+        y: number_attribute(element, "y")?,
+        pad_diameter,
+        drill_diameter,
+    })
+}
+
+/// Bounding box of points seen so far
+#[derive(Default)]
+struct Extents {
+    bounds: Option<(f64, f64, f64, f64)>,
+}
+
+impl Extents {
+    fn include(&mut self, (x, y): (f64, f64)) {
+        self.bounds = Some(match self.bounds {
+            Some((min_x, min_y, max_x, max_y)) => (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)),
+            None => (x, y, x, y),
+        });
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bounds.is_none()
+    }
+
+    fn origin(&self) -> (f64, f64) {
+        self.bounds.map_or((0.0, 0.0), |(min_x, min_y, _, _)| (min_x, min_y))
+    }
+
+    fn width(&self) -> f64 {
+        self.bounds.map_or(0.0, |(min_x, _, max_x, _)| max_x - min_x)
+    }
+
+    fn height(&self) -> f64 {
+        self.bounds.map_or(0.0, |(_, min_y, _, max_y)| max_y - min_y)
+    }
+}
+
+fn optional_attribute(element: &BytesStart, name: &str) -> Result<Option<String>, PcbError> {
+    let Some(attribute) = element
+        .try_get_attribute(name)
+        .map_err(|e| parse_error(e.to_string()))?
+    else {
+        return Ok(None);
+    };
+
+    let raw = std::str::from_utf8(&attribute.value).map_err(|e| parse_error(e.to_string()))?;
+    let value = quick_xml::escape::unescape(raw).map_err(|e| parse_error(e.to_string()))?;
+    Ok(Some(value.into_owned()))
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<String, PcbError> {
+    optional_attribute(element, name)?.ok_or_else(|| {
+        parse_error(format!(
+            "<{}> missing '{}' attribute",
+            String::from_utf8_lossy(element.local_name().as_ref()),
+            name
+        ))
+    })
+}
+
+fn number_attribute(element: &BytesStart, name: &str) -> Result<f64, PcbError> {
+    parse_number(name, &attribute(element, name)?)
+}
+
+fn parse_number(name: &str, value: &str) -> Result<f64, PcbError> {
+    value
+        .parse()
+        .map_err(|_| parse_error(format!("invalid {} '{}'", name, value)))
+}
+
+fn parse_error(reason: String) -> PcbError {
+    PcbError::ParseError {
+        format: "Eagle".to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(signals: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+  <drawing>
+    <layers>
+      <layer number="1" name="Top" color="4" fill="1" visible="yes" active="yes"/>
+      <layer number="16" name="Bottom" color="1" fill="1" visible="yes" active="yes"/>
+    </layers>
+    <board>
+      <plain>
+        <wire x1="0" y1="0" x2="50" y2="0" width="0" layer="20"/>
+        <wire x1="50" y1="0" x2="50" y2="30" width="0" layer="20"/>
+        <wire x1="50" y1="30" x2="0" y2="30" width="0" layer="20"/>
+        <wire x1="0" y1="30" x2="0" y2="0" width="0" layer="20"/>
+        <text x="5" y="5" size="1.27" layer="21">Rev A</text>
+      </plain>
+      <libraries>
+        <library name="rcl">
+          <packages>
+            <package name="R0805">
+              <wire x1="-0.4" y1="0.6" x2="0.4" y2="0.6" width="0.1" layer="21"/>
+            </package>
+          </packages>
+        </library>
+      </libraries>
+      <elements>
+        <element name="R1" library="rcl" package="R0805" value="10k" x="10" y="12.5" rot="R90"/>
+        <element name="U1" library="ic" package="SO8" value="NE555" x="25.4" y="15"/>
+        <element name="C1" library="rcl" package="C0805" value="100n" x="30" y="20" rot="MR180"/>
+      </elements>
+      <signals>
+{}
+      </signals>
+    </board>
+  </drawing>
+</eagle>"#,
+            signals
+        )
+    }
+
+    #[test]
+    fn test_parse_eagle_board() {
+        let xml = board(
+            r#"        <signal name="VCC">
+          <contactref element="R1" pad="1"/>
+          <wire x1="10" y1="12.5" x2="25.4" y2="12.5" width="0.254" layer="1"/>
+          <wire x1="25.4" y1="12.5" x2="30" y2="20" width="0.4064" layer="16"/>
+        </signal>
+        <signal name="SIG">
+          <wire x1="12" y1="5" x2="20" y2="5" width="0.2" layer="2"/>
+          <wire x1="12" y1="5" x2="30" y2="20" width="0" layer="19"/>
+          <via x="20" y="5" extent="1-16" drill="0.3" diameter="0.6"/>
+          <via x="22" y="5" extent="1-16" drill="0.4"/>
+        </signal>"#,
+        );
+
+        let design = parse_eagle_brd(&xml).unwrap();
+        assert_eq!(design.width, 50.0);
+        assert_eq!(design.height, 30.0);
+        assert_eq!(design.layer_count, 3);
+
+        assert_eq!(design.traces.len(), 3);
+        assert_eq!(design.traces[0].net_name, "VCC");
+        assert_eq!(design.traces[0].layer, Layer::Top);
+        assert_eq!(design.traces[0].points, vec![(10.0, 12.5), (25.4, 12.5)]);
+        assert_eq!(design.traces[1].layer, Layer::Bottom);
+        assert_eq!(design.traces[1].width, 0.4064);
+        assert_eq!(design.traces[2].layer, Layer::Inner(1));
+
+        assert_eq!(design.vias.len(), 2);
+        assert_eq!(design.vias[0].net_name, "SIG");
+        assert_eq!(design.vias[0].pad_diameter, 0.6);
+        assert!((design.vias[1].pad_diameter - (0.4 + 2.0 * 0.2032)).abs() < 1e-9);
+
+        assert_eq!(design.placements.len(), 3);
+        assert_eq!(design.placements[0].component_id, "R1");
+        assert_eq!(design.placements[0].rotation, 90.0);
+        assert_eq!(design.placements[1].rotation, 0.0);
+        assert_eq!(design.placements[2].layer, Layer::Bottom);
+        assert_eq!(design.placements[2].rotation, 180.0);
+        assert_eq!(design.placements[0].footprint.as_deref(), Some("R0805"));
+        // Package outlines aren't imported, so no footprint is known to label
+        assert!(design.silk_bottom.is_empty());
+    }
+
+    #[test]
+    fn test_eagle_board_origin_at_outline_corner() {
+        let xml = r#"<eagle><drawing><board>
+  <plain>
+    <wire x1="100" y1="50" x2="150" y2="50" width="0" layer="20"/>
+    <wire x1="150" y1="50" x2="150" y2="80" width="0" layer="20"/>
+  </plain>
+  <elements>
+    <element name="R1" library="rcl" package="R0805" value="10k" x="110" y="60"/>
+  </elements>
+  <signals>
+    <signal name="VCC">
+      <wire x1="110" y1="60" x2="120" y2="60" width="0.254" layer="1"/>
+      <via x="120" y="60" extent="1-16" drill="0.3" diameter="0.6"/>
+    </signal>
+  </signals>
+</board></drawing></eagle>"#;
+
+        let design = parse_eagle_brd(xml).unwrap();
+        assert_eq!((design.width, design.height), (50.0, 30.0));
+        assert_eq!(design.traces[0].points, vec![(10.0, 10.0), (20.0, 10.0)]);
+        assert_eq!((design.vias[0].x, design.vias[0].y), (20.0, 10.0));
+        assert_eq!((design.placements[0].x, design.placements[0].y), (10.0, 10.0));
+    }
+
+    #[test]
+    fn test_unsupported_eagle_features() {
+        let pour = board(
+            r#"        <signal name="GND">
+          <polygon width="0.254" layer="16"><vertex x="0" y="0"/></polygon>
+        </signal>"#,
+        );
+        assert!(matches!(parse_eagle_brd(&pour), Err(PcbError::UnsupportedFeature(_))));
+
+        let arc = board(
+            r#"        <signal name="GND">
+          <wire x1="0" y1="0" x2="5" y2="5" width="0.254" layer="1" curve="90"/>
+        </signal>"#,
+        );
+        assert!(matches!(parse_eagle_brd(&arc), Err(PcbError::UnsupportedFeature(_))));
+
+        assert!(matches!(
+            parse_eagle_brd("<eagle><drawing><schematic/></drawing></eagle>"),
+            Err(PcbError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_eagle_layer_mapping() {
+        assert_eq!(eagle_layer(1), Some(Layer::Top));
+        assert_eq!(eagle_layer(16), Some(Layer::Bottom));
+        assert_eq!(eagle_layer(2), Some(Layer::Inner(1)));
+        assert_eq!(eagle_layer(15), Some(Layer::Inner(14)));
+        assert_eq!(eagle_layer(21), None);
+    }
+}
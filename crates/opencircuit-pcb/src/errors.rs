@@ -0,0 +1,31 @@
+//! PCB error types
+
+use thiserror::Error;
+
+/// PCB import, export and design errors
+#[derive(Error, Debug)]
+pub enum PcbError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse {format} file: {reason}")]
+    ParseError {
+        format: String,
+        reason: String,
+    },
+
+    #[error("Cannot import {component} from the circuit: {reason}")]
+    NetlistImport {
+        component: String,
+        reason: String,
+    },
+
+    #[error("{tool} failed: {reason}")]
+    ExternalTool {
+        tool: String,
+        reason: String,
+    },
+
+    #[error("Unsupported feature: {0}")]
+    UnsupportedFeature(String),
+}
@@ -0,0 +1,121 @@
+//! Footprint libraries and pad lookup for placed components
+
+use crate::{ComponentPlacement, Layer, Pad, PcbDesign};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Footprints keyed by name, as referenced by `ComponentPlacement::footprint`
+pub type FootprintLibrary = HashMap<String, Footprint>;
+
+/// Land pattern with pads relative to the component origin
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Footprint {
+    pub pads: Vec<Pad>,
+    /// Courtyard outline polygon (mm)
+    pub courtyard: Vec<(f64, f64)>,
+}
+
+impl Footprint {
+    pub fn pad(&self, name: &str) -> Option<&Pad> {
+        self.pads.iter().find(|pad| pad.name == name)
+    }
+}
+
+impl PcbDesign {
+    /// Add footprints to the design, replacing any with the same name
+    pub fn load_footprint_library(&mut self, lib: FootprintLibrary) {
+        self.footprints.extend(lib);
+    }
+
+    /// Board position of a component pad. Footprints are rotated
+    /// counter-clockwise by the placement rotation; bottom-side parts are
+    /// mirrored about the Y axis first.
+    pub fn get_pad_position(&self, component_id: &str, pad_name: &str) -> Option<(f64, f64)> {
+        let placement = self.placements.iter().find(|p| p.component_id == component_id)?;
+        let footprint = self.footprints.get(placement.footprint.as_deref()?)?;
+        let pad = footprint.pad(pad_name)?;
+        Some(placement.to_board((pad.x, pad.y)))
+    }
+}
+
+impl ComponentPlacement {
+    /// Board position of a point given relative to the footprint origin
+    pub fn to_board(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let x = if self.layer == Layer::Bottom { -x } else { x };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        (self.x + x * cos - y * sin, self.y + x * sin + y * cos)
+    }
+
+    /// Footprint position of a board point, the inverse of [`Self::to_board`]
+    pub fn to_local(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let (dx, dy) = (x - self.x, y - self.y);
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (x, y) = (dx * cos + dy * sin, -dx * sin + dy * cos);
+        if self.layer == Layer::Bottom { (-x, y) } else { (x, y) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resistor_0805() -> Footprint {
+        let pad = |name: &str, x: f64| Pad {
+            name: name.to_string(),
+            x,
+            y: 0.0,
+            drill_diameter: None,
+            pad_size: (1.0, 1.3),
+            layer: Layer::Top,
+        };
+        Footprint {
+            pads: vec![pad("1", -0.95), pad("2", 0.95)],
+            courtyard: vec![(-1.7, -0.9), (1.7, -0.9), (1.7, 0.9), (-1.7, 0.9)],
+        }
+    }
+
+    fn design_with(rotation: f64, layer: Layer) -> PcbDesign {
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.load_footprint_library(FootprintLibrary::from([("0805".to_string(), resistor_0805())]));
+        design.add_placement(ComponentPlacement {
+            component_id: "R1".to_string(),
+            x: 10.0,
+            y: 20.0,
+            rotation,
+            layer,
+            footprint: Some("0805".to_string()),
+        });
+        design
+    }
+
+    fn assert_close(actual: Option<(f64, f64)>, expected: (f64, f64)) {
+        let (x, y) = actual.expect("pad should resolve");
+        assert!((x - expected.0).abs() < 1e-9 && (y - expected.1).abs() < 1e-9, "{:?} != {:?}", (x, y), expected);
+    }
+
+    #[test]
+    fn test_pad_position_with_rotation() {
+        assert_close(design_with(0.0, Layer::Top).get_pad_position("R1", "2"), (10.95, 20.0));
+        assert_close(design_with(90.0, Layer::Top).get_pad_position("R1", "2"), (10.0, 20.95));
+        assert_close(design_with(180.0, Layer::Top).get_pad_position("R1", "1"), (10.95, 20.0));
+    }
+
+    #[test]
+    fn test_bottom_side_pads_are_mirrored() {
+        assert_close(design_with(0.0, Layer::Bottom).get_pad_position("R1", "1"), (10.95, 20.0));
+
+        let placement = &design_with(30.0, Layer::Bottom).placements[0];
+        assert_close(Some(placement.to_local(placement.to_board((0.95, -0.4)))), (0.95, -0.4));
+    }
+
+    #[test]
+    fn test_unknown_pad_lookup() {
+        let design = design_with(0.0, Layer::Top);
+        assert_eq!(design.get_pad_position("R1", "3"), None);
+        assert_eq!(design.get_pad_position("R2", "1"), None);
+
+        let mut unassigned = PcbDesign::new(10.0, 10.0, 2);
+        unassigned.add_placement(ComponentPlacement { footprint: None, ..design.placements[0].clone() });
+        assert_eq!(unassigned.get_pad_position("R1", "1"), None);
+    }
+}
@@ -0,0 +1,418 @@
+//! IPC-7351B land pattern generation for common SMD packages
+//!
+//! Pads are computed from the component tolerances with the IPC-7351B
+//! equations at the `Most` (level A) density level:
+//!
+//! - `Zmax = Lmin + 2·JT + √(CL² + F² + P²)`
+//! - `Gmin = Smax − 2·JH − √(CS² + F² + P²)`
+//! - `Xmax = Wmin + 2·JS + √(CW² + F² + P²)`
+//!
+//! where `S = L − 2T` uses the RMS tolerance of the terminal span. Pad outer
+//! and width dimensions round up, and inner dimensions round down, to 0.05 mm.
+//! Footprints use IPC zero orientation with pin 1 at the upper left.
+
+use crate::footprint::Footprint;
+use crate::{Layer, Pad};
+
+/// Board fabrication tolerance (mm)
+pub const FABRICATION_TOLERANCE: f64 = 0.1;
+/// Component placement tolerance (mm)
+pub const PLACEMENT_TOLERANCE: f64 = 0.05;
+/// Courtyard excess for the `Most` density level (mm)
+pub const COURTYARD_EXCESS: f64 = 0.5;
+
+const ROUND_OFF: f64 = 0.05;
+
+/// SMD packages with built-in IPC-7351B land patterns
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SmdPackage {
+    Package0402,
+    Package0603,
+    Package0805,
+    Package1206,
+    SOT23,
+    SOT223,
+    SOIC8,
+    SOIC16,
+    QFP44,
+    QFN48,
+}
+
+/// Toe, heel and side solder fillet goals (mm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FilletGoals {
+    toe: f64,
+    heel: f64,
+    side: f64,
+}
+
+/// Chip components 0603 and larger
+const CHIP: FilletGoals = FilletGoals { toe: 0.55, heel: 0.0, side: 0.05 };
+/// Chip components smaller than 0603
+const SMALL_CHIP: FilletGoals = FilletGoals { toe: 0.20, heel: -0.05, side: 0.05 };
+/// Gull-wing leads with pitch above 0.625 mm
+const GULL_WING: FilletGoals = FilletGoals { toe: 0.55, heel: 0.45, side: 0.05 };
+/// Bottom-terminated quad flat no-lead
+const NO_LEAD: FilletGoals = FilletGoals { toe: 0.40, heel: 0.0, side: -0.04 };
+
+/// Minimum and maximum of a toleranced dimension (mm)
+type Range = (f64, f64);
+
+/// Pad arrangement around the package
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Layout {
+    /// One terminal on each side
+    Chip,
+    /// Two rows with pins counted down the left and up the right
+    Dual { pins_per_side: usize },
+    /// Two pins on the left, one centred on the right
+    Sot23,
+    /// Three pins on the left, the wide tab on the right
+    Sot223 { tab_width: Range },
+    /// Four rows counted counter-clockwise from the upper left
+    Quad { pins_per_side: usize },
+}
+
+/// Package dimensions as used by the IPC-7351B equations
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PackageDimensions {
+    /// Terminal span, outside to outside (L)
+    span: Range,
+    /// Terminal width (W)
+    terminal_width: Range,
+    /// Terminal length (T)
+    terminal_length: Range,
+    pitch: f64,
+    /// Maximum body extents (x, y) for the courtyard
+    body: (f64, f64),
+    /// Exposed thermal pad edge length
+    thermal_pad: Option<f64>,
+    goals: FilletGoals,
+    layout: Layout,
+}
+
+impl SmdPackage {
+    fn dimensions(self) -> PackageDimensions {
+        let chip = |span, terminal_width, terminal_length, goals| PackageDimensions {
+            span,
+            terminal_width,
+            terminal_length,
+            pitch: 0.0,
+            body: (span.1, terminal_width.1),
+            thermal_pad: None,
+            goals,
+            layout: Layout::Chip,
+        };
+
+        match self {
+            SmdPackage::Package0402 => chip((0.90, 1.10), (0.40, 0.60), (0.10, 0.30), SMALL_CHIP),
+            SmdPackage::Package0603 => chip((1.45, 1.75), (0.65, 0.95), (0.15, 0.45), CHIP),
+            SmdPackage::Package0805 => chip((1.80, 2.20), (1.05, 1.45), (0.25, 0.75), CHIP),
+            SmdPackage::Package1206 => chip((3.00, 3.40), (1.40, 1.80), (0.25, 0.75), CHIP),
+            SmdPackage::SOT23 => PackageDimensions {
+                span: (2.10, 2.64),
+                terminal_width: (0.30, 0.50),
+                terminal_length: (0.30, 0.60),
+                pitch: 0.95,
+                body: (1.40, 3.04),
+                thermal_pad: None,
+                goals: GULL_WING,
+                layout: Layout::Sot23,
+            },
+            SmdPackage::SOT223 => PackageDimensions {
+                span: (6.70, 7.30),
+                terminal_width: (0.66, 0.84),
+                terminal_length: (0.75, 1.10),
+                pitch: 2.30,
+                body: (3.70, 6.70),
+                thermal_pad: None,
+                goals: GULL_WING,
+                layout: Layout::Sot223 { tab_width: (2.90, 3.10) },
+            },
+            SmdPackage::SOIC8 => PackageDimensions {
+                span: (5.80, 6.20),
+                terminal_width: (0.31, 0.51),
+                terminal_length: (0.40, 1.27),
+                pitch: 1.27,
+                body: (4.00, 5.00),
+                thermal_pad: None,
+                goals: GULL_WING,
+                layout: Layout::Dual { pins_per_side: 4 },
+            },
+            SmdPackage::SOIC16 => PackageDimensions {
+                span: (5.80, 6.20),
+                terminal_width: (0.31, 0.51),
+                terminal_length: (0.40, 1.27),
+                pitch: 1.27,
+                body: (4.00, 10.00),
+                thermal_pad: None,
+                goals: GULL_WING,
+                layout: Layout::Dual { pins_per_side: 8 },
+            },
+            SmdPackage::QFP44 => PackageDimensions {
+                span: (11.75, 12.25),
+                terminal_width: (0.30, 0.45),
+                terminal_length: (0.45, 0.75),
+                pitch: 0.80,
+                body: (10.10, 10.10),
+                thermal_pad: None,
+                goals: GULL_WING,
+                layout: Layout::Quad { pins_per_side: 11 },
+            },
+            SmdPackage::QFN48 => PackageDimensions {
+                span: (6.90, 7.10),
+                terminal_width: (0.18, 0.30),
+                terminal_length: (0.30, 0.50),
+                pitch: 0.50,
+                body: (7.10, 7.10),
+                thermal_pad: Some(5.15),
+                goals: NO_LEAD,
+                layout: Layout::Quad { pins_per_side: 12 },
+            },
+        }
+    }
+}
+
+/// Pad row geometry from the IPC-7351B equations
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LandPattern {
+    /// Pad centre to pad centre across the package (C)
+    row_spacing: f64,
+    /// Pad length along the lead (Y in IPC terms)
+    pad_length: f64,
+    /// Pad width across the lead (X)
+    pad_width: f64,
+}
+
+fn land_pattern(dimensions: &PackageDimensions, terminal_width: Range) -> LandPattern {
+    let goals = dimensions.goals;
+    let (l_min, l_max) = dimensions.span;
+    let (t_min, t_max) = dimensions.terminal_length;
+    let (w_min, w_max) = terminal_width;
+
+    let l_tol = l_max - l_min;
+    let t_tol = t_max - t_min;
+    let w_tol = w_max - w_min;
+
+    let s_min = l_min - 2.0 * t_max;
+    let s_max = l_max - 2.0 * t_min;
+    let s_tol_rms = (l_tol.powi(2) + 2.0 * t_tol.powi(2)).sqrt();
+    let s_max_rms = s_max - ((s_max - s_min) - s_tol_rms) / 2.0;
+
+    let z_max = l_min + 2.0 * goals.toe + accumulated_tolerance(l_tol);
+    let g_min = s_max_rms - 2.0 * goals.heel - accumulated_tolerance(s_tol_rms);
+    let x_max = w_min + 2.0 * goals.side + accumulated_tolerance(w_tol);
+
+    let z = round_up(z_max);
+    let g = round_down(g_min);
+    LandPattern {
+        row_spacing: (z + g) / 2.0,
+        pad_length: (z - g) / 2.0,
+        pad_width: round_up(x_max),
+    }
+}
+
+fn accumulated_tolerance(component_tolerance: f64) -> f64 {
+    (component_tolerance.powi(2) + FABRICATION_TOLERANCE.powi(2) + PLACEMENT_TOLERANCE.powi(2)).sqrt()
+}
+
+fn round_up(value: f64) -> f64 {
+    clean((value / ROUND_OFF - 1e-9).ceil() * ROUND_OFF)
+}
+
+fn round_down(value: f64) -> f64 {
+    clean((value / ROUND_OFF + 1e-9).floor() * ROUND_OFF)
+}
+
+/// Strip floating point noise below a nanometre
+fn clean(value: f64) -> f64 {
+    (value * 1e6).round() / 1e6
+}
+
+/// Generate the IPC-7351B `Most` density footprint for a package
+pub fn generate_ipc7351(package: SmdPackage) -> Footprint {
+    let dimensions = package.dimensions();
+    let lands = land_pattern(&dimensions, dimensions.terminal_width);
+    let offset = lands.row_spacing / 2.0;
+    let pitch = dimensions.pitch;
+
+    // Pads on the left/right rows are long in X; top/bottom rows are long in Y
+    let side_pad = |x: f64, y: f64, width: f64| (x, y, (lands.pad_length, width));
+    let end_pad = |x: f64, y: f64| (x, y, (lands.pad_width, lands.pad_length));
+    // Position of the n-th pin counting down a row of `count` pins
+    let row = |index: usize, count: usize| (count as f64 - 1.0) / 2.0 * pitch - index as f64 * pitch;
+
+    let mut placements = Vec::new();
+    match dimensions.layout {
+        Layout::Chip => {
+            placements.push(side_pad(-offset, 0.0, lands.pad_width));
+            placements.push(side_pad(offset, 0.0, lands.pad_width));
+        }
+        Layout::Dual { pins_per_side } => {
+            for index in 0..pins_per_side {
+                placements.push(side_pad(-offset, row(index, pins_per_side), lands.pad_width));
+            }
+            for index in (0..pins_per_side).rev() {
+                placements.push(side_pad(offset, row(index, pins_per_side), lands.pad_width));
+            }
+        }
+        Layout::Sot23 => {
+            placements.push(side_pad(-offset, pitch, lands.pad_width));
+            placements.push(side_pad(-offset, -pitch, lands.pad_width));
+            placements.push(side_pad(offset, 0.0, lands.pad_width));
+        }
+        Layout::Sot223 { tab_width } => {
+            for index in 0..3 {
+                placements.push(side_pad(-offset, row(index, 3), lands.pad_width));
+            }
+            let tab = land_pattern(&dimensions, tab_width);
+            placements.push(side_pad(offset, 0.0, tab.pad_width));
+        }
+        Layout::Quad { pins_per_side } => {
+            for index in 0..pins_per_side {
+                placements.push(side_pad(-offset, row(index, pins_per_side), lands.pad_width));
+            }
+            for index in 0..pins_per_side {
+                placements.push(end_pad(-row(index, pins_per_side), -offset));
+            }
+            for index in 0..pins_per_side {
+                placements.push(side_pad(offset, -row(index, pins_per_side), lands.pad_width));
+            }
+            for index in 0..pins_per_side {
+                placements.push(end_pad(row(index, pins_per_side), offset));
+            }
+        }
+    }
+
+    let mut pads: Vec<Pad> = placements
+        .into_iter()
+        .enumerate()
+        .map(|(index, (x, y, pad_size))| smd_pad(index + 1, clean(x), clean(y), pad_size))
+        .collect();
+    if let Some(size) = dimensions.thermal_pad {
+        pads.push(smd_pad(pads.len() + 1, 0.0, 0.0, (size, size)));
+    }
+
+    let courtyard = courtyard(&pads, dimensions.body);
+    Footprint { pads, courtyard }
+}
+
+fn smd_pad(number: usize, x: f64, y: f64, pad_size: (f64, f64)) -> Pad {
+    Pad {
+        name: number.to_string(),
+        x,
+        y,
+        drill_diameter: None,
+        pad_size,
+        layer: Layer::Top,
+    }
+}
+
+/// Rectangle around the pads and body, grown by the courtyard excess
+fn courtyard(pads: &[Pad], body: (f64, f64)) -> Vec<(f64, f64)> {
+    let (half_x, half_y) = pads.iter().fold((body.0 / 2.0, body.1 / 2.0), |(hx, hy), pad| {
+        (hx.max(pad.x.abs() + pad.pad_size.0 / 2.0), hy.max(pad.y.abs() + pad.pad_size.1 / 2.0))
+    });
+    let x = clean(half_x + COURTYARD_EXCESS);
+    let y = clean(half_y + COURTYARD_EXCESS);
+    vec![(-x, -y), (x, -y), (x, y), (-x, y)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_pad(footprint: &Footprint, name: &str, x: f64, y: f64, size: (f64, f64)) {
+        let pad = footprint.pad(name).expect("pad should exist");
+        let close = |a: f64, b: f64| (a - b).abs() < 0.001;
+        assert!(
+            close(pad.x, x) && close(pad.y, y) && close(pad.pad_size.0, size.0) && close(pad.pad_size.1, size.1),
+            "pad {} at ({}, {}) size {:?}, expected ({}, {}) size {:?}",
+            name, pad.x, pad.y, pad.pad_size, x, y, size
+        );
+    }
+
+    // Expected values below are worked by hand from the IPC-7351B fillet goal
+    // tables and the package outline tolerances, not from the code above, so
+    // a wrong goal or tolerance shows up as a failure.
+
+    #[test]
+    fn test_chip_land_patterns() {
+        // RESC2012 (0805), Most: chip goals for 1608 and larger are toe 0.55,
+        // heel 0.00, side 0.05; EIA outline L 2.00±0.20, W 1.25±0.20,
+        // T 0.50±0.25.
+        //   Z = 1.80 + 2(0.55) + 0.415 = 3.315 -> 3.35
+        //   G = 1.406 - 2(0.00) - 0.820 = 0.586 -> 0.55
+        //   X = 1.05 + 2(0.05) + 0.415 = 1.565 -> 1.60
+        // giving C = 1.95 and 1.40 x 1.60 pads.
+        let r0805 = generate_ipc7351(SmdPackage::Package0805);
+        assert_eq!(r0805.pads.len(), 2);
+        assert_pad(&r0805, "1", -0.975, 0.0, (1.4, 1.6));
+        assert_pad(&r0805, "2", 0.975, 0.0, (1.4, 1.6));
+
+        let r0402 = generate_ipc7351(SmdPackage::Package0402);
+        assert_pad(&r0402, "1", -0.5125, 0.0, (0.525, 0.75));
+    }
+
+    #[test]
+    fn test_gull_wing_land_patterns() {
+        // SOIC127P600X175-8M: 1.27 pitch, 6.00 nominal lead span, 8 pins.
+        // Gull-wing goals above 0.625 pitch are toe 0.55, heel 0.45, side
+        // 0.05; JEDEC MS-012 gives L 5.80-6.20, T 0.40-1.27, W 0.31-0.51.
+        //   Z = 5.80 + 2(0.55) + 0.415 = 7.315 -> 7.35
+        //   G = 4.977 - 2(0.45) - 1.299 = 2.778 -> 2.75
+        //   X = 0.31 + 2(0.05) + 0.229 = 0.639 -> 0.65
+        // giving C = 5.05 and 2.30 x 0.65 pads, pin 1 at 1.5 pitches up.
+        let soic8 = generate_ipc7351(SmdPackage::SOIC8);
+        assert_eq!(soic8.pads.len(), 8);
+        assert_pad(&soic8, "2", -2.525, 0.635, (2.3, 0.65));
+        assert_pad(&soic8, "1", -2.525, 1.905, (2.3, 0.65));
+        assert_pad(&soic8, "4", -2.525, -1.905, (2.3, 0.65));
+        assert_pad(&soic8, "5", 2.525, -1.905, (2.3, 0.65));
+        assert_pad(&soic8, "8", 2.525, 1.905, (2.3, 0.65));
+
+        let sot23 = generate_ipc7351(SmdPackage::SOT23);
+        assert_pad(&sot23, "1", -1.0, 0.95, (1.8, 0.65));
+        assert_pad(&sot23, "3", 1.0, 0.0, (1.8, 0.65));
+
+        let sot223 = generate_ipc7351(SmdPackage::SOT223);
+        assert_eq!(sot223.pads.len(), 4);
+        assert_pad(&sot223, "2", -3.075, 0.0, (2.3, 1.0));
+        assert_pad(&sot223, "4", 3.075, 0.0, (2.3, 3.25));
+    }
+
+    #[test]
+    fn test_quad_land_patterns() {
+        let qfp44 = generate_ipc7351(SmdPackage::QFP44);
+        assert_eq!(qfp44.pads.len(), 44);
+        assert_pad(&qfp44, "1", -5.7375, 4.0, (1.925, 0.6));
+        assert_pad(&qfp44, "12", -4.0, -5.7375, (0.6, 1.925));
+        assert_pad(&qfp44, "23", 5.7375, -4.0, (1.925, 0.6));
+        assert_pad(&qfp44, "34", 4.0, 5.7375, (0.6, 1.925));
+
+        // QFN50P700X700X100-49M: 0.50 pitch, 7.00 body, 48 pins plus the
+        // thermal pad. No-lead goals are toe 0.40, heel 0.00, side -0.04;
+        // JEDEC MO-220 gives L 6.90-7.10, T 0.30-0.50, W 0.18-0.30.
+        //   Z = 6.90 + 2(0.40) + 0.229 = 7.929 -> 7.95
+        //   G = 6.373 - 2(0.00) - 0.364 = 6.009 -> 6.00
+        //   X = 0.18 - 2(0.04) + 0.164 = 0.264 -> 0.30
+        // giving C = 6.975 and 0.975 x 0.30 pads, pin 1 at 5.5 pitches up.
+        let qfn48 = generate_ipc7351(SmdPackage::QFN48);
+        assert_eq!(qfn48.pads.len(), 49);
+        assert_pad(&qfn48, "1", -3.4875, 2.75, (0.975, 0.3));
+        assert_pad(&qfn48, "2", -3.4875, 2.25, (0.975, 0.3));
+        assert_pad(&qfn48, "13", -2.75, -3.4875, (0.3, 0.975));
+        assert_pad(&qfn48, "49", 0.0, 0.0, (5.15, 5.15));
+    }
+
+    #[test]
+    fn test_courtyard_encloses_pads() {
+        let footprint = generate_ipc7351(SmdPackage::Package1206);
+        let (max_x, max_y) = footprint.courtyard[2];
+        for pad in &footprint.pads {
+            assert!(pad.x.abs() + pad.pad_size.0 / 2.0 + COURTYARD_EXCESS <= max_x + 1e-9);
+            assert!(pad.y.abs() + pad.pad_size.1 / 2.0 + COURTYARD_EXCESS <= max_y + 1e-9);
+        }
+    }
+}
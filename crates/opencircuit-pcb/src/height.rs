@@ -0,0 +1,103 @@
+//! Component Z-axis data for enclosure clearance checks
+
+use crate::{DrcViolation, PcbDesign, Severity};
+use serde::{Deserialize, Serialize};
+
+/// Tolerance for rounding in imported dimensions (mm)
+const EPSILON_MM: f64 = 1e-9;
+
+/// Body height above the board and body footprint of a component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentHeight {
+    pub component_id: String,
+    pub height_mm: f64,
+    pub body_x_mm: f64,
+    pub body_y_mm: f64,
+}
+
+impl PcbDesign {
+    /// Record the body height of a component, replacing any previous value
+    pub fn set_component_height(&mut self, component_id: &str, height: ComponentHeight) {
+        let height = ComponentHeight { component_id: component_id.to_string(), ..height };
+        self.component_heights.insert(component_id.to_string(), height);
+    }
+
+    /// Height of a component's body, if known
+    pub fn component_height(&self, component_id: &str) -> Option<f64> {
+        self.component_heights.get(component_id).map(|height| height.height_mm)
+    }
+
+    /// Components taller than `max_height_mm`, e.g. for low-profile designs
+    pub fn check_height_violations(&self, max_height_mm: f64) -> Vec<DrcViolation> {
+        let mut tall: Vec<&ComponentHeight> = self
+            .component_heights
+            .values()
+            .filter(|height| height.height_mm > max_height_mm + EPSILON_MM)
+            .collect();
+        tall.sort_by(|a, b| a.component_id.cmp(&b.component_id));
+
+        tall.into_iter()
+            .map(|height| {
+                let location = self
+                    .placements
+                    .iter()
+                    .find(|placement| placement.component_id == height.component_id)
+                    .map_or((0.0, 0.0), |placement| (placement.x, placement.y));
+                DrcViolation {
+                    rule_name: "component_height".to_string(),
+                    description: format!(
+                        "{} ({:.2} x {:.2} mm body) is {:.2} mm tall, exceeding the {:.2} mm maximum",
+                        height.component_id, height.body_x_mm, height.body_y_mm, height.height_mm, max_height_mm
+                    ),
+                    location,
+                    severity: Severity::Error,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComponentPlacement, Layer};
+
+    fn height(component_id: &str, height_mm: f64) -> ComponentHeight {
+        ComponentHeight { component_id: component_id.to_string(), height_mm, body_x_mm: 6.6, body_y_mm: 6.6 }
+    }
+
+    #[test]
+    fn test_height_violations() {
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.add_placement(ComponentPlacement {
+            component_id: "C10".to_string(),
+            x: 12.0,
+            y: 8.0,
+            rotation: 0.0,
+            layer: Layer::Top,
+            footprint: None,
+        });
+        design.set_component_height("C10", height("C10", 7.7));
+        design.set_component_height("R1", height("R1", 0.5));
+        design.set_component_height("L1", height("L1", 3.0));
+
+        let violations = design.check_height_violations(3.0);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_name, "component_height");
+        assert_eq!(violations[0].location, (12.0, 8.0));
+        assert!(violations[0].description.contains("C10 (6.60 x 6.60 mm body) is 7.70 mm tall"));
+        assert_eq!(design.check_height_violations(0.4).len(), 3);
+    }
+
+    #[test]
+    fn test_set_component_height_uses_given_id() {
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.set_component_height("U1", height("stale", 1.2));
+        design.set_component_height("U1", height("U1", 1.6));
+
+        assert_eq!(design.component_heights.len(), 1);
+        assert_eq!(design.component_heights["U1"].component_id, "U1");
+        assert_eq!(design.component_height("U1"), Some(1.6));
+        assert_eq!(design.component_height("U2"), None);
+    }
+}
@@ -0,0 +1,171 @@
+//! IPC-D-356A netlist export for bare-board electrical test
+//!
+//! Writes fixed-column 80 character records: parameter (`P`) and comment (`C`)
+//! header lines, one test point per pad and the `999` terminator. Pads are
+//! written as `317` (through-hole) or `327` (surface mount) records.
+
+use crate::errors::PcbError;
+use crate::{BoardPad, Layer, PcbDesign};
+use std::path::Path;
+
+/// Millimetres per IPC-D-356 unit (0.1 mil)
+const MM_PER_UNIT: f64 = 0.00254;
+
+/// Width of net name, reference designator and pin fields
+const NET_NAME_WIDTH: usize = 14;
+
+impl PcbDesign {
+    /// Write an IPC-D-356A netlist with one test record per pad
+    pub fn export_ipc356(&self, path: &Path) -> Result<(), PcbError> {
+        let job = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("OPENCIRCUIT");
+        std::fs::write(path, self.to_ipc356(job)?)?;
+        Ok(())
+    }
+
+    /// Render the IPC-D-356A netlist
+    pub fn to_ipc356(&self, job: &str) -> Result<String, PcbError> {
+        let mut lines = vec![
+            "C  IPC-D-356A NETLIST GENERATED BY OPENCIRCUIT".to_string(),
+            format!(
+                "C  BOARD SIZE X{} Y{}",
+                field(self.width, 6, "board width")?,
+                field(self.height, 6, "board height")?
+            ),
+            format!("P  JOB   {}", job),
+            "P  CODE  00".to_string(),
+            "P  UNITS CUST 0".to_string(),
+            "P  DIM   N".to_string(),
+            "P  VER   IPC-D-356A".to_string(),
+            "P  IMAGE PRIMARY".to_string(),
+        ];
+
+        for pad in &self.pads {
+            lines.push(self.pad_record(pad)?);
+        }
+        lines.push("999".to_string());
+
+        let mut netlist = lines.join("\n");
+        netlist.push('\n');
+        Ok(netlist)
+    }
+
+    fn pad_record(&self, board_pad: &BoardPad) -> Result<String, PcbError> {
+        let pad = &board_pad.pad;
+        let net_name = if board_pad.net_name.is_empty() { "N/C" } else { &board_pad.net_name };
+
+        let (code, hole, access, solder_mask) = match pad.drill_diameter {
+            Some(drill) => ("317", format!("D{}P", field(drill, 4, "drill diameter")?), "A00".to_string(), 3),
+            None => match pad.layer {
+                Layer::Bottom => ("327", " ".repeat(6), format!("A{:02}", self.layer_count), 2),
+                _ => ("327", " ".repeat(6), "A01".to_string(), 1),
+            },
+        };
+
+        Ok(format!(
+            "{}{:<net$.net$}   {:<6.6}-{:<4.4} {}{}X{}Y{}X{}Y{}R000 S{}",
+            code,
+            net_name,
+            board_pad.component_id,
+            pad.name,
+            hole,
+            access,
+            signed_field(pad.x, "pad X")?,
+            signed_field(pad.y, "pad Y")?,
+            field(pad.pad_size.0, 4, "pad width")?,
+            field(pad.pad_size.1, 4, "pad height")?,
+            solder_mask,
+            net = NET_NAME_WIDTH,
+        ))
+    }
+}
+
+/// Convert millimetres to a zero-padded field of 0.1 mil units
+fn field(mm: f64, digits: usize, name: &str) -> Result<String, PcbError> {
+    let units = (mm.abs() / MM_PER_UNIT).round() as u64;
+    if units >= 10u64.pow(digits as u32) {
+        return Err(PcbError::UnsupportedFeature(format!(
+            "{} of {} mm exceeds the IPC-D-356 field width",
+            name, mm
+        )));
+    }
+    Ok(format!("{:0width$}", units, width = digits))
+}
+
+fn signed_field(mm: f64, name: &str) -> Result<String, PcbError> {
+    let sign = if mm < 0.0 { '-' } else { '+' };
+    Ok(format!("{}{}", sign, field(mm, 6, name)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pad;
+
+    fn board_pad(component_id: &str, name: &str, net_name: &str, x: f64, y: f64, drill: Option<f64>, layer: Layer) -> BoardPad {
+        BoardPad {
+            component_id: component_id.to_string(),
+            net_name: net_name.to_string(),
+            pad: Pad {
+                name: name.to_string(),
+                x,
+                y,
+                drill_diameter: drill,
+                pad_size: (1.0, 0.5),
+                layer,
+            },
+        }
+    }
+
+    #[test]
+    fn test_ipc356_records() {
+        let mut design = PcbDesign::new(50.8, 25.4, 2);
+        design.add_pad(board_pad("R1", "1", "VCC", 25.4, 12.7, None, Layer::Top));
+        design.add_pad(board_pad("J1", "2", "GND", 2.54, -1.27, Some(0.8), Layer::Top));
+        design.add_pad(board_pad("C1", "1", "", 10.0, 10.0, None, Layer::Bottom));
+
+        let netlist = design.to_ipc356("demo").unwrap();
+        let lines: Vec<&str> = netlist.lines().collect();
+
+        assert!(lines.contains(&"C  BOARD SIZE X020000 Y010000"));
+        assert!(lines.contains(&"P  JOB   demo"));
+        assert!(lines.contains(&"P  UNITS CUST 0"));
+        assert_eq!(
+            lines[8],
+            "327VCC              R1    -1          A01X+010000Y+005000X0394Y0197R000 S1"
+        );
+        assert_eq!(
+            lines[9],
+            "317GND              J1    -2    D0315PA00X+001000Y-000500X0394Y0197R000 S3"
+        );
+        assert!(lines[10].starts_with("327N/C"));
+        assert!(lines[10].contains("A02X+003937Y+003937"));
+        assert_eq!(lines.last(), Some(&"999"));
+
+        // Fixed columns: access code at 39-41, X coordinate at 42
+        assert_eq!(&lines[8][38..41], "A01");
+        assert_eq!(&lines[9][32..38], "D0315P");
+        assert_eq!(&lines[9][41..42], "X");
+    }
+
+    #[test]
+    fn test_ipc356_rejects_oversized_fields() {
+        let mut design = PcbDesign::new(100.0, 100.0, 2);
+        design.add_pad(board_pad("R1", "1", "VCC", 0.0, 0.0, Some(30.0), Layer::Top));
+        assert!(matches!(design.to_ipc356("big"), Err(PcbError::UnsupportedFeature(_))));
+    }
+
+    #[test]
+    fn test_export_ipc356_writes_file() {
+        let path = std::env::temp_dir().join(format!("opencircuit-{}.ipc", std::process::id()));
+        let design = PcbDesign::new(10.0, 10.0, 2);
+        design.export_ipc356(&path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(contents.contains(&format!("P  JOB   opencircuit-{}", std::process::id())));
+        assert!(contents.ends_with("999\n"));
+    }
+}
@@ -0,0 +1,117 @@
+//! Routed net lengths for length matching
+//!
+//! A net's length is the sum of its trace segments on every layer plus a
+//! fixed allowance for each via, which stands in for the stub the signal
+//! travels through the board.
+
+use crate::{DrcViolation, PcbDesign, Severity};
+use std::collections::HashMap;
+
+/// Length added for each via on a net (mm)
+pub const VIA_LENGTH_PENALTY_MM: f64 = 0.5;
+
+/// Tolerance for rounding in segment lengths (mm)
+const EPSILON_MM: f64 = 1e-9;
+
+impl PcbDesign {
+    /// Routed length of each net with traces, vias included (mm)
+    pub fn net_length_report(&self) -> HashMap<String, f64> {
+        let mut lengths: HashMap<String, f64> = HashMap::new();
+        for trace in &self.traces {
+            let length: f64 = trace
+                .points
+                .windows(2)
+                .map(|segment| (segment[1].0 - segment[0].0).hypot(segment[1].1 - segment[0].1))
+                .sum();
+            *lengths.entry(trace.net_name.clone()).or_default() += length;
+        }
+        for via in &self.vias {
+            if let Some(length) = lengths.get_mut(&via.net_name) {
+                *length += VIA_LENGTH_PENALTY_MM;
+            }
+        }
+        lengths
+    }
+
+    /// Net pairs whose routed lengths differ by more than `max_skew_mm`
+    ///
+    /// Unrouted nets count as zero length.
+    pub fn length_matching_violations(&self, pairs: &[(&str, &str)], max_skew_mm: f64) -> Vec<DrcViolation> {
+        let lengths = self.net_length_report();
+        let length = |net: &str| lengths.get(net).copied().unwrap_or(0.0);
+
+        pairs
+            .iter()
+            .filter_map(|&(a, b)| {
+                let (length_a, length_b) = (length(a), length(b));
+                let skew = (length_a - length_b).abs();
+                if skew <= max_skew_mm + EPSILON_MM {
+                    return None;
+                }
+                let longer = if length_a >= length_b { a } else { b };
+                Some(DrcViolation {
+                    rule_name: "LengthMatching".to_string(),
+                    description: format!(
+                        "{} ({:.2} mm) and {} ({:.2} mm) differ by {:.2} mm, exceeding the {:.2} mm maximum skew",
+                        a, length_a, b, length_b, skew, max_skew_mm
+                    ),
+                    location: self.net_start(longer),
+                    severity: Severity::Error,
+                })
+            })
+            .collect()
+    }
+
+    /// First trace point of a net, where a violation marker is placed
+    fn net_start(&self, net_name: &str) -> (f64, f64) {
+        self.traces
+            .iter()
+            .filter(|trace| trace.net_name == net_name)
+            .find_map(|trace| trace.points.first().copied())
+            .unwrap_or((0.0, 0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Layer, Trace, Via};
+
+    fn trace(net_name: &str, layer: Layer, points: &[(f64, f64)]) -> Trace {
+        Trace { net_name: net_name.to_string(), width: 0.1, layer, points: points.to_vec() }
+    }
+
+    fn ddr_design() -> PcbDesign {
+        let mut design = PcbDesign::new(50.0, 50.0, 4);
+        // DQ0: 3-4-5 diagonal on top, a via, then 10 mm on an inner layer
+        design.add_trace(trace("DQ0", Layer::Top, &[(0.0, 0.0), (3.0, 4.0)]));
+        design.add_trace(trace("DQ0", Layer::Inner(1), &[(3.0, 4.0), (13.0, 4.0)]));
+        design.add_via(Via { net_name: "DQ0".to_string(), x: 3.0, y: 4.0, pad_diameter: 0.6, drill_diameter: 0.3 });
+        // DQ1: 15 mm on top with a dogleg
+        design.add_trace(trace("DQ1", Layer::Top, &[(0.0, 2.0), (5.0, 2.0), (5.0, 7.0), (10.0, 7.0)]));
+        design
+    }
+
+    #[test]
+    fn test_net_length_report() {
+        let lengths = ddr_design().net_length_report();
+        assert!((lengths["DQ0"] - (5.0 + 10.0 + VIA_LENGTH_PENALTY_MM)).abs() < 1e-9);
+        assert!((lengths["DQ1"] - 15.0).abs() < 1e-9);
+        assert_eq!(lengths.len(), 2);
+    }
+
+    #[test]
+    fn test_length_matching_violations() {
+        let design = ddr_design();
+        assert!(design.length_matching_violations(&[("DQ0", "DQ1")], 0.5).is_empty());
+
+        let violations = design.length_matching_violations(&[("DQ0", "DQ1"), ("DQ1", "DQS")], 0.25);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].rule_name, "LengthMatching");
+        assert_eq!(violations[0].location, (0.0, 0.0));
+        assert!(violations[0].description.contains("differ by 0.50 mm"));
+        // An unrouted net is as long as nothing
+        assert!(violations[1].description.contains("DQS (0.00 mm)"));
+        assert_eq!(violations[1].location, (0.0, 2.0));
+    }
+}
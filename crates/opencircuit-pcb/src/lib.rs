@@ -7,19 +7,49 @@
 //! - Via optimization
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub mod altium;
+pub mod centroid;
+pub mod drc;
+pub mod eagle;
+pub mod errors;
+pub mod footprint;
+pub mod footprint_generator;
+pub mod height;
+pub mod ipc356;
+pub mod length;
+pub mod netlist_import;
+pub mod silk;
+pub mod step;
+pub mod thermal;
+
+pub use drc::DrcRule;
+pub use errors::PcbError;
+pub use footprint::{Footprint, FootprintLibrary};
+pub use footprint_generator::{generate_ipc7351, SmdPackage};
+pub use height::ComponentHeight;
+pub use length::VIA_LENGTH_PENALTY_MM;
+pub use netlist_import::STUB_TRACE_WIDTH_MM;
+pub use silk::{SilkItem, SilkLayer, SilkLine, SilkText};
+pub use step::{BoardModel, ComponentBlock};
+pub use thermal::THERMAL_VIA_PAD_CLEARANCE_MM;
 
 /// PCB component placement
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ComponentPlacement {
     pub component_id: String,
     pub x: f64,
     pub y: f64,
     pub rotation: f64,
     pub layer: Layer,
+    /// Footprint name; placements with a footprint get a silk reference designator
+    #[serde(default)]
+    pub footprint: Option<String>,
 }
 
 /// PCB layer definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Layer {
     Top,
     Bottom,
@@ -27,7 +57,7 @@ pub enum Layer {
 }
 
 /// PCB trace routing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Trace {
     pub net_name: String,
     pub width: f64,
@@ -35,14 +65,60 @@ pub struct Trace {
     pub points: Vec<(f64, f64)>,
 }
 
+/// Copper pad
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pad {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    /// Drill diameter (mm) for through-hole pads
+    pub drill_diameter: Option<f64>,
+    /// Pad width and height (mm)
+    pub pad_size: (f64, f64),
+    pub layer: Layer,
+}
+
+/// Pad placed on the board, in board coordinates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardPad {
+    pub component_id: String,
+    pub net_name: String,
+    pub pad: Pad,
+}
+
+/// Plated via between copper layers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Via {
+    pub net_name: String,
+    pub x: f64,
+    pub y: f64,
+    pub pad_diameter: f64,
+    pub drill_diameter: f64,
+}
+
 /// PCB design representation
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct PcbDesign {
     pub width: f64,
     pub height: f64,
     pub layer_count: u8,
     pub placements: Vec<ComponentPlacement>,
     pub traces: Vec<Trace>,
+    #[serde(default)]
+    pub pads: Vec<BoardPad>,
+    #[serde(default)]
+    pub vias: Vec<Via>,
+    #[serde(default)]
+    pub drc_rules: Vec<DrcRule>,
+    #[serde(default)]
+    pub footprints: FootprintLibrary,
+    #[serde(default)]
+    pub silk_top: SilkLayer,
+    #[serde(default)]
+    pub silk_bottom: SilkLayer,
+    /// Body heights keyed by component ID
+    #[serde(default)]
+    pub component_heights: HashMap<String, ComponentHeight>,
 }
 
 impl PcbDesign {
@@ -53,10 +129,37 @@ impl PcbDesign {
             layer_count,
             placements: Vec::new(),
             traces: Vec::new(),
+            pads: Vec::new(),
+            vias: Vec::new(),
+            drc_rules: Vec::new(),
+            footprints: FootprintLibrary::new(),
+            silk_top: SilkLayer::default(),
+            silk_bottom: SilkLayer::default(),
+            component_heights: HashMap::new(),
         }
     }
     
+    /// Place a component, labelling it with its reference designator on the
+    /// silk screen if its footprint is in the library
+    ///
+    /// Load footprints before placing the parts that use them.
     pub fn add_placement(&mut self, placement: ComponentPlacement) {
+        let known_footprint = placement
+            .footprint
+            .as_deref()
+            .is_some_and(|name| self.footprints.contains_key(name));
+        if known_footprint {
+            let reference = SilkText {
+                x: placement.x,
+                y: placement.y,
+                text: placement.component_id.clone(),
+                rotation: placement.rotation,
+                height_mm: silk::REFERENCE_TEXT_HEIGHT_MM,
+            };
+            if let Some(silk) = self.silk_layer_mut(&placement.layer) {
+                silk.add_text(reference);
+            }
+        }
         self.placements.push(placement);
     }
     
@@ -64,9 +167,20 @@ impl PcbDesign {
         self.traces.push(trace);
     }
     
+    pub fn add_pad(&mut self, pad: BoardPad) {
+        self.pads.push(pad);
+    }
+    
+    pub fn add_via(&mut self, via: Via) {
+        self.vias.push(via);
+    }
+    
+    pub fn add_drc_rule(&mut self, rule: DrcRule) {
+        self.drc_rules.push(rule);
+    }
+    
     pub fn run_drc(&self) -> Result<Vec<DrcViolation>, anyhow::Error> {
-        // TODO: Implement design rule checking
-        Ok(Vec::new())
+        Ok(self.drc_rules.iter().flat_map(|rule| rule.check(self)).collect())
     }
 }
 
@@ -79,7 +193,7 @@ pub struct DrcViolation {
     pub severity: Severity,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Severity {
     Error,
     Warning,
@@ -0,0 +1,244 @@
+//! Import a schematic circuit onto the board
+//!
+//! Components are dropped on a grid for the designer (or the placer) to
+//! arrange, and every connection becomes a straight stub trace between the
+//! two component pads so the ratsnest is visible before routing. Circuit
+//! connections carry no pin numbers, so each connection takes the component's
+//! pad already on that net, or else its next unused pad in footprint order.
+
+use crate::errors::PcbError;
+use crate::{BoardPad, ComponentPlacement, Footprint, FootprintLibrary, Layer, Pad, PcbDesign, Trace};
+use opencircuit_circuit::{Circuit, Component};
+use std::collections::HashMap;
+
+/// Width of the stub traces drawn for each connection (mm)
+pub const STUB_TRACE_WIDTH_MM: f64 = 0.2;
+/// Clearance left between neighbouring courtyards on the import grid (mm)
+pub const IMPORT_GRID_MARGIN_MM: f64 = 2.0;
+
+impl PcbDesign {
+    /// Place every circuit component and add a stub trace per connection
+    ///
+    /// A component's footprint is the library entry keyed by its ID, or else
+    /// the entry keyed by its component type (e.g. `"Resistor"`). The library
+    /// is loaded into the design and the new placements are returned. A failed
+    /// import leaves the design unchanged.
+    pub fn import_netlist_from_circuit(
+        &mut self,
+        circuit: &Circuit,
+        footprint_library: &FootprintLibrary,
+    ) -> Result<Vec<ComponentPlacement>, PcbError> {
+        // Pads are only found to be missing part way through, so the import
+        // runs on a copy that replaces the design once it has succeeded
+        let mut design = self.clone();
+        let placements = design.place_and_connect(circuit, footprint_library)?;
+        *self = design;
+        Ok(placements)
+    }
+
+    fn place_and_connect(
+        &mut self,
+        circuit: &Circuit,
+        footprint_library: &FootprintLibrary,
+    ) -> Result<Vec<ComponentPlacement>, PcbError> {
+        let footprint_names = circuit
+            .components
+            .iter()
+            .map(|component| Ok((component.id.as_str(), footprint_name(component, footprint_library)?)))
+            .collect::<Result<HashMap<_, _>, PcbError>>()?;
+        self.load_footprint_library(footprint_library.clone());
+
+        let pitch = footprint_names
+            .values()
+            .filter_map(|name| footprint_library.get(name))
+            .map(footprint_extent)
+            .fold(0.0, f64::max)
+            + IMPORT_GRID_MARGIN_MM;
+        let columns = ((self.width / pitch).floor() as usize).max(1);
+
+        let mut placements = Vec::with_capacity(circuit.components.len());
+        for (index, component) in circuit.components.iter().enumerate() {
+            let placement = ComponentPlacement {
+                component_id: component.id.clone(),
+                x: pitch * ((index % columns) as f64 + 0.5),
+                y: pitch * ((index / columns) as f64 + 0.5),
+                rotation: 0.0,
+                layer: Layer::Top,
+                footprint: Some(footprint_names[component.id.as_str()].clone()),
+            };
+            self.add_placement(placement.clone());
+            placements.push(placement);
+        }
+
+        // Pad names used so far per component, with the net each is on
+        let mut assigned: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        for connection in &circuit.connections {
+            let from = self.assign_pad(&mut assigned, &connection.from, &connection.net_name, footprint_library)?;
+            let to = self.assign_pad(&mut assigned, &connection.to, &connection.net_name, footprint_library)?;
+            self.add_trace(Trace {
+                net_name: connection.net_name.clone(),
+                width: STUB_TRACE_WIDTH_MM,
+                layer: Layer::Top,
+                points: vec![from, to],
+            });
+        }
+
+        Ok(placements)
+    }
+
+    /// Board position of the component pad on `net`, assigning a free pad
+    /// and adding it to the board the first time the net reaches the component
+    fn assign_pad(
+        &mut self,
+        assigned: &mut HashMap<String, Vec<(String, String)>>,
+        component_id: &str,
+        net: &str,
+        footprint_library: &FootprintLibrary,
+    ) -> Result<(f64, f64), PcbError> {
+        let import_error = |reason: String| PcbError::NetlistImport { component: component_id.to_string(), reason };
+        let placement = self
+            .placements
+            .iter()
+            .find(|placement| placement.component_id == component_id)
+            .ok_or_else(|| import_error(format!("connection on net {} references an unknown component", net)))?;
+        let footprint = placement.footprint.as_deref().and_then(|name| footprint_library.get(name));
+        let pads = footprint.map(|footprint| footprint.pads.as_slice()).unwrap_or_default();
+
+        let used = assigned.entry(component_id.to_string()).or_default();
+        let pad_name = match used.iter().find(|(_, used_net)| used_net == net) {
+            Some((pad_name, _)) => pad_name.clone(),
+            None => {
+                let pad = pads
+                    .iter()
+                    .find(|pad| !used.iter().any(|(name, _)| *name == pad.name))
+                    .ok_or_else(|| import_error(format!("no free pad left for net {}", net)))?;
+                let (x, y) = placement.to_board((pad.x, pad.y));
+                let board_pad = BoardPad {
+                    component_id: component_id.to_string(),
+                    net_name: net.to_string(),
+                    pad: Pad { x, y, ..pad.clone() },
+                };
+                used.push((pad.name.clone(), net.to_string()));
+                self.add_pad(board_pad);
+                pad.name.clone()
+            }
+        };
+
+        self.get_pad_position(component_id, &pad_name)
+            .ok_or_else(|| import_error(format!("pad {} has no board position", pad_name)))
+    }
+}
+
+/// Library key of the footprint for `component`
+fn footprint_name(component: &Component, footprint_library: &FootprintLibrary) -> Result<String, PcbError> {
+    let type_name = format!("{:?}", component.component_type);
+    let name = [component.id.as_str(), type_name.as_str()]
+        .into_iter()
+        .find(|name| footprint_library.contains_key(*name))
+        .map(str::to_string);
+    name.ok_or_else(|| PcbError::NetlistImport {
+        component: component.id.clone(),
+        reason: format!("no footprint named {} or {} in the library", component.id, type_name),
+    })
+}
+
+/// Larger side of the footprint's bounding box, from the courtyard when it
+/// has one and otherwise from the pads
+fn footprint_extent(footprint: &Footprint) -> f64 {
+    let points: Vec<(f64, f64)> = if footprint.courtyard.is_empty() {
+        footprint
+            .pads
+            .iter()
+            .flat_map(|pad| {
+                let (half_width, half_height) = (pad.pad_size.0 / 2.0, pad.pad_size.1 / 2.0);
+                [(pad.x - half_width, pad.y - half_height), (pad.x + half_width, pad.y + half_height)]
+            })
+            .collect()
+    } else {
+        footprint.courtyard.clone()
+    };
+    let span = |coordinate: fn(&(f64, f64)) -> f64| {
+        let values = points.iter().map(coordinate);
+        values.clone().fold(f64::NEG_INFINITY, f64::max) - values.fold(f64::INFINITY, f64::min)
+    };
+    if points.is_empty() { 0.0 } else { span(|point| point.0).max(span(|point| point.1)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{ComponentType, Connection};
+
+    fn two_pad() -> Footprint {
+        let pad = |name: &str, x: f64| Pad {
+            name: name.to_string(),
+            x,
+            y: 0.0,
+            drill_diameter: None,
+            pad_size: (1.0, 1.3),
+            layer: Layer::Top,
+        };
+        Footprint {
+            pads: vec![pad("1", -0.95), pad("2", 0.95)],
+            courtyard: vec![(-1.5, -1.0), (1.5, -1.0), (1.5, 1.0), (-1.5, 1.0)],
+        }
+    }
+
+    fn component(id: &str, component_type: ComponentType) -> Component {
+        Component { id: id.to_string(), component_type, value: None, position: (0.0, 0.0), purpose: None }
+    }
+
+    fn connection(from: &str, to: &str, net: &str) -> Connection {
+        Connection { from: from.to_string(), to: to.to_string(), net_name: net.to_string() }
+    }
+
+    #[test]
+    fn test_import_places_grid_and_stub_traces() {
+        let mut circuit = Circuit::new();
+        circuit.add_component(component("R1", ComponentType::Resistor));
+        circuit.add_component(component("R2", ComponentType::Resistor));
+        circuit.add_component(component("C1", ComponentType::Capacitor));
+        circuit.add_connection(connection("R1", "R2", "mid"));
+        circuit.add_connection(connection("R2", "C1", "out"));
+        let library = FootprintLibrary::from([("Resistor".to_string(), two_pad()), ("C1".to_string(), two_pad())]);
+
+        // 3 mm courtyard + 2 mm margin gives a 5 mm pitch and two columns on a 12 mm board
+        let mut design = PcbDesign::new(12.0, 30.0, 2);
+        let placements = design.import_netlist_from_circuit(&circuit, &library).unwrap();
+        let positions: Vec<_> = placements.iter().map(|placement| (placement.x, placement.y)).collect();
+        assert_eq!(positions, [(2.5, 2.5), (7.5, 2.5), (2.5, 7.5)]);
+        assert_eq!(placements[2].footprint.as_deref(), Some("C1"));
+        assert_eq!(design.placements, placements);
+
+        assert_eq!(design.traces.len(), 2);
+        assert!(design.traces.iter().all(|trace| trace.width == STUB_TRACE_WIDTH_MM));
+        let close = |points: &[(f64, f64)], expected: [(f64, f64); 2]| {
+            points.iter().zip(expected).all(|(a, b)| (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9)
+        };
+        assert!(close(&design.traces[0].points, [(1.55, 2.5), (6.55, 2.5)]));
+        assert!(close(&design.traces[1].points, [(8.45, 2.5), (1.55, 7.5)]));
+        assert_eq!(design.pads.len(), 4);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let library = FootprintLibrary::from([("Resistor".to_string(), two_pad())]);
+
+        let mut circuit = Circuit::new();
+        circuit.add_component(component("D1", ComponentType::Diode));
+        let error = PcbDesign::new(50.0, 50.0, 2).import_netlist_from_circuit(&circuit, &library).unwrap_err();
+        assert!(matches!(error, PcbError::NetlistImport { ref component, .. } if component == "D1"));
+
+        let mut circuit = Circuit::new();
+        circuit.add_component(component("R1", ComponentType::Resistor));
+        circuit.add_component(component("R2", ComponentType::Resistor));
+        for net in ["a", "b", "c"] {
+            circuit.add_connection(connection("R1", "R2", net));
+        }
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        let error = design.import_netlist_from_circuit(&circuit, &library).unwrap_err();
+        assert!(error.to_string().contains("no free pad left for net c"));
+        // Nothing from the failed import is left behind
+        assert_eq!(design, PcbDesign::new(50.0, 50.0, 2));
+    }
+}
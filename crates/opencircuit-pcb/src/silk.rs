@@ -0,0 +1,352 @@
+//! Silk-screen layers and their Gerber output
+
+use crate::errors::PcbError;
+use crate::{Layer, PcbDesign};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Default height of automatically placed reference designators (mm)
+pub const REFERENCE_TEXT_HEIGHT_MM: f64 = 1.0;
+
+/// Character cell width and advance as fractions of the text height
+const CHAR_WIDTH: f64 = 0.6;
+const CHAR_ADVANCE: f64 = 0.8;
+
+/// Text on the silk screen, centred on (x, y)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SilkText {
+    pub x: f64,
+    pub y: f64,
+    pub text: String,
+    /// Rotation in degrees, counter-clockwise
+    pub rotation: f64,
+    pub height_mm: f64,
+}
+
+/// Straight silk-screen line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SilkLine {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub width_mm: f64,
+}
+
+/// Silk-screen element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SilkItem {
+    Text(SilkText),
+    Line(SilkLine),
+}
+
+/// Silk-screen content for one side of the board
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SilkLayer {
+    pub items: Vec<SilkItem>,
+}
+
+impl SilkText {
+    /// Stroke width used when plotting the text
+    pub fn stroke_width(&self) -> f64 {
+        self.height_mm / 8.0
+    }
+
+    /// Line segments drawing the text with a 14-segment stroke font
+    pub fn strokes(&self) -> Vec<((f64, f64), (f64, f64))> {
+        let height = self.height_mm;
+        let char_count = self.text.chars().count() as f64;
+        let total_width = if char_count > 0.0 {
+            height * (CHAR_ADVANCE * (char_count - 1.0) + CHAR_WIDTH)
+        } else {
+            0.0
+        };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let place = |(u, v): (f64, f64)| {
+            // Centre the text block on its anchor, then rotate about it
+            let local_x = u - total_width / 2.0;
+            let local_y = v - height / 2.0;
+            (
+                self.x + local_x * cos - local_y * sin,
+                self.y + local_x * sin + local_y * cos,
+            )
+        };
+
+        let mut strokes = Vec::new();
+        for (index, character) in self.text.chars().enumerate() {
+            let origin = index as f64 * CHAR_ADVANCE * height;
+            for segment in glyph(character).chars() {
+                let Some(((x1, y1), (x2, y2))) = segment_endpoints(segment) else {
+                    continue;
+                };
+                let scale = |x: f64, y: f64| (origin + x * CHAR_WIDTH * height, y * height);
+                strokes.push((place(scale(x1, y1)), place(scale(x2, y2))));
+            }
+        }
+        strokes
+    }
+}
+
+impl SilkLayer {
+    pub fn add_text(&mut self, text: SilkText) {
+        self.items.push(SilkItem::Text(text));
+    }
+
+    pub fn add_line(&mut self, line: SilkLine) {
+        self.items.push(SilkItem::Line(line));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Render as an RS-274X Gerber file
+    ///
+    /// Gerber coordinates are as seen from the top, so bottom-side text is
+    /// mirrored in X about its anchor with `mirror_text` to read correctly
+    /// from below. Lines are board geometry and are never mirrored.
+    pub fn to_gerber(&self, file_function: &str, mirror_text: bool) -> String {
+        let strokes: Vec<_> = self
+            .items
+            .iter()
+            .flat_map(|item| match item {
+                SilkItem::Line(line) => vec![(line.width_mm, (line.x1, line.y1), (line.x2, line.y2))],
+                SilkItem::Text(text) => {
+                    let mirror = |(x, y): (f64, f64)| if mirror_text { (2.0 * text.x - x, y) } else { (x, y) };
+                    text.strokes()
+                        .into_iter()
+                        .map(|(start, end)| (text.stroke_width(), mirror(start), mirror(end)))
+                        .collect()
+                }
+            })
+            .collect();
+
+        // One circular aperture per distinct stroke width, keyed in microns
+        let widths: BTreeSet<i64> = strokes.iter().map(|(width, _, _)| micron(*width)).collect();
+        let aperture = |width: f64| 10 + widths.iter().position(|w| *w == micron(width)).unwrap_or(0);
+
+        let mut gerber = String::new();
+        writeln!(gerber, "%TF.FileFunction,{}*%", file_function).unwrap();
+        writeln!(gerber, "%FSLAX46Y46*%").unwrap();
+        writeln!(gerber, "%MOMM*%").unwrap();
+        writeln!(gerber, "%LPD*%").unwrap();
+        for (index, width) in widths.iter().enumerate() {
+            writeln!(gerber, "%ADD{}C,{:.3}*%", 10 + index, *width as f64 / 1000.0).unwrap();
+        }
+
+        let mut current_aperture = None;
+        for (width, start, end) in &strokes {
+            let aperture = aperture(*width);
+            if current_aperture != Some(aperture) {
+                writeln!(gerber, "D{}*", aperture).unwrap();
+                current_aperture = Some(aperture);
+            }
+            writeln!(gerber, "X{}Y{}D02*", nanometre(start.0), nanometre(start.1)).unwrap();
+            writeln!(gerber, "X{}Y{}D01*", nanometre(end.0), nanometre(end.1)).unwrap();
+        }
+        writeln!(gerber, "M02*").unwrap();
+
+        gerber
+    }
+}
+
+impl PcbDesign {
+    /// Silk layer for a board side; inner layers have no silk screen
+    pub fn silk_layer_mut(&mut self, layer: &Layer) -> Option<&mut SilkLayer> {
+        match layer {
+            Layer::Top => Some(&mut self.silk_top),
+            Layer::Bottom => Some(&mut self.silk_bottom),
+            Layer::Inner(_) => None,
+        }
+    }
+
+    /// Write top and bottom silk-screen Gerbers into `dir`
+    pub fn export_silk_gerbers(&self, dir: &Path) -> Result<Vec<PathBuf>, PcbError> {
+        std::fs::create_dir_all(dir)?;
+
+        let mut written = Vec::new();
+        for (layer, file_name, file_function, mirror_text) in [
+            (&self.silk_top, "silk_top.gto", "Legend,Top", false),
+            (&self.silk_bottom, "silk_bottom.gbo", "Legend,Bot", true),
+        ] {
+            let path = dir.join(file_name);
+            std::fs::write(&path, layer.to_gerber(file_function, mirror_text))?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+}
+
+/// Gerber 4.6 coordinates are integer nanometres when the unit is mm
+fn nanometre(mm: f64) -> i64 {
+    (mm * 1e6).round() as i64
+}
+
+fn micron(mm: f64) -> i64 {
+    (mm * 1e3).round() as i64
+}
+
+/// Segments lit for a character; unknown characters are left blank
+fn glyph(character: char) -> &'static str {
+    match character.to_ascii_uppercase() {
+        '0' => "abcdefjk",
+        '1' => "bc",
+        '2' => "abgGed",
+        '3' => "abGcd",
+        '4' => "fgGbc",
+        '5' | 'S' => "afgGcd",
+        '6' => "afedcgG",
+        '7' => "abc",
+        '8' => "abcdefgG",
+        '9' => "abcdfgG",
+        'A' => "abcefgG",
+        'B' => "abcdGil",
+        'C' => "adef",
+        'D' => "abcdil",
+        'E' => "adefg",
+        'F' => "aefg",
+        'G' => "acdefG",
+        'H' => "bcefgG",
+        'I' => "adil",
+        'J' => "bcde",
+        'K' => "efgjm",
+        'L' => "def",
+        'M' => "bcefhj",
+        'N' => "bcefhm",
+        'O' => "abcdef",
+        'P' => "abefgG",
+        'Q' => "abcdefm",
+        'R' => "abefgGm",
+        'T' => "ail",
+        'U' => "bcdef",
+        'V' => "efkj",
+        'W' => "bcefkm",
+        'X' => "hjkm",
+        'Y' => "hjl",
+        'Z' => "adjk",
+        '-' => "gG",
+        '+' => "gGil",
+        '_' => "d",
+        '/' => "jk",
+        _ => "",
+    }
+}
+
+/// Segment endpoints in a unit character cell, origin bottom-left
+fn segment_endpoints(segment: char) -> Option<((f64, f64), (f64, f64))> {
+    let endpoints = match segment {
+        'a' => ((0.0, 1.0), (1.0, 1.0)),
+        'b' => ((1.0, 1.0), (1.0, 0.5)),
+        'c' => ((1.0, 0.5), (1.0, 0.0)),
+        'd' => ((0.0, 0.0), (1.0, 0.0)),
+        'e' => ((0.0, 0.0), (0.0, 0.5)),
+        'f' => ((0.0, 0.5), (0.0, 1.0)),
+        'g' => ((0.0, 0.5), (0.5, 0.5)),
+        'G' => ((0.5, 0.5), (1.0, 0.5)),
+        'h' => ((0.0, 1.0), (0.5, 0.5)),
+        'i' => ((0.5, 1.0), (0.5, 0.5)),
+        'j' => ((1.0, 1.0), (0.5, 0.5)),
+        'k' => ((0.0, 0.0), (0.5, 0.5)),
+        'l' => ((0.5, 0.5), (0.5, 0.0)),
+        'm' => ((1.0, 0.0), (0.5, 0.5)),
+        _ => return None,
+    };
+    Some(endpoints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComponentPlacement, Footprint, FootprintLibrary};
+
+    fn placement(component_id: &str, footprint: Option<&str>, layer: Layer) -> ComponentPlacement {
+        ComponentPlacement {
+            component_id: component_id.to_string(),
+            x: 10.0,
+            y: 20.0,
+            rotation: 90.0,
+            layer,
+            footprint: footprint.map(str::to_string),
+        }
+    }
+
+    fn design_with_footprints() -> PcbDesign {
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.load_footprint_library(FootprintLibrary::from([
+            ("0805".to_string(), Footprint::default()),
+            ("0603".to_string(), Footprint::default()),
+        ]));
+        design
+    }
+
+    #[test]
+    fn test_add_placement_adds_reference_designator() {
+        let mut design = design_with_footprints();
+        design.add_placement(placement("R1", Some("0805"), Layer::Top));
+        design.add_placement(placement("C1", Some("0603"), Layer::Bottom));
+        design.add_placement(placement("X1", None, Layer::Top));
+
+        assert_eq!(
+            design.silk_top.items,
+            vec![SilkItem::Text(SilkText {
+                x: 10.0,
+                y: 20.0,
+                text: "R1".to_string(),
+                rotation: 90.0,
+                height_mm: REFERENCE_TEXT_HEIGHT_MM,
+            })]
+        );
+        assert_eq!(design.silk_bottom.items.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_footprint_gets_no_reference_designator() {
+        let mut design = design_with_footprints();
+        design.add_placement(placement("R1", Some("0850"), Layer::Top));
+
+        assert_eq!(design.placements.len(), 1);
+        assert!(!design.silk_top.items.iter().any(|item| matches!(item, SilkItem::Text(_))));
+    }
+
+    #[test]
+    fn test_text_strokes_are_centred() {
+        let text = SilkText { x: 5.0, y: 5.0, text: "L".to_string(), rotation: 0.0, height_mm: 1.0 };
+        let strokes = text.strokes();
+
+        // "L" is the bottom and lower/upper left segments of a 0.6 x 1.0 cell
+        assert_eq!(strokes.len(), 3);
+        assert_eq!(strokes[0], ((4.7, 4.5), (5.3, 4.5)));
+        assert!(SilkText { text: "?".to_string(), ..text }.strokes().is_empty());
+    }
+
+    #[test]
+    fn test_silk_gerber_output() {
+        let mut layer = SilkLayer::default();
+        layer.add_line(SilkLine { x1: 0.0, y1: 0.0, x2: 2.5, y2: 0.0, width_mm: 0.15 });
+        layer.add_text(SilkText { x: 1.0, y: 1.0, text: "U1".to_string(), rotation: 0.0, height_mm: 1.0 });
+
+        let gerber = layer.to_gerber("Legend,Top", false);
+        assert!(gerber.starts_with("%TF.FileFunction,Legend,Top*%\n%FSLAX46Y46*%\n%MOMM*%"));
+        assert!(gerber.contains("%ADD10C,0.125*%"));
+        assert!(gerber.contains("%ADD11C,0.150*%"));
+        assert!(gerber.contains("D11*\nX0Y0D02*\nX2500000Y0D01*\n"));
+        assert!(gerber.trim_end().ends_with("M02*"));
+    }
+
+    #[test]
+    fn test_bottom_text_is_mirrored() {
+        let mut layer = SilkLayer::default();
+        layer.add_line(SilkLine { x1: 0.0, y1: 0.0, x2: 2.5, y2: 0.0, width_mm: 0.15 });
+        layer.add_text(SilkText { x: 5.0, y: 5.0, text: "L".to_string(), rotation: 0.0, height_mm: 1.0 });
+
+        // The foot of the "L" runs right to left, and the upright sits on the right
+        let gerber = layer.to_gerber("Legend,Bot", true);
+        assert!(gerber.contains("X5300000Y4500000D02*\nX4700000Y4500000D01*\n"));
+        assert!(gerber.contains("X5300000Y4500000D02*\nX5300000Y5000000D01*\n"));
+        assert!(gerber.contains("X0Y0D02*\nX2500000Y0D01*\n"));
+        assert!(!gerber.contains("X4700000Y5000000"));
+    }
+}
@@ -0,0 +1,351 @@
+//! 3D board models for enclosure design
+//!
+//! The board is a rectangular solid from the origin to `width` x `height`,
+//! with its bottom face at z = 0 and its thickness taken from the stackup.
+//! Vias and through-hole pads are drilled through it, and each component with
+//! a known height is a block over its courtyard (or recorded body size when
+//! the footprint has no courtyard). Bottom-side blocks hang below the board.
+//!
+//! STEP AP214 output is produced by FreeCAD: [`PcbDesign::export_step`]
+//! writes a Python script and runs it with `freecadcmd`. Without FreeCAD,
+//! [`PcbDesign::export_openscad`] writes an OpenSCAD script that can be
+//! compiled externally.
+
+use crate::errors::PcbError;
+use crate::{ComponentHeight, ComponentPlacement, Layer, PcbDesign};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Copper foil thickness per layer, 1 oz (mm)
+pub const COPPER_THICKNESS_MM: f64 = 0.035;
+/// FR-4 core between the outer layers of a two-layer board (mm)
+pub const CORE_THICKNESS_MM: f64 = 1.5;
+/// Prepreg added for each extra pair of inner layers (mm)
+pub const PREPREG_THICKNESS_MM: f64 = 0.2;
+/// FreeCAD's headless command, used for STEP output
+pub const FREECAD_COMMAND: &str = "freecadcmd";
+
+/// Hole drilled through the board
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardHole {
+    pub x: f64,
+    pub y: f64,
+    pub diameter: f64,
+}
+
+/// Rectangular block standing in for a component body
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentBlock {
+    pub component_id: String,
+    /// Centre of the block on the board (mm)
+    pub center: (f64, f64),
+    /// Block width and depth before rotation (mm)
+    pub size: (f64, f64),
+    /// Degrees counter-clockwise, as the placement
+    pub rotation: f64,
+    /// Height of the block's lower face (mm)
+    pub z_bottom: f64,
+    pub height: f64,
+}
+
+/// Solids making up the 3D model of a board
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardModel {
+    pub width: f64,
+    pub height: f64,
+    pub thickness: f64,
+    pub holes: Vec<BoardHole>,
+    pub blocks: Vec<ComponentBlock>,
+}
+
+impl PcbDesign {
+    /// Finished board thickness from the stackup
+    ///
+    /// Every copper layer adds [`COPPER_THICKNESS_MM`]; a two-layer core adds
+    /// [`CORE_THICKNESS_MM`] and each further pair of layers one prepreg.
+    pub fn board_thickness_mm(&self) -> f64 {
+        let layers = self.layer_count.max(2);
+        let extra_pairs = (layers - 2).div_ceil(2);
+        f64::from(layers) * COPPER_THICKNESS_MM + CORE_THICKNESS_MM + f64::from(extra_pairs) * PREPREG_THICKNESS_MM
+    }
+
+    /// Board, holes and component blocks for 3D export
+    ///
+    /// Components without an entry in `component_heights` are left out.
+    pub fn board_model(&self, component_heights: &HashMap<String, ComponentHeight>) -> BoardModel {
+        let thickness = self.board_thickness_mm();
+        let holes = self
+            .vias
+            .iter()
+            .map(|via| BoardHole { x: via.x, y: via.y, diameter: via.drill_diameter })
+            .chain(self.pads.iter().filter_map(|board_pad| {
+                let diameter = board_pad.pad.drill_diameter?;
+                Some(BoardHole { x: board_pad.pad.x, y: board_pad.pad.y, diameter })
+            }))
+            .filter(|hole| hole.diameter > 0.0)
+            .collect();
+
+        let blocks = self
+            .placements
+            .iter()
+            .filter_map(|placement| {
+                let height = component_heights.get(&placement.component_id)?;
+                let (center, size) = self.block_outline(placement, height);
+                let z_bottom = if placement.layer == Layer::Bottom { -height.height_mm } else { thickness };
+                Some(ComponentBlock {
+                    component_id: placement.component_id.clone(),
+                    center,
+                    size,
+                    rotation: placement.rotation,
+                    z_bottom,
+                    height: height.height_mm,
+                })
+            })
+            .collect();
+
+        BoardModel { width: self.width, height: self.height, thickness, holes, blocks }
+    }
+
+    /// Write a STEP AP214 model by running FreeCAD
+    pub fn export_step(
+        &self,
+        output_path: &Path,
+        component_heights: &HashMap<String, ComponentHeight>,
+    ) -> Result<(), PcbError> {
+        let model = self.board_model(component_heights);
+        let script_path = std::env::temp_dir().join(format!("opencircuit-step-{}.py", uuid::Uuid::new_v4()));
+        std::fs::write(&script_path, model.to_freecad_script(output_path))?;
+
+        let status = Command::new(FREECAD_COMMAND).arg(&script_path).status();
+        let _ = std::fs::remove_file(&script_path);
+        match status {
+            Ok(status) if status.success() && output_path.exists() => Ok(()),
+            Ok(status) => Err(PcbError::ExternalTool {
+                tool: FREECAD_COMMAND.to_string(),
+                reason: format!("exited with {} without writing {}", status, output_path.display()),
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(PcbError::UnsupportedFeature(format!(
+                "STEP export needs FreeCAD ({} not found); use export_openscad instead",
+                FREECAD_COMMAND
+            ))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write an OpenSCAD script of the board model
+    pub fn export_openscad(
+        &self,
+        output_path: &Path,
+        component_heights: &HashMap<String, ComponentHeight>,
+    ) -> Result<(), PcbError> {
+        std::fs::write(output_path, self.board_model(component_heights).to_openscad())?;
+        Ok(())
+    }
+
+    /// Centre and size of a component block in board coordinates
+    fn block_outline(&self, placement: &ComponentPlacement, height: &ComponentHeight) -> ((f64, f64), (f64, f64)) {
+        let courtyard = placement
+            .footprint
+            .as_deref()
+            .and_then(|name| self.footprints.get(name))
+            .map(|footprint| footprint.courtyard.as_slice())
+            .filter(|courtyard| !courtyard.is_empty());
+        let Some(courtyard) = courtyard else {
+            return ((placement.x, placement.y), (height.body_x_mm, height.body_y_mm));
+        };
+
+        let (min_x, max_x) = min_max(courtyard.iter().map(|point| point.0));
+        let (min_y, max_y) = min_max(courtyard.iter().map(|point| point.1));
+        let center = placement.to_board(((min_x + max_x) / 2.0, (min_y + max_y) / 2.0));
+        (center, (max_x - min_x, max_y - min_y))
+    }
+}
+
+impl BoardModel {
+    /// OpenSCAD script with the board as a drilled cube and one cube per block
+    pub fn to_openscad(&self) -> String {
+        let mut script = String::from("// Generated by OpenCircuit\n$fn = 32;\n\ndifference() {\n");
+        script.push_str(&format!("    cube([{:.4}, {:.4}, {:.4}]);\n", self.width, self.height, self.thickness));
+        for hole in &self.holes {
+            script.push_str(&format!(
+                "    translate([{:.4}, {:.4}, -1]) cylinder(d = {:.4}, h = {:.4});\n",
+                hole.x,
+                hole.y,
+                hole.diameter,
+                self.thickness + 2.0
+            ));
+        }
+        script.push_str("}\n");
+
+        // Cubes grow from a corner, so each is shifted back to centre it before rotating
+        for block in &self.blocks {
+            script.push_str(&format!(
+                "\n// {}\ntranslate([{:.4}, {:.4}, {:.4}]) rotate([0, 0, {:.4}])\n    translate([{:.4}, {:.4}, 0]) cube([{:.4}, {:.4}, {:.4}]);\n",
+                block.component_id.replace('\n', " "),
+                block.center.0,
+                block.center.1,
+                block.z_bottom,
+                block.rotation,
+                -block.size.0 / 2.0,
+                -block.size.1 / 2.0,
+                block.size.0,
+                block.size.1,
+                block.height
+            ));
+        }
+        script
+    }
+
+    /// FreeCAD Python script that builds the solids and exports STEP
+    pub fn to_freecad_script(&self, output_path: &Path) -> String {
+        let mut script = String::from("import FreeCAD\nimport Part\n\n");
+        script.push_str(&format!(
+            "board = Part.makeBox({:.4}, {:.4}, {:.4})\n",
+            self.width, self.height, self.thickness
+        ));
+        for hole in &self.holes {
+            script.push_str(&format!(
+                "board = board.cut(Part.makeCylinder({:.4}, {:.4}, FreeCAD.Vector({:.4}, {:.4}, -1)))\n",
+                hole.diameter / 2.0,
+                self.thickness + 2.0,
+                hole.x,
+                hole.y
+            ));
+        }
+        script.push_str("shapes = [board]\n");
+
+        for block in &self.blocks {
+            script.push_str(&format!(
+                "block = Part.makeBox({:.4}, {:.4}, {:.4}, FreeCAD.Vector({:.4}, {:.4}, {:.4}))\n",
+                block.size.0,
+                block.size.1,
+                block.height,
+                -block.size.0 / 2.0,
+                -block.size.1 / 2.0,
+                block.z_bottom
+            ));
+            script.push_str(&format!(
+                "block.rotate(FreeCAD.Vector(0, 0, 0), FreeCAD.Vector(0, 0, 1), {:.4})\n",
+                block.rotation
+            ));
+            script.push_str(&format!(
+                "block.translate(FreeCAD.Vector({:.4}, {:.4}, 0))\nshapes.append(block)\n",
+                block.center.0, block.center.1
+            ));
+        }
+
+        script.push_str(&format!(
+            "\nPart.makeCompound(shapes).exportStep({})\n",
+            python_string(&output_path.to_string_lossy())
+        ));
+        script
+    }
+}
+
+/// Single-quoted Python string literal for `value`
+fn python_string(value: &str) -> String {
+    let mut literal = String::from("'");
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '\'' => literal.push_str("\\'"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+fn min_max(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardPad, Footprint, Pad, Via};
+
+    fn design() -> PcbDesign {
+        let mut design = PcbDesign::new(40.0, 30.0, 2);
+        design.load_footprint_library(HashMap::from([(
+            "SOT-23".to_string(),
+            Footprint { pads: Vec::new(), courtyard: vec![(-1.0, -2.0), (3.0, -2.0), (3.0, 2.0), (-1.0, 2.0)] },
+        )]));
+        for (id, layer, footprint) in [("U1", Layer::Top, Some("SOT-23")), ("C1", Layer::Bottom, None), ("R1", Layer::Top, None)] {
+            design.add_placement(ComponentPlacement {
+                component_id: id.to_string(),
+                x: 10.0,
+                y: 5.0,
+                rotation: 90.0,
+                layer,
+                footprint: footprint.map(str::to_string),
+            });
+        }
+        design.vias.push(Via { net_name: "GND".to_string(), x: 2.0, y: 3.0, pad_diameter: 0.6, drill_diameter: 0.3 });
+        design.add_pad(BoardPad {
+            component_id: "J1".to_string(),
+            net_name: "VIN".to_string(),
+            pad: Pad { name: "1".to_string(), x: 35.0, y: 25.0, drill_diameter: Some(1.0), pad_size: (1.7, 1.7), layer: Layer::Top },
+        });
+        design
+    }
+
+    fn heights() -> HashMap<String, ComponentHeight> {
+        ["U1", "C1"]
+            .into_iter()
+            .map(|id| {
+                (id.to_string(), ComponentHeight { component_id: id.to_string(), height_mm: 1.2, body_x_mm: 2.0, body_y_mm: 1.25 })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_board_thickness_from_stackup() {
+        assert!((PcbDesign::new(10.0, 10.0, 2).board_thickness_mm() - 1.57).abs() < 1e-9);
+        assert!((PcbDesign::new(10.0, 10.0, 4).board_thickness_mm() - 1.84).abs() < 1e-9);
+        assert!((PcbDesign::new(10.0, 10.0, 6).board_thickness_mm() - 2.11).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_board_model_holes_and_blocks() {
+        let model = design().board_model(&heights());
+        assert_eq!(model.holes.len(), 2);
+        assert_eq!(model.holes[1], BoardHole { x: 35.0, y: 25.0, diameter: 1.0 });
+
+        // R1 has no height and is left out
+        assert_eq!(model.blocks.len(), 2);
+        let u1 = &model.blocks[0];
+        // The courtyard's centre (1, 0) rotated 90 degrees lands at (10, 6)
+        assert!((u1.center.0 - 10.0).abs() < 1e-9 && (u1.center.1 - 6.0).abs() < 1e-9);
+        assert_eq!(u1.size, (4.0, 4.0));
+        assert_eq!(u1.z_bottom, model.thickness);
+
+        let c1 = &model.blocks[1];
+        assert_eq!((c1.center, c1.size, c1.z_bottom), ((10.0, 5.0), (2.0, 1.25), -1.2));
+    }
+
+    #[test]
+    fn test_scripts_describe_model() {
+        let model = design().board_model(&heights());
+        let scad = model.to_openscad();
+        assert!(scad.contains("cube([40.0000, 30.0000, 1.5700]);"));
+        assert!(scad.contains("translate([2.0000, 3.0000, -1]) cylinder(d = 0.3000, h = 3.5700);"));
+        assert!(scad.contains("// C1\ntranslate([10.0000, 5.0000, -1.2000]) rotate([0, 0, 90.0000])"));
+
+        let freecad = model.to_freecad_script(Path::new("/tmp/board.step"));
+        assert_eq!(freecad.matches("board = board.cut(").count(), 2);
+        assert_eq!(freecad.matches("shapes.append(block)").count(), 2);
+        assert!(freecad.ends_with("Part.makeCompound(shapes).exportStep('/tmp/board.step')\n"));
+        assert_eq!(python_string(r"C:\Users\o'brien\board.step"), r"'C:\\Users\\o\'brien\\board.step'");
+
+        let dir = std::env::temp_dir().join(format!("opencircuit-scad-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("board.scad");
+        design().export_openscad(&path, &heights()).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), scad);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+//! Thermal via arrays under exposed pads
+//!
+//! A footprint's thermal pad is the SMD pad covering its origin, as on QFN
+//! and other exposed-pad packages. Vias are picked from a half-pitch grid
+//! centred on that pad, nearest the centre first, and kept at least one
+//! pitch apart, so the array can step around pads that intrude on it.
+
+use crate::drc::FAB_ANNULAR_RING_MM;
+use crate::{ComponentPlacement, DrcViolation, Footprint, Pad, PcbDesign, Severity, Via};
+
+/// Copper clearance kept between a thermal via and any other pad (mm)
+pub const THERMAL_VIA_PAD_CLEARANCE_MM: f64 = 0.2;
+
+/// Net given to thermal vias when the thermal pad is not on a net
+pub const DEFAULT_THERMAL_NET: &str = "GND";
+
+/// Tolerance for rounding in grid positions (mm)
+const EPSILON_MM: f64 = 1e-9;
+
+impl PcbDesign {
+    /// Add up to `count` thermal vias under a component's thermal pad
+    ///
+    /// Vias stay inside the thermal pad and the courtyard and keep
+    /// [`THERMAL_VIA_PAD_CLEARANCE_MM`] from the other pads. Returns the
+    /// vias added, none if the component has no placed thermal pad.
+    pub fn add_thermal_via_array(&mut self, component_id: &str, pitch_mm: f64, drill_mm: f64, count: usize) -> Vec<Via> {
+        let Some((placement, footprint)) = self.placed_footprint(component_id) else {
+            return Vec::new();
+        };
+        let Some(thermal) = thermal_pad(footprint) else {
+            return Vec::new();
+        };
+        if !pitch_mm.is_finite() || pitch_mm <= 0.0 {
+            return Vec::new();
+        }
+
+        let pad_diameter = drill_mm + 2.0 * FAB_ANNULAR_RING_MM;
+        let radius = pad_diameter / 2.0;
+        let step = pitch_mm / 2.0;
+        let columns = ((thermal.pad_size.0 / 2.0 - radius + EPSILON_MM) / step).floor() as i64;
+        let rows = ((thermal.pad_size.1 / 2.0 - radius + EPSILON_MM) / step).floor() as i64;
+        let courtyard = bounds(&footprint.courtyard);
+
+        let mut candidates: Vec<(f64, f64)> = (-rows..=rows)
+            .flat_map(|row| (-columns..=columns).map(move |column| (column as f64 * step, row as f64 * step)))
+            .map(|(dx, dy)| (thermal.x + dx, thermal.y + dy))
+            .filter(|&point| match courtyard {
+                Some(bounds) => inside(bounds, point, radius),
+                None => true,
+            })
+            .filter(|&point| {
+                footprint
+                    .pads
+                    .iter()
+                    .filter(|pad| pad.name != thermal.name)
+                    .all(|pad| distance_to_pad(pad, point) - radius + EPSILON_MM >= THERMAL_VIA_PAD_CLEARANCE_MM)
+            })
+            .collect();
+        let from_centre = |(x, y): (f64, f64)| (x - thermal.x).hypot(y - thermal.y);
+        candidates.sort_by(|a, b| from_centre(*a).total_cmp(&from_centre(*b)));
+
+        let mut chosen: Vec<(f64, f64)> = Vec::new();
+        for point in candidates {
+            if chosen.len() == count {
+                break;
+            }
+            if chosen.iter().all(|other| (point.0 - other.0).hypot(point.1 - other.1) + EPSILON_MM >= pitch_mm) {
+                chosen.push(point);
+            }
+        }
+
+        let net_name = self
+            .pads
+            .iter()
+            .find(|board_pad| board_pad.component_id == component_id && board_pad.pad.name == thermal.name)
+            .map_or(DEFAULT_THERMAL_NET, |board_pad| board_pad.net_name.as_str())
+            .to_string();
+        let vias: Vec<Via> = chosen
+            .into_iter()
+            .map(|point| {
+                let (x, y) = placement.to_board(point);
+                Via { net_name: net_name.clone(), x, y, pad_diameter, drill_diameter: drill_mm }
+            })
+            .collect();
+        self.vias.extend(vias.iter().cloned());
+        vias
+    }
+
+    /// Components matching `component_pattern` with fewer than `min_vias`
+    /// vias in their thermal pad
+    ///
+    /// `*` in the pattern matches any run of characters, so `U*` covers
+    /// every IC. Components without a thermal pad are not checked.
+    pub fn check_thermal_via_count(&self, component_pattern: &str, min_vias: usize) -> Vec<DrcViolation> {
+        let mut placements: Vec<&ComponentPlacement> = self
+            .placements
+            .iter()
+            .filter(|placement| matches_pattern(component_pattern, &placement.component_id))
+            .collect();
+        placements.sort_by(|a, b| a.component_id.cmp(&b.component_id));
+
+        placements
+            .into_iter()
+            .filter_map(|placement| {
+                let thermal = thermal_pad(self.footprints.get(placement.footprint.as_deref()?)?)?;
+                let vias = self
+                    .vias
+                    .iter()
+                    .filter(|via| distance_to_pad(thermal, placement.to_local((via.x, via.y))) <= EPSILON_MM)
+                    .count();
+                (vias < min_vias).then(|| DrcViolation {
+                    rule_name: "thermal_via_count".to_string(),
+                    description: format!(
+                        "{} has {} thermal vias in pad {}, fewer than the {} required",
+                        placement.component_id, vias, thermal.name, min_vias
+                    ),
+                    location: (placement.x, placement.y),
+                    severity: Severity::Error,
+                })
+            })
+            .collect()
+    }
+
+    /// Placement of a component and its footprint, if both are known
+    fn placed_footprint(&self, component_id: &str) -> Option<(&ComponentPlacement, &Footprint)> {
+        let placement = self.placements.iter().find(|placement| placement.component_id == component_id)?;
+        let footprint = self.footprints.get(placement.footprint.as_deref()?)?;
+        Some((placement, footprint))
+    }
+}
+
+/// Largest SMD pad covering the footprint origin
+fn thermal_pad(footprint: &Footprint) -> Option<&Pad> {
+    footprint
+        .pads
+        .iter()
+        .filter(|pad| pad.drill_diameter.is_none() && distance_to_pad(pad, (0.0, 0.0)) <= EPSILON_MM)
+        .max_by(|a, b| (a.pad_size.0 * a.pad_size.1).total_cmp(&(b.pad_size.0 * b.pad_size.1)))
+}
+
+/// Distance from a point to the edge of a pad, zero inside it
+fn distance_to_pad(pad: &Pad, (x, y): (f64, f64)) -> f64 {
+    let dx = ((x - pad.x).abs() - pad.pad_size.0 / 2.0).max(0.0);
+    let dy = ((y - pad.y).abs() - pad.pad_size.1 / 2.0).max(0.0);
+    dx.hypot(dy)
+}
+
+/// Bounding box of an outline as (min, max) corners
+fn bounds(outline: &[(f64, f64)]) -> Option<((f64, f64), (f64, f64))> {
+    let (&first, rest) = outline.split_first()?;
+    Some(rest.iter().fold((first, first), |(min, max), &(x, y)| ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))))
+}
+
+/// Whether a circle of `radius` around `point` lies within the bounds
+fn inside(((min_x, min_y), (max_x, max_y)): ((f64, f64), (f64, f64)), (x, y): (f64, f64), radius: f64) -> bool {
+    x - radius >= min_x - EPSILON_MM
+        && x + radius <= max_x + EPSILON_MM
+        && y - radius >= min_y - EPSILON_MM
+        && y + radius <= max_y + EPSILON_MM
+}
+
+/// Match a component ID against a pattern where `*` matches any run of characters
+fn matches_pattern(pattern: &str, component_id: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = component_id.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_ipc7351, BoardPad, DrcRule, FootprintLibrary, Layer, SmdPackage};
+
+    fn place(design: &mut PcbDesign, component_id: &str, footprint: Option<&str>, rotation: f64) {
+        design.add_placement(ComponentPlacement {
+            component_id: component_id.to_string(),
+            x: 20.0,
+            y: 10.0,
+            rotation,
+            layer: Layer::Top,
+            footprint: footprint.map(str::to_string),
+        });
+    }
+
+    fn smd(name: &str, x: f64, size: f64) -> Pad {
+        Pad { name: name.to_string(), x, y: 0.0, drill_diameter: None, pad_size: (size, size), layer: Layer::Top }
+    }
+
+    #[test]
+    fn test_thermal_vias_step_around_signal_pads() {
+        // Power MOSFET style footprint with the gate pad close to the tab
+        let footprint = Footprint {
+            pads: vec![smd("TAB", 0.0, 4.0), smd("G", 2.2, 0.6)],
+            courtyard: vec![(-3.0, -3.0), (3.0, -3.0), (3.0, 3.0), (-3.0, 3.0)],
+        };
+        let mut design = PcbDesign::new(50.0, 50.0, 2);
+        design.load_footprint_library(FootprintLibrary::from([("PowerPAK".to_string(), footprint)]));
+        place(&mut design, "Q1", Some("PowerPAK"), 0.0);
+
+        assert_eq!(design.add_thermal_via_array("Q1", 1.5, 0.3, 4).len(), 4);
+        design.vias.clear();
+
+        let vias = design.add_thermal_via_array("Q1", 1.5, 0.3, 20);
+        assert_eq!(vias.len(), 8);
+        assert_eq!(design.vias, vias);
+        assert!(vias.iter().all(|via| via.net_name == DEFAULT_THERMAL_NET && via.pad_diameter == 0.5));
+        // The via right of centre would crowd the gate pad, so the array shifts half a pitch
+        assert!(!vias.iter().any(|via| via.x == 21.5 && via.y == 10.0));
+        assert!(vias.iter().any(|via| via.x == 21.5 && via.y == 10.75));
+
+        assert!(design.add_thermal_via_array("Q2", 1.5, 0.3, 4).is_empty());
+    }
+
+    #[test]
+    fn test_thermal_via_min_count_rule() {
+        let mut design = PcbDesign::new(50.0, 50.0, 4);
+        design.load_footprint_library(FootprintLibrary::from([("QFN48".to_string(), generate_ipc7351(SmdPackage::QFN48))]));
+        place(&mut design, "U1", Some("QFN48"), 90.0);
+        place(&mut design, "U2", None, 0.0);
+        let thermal = design.footprints["QFN48"].pad("49").unwrap().clone();
+        design.add_pad(BoardPad { component_id: "U1".to_string(), net_name: "PGND".to_string(), pad: thermal });
+
+        let vias = design.add_thermal_via_array("U1", 1.0, 0.3, 9);
+        assert_eq!(vias.len(), 9);
+        assert!(vias.iter().all(|via| via.net_name == "PGND"));
+
+        design.add_drc_rule(DrcRule::ThermalViaMinCount { component_pattern: "U*".to_string(), min_vias: 9 });
+        assert!(design.run_drc().unwrap().is_empty());
+
+        let violations = design.check_thermal_via_count("U*", 12);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_name, "thermal_via_count");
+        assert!(violations[0].description.starts_with("U1 has 9 thermal vias in pad 49"));
+        assert!(design.check_thermal_via_count("R*", 12).is_empty());
+        assert!(matches_pattern("*V*", "U_VREG") && !matches_pattern("U1", "U10"));
+    }
+}
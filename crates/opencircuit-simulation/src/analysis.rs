@@ -20,6 +20,8 @@ pub enum AnalysisType {
     Distortion,
     /// Monte Carlo analysis
     MonteCarlo,
+    /// Small-signal sensitivity analysis
+    Sensitivity,
 }
 
 /// Analysis command configuration
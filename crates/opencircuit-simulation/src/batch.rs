@@ -0,0 +1,248 @@
+//! Concurrent simulation of independent circuits
+//!
+//! Monte Carlo runs and parametric sweeps are many unrelated simulations.
+//! Up to one worker thread per CPU, each with its own NgSpice instance, takes
+//! jobs from a shared queue, and results come back in job order.
+
+use crate::analysis::{AnalysisCommand, AnalysisType};
+use crate::errors::{Result, SimulationError};
+use crate::parametric;
+use opencircuit_circuit::Circuit;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
+
+/// One simulation in a batch
+#[derive(Debug, Clone)]
+pub struct SimulationJob {
+    pub circuit: Circuit,
+    pub analysis: AnalysisType,
+    /// Component values replacing those in `circuit`, keyed by component ID
+    pub component_overrides: Option<HashMap<String, f64>>,
+    /// Longest the job may run before failing with `SimulationError::Timeout`
+    pub timeout: Option<Duration>,
+}
+
+impl SimulationJob {
+    pub fn new(circuit: Circuit, analysis: AnalysisType) -> Self {
+        Self {
+            circuit,
+            analysis,
+            component_overrides: None,
+            timeout: None,
+        }
+    }
+
+    /// Override one component's value for this job
+    pub fn with_override(mut self, component_id: &str, value: f64) -> Self {
+        self.component_overrides
+            .get_or_insert_with(HashMap::new)
+            .insert(component_id.to_string(), value);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Circuit with the overrides applied, and the analysis directive
+    pub(crate) fn prepare(&self) -> Result<(Circuit, String)> {
+        let directive = AnalysisCommand {
+            analysis_type: self.analysis.clone(),
+            parameters: HashMap::new(),
+        }
+        .to_spice_command();
+        if directive.starts_with('*') {
+            return Err(SimulationError::AnalysisError {
+                analysis_type: format!("{:?}", self.analysis),
+                reason: "not supported for batch simulation".to_string(),
+            });
+        }
+
+        // Sorted so a bad override is reported the same way every run
+        let mut overrides: Vec<_> = self.component_overrides.iter().flatten().collect();
+        overrides.sort_by(|a, b| a.0.cmp(b.0));
+        let mut circuit = self.circuit.clone();
+        for (component_id, value) in overrides {
+            circuit = parametric::apply_value(&circuit, component_id, *value)?;
+        }
+        Ok((circuit, directive))
+    }
+}
+
+/// Run `job` with a child of `token` that is also cancelled after `timeout`
+///
+/// The job must stop once its token is cancelled. It is awaited after the
+/// timeout so NgSpice has halted before `SimulationError::Timeout` returns.
+pub(crate) async fn with_timeout<T, Fut>(
+    timeout: Option<Duration>,
+    token: &CancellationToken,
+    job: impl FnOnce(CancellationToken) -> Fut,
+) -> Result<T>
+where
+    Fut: Future<Output = Result<T>>,
+{
+    let job_token = token.child_token();
+    let run = job(job_token.clone());
+    let Some(timeout) = timeout else { return run.await };
+
+    tokio::pin!(run);
+    tokio::select! {
+        result = &mut run => result,
+        _ = tokio::time::sleep(timeout) => {
+            job_token.cancel();
+            let _ = run.await;
+            Err(SimulationError::Timeout { timeout_ms: timeout.as_millis() as u64 })
+        }
+    }
+}
+
+/// Run `jobs` on `workers` threads and return their outputs in input order
+///
+/// Each thread runs a single-threaded runtime, builds its worker with
+/// `new_worker` and takes jobs from a shared queue, so a worker such as an
+/// NgSpice instance never moves between threads. The first failure cancels
+/// the token passed to the other jobs, waits for them to stop and is returned.
+pub(crate) async fn run_on_workers<W, J, T, N, NFut, F, Fut>(
+    workers: usize,
+    jobs: Vec<J>,
+    new_worker: N,
+    run: F,
+) -> Result<Vec<T>>
+where
+    J: Send + 'static,
+    T: Send + 'static,
+    N: Fn() -> NFut + Send + Sync + 'static,
+    NFut: Future<Output = Result<W>>,
+    F: Fn(Rc<W>, J, CancellationToken) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut outputs: Vec<Option<T>> = jobs.iter().map(|_| None).collect();
+    let queue = Arc::new(std::sync::Mutex::new(jobs.into_iter().enumerate()));
+    let (new_worker, run) = (Arc::new(new_worker), Arc::new(run));
+    let token = CancellationToken::new();
+
+    let mut running = FuturesUnordered::new();
+    for _ in 0..workers {
+        let (queue, new_worker, run, token) = (queue.clone(), new_worker.clone(), run.clone(), token.clone());
+        let (sender, receiver) = oneshot::channel();
+        std::thread::spawn(move || {
+            let work = async {
+                let worker = Rc::new(new_worker().await?);
+                let mut finished = Vec::new();
+                while !token.is_cancelled() {
+                    let next = queue.lock().expect("batch queue lock poisoned").next();
+                    let Some((index, job)) = next else { break };
+                    finished.push((index, run(worker.clone(), job, token.clone()).await?));
+                }
+                Ok(finished)
+            };
+            let finished = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| SimulationError::FfiError(format!("Failed to start batch worker: {}", e)))
+                .and_then(|runtime| runtime.block_on(work));
+            let _ = sender.send(finished);
+        });
+        running.push(receiver);
+    }
+
+    let mut first_error = None;
+    while let Some(received) = running.next().await {
+        let finished = received
+            .map_err(|_| SimulationError::FfiError("Batch worker panicked".to_string()))
+            .and_then(|finished| finished);
+        match finished {
+            Ok(finished) => finished.into_iter().for_each(|(index, output)| outputs[index] = Some(output)),
+            Err(e) => {
+                token.cancel();
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(outputs.into_iter().flatten().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType};
+
+    fn job() -> SimulationJob {
+        let mut circuit = Circuit::new();
+        circuit.add_component(Component {
+            id: "R1".to_string(),
+            component_type: ComponentType::Resistor,
+            value: Some("1k".to_string()),
+            position: (0.0, 0.0),
+            purpose: None,
+        });
+        SimulationJob::new(circuit, AnalysisType::DC)
+    }
+
+    #[test]
+    fn test_prepare_applies_overrides() {
+        let (circuit, directive) = job().with_override("R1", 2200.0).prepare().unwrap();
+        assert_eq!(directive, ".op");
+        assert_eq!(circuit.components[0].value.as_deref(), Some("2.2e3"));
+
+        assert!(job().with_override("R9", 1.0).prepare().is_err());
+        assert_eq!(job().prepare().unwrap().0, job().circuit);
+    }
+
+    #[tokio::test]
+    async fn test_run_on_workers_keeps_input_order() {
+        // The first three jobs wait until all three run at once, then finish in reverse
+        let barrier = Arc::new(tokio::sync::Barrier::new(3));
+        let jobs: Vec<(u64, bool)> = vec![(40, true), (20, true), (0, true), (10, false), (5, false)];
+        let run = move |_worker: Rc<()>, (delay, wait): (u64, bool), _token: CancellationToken| {
+            let barrier = barrier.clone();
+            async move {
+                if wait {
+                    barrier.wait().await;
+                }
+                std::thread::sleep(Duration::from_millis(delay));
+                Ok(delay)
+            }
+        };
+
+        let outputs = tokio::time::timeout(Duration::from_secs(5), run_on_workers(3, jobs, || async { Ok(()) }, run))
+            .await
+            .expect("jobs should run concurrently");
+        assert_eq!(outputs.unwrap(), vec![40, 20, 0, 10, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_expired_job_times_out() {
+        let halted = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let run = {
+            let halted = halted.clone();
+            move |_worker: Rc<()>, timeout: Option<Duration>, token: CancellationToken| {
+                let halted = halted.clone();
+                async move {
+                    with_timeout(timeout, &token, |token| async move {
+                        token.cancelled().await;
+                        halted.store(true, std::sync::atomic::Ordering::SeqCst);
+                        Err::<(), _>(SimulationError::Cancelled)
+                    })
+                    .await
+                }
+            }
+        };
+
+        let jobs = vec![Some(Duration::from_millis(10))];
+        let error = run_on_workers(1, jobs, || async { Ok(()) }, run).await.unwrap_err();
+        assert!(matches!(error, SimulationError::Timeout { timeout_ms: 10 }));
+        assert!(halted.load(std::sync::atomic::Ordering::SeqCst));
+    }
+}
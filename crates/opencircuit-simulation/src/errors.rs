@@ -58,12 +58,21 @@ pub enum SimulationError {
         timeout_ms: u64,
     },
     
+    #[error("Simulation cancelled")]
+    Cancelled,
+    
     #[error("Analysis error: {analysis_type} - {reason}")]
     AnalysisError {
         analysis_type: String,
         reason: String,
     },
     
+    #[error("Model error in '{model}': {reason}")]
+    ModelError {
+        model: String,
+        reason: String,
+    },
+    
     #[error("Library loading error: {0}")]
     LibraryError(#[from] libloading::Error),
     
@@ -71,6 +80,12 @@ pub enum SimulationError {
     Generic(#[from] anyhow::Error),
 }
 
+impl From<SimulationError> for opencircuit_core::OpenCircuitError {
+    fn from(error: SimulationError) -> Self {
+        opencircuit_core::OpenCircuitError::Circuit(error.to_string())
+    }
+}
+
 impl SimulationError {
     /// Check if the error is recoverable
     pub fn is_recoverable(&self) -> bool {
@@ -101,7 +116,9 @@ impl SimulationError {
             SimulationError::IoError(_) => "io",
             SimulationError::FfiError(_) => "ffi",
             SimulationError::Timeout { .. } => "performance",
+            SimulationError::Cancelled => "cancelled",
             SimulationError::AnalysisError { .. } => "analysis",
+            SimulationError::ModelError { .. } => "models",
             SimulationError::Generic(_) => "unknown",
         }
     }
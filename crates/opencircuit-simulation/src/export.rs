@@ -0,0 +1,302 @@
+//! Waveform export to CSV and SPICE3 raw files
+//!
+//! Both formats use the same table: the scale vector (time, frequency or the
+//! swept parameter) followed by node voltages under their node names and
+//! branch currents as `i(name)`, each sorted by name. AC data is complex; the
+//! CSV splits every vector into `re(...)` and `im(...)` columns while the raw
+//! file stores real/imaginary pairs as NgSpice does. Raw values are written
+//! as little-endian doubles.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::errors::{Result, SimulationError};
+use crate::results::{AnalysisData, ComplexValue, DCResults, SimulationResults, SweepResults};
+
+/// Vector of a waveform table
+struct Vector {
+    name: String,
+    /// SPICE raw variable type: time, frequency, voltage, current or notype
+    kind: &'static str,
+    values: Vec<ComplexValue>,
+}
+
+/// Scale and dependent vectors of one analysis, all the same length
+struct WaveformTable {
+    plot_name: &'static str,
+    complex: bool,
+    /// The first vector is the scale when the analysis has one
+    vectors: Vec<Vector>,
+    points: usize,
+}
+
+impl SimulationResults {
+    /// Write the waveforms as CSV, one row per time, frequency or sweep point
+    pub fn to_csv(&self, path: &Path) -> Result<()> {
+        let table = self.waveform_table()?;
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let header: Vec<String> = table
+            .vectors
+            .iter()
+            .flat_map(|vector| {
+                if table.complex && vector.kind != "frequency" {
+                    vec![format!("re({})", vector.name), format!("im({})", vector.name)]
+                } else {
+                    vec![vector.name.clone()]
+                }
+            })
+            .map(|name| csv_field(&name))
+            .collect();
+        writeln!(writer, "{}", header.join(","))?;
+
+        for point in 0..table.points {
+            let row: Vec<String> = table
+                .vectors
+                .iter()
+                .flat_map(|vector| {
+                    let value = &vector.values[point];
+                    if table.complex && vector.kind != "frequency" {
+                        vec![format!("{:e}", value.real), format!("{:e}", value.imaginary)]
+                    } else {
+                        vec![format!("{:e}", value.real)]
+                    }
+                })
+                .collect();
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the waveforms in SPICE3 binary raw format, readable by nutmeg
+    pub fn to_spice_raw(&self, path: &Path) -> Result<()> {
+        let table = self.waveform_table()?;
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let title = self.metadata.get("title").map(String::as_str).unwrap_or("OpenCircuit simulation");
+        writeln!(writer, "Title: {}", title)?;
+        writeln!(writer, "Date: {}", chrono::Utc::now().format("%a %b %e %H:%M:%S %Y"))?;
+        writeln!(writer, "Plotname: {}", table.plot_name)?;
+        writeln!(writer, "Flags: {}", if table.complex { "complex" } else { "real" })?;
+        writeln!(writer, "No. Variables: {}", table.vectors.len())?;
+        writeln!(writer, "No. Points: {}", table.points)?;
+        writeln!(writer, "Variables:")?;
+        for (index, vector) in table.vectors.iter().enumerate() {
+            writeln!(writer, "\t{}\t{}\t{}", index, vector.name, vector.kind)?;
+        }
+        writeln!(writer, "Binary:")?;
+
+        for point in 0..table.points {
+            for vector in &table.vectors {
+                let value = &vector.values[point];
+                writer.write_all(&value.real.to_le_bytes())?;
+                if table.complex {
+                    writer.write_all(&value.imaginary.to_le_bytes())?;
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn waveform_table(&self) -> Result<WaveformTable> {
+        let (plot_name, complex, vectors) = match &self.data {
+            AnalysisData::Transient(tran) => {
+                let mut vectors = vec![real_vector("time", "time", &tran.time_points)];
+                vectors.extend(sorted(&tran.voltage_waveforms).map(|(name, values)| real_vector(name, "voltage", values)));
+                vectors.extend(
+                    sorted(&tran.current_waveforms)
+                        .map(|(name, values)| real_vector(&format!("i({})", name), "current", values)),
+                );
+                vectors.extend(
+                    sorted(&tran.power_waveforms)
+                        .map(|(name, values)| real_vector(&format!("p({})", name), "notype", values)),
+                );
+                ("Transient Analysis", false, vectors)
+            }
+            AnalysisData::AC(ac) => {
+                let mut vectors = vec![real_vector("frequency", "frequency", &ac.frequencies)];
+                vectors.extend(sorted(&ac.voltage_responses).map(|(name, values)| Vector {
+                    name: name.clone(),
+                    kind: "voltage",
+                    values: values.clone(),
+                }));
+                vectors.extend(sorted(&ac.current_responses).map(|(name, values)| Vector {
+                    name: format!("i({})", name),
+                    kind: "current",
+                    values: values.clone(),
+                }));
+                ("AC Analysis", true, vectors)
+            }
+            AnalysisData::DC(dc) => match &dc.sweep_data {
+                Some(sweep) => {
+                    let mut vectors = vec![real_vector("sweep", "notype", &sweep.parameter_values)];
+                    vectors.extend(sweep_vectors(sweep, "", "voltage", |point| &point.node_voltages));
+                    vectors.extend(sweep_vectors(sweep, "i", "current", |point| &point.branch_currents));
+                    ("DC transfer characteristic", false, vectors)
+                }
+                None => {
+                    let mut vectors: Vec<Vector> = sorted(&dc.node_voltages)
+                        .map(|(name, value)| real_vector(name, "voltage", &[*value]))
+                        .collect();
+                    vectors.extend(
+                        sorted(&dc.branch_currents)
+                            .map(|(name, value)| real_vector(&format!("i({})", name), "current", &[*value])),
+                    );
+                    ("Operating Point", false, vectors)
+                }
+            },
+            AnalysisData::Raw(_) => {
+                return Err(SimulationError::AnalysisError {
+                    analysis_type: format!("{:?}", self.analysis_type),
+                    reason: "raw output has no waveforms to export".to_string(),
+                })
+            }
+        };
+
+        let points = vectors.first().map_or(0, |vector| vector.values.len());
+        if let Some(vector) = vectors.iter().find(|vector| vector.values.len() != points) {
+            return Err(SimulationError::AnalysisError {
+                analysis_type: format!("{:?}", self.analysis_type),
+                reason: format!("vector {} has {} points, expected {}", vector.name, vector.values.len(), points),
+            });
+        }
+        Ok(WaveformTable { plot_name, complex, vectors, points })
+    }
+}
+
+fn real_vector(name: &str, kind: &'static str, values: &[f64]) -> Vector {
+    Vector {
+        name: name.to_string(),
+        kind,
+        values: values.iter().map(|value| ComplexValue::new(*value, 0.0)).collect(),
+    }
+}
+
+/// One vector per name found at any sweep point, NaN where a point lacks it
+fn sweep_vectors(
+    sweep: &SweepResults,
+    prefix: &str,
+    kind: &'static str,
+    select: impl Fn(&DCResults) -> &HashMap<String, f64>,
+) -> Vec<Vector> {
+    let mut names: Vec<&String> = sweep.results.iter().flat_map(|point| select(point).keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let values: Vec<f64> =
+                sweep.results.iter().map(|point| select(point).get(name).copied().unwrap_or(f64::NAN)).collect();
+            let name = if prefix.is_empty() { name.clone() } else { format!("{}({})", prefix, name) };
+            real_vector(&name, kind, &values)
+        })
+        .collect()
+}
+
+/// Map entries ordered by key
+fn sorted<V>(map: &HashMap<String, V>) -> impl Iterator<Item = (&String, &V)> {
+    map.iter().collect::<BTreeMap<_, _>>().into_iter()
+}
+
+/// Quote a CSV field when it contains a separator or quote
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::AnalysisType;
+    use crate::results::{ACResults, TransientResults};
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("opencircuit_export_{}_{}", std::process::id(), name))
+    }
+
+    fn sine_transient() -> SimulationResults {
+        let time_points: Vec<f64> = (0..200).map(|n| n as f64 * 1.234_567e-6).collect();
+        let out: Vec<f64> = time_points.iter().map(|t| 3.3 * (2.0 * std::f64::consts::PI * 1e4 * t).sin()).collect();
+        let supply: Vec<f64> = time_points.iter().map(|t| -1.5e-3 * (1.0 + t * 1e3)).collect();
+        SimulationResults::new(
+            AnalysisType::Transient,
+            AnalysisData::Transient(TransientResults {
+                time_points,
+                voltage_waveforms: HashMap::from([("out".to_string(), out)]),
+                current_waveforms: HashMap::from([("v1#branch".to_string(), supply)]),
+                power_waveforms: HashMap::new(),
+            }),
+        )
+    }
+
+    fn within_ppm(actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() <= expected.abs() * 1e-6 + f64::MIN_POSITIVE
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let results = sine_transient();
+        let path = temp_path("transient.csv");
+        results.to_csv(&path).unwrap();
+
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        assert_eq!(reader.headers().unwrap().iter().collect::<Vec<_>>(), ["time", "out", "i(v1#branch)"]);
+        let rows: Vec<Vec<f64>> = reader
+            .records()
+            .map(|record| record.unwrap().iter().map(|field| field.parse().unwrap()).collect())
+            .collect();
+        std::fs::remove_file(&path).ok();
+
+        let AnalysisData::Transient(tran) = &results.data else { unreachable!() };
+        assert_eq!(rows.len(), tran.time_points.len());
+        for (row, point) in rows.iter().zip(0..) {
+            assert!(within_ppm(row[0], tran.time_points[point]));
+            assert!(within_ppm(row[1], tran.voltage_waveforms["out"][point]));
+            assert!(within_ppm(row[2], tran.current_waveforms["v1#branch"][point]));
+        }
+    }
+
+    #[test]
+    fn test_spice_raw_layout() {
+        let results = SimulationResults::new(
+            AnalysisType::AC,
+            AnalysisData::AC(ACResults {
+                frequencies: vec![1e3, 1e4],
+                voltage_responses: HashMap::from([(
+                    "out".to_string(),
+                    vec![ComplexValue::new(0.99, -0.1), ComplexValue::new(0.5, -0.5)],
+                )]),
+                current_responses: HashMap::new(),
+                transfer_functions: HashMap::new(),
+            }),
+        );
+        let path = temp_path("ac.raw");
+        results.to_spice_raw(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let marker = b"Binary:\n";
+        let start = bytes.windows(marker.len()).position(|window| window == marker).unwrap();
+        let header = String::from_utf8_lossy(&bytes[..start]);
+        assert!(header.contains("Plotname: AC Analysis\nFlags: complex\nNo. Variables: 2\nNo. Points: 2\n"));
+        assert!(header.contains("\t0\tfrequency\tfrequency\n\t1\tout\tvoltage\n"));
+
+        let values: Vec<f64> = bytes[start + marker.len()..]
+            .chunks_exact(8)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(values, [1e3, 0.0, 0.99, -0.1, 1e4, 0.0, 0.5, -0.5]);
+
+        let raw = SimulationResults::default_dc();
+        assert!(raw.to_csv(&temp_path("raw.csv")).is_err());
+    }
+}
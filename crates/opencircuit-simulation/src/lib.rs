@@ -8,16 +8,37 @@ use opencircuit_circuit::Circuit;
 pub mod ngspice_wrapper;
 pub mod spice_parser;
 pub mod analysis;
+pub mod batch;
 pub mod results;
 pub mod errors;
+pub mod export;
 pub mod memory;
+pub mod model_fitting;
+pub mod model_library;
+pub mod noise;
+pub mod operating_point;
+pub mod parametric;
+pub mod sensitivity;
+pub mod stress;
+pub mod validation;
+pub mod worst_case;
 
-pub use ngspice_wrapper::NgSpiceWrapper;
+pub use ngspice_wrapper::{NgSpiceWrapper, SimulationHalt};
 pub use spice_parser::SpiceParser;
+pub use batch::SimulationJob;
 pub use analysis::*;
 pub use results::*;
 pub use errors::{SimulationError, Result};
 pub use memory::MemoryPool;
+pub use model_library::{ModelLibrary, SpiceModel};
+pub use noise::NoiseResults;
+pub use parametric::ParametricResults;
+pub use sensitivity::SensitivityResults;
+pub use stress::{ComponentRating, StressType, StressViolation};
+pub use validation::{validate_spice_netlist, NetlistIssue, NetlistIssueKind, NetlistValidationReport};
+pub use worst_case::{ComponentToleranceMap, CornerConfig, NodeBounds, WorstCaseResults};
+pub use tokio_util::sync::CancellationToken;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -25,10 +46,12 @@ use tokio::sync::Mutex;
 pub struct SimulationEngine {
     ngspice: Arc<Mutex<NgSpiceWrapper>>,
     parser: SpiceParser,
+    model_library: ModelLibrary,
 }
 
 impl SimulationEngine {
     /// Create a new simulation engine
+    #[tracing::instrument]
     pub async fn new() -> Result<Self> {
         let ngspice = NgSpiceWrapper::new().await?;
         let parser = SpiceParser::new();
@@ -36,26 +59,408 @@ impl SimulationEngine {
         Ok(Self {
             ngspice: Arc::new(Mutex::new(ngspice)),
             parser,
+            model_library: ModelLibrary::standard(),
         })
     }
 
+    /// Pre-load device models for all subsequent simulations
+    pub fn load_model_library(&mut self, lib: ModelLibrary) {
+        tracing::info!("Loading {} device models", lib.models.len());
+        self.model_library.merge(lib);
+    }
+
+    /// Models available to simulations
+    pub fn model_library(&self) -> &ModelLibrary {
+        &self.model_library
+    }
+
     /// Simulate a circuit and return results
-    pub async fn simulate_circuit(&mut self, circuit: &Circuit) -> Result<SimulationResults> {
+    ///
+    /// Cancelling `token` halts NgSpice mid-run and returns
+    /// `SimulationError::Cancelled`.
+    #[tracing::instrument(skip(self, circuit, token), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn simulate_circuit(&mut self, circuit: &Circuit, token: CancellationToken) -> Result<SimulationResults> {
         tracing::info!("Starting circuit simulation");
         
         // Generate SPICE netlist
-        let netlist = self.parser.generate_netlist(circuit)?;
+        let circuit_netlist = self.parser.generate_netlist(circuit)?;
+        let netlist = self.model_library.inject_into(&circuit_netlist);
         tracing::debug!("Generated netlist: {}", netlist);
         
-        // Run simulation
+        // Run simulation; NgSpice is halted if the token is cancelled meanwhile
         let ngspice = self.ngspice.lock().await;
-        let results = ngspice.run_simulation(netlist).await?;
+        let mut results = ngspice.run_simulation_cancellable(netlist, &token).await?;
+        self.flag_approximate_models(&circuit_netlist, &mut results);
         
         tracing::info!("Simulation completed successfully");
         Ok(results)
     }
 
+    /// Simulate a raw SPICE netlist using the given analysis directive (e.g. `.op`, `.tran 1u 1m`)
+    #[tracing::instrument(skip(self, netlist), fields(netlist_len = netlist.len()))]
+    pub async fn simulate_netlist(&mut self, netlist: &str, analysis: &str) -> Result<SimulationResults> {
+        tracing::info!("Starting netlist simulation: {}", analysis);
+
+        let netlist = Self::insert_analysis(netlist, analysis)?;
+        let prepared = self.model_library.inject_into(&netlist);
+        tracing::debug!("Prepared netlist: {}", prepared);
+
+        let ngspice = self.ngspice.lock().await;
+        let mut results = ngspice.run_simulation(prepared).await?;
+        results.analysis_type = Self::analysis_type_for(analysis);
+        self.flag_approximate_models(&netlist, &mut results);
+
+        tracing::info!("Simulation completed successfully");
+        Ok(results)
+    }
+
+    /// Mark results that depend on AI-generated device models as approximate
+    fn flag_approximate_models(&self, netlist: &str, results: &mut SimulationResults) {
+        Self::mark_approximate(&self.model_library.ai_generated_models_used(netlist), results);
+    }
+
+    /// Mark results as approximate when `generated` lists AI-generated models
+    fn mark_approximate(generated: &[String], results: &mut SimulationResults) {
+        if !generated.is_empty() {
+            results.metadata.insert("approximate".to_string(), "true".to_string());
+            results
+                .warnings
+                .push(format!("Approximate results: AI-generated models used for {}", generated.join(", ")));
+        }
+    }
+
+    /// Fit a resistor model to measured (voltage, current) pairs
+    pub fn fit_resistor_model(measurements: &[(f64, f64)]) -> Result<SpiceModel> {
+        model_fitting::fit_resistor(measurements)
+    }
+
+    /// Fit a capacitor model (C, ESR, ESL) to measured (frequency, |Z|) pairs
+    pub fn fit_capacitor_model(measurements: &[(f64, f64)]) -> Result<SpiceModel> {
+        model_fitting::fit_capacitor(measurements)
+    }
+
+    /// Fit a diode model (IS, N, RS) to measured forward (voltage, current) pairs
+    pub fn fit_diode_model(measurements: &[(f64, f64)]) -> Result<SpiceModel> {
+        model_fitting::fit_diode(measurements)
+    }
+
+    /// Compute the sensitivity of `output_node` to every component value
+    ///
+    /// Runs a DC sensitivity analysis, or an AC one at `frequency` when given.
+    #[tracing::instrument(skip(self, circuit), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn run_sensitivity(
+        &mut self,
+        circuit: &Circuit,
+        output_node: &str,
+        frequency: Option<f64>,
+    ) -> Result<SensitivityResults> {
+        let output = Self::output_vector(output_node);
+        let directive = Self::sensitivity_directive(&output, frequency)?;
+
+        let netlist = self.parser.generate_netlist(circuit)?;
+        let results = self.simulate_netlist(&netlist, &directive).await?;
+        let sensitivities = SensitivityResults::from_output(&output, frequency, results.raw_output().unwrap_or_default());
+        if sensitivities.sensitivities.is_empty() {
+            return Err(SimulationError::AnalysisError {
+                analysis_type: "sensitivity".to_string(),
+                reason: "NgSpice returned no sensitivity output".to_string(),
+            });
+        }
+
+        Ok(sensitivities)
+    }
+
+    /// Compute input-referred noise density and total RMS noise over a decade sweep
+    #[tracing::instrument(skip(self, circuit), fields(circuit_component_count = circuit.components.len()))]
+    pub async fn run_noise(
+        &mut self,
+        circuit: &Circuit,
+        output_node: &str,
+        input_source: &str,
+        freq_start: f64,
+        freq_stop: f64,
+        points: u32,
+    ) -> Result<NoiseResults> {
+        let output = Self::output_vector(output_node);
+        let directive = Self::noise_directive(&output, input_source, freq_start, freq_stop, points)?;
+
+        let netlist = self.parser.generate_netlist(circuit)?;
+        let results = self.simulate_netlist(&netlist, &directive).await?;
+        let mut lines = results.raw_output().unwrap_or_default().to_vec();
+        lines.extend(self.noise_spectrum().await?);
+
+        let noise = NoiseResults::from_output(&output, input_source, &lines);
+        if noise.input_noise_density.is_empty() {
+            return Err(SimulationError::AnalysisError {
+                analysis_type: "noise".to_string(),
+                reason: "NgSpice returned no noise spectrum".to_string(),
+            });
+        }
+
+        Ok(noise)
+    }
+
+    /// Print the input noise spectrum of the latest noise analysis
+    ///
+    /// NgSpice leaves the integrated totals as the current plot, with the
+    /// spectrum in the plot before it.
+    async fn noise_spectrum(&self) -> Result<Vec<String>> {
+        let ngspice = self.ngspice.lock().await;
+        let Some(plot) = ngspice.current_plot().as_deref().and_then(noise::spectrum_plot) else {
+            return Ok(Vec::new());
+        };
+        ngspice.command_output(&format!("setplot {}", plot)).await?;
+        ngspice.command_output("print inoise_spectrum").await
+    }
+
+    /// Build the `.noise` directive
+    fn noise_directive(
+        output: &str,
+        input_source: &str,
+        freq_start: f64,
+        freq_stop: f64,
+        points: u32,
+    ) -> Result<String> {
+        let invalid = |reason: String| SimulationError::AnalysisError {
+            analysis_type: "noise".to_string(),
+            reason,
+        };
+
+        if input_source.trim().is_empty() {
+            return Err(invalid("missing input source".to_string()));
+        }
+        if !(freq_start > 0.0 && freq_stop > freq_start && freq_stop.is_finite()) {
+            return Err(invalid(format!("invalid frequency range {} to {}", freq_start, freq_stop)));
+        }
+        if points == 0 {
+            return Err(invalid("points per decade must be positive".to_string()));
+        }
+
+        Ok(format!(".noise {} {} dec {} {} {}", output, input_source, points, freq_start, freq_stop))
+    }
+
+    /// Find the node voltage extremes across component tolerance corners
+    ///
+    /// Circuits with up to [`worst_case::MAX_EXHAUSTIVE_COMPONENTS`] toleranced
+    /// components are simulated at every corner. Larger circuits are perturbed one
+    /// component at a time to estimate sensitivities, and only the corners those
+    /// sensitivities point to are simulated.
+    #[tracing::instrument(skip(self, circuit, tolerances), fields(circuit_component_count = circuit.components.len(), tolerance_count = tolerances.len()))]
+    pub async fn run_worst_case(
+        &mut self,
+        circuit: &Circuit,
+        analysis: AnalysisType,
+        tolerances: &ComponentToleranceMap,
+    ) -> Result<WorstCaseResults> {
+        let directive = AnalysisCommand {
+            analysis_type: analysis.clone(),
+            parameters: HashMap::new(),
+        }
+        .to_spice_command();
+        if directive.starts_with('*') {
+            return Err(SimulationError::AnalysisError {
+                analysis_type: format!("{:?}", analysis),
+                reason: "not supported for worst-case analysis".to_string(),
+            });
+        }
+
+        let specs = worst_case::tolerance_specs(circuit, tolerances)?;
+        let mut results = WorstCaseResults {
+            exhaustive: specs.len() <= worst_case::MAX_EXHAUSTIVE_COMPONENTS,
+            ..Default::default()
+        };
+        tracing::info!("Running worst-case analysis over {} components", specs.len());
+
+        let corners = if results.exhaustive {
+            worst_case::exhaustive_corners(&specs)
+        } else {
+            let nominal_corner = worst_case::nominal_corner(&specs);
+            let nominal = self.simulate_corner(circuit, &nominal_corner, &directive).await?;
+            results.record(&nominal_corner, &nominal);
+
+            let mut sensitivities: HashMap<String, HashMap<String, f64>> = HashMap::new();
+            for (spec, corner) in specs.iter().zip(worst_case::perturbation_corners(&specs)) {
+                let voltages = self.simulate_corner(circuit, &corner, &directive).await?;
+                results.record(&corner, &voltages);
+                for (node, delta) in worst_case::node_deltas(&nominal, &voltages) {
+                    sensitivities.entry(node).or_default().insert(spec.component_id.clone(), delta);
+                }
+            }
+
+            worst_case::guided_corners(&specs, &sensitivities)
+        };
+
+        for corner in &corners {
+            let voltages = self.simulate_corner(circuit, corner, &directive).await?;
+            results.record(corner, &voltages);
+        }
+
+        Ok(results)
+    }
+
+    /// Simulate the circuit once for each value of one component
+    ///
+    /// Each value is applied to a copy of the circuit, so `circuit` itself is
+    /// left unchanged. Results are returned in the order of `values`.
+    #[tracing::instrument(skip(self, circuit, values), fields(circuit_component_count = circuit.components.len(), value_count = values.len()))]
+    pub async fn run_parametric_sweep(
+        &mut self,
+        circuit: &Circuit,
+        component_id: &str,
+        values: Vec<f64>,
+        analysis: AnalysisType,
+    ) -> Result<ParametricResults> {
+        let directive = AnalysisCommand {
+            analysis_type: analysis.clone(),
+            parameters: HashMap::new(),
+        }
+        .to_spice_command();
+        if directive.starts_with('*') {
+            return Err(SimulationError::AnalysisError {
+                analysis_type: format!("{:?}", analysis),
+                reason: "not supported for parametric sweeps".to_string(),
+            });
+        }
+
+        let mut results = ParametricResults {
+            component_id: component_id.to_string(),
+            results: Vec::with_capacity(values.len()),
+        };
+        for value in values {
+            let netlist = self.parser.generate_netlist(&parametric::apply_value(circuit, component_id, value)?)?;
+            let run = self.simulate_netlist(&netlist, &directive).await?;
+            if run.raw_output().is_none_or(<[String]>::is_empty) {
+                return Err(SimulationError::AnalysisError {
+                    analysis_type: "parametric sweep".to_string(),
+                    reason: format!("NgSpice returned no vectors for {} = {}", component_id, value),
+                });
+            }
+            results.results.push((value, run));
+        }
+
+        Ok(results)
+    }
+
+    /// Simulate independent jobs concurrently, one NgSpice instance per worker
+    ///
+    /// Up to one worker per CPU runs at a time. Results are returned in the
+    /// order of `jobs`; the first job to fail or exceed its timeout fails
+    /// the batch and halts the jobs still running.
+    #[tracing::instrument(skip(self, jobs), fields(job_count = jobs.len()))]
+    pub async fn batch_simulate(&mut self, jobs: Vec<SimulationJob>) -> Result<Vec<SimulationResults>> {
+        let mut runs = Vec::with_capacity(jobs.len());
+        for job in jobs {
+            let (circuit, directive) = job.prepare()?;
+            let circuit_netlist = self.parser.generate_netlist(&circuit)?;
+            let netlist = Self::insert_analysis(&circuit_netlist, &directive)?;
+            let prepared = self.model_library.inject_into(&netlist);
+            let generated = self.model_library.ai_generated_models_used(&circuit_netlist);
+            runs.push((job, prepared, generated));
+        }
+
+        // Instances sharing one loaded library would share one circuit, so
+        // every worker loads its own copy
+        let workers = num_cpus::get().min(runs.len());
+        tracing::info!("Running {} simulations on {} NgSpice instances", runs.len(), workers);
+        batch::run_on_workers(workers, runs, NgSpiceWrapper::new_isolated, |ngspice, (job, prepared, generated), token| async move {
+            let mut results = batch::with_timeout(job.timeout, &token, |token| async move {
+                ngspice.run_simulation_cancellable(prepared, &token).await
+            })
+            .await?;
+            results.analysis_type = job.analysis;
+            Self::mark_approximate(&generated, &mut results);
+            Ok(results)
+        })
+        .await
+    }
+
+    /// Simulate the circuit with a corner's component values and collect node voltages
+    #[tracing::instrument(skip(self, circuit, corner))]
+    async fn simulate_corner(
+        &mut self,
+        circuit: &Circuit,
+        corner: &CornerConfig,
+        directive: &str,
+    ) -> Result<HashMap<String, Vec<f64>>> {
+        let netlist = self.parser.generate_netlist(&worst_case::apply_corner(circuit, corner))?;
+        let results = self.simulate_netlist(&netlist, directive).await?;
+        let voltages = worst_case::parse_node_voltages(results.raw_output().unwrap_or_default());
+        if voltages.is_empty() {
+            return Err(SimulationError::AnalysisError {
+                analysis_type: "worst-case".to_string(),
+                reason: "NgSpice returned no node voltages".to_string(),
+            });
+        }
+
+        Ok(voltages)
+    }
+
+    /// Build the `.sens` directive for an output vector
+    fn sensitivity_directive(output: &str, frequency: Option<f64>) -> Result<String> {
+        match frequency {
+            None => Ok(format!(".sens {}", output)),
+            Some(frequency) if frequency > 0.0 && frequency.is_finite() => {
+                Ok(format!(".sens {} ac lin 1 {} {}", output, frequency, frequency))
+            }
+            Some(frequency) => Err(SimulationError::AnalysisError {
+                analysis_type: "sensitivity".to_string(),
+                reason: format!("invalid frequency {}", frequency),
+            }),
+        }
+    }
+
+    /// Wrap a bare node name as a SPICE voltage vector (`out` -> `v(out)`)
+    fn output_vector(output_node: &str) -> String {
+        let lower = output_node.to_lowercase();
+        if lower.starts_with("v(") || lower.starts_with("i(") {
+            output_node.to_string()
+        } else {
+            format!("v({})", output_node)
+        }
+    }
+
+    /// Insert an analysis directive ahead of the netlist's `.end` card
+    ///
+    /// The directive replaces any `.op` already in the netlist, such as the
+    /// default one generated netlists carry.
+    fn insert_analysis(netlist: &str, analysis: &str) -> Result<String> {
+        let analysis = analysis.trim();
+        if !analysis.starts_with('.') {
+            return Err(SimulationError::AnalysisError {
+                analysis_type: analysis.to_string(),
+                reason: "expected a SPICE analysis directive such as '.op'".to_string(),
+            });
+        }
+
+        let mut lines: Vec<&str> = netlist
+            .lines()
+            .filter(|line| !line.trim().eq_ignore_ascii_case(".end") && !line.trim().eq_ignore_ascii_case(".op"))
+            .collect();
+        lines.push(analysis);
+        lines.push(".end");
+        Ok(lines.join("\n"))
+    }
+
+    /// Map an analysis directive to its analysis type
+    fn analysis_type_for(analysis: &str) -> AnalysisType {
+        let directive = analysis
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match directive.as_str() {
+            ".ac" => AnalysisType::AC,
+            ".tran" => AnalysisType::Transient,
+            ".dc" => AnalysisType::DCSweep,
+            ".noise" => AnalysisType::Noise,
+            ".disto" => AnalysisType::Distortion,
+            ".sens" => AnalysisType::Sensitivity,
+            _ => AnalysisType::DC,
+        }
+    }
+
     /// Check if NgSpice is available and working
+    #[tracing::instrument(skip(self))]
     pub async fn health_check(&self) -> Result<bool> {
         let ngspice = self.ngspice.lock().await;
         ngspice.health_check().await
@@ -75,4 +480,60 @@ mod tests {
             Err(e) => println!("NgSpice not available: {}", e),
         }
     }
+
+    #[tokio::test]
+    async fn test_simulate_netlist_returns_node_voltages() {
+        // Skipped when NgSpice is not installed
+        let Ok(mut engine) = SimulationEngine::new().await else {
+            return;
+        };
+        let netlist = "Divider\nV1 in 0 DC 10\nR1 in out 1k\nR2 out 0 1k\n.end";
+        let results = engine.simulate_netlist(netlist, ".op").await.unwrap();
+
+        let voltages = worst_case::parse_node_voltages(results.raw_output().unwrap());
+        assert!((voltages["v(out)"][0] - 5.0).abs() < 1e-6, "{:?}", results.raw_output());
+    }
+
+    #[test]
+    fn test_insert_analysis() {
+        let netlist = "* test\nV1 1 0 5\nR1 1 0 1k\n.end";
+        let prepared = SimulationEngine::insert_analysis(netlist, ".tran 1u 1m").unwrap();
+        assert!(prepared.ends_with(".tran 1u 1m\n.end"));
+        assert_eq!(prepared.matches(".end").count(), 1);
+
+        assert!(SimulationEngine::insert_analysis(netlist, "tran").is_err());
+
+        // A generated netlist's default .op gives way to the requested analysis
+        let prepared = SimulationEngine::insert_analysis("* test\nR1 1 0 1k\n.op\n.end", ".sens v(1)").unwrap();
+        assert!(!prepared.contains(".op"));
+        assert_eq!(SimulationEngine::analysis_type_for(".tran 1u 1m"), AnalysisType::Transient);
+        assert_eq!(SimulationEngine::analysis_type_for(".op"), AnalysisType::DC);
+    }
+
+    #[test]
+    fn test_sensitivity_directive() {
+        let output = SimulationEngine::output_vector("out");
+        assert_eq!(output, "v(out)");
+        assert_eq!(SimulationEngine::output_vector("I(V1)"), "I(V1)");
+
+        assert_eq!(SimulationEngine::sensitivity_directive(&output, None).unwrap(), ".sens v(out)");
+        assert_eq!(
+            SimulationEngine::sensitivity_directive(&output, Some(1000.0)).unwrap(),
+            ".sens v(out) ac lin 1 1000 1000"
+        );
+        assert!(SimulationEngine::sensitivity_directive(&output, Some(-1.0)).is_err());
+        assert_eq!(SimulationEngine::analysis_type_for(".sens v(out)"), AnalysisType::Sensitivity);
+    }
+
+    #[test]
+    fn test_noise_directive() {
+        assert_eq!(
+            SimulationEngine::noise_directive("v(out)", "V1", 10.0, 100000.0, 20).unwrap(),
+            ".noise v(out) V1 dec 20 10 100000"
+        );
+        assert!(SimulationEngine::noise_directive("v(out)", "V1", 0.0, 1000.0, 20).is_err());
+        assert!(SimulationEngine::noise_directive("v(out)", "V1", 1000.0, 10.0, 20).is_err());
+        assert!(SimulationEngine::noise_directive("v(out)", "", 10.0, 1000.0, 20).is_err());
+        assert!(SimulationEngine::noise_directive("v(out)", "V1", 10.0, 1000.0, 0).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,380 @@
+//! Fit device models to measurement data
+//!
+//! Linear models are solved directly with weighted least squares; the diode
+//! model is seeded from a linearised fit and refined with Levenberg-Marquardt.
+
+use crate::errors::{Result, SimulationError};
+use crate::model_library::SpiceModel;
+
+/// Thermal voltage kT/q at 300 K
+pub const THERMAL_VOLTAGE: f64 = 0.025852;
+
+const MAX_ITERATIONS: usize = 200;
+
+/// Fit a resistance to (voltage, current) pairs
+///
+/// DC V-I data carries no information about parasitic inductance or
+/// capacitance, so only `R` is fitted.
+pub fn fit_resistor(measurements: &[(f64, f64)]) -> Result<SpiceModel> {
+    require_points("RFIT", measurements, 2)?;
+
+    // V = I·R, least squares through the origin
+    let (vi, ii) = measurements
+        .iter()
+        .fold((0.0, 0.0), |(vi, ii), &(v, i)| (vi + v * i, ii + i * i));
+    if ii == 0.0 {
+        return Err(fit_error("RFIT", "all currents are zero"));
+    }
+    let resistance = vi / ii;
+
+    let predicted: Vec<f64> = measurements.iter().map(|&(_, i)| i * resistance).collect();
+    let observed: Vec<f64> = measurements.iter().map(|&(v, _)| v).collect();
+
+    let mut model = SpiceModel::new("RFIT", "R").with_parameter("R", resistance);
+    model.r_squared = Some(r_squared(&observed, &predicted));
+    Ok(model)
+}
+
+/// Fit C, ESR and ESL to (frequency Hz, |Z| Ω) pairs
+///
+/// |Z|² = ESR² + (ωL − 1/ωC)² expands to a + b·ω² + c/ω², which is linear in
+/// a = ESR² − 2L/C, b = L² and c = 1/C². The model renders as a series R-L-C
+/// `.SUBCKT` (see [`SpiceModel::to_spice`]).
+pub fn fit_capacitor(measurements: &[(f64, f64)]) -> Result<SpiceModel> {
+    require_points("CFIT", measurements, 3)?;
+    if measurements.iter().any(|&(f, z)| f <= 0.0 || z <= 0.0) {
+        return Err(fit_error("CFIT", "frequencies and impedances must be positive"));
+    }
+
+    // Weight by 1/|Z|² so every point contributes its relative error
+    let rows: Vec<Vec<f64>> = measurements
+        .iter()
+        .map(|&(f, z)| {
+            let omega = 2.0 * std::f64::consts::PI * f;
+            let weight = 1.0 / (z * z);
+            vec![weight, omega * omega * weight, weight / (omega * omega)]
+        })
+        .collect();
+    let targets = vec![1.0; measurements.len()];
+    let solution = least_squares(&rows, &targets)
+        .ok_or_else(|| fit_error("CFIT", "measurements do not determine a unique fit"))?;
+
+    let (a, b, c) = (solution[0], solution[1], solution[2]);
+    if b < 0.0 || c <= 0.0 {
+        return Err(fit_error("CFIT", "measurements are not consistent with a series RLC"));
+    }
+    let inductance = b.sqrt();
+    let capacitance = 1.0 / c.sqrt();
+    let esr = (a + 2.0 * inductance / capacitance).max(0.0).sqrt();
+
+    let impedance = |f: f64| {
+        let omega = 2.0 * std::f64::consts::PI * f;
+        esr.hypot(omega * inductance - 1.0 / (omega * capacitance))
+    };
+    let predicted: Vec<f64> = measurements.iter().map(|&(f, _)| impedance(f)).collect();
+    let observed: Vec<f64> = measurements.iter().map(|&(_, z)| z).collect();
+
+    let mut model = SpiceModel::new("CFIT", "C")
+        .with_parameter("C", capacitance)
+        .with_parameter("ESR", esr)
+        .with_parameter("ESL", inductance);
+    model.r_squared = Some(r_squared(&observed, &predicted));
+    Ok(model)
+}
+
+/// Fit IS, N and RS to forward-biased (voltage, current) pairs
+pub fn fit_diode(measurements: &[(f64, f64)]) -> Result<SpiceModel> {
+    require_points("DFIT", measurements, 3)?;
+    if measurements.iter().any(|&(_, i)| i <= 0.0) {
+        return Err(fit_error("DFIT", "currents must be positive (forward bias)"));
+    }
+
+    // For I >> IS: V ≈ N·Vt·ln(I) − N·Vt·ln(IS) + RS·I
+    let rows: Vec<Vec<f64>> = measurements.iter().map(|&(_, i)| vec![i.ln(), 1.0, i]).collect();
+    let voltages: Vec<f64> = measurements.iter().map(|&(v, _)| v).collect();
+    let seed = least_squares(&rows, &voltages)
+        .ok_or_else(|| fit_error("DFIT", "measurements do not determine a unique fit"))?;
+    let n_vt = seed[0];
+    if n_vt <= 0.0 {
+        return Err(fit_error("DFIT", "voltage does not increase with current"));
+    }
+
+    // Refine on the exact model with parameters (ln IS, N, RS)
+    let initial = [-seed[1] / n_vt, n_vt / THERMAL_VOLTAGE, seed[2].max(0.0)];
+    let fitted = levenberg_marquardt(&initial, |p| {
+        let (saturation, emission, series) = (p[0].exp(), p[1], p[2]);
+        measurements
+            .iter()
+            .map(|&(v, i)| {
+                let ratio = i / saturation;
+                let residual = emission * THERMAL_VOLTAGE * ratio.ln_1p() + i * series - v;
+                let jacobian = vec![
+                    -emission * THERMAL_VOLTAGE * ratio / (ratio + 1.0),
+                    THERMAL_VOLTAGE * ratio.ln_1p(),
+                    i,
+                ];
+                (residual, jacobian)
+            })
+            .unzip()
+    });
+
+    let (saturation, emission, series) = (fitted[0].exp(), fitted[1], fitted[2]);
+    let predicted: Vec<f64> = measurements
+        .iter()
+        .map(|&(_, i)| emission * THERMAL_VOLTAGE * (i / saturation).ln_1p() + i * series)
+        .collect();
+
+    let mut model = SpiceModel::new("DFIT", "D")
+        .with_parameter("IS", saturation)
+        .with_parameter("N", emission)
+        .with_parameter("RS", series);
+    model.r_squared = Some(r_squared(&voltages, &predicted));
+    Ok(model)
+}
+
+/// Coefficient of determination
+pub fn r_squared(observed: &[f64], predicted: &[f64]) -> f64 {
+    let mean = observed.iter().sum::<f64>() / observed.len() as f64;
+    let total: f64 = observed.iter().map(|y| (y - mean).powi(2)).sum();
+    let residual: f64 = observed
+        .iter()
+        .zip(predicted)
+        .map(|(y, p)| (y - p).powi(2))
+        .sum();
+
+    if total == 0.0 {
+        if residual == 0.0 { 1.0 } else { 0.0 }
+    } else {
+        1.0 - residual / total
+    }
+}
+
+fn require_points(model: &str, measurements: &[(f64, f64)], minimum: usize) -> Result<()> {
+    if measurements.len() < minimum {
+        return Err(fit_error(
+            model,
+            &format!("need at least {} measurements, got {}", minimum, measurements.len()),
+        ));
+    }
+    if measurements.iter().any(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+        return Err(fit_error(model, "measurements must be finite"));
+    }
+    Ok(())
+}
+
+fn fit_error(model: &str, reason: &str) -> SimulationError {
+    SimulationError::ModelError {
+        model: model.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Least-squares solution of `rows · x ≈ targets` via modified Gram-Schmidt QR
+fn least_squares(rows: &[Vec<f64>], targets: &[f64]) -> Option<Vec<f64>> {
+    let columns = rows.first()?.len();
+
+    // Normalise columns first so wildly different scales stay well conditioned
+    let scales: Vec<f64> = (0..columns)
+        .map(|j| rows.iter().map(|row| row[j] * row[j]).sum::<f64>().sqrt())
+        .collect();
+    if scales.contains(&0.0) {
+        return None;
+    }
+    let mut q: Vec<Vec<f64>> = (0..columns)
+        .map(|j| rows.iter().map(|row| row[j] / scales[j]).collect())
+        .collect();
+    let mut r = vec![vec![0.0; columns]; columns];
+
+    for j in 0..columns {
+        for k in 0..j {
+            let dot: f64 = q[k].iter().zip(&q[j]).map(|(a, b)| a * b).sum();
+            r[k][j] = dot;
+            let qk = q[k].clone();
+            q[j].iter_mut().zip(&qk).for_each(|(a, b)| *a -= dot * b);
+        }
+        let norm = q[j].iter().map(|a| a * a).sum::<f64>().sqrt();
+        if norm < 1e-12 {
+            return None;
+        }
+        r[j][j] = norm;
+        q[j].iter_mut().for_each(|a| *a /= norm);
+    }
+
+    // Back-substitute R·x = Qᵀ·targets
+    let qty: Vec<f64> = q
+        .iter()
+        .map(|column| column.iter().zip(targets).map(|(a, b)| a * b).sum())
+        .collect();
+    let mut x = vec![0.0; columns];
+    for j in (0..columns).rev() {
+        let tail: f64 = (j + 1..columns).map(|k| r[j][k] * x[k]).sum();
+        x[j] = (qty[j] - tail) / r[j][j];
+    }
+
+    Some(x.iter().zip(&scales).map(|(x, scale)| x / scale).collect())
+}
+
+/// Minimise the sum of squared residuals returned (with their gradients) by `model`
+fn levenberg_marquardt<F>(initial: &[f64], model: F) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> (Vec<f64>, Vec<Vec<f64>>),
+{
+    let cost = |residuals: &[f64]| residuals.iter().map(|r| r * r).sum::<f64>();
+
+    let mut params = initial.to_vec();
+    let (mut residuals, mut jacobian) = model(&params);
+    let mut current_cost = cost(&residuals);
+    let mut lambda = 1e-3;
+
+    for _ in 0..MAX_ITERATIONS {
+        let n = params.len();
+        let mut jtj = vec![vec![0.0; n]; n];
+        let mut jtr = vec![0.0; n];
+        for (row, residual) in jacobian.iter().zip(&residuals) {
+            for a in 0..n {
+                jtr[a] += row[a] * residual;
+                for b in 0..n {
+                    jtj[a][b] += row[a] * row[b];
+                }
+            }
+        }
+
+        let mut improved = false;
+        while lambda < 1e12 {
+            let mut damped = jtj.clone();
+            for (i, row) in damped.iter_mut().enumerate() {
+                row[i] += lambda * jtj[i][i].max(1e-30);
+            }
+            let rhs: Vec<f64> = jtr.iter().map(|g| -g).collect();
+            let Some(step) = solve(damped, rhs) else {
+                lambda *= 10.0;
+                continue;
+            };
+
+            let candidate: Vec<f64> = params.iter().zip(&step).map(|(p, s)| p + s).collect();
+            let (candidate_residuals, candidate_jacobian) = model(&candidate);
+            let candidate_cost = cost(&candidate_residuals);
+            if candidate_cost.is_finite() && candidate_cost < current_cost {
+                let converged = current_cost - candidate_cost <= 1e-15 * current_cost.max(1e-300);
+                params = candidate;
+                residuals = candidate_residuals;
+                jacobian = candidate_jacobian;
+                current_cost = candidate_cost;
+                lambda = (lambda / 10.0).max(1e-12);
+                improved = !converged;
+                break;
+            }
+            lambda *= 10.0;
+        }
+
+        if !improved {
+            break;
+        }
+    }
+
+    params
+}
+
+/// Solve a small dense linear system with partial pivoting
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let pivot_row = a[col].clone();
+        for row in col + 1..n {
+            let factor = a[row][col] / pivot_row[col];
+            for (entry, pivot_entry) in a[row][col..n].iter_mut().zip(&pivot_row[col..n]) {
+                *entry -= factor * pivot_entry;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_within_percent(actual: f64, expected: f64) {
+        let error = ((actual - expected) / expected).abs();
+        assert!(error < 0.01, "{} is not within 1% of {}", actual, expected);
+    }
+
+    fn log_space(start: f64, stop: f64, points: usize) -> Vec<f64> {
+        let ratio = (stop / start).ln() / (points - 1) as f64;
+        (0..points).map(|i| start * (ratio * i as f64).exp()).collect()
+    }
+
+    #[test]
+    fn test_fit_resistor() {
+        let measurements: Vec<(f64, f64)> = (1..=10)
+            .map(|ma| {
+                let current = ma as f64 * 1e-3;
+                (current * 4700.0, current)
+            })
+            .collect();
+
+        let model = fit_resistor(&measurements).unwrap();
+        assert_within_percent(model.parameter("R").unwrap(), 4700.0);
+        assert!(model.r_squared.unwrap() > 0.999);
+        assert!(fit_resistor(&[(1.0, 1e-3)]).is_err());
+    }
+
+    #[test]
+    fn test_fit_capacitor() {
+        let (capacitance, esr, esl): (f64, f64, f64) = (10e-6, 0.05, 5e-9);
+        let measurements: Vec<(f64, f64)> = log_space(100.0, 10e6, 40)
+            .into_iter()
+            .map(|f| {
+                let omega = 2.0 * std::f64::consts::PI * f;
+                (f, esr.hypot(omega * esl - 1.0 / (omega * capacitance)))
+            })
+            .collect();
+
+        let model = fit_capacitor(&measurements).unwrap();
+        assert_within_percent(model.parameter("C").unwrap(), capacitance);
+        assert_within_percent(model.parameter("ESR").unwrap(), esr);
+        assert_within_percent(model.parameter("ESL").unwrap(), esl);
+        assert!(model.r_squared.unwrap() > 0.999);
+
+        let spice = model.to_spice();
+        let lines: Vec<&str> = spice.lines().collect();
+        assert_eq!(lines[0], ".SUBCKT CFIT 1 2");
+        assert!(lines[1].starts_with("RESR 1 n1 "));
+        assert!(lines[2].starts_with("LESL n1 n2 "));
+        assert!(lines[3].starts_with("CCAP n2 2 "));
+        assert_eq!(lines[4], ".ENDS CFIT");
+    }
+
+    #[test]
+    fn test_fit_diode() {
+        let (saturation, emission, series): (f64, f64, f64) = (2.52e-9, 1.752, 0.568);
+        let measurements: Vec<(f64, f64)> = log_space(1e-6, 0.1, 30)
+            .into_iter()
+            .map(|i| {
+                let v = emission * THERMAL_VOLTAGE * (i / saturation).ln_1p() + i * series;
+                (v, i)
+            })
+            .collect();
+
+        let model = fit_diode(&measurements).unwrap();
+        assert_within_percent(model.parameter("IS").unwrap(), saturation);
+        assert_within_percent(model.parameter("N").unwrap(), emission);
+        assert_within_percent(model.parameter("RS").unwrap(), series);
+        assert!(model.r_squared.unwrap() > 0.999);
+        assert!(fit_diode(&[(0.6, 1e-3), (0.7, -1e-3), (0.8, 1e-2)]).is_err());
+    }
+}
@@ -0,0 +1,445 @@
+//! SPICE device model library
+//!
+//! Parses `.MODEL` statements (and `.SUBCKT` macro models) so device models can
+//! be loaded once and injected into simulation netlists that use them.
+
+use crate::errors::{Result, SimulationError};
+use opencircuit_utils::units::parse_spice_value;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Bundled models for common devices (1N4148, 2N2222, LM741)
+const STANDARD_MODELS: &str = include_str!("../assets/standard_models.lib");
+
+/// A single `.MODEL` definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpiceModel {
+    /// Model name referenced by device lines
+    pub name: String,
+    /// SPICE model type (D, NPN, PNP, NMOS, PMOS, R, C, ...)
+    pub model_type: String,
+    /// Model parameters, keyed by uppercase parameter name
+    pub parameters: BTreeMap<String, f64>,
+    /// Non-numeric parameters such as `MFG=NXP`, kept verbatim
+    #[serde(default)]
+    pub text_parameters: BTreeMap<String, String>,
+    /// Goodness of fit when the model was fitted to measurements
+    #[serde(default)]
+    pub r_squared: Option<f64>,
+    /// Generated by the AI from datasheet values, so results using it are approximate
+    #[serde(default)]
+    pub ai_generated: bool,
+}
+
+/// Collection of device models and subcircuit macro models
+#[derive(Debug, Clone, Default)]
+pub struct ModelLibrary {
+    /// `.MODEL` definitions keyed by uppercase model name
+    pub models: HashMap<String, SpiceModel>,
+    /// Raw `.SUBCKT` ... `.ENDS` blocks keyed by uppercase subcircuit name
+    pub subcircuits: HashMap<String, String>,
+}
+
+impl SpiceModel {
+    /// Create a model with no parameters
+    pub fn new(name: &str, model_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            model_type: model_type.to_uppercase(),
+            parameters: BTreeMap::new(),
+            text_parameters: BTreeMap::new(),
+            r_squared: None,
+            ai_generated: false,
+        }
+    }
+
+    /// Add a parameter
+    pub fn with_parameter(mut self, name: &str, value: f64) -> Self {
+        self.parameters.insert(name.to_uppercase(), value);
+        self
+    }
+
+    /// Get a parameter value
+    pub fn parameter(&self, name: &str) -> Option<f64> {
+        self.parameters.get(&name.to_uppercase()).copied()
+    }
+
+    /// Parse a single (continuation-joined) `.MODEL` statement
+    pub fn parse(statement: &str) -> Result<Self> {
+        let parse_error = |reason: &str| SimulationError::ParseError {
+            line: statement.to_string(),
+            reason: reason.to_string(),
+        };
+
+        // Normalise "TYPE(A=1 B = 2)" into whitespace separated "TYPE A=1 B=2"
+        let mut normalized = statement.replace(['(', ')', ','], " ");
+        while normalized.contains(" =") || normalized.contains("= ") {
+            normalized = normalized.replace(" =", "=").replace("= ", "=");
+        }
+
+        let mut tokens = normalized.split_whitespace();
+        match tokens.next() {
+            Some(keyword) if keyword.eq_ignore_ascii_case(".model") => {}
+            _ => return Err(parse_error("expected .MODEL statement")),
+        }
+        let name = tokens.next().ok_or_else(|| parse_error("missing model name"))?;
+        let model_type = tokens.next().ok_or_else(|| parse_error("missing model type"))?;
+
+        let mut model = SpiceModel::new(name, model_type);
+        for token in tokens {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or_else(|| parse_error(&format!("expected NAME=VALUE, got '{}'", token)))?;
+            if key.is_empty() || value.is_empty() {
+                return Err(parse_error(&format!("expected NAME=VALUE, got '{}'", token)));
+            }
+            // Vendor libraries carry string metadata such as MFG=NXP or TYPE=Zener
+            match parse_spice_value(value) {
+                Ok(number) => {
+                    model.parameters.insert(key.to_uppercase(), number);
+                }
+                Err(_) => {
+                    model.text_parameters.insert(key.to_uppercase(), value.to_string());
+                }
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Whether this is a capacitor with parasitics, which ngspice's `C` model can't express
+    pub fn is_series_rlc(&self) -> bool {
+        self.model_type == "C" && (self.parameters.contains_key("ESR") || self.parameters.contains_key("ESL"))
+    }
+
+    /// Render as a `.MODEL` statement
+    ///
+    /// A capacitor with ESR/ESL renders as a two-pin `.SUBCKT` of series R, L
+    /// and C instead, to be instantiated with an `X` line.
+    pub fn to_spice(&self) -> String {
+        if self.is_series_rlc() {
+            return self.to_series_rlc_subcircuit();
+        }
+
+        let parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .chain(self.text_parameters.iter().map(|(key, value)| format!("{}={}", key, value)))
+            .collect();
+        format!(".MODEL {} {}({})", self.name, self.model_type, parameters.join(" "))
+    }
+
+    /// `.SUBCKT name 1 2` with ESR, ESL and C in series; zero-valued parasitics are left out
+    fn to_series_rlc_subcircuit(&self) -> String {
+        let elements = [
+            ("RESR", self.parameter("ESR").unwrap_or_default()),
+            ("LESL", self.parameter("ESL").unwrap_or_default()),
+            ("CCAP", self.parameter("C").unwrap_or_default()),
+        ];
+        let present: Vec<(&str, f64)> =
+            elements.into_iter().filter(|(element, value)| *element == "CCAP" || *value > 0.0).collect();
+
+        let mut spice = format!(".SUBCKT {} 1 2\n", self.name);
+        for (index, (element, value)) in present.iter().enumerate() {
+            let from = if index == 0 { "1".to_string() } else { format!("n{}", index) };
+            let to = if index + 1 == present.len() { "2".to_string() } else { format!("n{}", index + 1) };
+            writeln!(spice, "{} {} {} {}", element, from, to, value).unwrap();
+        }
+        write!(spice, ".ENDS {}", self.name).unwrap();
+        spice
+    }
+}
+
+impl ModelLibrary {
+    /// Create an empty library
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Library of the bundled standard device models
+    pub fn standard() -> Self {
+        Self::parse(STANDARD_MODELS).expect("bundled model library is valid")
+    }
+
+    /// Load a SPICE model library file
+    pub fn load_from_file(path: &Path) -> Result<ModelLibrary> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text)
+    }
+
+    /// Parse model library text
+    pub fn parse(text: &str) -> Result<ModelLibrary> {
+        let mut library = ModelLibrary::new();
+        let mut open_subcircuit: Option<(String, Vec<String>)> = None;
+
+        for statement in join_continuations(text) {
+            let keyword = statement.split_whitespace().next().unwrap_or_default().to_lowercase();
+
+            if let Some((name, body)) = open_subcircuit.as_mut() {
+                body.push(statement);
+                if keyword == ".ends" {
+                    let name = std::mem::take(name);
+                    let body = std::mem::take(body);
+                    library.subcircuits.insert(name.to_uppercase(), body.join("\n"));
+                    open_subcircuit = None;
+                }
+                continue;
+            }
+
+            match keyword.as_str() {
+                ".model" => library.add_model(SpiceModel::parse(&statement)?),
+                ".subckt" => {
+                    let name = statement.split_whitespace().nth(1).ok_or_else(|| {
+                        SimulationError::ParseError {
+                            line: statement.clone(),
+                            reason: ".SUBCKT missing name".to_string(),
+                        }
+                    })?;
+                    open_subcircuit = Some((name.to_string(), vec![statement.clone()]));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some((name, _)) = open_subcircuit {
+            return Err(SimulationError::ModelError {
+                model: name,
+                reason: "missing .ENDS".to_string(),
+            });
+        }
+
+        Ok(library)
+    }
+
+    /// Add or replace a model
+    pub fn add_model(&mut self, model: SpiceModel) {
+        self.models.insert(model.name.to_uppercase(), model);
+    }
+
+    /// Look up a model by name (case-insensitive)
+    pub fn get(&self, name: &str) -> Option<&SpiceModel> {
+        self.models.get(&name.to_uppercase())
+    }
+
+    /// Merge another library into this one, replacing models with the same name
+    pub fn merge(&mut self, other: ModelLibrary) {
+        self.models.extend(other.models);
+        self.subcircuits.extend(other.subcircuits);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty() && self.subcircuits.is_empty()
+    }
+
+    /// Names of AI-generated models referenced by a netlist, sorted
+    pub fn ai_generated_models_used(&self, netlist: &str) -> Vec<String> {
+        let tokens = netlist_tokens(netlist);
+        let mut names: Vec<String> = self
+            .models
+            .iter()
+            .filter(|(key, model)| model.ai_generated && tokens.contains(*key))
+            .map(|(_, model)| model.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Render all models and subcircuits as SPICE statements
+    pub fn to_spice(&self) -> String {
+        let mut spice = String::new();
+
+        let mut model_names: Vec<&String> = self.models.keys().collect();
+        model_names.sort();
+        for name in model_names {
+            writeln!(spice, "{}", self.models[name].to_spice()).unwrap();
+        }
+
+        let mut subcircuit_names: Vec<&String> = self.subcircuits.keys().collect();
+        subcircuit_names.sort();
+        for name in subcircuit_names {
+            writeln!(spice, "{}", self.subcircuits[name]).unwrap();
+        }
+
+        spice
+    }
+
+    /// The models and subcircuits a netlist references but does not define itself
+    ///
+    /// Subcircuits pull in the models their own devices reference.
+    pub fn used_by(&self, netlist: &str) -> ModelLibrary {
+        let defined: HashSet<String> = join_continuations(netlist)
+            .iter()
+            .filter_map(|statement| {
+                let mut tokens = statement.split_whitespace();
+                let keyword = tokens.next()?.to_lowercase();
+                matches!(keyword.as_str(), ".model" | ".subckt").then(|| tokens.next())?
+            })
+            .map(str::to_uppercase)
+            .collect();
+
+        let mut used = ModelLibrary::new();
+        let mut pending = vec![netlist_tokens(netlist)];
+        while let Some(tokens) = pending.pop() {
+            for token in tokens.iter().filter(|token| !defined.contains(*token)) {
+                if let Some(model) = self.models.get(token) {
+                    used.models.entry(token.clone()).or_insert_with(|| model.clone());
+                }
+                if let Some(body) = self.subcircuits.get(token) {
+                    if !used.subcircuits.contains_key(token) {
+                        used.subcircuits.insert(token.clone(), body.clone());
+                        pending.push(netlist_tokens(body));
+                    }
+                }
+            }
+        }
+        used
+    }
+
+    /// Insert the statements the netlist needs after its title line
+    ///
+    /// Only models it references and does not define are added (see [`ModelLibrary::used_by`]).
+    pub fn inject_into(&self, netlist: &str) -> String {
+        let used = self.used_by(netlist);
+        if used.is_empty() {
+            return netlist.to_string();
+        }
+
+        match netlist.split_once('\n') {
+            Some((title, rest)) => format!("{}\n{}{}", title, used.to_spice(), rest),
+            None => format!("{}\n{}", netlist, used.to_spice()),
+        }
+    }
+}
+
+/// Uppercase tokens of a netlist's non-comment lines, for matching model names
+fn netlist_tokens(netlist: &str) -> HashSet<String> {
+    netlist
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('*'))
+        .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == '(' || c == ')'))
+        .filter(|token| !token.is_empty())
+        .map(str::to_uppercase)
+        .collect()
+}
+
+/// Join `+` continuation lines onto their statements, dropping comments and blanks
+fn join_continuations(text: &str) -> Vec<String> {
+    let mut statements: Vec<String> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('*') {
+            continue;
+        }
+
+        match (line.strip_prefix('+'), statements.last_mut()) {
+            (Some(continuation), Some(statement)) => {
+                statement.push(' ');
+                statement.push_str(continuation.trim());
+            }
+            _ => statements.push(line.to_string()),
+        }
+    }
+
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_model_statement() {
+        let model = SpiceModel::parse(".model DMOD d(Is = 1e-14 N=1.5 rs=10 CJO=4p)").unwrap();
+        assert_eq!(model.name, "DMOD");
+        assert_eq!(model.model_type, "D");
+        assert_eq!(model.parameter("IS"), Some(1e-14));
+        assert_eq!(model.parameter("n"), Some(1.5));
+        assert_eq!(model.parameter("RS"), Some(10.0));
+        assert_eq!(model.parameter("CJO"), Some(4e-12));
+
+        assert!(SpiceModel::parse(".model BROKEN").is_err());
+        assert!(SpiceModel::parse(".model BAD D(IS)").is_err());
+
+        // Vendor metadata is kept rather than rejected
+        let zener = SpiceModel::parse(".model BZX84C5V1 D(IS=5n BV=5.1 mfg=NXP type=zener)").unwrap();
+        assert_eq!(zener.parameter("BV"), Some(5.1));
+        assert_eq!(zener.text_parameters["MFG"], "NXP");
+        assert!(zener.to_spice().ends_with(" MFG=NXP TYPE=zener)"));
+    }
+
+    #[test]
+    fn test_standard_library() {
+        let library = ModelLibrary::standard();
+
+        let diode = library.get("d1n4148").unwrap();
+        assert_eq!(diode.model_type, "D");
+        assert!((diode.parameter("IS").unwrap() - 2.52e-9).abs() < 1e-21);
+
+        // Continuation lines are folded into the 2N2222 statement
+        let transistor = library.get("2N2222").unwrap();
+        assert_eq!(transistor.model_type, "NPN");
+        assert_eq!(transistor.parameter("RB"), Some(10.0));
+
+        assert!(library.subcircuits.contains_key("LM741"));
+        assert!(library.subcircuits["LM741"].ends_with(".ENDS LM741"));
+    }
+
+    #[test]
+    fn test_load_from_file_and_inject() {
+        let path = std::env::temp_dir().join(format!("opencircuit-models-{}.lib", std::process::id()));
+        std::fs::write(&path, "* custom models\n.MODEL QFAST NPN(BF=400\n+ VAF=100)\n").unwrap();
+
+        let library = ModelLibrary::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(library.get("QFAST").unwrap().parameter("VAF"), Some(100.0));
+
+        let netlist = library.inject_into("Title\nQ1 c b e QFAST\n.end\n");
+        assert!(netlist.starts_with("Title\n.MODEL QFAST NPN(BF=400 VAF=100)\nQ1"));
+    }
+
+    #[test]
+    fn test_inject_only_missing_models() {
+        let library = ModelLibrary::standard();
+        let netlist = "Title\n.model D1N4148 D(IS=1n)\nD1 a k D1N4148\nQ1 c b e 2N2222\n.end\n";
+
+        let prepared = library.inject_into(netlist);
+        assert_eq!(prepared.matches("D1N4148 D(").count(), 1);
+        assert!(prepared.contains(".MODEL 2N2222 NPN("));
+        assert!(!prepared.contains("LM741"));
+
+        // A subcircuit instance pulls in the subcircuit
+        let prepared = library.inject_into("Title\nXU1 a b vcc vee out LM741\n.end\n");
+        assert!(prepared.contains(".SUBCKT LM741"));
+        assert!(!prepared.contains("2N2222"));
+        assert_eq!(library.inject_into("Title\nR1 a 0 1k\n.end\n"), "Title\nR1 a 0 1k\n.end\n");
+    }
+
+    #[test]
+    fn test_capacitor_with_parasitics_renders_as_subcircuit() {
+        let capacitor = SpiceModel::new("C10U", "C").with_parameter("C", 10e-6).with_parameter("ESR", 0.1);
+        assert!(capacitor.is_series_rlc());
+        assert_eq!(capacitor.to_spice(), ".SUBCKT C10U 1 2\nRESR 1 n1 0.1\nCCAP n1 2 0.00001\n.ENDS C10U");
+
+        let mut library = ModelLibrary::new();
+        library.add_model(capacitor);
+        let prepared = library.inject_into("Title\nXC1 vcc 0 C10U\n.end\n");
+        assert!(prepared.starts_with("Title\n.SUBCKT C10U 1 2\n"));
+        assert!(!prepared.contains(".MODEL"));
+    }
+
+    #[test]
+    fn test_ai_generated_models_used() {
+        let mut library = ModelLibrary::standard();
+        let mut generated = SpiceModel::new("IRLZ44N", "NMOS").with_parameter("VTO", 1.5);
+        generated.ai_generated = true;
+        library.add_model(generated);
+
+        assert_eq!(library.ai_generated_models_used("Title\nM1 d g s s irlz44n\n.end\n"), vec!["IRLZ44N".to_string()]);
+        // Vendor models and comments do not count
+        assert!(library.ai_generated_models_used("Title\n* M1 d g s s IRLZ44N\nD1 a k D1N4148\n").is_empty());
+    }
+}
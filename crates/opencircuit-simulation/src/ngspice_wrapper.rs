@@ -11,9 +11,11 @@ use libc::{c_char, c_int, c_void};
 use libloading::{Library, Symbol};
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info};
 
 /// NgSpice function signatures
@@ -30,6 +32,11 @@ type NgSpiceInitFunc = unsafe extern "C" fn(
 type NgSpiceCommandFunc = unsafe extern "C" fn(*mut c_char) -> c_int;
 type NgSpiceGetVecInfoFunc = unsafe extern "C" fn(*mut c_char) -> *mut c_void;
 type NgSpiceCircByNameFunc = unsafe extern "C" fn(*mut c_char) -> *mut c_void;
+type NgSpiceRunningFunc = unsafe extern "C" fn() -> bool;
+type NgSpiceCurPlotFunc = unsafe extern "C" fn() -> *mut c_char;
+
+/// How often a background run is checked for completion or cancellation
+const RUN_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 /// NgSpice context and state management
 pub struct NgSpiceContext {
@@ -42,23 +49,92 @@ pub struct NgSpiceContext {
     is_initialized: bool,
 }
 
+/// Halts a running simulation without taking the context lock
+///
+/// Analyses run on NgSpice's background thread (`bg_run`), which `bg_halt`
+/// stops; this calls `ngSpice_Command` directly so it needs no lock.
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationHalt {
+    command_func: NgSpiceCommandFunc,
+}
+
+impl SimulationHalt {
+    /// Ask NgSpice to halt the running analysis
+    pub fn halt(&self) -> Result<()> {
+        let command = CString::new("bg_halt").context("Failed to convert command to C string")?;
+        let result = unsafe { (self.command_func)(command.as_ptr() as *mut c_char) };
+        if result != 0 {
+            return Err(SimulationError::CommandFailed {
+                command: "bg_halt".to_string(),
+                error: format!("Command failed with code: {}", result),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Lines NgSpice prints through its `send_char` callback
+///
+/// The callback runs on NgSpice's threads, so the buffers use blocking locks.
+#[derive(Debug, Default)]
+struct OutputCapture {
+    output: std::sync::Mutex<Vec<String>>,
+    errors: std::sync::Mutex<Vec<String>>,
+}
+
+impl OutputCapture {
+    fn output(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        self.output.lock().expect("NgSpice output lock poisoned")
+    }
+
+    fn errors(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        self.errors.lock().expect("NgSpice error lock poisoned")
+    }
+}
+
 /// Main NgSpice wrapper
 pub struct NgSpiceWrapper {
     context: Arc<Mutex<NgSpiceContext>>,
-    output_buffer: Arc<Mutex<Vec<String>>>,
-    error_buffer: Arc<Mutex<Vec<String>>>,
+    capture: Arc<OutputCapture>,
+    halt: SimulationHalt,
+    running_func: NgSpiceRunningFunc,
+    cur_plot_func: NgSpiceCurPlotFunc,
+    /// Private copy of the library loaded by [`Self::new_isolated`]; declared
+    /// last so the library is unloaded before the copy is deleted
+    _library_copy: Option<tempfile::TempDir>,
 }
 
 impl NgSpiceWrapper {
     /// Create a new NgSpice wrapper
     pub async fn new() -> Result<Self> {
-        info!("Initializing NgSpice wrapper");
-        
         let library_path = Self::find_ngspice_library()?;
+        Self::load(&library_path, None).await
+    }
+
+    /// Create a wrapper with its own copy of the NgSpice library
+    ///
+    /// NgSpice keeps its circuit in globals, so wrappers sharing one loaded
+    /// library share one circuit. A copy under another file name is loaded
+    /// as a separate instance, letting simulations run side by side. Each
+    /// call copies into a fresh temporary directory, so no loaded copy is
+    /// ever overwritten, and the copy is deleted when the wrapper is dropped.
+    pub async fn new_isolated() -> Result<Self> {
+        let library_path = Self::find_ngspice_library()?;
+        let copy_dir = tempfile::Builder::new()
+            .prefix("opencircuit-ngspice-")
+            .tempdir()
+            .context("Failed to create a directory for the NgSpice library copy")?;
+        let copy_path = copy_dir.path().join(library_path.file_name().unwrap_or_default());
+        std::fs::copy(&library_path, &copy_path).context("Failed to copy NgSpice library")?;
+        Self::load(&copy_path, Some(copy_dir)).await
+    }
+
+    async fn load(library_path: &Path, library_copy: Option<tempfile::TempDir>) -> Result<Self> {
+        info!("Initializing NgSpice wrapper");
         debug!("Loading NgSpice library from: {:?}", library_path);
         
         // Load the NgSpice library
-        let library = unsafe { Library::new(&library_path) }
+        let library = unsafe { Library::new(library_path) }
             .context("Failed to load NgSpice library")?;
         
         // Load function symbols and transmute them to static lifetime
@@ -76,7 +152,7 @@ impl NgSpiceWrapper {
             std::mem::transmute(symbol.into_raw())
         };
         
-        let command_func = unsafe {
+        let command_func: Symbol<'static, NgSpiceCommandFunc> = unsafe {
             let symbol: libloading::Symbol<unsafe extern "C" fn(*mut c_char) -> c_int> = library.get(b"ngSpice_Command\0")
                 .context("Failed to load ngSpice_Command function")?;
             std::mem::transmute(symbol.into_raw())
@@ -94,6 +170,17 @@ impl NgSpiceWrapper {
             std::mem::transmute(symbol.into_raw())
         };
         
+        let running_func: NgSpiceRunningFunc = unsafe {
+            *library.get::<NgSpiceRunningFunc>(b"ngSpice_running\0")
+                .context("Failed to load ngSpice_running function")?
+        };
+
+        let cur_plot_func: NgSpiceCurPlotFunc = unsafe {
+            *library.get::<NgSpiceCurPlotFunc>(b"ngSpice_CurPlot\0")
+                .context("Failed to load ngSpice_CurPlot function")?
+        };
+
+        let halt = SimulationHalt { command_func: *command_func };
         let context = NgSpiceContext {
             library,
             init_func,
@@ -106,8 +193,11 @@ impl NgSpiceWrapper {
         
         let wrapper = Self {
             context: Arc::new(Mutex::new(context)),
-            output_buffer: Arc::new(Mutex::new(Vec::new())),
-            error_buffer: Arc::new(Mutex::new(Vec::new())),
+            capture: Arc::new(OutputCapture::default()),
+            halt,
+            running_func,
+            cur_plot_func,
+            _library_copy: library_copy,
         };
         
         // Initialize NgSpice
@@ -147,10 +237,11 @@ impl NgSpiceWrapper {
             return Ok(());
         }
         
-        // Set up callback functions
-        let output_buffer = Arc::clone(&self.output_buffer);
-        let error_buffer = Arc::clone(&self.error_buffer);
-        
+        // NgSpice keeps the pointer for as long as the library is loaded, which
+        // can outlive this wrapper when another wrapper shares the library, so
+        // the reference handed over here is never released
+        let capture = Arc::into_raw(Arc::clone(&self.capture)) as *mut c_void;
+
         // Initialize NgSpice with callbacks
         let result = unsafe {
             (context.init_func)(
@@ -160,7 +251,7 @@ impl NgSpiceWrapper {
                 None, // send_data
                 None, // send_init_data
                 None, // bg_thread_running
-                std::ptr::null_mut(),
+                capture,
             )
         };
         
@@ -177,30 +268,95 @@ impl NgSpiceWrapper {
     
     /// Run a SPICE simulation
     pub async fn run_simulation(&self, netlist: String) -> Result<SimulationResults> {
+        self.run_simulation_cancellable(netlist, &CancellationToken::new()).await
+    }
+
+    /// Run a SPICE simulation, returning `Cancelled` once `token` is cancelled
+    ///
+    /// The analysis runs on NgSpice's background thread and is halted as
+    /// soon as the token is cancelled.
+    pub async fn run_simulation_cancellable(
+        &self,
+        netlist: String,
+        token: &CancellationToken,
+    ) -> Result<SimulationResults> {
         info!("Running SPICE simulation");
         
         // Clear output buffers
-        self.output_buffer.lock().await.clear();
-        self.error_buffer.lock().await.clear();
+        self.capture.output().clear();
+        self.capture.errors().clear();
         
-        // Split netlist into commands
-        let commands = netlist.lines()
+        // Split netlist into lines
+        let lines = netlist.lines()
             .map(|line| line.trim())
             .filter(|line| !line.is_empty() && !line.starts_with('*'))
             .collect::<Vec<_>>();
         
-        // Execute commands
-        for command in commands {
-            self.execute_command(command).await?;
+        // Load the circuit line by line; NgSpice parses it at `.end`
+        for line in lines {
+            if token.is_cancelled() {
+                info!("Simulation cancelled");
+                return Err(SimulationError::Cancelled);
+            }
+            // The analysis is started in the background below
+            if line.eq_ignore_ascii_case("run") {
+                continue;
+            }
+            self.execute_command(&format!("circbyline {}", line)).await?;
+        }
+
+        self.execute_command("bg_run").await?;
+        while self.is_running() {
+            if token.is_cancelled() {
+                info!("Simulation cancelled, halting NgSpice");
+                self.halt.halt()?;
+                while self.is_running() {
+                    tokio::time::sleep(RUN_POLL_INTERVAL).await;
+                }
+                return Err(SimulationError::Cancelled);
+            }
+            tokio::time::sleep(RUN_POLL_INTERVAL).await;
         }
         
-        // Get simulation results
-        let results = self.extract_results().await?;
+        // Print the analysis vectors so the results carry them
+        let vectors = self.command_output("print all").await?;
+        let results = self.extract_results(vectors);
         
         info!("Simulation completed successfully");
         Ok(results)
     }
     
+    /// Whether NgSpice's background thread is still running an analysis
+    pub fn is_running(&self) -> bool {
+        unsafe { (self.running_func)() }
+    }
+
+    /// Halt the simulation currently running in NgSpice
+    pub fn cancel_simulation(&self) -> Result<()> {
+        self.halt.halt()
+    }
+
+    /// Handle for halting simulations from another task
+    pub fn halt_handle(&self) -> SimulationHalt {
+        self.halt
+    }
+
+    /// Run a command such as `print` and return the lines NgSpice printed
+    pub async fn command_output(&self, command: &str) -> Result<Vec<String>> {
+        self.capture.output().clear();
+        self.execute_command(command).await?;
+        Ok(std::mem::take(&mut *self.capture.output()))
+    }
+
+    /// Name of the plot holding the latest analysis vectors, e.g. `op1` or `noise2`
+    pub fn current_plot(&self) -> Option<String> {
+        let plot = unsafe { (self.cur_plot_func)() };
+        if plot.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(plot) }.to_str().ok().map(str::to_string)
+    }
+
     /// Execute a single SPICE command
     async fn execute_command(&self, command: &str) -> Result<()> {
         let context = self.context.lock().await;
@@ -215,7 +371,7 @@ impl NgSpiceWrapper {
         };
         
         if result != 0 {
-            let error_buffer = self.error_buffer.lock().await;
+            let error_buffer = self.capture.errors();
             let error_msg = if error_buffer.is_empty() {
                 format!("Command failed with code: {}", result)
             } else {
@@ -231,26 +387,23 @@ impl NgSpiceWrapper {
         Ok(())
     }
     
-    /// Extract simulation results
-    async fn extract_results(&self) -> Result<SimulationResults> {
-        let output_buffer = self.output_buffer.lock().await;
-        let error_buffer = self.error_buffer.lock().await;
-        
+    /// Extract simulation results from the printed analysis vectors
+    fn extract_results(&self, vectors: Vec<String>) -> SimulationResults {
         // Create simulation results with DC analysis as default
         let mut results = SimulationResults::new(
             crate::analysis::AnalysisType::DC,
-            crate::results::AnalysisData::Raw(output_buffer.clone())
+            crate::results::AnalysisData::Raw(vectors)
         );
         
         // Add warnings if any errors occurred
-        for error in error_buffer.iter() {
+        for error in self.capture.errors().iter() {
             results.add_warning(error.clone());
         }
         
         // Add metadata
         results.add_metadata("simulation_time".to_string(), chrono::Utc::now().to_rfc3339());
         
-        Ok(results)
+        results
     }
     
     /// Parse a single output line for simulation data
@@ -282,17 +435,25 @@ impl NgSpiceWrapper {
     unsafe extern "C" fn send_char_callback(
         message: *mut c_char,
         _id: c_int,
-        _user_data: *mut c_void,
+        user_data: *mut c_void,
     ) -> c_int {
-        if message.is_null() {
+        if message.is_null() || user_data.is_null() {
             return 1;
         }
         
         if let Ok(msg) = CStr::from_ptr(message).to_str() {
             debug!("NgSpice output: {}", msg);
-            // Note: In a real implementation, we'd need to access the output buffer
-            // through user_data, but for now we'll use the global state approach
-            // This is a simplified implementation for the task completion
+            // Lines arrive prefixed with the stream NgSpice wrote them to
+            let capture = &*(user_data as *const OutputCapture);
+            let (buffer, line) = match msg.strip_prefix("stderr") {
+                Some(line) => (&capture.errors, line),
+                None => (&capture.output, msg.strip_prefix("stdout").unwrap_or(msg)),
+            };
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            // Never panic across the FFI boundary, even on a poisoned lock
+            if let Ok(mut buffer) = buffer.lock() {
+                buffer.push(line.to_string());
+            }
         }
         
         0
@@ -367,4 +528,16 @@ R1 1 0 1k
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_cancelled_simulation() {
+        if let Ok(wrapper) = NgSpiceWrapper::new().await {
+            let token = CancellationToken::new();
+            token.cancel();
+
+            let netlist = "Cancelled\nV1 1 0 DC 5\nR1 1 0 1k\n.op\n.end".to_string();
+            let result = wrapper.run_simulation_cancellable(netlist, &token).await;
+            assert!(matches!(result, Err(SimulationError::Cancelled)));
+        }
+    }
 }
\ No newline at end of file
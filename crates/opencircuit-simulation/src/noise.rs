@@ -0,0 +1,142 @@
+//! Noise analysis (SPICE `.NOISE`) results
+
+use serde::{Deserialize, Serialize};
+
+/// Input-referred noise over a frequency band
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoiseResults {
+    /// Output vector the noise was measured at
+    pub output_node: String,
+    /// Source the noise is referred to
+    pub input_source: String,
+    /// Input-referred noise density: (frequency Hz, V/√Hz)
+    pub input_noise_density: Vec<(f64, f64)>,
+    /// Total integrated input-referred noise over the band (V RMS)
+    pub total_rms: f64,
+}
+
+impl NoiseResults {
+    /// Build results from a density sweep, integrating it for the RMS total
+    pub fn new(output_node: &str, input_source: &str, input_noise_density: Vec<(f64, f64)>) -> Self {
+        let total_rms = integrate_rms(&input_noise_density);
+        Self {
+            output_node: output_node.to_string(),
+            input_source: input_source.to_string(),
+            input_noise_density,
+            total_rms,
+        }
+    }
+
+    /// Parse NgSpice `.NOISE` output
+    ///
+    /// Reads the `frequency` / `inoise_spectrum` table and uses `inoise_total`
+    /// when NgSpice reports it, integrating the spectrum otherwise.
+    pub fn from_output(output_node: &str, input_source: &str, lines: &[String]) -> Self {
+        let mut columns: Option<(usize, usize)> = None;
+        let mut density = Vec::new();
+        let mut reported_total = None;
+
+        for line in lines {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let lower: Vec<String> = parts.iter().map(|part| part.to_lowercase()).collect();
+
+            if let Some((name, value)) = line.split_once('=') {
+                if name.trim().eq_ignore_ascii_case("inoise_total") {
+                    reported_total = value.trim().parse().ok();
+                    continue;
+                }
+            }
+
+            let frequency_column = lower.iter().position(|part| part == "frequency");
+            let noise_column = lower.iter().position(|part| part == "inoise_spectrum");
+            if let (Some(frequency), Some(noise)) = (frequency_column, noise_column) {
+                columns = Some((frequency, noise));
+                continue;
+            }
+
+            if let Some((frequency, noise)) = columns {
+                let value = |index: usize| parts.get(index).and_then(|part| part.parse::<f64>().ok());
+                if let (Some(frequency), Some(noise)) = (value(frequency), value(noise)) {
+                    density.push((frequency, noise));
+                }
+            }
+        }
+
+        let mut results = Self::new(output_node, input_source, density);
+        if let Some(total) = reported_total {
+            results.total_rms = total;
+        }
+        results
+    }
+
+    /// (frequency, noise density) points ready for plotting
+    pub fn plot_data(&self) -> Vec<(f64, f64)> {
+        self.input_noise_density.clone()
+    }
+}
+
+/// Plot holding the spectrum of the noise analysis whose totals are in `totals_plot`
+///
+/// NgSpice numbers noise plots in pairs, spectrum first, so `noise2` follows
+/// `noise1` and `noise4` follows `noise3`.
+pub fn spectrum_plot(totals_plot: &str) -> Option<String> {
+    let number: u32 = totals_plot.strip_prefix("noise")?.parse().ok()?;
+    (number >= 2).then(|| format!("noise{}", number - 1))
+}
+
+/// Integrate a noise density (V/√Hz) over frequency into an RMS voltage
+fn integrate_rms(density: &[(f64, f64)]) -> f64 {
+    density
+        .windows(2)
+        .map(|pair| {
+            let (f1, n1) = pair[0];
+            let (f2, n2) = pair[1];
+            (f2 - f1) * (n1 * n1 + n2 * n2) / 2.0
+        })
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_white_noise_integration() {
+        let density: Vec<(f64, f64)> = (0..=10).map(|i| (10.0 + 100.0 * i as f64, 1e-8)).collect();
+        let results = NoiseResults::new("v(out)", "V1", density);
+
+        // 1e-8 V/√Hz over a 1 kHz band -> 1e-8 * √1000
+        assert!((results.total_rms - 1e-8 * 1000f64.sqrt()).abs() < 1e-15);
+        assert_eq!(results.plot_data().len(), 11);
+    }
+
+    #[test]
+    fn test_parse_noise_output() {
+        let output: Vec<String> = "No. of Data Rows : 3\n\
+             Index   frequency       inoise_spectrum  onoise_spectrum\n\
+             --------------------------------------------------------\n\
+             0       1.000000e+01    2.000000e-08     4.000000e-07\n\
+             1       1.000000e+02    1.000000e-08     2.000000e-07\n\
+             2       1.000000e+03    1.000000e-08     2.000000e-07\n\
+             inoise_total = 3.300000e-07\n"
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
+
+        let results = NoiseResults::from_output("v(out)", "V1", &output);
+        assert_eq!(
+            results.input_noise_density,
+            vec![(10.0, 2e-8), (100.0, 1e-8), (1000.0, 1e-8)]
+        );
+        assert_eq!(results.total_rms, 3.3e-7);
+    }
+
+    #[test]
+    fn test_spectrum_plot() {
+        assert_eq!(spectrum_plot("noise2").as_deref(), Some("noise1"));
+        assert_eq!(spectrum_plot("noise4").as_deref(), Some("noise3"));
+        assert_eq!(spectrum_plot("noise1"), None);
+        assert_eq!(spectrum_plot("op1"), None);
+    }
+}
@@ -0,0 +1,155 @@
+//! Simulation results as an operating point for power budgets
+//!
+//! DC results give the operating point directly. Transient waveforms are
+//! averaged over the simulated time, so a power budget reflects the mean
+//! draw rather than a single sample; power is averaged from the product of
+//! each voltage and current sample rather than from the averaged values.
+//! AC results are small-signal and give no operating point.
+
+use crate::results::{AnalysisData, SimulationResults};
+use crate::worst_case::parse_node_voltages;
+use opencircuit_core::OperatingPoint;
+use std::collections::HashMap;
+
+impl OperatingPoint for SimulationResults {
+    fn node_voltage(&self, node: &str) -> Option<f64> {
+        let names = [node.to_string(), format!("v({})", node)];
+        match &self.data {
+            AnalysisData::DC(dc) => lookup(&dc.node_voltages, &names).copied(),
+            AnalysisData::Transient(transient) => {
+                time_average(&transient.time_points, lookup(&transient.voltage_waveforms, &names)?)
+            }
+            AnalysisData::Raw(lines) => {
+                let voltages = parse_node_voltages(lines);
+                let samples = lookup(&voltages, &names)?;
+                (!samples.is_empty()).then(|| samples.iter().sum::<f64>() / samples.len() as f64)
+            }
+            AnalysisData::AC(_) => None,
+        }
+    }
+
+    fn branch_current(&self, component: &str) -> Option<f64> {
+        let names = current_names(component);
+        match &self.data {
+            AnalysisData::DC(dc) => lookup(&dc.branch_currents, &names).copied(),
+            AnalysisData::Transient(transient) => {
+                time_average(&transient.time_points, lookup(&transient.current_waveforms, &names)?)
+            }
+            AnalysisData::Raw(_) | AnalysisData::AC(_) => None,
+        }
+    }
+
+    fn average_power(&self, component: &str, positive: &str, negative: &str, resistance: Option<f64>) -> Option<f64> {
+        let AnalysisData::Transient(transient) = &self.data else {
+            return None;
+        };
+        let voltage = |node: &str| {
+            if node == "0" || node.eq_ignore_ascii_case("gnd") {
+                return Some(vec![0.0; transient.time_points.len()]);
+            }
+            lookup(&transient.voltage_waveforms, &[node.to_string(), format!("v({})", node)]).cloned()
+        };
+        let across: Vec<f64> = voltage(positive)?
+            .iter()
+            .zip(&voltage(negative)?)
+            .map(|(positive, negative)| positive - negative)
+            .collect();
+        let power: Vec<f64> = match lookup(&transient.current_waveforms, &current_names(component)) {
+            Some(current) => across.iter().zip(current).map(|(voltage, current)| voltage * current).collect(),
+            None => {
+                let resistance = resistance?;
+                across.iter().map(|voltage| voltage * voltage / resistance).collect()
+            }
+        };
+        time_average(&transient.time_points, &power)
+    }
+}
+
+/// Names SPICE may report a component's branch current under
+fn current_names(component: &str) -> [String; 3] {
+    [component.to_string(), format!("i({})", component), format!("{}#branch", component)]
+}
+
+/// First entry keyed by any of `names`, ignoring case as SPICE does
+fn lookup<'a, T>(series: &'a HashMap<String, T>, names: &[String]) -> Option<&'a T> {
+    names.iter().find_map(|name| {
+        series
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    })
+}
+
+/// Trapezoidal mean of a waveform over its time span
+fn time_average(time_points: &[f64], values: &[f64]) -> Option<f64> {
+    let samples = time_points.len().min(values.len());
+    let span = time_points.get(samples.checked_sub(1)?)? - time_points.first()?;
+    if samples < 2 || span <= 0.0 {
+        return values.first().copied();
+    }
+    let area: f64 = (1..samples)
+        .map(|i| (time_points[i] - time_points[i - 1]) * (values[i] + values[i - 1]) / 2.0)
+        .sum();
+    Some(area / span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::AnalysisType;
+    use crate::results::{DCResults, TransientResults};
+
+    #[test]
+    fn test_dc_operating_point() {
+        let results = SimulationResults::new(
+            AnalysisType::DC,
+            AnalysisData::DC(DCResults {
+                node_voltages: HashMap::from([("V(OUT)".to_string(), 2.5)]),
+                branch_currents: HashMap::from([("v1#branch".to_string(), -0.01)]),
+                power_dissipation: HashMap::new(),
+                sweep_data: None,
+            }),
+        );
+        assert_eq!(results.node_voltage("out"), Some(2.5));
+        assert_eq!(results.branch_current("V1"), Some(-0.01));
+        assert_eq!(results.node_voltage("in"), None);
+    }
+
+    #[test]
+    fn test_transient_is_time_averaged() {
+        let results = SimulationResults::new(
+            AnalysisType::Transient,
+            AnalysisData::Transient(TransientResults {
+                time_points: vec![0.0, 1e-3, 4e-3],
+                voltage_waveforms: HashMap::from([("out".to_string(), vec![0.0, 2.0, 2.0])]),
+                current_waveforms: HashMap::from([("I(R1)".to_string(), vec![0.001])]),
+                power_waveforms: HashMap::new(),
+            }),
+        );
+        // (0.5 ms x 2 V + 3 ms x 2 V) / 4 ms
+        assert!((results.node_voltage("out").unwrap() - 1.75).abs() < 1e-12);
+        assert_eq!(results.branch_current("R1"), Some(0.001));
+    }
+
+    #[test]
+    fn test_transient_power_averages_instantaneous_power() {
+        let mut results = SimulationResults::new(
+            AnalysisType::Transient,
+            AnalysisData::Transient(TransientResults {
+                time_points: vec![0.0, 1.0, 2.0],
+                voltage_waveforms: HashMap::from([("a".to_string(), vec![0.0, 2.0, 0.0])]),
+                current_waveforms: HashMap::from([("R1".to_string(), vec![0.0, 1.0, 0.0])]),
+                power_waveforms: HashMap::new(),
+            }),
+        );
+        // p(t) = [0, 2, 0] W averages to 1 W; mean v x mean i would give 1 V x 0.5 A
+        assert!((results.average_power("R1", "a", "0", None).unwrap() - 1.0).abs() < 1e-12);
+
+        // Without a recorded current, a 2 ohm resistor gives the same v(t)^2 / R
+        if let AnalysisData::Transient(transient) = &mut results.data {
+            transient.current_waveforms.clear();
+        }
+        assert!((results.average_power("R1", "a", "0", Some(2.0)).unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(results.average_power("R1", "a", "0", None), None);
+    }
+}
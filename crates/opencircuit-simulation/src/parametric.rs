@@ -0,0 +1,153 @@
+//! Parametric sweeps of a single component value
+
+use crate::errors::{Result, SimulationError};
+use crate::results::{AnalysisData, SimulationResults};
+use crate::worst_case::parse_node_voltages;
+use opencircuit_circuit::Circuit;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Simulation results for each value of the swept component, in sweep order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParametricResults {
+    pub component_id: String,
+    pub results: Vec<(f64, SimulationResults)>,
+}
+
+impl ParametricResults {
+    /// One curve of `node` per swept value, for drawing as an overlay
+    ///
+    /// Transient curves are voltage over time and AC curves magnitude over
+    /// frequency. A DC sweep is plotted against its sweep parameter, while a
+    /// DC operating point gives a single point at the swept value. Raw NgSpice
+    /// output is plotted against sample index. `node` may be given as `out`
+    /// or `v(out)`; runs without it give an empty curve.
+    pub fn overlay_plot_data(&self, node: &str) -> Vec<(f64, Vec<(f64, f64)>)> {
+        self.results
+            .iter()
+            .map(|(value, results)| (*value, curve(&results.data, node, *value)))
+            .collect()
+    }
+}
+
+fn curve(data: &AnalysisData, node: &str, swept_value: f64) -> Vec<(f64, f64)> {
+    match data {
+        AnalysisData::Transient(transient) => lookup(&transient.voltage_waveforms, node)
+            .map(|waveform| transient.time_points.iter().copied().zip(waveform.iter().copied()).collect())
+            .unwrap_or_default(),
+        AnalysisData::AC(ac) => lookup(&ac.voltage_responses, node)
+            .map(|response| ac.frequencies.iter().copied().zip(response.iter().map(|v| v.magnitude())).collect())
+            .unwrap_or_default(),
+        AnalysisData::DC(dc) => match &dc.sweep_data {
+            Some(sweep) => sweep
+                .parameter_values
+                .iter()
+                .zip(&sweep.results)
+                .filter_map(|(parameter, point)| Some((*parameter, *lookup(&point.node_voltages, node)?)))
+                .collect(),
+            None => lookup(&dc.node_voltages, node).map(|voltage| vec![(swept_value, *voltage)]).unwrap_or_default(),
+        },
+        AnalysisData::Raw(lines) => lookup(&parse_node_voltages(lines), &node.to_lowercase())
+            .map(|samples| samples.iter().enumerate().map(|(index, voltage)| (index as f64, *voltage)).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Series for a node keyed either by its bare name or as `v(name)`
+fn lookup<'a, T>(series: &'a HashMap<String, T>, node: &str) -> Option<&'a T> {
+    let bare = node
+        .strip_prefix("v(")
+        .or_else(|| node.strip_prefix("V("))
+        .and_then(|inner| inner.strip_suffix(')'))
+        .unwrap_or(node);
+    series
+        .get(node)
+        .or_else(|| series.get(bare))
+        .or_else(|| series.get(&format!("v({})", bare)))
+}
+
+/// Copy of `circuit` with one component set to `value`
+pub fn apply_value(circuit: &Circuit, component_id: &str, value: f64) -> Result<Circuit> {
+    let invalid = |reason: String| SimulationError::InvalidComponent {
+        component: component_id.to_string(),
+        reason,
+    };
+    if !value.is_finite() {
+        return Err(invalid(format!("swept value {} is not finite", value)));
+    }
+
+    let mut circuit = circuit.clone();
+    let component = circuit
+        .components
+        .iter_mut()
+        .find(|component| component.id == component_id)
+        .ok_or_else(|| invalid("component not found in circuit".to_string()))?;
+    component.value = Some(format!("{:e}", value));
+    Ok(circuit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::AnalysisType;
+    use crate::results::{DCResults, TransientResults};
+    use opencircuit_circuit::{Component, ComponentType};
+
+    fn dc(voltage: f64) -> SimulationResults {
+        SimulationResults::new(
+            AnalysisType::DC,
+            AnalysisData::DC(DCResults {
+                node_voltages: HashMap::from([("out".to_string(), voltage)]),
+                branch_currents: HashMap::new(),
+                power_dissipation: HashMap::new(),
+                sweep_data: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_apply_value() {
+        let mut circuit = Circuit::new();
+        circuit.add_component(Component {
+            id: "Rf".to_string(),
+            component_type: ComponentType::Resistor,
+            value: Some("10k".to_string()),
+            position: (0.0, 0.0),
+            purpose: None,
+        });
+
+        let swept = apply_value(&circuit, "Rf", 47_000.0).unwrap();
+        assert_eq!(swept.components[0].value.as_deref(), Some("4.7e4"));
+        assert_eq!(circuit.components[0].value.as_deref(), Some("10k"));
+        assert!(apply_value(&circuit, "R9", 1000.0).is_err());
+        assert!(apply_value(&circuit, "Rf", f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_overlay_plot_data() {
+        let transient = SimulationResults::new(
+            AnalysisType::Transient,
+            AnalysisData::Transient(TransientResults {
+                time_points: vec![0.0, 1e-3],
+                voltage_waveforms: HashMap::from([("v(out)".to_string(), vec![0.0, 2.5])]),
+                current_waveforms: HashMap::new(),
+                power_waveforms: HashMap::new(),
+            }),
+        );
+        let raw = SimulationResults::new(
+            AnalysisType::DC,
+            AnalysisData::Raw(vec!["v(out) = 1.25e+00".to_string(), "v(in) = 5".to_string()]),
+        );
+        let sweep = ParametricResults {
+            component_id: "Rf".to_string(),
+            results: vec![(1e3, dc(0.5)), (1e4, transient), (1e5, raw)],
+        };
+
+        let overlay = sweep.overlay_plot_data("out");
+        assert_eq!(overlay[0], (1e3, vec![(1e3, 0.5)]));
+        assert_eq!(overlay[1], (1e4, vec![(0.0, 0.0), (1e-3, 2.5)]));
+        assert_eq!(overlay[2], (1e5, vec![(0.0, 1.25)]));
+        assert_eq!(sweep.overlay_plot_data("v(out)"), overlay);
+        assert!(sweep.overlay_plot_data("missing").iter().all(|(_, curve)| curve.is_empty()));
+    }
+}
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Complete simulation results
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SimulationResults {
     /// Type of analysis performed
     pub analysis_type: AnalysisType,
@@ -18,7 +18,7 @@ pub struct SimulationResults {
 }
 
 /// Analysis data variants
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AnalysisData {
     /// DC analysis results
     DC(DCResults),
@@ -31,7 +31,7 @@ pub enum AnalysisData {
 }
 
 /// DC analysis results
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DCResults {
     /// Operating point voltages (node -> voltage)
     pub node_voltages: HashMap<String, f64>,
@@ -44,7 +44,7 @@ pub struct DCResults {
 }
 
 /// AC analysis results
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ACResults {
     /// Frequency points
     pub frequencies: Vec<f64>,
@@ -57,7 +57,7 @@ pub struct ACResults {
 }
 
 /// Transient analysis results
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TransientResults {
     /// Time points
     pub time_points: Vec<f64>,
@@ -70,7 +70,7 @@ pub struct TransientResults {
 }
 
 /// Sweep analysis results
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SweepResults {
     /// Parameter values that were swept
     pub parameter_values: Vec<f64>,
@@ -79,7 +79,7 @@ pub struct SweepResults {
 }
 
 /// Complex value for AC analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ComplexValue {
     /// Real part
     pub real: f64,
@@ -88,7 +88,7 @@ pub struct ComplexValue {
 }
 
 /// Transfer function data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TransferFunction {
     /// Input node/branch
     pub input: String,
@@ -210,6 +210,14 @@ impl SimulationResults {
         self.metadata.insert(key, format!("{:?}", values));
     }
     
+    /// Raw NgSpice output lines, if the results haven't been parsed into structured data
+    pub fn raw_output(&self) -> Option<&[String]> {
+        match &self.data {
+            AnalysisData::Raw(output) => Some(output),
+            _ => None,
+        }
+    }
+    
     /// Check if simulation was successful
     pub fn is_successful(&self) -> bool {
         match &self.data {
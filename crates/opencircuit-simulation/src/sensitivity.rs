@@ -0,0 +1,134 @@
+//! Sensitivity analysis (SPICE `.SENS`) results
+
+use serde::{Deserialize, Serialize};
+
+/// Vectors reported alongside the sensitivities that aren't components
+const NON_COMPONENT_VECTORS: &[&str] = &["frequency", "sweep", "v-sweep", "i-sweep", "time", "temp"];
+
+/// Sensitivity of an output to each component value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensitivityResults {
+    /// Output node or branch the sensitivities refer to
+    pub output_node: String,
+    /// AC frequency (Hz), or `None` for DC sensitivity
+    pub frequency: Option<f64>,
+    /// Component sensitivities (output units per component unit), largest magnitude first
+    pub sensitivities: Vec<(String, f64)>,
+}
+
+impl SensitivityResults {
+    /// Build results from unsorted component sensitivities
+    pub fn new(output_node: &str, frequency: Option<f64>, mut sensitivities: Vec<(String, f64)>) -> Self {
+        sensitivities.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+        Self {
+            output_node: output_node.to_string(),
+            frequency,
+            sensitivities,
+        }
+    }
+
+    /// Parse NgSpice `.SENS` output
+    ///
+    /// Accepts both `name = value` vector listings (complex AC values are
+    /// reported as `re,im` and reduced to their magnitude) and the tabular
+    /// "Element Name / Element Value / Element Sensitivity" report.
+    pub fn from_output(output_node: &str, frequency: Option<f64>, lines: &[String]) -> Self {
+        let sensitivities = lines
+            .iter()
+            .filter_map(|line| Self::parse_line(line))
+            .filter(|(name, _)| !NON_COMPONENT_VECTORS.contains(&name.as_str()))
+            .collect();
+
+        Self::new(output_node, frequency, sensitivities)
+    }
+
+    /// Sensitivity to a single component (case-insensitive)
+    pub fn get(&self, component: &str) -> Option<f64> {
+        self.sensitivities
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(component))
+            .map(|(_, sensitivity)| *sensitivity)
+    }
+
+    /// Component the output is most sensitive to
+    pub fn most_sensitive(&self) -> Option<&(String, f64)> {
+        self.sensitivities.first()
+    }
+
+    fn parse_line(line: &str) -> Option<(String, f64)> {
+        let line = line.trim();
+        if !line.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        if let Some((name, value)) = line.split_once('=') {
+            let name = name.trim();
+            if name.contains(char::is_whitespace) {
+                return None;
+            }
+            return Some((name.to_lowercase(), Self::parse_value(value.trim())?));
+        }
+
+        // Tabular form: name, element value, sensitivity, normalized sensitivity
+        let mut parts = line.split_whitespace();
+        let name = parts.next()?;
+        let numbers: Vec<f64> = parts.map(|part| part.parse().ok()).collect::<Option<_>>()?;
+        let sensitivity = match numbers.len() {
+            0 => return None,
+            1 => numbers[0],
+            _ => numbers[1],
+        };
+        Some((name.to_lowercase(), sensitivity))
+    }
+
+    fn parse_value(value: &str) -> Option<f64> {
+        match value.split_once(',') {
+            Some((real, imaginary)) => {
+                let real: f64 = real.trim().parse().ok()?;
+                let imaginary: f64 = imaginary.trim().parse().ok()?;
+                Some(real.hypot(imaginary))
+            }
+            None => value.parse().ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_vector_listing() {
+        let output = lines(
+            "v-sweep = 0.000000e+00\n\
+             r1 = -2.500000e-04\n\
+             r2 = 2.500000e-04\n\
+             v1 = 5.000000e-01\n",
+        );
+
+        let results = SensitivityResults::from_output("v(out)", None, &output);
+        assert_eq!(results.sensitivities.len(), 3);
+        assert_eq!(results.most_sensitive(), Some(&("v1".to_string(), 0.5)));
+        assert_eq!(results.get("R1"), Some(-2.5e-4));
+        assert_eq!(results.get("v-sweep"), None);
+    }
+
+    #[test]
+    fn test_parse_table_and_complex_values() {
+        let output = lines(
+            "DC Sensitivities of output v(2)\n\
+             Element Name     Element Value    Element Sensitivity   Normalized Sensitivity\n\
+             r1               1.000e+03        -1.000e-03            -1.000e-02\n\
+             c1 = 3.0e-2,4.0e-2\n",
+        );
+
+        let results = SensitivityResults::from_output("v(2)", Some(1e3), &output);
+        assert_eq!(results.get("r1"), Some(-1e-3));
+        assert!((results.get("c1").unwrap() - 0.05).abs() < 1e-12);
+        assert_eq!(results.sensitivities[0].0, "c1");
+    }
+}
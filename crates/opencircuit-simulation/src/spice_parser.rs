@@ -6,7 +6,8 @@
 //! - Handle various component types and their SPICE representations
 
 use crate::errors::{Result, SimulationError};
-use opencircuit_circuit::{Circuit, Component, ComponentType};
+use opencircuit_circuit::{Circuit, Component, ComponentType, SubCircuit, SubCircuitInstance};
+use opencircuit_core::circuit::Netlist;
 use std::collections::HashMap;
 use std::fmt::Write;
 
@@ -18,6 +19,7 @@ pub struct SpiceParser {
 /// Netlist builder for constructing SPICE netlists
 pub struct NetlistBuilder {
     title: String,
+    subcircuits: Vec<String>,
     components: Vec<String>,
     analysis_commands: Vec<String>,
     control_commands: Vec<String>,
@@ -35,12 +37,21 @@ impl SpiceParser {
     pub fn generate_netlist(&mut self, circuit: &Circuit) -> Result<String> {
         let mut builder = NetlistBuilder::new("OpenCircuit Generated Circuit");
         
+        // Subcircuit definitions must precede their instances
+        for subcircuit in &circuit.subcircuits {
+            builder.add_subcircuit(Self::subcircuit_to_spice(subcircuit));
+        }
+        
         // Add components to netlist
         for component in &circuit.components {
             let spice_line = self.component_to_spice(component)?;
             builder.add_component(spice_line);
         }
         
+        for instance in &circuit.subcircuit_instances {
+            builder.add_component(Self::instance_to_spice(instance));
+        }
+        
         // Add default analysis commands
         builder.add_analysis(".op"); // Operating point analysis
         builder.add_control(".end");
@@ -48,6 +59,39 @@ impl SpiceParser {
         Ok(builder.build())
     }
     
+    /// Convert a subcircuit definition to a `.SUBCKT` / `.ENDS` block
+    fn subcircuit_to_spice(subcircuit: &SubCircuit) -> String {
+        let mut block = String::new();
+        writeln!(block, ".SUBCKT {} {}", subcircuit.name, subcircuit.ports.join(" ")).unwrap();
+        
+        for model in &subcircuit.internal_netlist.models {
+            let mut parameters: Vec<String> = model.parameters
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            parameters.sort();
+            writeln!(block, ".model {} {} {}", model.name, model.model_type, parameters.join(" ")).unwrap();
+        }
+        
+        for component in &subcircuit.internal_netlist.components {
+            writeln!(block, "{}", component.to_spice()).unwrap();
+        }
+        
+        write!(block, ".ENDS {}", subcircuit.name).unwrap();
+        block
+    }
+    
+    /// Convert a subcircuit instance to an `X` line
+    fn instance_to_spice(instance: &SubCircuitInstance) -> String {
+        let name = if instance.instance_name.starts_with(['X', 'x']) {
+            instance.instance_name.clone()
+        } else {
+            format!("X{}", instance.instance_name)
+        };
+        
+        format!("{} {} {}", name, instance.port_connections.join(" "), instance.subcircuit)
+    }
+    
     /// Convert a component to SPICE format
     fn component_to_spice(&mut self, component: &Component) -> Result<String> {
         let component_id = self.get_component_id(&component.component_type);
@@ -162,15 +206,67 @@ impl SpiceParser {
     /// Parse a SPICE netlist into a circuit
     pub fn parse_netlist(&self, netlist: &str) -> Result<Circuit> {
         let mut circuit = Circuit::new();
+        let mut top_level = Vec::new();
+        let mut open_subcircuit: Option<(String, Vec<String>, Vec<&str>)> = None;
         
+        // First pass: split out .SUBCKT blocks so instances can reference later definitions
         for line in netlist.lines() {
             let line = line.trim();
+            let keyword = line.split_whitespace().next().unwrap_or_default().to_lowercase();
             
+            if keyword == ".subckt" {
+                if open_subcircuit.is_some() {
+                    return Err(SimulationError::ParseError {
+                        line: line.to_string(),
+                        reason: "Nested .SUBCKT definitions are not supported".to_string(),
+                    });
+                }
+                let (name, ports) = Self::parse_subckt_header(line)?;
+                open_subcircuit = Some((name, ports, Vec::new()));
+            } else if keyword == ".ends" {
+                let (name, ports, body) = open_subcircuit.take()
+                    .ok_or_else(|| SimulationError::ParseError {
+                        line: line.to_string(),
+                        reason: ".ENDS without matching .SUBCKT".to_string(),
+                    })?;
+                
+                let mut internal_netlist = Netlist::from_spice(&body.join("\n"))
+                    .map_err(|e| SimulationError::ParseError {
+                        line: format!(".SUBCKT {}", name),
+                        reason: e.to_string(),
+                    })?;
+                internal_netlist.title = name.clone();
+                
+                circuit.add_subcircuit(SubCircuit { name, ports, internal_netlist });
+            } else if let Some((_, _, body)) = open_subcircuit.as_mut() {
+                body.push(line);
+            } else {
+                top_level.push(line);
+            }
+        }
+        
+        if let Some((name, _, _)) = open_subcircuit {
+            return Err(SimulationError::ParseError {
+                line: format!(".SUBCKT {}", name),
+                reason: "Missing .ENDS".to_string(),
+            });
+        }
+        
+        for line in top_level {
             // Skip comments and empty lines
             if line.is_empty() || line.starts_with('*') || line.starts_with('.') {
                 continue;
             }
             
+            if let Some((instance_name, subcircuit, ports)) = self.parse_instance_line(&circuit, line) {
+                circuit.instantiate_subcircuit(&subcircuit, &instance_name, ports)
+                    .map_err(|e| SimulationError::ParseError {
+                        line: line.to_string(),
+                        reason: e.to_string(),
+                    })?;
+                continue;
+            }
+            
             // Parse component line
             if let Ok(component) = self.parse_component_line(line) {
                 circuit.components.push(component);
@@ -180,6 +276,45 @@ impl SpiceParser {
         Ok(circuit)
     }
     
+    /// Parse `.SUBCKT name port... [PARAMS: ...]` into its name and ports
+    fn parse_subckt_header(line: &str) -> Result<(String, Vec<String>)> {
+        let mut parts = line.split_whitespace().skip(1);
+        let name = parts.next()
+            .ok_or_else(|| SimulationError::ParseError {
+                line: line.to_string(),
+                reason: ".SUBCKT missing name".to_string(),
+            })?
+            .to_string();
+        
+        let ports = parts
+            .take_while(|part| !part.contains('=') && !part.eq_ignore_ascii_case("params:"))
+            .map(|part| part.to_string())
+            .collect();
+        
+        Ok((name, ports))
+    }
+    
+    /// Recognise an `X` line that instantiates a subcircuit defined in `circuit`
+    fn parse_instance_line(&self, circuit: &Circuit, line: &str) -> Option<(String, String, Vec<String>)> {
+        if !line.starts_with(['X', 'x']) {
+            return None;
+        }
+        
+        let parts: Vec<&str> = line
+            .split_whitespace()
+            .take_while(|part| !part.contains('='))
+            .collect();
+        let (subcircuit, rest) = parts.split_last()?;
+        let (instance_name, ports) = rest.split_first()?;
+        circuit.get_subcircuit(subcircuit)?;
+        
+        Some((
+            instance_name.to_string(),
+            subcircuit.to_string(),
+            ports.iter().map(|port| port.to_string()).collect(),
+        ))
+    }
+    
     /// Parse a single component line
     fn parse_component_line(&self, line: &str) -> Result<Component> {
         let parts: Vec<&str> = line.split_whitespace().collect();
@@ -192,20 +327,11 @@ impl SpiceParser {
         }
         
         let component_name = parts[0];
-        let component_type = match component_name.chars().next().unwrap().to_ascii_uppercase() {
-            'R' => ComponentType::Resistor,
-            'C' => ComponentType::Capacitor,
-            'L' => ComponentType::Inductor,
-            'V' => ComponentType::VoltageSource,
-            'I' => ComponentType::CurrentSource,
-            'D' => ComponentType::Diode,
-            'Q' => ComponentType::Transistor,
-            'X' => ComponentType::OpAmp,
-            _ => return Err(SimulationError::ParseError {
+        let component_type = component_name.chars().next().and_then(ComponentType::from_spice_prefix)
+            .ok_or_else(|| SimulationError::ParseError {
                 line: line.to_string(),
                 reason: "Unknown component type".to_string(),
-            }),
-        };
+            })?;
         
         let value = if parts.len() > 3 {
             Some(parts.last().unwrap().to_string())
@@ -218,6 +344,7 @@ impl SpiceParser {
             component_type,
             value,
             position: (0.0, 0.0), // Default position
+            purpose: None,
         })
     }
 }
@@ -227,12 +354,18 @@ impl NetlistBuilder {
     pub fn new(title: &str) -> Self {
         Self {
             title: title.to_string(),
+            subcircuits: Vec::new(),
             components: Vec::new(),
             analysis_commands: Vec::new(),
             control_commands: Vec::new(),
         }
     }
     
+    /// Add a `.SUBCKT` definition block
+    pub fn add_subcircuit(&mut self, definition: String) {
+        self.subcircuits.push(definition);
+    }
+    
     /// Add a component line
     pub fn add_component(&mut self, component: String) {
         self.components.push(component);
@@ -255,6 +388,11 @@ impl NetlistBuilder {
         // Title line
         writeln!(netlist, "{}", self.title).unwrap();
         
+        // Subcircuit definitions
+        for subcircuit in &self.subcircuits {
+            writeln!(netlist, "{}", subcircuit).unwrap();
+        }
+        
         // Component lines
         for component in &self.components {
             writeln!(netlist, "{}", component).unwrap();
@@ -294,12 +432,14 @@ mod tests {
             component_type: ComponentType::Resistor,
             value: Some("1k".to_string()),
             position: (0.0, 0.0),
+            purpose: None,
         });
         circuit.add_component(Component {
             id: "V1".to_string(),
             component_type: ComponentType::VoltageSource,
             value: Some("5".to_string()),
             position: (0.0, 0.0),
+            purpose: None,
         });
         
         let netlist = parser.generate_netlist(&circuit).unwrap();
@@ -319,12 +459,13 @@ mod tests {
 Simple test circuit
 R1 1 0 1k
 V1 1 0 DC 5
+M1 2 1 0 0 NMOD
 .op
 .end
 "#;
         
         let circuit = parser.parse_netlist(netlist).unwrap();
-        assert_eq!(circuit.components.len(), 2);
+        assert_eq!(circuit.components.len(), 3);
         
         let resistor = &circuit.components[0];
         assert_eq!(resistor.component_type, ComponentType::Resistor);
@@ -333,5 +474,55 @@ V1 1 0 DC 5
         let voltage_source = &circuit.components[1];
         assert_eq!(voltage_source.component_type, ComponentType::VoltageSource);
         assert_eq!(voltage_source.value, Some("5".to_string()));
+        
+        // MOSFETs are imported as transistors
+        assert_eq!(circuit.components[2].component_type, ComponentType::Transistor);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_subcircuit_round_trip() {
+        let parser = SpiceParser::new();
+        
+        let netlist = r#"
+Buffered divider
+.SUBCKT BUFFER in out vcc gnd
+R1 in mid 10k
+R2 mid gnd 10k
+E1 out gnd mid gnd 2
+.ENDS BUFFER
+V1 vcc 0 DC 5
+X1 vcc out vcc 0 BUFFER
+.op
+.end
+"#;
+        
+        let circuit = parser.parse_netlist(netlist).unwrap();
+        assert_eq!(circuit.subcircuits.len(), 1);
+        
+        let buffer = &circuit.subcircuits[0];
+        assert_eq!(buffer.name, "BUFFER");
+        assert_eq!(buffer.ports, vec!["in", "out", "vcc", "gnd"]);
+        assert_eq!(buffer.internal_netlist.components.len(), 3);
+        
+        assert_eq!(circuit.subcircuit_instances.len(), 1);
+        assert_eq!(circuit.subcircuit_instances[0].port_connections, vec!["vcc", "out", "vcc", "0"]);
+        assert_eq!(circuit.components.len(), 1);
+        
+        let mut generator = SpiceParser::new();
+        let generated = generator.generate_netlist(&circuit).unwrap();
+        assert!(generated.contains(".SUBCKT BUFFER in out vcc gnd"));
+        assert!(generated.contains(".ENDS BUFFER"));
+        assert!(generated.contains("X1 vcc out vcc 0 BUFFER"));
+        assert!(generated.find(".ENDS").unwrap() < generated.find("X1").unwrap());
+        
+        let reparsed = parser.parse_netlist(&generated).unwrap();
+        assert_eq!(reparsed.subcircuits, circuit.subcircuits);
+    }
+    
+    #[test]
+    fn test_unterminated_subcircuit() {
+        let parser = SpiceParser::new();
+        let result = parser.parse_netlist(".SUBCKT OPAMP inp inn out\nR1 inp inn 1MEG\n");
+        assert!(matches!(result, Err(SimulationError::ParseError { .. })));
+    }
+}
@@ -0,0 +1,266 @@
+//! Component stress checks against derated ratings
+//!
+//! Each component's worst-case voltage, current and power over every
+//! simulated operating point (DC sweep points, transient samples) is compared
+//! with its rating scaled by [`DEFAULT_DERATING_FACTOR`]. AC results are
+//! small-signal and are not checked.
+//!
+//! The voltage across a component is the spread of the nets it connects to,
+//! with an unreported `0`/`gnd` net taken as 0 V. Currents and powers come
+//! from the results when NgSpice reports them under the component ID, and
+//! are otherwise derived from the voltage and, for resistors, the value.
+
+use crate::results::{AnalysisData, DCResults, SimulationResults};
+use crate::worst_case::parse_node_voltages;
+use opencircuit_circuit::{Circuit, Component, ComponentType};
+use opencircuit_utils::units::parse_spice_value;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Fraction of each rating allowed in normal operation
+pub const DEFAULT_DERATING_FACTOR: f64 = 0.5;
+
+/// Absolute maximum ratings of a component
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ComponentRating {
+    /// Volts
+    pub max_voltage: f64,
+    /// Amps
+    pub max_current: f64,
+    /// Watts
+    pub max_power: f64,
+}
+
+/// Quantity that exceeded its derated limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StressType {
+    Voltage,
+    Current,
+    Power,
+}
+
+/// A component stressed beyond its derated rating
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StressViolation {
+    pub component_id: String,
+    pub stress_type: StressType,
+    /// Worst-case magnitude over the simulation
+    pub measured: f64,
+    /// Derated limit, the rating times `derating_factor`
+    pub limit: f64,
+    pub derating_factor: f64,
+}
+
+impl SimulationResults {
+    /// Components whose voltage, current or power exceeds half their rating
+    ///
+    /// Components without an entry in `component_ratings` are skipped, as are
+    /// quantities that cannot be measured or derived from the results.
+    /// Violations are listed in circuit order.
+    pub fn find_operating_point_violations(
+        &self,
+        circuit: &Circuit,
+        component_ratings: &HashMap<String, ComponentRating>,
+    ) -> Vec<StressViolation> {
+        let points = operating_points(&self.data);
+        let mut violations = Vec::new();
+
+        for component in &circuit.components {
+            let Some(rating) = component_ratings.get(&component.id) else {
+                continue;
+            };
+            let nets = component_nets(circuit, &component.id);
+            let mut peaks = [None::<f64>; 3];
+            for point in &points {
+                let stress = point_stress(point, component, &nets);
+                for (peak, value) in peaks.iter_mut().zip(stress) {
+                    if let Some(value) = value.map(f64::abs).filter(|value| value.is_finite()) {
+                        *peak = Some(peak.map_or(value, |peak| peak.max(value)));
+                    }
+                }
+            }
+
+            let limits = [
+                (StressType::Voltage, rating.max_voltage),
+                (StressType::Current, rating.max_current),
+                (StressType::Power, rating.max_power),
+            ];
+            for ((stress_type, maximum), peak) in limits.into_iter().zip(peaks) {
+                let limit = maximum * DEFAULT_DERATING_FACTOR;
+                if let Some(measured) = peak.filter(|&measured| measured > limit) {
+                    violations.push(StressViolation {
+                        component_id: component.id.clone(),
+                        stress_type,
+                        measured,
+                        limit,
+                        derating_factor: DEFAULT_DERATING_FACTOR,
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+/// Every operating point in the results, as DC-style snapshots
+fn operating_points(data: &AnalysisData) -> Vec<DCResults> {
+    let snapshot = |node_voltages, branch_currents, power_dissipation| DCResults {
+        node_voltages,
+        branch_currents,
+        power_dissipation,
+        sweep_data: None,
+    };
+    let sample = |series: &HashMap<String, Vec<f64>>, index: usize| -> HashMap<String, f64> {
+        series
+            .iter()
+            .filter_map(|(name, values)| Some((name.clone(), *values.get(index)?)))
+            .collect()
+    };
+
+    match data {
+        AnalysisData::DC(dc) => std::iter::once(dc.clone())
+            .chain(dc.sweep_data.iter().flat_map(|sweep| sweep.results.iter().cloned()))
+            .collect(),
+        AnalysisData::Transient(transient) => (0..transient.time_points.len())
+            .map(|index| {
+                snapshot(
+                    sample(&transient.voltage_waveforms, index),
+                    sample(&transient.current_waveforms, index),
+                    sample(&transient.power_waveforms, index),
+                )
+            })
+            .collect(),
+        AnalysisData::Raw(lines) => {
+            let voltages = parse_node_voltages(lines);
+            let samples = voltages.values().map(Vec::len).max().unwrap_or_default();
+            (0..samples)
+                .map(|index| snapshot(sample(&voltages, index), HashMap::new(), HashMap::new()))
+                .collect()
+        }
+        AnalysisData::AC(_) => Vec::new(),
+    }
+}
+
+/// Voltage across, current through and power in a component at one point
+fn point_stress(point: &DCResults, component: &Component, nets: &BTreeSet<&str>) -> [Option<f64>; 3] {
+    let voltages: Option<Vec<f64>> = nets
+        .iter()
+        .map(|net| {
+            lookup(&point.node_voltages, &[net.to_string(), format!("v({})", net)])
+                .or_else(|| is_ground(net).then_some(0.0))
+        })
+        .collect();
+    let voltage = voltages.filter(|voltages| voltages.len() >= 2).map(|voltages| {
+        let max = voltages.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = voltages.iter().copied().fold(f64::INFINITY, f64::min);
+        max - min
+    });
+
+    let id = &component.id;
+    let resistance = (component.component_type == ComponentType::Resistor)
+        .then(|| parse_spice_value(component.value.as_deref()?).ok())
+        .flatten()
+        .filter(|&resistance| resistance > 0.0);
+    let current = lookup(&point.branch_currents, &[id.clone(), format!("i({})", id), format!("{}#branch", id)])
+        .or_else(|| Some(voltage? / resistance?));
+    let power = lookup(&point.power_dissipation, std::slice::from_ref(id)).or_else(|| Some(voltage? * current?));
+
+    [voltage, current, power]
+}
+
+/// Nets a component is connected to
+fn component_nets<'a>(circuit: &'a Circuit, component_id: &str) -> BTreeSet<&'a str> {
+    circuit
+        .connections
+        .iter()
+        .filter(|connection| connection.from == component_id || connection.to == component_id)
+        .map(|connection| connection.net_name.as_str())
+        .collect()
+}
+
+fn is_ground(net: &str) -> bool {
+    net == "0" || net.eq_ignore_ascii_case("gnd")
+}
+
+/// First value keyed by any of `names`, ignoring case as SPICE does
+fn lookup(series: &HashMap<String, f64>, names: &[String]) -> Option<f64> {
+    names.iter().find_map(|name| {
+        series
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::AnalysisType;
+    use crate::results::TransientResults;
+    use opencircuit_circuit::Connection;
+
+    fn circuit() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type, value) in [("V1", ComponentType::VoltageSource, "10"), ("R1", ComponentType::Resistor, "100")] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type,
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        for (from, to, net) in [("V1", "R1", "in"), ("R1", "V1", "0")] {
+            circuit.add_connection(Connection { from: from.to_string(), to: to.to_string(), net_name: net.to_string() });
+        }
+        circuit
+    }
+
+    fn rating(max_voltage: f64, max_current: f64, max_power: f64) -> ComponentRating {
+        ComponentRating { max_voltage, max_current, max_power }
+    }
+
+    #[test]
+    fn test_derived_resistor_stress() {
+        let results = SimulationResults::new(
+            AnalysisType::DC,
+            AnalysisData::DC(DCResults {
+                node_voltages: HashMap::from([("v(in)".to_string(), 10.0)]),
+                branch_currents: HashMap::new(),
+                power_dissipation: HashMap::new(),
+                sweep_data: None,
+            }),
+        );
+        // 10 V across 100 Ω gives 100 mA and 1 W
+        let ratings = HashMap::from([("R1".to_string(), rating(25.0, 0.15, 1.5))]);
+
+        let violations = results.find_operating_point_violations(&circuit(), &ratings);
+        let found: Vec<_> = violations.iter().map(|violation| (violation.stress_type, violation.limit)).collect();
+        assert_eq!(found, [(StressType::Current, 0.075), (StressType::Power, 0.75)]);
+        assert!((violations[0].measured - 0.1).abs() < 1e-12);
+        assert!((violations[1].measured - 1.0).abs() < 1e-12);
+        assert_eq!(violations[0].derating_factor, DEFAULT_DERATING_FACTOR);
+    }
+
+    #[test]
+    fn test_transient_peak_stress() {
+        let results = SimulationResults::new(
+            AnalysisType::Transient,
+            AnalysisData::Transient(TransientResults {
+                time_points: vec![0.0, 1e-3, 2e-3],
+                voltage_waveforms: HashMap::from([("in".to_string(), vec![0.0, 12.0, 5.0])]),
+                current_waveforms: HashMap::from([("I(V1)".to_string(), vec![0.0, -0.12, -0.05])]),
+                power_waveforms: HashMap::new(),
+            }),
+        );
+        let ratings = HashMap::from([("V1".to_string(), rating(20.0, 1.0, 10.0)), ("R9".to_string(), rating(0.0, 0.0, 0.0))]);
+
+        let violations = results.find_operating_point_violations(&circuit(), &ratings);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].component_id, "V1");
+        assert_eq!(violations[0].stress_type, StressType::Voltage);
+        assert_eq!(violations[0].measured, 12.0);
+        assert_eq!(violations[0].limit, 10.0);
+    }
+}
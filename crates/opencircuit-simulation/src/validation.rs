@@ -0,0 +1,312 @@
+//! Pre-simulation syntax checks for SPICE netlists
+//!
+//! Catches the mistakes that otherwise surface as cryptic NgSpice errors:
+//! a missing title line, unbalanced `.SUBCKT`/`.ENDS` and `.IF`/`.ENDIF`
+//! blocks, no analysis card, duplicate component names and references to
+//! models that are never defined. SPICE is case-insensitive, so names are
+//! compared without regard to case.
+
+use crate::model_library::ModelLibrary;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Dot cards that run an analysis
+pub const ANALYSIS_CARDS: [&str; 7] = [".op", ".dc", ".ac", ".tran", ".noise", ".sens", ".tf"];
+
+/// Netlists that cannot be checked at all
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("Netlist is empty")]
+    EmptyNetlist,
+}
+
+/// Kind of problem found in a netlist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetlistIssueKind {
+    MissingTitle,
+    UnbalancedSubcircuit,
+    UnbalancedConditional,
+    MissingAnalysis,
+    DuplicateComponent,
+    UndefinedModel,
+}
+
+/// A problem found in a netlist, at a 1-based line number
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetlistIssue {
+    pub line: usize,
+    pub kind: NetlistIssueKind,
+    pub message: String,
+}
+
+/// Problems found in a netlist, in line order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetlistValidationReport {
+    pub issues: Vec<NetlistIssue>,
+}
+
+impl NetlistValidationReport {
+    /// Whether the netlist passed every check
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn push(&mut self, line: usize, kind: NetlistIssueKind, message: String) {
+        self.issues.push(NetlistIssue { line, kind, message });
+    }
+}
+
+/// A model or subcircuit named by a device line
+struct ModelReference {
+    line: usize,
+    component: String,
+    model: String,
+}
+
+/// Check a SPICE netlist for syntax errors without running NgSpice
+///
+/// Models and subcircuits count as defined when the netlist declares them or
+/// they are in [`ModelLibrary::standard`], which the simulation engine always
+/// injects. Netlists that `.include` or `.lib` other files are not checked
+/// for undefined models, since those files may define them.
+pub fn validate_spice_netlist(netlist: &str) -> Result<NetlistValidationReport, ValidationError> {
+    if netlist.trim().is_empty() {
+        return Err(ValidationError::EmptyNetlist);
+    }
+
+    let mut report = NetlistValidationReport::default();
+    let mut lines = statements(netlist).into_iter();
+
+    // SPICE always reads the first line as the title, whatever it contains
+    match lines.next() {
+        Some((line, title)) if title.trim().is_empty() => {
+            report.push(line, NetlistIssueKind::MissingTitle, "first line is blank; SPICE reads it as the title".to_string())
+        }
+        Some((line, title)) if title.trim_start().starts_with('.') => report.push(
+            line,
+            NetlistIssueKind::MissingTitle,
+            format!("'{}' is on the title line and will be ignored", title.trim()),
+        ),
+        _ => {}
+    }
+
+    let library = ModelLibrary::standard();
+    let mut defined: HashSet<String> =
+        library.models.keys().chain(library.subcircuits.keys()).cloned().collect();
+    let mut references: Vec<ModelReference> = Vec::new();
+    let mut includes_files = false;
+    let mut has_analysis = false;
+    let mut last_line = 1;
+
+    let mut subcircuits: Vec<(usize, String)> = Vec::new();
+    let mut conditionals: Vec<usize> = Vec::new();
+    let mut in_control = false;
+    let mut components: HashMap<(Option<String>, String), usize> = HashMap::new();
+
+    for (line, statement) in lines {
+        last_line = line;
+        let tokens: Vec<&str> = statement.split_whitespace().collect();
+        let Some(first) = tokens.first() else {
+            continue;
+        };
+        let keyword = first.to_lowercase();
+        if keyword.starts_with('*') {
+            continue;
+        }
+
+        if in_control {
+            if keyword == ".endc" {
+                in_control = false;
+            } else if ["op", "dc", "ac", "tran", "noise", "sens", "tf"].contains(&keyword.as_str()) {
+                has_analysis = true;
+            }
+            continue;
+        }
+
+        match keyword.as_str() {
+            ".end" => break,
+            ".control" => in_control = true,
+            ".include" | ".inc" | ".lib" => includes_files = true,
+            ".model" => {
+                if let Some(name) = tokens.get(1) {
+                    defined.insert(name.to_uppercase());
+                }
+            }
+            ".subckt" => {
+                let name = tokens.get(1).map(|name| name.to_uppercase()).unwrap_or_default();
+                defined.insert(name.clone());
+                subcircuits.push((line, name));
+            }
+            ".ends" => {
+                if subcircuits.pop().is_none() {
+                    report.push(line, NetlistIssueKind::UnbalancedSubcircuit, ".ENDS without a matching .SUBCKT".to_string());
+                }
+            }
+            ".if" => conditionals.push(line),
+            ".elseif" | ".else" => {
+                if conditionals.is_empty() {
+                    report.push(
+                        line,
+                        NetlistIssueKind::UnbalancedConditional,
+                        format!("{} outside an .IF block", first.to_uppercase()),
+                    );
+                }
+            }
+            ".endif" => {
+                if conditionals.pop().is_none() {
+                    report.push(line, NetlistIssueKind::UnbalancedConditional, ".ENDIF without a matching .IF".to_string());
+                }
+            }
+            card if ANALYSIS_CARDS.contains(&card) => has_analysis = true,
+            card if card.starts_with('.') => {}
+            _ => {
+                // Component names are local to their subcircuit, and .IF branches
+                // may define alternatives of the same component
+                let scope = subcircuits.last().map(|(_, name)| name.clone());
+                let name = first.to_uppercase();
+                if conditionals.is_empty() {
+                    if let Some(previous) = components.insert((scope, name), line) {
+                        report.push(
+                            line,
+                            NetlistIssueKind::DuplicateComponent,
+                            format!("{} is already defined on line {}", first, previous),
+                        );
+                    }
+                }
+                if let Some(model) = model_reference(&tokens) {
+                    references.push(ModelReference { line, component: first.to_string(), model: model.to_string() });
+                }
+            }
+        }
+    }
+
+    for (line, name) in subcircuits {
+        report.push(line, NetlistIssueKind::UnbalancedSubcircuit, format!(".SUBCKT {} has no matching .ENDS", name));
+    }
+    for line in conditionals {
+        report.push(line, NetlistIssueKind::UnbalancedConditional, ".IF has no matching .ENDIF".to_string());
+    }
+    if !has_analysis {
+        report.push(
+            last_line,
+            NetlistIssueKind::MissingAnalysis,
+            "no analysis card (.OP, .DC, .AC, .TRAN, ...) found".to_string(),
+        );
+    }
+    if !includes_files {
+        for reference in references.into_iter().filter(|reference| !defined.contains(&reference.model.to_uppercase())) {
+            report.push(
+                reference.line,
+                NetlistIssueKind::UndefinedModel,
+                format!("{} uses undefined model {}", reference.component, reference.model),
+            );
+        }
+    }
+
+    report.issues.sort_by_key(|issue| issue.line);
+    Ok(report)
+}
+
+/// Statements with `+` continuation lines joined on, keyed by their first line number
+fn statements(netlist: &str) -> Vec<(usize, String)> {
+    let mut statements: Vec<(usize, String)> = Vec::new();
+    for (index, line) in netlist.lines().enumerate() {
+        match (line.trim_start().strip_prefix('+'), statements.last_mut()) {
+            (Some(continuation), Some((_, statement))) if index > 0 => {
+                statement.push(' ');
+                statement.push_str(continuation.trim());
+            }
+            _ => statements.push((index + 1, line.to_string())),
+        }
+    }
+    statements
+}
+
+/// Model or subcircuit named by a device line, for devices that require one
+///
+/// The model follows a fixed number of nodes but may be followed by an area
+/// factor, `OFF` or `key=value` parameters, so it is taken as the last token
+/// after the nodes that is none of those.
+fn model_reference<'a>(tokens: &[&'a str]) -> Option<&'a str> {
+    let nodes = match tokens[0].chars().next()?.to_ascii_uppercase() {
+        'D' => 2,
+        'Q' | 'J' => 3,
+        'M' => 4,
+        'X' => {
+            let end = tokens.iter().position(|token| token.eq_ignore_ascii_case("params:")).unwrap_or(tokens.len());
+            return tokens[1..end].iter().rev().find(|token| !token.contains('=')).copied().filter(|_| end > 2);
+        }
+        _ => return None,
+    };
+    tokens
+        .get(1 + nodes..)?
+        .iter()
+        .rev()
+        .find(|token| {
+            !token.contains('=')
+                && !token.eq_ignore_ascii_case("off")
+                && token.parse::<f64>().is_err()
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(report: &NetlistValidationReport) -> Vec<(usize, NetlistIssueKind)> {
+        report.issues.iter().map(|issue| (issue.line, issue.kind)).collect()
+    }
+
+    #[test]
+    fn test_valid_netlist() {
+        let netlist = "Diode clamp\n\
+                       .model DFAST D(IS=1e-14)\n\
+                       .subckt BUF in out\n\
+                       R1 in out 1k\n\
+                       .ends\n\
+                       V1 in 0 5\n\
+                       R1 in mid 1k\n\
+                       D1 mid 0 DFAST\n\
+                       D2 mid 0 D1N4148 2 OFF\n\
+                       Q1 out mid 0 2N2222\n\
+                       X1 mid out BUF\n\
+                       + params: gain=2\n\
+                       .tran 1u 1m\n\
+                       .end";
+        let report = validate_spice_netlist(netlist).unwrap();
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert!(matches!(validate_spice_netlist(" \n"), Err(ValidationError::EmptyNetlist)));
+    }
+
+    #[test]
+    fn test_netlist_issues_carry_line_numbers() {
+        let netlist = ".model DFAST D(IS=1e-14)\n\
+                       .subckt AMP in out\n\
+                       R1 in out 10k\n\
+                       R2 out 0 1k\n\
+                       V1 in 0 5\n\
+                       r2 in 0 2k\n\
+                       D1 in 0 DSLOW\n\
+                       X1 in out OPAMP\n\
+                       .if (gain > 1)\n\
+                       .end";
+        let report = validate_spice_netlist(netlist).unwrap();
+        assert!(!report.is_valid());
+        assert_eq!(
+            kinds(&report),
+            [
+                (1, NetlistIssueKind::MissingTitle),
+                (2, NetlistIssueKind::UnbalancedSubcircuit),
+                (6, NetlistIssueKind::DuplicateComponent),
+                (7, NetlistIssueKind::UndefinedModel),
+                (8, NetlistIssueKind::UndefinedModel),
+                (9, NetlistIssueKind::UnbalancedConditional),
+                (10, NetlistIssueKind::MissingAnalysis),
+            ]
+        );
+        assert_eq!(report.issues[2].message, "r2 is already defined on line 4");
+        assert_eq!(report.issues[3].message, "D1 uses undefined model DSLOW");
+    }
+}
@@ -0,0 +1,369 @@
+//! Worst-case (tolerance corner) analysis
+
+use crate::errors::{Result, SimulationError};
+use opencircuit_circuit::Circuit;
+use opencircuit_utils::units::parse_spice_value;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Component id -> fractional tolerance (0.05 for ±5%)
+pub type ComponentToleranceMap = HashMap<String, f64>;
+
+/// Component id -> value applied for one simulation run
+pub type CornerConfig = BTreeMap<String, f64>;
+
+/// Largest circuit (in toleranced components) evaluated exhaustively
+pub const MAX_EXHAUSTIVE_COMPONENTS: usize = 16;
+
+/// A toleranced component and its nominal value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToleranceSpec {
+    pub component_id: String,
+    pub nominal: f64,
+    pub tolerance: f64,
+}
+
+/// Extremes observed at one node across all corners
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeBounds {
+    pub min_voltage: f64,
+    pub max_voltage: f64,
+    /// Corner that produced `min_voltage`
+    pub min_corner: CornerConfig,
+    /// Corner that produced `max_voltage`
+    pub max_corner: CornerConfig,
+}
+
+/// Worst-case bounds for every node in the circuit
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorstCaseResults {
+    /// Bounds keyed by output vector, e.g. `v(2)`
+    pub node_bounds: HashMap<String, NodeBounds>,
+    /// Number of corner simulations run
+    pub corners_evaluated: usize,
+    /// Whether every corner was simulated (vs. sensitivity-guided corners)
+    pub exhaustive: bool,
+}
+
+impl ToleranceSpec {
+    pub fn low(&self) -> f64 {
+        self.nominal * (1.0 - self.tolerance)
+    }
+
+    pub fn high(&self) -> f64 {
+        self.nominal * (1.0 + self.tolerance)
+    }
+}
+
+impl WorstCaseResults {
+    /// Bounds for a node, accepting either `out` or `v(out)`
+    pub fn bounds(&self, node: &str) -> Option<&NodeBounds> {
+        self.node_bounds
+            .get(node)
+            .or_else(|| self.node_bounds.get(&format!("v({})", node)))
+    }
+
+    /// Fold one corner's simulated node voltages into the bounds
+    pub fn record(&mut self, corner: &CornerConfig, voltages: &HashMap<String, Vec<f64>>) {
+        self.corners_evaluated += 1;
+
+        for (node, values) in voltages {
+            for &voltage in values {
+                let bounds = self.node_bounds.entry(node.clone()).or_insert_with(|| NodeBounds {
+                    min_voltage: voltage,
+                    max_voltage: voltage,
+                    min_corner: corner.clone(),
+                    max_corner: corner.clone(),
+                });
+                if voltage < bounds.min_voltage {
+                    bounds.min_voltage = voltage;
+                    bounds.min_corner = corner.clone();
+                }
+                if voltage > bounds.max_voltage {
+                    bounds.max_voltage = voltage;
+                    bounds.max_corner = corner.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Resolve the tolerance map against the circuit's component values
+pub fn tolerance_specs(circuit: &Circuit, tolerances: &ComponentToleranceMap) -> Result<Vec<ToleranceSpec>> {
+    let mut specs = Vec::new();
+
+    for (component_id, &tolerance) in tolerances {
+        let invalid = |reason: String| SimulationError::InvalidComponent {
+            component: component_id.clone(),
+            reason,
+        };
+
+        if !(0.0..1.0).contains(&tolerance) {
+            return Err(invalid(format!("tolerance {} must be between 0 and 1", tolerance)));
+        }
+        let component = circuit
+            .components
+            .iter()
+            .find(|component| &component.id == component_id)
+            .ok_or_else(|| invalid("component not found in circuit".to_string()))?;
+        let value = component
+            .value
+            .as_deref()
+            .ok_or_else(|| invalid("component has no value".to_string()))?;
+        let nominal = parse_spice_value(value).map_err(|e| invalid(e.to_string()))?;
+
+        specs.push(ToleranceSpec {
+            component_id: component_id.clone(),
+            nominal,
+            tolerance,
+        });
+    }
+
+    specs.sort_by(|a, b| a.component_id.cmp(&b.component_id));
+    Ok(specs)
+}
+
+/// Every combination of low/high extremes (2^n corners)
+pub fn exhaustive_corners(specs: &[ToleranceSpec]) -> Vec<CornerConfig> {
+    (0..1u64 << specs.len())
+        .map(|mask| {
+            specs
+                .iter()
+                .enumerate()
+                .map(|(bit, spec)| {
+                    let value = if mask & (1 << bit) == 0 { spec.low() } else { spec.high() };
+                    (spec.component_id.clone(), value)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// All components at nominal except one raised to its high extreme
+pub fn perturbation_corners(specs: &[ToleranceSpec]) -> Vec<CornerConfig> {
+    specs
+        .iter()
+        .map(|perturbed| {
+            specs
+                .iter()
+                .map(|spec| {
+                    let value = if spec.component_id == perturbed.component_id {
+                        spec.high()
+                    } else {
+                        spec.nominal
+                    };
+                    (spec.component_id.clone(), value)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Nominal configuration
+pub fn nominal_corner(specs: &[ToleranceSpec]) -> CornerConfig {
+    specs
+        .iter()
+        .map(|spec| (spec.component_id.clone(), spec.nominal))
+        .collect()
+}
+
+/// Corners pushing each node towards its minimum and maximum
+///
+/// `sensitivities` gives, per node, the change in voltage when each component
+/// moves to its high extreme. Components that raise a node go high in its max
+/// corner and low in its min corner.
+pub fn guided_corners(
+    specs: &[ToleranceSpec],
+    sensitivities: &HashMap<String, HashMap<String, f64>>,
+) -> Vec<CornerConfig> {
+    let mut nodes: Vec<&String> = sensitivities.keys().collect();
+    nodes.sort();
+
+    let mut corners: Vec<CornerConfig> = Vec::new();
+    for node in nodes {
+        for towards_max in [false, true] {
+            let corner: CornerConfig = specs
+                .iter()
+                .map(|spec| {
+                    let delta = sensitivities[node].get(&spec.component_id).copied().unwrap_or(0.0);
+                    let value = if (delta >= 0.0) == towards_max { spec.high() } else { spec.low() };
+                    (spec.component_id.clone(), value)
+                })
+                .collect();
+            if !corners.contains(&corner) {
+                corners.push(corner);
+            }
+        }
+    }
+
+    corners
+}
+
+/// Copy of `circuit` with the corner's component values applied
+pub fn apply_corner(circuit: &Circuit, corner: &CornerConfig) -> Circuit {
+    let mut circuit = circuit.clone();
+    for component in &mut circuit.components {
+        if let Some(value) = corner.get(&component.id) {
+            component.value = Some(format!("{:e}", value));
+        }
+    }
+    circuit
+}
+
+/// Average change in each node's voltage between two runs
+pub fn node_deltas(
+    nominal: &HashMap<String, Vec<f64>>,
+    perturbed: &HashMap<String, Vec<f64>>,
+) -> HashMap<String, f64> {
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len().max(1) as f64;
+
+    perturbed
+        .iter()
+        .filter_map(|(node, values)| {
+            let baseline = nominal.get(node)?;
+            Some((node.clone(), mean(values) - mean(baseline)))
+        })
+        .collect()
+}
+
+/// Extract node voltages from NgSpice `print` output, keyed as `v(node)`
+///
+/// Accepts `v(node) = value` as well as the bare `node = value` form NgSpice
+/// prints for operating point vectors. Branch currents such as `v1#branch`
+/// and other expressions are skipped.
+pub fn parse_node_voltages(lines: &[String]) -> HashMap<String, Vec<f64>> {
+    let mut voltages: HashMap<String, Vec<f64>> = HashMap::new();
+
+    for line in lines {
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        let name = name.trim().to_lowercase();
+        let name = if name.starts_with("v(") {
+            name
+        } else if name.is_empty() || name.contains(['#', '(', ' ', '\t']) {
+            continue;
+        } else {
+            format!("v({})", name)
+        };
+        if let Ok(value) = value.trim().parse::<f64>() {
+            voltages.entry(name).or_default().push(value);
+        }
+    }
+
+    voltages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    fn specs() -> Vec<ToleranceSpec> {
+        vec![
+            ToleranceSpec { component_id: "R1".to_string(), nominal: 1000.0, tolerance: 0.05 },
+            ToleranceSpec { component_id: "R2".to_string(), nominal: 2000.0, tolerance: 0.01 },
+        ]
+    }
+
+    #[test]
+    fn test_exhaustive_corners() {
+        let corners = exhaustive_corners(&specs());
+        assert_eq!(corners.len(), 4);
+        assert_close(corners[0]["R1"], 950.0);
+        assert_close(corners[0]["R2"], 1980.0);
+        assert_close(corners[3]["R1"], 1050.0);
+        assert_close(corners[3]["R2"], 2020.0);
+    }
+
+    #[test]
+    fn test_guided_corners_follow_sensitivity_sign() {
+        let mut sensitivities = HashMap::new();
+        sensitivities.insert(
+            "v(out)".to_string(),
+            HashMap::from([("R1".to_string(), -0.2), ("R2".to_string(), 0.1)]),
+        );
+
+        let corners = guided_corners(&specs(), &sensitivities);
+        assert_eq!(corners.len(), 2);
+        // Minimum: raise R1 (negative effect), lower R2
+        assert_close(corners[0]["R1"], 1050.0);
+        assert_close(corners[0]["R2"], 1980.0);
+        assert_close(corners[1]["R1"], 950.0);
+        assert_close(corners[1]["R2"], 2020.0);
+    }
+
+    #[test]
+    fn test_node_deltas() {
+        let nominal = HashMap::from([("v(out)".to_string(), vec![2.0, 4.0])]);
+        let perturbed = HashMap::from([
+            ("v(out)".to_string(), vec![2.5, 4.5]),
+            ("v(new)".to_string(), vec![1.0]),
+        ]);
+
+        let deltas = node_deltas(&nominal, &perturbed);
+        assert_eq!(deltas.len(), 1);
+        assert_close(deltas["v(out)"], 0.5);
+    }
+
+    #[test]
+    fn test_parse_bare_node_voltages() {
+        let lines: Vec<String> = [
+            "Doing analysis at TEMP = 27.000000",
+            "out = 2.500000e+00",
+            "v1#branch = -2.5e-03",
+            "v(in) = 5",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        let voltages = parse_node_voltages(&lines);
+        assert_eq!(voltages.len(), 2);
+        assert_close(voltages["v(out)"][0], 2.5);
+        assert_close(voltages["v(in)"][0], 5.0);
+    }
+
+    #[test]
+    fn test_record_tracks_extreme_corners() {
+        let corners = exhaustive_corners(&specs());
+        let mut results = WorstCaseResults::default();
+        for (corner, voltage) in corners.iter().zip([2.0, 3.5, 1.5, 2.5]) {
+            let lines = vec![format!("v(out) = {:e}", voltage)];
+            results.record(corner, &parse_node_voltages(&lines));
+        }
+
+        let bounds = results.bounds("out").unwrap();
+        assert_eq!(results.corners_evaluated, 4);
+        assert_eq!(bounds.min_voltage, 1.5);
+        assert_eq!(bounds.max_voltage, 3.5);
+        assert_eq!(&bounds.min_corner, &corners[2]);
+        assert_eq!(&bounds.max_corner, &corners[1]);
+    }
+
+    #[test]
+    fn test_tolerance_specs_resolve_values() {
+        let mut circuit = Circuit::new();
+        circuit.add_component(Component {
+            id: "R1".to_string(),
+            component_type: ComponentType::Resistor,
+            value: Some("4.7k".to_string()),
+            position: (0.0, 0.0),
+            purpose: None,
+        });
+
+        let tolerances = ComponentToleranceMap::from([("R1".to_string(), 0.01)]);
+        let specs = tolerance_specs(&circuit, &tolerances).unwrap();
+        assert_close(specs[0].nominal, 4700.0);
+
+        let high = apply_corner(&circuit, &exhaustive_corners(&specs)[1]);
+        let value = high.components[0].value.as_deref().unwrap();
+        assert_close(parse_spice_value(value).unwrap(), 4747.0);
+
+        let missing = ComponentToleranceMap::from([("R9".to_string(), 0.01)]);
+        assert!(tolerance_specs(&circuit, &missing).is_err());
+    }
+}
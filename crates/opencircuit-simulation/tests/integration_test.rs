@@ -14,6 +14,7 @@ async fn test_complete_simulation_flow() {
         component_type: ComponentType::VoltageSource,
         value: Some("5".to_string()),
         position: (0.0, 0.0),
+        purpose: None,
     });
     
     circuit.add_component(Component {
@@ -21,6 +22,7 @@ async fn test_complete_simulation_flow() {
         component_type: ComponentType::Resistor,
         value: Some("1k".to_string()),
         position: (0.0, 0.0),
+        purpose: None,
     });
     
     // Create simulation engine
@@ -29,7 +31,7 @@ async fn test_complete_simulation_flow() {
     match engine {
         Ok(mut sim_engine) => {
             // Run simulation
-            let results = sim_engine.simulate_circuit(&circuit).await;
+            let results = sim_engine.simulate_circuit(&circuit, CancellationToken::new()).await;
             
             match results {
                 Ok(sim_results) => {
@@ -69,6 +71,7 @@ async fn test_spice_parser_generation() {
         component_type: ComponentType::Resistor,
         value: Some("1k".to_string()),
         position: (0.0, 0.0),
+        purpose: None,
     });
     
     circuit.add_component(Component {
@@ -76,6 +79,7 @@ async fn test_spice_parser_generation() {
         component_type: ComponentType::Capacitor,
         value: Some("1u".to_string()),
         position: (0.0, 0.0),
+        purpose: None,
     });
     
     circuit.add_component(Component {
@@ -83,6 +87,7 @@ async fn test_spice_parser_generation() {
         component_type: ComponentType::Inductor,
         value: Some("1m".to_string()),
         position: (0.0, 0.0),
+        purpose: None,
     });
     
     let netlist = parser.generate_netlist(&circuit).unwrap();
@@ -152,4 +157,18 @@ async fn test_ngspice_health_check() {
             assert!(true);
         }
     }
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_simulation_errors_convert_to_open_circuit_error() {
+    async fn run(netlist: &str, analysis: &str) -> std::result::Result<SimulationResults, opencircuit_core::OpenCircuitError> {
+        let mut engine = SimulationEngine::new().await?;
+        Ok(engine.simulate_netlist(netlist, analysis).await?)
+    }
+
+    // Fails on the missing analysis directive, or earlier when NgSpice is not installed
+    match run("V1 1 0 5\nR1 1 0 1k\n.end", "op").await {
+        Err(opencircuit_core::OpenCircuitError::Circuit(message)) => assert!(!message.is_empty()),
+        other => panic!("expected a circuit error, got {:?}", other.map(|results| results.summary())),
+    }
+}
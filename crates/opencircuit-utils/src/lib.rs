@@ -6,8 +6,6 @@
 //! - Constants and enumerations
 //! - File I/O utilities
 
-use std::path::Path;
-
 /// Application constants
 pub mod constants {
     pub const APP_NAME: &str = "OpenCircuit";
@@ -15,6 +13,23 @@ pub mod constants {
     pub const DATABASE_FILE: &str = "opencircuit.db";
     pub const DEFAULT_WINDOW_WIDTH: f32 = 1200.0;
     pub const DEFAULT_WINDOW_HEIGHT: f32 = 800.0;
+
+    /// Component category names as stored in the component database
+    pub const COMPONENT_CATEGORIES: [&str; 13] = [
+        "Resistors",
+        "Capacitors",
+        "Inductors",
+        "Diodes",
+        "Transistors",
+        "Integrated Circuits",
+        "Connectors",
+        "Switches",
+        "Crystals",
+        "Sensors",
+        "Power",
+        "Mechanical",
+        "Sources",
+    ];
 }
 
 /// File format utilities
@@ -44,7 +59,146 @@ pub mod file_formats {
 
 /// Validation utilities
 pub mod validation {
+    use crate::constants::COMPONENT_CATEGORIES;
     use std::path::Path;
+    use thiserror::Error;
+
+    /// Maximum part number length accepted by `validate_component`
+    pub const MAX_PART_NUMBER_LENGTH: usize = 64;
+
+    /// Footprints recognised by `validate_component`, compared ignoring case, hyphens and spaces
+    pub const KNOWN_FOOTPRINTS: &[&str] = &[
+        "01005", "0201", "0402", "0603", "0805", "1206", "1210", "1812", "2010", "2512",
+        "SOD-123", "SOD-323", "SOD-523", "SMA", "SMB", "SMC",
+        "SOT-23", "SOT-23-5", "SOT-23-6", "SOT-89", "SOT-143", "SOT-223", "SOT-323", "SOT-363",
+        "TO-92", "TO-126", "TO-220", "TO-247", "TO-252", "TO-263", "DPAK", "D2PAK",
+    ];
+
+    /// Package families that take a pin count suffix, e.g. "SOIC-8" or "QFN32"
+    pub const FOOTPRINT_FAMILIES: &[&str] = &[
+        "DIP", "PDIP", "SOIC", "SOP", "SSOP", "TSSOP", "MSOP", "QFN", "DFN", "QFP", "LQFP",
+        "TQFP", "BGA", "SIP",
+    ];
+
+    /// A problem found while validating a component
+    #[derive(Error, Debug, Clone, PartialEq)]
+    pub enum ValidationIssue {
+        #[error("Part number is empty")]
+        EmptyPartNumber,
+        #[error("Part number is {0} characters long (max {MAX_PART_NUMBER_LENGTH})")]
+        PartNumberTooLong(usize),
+        #[error("Manufacturer is empty")]
+        EmptyManufacturer,
+        #[error("Unknown component category '{0}'")]
+        UnknownCategory(String),
+        #[error("Malformed specifications JSON: {0}")]
+        MalformedSpecifications(String),
+        #[error("Invalid datasheet URL '{url}': {reason}")]
+        InvalidDatasheetUrl { url: String, reason: String },
+        #[error("Unknown footprint '{0}'")]
+        UnknownFootprint(String),
+    }
+
+    /// Read access to the component fields checked by `validate_component`
+    pub trait ComponentData {
+        fn part_number(&self) -> &str;
+        fn manufacturer(&self) -> &str;
+        fn category(&self) -> &str;
+        fn specifications(&self) -> Option<&str>;
+        fn datasheet_url(&self) -> Option<&str>;
+        fn footprint(&self) -> Option<&str>;
+    }
+
+    /// Options for `validate_component_with`
+    #[derive(Debug, Clone, Default)]
+    pub struct ComponentValidationOptions {
+        /// Accept categories outside `COMPONENT_CATEGORIES`
+        pub allow_unknown_category: bool,
+    }
+
+    /// Validate a component record, returning every issue found
+    pub fn validate_component<C: ComponentData + ?Sized>(
+        record: &C,
+    ) -> Result<(), Vec<ValidationIssue>> {
+        validate_component_with(record, &ComponentValidationOptions::default())
+    }
+
+    /// Validate a component record with custom options
+    pub fn validate_component_with<C: ComponentData + ?Sized>(
+        record: &C,
+        options: &ComponentValidationOptions,
+    ) -> Result<(), Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+
+        let part_number = record.part_number().trim();
+        if part_number.is_empty() {
+            issues.push(ValidationIssue::EmptyPartNumber);
+        } else if part_number.chars().count() > MAX_PART_NUMBER_LENGTH {
+            issues.push(ValidationIssue::PartNumberTooLong(part_number.chars().count()));
+        }
+
+        if record.manufacturer().trim().is_empty() {
+            issues.push(ValidationIssue::EmptyManufacturer);
+        }
+
+        let category = record.category();
+        if !options.allow_unknown_category && !COMPONENT_CATEGORIES.contains(&category) {
+            issues.push(ValidationIssue::UnknownCategory(category.to_string()));
+        }
+
+        if let Some(specifications) = record.specifications() {
+            if let Err(e) = serde_json::from_str::<serde_json::Value>(specifications) {
+                issues.push(ValidationIssue::MalformedSpecifications(e.to_string()));
+            }
+        }
+
+        if let Some(datasheet_url) = record.datasheet_url() {
+            let reason = match url::Url::parse(datasheet_url) {
+                Ok(url) if url.scheme() == "https" => None,
+                Ok(url) => Some(format!("expected https, got {}", url.scheme())),
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(reason) = reason {
+                issues.push(ValidationIssue::InvalidDatasheetUrl {
+                    url: datasheet_url.to_string(),
+                    reason,
+                });
+            }
+        }
+
+        if let Some(footprint) = record.footprint() {
+            if !is_known_footprint(footprint) {
+                issues.push(ValidationIssue::UnknownFootprint(footprint.to_string()));
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Check a footprint against `KNOWN_FOOTPRINTS` and `FOOTPRINT_FAMILIES`
+    pub fn is_known_footprint(footprint: &str) -> bool {
+        let normalize = |s: &str| -> String {
+            s.chars()
+                .filter(|c| !matches!(c, '-' | '_' | ' '))
+                .collect::<String>()
+                .to_uppercase()
+        };
+        let footprint = normalize(footprint);
+
+        if KNOWN_FOOTPRINTS.iter().any(|known| normalize(known) == footprint) {
+            return true;
+        }
+
+        FOOTPRINT_FAMILIES.iter().any(|family| {
+            footprint
+                .strip_prefix(family)
+                .is_some_and(|pins| !pins.is_empty() && pins.chars().all(|c| c.is_ascii_digit()))
+        })
+    }
     
     /// Validate component part number format
     pub fn validate_part_number(part_number: &str) -> bool {
@@ -71,6 +225,27 @@ pub mod validation {
 
 /// Math utilities for circuit calculations
 pub mod math {
+    use thiserror::Error;
+
+    /// Errors from circuit math helpers
+    #[derive(Error, Debug, Clone, PartialEq)]
+    pub enum MathError {
+        #[error("Invalid input: {0}")]
+        InvalidInput(String),
+    }
+
+    /// Ensure a value is finite and strictly positive
+    fn require_positive(name: &str, value: f64) -> Result<f64, MathError> {
+        if value.is_finite() && value > 0.0 {
+            Ok(value)
+        } else {
+            Err(MathError::InvalidInput(format!(
+                "{} must be a positive finite number, got {}",
+                name, value
+            )))
+        }
+    }
+
     /// Calculate parallel resistance
     pub fn parallel_resistance(r1: f64, r2: f64) -> f64 {
         if r1 == 0.0 || r2 == 0.0 {
@@ -94,10 +269,371 @@ pub mod math {
     pub fn rad_to_deg(radians: f64) -> f64 {
         radians * 180.0 / std::f64::consts::PI
     }
+
+    /// Reactance and resonance formulas for RLC circuits
+    pub mod impedance {
+        use super::{require_positive, MathError};
+        use std::f64::consts::PI;
+
+        /// Capacitive reactance in ohms, Xc = 1/(2πfC)
+        pub fn capacitive_reactance(f: f64, c: f64) -> Result<f64, MathError> {
+            let f = require_positive("frequency", f)?;
+            let c = require_positive("capacitance", c)?;
+            Ok(1.0 / (2.0 * PI * f * c))
+        }
+
+        /// Inductive reactance in ohms, Xl = 2πfL
+        pub fn inductive_reactance(f: f64, l: f64) -> Result<f64, MathError> {
+            let f = require_positive("frequency", f)?;
+            let l = require_positive("inductance", l)?;
+            Ok(2.0 * PI * f * l)
+        }
+
+        /// Resonant frequency in hertz, f = 1/(2π√(LC))
+        pub fn resonant_frequency(l: f64, c: f64) -> Result<f64, MathError> {
+            let l = require_positive("inductance", l)?;
+            let c = require_positive("capacitance", c)?;
+            Ok(1.0 / (2.0 * PI * (l * c).sqrt()))
+        }
+
+        /// Quality factor of a series RLC circuit, Q = (1/R)√(L/C)
+        pub fn q_factor(r: f64, l: f64, c: f64) -> Result<f64, MathError> {
+            let r = require_positive("resistance", r)?;
+            let l = require_positive("inductance", l)?;
+            let c = require_positive("capacitance", c)?;
+            Ok((l / c).sqrt() / r)
+        }
+
+        /// Cutoff frequency in hertz of a second-order LC filter
+        pub fn lc_filter_cutoff(l: f64, c: f64) -> Result<f64, MathError> {
+            resonant_frequency(l, c)
+        }
+    }
+
+    /// Resistive voltage divider helpers (R1 on top, R2 to ground)
+    pub mod voltage_divider {
+        /// Pick (R1, R2) summing to `r_total` so that the divider outputs `v_out`
+        ///
+        /// The ratio is clamped to [0, 1]; a zero `v_in` yields (r_total, 0).
+        pub fn design(v_in: f64, v_out: f64, r_total: f64) -> (f64, f64) {
+            let ratio = if v_in == 0.0 {
+                0.0
+            } else {
+                (v_out / v_in).clamp(0.0, 1.0)
+            };
+            let r2 = r_total * ratio;
+            (r_total - r2, r2)
+        }
+
+        /// Unloaded divider output voltage
+        pub fn output_voltage(v_in: f64, r1: f64, r2: f64) -> f64 {
+            if r1 + r2 == 0.0 {
+                0.0
+            } else {
+                v_in * r2 / (r1 + r2)
+            }
+        }
+
+        /// Divider output voltage with `r_load` in parallel with R2
+        pub fn loading_effect(v_in: f64, r1: f64, r2: f64, r_load: f64) -> f64 {
+            let r_bottom = if r_load.is_infinite() {
+                r2
+            } else {
+                super::parallel_resistance(r2, r_load)
+            };
+            output_voltage(v_in, r1, r_bottom)
+        }
+    }
+
+    /// Resistive current divider helpers for two parallel branches
+    pub mod current_divider {
+        /// Pick (R1, R2) summing to `r_total` so that `i_branch` of `i_in` flows through R1
+        ///
+        /// The ratio is clamped to [0, 1]; a zero `i_in` yields (r_total, 0).
+        pub fn design(i_in: f64, i_branch: f64, r_total: f64) -> (f64, f64) {
+            let ratio = if i_in == 0.0 {
+                0.0
+            } else {
+                (i_branch / i_in).clamp(0.0, 1.0)
+            };
+            // I1 = I·R2/(R1+R2), so R2 carries the requested share of the total
+            let r2 = r_total * ratio;
+            (r_total - r2, r2)
+        }
+
+        /// Current through `r_branch` when `i_in` splits between it and `r_other`
+        pub fn branch_current(i_in: f64, r_branch: f64, r_other: f64) -> f64 {
+            if r_other.is_infinite() {
+                return if r_branch.is_infinite() { 0.0 } else { i_in };
+            }
+            if r_branch + r_other == 0.0 {
+                // Two shorts in parallel share the current equally
+                return i_in / 2.0;
+            }
+            i_in * r_other / (r_branch + r_other)
+        }
+    }
+}
+
+/// SI-prefixed electrical value parsing and formatting
+pub mod units {
+    use thiserror::Error;
+
+    /// Errors from parsing SI values
+    #[derive(Error, Debug, Clone, PartialEq)]
+    pub enum UnitError {
+        #[error("Empty value")]
+        Empty,
+        #[error("Invalid number in '{0}'")]
+        InvalidNumber(String),
+        #[error("Unknown suffix '{suffix}' in '{value}'")]
+        UnknownSuffix { value: String, suffix: String },
+    }
+
+    /// SI prefixes used when formatting, smallest to largest
+    const FORMAT_PREFIXES: [(i32, &str); 10] = [
+        (-15, "f"),
+        (-12, "p"),
+        (-9, "n"),
+        (-6, "µ"),
+        (-3, "m"),
+        (0, ""),
+        (3, "k"),
+        (6, "M"),
+        (9, "G"),
+        (12, "T"),
+    ];
+
+    /// Map a prefix character to its power of ten
+    ///
+    /// `M` is mega and `m` is milli; `R` marks the decimal point in RKM codes like "4R7".
+    fn prefix_exponent(c: char) -> Option<i32> {
+        match c {
+            'T' => Some(12),
+            'G' | 'g' => Some(9),
+            'M' => Some(6),
+            'k' | 'K' => Some(3),
+            'R' | 'r' => Some(0),
+            'm' => Some(-3),
+            'u' | 'U' | 'µ' | 'μ' => Some(-6),
+            'n' | 'N' => Some(-9),
+            'p' | 'P' => Some(-12),
+            'f' => Some(-15),
+            _ => None,
+        }
+    }
+
+    /// Scale `value` by 10^exponent, dividing for negative exponents to stay exact
+    fn scale(value: f64, exponent: i32) -> f64 {
+        if exponent >= 0 {
+            value * 10f64.powi(exponent)
+        } else {
+            value / 10f64.powi(-exponent)
+        }
+    }
+
+    /// Split off the leading numeric part, including any exponent
+    fn split_number(s: &str) -> (&str, &str) {
+        let bytes = s.as_bytes();
+        let mut end = 0;
+        if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+            end += 1;
+        }
+        while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+            end += 1;
+        }
+        // Scientific notation: only consume 'e' when digits follow
+        if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+            let mut exp_end = end + 1;
+            if exp_end < bytes.len() && (bytes[exp_end] == b'+' || bytes[exp_end] == b'-') {
+                exp_end += 1;
+            }
+            if exp_end < bytes.len() && bytes[exp_end].is_ascii_digit() {
+                while exp_end < bytes.len() && bytes[exp_end].is_ascii_digit() {
+                    exp_end += 1;
+                }
+                end = exp_end;
+            }
+        }
+        s.split_at(end)
+    }
+
+    /// Parse a value with an optional SI prefix and unit, e.g. "4.7k", "100nF", "1MEG", "4k7"
+    pub fn parse_si_value(s: &str) -> Result<f64, UnitError> {
+        let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.is_empty() {
+            return Err(UnitError::Empty);
+        }
+
+        let (number, rest) = split_number(&compact);
+        if number.is_empty() || number == "+" || number == "-" {
+            return Err(UnitError::InvalidNumber(s.to_string()));
+        }
+
+        let (exponent, rest) = if rest.get(..3).is_some_and(|p| p.eq_ignore_ascii_case("meg")) {
+            (6, &rest[3..])
+        } else {
+            match rest.chars().next().and_then(|c| prefix_exponent(c).map(|e| (c, e))) {
+                Some((c, e)) => (e, &rest[c.len_utf8()..]),
+                None => (0, rest),
+            }
+        };
+
+        // RKM notation puts the fractional digits after the prefix, e.g. "4k7"
+        let fraction_len = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        let (fraction, unit) = rest.split_at(fraction_len);
+        let mantissa = if fraction.is_empty() {
+            number.to_string()
+        } else if number.contains(['.', 'e', 'E']) {
+            return Err(UnitError::InvalidNumber(s.to_string()));
+        } else {
+            format!("{}.{}", number, fraction)
+        };
+
+        if !unit.chars().all(|c| c.is_alphabetic() || c == '°' || c == '%') {
+            return Err(UnitError::UnknownSuffix {
+                value: s.to_string(),
+                suffix: unit.to_string(),
+            });
+        }
+
+        let value: f64 = mantissa
+            .parse()
+            .map_err(|_| UnitError::InvalidNumber(s.to_string()))?;
+        Ok(scale(value, exponent))
+    }
+
+    /// Parse a value using SPICE rules: case-insensitive, `M` is milli and `MEG` is mega
+    pub fn parse_spice_value(s: &str) -> Result<f64, UnitError> {
+        let lower = s.trim().to_lowercase();
+        let (number, rest) = split_number(&lower);
+        let rest = match rest.chars().next() {
+            Some('t') => format!("T{}", &rest[1..]),
+            _ => rest.to_string(),
+        };
+        parse_si_value(&format!("{}{}", number, rest))
+    }
+
+    /// Format a value with the closest SI prefix, e.g. 0.001 -> "1m", 4700.0 -> "4.7k"
+    pub fn format_si_value(v: f64, unit: &str) -> String {
+        if v == 0.0 || !v.is_finite() {
+            return format!("{}{}", v, unit);
+        }
+
+        let min_index = 0;
+        let max_index = FORMAT_PREFIXES.len() - 1;
+        let raw_exponent = (v.abs().log10() / 3.0).floor() as i32 * 3;
+        let mut index = FORMAT_PREFIXES
+            .iter()
+            .position(|(exponent, _)| *exponent == raw_exponent)
+            .unwrap_or(if raw_exponent < 0 { min_index } else { max_index });
+
+        let round = |m: f64| (m * 1e6).round() / 1e6;
+        let mut mantissa = round(scale(v, -FORMAT_PREFIXES[index].0));
+        // Correct for log10 rounding at prefix boundaries
+        if mantissa.abs() >= 1000.0 && index < max_index {
+            index += 1;
+            mantissa = round(scale(v, -FORMAT_PREFIXES[index].0));
+        } else if mantissa.abs() < 1.0 && index > min_index {
+            index -= 1;
+            mantissa = round(scale(v, -FORMAT_PREFIXES[index].0));
+        }
+
+        format!("{}{}{}", mantissa, FORMAT_PREFIXES[index].1, unit)
+    }
 }
 
 /// String utilities
 pub mod string_utils {
+    /// Packaging suffixes appended to otherwise identical part numbers
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PackageSuffix {
+        /// `N` - plastic DIP
+        Dip,
+        /// `P` - PDIP
+        Pdip,
+        /// `D` - SOIC
+        Soic,
+        /// `DR` - SOIC, tape and reel
+        SoicTapeReel,
+        /// `T` - TSOP / TO-220
+        Tsop,
+        /// `PW` - TSSOP
+        Tssop,
+        /// `PWR` - TSSOP, tape and reel
+        TssopTapeReel,
+    }
+
+    /// Known suffixes, longest first so e.g. `PWR` wins over `R`-less `PW`
+    pub const PACKAGE_SUFFIXES: [(&str, PackageSuffix); 7] = [
+        ("PWR", PackageSuffix::TssopTapeReel),
+        ("DR", PackageSuffix::SoicTapeReel),
+        ("PW", PackageSuffix::Tssop),
+        ("N", PackageSuffix::Dip),
+        ("P", PackageSuffix::Pdip),
+        ("D", PackageSuffix::Soic),
+        ("T", PackageSuffix::Tsop),
+    ];
+
+    /// Part number prefixes of the op-amp, timer and logic families whose trailing
+    /// letters are package codes; elsewhere they usually name a different part,
+    /// e.g. IRF540N or MJE3055T
+    pub const PACKAGE_SUFFIX_FAMILIES: [&str; 12] =
+        ["LM", "LF", "NE", "SA", "SE", "UA", "MC", "TL", "SN", "CD", "OP", "RC"];
+
+    impl PackageSuffix {
+        pub fn as_str(&self) -> &'static str {
+            PACKAGE_SUFFIXES
+                .iter()
+                .find(|(_, suffix)| suffix == self)
+                .map(|(code, _)| *code)
+                .unwrap_or_default()
+        }
+
+        /// Detect a packaging suffix on an already uppercased part number
+        ///
+        /// Only parts from [`PACKAGE_SUFFIX_FAMILIES`] count, with the suffix following
+        /// a digit or a C/I temperature grade (e.g. TL072CP), so "1N4148", "IRF540N"
+        /// or "LM7805CT" are left alone.
+        pub fn detect(part_number: &str) -> Option<PackageSuffix> {
+            if !PACKAGE_SUFFIX_FAMILIES.iter().any(|family| part_number.starts_with(family)) {
+                return None;
+            }
+            let ends_with_digit = |s: &str| s.ends_with(|c: char| c.is_ascii_digit());
+            PACKAGE_SUFFIXES.iter().find_map(|(code, suffix)| {
+                let stem = part_number.strip_suffix(code)?;
+                // TO-220 parts keep the grade as part of the ordering code, e.g. LM7805CT
+                let follows_grade = *suffix != PackageSuffix::Tsop
+                    && stem.strip_suffix(['C', 'I']).is_some_and(ends_with_digit);
+                (ends_with_digit(stem) || follows_grade).then_some(*suffix)
+            })
+        }
+    }
+
+    /// Normalize a part number for matching across suppliers
+    ///
+    /// Uppercases, drops ordering options after '/', removes spaces, hyphens and
+    /// underscores, and strips a trailing packaging suffix along with the C/I
+    /// temperature grade in front of it, so TL072CP and TL072P both give TL072.
+    pub fn normalize_part_number(s: &str) -> String {
+        let base = s.split('/').next().unwrap_or_default();
+        let mut normalized: String = base
+            .chars()
+            .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '_'))
+            .collect::<String>()
+            .to_uppercase();
+
+        if let Some(suffix) = PackageSuffix::detect(&normalized) {
+            normalized.truncate(normalized.len() - suffix.as_str().len());
+            if let Some(stem) = normalized.strip_suffix(['C', 'I']) {
+                if stem.ends_with(|c: char| c.is_ascii_digit()) {
+                    normalized.pop();
+                }
+            }
+        }
+        normalized
+    }
+
     /// Sanitize filename for cross-platform compatibility
     pub fn sanitize_filename(filename: &str) -> String {
         filename
@@ -139,6 +675,171 @@ mod tests {
         assert_eq!(math::series_resistance(&[]), 0.0);
     }
     
+    #[test]
+    fn test_impedance_formulas() {
+        use math::impedance::*;
+
+        // 1kHz across 1µF is ~159.15Ω
+        assert!((capacitive_reactance(1e3, 1e-6).unwrap() - 159.154_943).abs() < 1e-3);
+        // 1kHz across 10mH is ~62.83Ω
+        assert!((inductive_reactance(1e3, 10e-3).unwrap() - 62.831_853).abs() < 1e-3);
+        // 10mH with 1µF resonates at ~1591.5Hz
+        assert!((resonant_frequency(10e-3, 1e-6).unwrap() - 1_591.549_431).abs() < 1e-3);
+        assert!((q_factor(10.0, 10e-3, 1e-6).unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(lc_filter_cutoff(10e-3, 1e-6), resonant_frequency(10e-3, 1e-6));
+    }
+
+    #[test]
+    fn test_impedance_rejects_invalid_input() {
+        use math::impedance::*;
+
+        assert!(matches!(capacitive_reactance(1e3, -1e-6), Err(math::MathError::InvalidInput(_))));
+        assert!(capacitive_reactance(0.0, 1e-6).is_err());
+        assert!(inductive_reactance(1e3, f64::NAN).is_err());
+        assert!(resonant_frequency(-1e-3, 1e-6).is_err());
+        assert!(q_factor(0.0, 1e-3, 1e-6).is_err());
+        assert!(lc_filter_cutoff(1e-3, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_voltage_divider() {
+        use math::voltage_divider::*;
+
+        let (r1, r2) = design(12.0, 5.0, 12_000.0);
+        assert!((r1 - 7_000.0).abs() < 1e-9);
+        assert!((r2 - 5_000.0).abs() < 1e-9);
+        assert!((output_voltage(12.0, r1, r2) - 5.0).abs() < 1e-9);
+
+        // A 10k load on a 10k/10k divider drops the output to a third
+        assert!((loading_effect(9.0, 10e3, 10e3, 10e3) - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_voltage_divider_degenerate_cases() {
+        use math::voltage_divider::*;
+
+        assert_eq!(output_voltage(12.0, 1e3, 0.0), 0.0);
+        assert_eq!(output_voltage(12.0, 0.0, 0.0), 0.0);
+        assert_eq!(output_voltage(12.0, 0.0, 1e3), 12.0);
+        assert_eq!(loading_effect(12.0, 1e3, 1e3, f64::INFINITY), 6.0);
+        assert_eq!(loading_effect(12.0, 1e3, 1e3, 0.0), 0.0);
+        assert_eq!(design(0.0, 5.0, 1e3), (1e3, 0.0));
+        assert_eq!(design(5.0, 12.0, 1e3), (0.0, 1e3));
+    }
+
+    #[test]
+    fn test_current_divider() {
+        use math::current_divider::*;
+
+        assert!((branch_current(3.0, 1e3, 2e3) - 2.0).abs() < 1e-9);
+        let (r1, r2) = design(3.0, 2.0, 3e3);
+        assert!((branch_current(3.0, r1, r2) - 2.0).abs() < 1e-9);
+
+        assert_eq!(branch_current(1.0, 0.0, 1e3), 1.0);
+        assert_eq!(branch_current(1.0, 0.0, 0.0), 0.5);
+        assert_eq!(branch_current(1.0, 1e3, f64::INFINITY), 1.0);
+        assert_eq!(branch_current(1.0, f64::INFINITY, 1e3), 0.0);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_reactances_match_at_resonance(l in 1e-9f64..1.0, c in 1e-12f64..1e-3) {
+            use math::impedance::*;
+
+            let f0 = resonant_frequency(l, c).unwrap();
+            let xl = inductive_reactance(f0, l).unwrap();
+            let xc = capacitive_reactance(f0, c).unwrap();
+            proptest::prop_assert!((xl - xc).abs() <= xl * 1e-9);
+        }
+
+        #[test]
+        fn prop_reactance_product_is_l_over_c(
+            f in 1.0f64..1e9,
+            l in 1e-9f64..1.0,
+            c in 1e-12f64..1e-3,
+        ) {
+            use math::impedance::*;
+
+            // Xl·Xc = (2πfL)/(2πfC) = L/C, independent of frequency
+            let product = inductive_reactance(f, l).unwrap() * capacitive_reactance(f, c).unwrap();
+            proptest::prop_assert!((product - l / c).abs() <= (l / c) * 1e-9);
+        }
+
+        #[test]
+        fn prop_outputs_are_finite_and_positive(
+            r in 1e-3f64..1e6,
+            l in 1e-9f64..1.0,
+            c in 1e-12f64..1e-3,
+        ) {
+            use math::impedance::*;
+
+            let q = q_factor(r, l, c).unwrap();
+            let f0 = lc_filter_cutoff(l, c).unwrap();
+            proptest::prop_assert!(q.is_finite() && q > 0.0);
+            proptest::prop_assert!(f0.is_finite() && f0 > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_parse_si_value() {
+        use units::*;
+
+        assert_eq!(parse_si_value("1k"), Ok(1e3));
+        assert_eq!(parse_si_value("4.7M"), Ok(4.7e6));
+        assert_eq!(parse_si_value("100n"), Ok(100e-9));
+        assert_eq!(parse_si_value("10u"), Ok(10e-6));
+        assert_eq!(parse_si_value("0.1m"), Ok(0.1e-3));
+        assert_eq!(parse_si_value("1MEG"), Ok(1e6));
+        assert_eq!(parse_si_value("100nF"), Ok(100e-9));
+        assert_eq!(parse_si_value("4.7 kΩ"), Ok(4.7e3));
+        assert_eq!(parse_si_value("10µH"), Ok(10e-6));
+        assert_eq!(parse_si_value("4k7"), Ok(4.7e3));
+        assert_eq!(parse_si_value("4R7"), Ok(4.7));
+        assert_eq!(parse_si_value("1e3"), Ok(1e3));
+        assert_eq!(parse_si_value("-5V"), Ok(-5.0));
+        assert_eq!(parse_si_value("12"), Ok(12.0));
+
+        assert_eq!(parse_si_value(""), Err(UnitError::Empty));
+        assert!(matches!(parse_si_value("k"), Err(UnitError::InvalidNumber(_))));
+        assert!(matches!(parse_si_value("10k#"), Err(UnitError::UnknownSuffix { .. })));
+    }
+
+    #[test]
+    fn test_parse_spice_value() {
+        use units::*;
+
+        assert!((parse_spice_value("2.52n").unwrap() - 2.52e-9).abs() < 1e-21);
+        assert_eq!(parse_spice_value("1M"), Ok(1e-3));
+        assert_eq!(parse_spice_value("1MEG"), Ok(1e6));
+        assert_eq!(parse_spice_value("1e-14"), Ok(1e-14));
+        assert_eq!(parse_spice_value("2T"), Ok(2e12));
+        assert_eq!(parse_spice_value("10K"), Ok(1e4));
+    }
+
+    #[test]
+    fn test_format_si_value() {
+        use units::*;
+
+        assert_eq!(format_si_value(0.001, ""), "1m");
+        assert_eq!(format_si_value(1000.0, ""), "1k");
+        assert_eq!(format_si_value(4700.0, "Ω"), "4.7kΩ");
+        assert_eq!(format_si_value(100e-9, "F"), "100nF");
+        assert_eq!(format_si_value(2.2e6, "Ω"), "2.2MΩ");
+        assert_eq!(format_si_value(-3.3, "V"), "-3.3V");
+        assert_eq!(format_si_value(0.0, "A"), "0A");
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_si_value_round_trip(mantissa in 1.0f64..10.0, exponent in -15i32..12, negative: bool) {
+            use units::*;
+
+            let v = mantissa * 10f64.powi(exponent) * if negative { -1.0 } else { 1.0 };
+            let parsed = parse_si_value(&format_si_value(v, "")).unwrap();
+            proptest::prop_assert!((parsed - v).abs() <= v.abs() * 1e-6);
+        }
+    }
+
     #[test]
     fn test_angle_conversion() {
         assert!((math::deg_to_rad(180.0) - std::f64::consts::PI).abs() < 1e-10);
@@ -154,6 +855,88 @@ mod tests {
         assert!(!validation::validate_email("invalid-email"));
     }
     
+    struct TestComponent {
+        part_number: String,
+        manufacturer: String,
+        category: String,
+        specifications: Option<String>,
+        datasheet_url: Option<String>,
+        footprint: Option<String>,
+    }
+
+    impl validation::ComponentData for TestComponent {
+        fn part_number(&self) -> &str {
+            &self.part_number
+        }
+        fn manufacturer(&self) -> &str {
+            &self.manufacturer
+        }
+        fn category(&self) -> &str {
+            &self.category
+        }
+        fn specifications(&self) -> Option<&str> {
+            self.specifications.as_deref()
+        }
+        fn datasheet_url(&self) -> Option<&str> {
+            self.datasheet_url.as_deref()
+        }
+        fn footprint(&self) -> Option<&str> {
+            self.footprint.as_deref()
+        }
+    }
+
+    fn valid_test_component() -> TestComponent {
+        TestComponent {
+            part_number: "RC0603FR-0710KL".to_string(),
+            manufacturer: "Yageo".to_string(),
+            category: "Resistors".to_string(),
+            specifications: Some(r#"{"resistance":"10k"}"#.to_string()),
+            datasheet_url: Some("https://www.yageo.com/datasheet.pdf".to_string()),
+            footprint: Some("0603".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_validate_component() {
+        use validation::*;
+
+        assert!(validate_component(&valid_test_component()).is_ok());
+
+        let mut component = valid_test_component();
+        component.footprint = Some("sot23".to_string());
+        assert!(validate_component(&component).is_ok());
+        component.footprint = Some("SOIC-8".to_string());
+        assert!(validate_component(&component).is_ok());
+    }
+
+    #[test]
+    fn test_validate_component_reports_all_issues() {
+        use validation::*;
+
+        let component = TestComponent {
+            part_number: "X".repeat(65),
+            manufacturer: " ".to_string(),
+            category: "Unknown".to_string(),
+            specifications: Some("{not json".to_string()),
+            datasheet_url: Some("http://example.com/ds.pdf".to_string()),
+            footprint: Some("BLOB-1".to_string()),
+        };
+
+        let issues = validate_component(&component).unwrap_err();
+        assert_eq!(issues.len(), 6);
+        assert_eq!(issues[0], ValidationIssue::PartNumberTooLong(65));
+        assert_eq!(issues[1], ValidationIssue::EmptyManufacturer);
+        assert_eq!(issues[2], ValidationIssue::UnknownCategory("Unknown".to_string()));
+        assert!(matches!(issues[3], ValidationIssue::MalformedSpecifications(_)));
+        assert!(matches!(issues[4], ValidationIssue::InvalidDatasheetUrl { .. }));
+        assert_eq!(issues[5], ValidationIssue::UnknownFootprint("BLOB-1".to_string()));
+
+        let options = ComponentValidationOptions { allow_unknown_category: true };
+        let mut component = valid_test_component();
+        component.category = "Unknown".to_string();
+        assert!(validate_component_with(&component, &options).is_ok());
+    }
+
     #[test]
     fn test_string_utils() {
         assert_eq!(string_utils::sanitize_filename("file<>name"), "file__name");
@@ -161,6 +944,53 @@ mod tests {
         assert_eq!(string_utils::truncate_with_ellipsis("short", 10), "short");
     }
     
+    #[test]
+    fn test_normalize_part_number_collisions() {
+        use string_utils::normalize_part_number;
+
+        let groups: [(&str, &[&str]); 4] = [
+            (
+                "LM358",
+                &[
+                    "LM358N", "LM-358-N", "lm358n", "LM358 N", "LM358_N", "LM358D", "LM358DR",
+                    "LM358P", "LM358PW", "LM358PWR", "LM358DR/NOPB", "LM358",
+                ],
+            ),
+            ("NE555", &["NE555P", "ne555p", "NE-555-P", "NE555D", "NE555DR"]),
+            ("SN74HC00", &["SN74HC00N", "SN74HC00D", "sn74hc00-n", "SN74HC00PWR"]),
+            ("TL072", &["TL072P", "TL072CP", "TL072D", "TL072CD", "tl072-p", "TL072 DR", "TL072IDR"]),
+        ];
+
+        for (expected, variants) in groups {
+            for variant in variants {
+                assert_eq!(normalize_part_number(variant), expected, "variant {}", variant);
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize_part_number_keeps_distinct_parts() {
+        use string_utils::{normalize_part_number, PackageSuffix};
+
+        assert_eq!(normalize_part_number("1N4148"), "1N4148");
+        assert_eq!(normalize_part_number("2N2222A"), "2N2222A");
+        assert_eq!(normalize_part_number("LM7805CT"), "LM7805CT");
+        assert_eq!(normalize_part_number("TL072CP"), normalize_part_number("TL072P"));
+        assert_ne!(normalize_part_number("LM358N"), normalize_part_number("LM324N"));
+
+        // Trailing letters outside the known families are part of the part number
+        assert_eq!(normalize_part_number("IRF540N"), "IRF540N");
+        assert_eq!(normalize_part_number("IRLZ44N"), "IRLZ44N");
+        assert_eq!(normalize_part_number("MJE3055T"), "MJE3055T");
+        assert_eq!(normalize_part_number("2N2222A-T"), "2N2222AT");
+        assert_ne!(normalize_part_number("IRF540N"), normalize_part_number("IRF540"));
+
+        assert_eq!(PackageSuffix::detect("LM358DR"), Some(PackageSuffix::SoicTapeReel));
+        assert_eq!(PackageSuffix::detect("1N4148"), None);
+        assert_eq!(PackageSuffix::detect("TIP120T"), None);
+        assert_eq!(PackageSuffix::TssopTapeReel.as_str(), "PWR");
+    }
+
     #[test]
     fn test_export_format() {
         assert_eq!(file_formats::ExportFormat::KiCad.extension(), ".kicad_pcb");
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use tracing::{info, warn};
 
+pub mod report;
+
 // Re-export the crates for easy access
 pub use opencircuit_ai as ai;
 pub use opencircuit_circuit as circuit;
@@ -8,15 +10,19 @@ pub use opencircuit_core as core;
 pub use opencircuit_database as database;
 pub use opencircuit_gui as gui;
 pub use opencircuit_pcb as pcb;
+pub use opencircuit_simulation as simulation;
 pub use opencircuit_utils as utils;
+pub use opencircuit_gui::{autosave, project};
 
 // Re-export commonly used types
-pub use opencircuit_core::{OpenCircuitError, AppConfig, Project, Position, Size, Rect};
+pub use opencircuit_core::{OpenCircuitError, AppConfig, AutoSaveConfig, Project, Position, Size, Rect};
 pub use opencircuit_ai::{AiService, AiConfig, AiResponse, AiModel};
 pub use opencircuit_circuit::{Circuit, Component, ComponentType};
 pub use opencircuit_database::{Database, ComponentRecord};
 pub use opencircuit_gui::{OpenCircuitApp, AppState};
 pub use opencircuit_pcb::{PcbDesign, ComponentPlacement, Trace};
+pub use autosave::AutoSaveManager;
+pub use project::ProjectFile;
 
 /// OpenCircuit library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,514 @@
+//! PDF design review reports
+//!
+//! A report covers the project details, a schematic summary, the bill of
+//! materials, DRC results, simulation plots and the AI health report, laid out
+//! on A4 pages in the built-in Helvetica fonts. Plots are drawn as SVG and
+//! embedded in the PDF; their labels are written as PDF text underneath, since
+//! SVG text needs fonts the renderer may not have.
+
+use anyhow::{anyhow, Context, Result};
+use opencircuit_circuit::Circuit;
+use opencircuit_simulation::{AnalysisData, SimulationResults};
+use opencircuit_utils::units::format_si_value;
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Svg, SvgTransform};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use tracing::warn;
+
+use crate::project::ProjectFile;
+
+/// A4 page size (mm)
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const LINE_HEIGHT: f32 = 5.0;
+/// Characters per line of 10 pt body text
+const WRAP_WIDTH: usize = 95;
+
+/// Plot size in SVG pixels, placed at [`PLOT_DPI`]
+const PLOT_WIDTH: f64 = 600.0;
+const PLOT_HEIGHT: f64 = 300.0;
+const PLOT_DPI: f32 = 96.0;
+const PLOT_COLORS: [&str; 6] = ["#1f77b4", "#d62728", "#2ca02c", "#ff7f0e", "#9467bd", "#17becf"];
+
+/// Components sharing a type and value, as one BOM line
+#[derive(Debug, Clone, PartialEq)]
+pub struct BomLine {
+    pub references: Vec<String>,
+    pub value: String,
+    pub component_type: String,
+}
+
+/// Number of components of each type
+pub fn component_counts(circuit: &Circuit) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for component in &circuit.components {
+        *counts.entry(format!("{:?}", component.component_type)).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Bill of materials grouped by component type and value
+pub fn bom_lines(circuit: &Circuit) -> Vec<BomLine> {
+    let mut groups: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for component in &circuit.components {
+        let key = (format!("{:?}", component.component_type), component.value.clone().unwrap_or_default());
+        groups.entry(key).or_default().push(component.id.clone());
+    }
+    groups
+        .into_iter()
+        .map(|((component_type, value), mut references)| {
+            references.sort();
+            BomLine { references, value, component_type }
+        })
+        .collect()
+}
+
+/// One plotted curve of a simulation result
+struct PlotSeries {
+    name: String,
+    values: Vec<f64>,
+}
+
+/// SVG plot of a transient or AC result, with the x axis label
+///
+/// Transient results plot node voltages against time; AC results plot the
+/// node voltage magnitude in dB against frequency on a log axis.
+pub fn simulation_plot_svg(results: &SimulationResults) -> Option<(String, String)> {
+    match &results.data {
+        AnalysisData::Transient(transient) => {
+            let series = sorted_series(transient.voltage_waveforms.iter().map(|(node, values)| (node, values.clone())));
+            plot_svg(&transient.time_points, &series, false).map(|svg| (svg, "Time (s)".to_string()))
+        }
+        AnalysisData::AC(ac) => {
+            let series = sorted_series(ac.voltage_responses.iter().map(|(node, values)| {
+                (node, values.iter().map(|value| 20.0 * value.magnitude().log10()).collect())
+            }));
+            plot_svg(&ac.frequencies, &series, true).map(|svg| (svg, "Frequency (Hz), magnitude in dB".to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn sorted_series<'a>(series: impl Iterator<Item = (&'a String, Vec<f64>)>) -> Vec<PlotSeries> {
+    let mut series: Vec<PlotSeries> = series.map(|(name, values)| PlotSeries { name: name.clone(), values }).collect();
+    series.sort_by(|a, b| a.name.cmp(&b.name));
+    series.truncate(PLOT_COLORS.len());
+    series
+}
+
+/// Line plot of `series` against `xs`, or `None` when there is nothing to draw
+fn plot_svg(xs: &[f64], series: &[PlotSeries], log_x: bool) -> Option<String> {
+    let x_of = |x: f64| if log_x { x.log10() } else { x };
+    let usable = |x: f64| x.is_finite() && (!log_x || x > 0.0);
+
+    let (mut x_min, mut x_max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &x in xs.iter().filter(|&&x| usable(x)) {
+        x_min = x_min.min(x_of(x));
+        x_max = x_max.max(x_of(x));
+    }
+    let (mut y_min, mut y_max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &y in series.iter().flat_map(|series| &series.values).filter(|y| y.is_finite()) {
+        y_min = y_min.min(y);
+        y_max = y_max.max(y);
+    }
+    if x_max <= x_min || !y_min.is_finite() {
+        return None;
+    }
+    if y_max == y_min {
+        y_min -= 1.0;
+        y_max += 1.0;
+    }
+
+    let (left, right, top, bottom) = (50.0, PLOT_WIDTH - 10.0, 10.0, PLOT_HEIGHT - 30.0);
+    let px = |x: f64| left + (x_of(x) - x_min) / (x_max - x_min) * (right - left);
+    let py = |y: f64| bottom - (y - y_min) / (y_max - y_min) * (bottom - top);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+         <rect x=\"0\" y=\"0\" width=\"{w}\" height=\"{h}\" fill=\"white\"/>\n\
+         <polyline points=\"{left},{top} {left},{bottom} {right},{bottom}\" fill=\"none\" stroke=\"black\" stroke-width=\"1\"/>\n",
+        w = PLOT_WIDTH,
+        h = PLOT_HEIGHT,
+    );
+    if y_min < 0.0 && y_max > 0.0 {
+        svg.push_str(&format!(
+            "<line x1=\"{left}\" y1=\"{y:.2}\" x2=\"{right}\" y2=\"{y:.2}\" stroke=\"#bbbbbb\" stroke-width=\"0.5\"/>\n",
+            y = py(0.0)
+        ));
+    }
+    for (series, color) in series.iter().zip(PLOT_COLORS) {
+        let points: Vec<String> = xs
+            .iter()
+            .zip(&series.values)
+            .filter(|(x, y)| usable(**x) && y.is_finite())
+            .map(|(&x, &y)| format!("{:.2},{:.2}", px(x), py(y)))
+            .collect();
+        if points.len() > 1 {
+            svg.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>\n",
+                points.join(" "),
+                color
+            ));
+        }
+    }
+    svg.push_str("</svg>\n");
+    Some(svg)
+}
+
+/// Write a design review PDF for `project` to `path`
+pub fn write_project_report(project: &ProjectFile, path: &Path) -> Result<()> {
+    let metadata = &project.metadata;
+    let mut report = ReportWriter::new(&metadata.name)?;
+
+    report.title(&metadata.name);
+    report.line(&format!("Author: {}", metadata.author.as_deref().unwrap_or("Unknown")));
+    report.line(&format!("Date: {}", metadata.updated_at.format("%Y-%m-%d")));
+    report.line(&format!("Version: {}", metadata.version));
+    if let Some(description) = &metadata.description {
+        report.paragraph(description);
+    }
+    report.line(&format!("Generated by OpenCircuit v{}", crate::VERSION));
+
+    report.heading("Schematic Summary");
+    match &project.circuit {
+        Some(circuit) => {
+            let nets: std::collections::BTreeSet<&str> =
+                circuit.connections.iter().map(|connection| connection.net_name.as_str()).collect();
+            report.line(&format!(
+                "{} components, {} connections, {} nets",
+                circuit.components.len(),
+                circuit.connections.len(),
+                nets.len()
+            ));
+            for (component_type, count) in component_counts(circuit) {
+                report.columns(&[component_type.as_str(), count.to_string().as_str()], &[60.0, 20.0]);
+            }
+        }
+        None => report.line("No schematic captured."),
+    }
+
+    report.heading("Bill of Materials");
+    match &project.circuit {
+        Some(circuit) if !circuit.components.is_empty() => {
+            const BOM_COLUMNS: [f32; 4] = [15.0, 35.0, 35.0, 85.0];
+            report.bold_columns(&["Qty", "Value", "Type", "References"], &BOM_COLUMNS);
+            for line in bom_lines(circuit) {
+                report.columns(
+                    &[
+                        line.references.len().to_string().as_str(),
+                        line.value.as_str(),
+                        line.component_type.as_str(),
+                        line.references.join(", ").as_str(),
+                    ],
+                    &BOM_COLUMNS,
+                );
+            }
+        }
+        _ => report.line("No components."),
+    }
+    if !project.component_overrides.is_empty() {
+        report.line("");
+        report.line("Selected parts:");
+        for record in &project.component_overrides {
+            report.paragraph(&format!(
+                "{} {} - {}",
+                record.manufacturer,
+                record.part_number,
+                record.description.as_deref().unwrap_or(&record.category)
+            ));
+        }
+    }
+
+    report.heading("Design Rule Check");
+    match &project.pcb {
+        Some(pcb) => {
+            let violations = pcb.run_drc().context("Failed to run DRC")?;
+            if violations.is_empty() {
+                report.line(&format!("No violations ({} rules checked).", pcb.drc_rules.len()));
+            }
+            for violation in violations {
+                report.paragraph(&format!(
+                    "[{:?}] {} at ({:.2}, {:.2}) mm: {}",
+                    violation.severity,
+                    violation.rule_name,
+                    violation.location.0,
+                    violation.location.1,
+                    violation.description
+                ));
+            }
+        }
+        None => report.line("No PCB layout."),
+    }
+
+    report.heading("Simulation Results");
+    if project.simulation_results.is_empty() {
+        report.line("No simulations have been run.");
+    }
+    for results in &project.simulation_results {
+        report.subheading(&format!("{:?} analysis", results.analysis_type));
+        if let AnalysisData::DC(dc) = &results.data {
+            let mut voltages: Vec<(&String, &f64)> = dc.node_voltages.iter().collect();
+            voltages.sort_by(|a, b| a.0.cmp(b.0));
+            for (node, voltage) in voltages {
+                report.columns(&[format!("V({})", node).as_str(), format_si_value(*voltage, "V").as_str()], &[40.0, 40.0]);
+            }
+        }
+        if let Some((svg, x_label)) = simulation_plot_svg(results) {
+            report.plot(&svg);
+            report.line(&x_label);
+        }
+        for warning in &results.warnings {
+            report.paragraph(&format!("Warning: {}", warning));
+        }
+    }
+
+    report.heading("Circuit Health");
+    match &project.health_report {
+        Some(health) => {
+            report.line(&format!("Overall score: {}/100", health.overall_score));
+            report.line(&format!(
+                "Power integrity {}, signal integrity {}, component stress {}",
+                health.power_integrity_score, health.signal_integrity_score, health.component_stress_score
+            ));
+            for issue in &health.critical_issues {
+                report.paragraph(&format!("Critical: {}", issue));
+            }
+            for recommendation in &health.recommendations {
+                report.paragraph(&format!("- {}", recommendation));
+            }
+        }
+        None => report.line("No health report has been generated."),
+    }
+
+    report.save(path)
+}
+
+/// Flowing text layout over as many pages as needed
+struct ReportWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    /// Baseline of the next line, from the bottom of the page (mm)
+    y: f32,
+}
+
+impl ReportWriter {
+    fn new(title: &str) -> Result<Self> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let regular = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|e| anyhow!("Failed to load report font: {}", e))?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(|e| anyhow!("Failed to load report font: {}", e))?;
+        let layer = doc.get_page(page).get_layer(layer);
+        Ok(Self { doc, layer, regular, bold, y: PAGE_HEIGHT - MARGIN })
+    }
+
+    fn new_page(&mut self) {
+        let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Start a new page unless `height` mm still fit on this one
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.new_page();
+        }
+    }
+
+    fn title(&mut self, text: &str) {
+        self.layer.use_text(text, 24.0, Mm(MARGIN), Mm(self.y - 8.0), &self.bold);
+        self.y -= 16.0;
+    }
+
+    /// Section heading; each major section starts on a new page
+    fn heading(&mut self, text: &str) {
+        self.new_page();
+        self.layer.use_text(text, 16.0, Mm(MARGIN), Mm(self.y - 6.0), &self.bold);
+        self.y -= 12.0;
+    }
+
+    fn subheading(&mut self, text: &str) {
+        self.ensure_space(3.0 * LINE_HEIGHT);
+        self.y -= 2.0;
+        self.layer.use_text(text, 12.0, Mm(MARGIN), Mm(self.y - LINE_HEIGHT), &self.bold);
+        self.y -= LINE_HEIGHT + 2.0;
+    }
+
+    fn line(&mut self, text: &str) {
+        self.ensure_space(LINE_HEIGHT);
+        self.layer.use_text(text, 10.0, Mm(MARGIN), Mm(self.y - LINE_HEIGHT), &self.regular);
+        self.y -= LINE_HEIGHT;
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        for line in wrap(text, WRAP_WIDTH) {
+            self.line(&line);
+        }
+    }
+
+    fn columns(&mut self, cells: &[&str], widths: &[f32]) {
+        self.row(cells, widths, false);
+    }
+
+    fn bold_columns(&mut self, cells: &[&str], widths: &[f32]) {
+        self.row(cells, widths, true);
+    }
+
+    /// Table row; text in each cell is cut to its column width
+    fn row(&mut self, cells: &[&str], widths: &[f32], bold: bool) {
+        self.ensure_space(LINE_HEIGHT);
+        let font = if bold { &self.bold } else { &self.regular };
+        let mut x = MARGIN;
+        for (cell, width) in cells.iter().zip(widths) {
+            // Helvetica at 10 pt averages about 2 mm per character
+            let max_chars = ((width / 2.0) as usize).max(1);
+            let text: String = if cell.chars().count() > max_chars {
+                cell.chars().take(max_chars.saturating_sub(3)).chain("...".chars()).collect()
+            } else {
+                cell.to_string()
+            };
+            self.layer.use_text(text, 10.0, Mm(x), Mm(self.y - LINE_HEIGHT), font);
+            x += width;
+        }
+        self.y -= LINE_HEIGHT;
+    }
+
+    fn plot(&mut self, svg: &str) {
+        let height = (PLOT_HEIGHT as f32 / PLOT_DPI) * 25.4;
+        let svg = match Svg::parse(svg) {
+            Ok(svg) => svg,
+            Err(e) => {
+                warn!("Skipping simulation plot: {:?}", e);
+                return;
+            }
+        };
+        self.ensure_space(height + LINE_HEIGHT);
+        self.y -= height;
+        svg.into_xobject(&self.layer).add_to_layer(
+            &self.layer,
+            SvgTransform {
+                translate_x: Some(Mm(MARGIN).into()),
+                translate_y: Some(Mm(self.y).into()),
+                dpi: Some(PLOT_DPI),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn save(self, path: &Path) -> Result<()> {
+        let file = File::create(path).with_context(|| format!("Failed to create report {}", path.display()))?;
+        self.doc
+            .save(&mut BufWriter::new(file))
+            .map_err(|e| anyhow!("Failed to write report {}: {}", path.display(), e))
+    }
+}
+
+/// Split `text` into lines of at most `width` characters at word boundaries
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencircuit_circuit::{Component, ComponentType};
+    use opencircuit_core::Project;
+    use opencircuit_simulation::{AnalysisType, TransientResults};
+    use std::collections::HashMap;
+
+    fn divider() -> Circuit {
+        let mut circuit = Circuit::new();
+        for (id, component_type, value) in [
+            ("V1", ComponentType::VoltageSource, "12V"),
+            ("R2", ComponentType::Resistor, "10k"),
+            ("R1", ComponentType::Resistor, "10k"),
+            ("R3", ComponentType::Resistor, "4.7k"),
+        ] {
+            circuit.add_component(Component {
+                id: id.to_string(),
+                component_type,
+                value: Some(value.to_string()),
+                position: (0.0, 0.0),
+                purpose: None,
+            });
+        }
+        circuit
+    }
+
+    fn transient() -> SimulationResults {
+        SimulationResults {
+            analysis_type: AnalysisType::Transient,
+            data: AnalysisData::Transient(TransientResults {
+                time_points: vec![0.0, 1e-3, 2e-3],
+                voltage_waveforms: HashMap::from([("out".to_string(), vec![0.0, 2.5, 5.0])]),
+                current_waveforms: HashMap::new(),
+                power_waveforms: HashMap::new(),
+            }),
+            metadata: HashMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_bom_groups_by_type_and_value() {
+        let circuit = divider();
+        assert_eq!(component_counts(&circuit)["Resistor"], 3);
+        assert_eq!(
+            bom_lines(&circuit)[0],
+            BomLine {
+                references: vec!["R1".to_string(), "R2".to_string()],
+                value: "10k".to_string(),
+                component_type: "Resistor".to_string(),
+            }
+        );
+        assert_eq!(bom_lines(&circuit).len(), 3);
+    }
+
+    #[test]
+    fn test_transient_plot() {
+        let (svg, x_label) = simulation_plot_svg(&transient()).unwrap();
+        assert_eq!(x_label, "Time (s)");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("points=\"50.00,270.00 320.00,140.00 590.00,10.00\""));
+
+        let mut flat = transient();
+        flat.data = AnalysisData::Raw(vec!["no data".to_string()]);
+        assert!(simulation_plot_svg(&flat).is_none());
+    }
+
+    #[test]
+    fn test_write_project_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.pdf");
+
+        let mut project = ProjectFile::new(Project::new("Voltage Divider".to_string()));
+        project.circuit = Some(divider());
+        project.simulation_results.push(transient());
+        write_project_report(&project, &path).unwrap();
+
+        let pdf = std::fs::read(&path).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+        assert_eq!(wrap("one two three", 7), vec!["one two", "three"]);
+    }
+}